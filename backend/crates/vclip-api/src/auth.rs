@@ -1,17 +1,29 @@
-//! Firebase ID token authentication.
+//! Request authentication.
+//!
+//! Requests authenticate with either a Firebase ID token or, for plans with
+//! API access, a personal API key (`Authorization: Bearer vck_...` or
+//! `X-API-Key: vck_...`). API keys are limited to the routes their scopes
+//! cover and have their own rate limit.
 
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 use axum::extract::FromRequestParts;
 use axum::http::request::Parts;
+use axum::http::Method;
+use chrono::Utc;
 use jsonwebtoken::{decode, decode_header, DecodingKey, Validation, Algorithm};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tracing::{debug, warn};
 
+use vclip_firestore::ApiKeyRepository;
+use vclip_models::{hash_api_key, is_api_key, ApiKeyScope};
+
 use crate::error::ApiError;
+use crate::metrics;
+use crate::middleware::{check_api_key_rate_limit, client_ip};
 use crate::state::AppState;
 
 /// Google JWKS URL for Firebase Auth.
@@ -24,6 +36,12 @@ const FIREBASE_ISSUER_PREFIX: &str = "https://securetoken.google.com/";
 /// JWKS cache TTL.
 const JWKS_CACHE_TTL: Duration = Duration::from_secs(3600); // 1 hour
 
+/// Header carrying an API key as an alternative to `Authorization`.
+pub const API_KEY_HEADER: &str = "X-API-Key";
+
+/// How stale an API key's last-used time may get before it is rewritten.
+const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
 /// Decoded Firebase ID token claims.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FirebaseClaims {
@@ -58,6 +76,8 @@ pub struct AuthUser {
     pub uid: String,
     pub email: Option<String>,
     pub email_verified: bool,
+    /// ID of the API key used, if the request was not made with an ID token
+    pub api_key_id: Option<String>,
 }
 
 impl From<FirebaseClaims> for AuthUser {
@@ -66,6 +86,7 @@ impl From<FirebaseClaims> for AuthUser {
             uid: claims.sub,
             email: claims.email,
             email_verified: claims.email_verified.unwrap_or(false),
+            api_key_id: None,
         }
     }
 }
@@ -190,6 +211,11 @@ impl FromRequestParts<AppState> for AuthUser {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
+        // API keys may also be sent in their own header
+        if let Some(key) = parts.headers.get(API_KEY_HEADER).and_then(|v| v.to_str().ok()) {
+            return authenticate_api_key(parts, state, key.trim()).await;
+        }
+
         // Get Authorization header
         let auth_header = parts
             .headers
@@ -202,9 +228,158 @@ impl FromRequestParts<AppState> for AuthUser {
             .strip_prefix("Bearer ")
             .ok_or_else(|| ApiError::unauthorized("Invalid Authorization header format"))?;
 
+        if is_api_key(token) {
+            return authenticate_api_key(parts, state, token).await;
+        }
+
         // Verify token
         let claims = state.jwks.verify_token(token).await?;
 
         Ok(AuthUser::from(claims))
     }
 }
+
+/// Authenticate a request made with an API key.
+async fn authenticate_api_key(
+    parts: &Parts,
+    state: &AppState,
+    key: &str,
+) -> Result<AuthUser, ApiError> {
+    if !is_api_key(key) {
+        return Err(ApiError::unauthorized("Invalid API key"));
+    }
+
+    let path = parts.uri.path();
+    let scope = required_scope(&parts.method, path)
+        .ok_or_else(|| ApiError::forbidden("This endpoint is not available to API keys"))?;
+
+    // Guessed keys are limited per client IP; only real keys get a bucket
+    let client_ip = client_ip(&parts.headers, &parts.extensions);
+    if client_ip.is_some_and(|ip| state.api_key_lookups.is_blocked(ip)) {
+        warn!(path = %path, "Too many failed API key lookups");
+        metrics::record_rate_limit_hit(path);
+        return Err(ApiError::RateLimited);
+    }
+
+    let key_hash = hash_api_key(key);
+    let repo = ApiKeyRepository::new((*state.firestore).clone());
+    let Some(index) = repo.get_by_hash(&key_hash).await? else {
+        if let Some(ip) = client_ip {
+            state.api_key_lookups.record_failure(ip);
+        }
+        return Err(ApiError::unauthorized("Invalid API key"));
+    };
+
+    if !check_api_key_rate_limit(&state.api_key_limiter, &key_hash) {
+        warn!(path = %path, "API key rate limit exceeded");
+        metrics::record_rate_limit_hit(path);
+        return Err(ApiError::RateLimited);
+    }
+
+    if !index.scopes.contains(&scope) {
+        return Err(ApiError::forbidden(format!(
+            "API key is missing the '{}' scope",
+            scope
+        )));
+    }
+
+    // Keys stop working when the plan loses API access
+    let limits = state.user_service.get_plan_limits(&index.user_id).await?;
+    if !limits.api_access {
+        return Err(ApiError::forbidden("API access is not included in your plan"));
+    }
+
+    let now = Utc::now();
+    let stale = index
+        .last_used_at
+        .is_none_or(|t| (now - t).num_seconds() >= LAST_USED_RESOLUTION_SECS);
+    if stale {
+        let index = index.clone();
+        tokio::spawn(async move {
+            if let Err(e) = repo.touch(&index, now).await {
+                debug!("Failed to record use of API key {}: {}", index.key_id, e);
+            }
+        });
+    }
+
+    Ok(AuthUser {
+        uid: index.user_id,
+        email: None,
+        email_verified: false,
+        api_key_id: Some(index.key_id),
+    })
+}
+
+/// Scope an API key needs to call a route, or `None` if API keys may not
+/// call it at all.
+///
+/// Admin and key management routes are session-only. Reads need `read`,
+/// deletes need `delete`; other writes are allowed only where listed.
+pub fn required_scope(method: &Method, path: &str) -> Option<ApiKeyScope> {
+    let path = path
+        .strip_prefix("/api")
+        .filter(|rest| rest.starts_with('/'))
+        .unwrap_or(path);
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    if matches!(segments.first(), Some(&"admin") | Some(&"api-keys")) {
+        return None;
+    }
+
+    if method == Method::GET || method == Method::HEAD {
+        return Some(ApiKeyScope::Read);
+    }
+    if method == Method::DELETE {
+        return Some(ApiKeyScope::Delete);
+    }
+
+    match segments.as_slice() {
+        ["analyze"]
+        | ["analyze", "live"]
+        | ["drafts", _, "live", "stop"]
+        | ["videos", _, "highlights", ..] => Some(ApiKeyScope::Analyze),
+        ["videos", "process"]
+        | ["drafts", _, "process"]
+        | ["videos", _, "reprocess"]
+        | ["jobs", _, "cancel"] => Some(ApiKeyScope::Render),
+        ["clips", _, "play-url" | "download-url" | "thumbnail-url"] | ["storage", "check"] => {
+            Some(ApiKeyScope::Read)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_scope() {
+        assert_eq!(required_scope(&Method::GET, "/videos/v1"), Some(ApiKeyScope::Read));
+        assert_eq!(required_scope(&Method::GET, "/api/user/videos"), Some(ApiKeyScope::Read));
+        assert_eq!(required_scope(&Method::POST, "/analyze"), Some(ApiKeyScope::Analyze));
+        assert_eq!(required_scope(&Method::POST, "/analyze/live"), Some(ApiKeyScope::Analyze));
+        assert_eq!(
+            required_scope(&Method::POST, "/drafts/d1/live/stop"),
+            Some(ApiKeyScope::Analyze)
+        );
+        assert_eq!(
+            required_scope(&Method::PATCH, "/videos/v1/highlights/3"),
+            Some(ApiKeyScope::Analyze)
+        );
+        assert_eq!(required_scope(&Method::POST, "/videos/process"), Some(ApiKeyScope::Render));
+        assert_eq!(required_scope(&Method::POST, "/drafts/d1/process"), Some(ApiKeyScope::Render));
+        assert_eq!(required_scope(&Method::POST, "/clips/c1/download-url"), Some(ApiKeyScope::Read));
+        assert_eq!(required_scope(&Method::DELETE, "/videos/v1"), Some(ApiKeyScope::Delete));
+    }
+
+    #[test]
+    fn test_required_scope_session_only_routes() {
+        assert_eq!(required_scope(&Method::GET, "/admin/users"), None);
+        assert_eq!(required_scope(&Method::GET, "/api-keys"), None);
+        assert_eq!(required_scope(&Method::DELETE, "/api-keys/k1"), None);
+        assert_eq!(required_scope(&Method::POST, "/api/api-keys"), None);
+        assert_eq!(required_scope(&Method::POST, "/settings"), None);
+        assert_eq!(required_scope(&Method::POST, "/clips/c1/share"), None);
+    }
+}
@@ -15,12 +15,18 @@ pub struct ApiConfig {
     pub rate_limit_rps: u32,
     /// Rate limit burst
     pub rate_limit_burst: u32,
+    /// Requests per minute allowed per API key
+    pub api_key_rate_limit_rpm: u32,
+    /// Burst allowed per API key
+    pub api_key_rate_limit_burst: u32,
     /// Request timeout
     pub request_timeout: Duration,
     /// Max request body size
     pub max_body_size: usize,
     /// Environment (development/production)
     pub environment: String,
+    /// Key for hashing share viewers (IP + user agent) into anonymous IDs
+    pub share_viewer_salt: String,
 }
 
 impl Default for ApiConfig {
@@ -33,9 +39,12 @@ impl Default for ApiConfig {
             cors_origins: Vec::new(),
             rate_limit_rps: 10,
             rate_limit_burst: 20,
+            api_key_rate_limit_rpm: 120,
+            api_key_rate_limit_burst: 30,
             request_timeout: Duration::from_secs(30),
             max_body_size: 10 * 1024 * 1024, // 10MB
             environment: "development".to_string(),
+            share_viewer_salt: String::new(),
         }
     }
 }
@@ -60,6 +69,14 @@ impl ApiConfig {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(20),
+            api_key_rate_limit_rpm: std::env::var("API_KEY_RATE_LIMIT_RPM")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(120),
+            api_key_rate_limit_burst: std::env::var("API_KEY_RATE_LIMIT_BURST")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
             request_timeout: Duration::from_secs(
                 std::env::var("REQUEST_TIMEOUT")
                     .ok()
@@ -71,6 +88,7 @@ impl ApiConfig {
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(10 * 1024 * 1024),
             environment: std::env::var("ENVIRONMENT").unwrap_or_else(|_| "development".to_string()),
+            share_viewer_salt: std::env::var("SHARE_VIEWER_SALT").unwrap_or_default(),
         }
     }
 
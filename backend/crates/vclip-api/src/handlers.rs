@@ -2,24 +2,35 @@
 
 pub mod admin;
 pub mod analysis;
+pub mod api_keys;
+pub mod channels;
 pub mod clip_delivery;
 pub mod credits;
 pub mod health;
 pub mod highlights;
 pub mod jobs;
+pub mod live;
 pub mod settings;
+pub mod share_page;
 pub mod storage;
+pub mod uploads;
 pub mod video_status;
 pub mod videos;
+pub mod webhooks;
 
 pub use admin::*;
 pub use analysis::*;
+pub use api_keys::*;
+pub use channels::*;
 pub use clip_delivery::*;
 pub use credits::*;
 pub use health::*;
 pub use highlights::*;
 pub use jobs::*;
+pub use live::*;
 pub use settings::*;
 pub use storage::*;
+pub use uploads::*;
 pub use video_status::*;
 pub use videos::*;
+pub use webhooks::*;
@@ -5,15 +5,18 @@
 use axum::extract::{Path, Query, State};
 use axum::Json;
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use tracing::{info, warn};
 
  use vclip_firestore::{FromFirestoreValue, ToFirestoreValue, Value, FirestoreError};
 use vclip_models::{AspectRatio, CropMode, Style};
-use vclip_queue::ProcessVideoJob;
+use vclip_firestore::AdminAuditRepository;
+use vclip_models::AdminAuditEntry;
+use vclip_queue::{DlqEntry, DlqFilter, ProcessVideoJob, QueueError, QueueLane};
 
 use crate::auth::AuthUser;
 use crate::error::{ApiError, ApiResult};
  use crate::security::sanitize_string;
+use crate::services::storage_reconcile::{ReconcileReport, StorageReconciler};
 use crate::state::AppState;
 
 /// Synthetic job request for canary testing.
@@ -106,6 +109,16 @@ pub async fn enqueue_synthetic_job(
 pub struct QueueStatusResponse {
     pub queue_length: u64,
     pub dlq_length: u64,
+    /// Jobs waiting in each priority lane
+    pub lanes: Vec<LaneStatus>,
+}
+
+/// Depth of a priority lane.
+#[derive(Serialize)]
+pub struct LaneStatus {
+    pub lane: QueueLane,
+    pub depth: u64,
+    pub weight: u32,
 }
 
 /// Get queue status (admin only).
@@ -120,10 +133,23 @@ pub async fn get_queue_status(
 
     let queue_length = state.queue.len().await.unwrap_or(0);
     let dlq_length = state.queue.dlq_len().await.unwrap_or(0);
+    let lanes = state
+        .queue
+        .lane_depths()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(lane, depth)| LaneStatus {
+            lane,
+            depth,
+            weight: lane.weight(),
+        })
+        .collect();
 
     Ok(Json(QueueStatusResponse {
         queue_length,
         dlq_length,
+        lanes,
     }))
 }
 
@@ -455,6 +481,53 @@ pub async fn recalculate_user_storage(
     }))
 }
 
+/// Reconcile storage query.
+#[derive(Debug, Deserialize)]
+pub struct ReconcileStorageQuery {
+    /// Delete orphaned objects and dangling clips and fix the accounting
+    #[serde(default)]
+    pub repair: bool,
+}
+
+/// Reconcile a user's R2 objects with their Firestore records (admin only).
+///
+/// Reports orphaned objects, dangling clip records and per-bucket accounting
+/// drift; `?repair=true` also fixes them.
+pub async fn reconcile_user_storage(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(target_uid): Path<String>,
+    Query(query): Query<ReconcileStorageQuery>,
+) -> ApiResult<Json<ReconcileReport>> {
+    if !state.user_service.is_super_admin(&user.uid).await? {
+        return Err(ApiError::forbidden("Admin access required"));
+    }
+
+    let reconciler = StorageReconciler::new(state.storage.clone(), state.firestore.clone());
+    let report = reconciler
+        .reconcile_user(&state.user_service, &target_uid, query.repair)
+        .await?;
+
+    if report.repaired {
+        record_admin_action(
+            &state,
+            AdminAuditEntry::new(&user.uid, "storage.reconcile")
+                .with_detail("uid", &target_uid)
+                .with_detail("orphaned_objects", report.orphaned_objects.len())
+                .with_detail("orphaned_bytes", report.orphaned_bytes)
+                .with_detail("dangling_clips", report.dangling_clips.len()),
+        )
+        .await;
+    }
+
+    info!(
+        "Admin {} reconciled storage for user {} (repair: {})",
+        user.uid, target_uid, query.repair
+    );
+
+    Ok(Json(report))
+}
+
 /// Reset video status request.
 #[derive(Debug, Deserialize)]
 pub struct ResetVideoRequest {
@@ -532,3 +605,307 @@ pub async fn reset_video_status(
         ),
     }))
 }
+
+// ============================================================================
+// Dead Letter Queue
+// ============================================================================
+
+/// Default page size when listing DLQ entries.
+const DLQ_PAGE_SIZE: usize = 50;
+
+/// Maximum entries replayed by one batch request.
+const MAX_DLQ_BATCH: usize = 500;
+
+/// List DLQ query params.
+#[derive(Debug, Deserialize)]
+pub struct ListDlqQuery {
+    /// Last message ID of the previous page
+    pub before: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// List DLQ response.
+#[derive(Serialize)]
+pub struct ListDlqResponse {
+    pub entries: Vec<DlqEntry>,
+    /// Total entries in the DLQ
+    pub total: u64,
+    /// Cursor for the next page
+    pub next_before: Option<String>,
+}
+
+/// List dead-lettered jobs, newest first (admin only).
+pub async fn list_dlq(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(query): Query<ListDlqQuery>,
+) -> ApiResult<Json<ListDlqResponse>> {
+    if !state.user_service.is_super_admin(&user.uid).await? {
+        return Err(ApiError::forbidden("Admin access required"));
+    }
+    if let Some(ref before) = query.before {
+        validate_stream_id(before)?;
+    }
+
+    let limit = query.limit.unwrap_or(DLQ_PAGE_SIZE).clamp(1, 200);
+    let entries = state.queue.list_dlq(query.before.as_deref(), limit).await?;
+    let total = state.queue.dlq_len().await?;
+    let next_before = (entries.len() == limit)
+        .then(|| entries.last().map(|e| e.message_id.clone()))
+        .flatten();
+
+    Ok(Json(ListDlqResponse {
+        entries,
+        total,
+        next_before,
+    }))
+}
+
+/// A replayed DLQ entry.
+#[derive(Serialize)]
+pub struct ReplayedEntry {
+    pub message_id: String,
+    pub job_id: String,
+}
+
+/// A DLQ entry that could not be replayed.
+#[derive(Serialize)]
+pub struct SkippedEntry {
+    pub message_id: String,
+    pub reason: String,
+}
+
+/// Replay response.
+#[derive(Serialize)]
+pub struct ReplayDlqResponse {
+    pub replayed: Vec<ReplayedEntry>,
+    pub skipped: Vec<SkippedEntry>,
+}
+
+/// Replay a single DLQ entry onto the queue (admin only).
+pub async fn replay_dlq_entry(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(message_id): Path<String>,
+) -> ApiResult<Json<ReplayDlqResponse>> {
+    if !state.user_service.is_super_admin(&user.uid).await? {
+        return Err(ApiError::forbidden("Admin access required"));
+    }
+    validate_stream_id(&message_id)?;
+
+    let entry = state
+        .queue
+        .get_dlq(&message_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("DLQ entry not found"))?;
+
+    let response = replay_entries(&state, vec![entry]).await;
+
+    record_admin_action(
+        &state,
+        AdminAuditEntry::new(&user.uid, "dlq.replay")
+            .with_detail("message_id", &message_id)
+            .with_detail("replayed", response.replayed.len())
+            .with_detail("skipped", response.skipped.len()),
+    )
+    .await;
+
+    Ok(Json(response))
+}
+
+/// Batch replay request; unset filter fields match everything.
+#[derive(Debug, Deserialize)]
+pub struct ReplayDlqRequest {
+    #[serde(flatten)]
+    pub filter: DlqFilter,
+    /// Maximum entries to replay (oldest first)
+    pub limit: Option<usize>,
+}
+
+/// Replay DLQ entries matching a filter (admin only).
+pub async fn replay_dlq_batch(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(request): Json<ReplayDlqRequest>,
+) -> ApiResult<Json<ReplayDlqResponse>> {
+    if !state.user_service.is_super_admin(&user.uid).await? {
+        return Err(ApiError::forbidden("Admin access required"));
+    }
+
+    let limit = request.limit.unwrap_or(MAX_DLQ_BATCH).clamp(1, MAX_DLQ_BATCH);
+    let entries = state.queue.find_dlq(&request.filter, limit).await?;
+    let response = replay_entries(&state, entries).await;
+
+    info!(
+        "Admin {} replayed {} DLQ entries ({} skipped)",
+        user.uid,
+        response.replayed.len(),
+        response.skipped.len()
+    );
+
+    let filter = &request.filter;
+    record_admin_action(
+        &state,
+        AdminAuditEntry::new(&user.uid, "dlq.replay_batch")
+            .with_detail("job_type", filter.job_type.as_deref().unwrap_or("*"))
+            .with_detail("user_id", filter.user_id.as_deref().unwrap_or("*"))
+            .with_detail("error_contains", filter.error_contains.as_deref().unwrap_or(""))
+            .with_detail("limit", limit)
+            .with_detail("replayed", response.replayed.len())
+            .with_detail("skipped", response.skipped.len()),
+    )
+    .await;
+
+    Ok(Json(response))
+}
+
+/// Delete DLQ entry response.
+#[derive(Serialize)]
+pub struct DeleteDlqEntryResponse {
+    pub success: bool,
+    pub message_id: String,
+}
+
+/// Delete a single DLQ entry (admin only).
+pub async fn delete_dlq_entry(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(message_id): Path<String>,
+) -> ApiResult<Json<DeleteDlqEntryResponse>> {
+    if !state.user_service.is_super_admin(&user.uid).await? {
+        return Err(ApiError::forbidden("Admin access required"));
+    }
+    validate_stream_id(&message_id)?;
+
+    if !state.queue.delete_dlq(&message_id).await? {
+        return Err(ApiError::not_found("DLQ entry not found"));
+    }
+
+    record_admin_action(
+        &state,
+        AdminAuditEntry::new(&user.uid, "dlq.delete").with_detail("message_id", &message_id),
+    )
+    .await;
+
+    Ok(Json(DeleteDlqEntryResponse {
+        success: true,
+        message_id,
+    }))
+}
+
+/// Purge DLQ request.
+#[derive(Debug, Deserialize)]
+pub struct PurgeDlqRequest {
+    /// Delete entries dead-lettered more than this many hours ago
+    pub older_than_hours: u32,
+}
+
+/// Purge DLQ response.
+#[derive(Serialize)]
+pub struct PurgeDlqResponse {
+    pub purged: u64,
+    pub remaining: u64,
+}
+
+/// Delete old DLQ entries (admin only).
+pub async fn purge_dlq(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(request): Json<PurgeDlqRequest>,
+) -> ApiResult<Json<PurgeDlqResponse>> {
+    if !state.user_service.is_super_admin(&user.uid).await? {
+        return Err(ApiError::forbidden("Admin access required"));
+    }
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::hours(i64::from(request.older_than_hours));
+    let purged = state.queue.purge_dlq(cutoff).await?;
+    let remaining = state.queue.dlq_len().await?;
+
+    info!(
+        "Admin {} purged {} DLQ entries older than {}h",
+        user.uid, purged, request.older_than_hours
+    );
+
+    record_admin_action(
+        &state,
+        AdminAuditEntry::new(&user.uid, "dlq.purge")
+            .with_detail("older_than_hours", request.older_than_hours)
+            .with_detail("purged", purged),
+    )
+    .await;
+
+    Ok(Json(PurgeDlqResponse { purged, remaining }))
+}
+
+/// Replay entries one by one, collecting per-entry outcomes.
+async fn replay_entries(state: &AppState, entries: Vec<DlqEntry>) -> ReplayDlqResponse {
+    let mut response = ReplayDlqResponse {
+        replayed: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    for entry in entries {
+        match state.queue.replay_dlq(&entry).await {
+            Ok(job_id) => response.replayed.push(ReplayedEntry {
+                message_id: entry.message_id,
+                job_id,
+            }),
+            Err(e) => {
+                let reason = match e {
+                    QueueError::EnqueueFailed(_) => "An identical job is already queued".to_string(),
+                    other => other.to_string(),
+                };
+                response.skipped.push(SkippedEntry {
+                    message_id: entry.message_id,
+                    reason,
+                });
+            }
+        }
+    }
+
+    response
+}
+
+/// Record an admin action in the audit log.
+///
+/// The action has already happened, so failures are logged, not returned.
+async fn record_admin_action(state: &AppState, entry: AdminAuditEntry) {
+    let repo = AdminAuditRepository::new((*state.firestore).clone());
+    if let Err(e) = repo.record(&entry).await {
+        warn!("Failed to record admin action {} by {}: {}", entry.action, entry.admin_uid, e);
+    }
+}
+
+/// Validate a Redis stream message ID (`<ms>-<seq>`).
+fn is_valid_stream_id(id: &str) -> bool {
+    id.split_once('-').is_some_and(|(ms, seq)| {
+        [ms, seq]
+            .iter()
+            .all(|part| !part.is_empty() && part.len() <= 20 && part.bytes().all(|b| b.is_ascii_digit()))
+    })
+}
+
+fn validate_stream_id(id: &str) -> ApiResult<()> {
+    if is_valid_stream_id(id) {
+        Ok(())
+    } else {
+        Err(ApiError::bad_request("Invalid message ID"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_ids() {
+        assert!(is_valid_stream_id("1700000000000-0"));
+        assert!(is_valid_stream_id("1-12"));
+        assert!(!is_valid_stream_id(""));
+        assert!(!is_valid_stream_id("1700000000000"));
+        assert!(!is_valid_stream_id("1700000000000-"));
+        assert!(!is_valid_stream_id("+"));
+        assert!(!is_valid_stream_id("(1700000000000-0"));
+        assert!(!is_valid_stream_id("1700000000000-0-1"));
+    }
+}
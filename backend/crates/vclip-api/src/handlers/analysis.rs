@@ -10,13 +10,13 @@ use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 use uuid::Uuid;
 
-use vclip_firestore::AnalysisDraftRepository;
+use vclip_firestore::{AnalysisDraftRepository, VideoRepository};
 use vclip_models::{
     AnalysisDraft, AnalysisStatus, AnalysisStatusResponse, CreditContext, CreditOperationType,
-    DetectionTier, DraftScene, ProcessDraftRequest, ProcessingEstimate, StartAnalysisResponse,
-    Style,
+    DetectionTier, DraftScene, HighlightDetection, JobId, ProcessDraftRequest, ProcessingEstimate,
+    SceneRankQuery, SceneSort, SourceVideoStatus, StartAnalysisResponse, Style, VideoMetadata,
 };
-use vclip_queue::{AnalyzeVideoJob, RenderSceneStyleJob};
+use vclip_queue::{AnalyzeVideoJob, QueueLane, RenderSceneStyleJob};
 
 use crate::auth::AuthUser;
 use crate::error::{ApiError, ApiResult};
@@ -39,6 +39,9 @@ pub struct StartAnalysisRequest {
     /// Optional AI instructions
     #[serde(default)]
     pub prompt: Option<String>,
+    /// How highlights are detected (transcript, signals or fused)
+    #[serde(default)]
+    pub highlight_detection: HighlightDetection,
 }
 
 /// Start an async video analysis job.
@@ -83,7 +86,8 @@ pub async fn start_analysis(
     })?;
 
     // Create and enqueue the analysis job with validated URL
-    let mut job = AnalyzeVideoJob::new(&user.uid, &draft_id, &validated_url);
+    let mut job = AnalyzeVideoJob::new(&user.uid, &draft_id, &validated_url)
+        .with_highlight_detection(request.highlight_detection);
     if let Some(p) = prompt {
         job = job.with_prompt(p);
     }
@@ -142,6 +146,7 @@ pub async fn get_analysis_status(
         error_message: draft.error_message,
         scene_count: draft.scene_count,
         warning_count: draft.warning_count,
+        live_capture: draft.live_capture,
     }))
 }
 
@@ -217,9 +222,13 @@ pub struct DraftWithScenesResponse {
 }
 
 /// Get a draft with all its scenes.
+///
+/// Scenes are sorted by ID unless `?sort=start|score` is given; `?min_score`
+/// drops scenes below the given virality score.
 pub async fn get_draft(
     State(state): State<AppState>,
     Path(draft_id): Path<String>,
+    Query(rank): Query<SceneRankQuery>,
     user: AuthUser,
 ) -> ApiResult<Json<DraftWithScenesResponse>> {
     let draft_repo = AnalysisDraftRepository::new((*state.firestore).clone(), &user.uid);
@@ -246,8 +255,11 @@ pub async fn get_draft(
         ApiError::internal("Failed to get draft scenes")
     })?;
 
-    // Sort scenes by ID
-    scenes.sort_by_key(|s| s.id);
+    SceneRankQuery {
+        sort: Some(rank.sort.unwrap_or(SceneSort::Id)),
+        ..rank
+    }
+    .apply(&mut scenes);
 
     Ok(Json(DraftWithScenesResponse {
         draft,
@@ -312,6 +324,8 @@ pub struct ProcessDraftResponse {
     pub success: bool,
     pub draft_id: String,
     pub video_id: String,
+    /// Parent job of the render jobs; cancelling it stops them all
+    pub job_id: String,
     pub jobs_enqueued: u32,
 }
 
@@ -348,8 +362,8 @@ pub async fn process_draft(
         return Err(ApiError::not_found("Draft not found"));
     }
 
-    // Check if draft is ready
-    if draft.status != AnalysisStatus::Completed {
+    // Check if draft is ready (live captures can be rendered while recording)
+    if draft.status != AnalysisStatus::Completed && draft.status != AnalysisStatus::Live {
         return Err(ApiError::Conflict(format!(
             "Draft is not ready for processing. Status: {:?}",
             draft.status.as_str()
@@ -475,7 +489,64 @@ pub async fn process_draft(
         total_credits, draft_id, scene_count
     );
 
+    // Output format shared by every render job (validated above)
+    let target_aspect = request.aspect_ratio().map_err(ApiError::bad_request)?;
+
+    // Uploaded sources have no URL to download from; point the renders at
+    // the stored file instead
+    if let Some(ref source_key) = draft.source_r2_key {
+        let mut video_meta = VideoMetadata::new(
+            video_id.clone(),
+            &user.uid,
+            &draft.source_url,
+            draft.video_title.as_deref().unwrap_or("Uploaded video"),
+        )
+        .with_output_format(target_aspect, request.resolution);
+        video_meta.source_video_r2_key = Some(source_key.clone());
+        video_meta.source_video_status = Some(SourceVideoStatus::Ready);
+
+        VideoRepository::new((*state.firestore).clone(), &user.uid)
+            .create(&video_meta)
+            .await
+            .map_err(|e| {
+                warn!("Failed to create video record: {}", e);
+                ApiError::internal("Failed to start processing")
+            })?;
+    }
+
+    // Live captures have no single source file; renders join the recorded
+    // segments listed in the manifest
+    if let Some(ref live) = draft.live_capture {
+        let mut video_meta = VideoMetadata::new(
+            video_id.clone(),
+            &user.uid,
+            &draft.source_url,
+            draft.video_title.as_deref().unwrap_or("Live stream"),
+        )
+        .with_output_format(target_aspect, request.resolution);
+        video_meta.live_manifest_r2_key = Some(live.manifest_r2_key.clone());
+
+        VideoRepository::new((*state.firestore).clone(), &user.uid)
+            .create(&video_meta)
+            .await
+            .map_err(|e| {
+                warn!("Failed to create video record: {}", e);
+                ApiError::internal("Failed to start processing")
+            })?;
+    }
+
+    // The render jobs share one parent job so they can be polled and cancelled together
+    let parent_job_id = JobId::new();
+    if let Err(e) = state
+        .progress
+        .register_job(&parent_job_id, video_id.as_str(), &user.uid)
+        .await
+    {
+        warn!("Failed to register job {}: {}", parent_job_id, e);
+    }
+
     // Create and enqueue render jobs
+    let lane = QueueLane::for_plan(limits.tier);
     let mut jobs_enqueued = 0u32;
 
     for selection in &request.selected_scenes {
@@ -494,7 +565,14 @@ pub async fn process_draft(
                 &scene.end,
             )
             .with_pad_before(Some(scene.pad_before))
-            .with_pad_after(Some(scene.pad_after));
+            .with_pad_after(Some(scene.pad_after))
+            .with_target_aspect(target_aspect)
+            .with_resolution(request.resolution)
+            .with_export_profiles(request.export_profiles.clone())
+            .with_audio(request.audio.clone())
+            .with_captions(request.captions.clone())
+            .with_parent_job(parent_job_id.clone())
+            .with_lane(lane);
 
             state
                 .queue
@@ -519,7 +597,14 @@ pub async fn process_draft(
                 &scene.end,
             )
             .with_pad_before(Some(scene.pad_before))
-            .with_pad_after(Some(scene.pad_after));
+            .with_pad_after(Some(scene.pad_after))
+            .with_target_aspect(target_aspect)
+            .with_resolution(request.resolution)
+            .with_export_profiles(request.export_profiles.clone())
+            .with_audio(request.audio.clone())
+            .with_captions(request.captions.clone())
+            .with_parent_job(parent_job_id.clone())
+            .with_lane(lane);
 
             state
                 .queue
@@ -543,6 +628,7 @@ pub async fn process_draft(
         success: true,
         draft_id,
         video_id: video_id.to_string(),
+        job_id: parent_job_id.to_string(),
         jobs_enqueued,
     }))
 }
@@ -656,7 +742,7 @@ pub async fn estimate_processing(
 // ============================================================================
 
 /// Get draft TTL based on user's plan.
-async fn get_draft_ttl(state: &AppState, user_id: &str) -> i64 {
+pub(crate) async fn get_draft_ttl(state: &AppState, user_id: &str) -> i64 {
     // Check if user has a paid plan
     match state.user_service.has_pro_or_studio_plan(user_id).await {
         Ok(true) => PAID_DRAFT_TTL_DAYS,
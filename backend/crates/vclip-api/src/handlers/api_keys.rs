@@ -0,0 +1,159 @@
+//! API key management handlers.
+//!
+//! Users on plans with API access can create scoped keys for calling the API
+//! from their own backends. These routes only accept ID tokens, so a key
+//! can never create or revoke keys.
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use vclip_firestore::ApiKeyRepository;
+use vclip_models::{ApiKey, ApiKeyScope};
+
+use crate::auth::AuthUser;
+use crate::error::{ApiError, ApiResult};
+use crate::security::sanitize_string;
+use crate::state::AppState;
+
+/// Active keys a user may hold at once.
+const MAX_ACTIVE_API_KEYS: usize = 10;
+
+/// Maximum length of a key name.
+const MAX_API_KEY_NAME_LENGTH: usize = 64;
+
+/// An API key as returned by the API (never includes the secret).
+#[derive(Serialize)]
+pub struct ApiKeyInfo {
+    pub id: String,
+    pub name: String,
+    /// First characters of the secret, to tell keys apart
+    pub display_prefix: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<String>,
+}
+
+impl From<ApiKey> for ApiKeyInfo {
+    fn from(key: ApiKey) -> Self {
+        Self {
+            id: key.id,
+            name: key.name,
+            display_prefix: key.display_prefix,
+            scopes: key.scopes,
+            created_at: key.created_at.to_rfc3339(),
+            last_used_at: key.last_used_at.map(|t| t.to_rfc3339()),
+            revoked_at: key.revoked_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+/// Response for listing API keys.
+#[derive(Serialize)]
+pub struct ListApiKeysResponse {
+    pub keys: Vec<ApiKeyInfo>,
+}
+
+/// List the user's API keys, including revoked ones.
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> ApiResult<Json<ListApiKeysResponse>> {
+    let repo = ApiKeyRepository::new((*state.firestore).clone());
+    let keys = repo.list(&user.uid).await?;
+
+    Ok(Json(ListApiKeysResponse {
+        keys: keys.into_iter().map(ApiKeyInfo::from).collect(),
+    }))
+}
+
+/// Request to create an API key.
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyRequest {
+    /// Label shown in the key list
+    pub name: String,
+    /// Scopes the key may use
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+/// Response with a newly created key.
+#[derive(Serialize)]
+pub struct CreateApiKeyResponse {
+    #[serde(flatten)]
+    pub key: ApiKeyInfo,
+    /// The key itself; shown only once
+    pub secret: String,
+}
+
+/// Create an API key.
+///
+/// The secret is returned only in this response; only its hash is stored.
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> ApiResult<Json<CreateApiKeyResponse>> {
+    let limits = state.user_service.get_plan_limits(&user.uid).await?;
+    if !limits.api_access {
+        return Err(ApiError::forbidden("API access is not included in your plan"));
+    }
+
+    let name = sanitize_string(request.name.trim());
+    if name.is_empty() || name.chars().count() > MAX_API_KEY_NAME_LENGTH {
+        return Err(ApiError::bad_request(format!(
+            "Key name must be 1-{} characters",
+            MAX_API_KEY_NAME_LENGTH
+        )));
+    }
+
+    let mut scopes = request.scopes;
+    scopes.sort_by_key(|s| ApiKeyScope::ALL.iter().position(|a| a == s));
+    scopes.dedup();
+    if scopes.is_empty() {
+        return Err(ApiError::bad_request("At least one scope is required"));
+    }
+
+    let repo = ApiKeyRepository::new((*state.firestore).clone());
+    let active = repo.list(&user.uid).await?.iter().filter(|k| k.is_active()).count();
+    if active >= MAX_ACTIVE_API_KEYS {
+        return Err(ApiError::forbidden(format!(
+            "You can have at most {} active API keys",
+            MAX_ACTIVE_API_KEYS
+        )));
+    }
+
+    let (key, secret) = ApiKey::generate(&user.uid, name, scopes);
+    repo.create(&key).await?;
+
+    info!(user_id = %user.uid, key_id = %key.id, "Created API key");
+
+    Ok(Json(CreateApiKeyResponse {
+        key: ApiKeyInfo::from(key),
+        secret,
+    }))
+}
+
+/// Revoke an API key. Requests made with it fail immediately.
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(key_id): Path<String>,
+) -> ApiResult<Json<ApiKeyInfo>> {
+    let repo = ApiKeyRepository::new((*state.firestore).clone());
+    let mut key = repo
+        .get(&user.uid, &key_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("API key not found"))?;
+
+    if key.is_active() {
+        repo.revoke(&key).await?;
+        key.revoked_at = Some(chrono::Utc::now());
+        info!(user_id = %user.uid, key_id = %key.id, "Revoked API key");
+    }
+
+    Ok(Json(ApiKeyInfo::from(key)))
+}
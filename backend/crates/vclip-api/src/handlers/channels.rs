@@ -0,0 +1,235 @@
+//! Channel monitoring API handlers.
+//!
+//! Users on plans with channel monitoring can watch YouTube channels; the
+//! worker polls them and analyzes new uploads automatically.
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use uuid::Uuid;
+
+use vclip_firestore::ChannelRepository;
+use vclip_models::{normalize_channel_url, HighlightDetection, MonitoredChannel};
+
+use crate::auth::AuthUser;
+use crate::error::{ApiError, ApiResult};
+use crate::security::sanitize_string;
+use crate::state::AppState;
+
+/// A monitored channel as returned by the API.
+#[derive(Serialize)]
+pub struct ChannelInfo {
+    pub id: String,
+    pub channel_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub enabled: bool,
+    pub highlight_detection: HighlightDetection,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    /// Uploads analyzed since the channel was added
+    pub uploads_queued: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_polled_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub created_at: String,
+}
+
+impl From<MonitoredChannel> for ChannelInfo {
+    fn from(channel: MonitoredChannel) -> Self {
+        Self {
+            id: channel.id,
+            channel_url: channel.channel_url,
+            title: channel.title,
+            enabled: channel.enabled,
+            highlight_detection: channel.highlight_detection,
+            prompt: channel.prompt,
+            uploads_queued: channel.uploads_queued,
+            last_polled_at: channel.last_polled_at.map(|t| t.to_rfc3339()),
+            last_error: channel.last_error,
+            created_at: channel.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Response for listing channels.
+#[derive(Serialize)]
+pub struct ListChannelsResponse {
+    pub channels: Vec<ChannelInfo>,
+    /// Channels included in the user's plan
+    pub channels_included: u32,
+}
+
+/// List the user's monitored channels.
+pub async fn list_channels(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> ApiResult<Json<ListChannelsResponse>> {
+    let limits = state.user_service.get_plan_limits(&user.uid).await?;
+    let repo = ChannelRepository::new((*state.firestore).clone(), &user.uid);
+    let channels = repo.list().await?;
+
+    Ok(Json(ListChannelsResponse {
+        channels: channels.into_iter().map(ChannelInfo::from).collect(),
+        channels_included: limits.channel_monitoring_included,
+    }))
+}
+
+/// Request to watch a channel.
+#[derive(Debug, Deserialize)]
+pub struct CreateChannelRequest {
+    /// YouTube channel URL (`@handle`, `/channel/UC...`, `/c/...` or `/user/...`)
+    pub url: String,
+    /// How highlights are detected for new uploads
+    #[serde(default)]
+    pub highlight_detection: HighlightDetection,
+    /// Optional AI instructions applied to every upload
+    #[serde(default)]
+    pub prompt: Option<String>,
+}
+
+/// Start watching a channel.
+///
+/// Uploads already on the channel are not analyzed; only uploads published
+/// after the first poll are.
+pub async fn create_channel(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(request): Json<CreateChannelRequest>,
+) -> ApiResult<Json<ChannelInfo>> {
+    let limits = state.user_service.get_plan_limits(&user.uid).await?;
+    if limits.channel_monitoring_included == 0 {
+        return Err(ApiError::forbidden(
+            "Channel monitoring is not included in your plan",
+        ));
+    }
+
+    let channel_url = normalize_channel_url(&request.url)
+        .ok_or_else(|| ApiError::bad_request("Not a YouTube channel URL"))?;
+
+    let repo = ChannelRepository::new((*state.firestore).clone(), &user.uid);
+    let existing = repo.list().await?;
+    if existing.iter().any(|c| c.channel_url == channel_url) {
+        return Err(ApiError::Conflict("Channel is already monitored".to_string()));
+    }
+    if existing.len() >= limits.channel_monitoring_included as usize {
+        return Err(ApiError::forbidden(format!(
+            "Your plan includes {} monitored channels",
+            limits.channel_monitoring_included
+        )));
+    }
+
+    let mut channel = MonitoredChannel::new(Uuid::new_v4().to_string(), &user.uid, channel_url)
+        .with_highlight_detection(request.highlight_detection);
+    if let Some(prompt) = request.prompt.as_deref().map(sanitize_string) {
+        if !prompt.is_empty() {
+            channel = channel.with_prompt(prompt);
+        }
+    }
+
+    repo.create(&channel).await?;
+
+    info!(
+        user_id = %user.uid,
+        channel_id = %channel.id,
+        channel_url = %channel.channel_url,
+        "Added monitored channel"
+    );
+
+    Ok(Json(channel.into()))
+}
+
+/// Get a monitored channel.
+pub async fn get_channel(
+    State(state): State<AppState>,
+    Path(channel_id): Path<String>,
+    user: AuthUser,
+) -> ApiResult<Json<ChannelInfo>> {
+    let repo = ChannelRepository::new((*state.firestore).clone(), &user.uid);
+    let channel = repo
+        .get(&channel_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Channel not found"))?;
+
+    Ok(Json(channel.into()))
+}
+
+/// Request to change a channel's settings.
+#[derive(Debug, Deserialize)]
+pub struct UpdateChannelRequest {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub highlight_detection: Option<HighlightDetection>,
+    /// New AI instructions; an empty string clears them
+    #[serde(default)]
+    pub prompt: Option<String>,
+}
+
+/// Pause, resume or reconfigure a monitored channel.
+pub async fn update_channel(
+    State(state): State<AppState>,
+    Path(channel_id): Path<String>,
+    user: AuthUser,
+    Json(request): Json<UpdateChannelRequest>,
+) -> ApiResult<Json<ChannelInfo>> {
+    let repo = ChannelRepository::new((*state.firestore).clone(), &user.uid);
+    let mut channel = repo
+        .get(&channel_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Channel not found"))?;
+
+    if let Some(enabled) = request.enabled {
+        channel.enabled = enabled;
+    }
+    if let Some(mode) = request.highlight_detection {
+        channel.highlight_detection = mode;
+    }
+    if let Some(prompt) = request.prompt {
+        let prompt = sanitize_string(&prompt);
+        channel.prompt = (!prompt.is_empty()).then_some(prompt);
+    }
+
+    repo.update_settings(&channel).await?;
+
+    info!(
+        user_id = %user.uid,
+        channel_id = %channel_id,
+        enabled = channel.enabled,
+        "Updated monitored channel"
+    );
+
+    Ok(Json(channel.into()))
+}
+
+/// Response for deleting a channel.
+#[derive(Serialize)]
+pub struct DeleteChannelResponse {
+    pub success: bool,
+    pub channel_id: String,
+}
+
+/// Stop watching a channel.
+///
+/// Drafts already created for its uploads are kept.
+pub async fn delete_channel(
+    State(state): State<AppState>,
+    Path(channel_id): Path<String>,
+    user: AuthUser,
+) -> ApiResult<Json<DeleteChannelResponse>> {
+    let repo = ChannelRepository::new((*state.firestore).clone(), &user.uid);
+    if repo.get(&channel_id).await?.is_none() {
+        return Err(ApiError::not_found("Channel not found"));
+    }
+
+    repo.delete(&channel_id).await?;
+
+    info!(user_id = %user.uid, channel_id = %channel_id, "Deleted monitored channel");
+
+    Ok(Json(DeleteChannelResponse {
+        success: true,
+        channel_id,
+    }))
+}
@@ -2,24 +2,29 @@
 //!
 //! Secure endpoints for clip playback, download, and sharing.
 
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::Response;
 use axum::Json;
 use chrono::{Duration as ChronoDuration, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
-use vclip_firestore::{ClipRepository, ShareRepository};
+use vclip_firestore::{ClipRepository, ShareRepository, ShareSlugIndex, ShareViewClaim};
 use vclip_models::{
-    ClipStatus, CreateShareRequest, ShareConfig, ShareResponse,
-    is_valid_share_slug, MAX_SHARE_EXPIRY_HOURS,
+    ClipStatus, CreateShareRequest, ExportProfile, ShareConfig, ShareResponse, ShareStats,
+    ShareViewAction, UnlockShareRequest, is_allowed_viewer, is_valid_share_slug,
+    normalize_allowed_viewer, referrer_domain, share_viewer_hash, verify_share_passcode,
+    MAX_SHARE_ALLOWED_VIEWERS, MAX_SHARE_EXPIRY_HOURS, MAX_SHARE_PASSCODE_LEN,
+    MIN_SHARE_PASSCODE_LEN,
 };
+use vclip_queue::{share_stats_day, ShareView};
 use vclip_storage::{DeliveryConfig, DeliveryUrl, DeliveryUrlGenerator};
 
 use crate::auth::AuthUser;
 use crate::error::{ApiError, ApiResult};
-use crate::security::is_valid_clip_name;
+use crate::handlers::share_page;
+use crate::security::{is_valid_clip_name, is_valid_video_id};
 use crate::state::AppState;
 
 // ============================================================================
@@ -51,12 +56,15 @@ pub struct ClipSummary {
     pub file_size_bytes: u64,
 }
 
-/// Request body for download URL (optional filename override).
+/// Request body for download URL (optional filename override and export variant).
 #[derive(Debug, Deserialize)]
 pub struct DownloadUrlRequest {
     /// Custom filename for the download.
     #[serde(default)]
     pub filename: Option<String>,
+    /// Download the clip's variant for this export profile instead of the master.
+    #[serde(default)]
+    pub profile: Option<ExportProfile>,
 }
 
 // ============================================================================
@@ -134,7 +142,7 @@ pub async fn get_playback_url(
 ///
 /// Request body (optional):
 /// ```json
-/// { "filename": "my-custom-filename.mp4" }
+/// { "filename": "my-custom-filename.mp4", "profile": "tiktok" }
 /// ```
 ///
 /// Response: Same as playback URL.
@@ -157,19 +165,36 @@ pub async fn get_download_url(
         return Err(ApiError::forbidden("You don't own this clip"));
     }
 
+    // Resolve the requested export variant (defaults to the master clip)
+    let variant = match body.as_ref().and_then(|b| b.profile) {
+        Some(profile) => Some(
+            clip.variants
+                .iter()
+                .find(|v| v.profile == profile)
+                .ok_or_else(|| {
+                    ApiError::not_found(format!("No {} variant for this clip", profile))
+                })?,
+        ),
+        None => None,
+    };
+    let (r2_key, default_filename, file_size_bytes) = match variant {
+        Some(v) => (&v.r2_key, &v.filename, v.file_size_bytes),
+        None => (&clip.r2_key, &clip.filename, clip.file_size_bytes),
+    };
+
     // Determine filename for Content-Disposition
     let filename = body
         .as_ref()
         .and_then(|b| b.filename.as_deref())
         .map(sanitize_download_filename)
-        .unwrap_or_else(|| clip.filename.clone());
+        .unwrap_or_else(|| default_filename.clone());
 
     // Generate delivery URL
     let delivery_config = DeliveryConfig::from_env();
     let generator = DeliveryUrlGenerator::new((*state.storage).clone(), delivery_config);
 
     let delivery_url = generator
-        .download_url(&clip.r2_key, &clip.clip_id, &user.uid, Some(&filename))
+        .download_url(r2_key, &clip.clip_id, &user.uid, Some(&filename))
         .await
         .map_err(|e| {
             warn!(clip_id = %clip_id, error = %e, "Failed to generate download URL");
@@ -178,6 +203,9 @@ pub async fn get_download_url(
 
     info!(clip_id = %clip_id, user_id = %user.uid, "Generated download URL");
 
+    let duration_seconds = variant.map_or(clip.duration_seconds, |v| v.duration_seconds);
+    let filename = default_filename.clone();
+
     Ok(Json(PlaybackUrlResponse {
         url: delivery_url.url,
         expires_at: delivery_url.expires_at,
@@ -185,10 +213,10 @@ pub async fn get_download_url(
         content_type: delivery_url.content_type,
         clip: ClipSummary {
             clip_id: clip.clip_id,
-            filename: clip.filename,
+            filename,
             title: clip.scene_title,
-            duration_seconds: clip.duration_seconds,
-            file_size_bytes: clip.file_size_bytes,
+            duration_seconds,
+            file_size_bytes,
         },
     }))
 }
@@ -251,10 +279,15 @@ pub async fn get_thumbnail_url(
 /// {
 ///   "access_level": "view_playback",
 ///   "expires_in_hours": 24,
-///   "watermark_enabled": false
+///   "watermark_enabled": false,
+///   "passcode": "optional passcode",
+///   "max_views": 10,
+///   "allowed_viewers": ["acme.com", "jane@client.io"]
 /// }
 /// ```
 ///
+/// Replacing an existing share retires its previous link.
+///
 /// Response:
 /// ```json
 /// {
@@ -263,6 +296,9 @@ pub async fn get_thumbnail_url(
 ///   "access_level": "view_playback",
 ///   "expires_at": "2024-01-02T12:00:00Z",
 ///   "watermark_enabled": false,
+///   "passcode_protected": true,
+///   "max_views": 10,
+///   "allowed_viewers": ["acme.com", "jane@client.io"],
 ///   "created_at": "2024-01-01T12:00:00Z"
 /// }
 /// ```
@@ -296,6 +332,36 @@ pub async fn create_share(
         }
     }
 
+    // Validate access restrictions
+    let passcode = body.passcode.as_deref().filter(|p| !p.is_empty());
+    if let Some(passcode) = passcode {
+        let len = passcode.chars().count();
+        if !(MIN_SHARE_PASSCODE_LEN..=MAX_SHARE_PASSCODE_LEN).contains(&len) {
+            return Err(ApiError::bad_request(format!(
+                "Passcode must be {} to {} characters",
+                MIN_SHARE_PASSCODE_LEN, MAX_SHARE_PASSCODE_LEN
+            )));
+        }
+    }
+    if body.max_views == Some(0) {
+        return Err(ApiError::bad_request("max_views must be at least 1"));
+    }
+    if body.allowed_viewers.len() > MAX_SHARE_ALLOWED_VIEWERS {
+        return Err(ApiError::bad_request(format!(
+            "At most {} allowed viewers",
+            MAX_SHARE_ALLOWED_VIEWERS
+        )));
+    }
+    let mut allowed_viewers = Vec::with_capacity(body.allowed_viewers.len());
+    for entry in &body.allowed_viewers {
+        let normalized = normalize_allowed_viewer(entry).ok_or_else(|| {
+            ApiError::bad_request(format!("Invalid allowed viewer: {}", entry))
+        })?;
+        if !allowed_viewers.contains(&normalized) {
+            allowed_viewers.push(normalized);
+        }
+    }
+
     // Create share config
     let mut share_config = ShareConfig::new(
         &clip.clip_id,
@@ -315,15 +381,43 @@ pub async fn create_share(
         share_config = share_config.with_watermark();
     }
 
-    // Persist share config to Firestore (dual-document pattern)
+    if let Some(passcode) = passcode {
+        let passcode = passcode.to_string();
+        share_config = tokio::task::spawn_blocking(move || share_config.with_passcode(&passcode))
+            .await
+            .map_err(|e| ApiError::internal(format!("Passcode hashing failed: {}", e)))?;
+    }
+    if let Some(max_views) = body.max_views {
+        share_config = share_config.with_max_views(max_views);
+    }
+    if !allowed_viewers.is_empty() {
+        share_config = share_config.with_allowed_viewers(allowed_viewers);
+    }
+
     let share_repo = ShareRepository::new((*state.firestore).clone());
+
+    // Retire the previous link so its (possibly weaker) settings stop applying
+    let previous = share_repo
+        .get_config(&user.uid, clip.video_id.as_str(), &clip_id)
+        .await
+        .map_err(|e| {
+            warn!(clip_id = %clip_id, error = %e, "Failed to look up share config");
+            ApiError::internal("Database error")
+        })?;
+    if let Some(previous) = previous.filter(|p| p.disabled_at.is_none()) {
+        share_repo.delete_slug(&previous.share_slug).await.map_err(|e| {
+            warn!(share_slug = %previous.share_slug, error = %e, "Failed to retire previous share link");
+            ApiError::internal("Failed to create share link")
+        })?;
+    }
+
+    // Persist share config to Firestore (dual-document pattern)
     share_repo.create_share(&share_config).await.map_err(|e| {
         warn!(clip_id = %clip_id, error = %e, "Failed to persist share config");
         ApiError::internal("Failed to create share link")
     })?;
 
-    let base_url = std::env::var("PUBLIC_APP_URL")
-        .unwrap_or_else(|_| "https://viralclipai.io".to_string());
+    let base_url = public_app_url();
 
     let response = ShareResponse::from_config(&share_config, &base_url);
 
@@ -398,31 +492,374 @@ pub async fn revoke_share(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// A clip's share link and its view statistics.
+#[derive(Debug, Serialize)]
+pub struct ShareDetailsResponse {
+    /// Current share link, if the clip has ever been shared
+    pub share: Option<ShareResponse>,
+    /// Whether the share link currently resolves
+    pub active: bool,
+    /// Views counted against the link's view limit (view-limited links only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub views_used: Option<u64>,
+    /// Views across all of the clip's share links
+    pub stats: ShareStats,
+}
+
+/// Get a clip's share link and view statistics.
+///
+/// GET /api/clips/{clip_id}/share
+pub async fn get_share(
+    State(state): State<AppState>,
+    Path(clip_id): Path<String>,
+    user: AuthUser,
+) -> ApiResult<Json<ShareDetailsResponse>> {
+    // Validate clip_id format
+    if !is_valid_clip_name(&clip_id) {
+        return Err(ApiError::bad_request("Invalid clip ID format"));
+    }
+
+    // Look up clip metadata
+    let clip = find_clip_by_id(&state, &user.uid, &clip_id).await?;
+
+    // Verify ownership
+    if clip.user_id != user.uid {
+        return Err(ApiError::forbidden("You don't own this clip"));
+    }
+
+    let details = load_share_details(&state, &user.uid, clip.video_id.as_str(), &clip_id).await?;
+    Ok(Json(details))
+}
+
+/// Share statistics of one clip in a video summary.
+#[derive(Debug, Serialize)]
+pub struct ClipShareStats {
+    pub clip_id: String,
+    pub share_slug: Option<String>,
+    pub active: bool,
+    pub stats: ShareStats,
+}
+
+/// Share statistics of all clips in a video.
+#[derive(Debug, Serialize)]
+pub struct VideoShareStatsResponse {
+    pub video_id: String,
+    /// Clips that have been shared
+    pub clips: Vec<ClipShareStats>,
+    /// Sum over all clips
+    pub totals: ShareStats,
+}
+
+/// Summarize share link views across a video's clips.
+///
+/// GET /api/videos/{video_id}/share-stats
+pub async fn get_video_share_stats(
+    State(state): State<AppState>,
+    Path(video_id): Path<String>,
+    user: AuthUser,
+) -> ApiResult<Json<VideoShareStatsResponse>> {
+    if !is_valid_video_id(&video_id) {
+        return Err(ApiError::bad_request("Invalid video ID format"));
+    }
+
+    if !state.user_service.user_owns_video(&user.uid, &video_id).await? {
+        return Err(ApiError::not_found("Video not found"));
+    }
+
+    let clip_repo = ClipRepository::new(
+        (*state.firestore).clone(),
+        &user.uid,
+        vclip_models::VideoId::from_string(video_id.clone()),
+    );
+    let clips = clip_repo.list(None).await.map_err(|e| {
+        warn!(video_id = %video_id, error = %e, "Failed to list clips");
+        ApiError::internal("Database error")
+    })?;
+
+    let mut summary = VideoShareStatsResponse {
+        video_id: video_id.clone(),
+        clips: Vec::new(),
+        totals: ShareStats::default(),
+    };
+    for clip in clips {
+        let details = load_share_details(&state, &user.uid, &video_id, &clip.clip_id).await?;
+        if details.share.is_none() && details.stats.is_empty() {
+            continue;
+        }
+        summary.totals.merge(&details.stats);
+        summary.clips.push(ClipShareStats {
+            clip_id: clip.clip_id,
+            share_slug: details.share.map(|s| s.share_slug),
+            active: details.active,
+            stats: details.stats,
+        });
+    }
+
+    Ok(Json(summary))
+}
+
+/// Load a clip's share config and stats, including views not yet flushed.
+async fn load_share_details(
+    state: &AppState,
+    user_id: &str,
+    video_id: &str,
+    clip_id: &str,
+) -> ApiResult<ShareDetailsResponse> {
+    let share_repo = ShareRepository::new((*state.firestore).clone());
+    let config = share_repo
+        .get_config(user_id, video_id, clip_id)
+        .await
+        .map_err(|e| {
+            warn!(clip_id = %clip_id, error = %e, "Failed to look up share config");
+            ApiError::internal("Database error")
+        })?;
+    let mut stats = share_repo
+        .get_stats(user_id, video_id, clip_id)
+        .await
+        .map_err(|e| {
+            warn!(clip_id = %clip_id, error = %e, "Failed to load share stats");
+            ApiError::internal("Database error")
+        })?
+        .unwrap_or_default();
+
+    let mut views_used = None;
+    if let Some(config) = config.as_ref().filter(|c| c.max_views.is_some()) {
+        views_used = share_repo
+            .get_by_slug(&config.share_slug)
+            .await
+            .map_err(|e| {
+                warn!(share_slug = %config.share_slug, error = %e, "Failed to look up share slug");
+                ApiError::internal("Database error")
+            })?
+            .map(|index| index.view_count);
+    }
+
+    if let Some(config) = &config {
+        match state.share_stats.pending(&config.share_slug).await {
+            Ok(pending) => stats.merge(&pending),
+            Err(e) => {
+                warn!(share_slug = %config.share_slug, error = %e, "Failed to read pending share stats");
+            }
+        }
+    }
+
+    let base_url = public_app_url();
+
+    Ok(ShareDetailsResponse {
+        active: config.as_ref().is_some_and(ShareConfig::is_active),
+        views_used,
+        share: config.as_ref().map(|c| ShareResponse::from_config(c, &base_url)),
+        stats,
+    })
+}
+
 // ============================================================================
 // Public Share Resolution (for /c/{share_slug} redirect)
 // ============================================================================
 
-/// Resolve a share slug to a playback redirect.
+/// Query parameters for share resolution.
+#[derive(Debug, Default, Deserialize)]
+pub struct ResolveShareQuery {
+    /// Redirect to a download (attachment) URL instead of playback.
+    /// Only honored for shares with download access.
+    #[serde(default)]
+    pub download: bool,
+
+    /// Always redirect to the clip, even for browsers.
+    #[serde(default, deserialize_with = "deserialize_flag")]
+    pub raw: bool,
+
+    /// Render the bare player (for oEmbed iframes).
+    #[serde(default, deserialize_with = "deserialize_flag")]
+    pub embed: bool,
+}
+
+/// Query flag given as `1`/`true` (or `0`/`false`).
+fn deserialize_flag<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    match value.as_str() {
+        "1" | "true" | "" => Ok(true),
+        "0" | "false" => Ok(false),
+        other => Err(serde::de::Error::custom(format!("invalid flag: {}", other))),
+    }
+}
+
+/// Response for a share that must be unlocked first.
+#[derive(Debug, Serialize)]
+pub struct ShareLockedResponse {
+    pub detail: String,
+    /// Viewers must enter the share's passcode
+    pub passcode_required: bool,
+    /// Viewers must sign in with an allowed email
+    pub sign_in_required: bool,
+}
+
+/// Resolve a share slug to a player page or a playback redirect.
 ///
 /// GET /c/{share_slug}
+/// GET /c/{share_slug}?download=true
+/// GET /c/{share_slug}?raw=1
+///
+/// Browsers and link unfurlers get the HTML player page (see
+/// [`share_page`](crate::handlers::share_page)); `?raw=1`, `?download=true`
+/// and non-HTML clients get the redirect.
+///
+/// Every redirect is counted in the share's stats (buffered in Redis).
+/// Shares with a passcode or allowed-viewer list answer 401 with a
+/// [`ShareLockedResponse`]; the viewer then unlocks them with
+/// `POST /c/{share_slug}/unlock`.
 ///
 /// Returns: 302 redirect to a short-lived presigned URL, or error.
 pub async fn resolve_share(
     State(state): State<AppState>,
     Path(share_slug): Path<String>,
+    Query(query): Query<ResolveShareQuery>,
+    headers: HeaderMap,
 ) -> Result<Response, ApiError> {
     use axum::http::header;
     use axum::response::IntoResponse;
-    use std::time::Duration;
 
+    let slug_info = load_available_share(&state, &share_slug).await?;
+
+    if !query.raw && !query.download && share_page::wants_html(&headers) {
+        return share_page::render_share_page(&state, &slug_info, query.embed).await;
+    }
+
+    if slug_info.requires_unlock() {
+        info!(share_slug = %share_slug, "Share requires unlock");
+        let locked = ShareLockedResponse {
+            detail: "This share link is protected".to_string(),
+            passcode_required: slug_info.passcode_hash.is_some(),
+            sign_in_required: !slug_info.allowed_viewers.is_empty(),
+        };
+        return Ok((
+            StatusCode::UNAUTHORIZED,
+            [(header::CACHE_CONTROL, "no-store")],
+            Json(locked),
+        )
+            .into_response());
+    }
+
+    let delivery_url = deliver_share(&state, &slug_info, query.download, &headers).await?;
+
+    // Build 302 redirect response with cache control
+    let response = (
+        StatusCode::FOUND,
+        [
+            (header::LOCATION, delivery_url.url),
+            // Prevent caching so revocation takes effect quickly
+            (header::CACHE_CONTROL, "private, max-age=60".to_string()),
+        ],
+    )
+        .into_response();
+
+    Ok(response)
+}
+
+/// Unlock a protected share link.
+///
+/// POST /c/{share_slug}/unlock
+///
+/// Request body:
+/// ```json
+/// { "passcode": "optional passcode", "download": false }
+/// ```
+///
+/// Shares with an allowed-viewer list also require a Firebase ID token of a
+/// user with a verified, allowed email. Rate limited per IP on its own, and
+/// repeated wrong passcodes lock the link for a backoff period (429).
+///
+/// Returns: the short-lived delivery URL (the same URL `GET /c/{share_slug}`
+/// redirects to for unprotected shares).
+pub async fn unlock_share(
+    State(state): State<AppState>,
+    Path(share_slug): Path<String>,
+    user: Option<AuthUser>,
+    headers: HeaderMap,
+    Json(body): Json<UnlockShareRequest>,
+) -> ApiResult<Json<DeliveryUrl>> {
+    let slug_info = load_available_share(&state, &share_slug).await?;
+
+    if let Some(hash) = slug_info.passcode_hash.clone() {
+        let passcode = body.passcode.clone().unwrap_or_default();
+        if passcode.is_empty() {
+            return Err(ApiError::unauthorized("Passcode required"));
+        }
+        // Attempts against one link are bounded regardless of the client IP
+        match state.share_unlock.locked_for(&share_slug).await {
+            Ok(Some(secs)) => {
+                info!(share_slug = %share_slug, retry_after_secs = secs, "Share passcode locked out");
+                return Err(ApiError::RateLimited);
+            }
+            Ok(None) => {}
+            Err(e) => warn!(share_slug = %share_slug, "Failed to check passcode lockout: {}", e),
+        }
+        // Argon2 is deliberately slow; keep it off the async workers
+        let valid = tokio::task::spawn_blocking(move || verify_share_passcode(&passcode, &hash))
+            .await
+            .map_err(|e| ApiError::internal(format!("Passcode check failed: {}", e)))?;
+        if !valid {
+            info!(share_slug = %share_slug, "Incorrect share passcode");
+            match state.share_unlock.record_failure(&share_slug).await {
+                Ok(Some(secs)) => {
+                    warn!(share_slug = %share_slug, lockout_secs = secs, "Share passcode locked after repeated failures");
+                }
+                Ok(None) => {}
+                Err(e) => warn!(share_slug = %share_slug, "Failed to record passcode failure: {}", e),
+            }
+            return Err(ApiError::unauthorized("Incorrect passcode"));
+        }
+        if let Err(e) = state.share_unlock.clear(&share_slug).await {
+            warn!(share_slug = %share_slug, "Failed to clear passcode failures: {}", e);
+        }
+    }
+
+    if !slug_info.allowed_viewers.is_empty() {
+        // API keys identify an account, not a viewer's email
+        let email = user
+            .as_ref()
+            .filter(|u| u.api_key_id.is_none() && u.email_verified)
+            .and_then(|u| u.email.as_deref())
+            .ok_or_else(|| ApiError::unauthorized("Sign in with a verified email to view this clip"))?;
+        if !is_allowed_viewer(&slug_info.allowed_viewers, email) {
+            info!(share_slug = %share_slug, "Share viewer not allowed");
+            return Err(ApiError::forbidden("This account is not allowed to view this clip"));
+        }
+    }
+
+    let delivery_url = deliver_share(&state, &slug_info, body.download, &headers).await?;
+    Ok(Json(delivery_url))
+}
+
+/// Whether a share link is still active.
+///
+/// GET /c/{share_slug}/status
+///
+/// Used by the CDN Worker to reject delivery tokens of revoked shares.
+/// Does not count a view.
+///
+/// Returns: 204 if active, 404 if unknown or revoked, 410 if expired.
+pub async fn share_status(
+    State(state): State<AppState>,
+    Path(share_slug): Path<String>,
+) -> ApiResult<StatusCode> {
+    load_available_share(&state, &share_slug).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Look up a share slug and check it is neither revoked nor expired.
+pub(crate) async fn load_available_share(state: &AppState, share_slug: &str) -> ApiResult<ShareSlugIndex> {
     // Validate share_slug format
-    if !is_valid_share_slug(&share_slug) {
+    if !is_valid_share_slug(share_slug) {
         return Err(ApiError::bad_request("Invalid share link"));
     }
 
     // Look up share by slug
     let share_repo = ShareRepository::new((*state.firestore).clone());
-    let slug_info = share_repo.get_by_slug(&share_slug).await.map_err(|e| {
+    let slug_info = share_repo.get_by_slug(share_slug).await.map_err(|e| {
         warn!(share_slug = %share_slug, error = %e, "Failed to look up share slug");
         ApiError::internal("Database error")
     })?;
@@ -450,33 +887,92 @@ pub async fn resolve_share(
         }
     }
 
+    Ok(slug_info)
+}
+
+/// Count a view of an available share and generate its delivery URL.
+async fn deliver_share(
+    state: &AppState,
+    slug_info: &ShareSlugIndex,
+    download: bool,
+    headers: &HeaderMap,
+) -> ApiResult<DeliveryUrl> {
+    use axum::http::header;
+    use std::time::Duration;
+
+    let share_slug = slug_info.share_slug.as_str();
+
+    if download && !slug_info.access_level.allows_download() {
+        return Err(ApiError::forbidden("This share link does not allow downloads"));
+    }
+    let action = if download {
+        ShareViewAction::Download
+    } else {
+        ShareViewAction::Play
+    };
+
+    if slug_info.views_exhausted() {
+        return Err(ApiError::Gone("Share link has reached its view limit".to_string()));
+    }
+
     // Look up clip metadata to get R2 key
     let clip = find_clip_by_owner_context(
-        &state,
+        state,
         &slug_info.user_id,
         &slug_info.video_id,
         &slug_info.clip_id,
     )
     .await?;
 
+    // Claim a view last, so failed lookups don't use up a view-limited share
+    let share_repo = ShareRepository::new((*state.firestore).clone());
+    let claim = share_repo.claim_view(share_slug).await.map_err(|e| {
+        warn!(share_slug = %share_slug, error = %e, "Failed to claim share view");
+        ApiError::internal("Database error")
+    })?;
+    match claim {
+        ShareViewClaim::Granted { .. } => {}
+        ShareViewClaim::Exhausted => {
+            info!(share_slug = %share_slug, "Share view limit reached");
+            return Err(ApiError::Gone("Share link has reached its view limit".to_string()));
+        }
+        ShareViewClaim::NotFound => {
+            return Err(ApiError::Gone("Share link has been revoked".to_string()));
+        }
+    }
+
     // Generate short-lived delivery URL (1 hour)
     let delivery_config = DeliveryConfig::from_env();
     let generator = DeliveryUrlGenerator::new((*state.storage).clone(), delivery_config.clone());
 
-    // Use Worker URL if configured, otherwise presigned URL
+    // Use Worker URL if configured (bound to the share, so revocation stops it),
+    // otherwise presigned URL
     let delivery_url = if delivery_config.should_use_worker() {
+        let scope = match action {
+            ShareViewAction::Play => vclip_storage::DeliveryScope::Playback,
+            ShareViewAction::Download => vclip_storage::DeliveryScope::Download,
+        };
         generator
-            .generate_worker_url_with_key(
+            .generate_share_worker_url(
                 &clip.clip_id,
                 &slug_info.user_id,
-                Some(&clip.r2_key),
-                vclip_storage::DeliveryScope::Playback,
+                &clip.r2_key,
+                share_slug,
+                scope,
                 Duration::from_secs(3600), // 1 hour
             )
             .map_err(|e| {
                 warn!(share_slug = %share_slug, error = %e, "Failed to generate worker URL");
                 ApiError::internal("Failed to generate playback URL")
             })?
+    } else if action == ShareViewAction::Download {
+        generator
+            .download_url(&clip.r2_key, &clip.clip_id, &slug_info.user_id, Some(&clip.filename))
+            .await
+            .map_err(|e| {
+                warn!(share_slug = %share_slug, error = %e, "Failed to generate download URL");
+                ApiError::internal("Failed to generate download URL")
+            })?
     } else {
         generator
             .playback_url(&clip.r2_key, &clip.clip_id, &slug_info.user_id)
@@ -487,29 +983,68 @@ pub async fn resolve_share(
             })?
     };
 
+    // Count the view; analytics must never block the redirect
+    let day = share_stats_day(Utc::now());
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    let viewer_hash = share_viewer_hash(
+        &share_viewer_ip(headers),
+        user_agent,
+        &day,
+        &state.config.share_viewer_salt,
+    );
+    let referrer = headers
+        .get(header::REFERER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(referrer_domain);
+    let view = ShareView {
+        share_slug,
+        user_id: &slug_info.user_id,
+        video_id: &slug_info.video_id,
+        clip_id: &slug_info.clip_id,
+        viewer_hash: &viewer_hash,
+        day: &day,
+        referrer: referrer.as_deref(),
+        action,
+    };
+    if let Err(e) = state.share_stats.record_view(&view).await {
+        warn!(share_slug = %share_slug, error = %e, "Failed to record share view");
+    }
+
     info!(
         share_slug = %share_slug,
         clip_id = %clip.clip_id,
         user_id = %slug_info.user_id,
+        action = action.as_str(),
         "Resolved share link"
     );
 
-    // Build 302 redirect response with cache control
-    let response = (
-        StatusCode::FOUND,
-        [
-            (header::LOCATION, delivery_url.url),
-            // Prevent caching so revocation takes effect quickly
-            (header::CACHE_CONTROL, "private, max-age=60".to_string()),
-        ],
-    )
-        .into_response();
+    Ok(delivery_url)
+}
 
-    Ok(response)
+/// Public app URL that share links are built on (no trailing slash).
+pub(crate) fn public_app_url() -> String {
+    std::env::var("PUBLIC_APP_URL")
+        .unwrap_or_else(|_| "https://viralclipai.io".to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Client IP of a share viewer, from the proxy headers.
+fn share_viewer_ip(headers: &HeaderMap) -> String {
+    headers
+        .get("X-Forwarded-For")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .or_else(|| headers.get("X-Real-IP").and_then(|v| v.to_str().ok()))
+        .map(|ip| ip.trim().to_string())
+        .unwrap_or_default()
 }
 
 /// Find a clip by owner context (user_id, video_id, clip_id).
-async fn find_clip_by_owner_context(
+pub(crate) async fn find_clip_by_owner_context(
     state: &AppState,
     user_id: &str,
     video_id: &str,
@@ -38,7 +38,7 @@ fn default_limit() -> u32 {
 fn validate_operation_type(op_type: &str) -> Result<(), ApiError> {
     if CreditOperationType::from_str(op_type).is_none() {
         return Err(ApiError::bad_request(format!(
-            "Invalid operation_type '{}'. Must be one of: analysis, scene_processing, reprocessing, silent_remover, object_detection, scene_originals, admin_adjustment",
+            "Invalid operation_type '{}'. Must be one of: analysis, scene_processing, reprocessing, silent_remover, object_detection, scene_originals, admin_adjustment, refund",
             op_type
         )));
     }
@@ -14,18 +14,18 @@ use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
 use vclip_firestore::{FromFirestoreValue, HighlightsRepository};
+use vclip_highlights::prompt::{
+    build_existing_scenes_context, build_generate_more_prompt, fallback_base_prompt,
+};
+use vclip_highlights::{hook_strength, HighlightProviderKind, HighlightRequest};
 use vclip_models::{
-    CreditContext, CreditOperationType, Highlight, HighlightCategory, VideoId,
-    parse_timestamp, validate_timestamps, TimestampError,
+    BoundaryRefinement, CreditContext, CreditOperationType, Highlight, HighlightCategory,
+    QualityMetrics, VideoId, parse_timestamp, validate_timestamps, TimestampError,
 };
 
 use crate::auth::AuthUser;
 use crate::error::{ApiError, ApiResult};
 use crate::security::sanitize_string;
-use crate::services::gemini::{
-    build_existing_scenes_context, build_generate_more_prompt, get_fallback_base_prompt,
-    GeminiClient,
-};
 use crate::state::AppState;
 
 /// Maximum scenes allowed in a bulk add operation.
@@ -142,6 +142,15 @@ pub async fn update_scene_timestamps(
     video_highlights.highlights[scene_idx].start = validated.start.clone();
     video_highlights.highlights[scene_idx].end = validated.end.clone();
     video_highlights.highlights[scene_idx].duration = validated.duration_secs;
+    // User-set timestamps are kept as-is by boundary refinement
+    video_highlights.highlights[scene_idx].refinement =
+        Some(BoundaryRefinement::manual(&validated.start, &validated.end));
+    // Time-based metrics no longer apply; they are recomputed on the next render
+    let quality = video_highlights.highlights[scene_idx]
+        .quality
+        .unwrap_or_default()
+        .retimed(validated.duration_secs as f64);
+    video_highlights.highlights[scene_idx].set_quality(quality);
     video_highlights.updated_at = Utc::now();
 
     // Save to Firestore
@@ -276,7 +285,8 @@ pub async fn add_scene(
     // Parse hook category
     let hook_category = request.hook_category.as_ref().and_then(|c| parse_hook_category(c));
 
-    // Create new highlight
+    // Create new highlight (only duration fit is known until it renders)
+    let quality = QualityMetrics::default().with_duration(validated.duration_secs as f64);
     let new_highlight = Highlight {
         id: next_id,
         title: title.to_string(),
@@ -288,6 +298,9 @@ pub async fn add_scene(
         hook_category,
         reason: Some(reason.to_string()),
         description: request.description.as_ref().map(|d| sanitize_string(d)),
+        refinement: Some(BoundaryRefinement::manual(&validated.start, &validated.end)),
+        virality_score: quality.virality_score(),
+        quality: Some(quality),
     };
 
     // Add to highlights and sort by start time
@@ -471,6 +484,8 @@ pub async fn bulk_add_scenes(
         let hook_category = entry.hook_category.as_ref().and_then(|c| parse_hook_category(c));
 
         // Create highlight
+        let refinement = BoundaryRefinement::manual(&validated.start, &validated.end);
+        let quality = QualityMetrics::default().with_duration(validated.duration_secs as f64);
         let new_highlight = Highlight {
             id: next_id,
             title: title.to_string(),
@@ -482,6 +497,9 @@ pub async fn bulk_add_scenes(
             hook_category,
             reason: Some(reason.to_string()),
             description: entry.description.as_ref().map(|d| sanitize_string(d)),
+            refinement: Some(refinement),
+            virality_score: quality.virality_score(),
+            quality: Some(quality),
         };
 
         added_scenes.push(SceneInfo {
@@ -653,6 +671,14 @@ pub async fn generate_more_scenes(
     // Get admin base prompt
     let admin_prompt = get_admin_base_prompt_from_firestore(&state).await;
 
+    // Cached transcript from the original analysis (required by the heuristic provider)
+    let transcript = vclip_storage::load_transcript(
+        &state.storage,
+        &user.uid,
+        &vclip_storage::transcript_cache_id_from_url(video_url),
+    )
+    .await;
+
     // Build prompt with existing scenes context
     let existing_scenes_context = build_existing_scenes_context(&video_highlights.highlights);
     let user_prompt = video_highlights.custom_prompt.clone().unwrap_or_default();
@@ -661,14 +687,41 @@ pub async fn generate_more_scenes(
         &admin_prompt,
         &user_prompt,
         &existing_scenes_context,
+        transcript.as_deref(),
         count,
     );
 
-    // Create Gemini client and generate
-    let gemini_client = GeminiClient::new()?;
+    let existing_ranges: Vec<(f64, f64)> = video_highlights
+        .highlights
+        .iter()
+        .filter_map(|h| {
+            Some((
+                parse_timestamp(&h.start).ok()?,
+                parse_timestamp(&h.end).ok()?,
+            ))
+        })
+        .collect();
+
+    // Use the user's preferred provider, falling back to the configured default
+    let preferred = state
+        .user_service
+        .get_user_settings(&user.uid)
+        .await?
+        .highlight_provider
+        .and_then(|p| p.parse::<HighlightProviderKind>().ok());
+    let provider = state.highlight_providers.get(preferred);
+
+    let transcript = transcript.unwrap_or_default();
+    let provider_request = HighlightRequest::new(
+        generate_more_prompt,
+        transcript.clone(),
+        video_url,
+    )
+    .with_max_highlights(count as usize)
+    .with_exclusions(existing_ranges);
 
-    let ai_response = gemini_client
-        .generate_more_scenes(&generate_more_prompt, video_url)
+    let ai_response = provider
+        .detect_highlights(&provider_request)
         .await
         .map_err(|e| {
             warn!("AI analysis failed for {}: {:?}", video_id, e);
@@ -712,7 +765,14 @@ pub async fn generate_more_scenes(
         // Parse hook category
         let hook_category = ai_highlight.hook_category.as_ref().and_then(|c| parse_hook_category(c));
 
-        let new_highlight = Highlight {
+        let quality = QualityMetrics {
+            hook_strength: hook_strength(&transcript, new_start),
+            llm_confidence: ai_highlight.confidence.map(|c| c.clamp(0.0, 1.0)),
+            ..Default::default()
+        }
+        .with_duration(validated.duration_secs as f64);
+
+        let mut new_highlight = Highlight {
             id: next_id,
             title: ai_highlight.title,
             start: validated.start.clone(),
@@ -723,7 +783,11 @@ pub async fn generate_more_scenes(
             hook_category,
             reason: ai_highlight.reason,
             description: ai_highlight.description,
+            refinement: None,
+            virality_score: None,
+            quality: None,
         };
+        new_highlight.set_quality(quality);
 
         new_scenes.push(SceneInfo {
             id: new_highlight.id,
@@ -778,7 +842,7 @@ pub async fn generate_more_scenes(
 async fn get_admin_base_prompt_from_firestore(state: &AppState) -> String {
     let doc = match state.firestore.get_document("admin", "config").await {
         Ok(Some(d)) => d,
-        _ => return get_fallback_base_prompt(),
+        _ => return fallback_base_prompt(),
     };
 
     doc.fields
@@ -786,7 +850,7 @@ async fn get_admin_base_prompt_from_firestore(state: &AppState) -> String {
         .and_then(|fields| fields.get("base_prompt"))
         .and_then(|v| String::from_firestore_value(v))
         .filter(|s| !s.is_empty())
-        .unwrap_or_else(get_fallback_base_prompt)
+        .unwrap_or_else(fallback_base_prompt)
 }
 
 // ============================================================================
@@ -3,19 +3,35 @@
 //! Provides REST API endpoints for:
 //! - Getting job status (for polling fallback when WebSocket disconnects)
 //! - Getting progress history (for recovery after reconnect)
+//! - Streaming live progress over Server-Sent Events
+//! - Cancelling a queued or running job
+
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::time::Duration;
 
 use axum::extract::{Path, Query, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::Json;
+use futures_util::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use vclip_models::JobId;
-use vclip_queue::{STALE_GRACE_PERIOD_SECS, STALE_THRESHOLD_SECS};
+use vclip_models::{JobId, WsMessage};
+use vclip_queue::{ProgressEvent, STALE_GRACE_PERIOD_SECS, STALE_THRESHOLD_SECS};
 
 use crate::auth::AuthUser;
 use crate::error::{ApiError, ApiResult};
 use crate::state::AppState;
 
+/// Interval between keep-alive comments on an event stream.
+const SSE_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Longest an event stream stays open; clients reconnect with `Last-Event-ID`.
+const SSE_MAX_DURATION: Duration = Duration::from_secs(2 * 3600);
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -40,7 +56,7 @@ pub struct JobStatusResponse {
     pub job_id: String,
     /// Associated video ID
     pub video_id: String,
-    /// Current status: queued, processing, completed, failed, stale
+    /// Current status: queued, processing, completed, failed, stale, cancelled
     pub status: String,
     /// Progress percentage (0-100)
     pub progress: u8,
@@ -234,16 +250,9 @@ pub async fn get_job_history(
 
     let limit = query.limit.unwrap_or(1000).min(5000) as usize;
     let events: Vec<serde_json::Value> = history
-        .into_iter()
+        .iter()
         .take(limit)
-        .filter_map(|e| {
-            let mut value = serde_json::to_value(&e.message).ok()?;
-            if let Some(obj) = value.as_object_mut() {
-                obj.insert("timestamp_ms".to_string(), serde_json::json!(e.timestamp_ms));
-                obj.insert("seq".to_string(), serde_json::json!(e.seq));
-            }
-            Some(value)
-        })
+        .filter_map(event_json)
         .collect();
 
     Ok(Json(JobHistoryResponse {
@@ -253,6 +262,204 @@ pub async fn get_job_history(
     }))
 }
 
+/// Live progress stream of a job.
+type JobEventStream = Pin<Box<dyn Stream<Item = ProgressEvent> + Send>>;
+
+/// GET /api/jobs/:job_id/events
+///
+/// Stream a job's progress as Server-Sent Events.
+///
+/// Each event carries the progress sequence number as its ID and the message
+/// type as its name. A client reconnecting with `Last-Event-ID` first gets
+/// the events it missed from history, then live events. Keep-alive comments
+/// are sent while the job is quiet, and the stream ends after the job's
+/// `done`, `error` or `cancelled` event.
+///
+/// Returns:
+/// - 200: Event stream
+/// - 401: Not authenticated
+/// - 403: Job belongs to another user
+/// - 404: Job not found
+pub async fn stream_job_events(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+    headers: HeaderMap,
+    user: AuthUser,
+) -> ApiResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    // Validate job ID format
+    if !is_valid_job_id(&job_id) {
+        return Err(ApiError::bad_request("Invalid job ID format"));
+    }
+
+    let job_id_typed = JobId::from(job_id.clone());
+
+    // Not every job has a status cache entry; fall back to the enqueue record
+    let status = state
+        .progress
+        .get_job_status(&job_id_typed)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to get job status: {}", e)))?;
+    let owner = match &status {
+        Some(status) => Some(status.user_id.clone()),
+        None => state
+            .progress
+            .get_job_owner(&job_id_typed)
+            .await
+            .map_err(|e| ApiError::internal(format!("Failed to get job owner: {}", e)))?,
+    };
+    let owner = owner.ok_or_else(|| ApiError::not_found("Job not found"))?;
+
+    // Verify ownership
+    if owner != user.uid {
+        return Err(ApiError::forbidden("Access denied"));
+    }
+
+    let last_seq = last_event_id(&headers).unwrap_or(0);
+
+    // Subscribe before reading history so no event falls between the two
+    let live = state
+        .progress
+        .subscribe(&job_id_typed)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to subscribe to job: {}", e)))?;
+    let history = state
+        .progress
+        .get_history_after_seq(&job_id_typed, last_seq)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to get history: {}", e)))?;
+
+    info!(
+        "stream_job_events uid={} job_id={} last_event_id={} replay={}",
+        user.uid,
+        job_id,
+        last_seq,
+        history.len()
+    );
+
+    // A job that already finished gets its history and nothing more
+    let finished = status.is_some_and(|s| s.is_terminal())
+        || history.iter().any(|e| is_final_message(&e.message));
+    let cursor = EventCursor {
+        replay: history.into(),
+        live: (!finished).then_some(live),
+        last_seq,
+        done: false,
+    };
+
+    let stream = futures_util::stream::unfold(cursor, EventCursor::next)
+        .take_until(tokio::time::sleep(SSE_MAX_DURATION));
+
+    Ok(Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(SSE_HEARTBEAT_INTERVAL)
+            .text("heartbeat"),
+    ))
+}
+
+/// Replays history, then follows live events, skipping anything already sent.
+struct EventCursor {
+    replay: VecDeque<ProgressEvent>,
+    live: Option<JobEventStream>,
+    last_seq: u64,
+    done: bool,
+}
+
+impl EventCursor {
+    async fn next(mut self) -> Option<(Result<Event, Infallible>, Self)> {
+        loop {
+            if self.done {
+                return None;
+            }
+
+            let event = match self.replay.pop_front() {
+                Some(event) => event,
+                None => self.live.as_mut()?.next().await?,
+            };
+
+            // Live events can overlap the replayed history
+            if event.seq != 0 && event.seq <= self.last_seq {
+                continue;
+            }
+            self.last_seq = self.last_seq.max(event.seq);
+            self.done = is_final_message(&event.message);
+
+            if let Some(sse_event) = sse_event(&event) {
+                return Some((Ok(sse_event), self));
+            }
+        }
+    }
+}
+
+/// Job cancellation response.
+#[derive(Debug, Serialize)]
+pub struct CancelJobResponse {
+    pub job_id: String,
+    /// Always "cancelling"; the job turns "cancelled" once the worker stops it
+    pub status: String,
+}
+
+/// POST /api/jobs/:job_id/cancel
+///
+/// Request cancellation of a queued or running job.
+///
+/// The worker stops in-flight rendering and downloads within a few seconds,
+/// removes partial outputs and refunds credits reserved for clips that were
+/// not delivered. Clips already finished are kept.
+///
+/// Returns:
+/// - 200: Cancellation requested
+/// - 401: Not authenticated
+/// - 403: Job belongs to another user
+/// - 404: Job not found
+/// - 409: Job already finished
+pub async fn cancel_job(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+    user: AuthUser,
+) -> ApiResult<Json<CancelJobResponse>> {
+    // Validate job ID format
+    if !is_valid_job_id(&job_id) {
+        return Err(ApiError::bad_request("Invalid job ID format"));
+    }
+
+    let job_id_typed = JobId::from(job_id.clone());
+
+    let status = state
+        .progress
+        .get_job_status(&job_id_typed)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to get job status: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Job not found"))?;
+
+    // Verify ownership
+    if status.user_id != user.uid {
+        return Err(ApiError::forbidden("Access denied"));
+    }
+
+    if status.is_terminal() {
+        return Err(ApiError::Conflict(format!(
+            "Job is already {}",
+            status.status.as_str()
+        )));
+    }
+
+    state
+        .progress
+        .request_cancel(&job_id_typed)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to cancel job: {}", e)))?;
+
+    info!(
+        "cancel_job uid={} job_id={} video_id={}",
+        user.uid, job_id, status.video_id
+    );
+
+    Ok(Json(CancelJobResponse {
+        job_id,
+        status: "cancelling".to_string(),
+    }))
+}
+
 /// Query parameters for job history endpoint.
 #[derive(Debug, Deserialize)]
 pub struct GetJobHistoryQuery {
@@ -276,6 +483,47 @@ pub struct JobHistoryResponse {
 // Helpers
 // ============================================================================
 
+/// Progress event as JSON: the message plus its timestamp and sequence number.
+fn event_json(event: &ProgressEvent) -> Option<serde_json::Value> {
+    let mut value = serde_json::to_value(&event.message).ok()?;
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("timestamp_ms".to_string(), serde_json::json!(event.timestamp_ms));
+        obj.insert("seq".to_string(), serde_json::json!(event.seq));
+    }
+    Some(value)
+}
+
+/// Progress event as an SSE event named after the message type.
+fn sse_event(event: &ProgressEvent) -> Option<Event> {
+    let value = event_json(event)?;
+    let name = value.get("type").and_then(|t| t.as_str()).unwrap_or("message");
+    Some(
+        Event::default()
+            .id(event.seq.to_string())
+            .event(name)
+            .data(value.to_string()),
+    )
+}
+
+/// Whether a message is the last one a job publishes.
+fn is_final_message(message: &WsMessage) -> bool {
+    matches!(
+        message,
+        WsMessage::Done { .. } | WsMessage::Error { .. } | WsMessage::Cancelled { .. }
+    )
+}
+
+/// Sequence number from the `Last-Event-ID` header.
+fn last_event_id(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get("last-event-id")?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
 /// Validate job ID format to prevent injection attacks.
 ///
 /// Valid format: alphanumeric characters and hyphens only, 8-64 chars.
@@ -307,4 +555,30 @@ mod tests {
         assert!(!is_valid_job_id("has.dot"));
         assert!(!is_valid_job_id(&"a".repeat(65)));
     }
+
+    #[test]
+    fn test_last_event_id() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(last_event_id(&headers), None);
+
+        headers.insert("last-event-id", " 42 ".parse().unwrap());
+        assert_eq!(last_event_id(&headers), Some(42));
+
+        headers.insert("last-event-id", "abc".parse().unwrap());
+        assert_eq!(last_event_id(&headers), None);
+    }
+
+    #[test]
+    fn test_event_json_includes_seq() {
+        let event = ProgressEvent::new(JobId::from("job-12345"), WsMessage::progress(40)).with_seq(7);
+        let value = event_json(&event).unwrap();
+        assert_eq!(value["type"], "progress");
+        assert_eq!(value["value"], 40);
+        assert_eq!(value["seq"], 7);
+
+        assert!(!is_final_message(&event.message));
+        assert!(is_final_message(&WsMessage::done("video-1")));
+        assert!(is_final_message(&WsMessage::error("boom")));
+        assert!(is_final_message(&WsMessage::cancelled(None)));
+    }
 }
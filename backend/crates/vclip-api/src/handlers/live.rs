@@ -0,0 +1,169 @@
+//! Live-stream capture API handlers.
+//!
+//! A live capture records a running broadcast and publishes draft scenes
+//! while it is still going:
+//! 1. Start: create a `live` draft and enqueue the capture job
+//! 2. Poll `/analyze/:draft_id/status` or the draft for new scenes; they can
+//!    be processed like any other draft scene while recording continues
+//! 3. Stop: end the capture early (it also ends with the stream)
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use vclip_firestore::AnalysisDraftRepository;
+use vclip_models::{
+    AnalysisDraft, HighlightDetection, JobId, LiveCapture, LiveCaptureMode, StartAnalysisResponse,
+};
+use vclip_queue::CaptureLiveJob;
+use vclip_storage::live_manifest_key;
+
+use crate::auth::AuthUser;
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::analysis::get_draft_ttl;
+use crate::security::{sanitize_string, validate_live_url};
+use crate::state::AppState;
+
+/// Request to start a live capture.
+#[derive(Debug, Deserialize)]
+pub struct StartLiveCaptureRequest {
+    /// Live stream or Twitch/Kick channel URL
+    pub url: String,
+    /// Optional AI instructions
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// How highlights are detected (transcript, signals or fused)
+    #[serde(default)]
+    pub highlight_detection: HighlightDetection,
+    /// Record from the start of the broadcast or from now
+    #[serde(default)]
+    pub live_capture_mode: LiveCaptureMode,
+}
+
+/// Response after requesting a capture stop.
+#[derive(Serialize)]
+pub struct StopLiveCaptureResponse {
+    pub draft_id: String,
+    pub job_id: String,
+    pub status: String,
+}
+
+/// POST /api/analyze/live
+///
+/// Start recording a live stream into a new analysis draft.
+///
+/// Returns:
+/// - 200: Capture queued (`job_id` for progress, `draft_id` for scenes)
+/// - 400: Invalid URL
+/// - 403: Live capture requires a Pro or Studio plan
+pub async fn start_live_capture(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(request): Json<StartLiveCaptureRequest>,
+) -> ApiResult<Json<StartAnalysisResponse>> {
+    if !state.user_service.has_pro_or_studio_plan(&user.uid).await? {
+        return Err(ApiError::forbidden(
+            "Live stream capture requires a Pro or Studio plan. Please upgrade to access this feature.",
+        ));
+    }
+
+    let validated_url = validate_live_url(&request.url)
+        .into_result()
+        .map_err(ApiError::bad_request)?;
+
+    let prompt = request.prompt.as_ref().map(|p| sanitize_string(p));
+    let ttl_days = get_draft_ttl(&state, &user.uid).await;
+
+    let draft_id = Uuid::new_v4().to_string();
+    let request_id = Uuid::new_v4().to_string();
+
+    let mut job = CaptureLiveJob::new(&user.uid, &draft_id, &validated_url)
+        .with_capture_mode(request.live_capture_mode)
+        .with_highlight_detection(request.highlight_detection);
+    if let Some(ref p) = prompt {
+        job = job.with_prompt(p);
+    }
+    let job_id = job.job_id.to_string();
+
+    let live_capture = LiveCapture::new(
+        &job_id,
+        request.live_capture_mode,
+        live_manifest_key(&user.uid, &draft_id),
+    );
+    let mut draft = AnalysisDraft::new(&draft_id, &user.uid, &validated_url, ttl_days)
+        .with_request_id(&request_id)
+        .with_live_capture(live_capture);
+    if let Some(ref p) = prompt {
+        draft = draft.with_prompt(p);
+    }
+
+    let draft_repo = AnalysisDraftRepository::new((*state.firestore).clone(), &user.uid);
+    draft_repo.create(&draft).await.map_err(|e| {
+        warn!("Failed to create live capture draft: {}", e);
+        ApiError::internal("Failed to create analysis draft")
+    })?;
+
+    state.queue.enqueue_capture_live(job).await.map_err(|e| {
+        warn!("Failed to enqueue live capture job: {}", e);
+        ApiError::internal("Failed to start live capture")
+    })?;
+
+    info!(
+        "Started live capture job {} for user {} (draft: {})",
+        job_id, user.uid, draft_id
+    );
+
+    Ok(Json(StartAnalysisResponse { job_id, draft_id }))
+}
+
+/// POST /api/drafts/:draft_id/live/stop
+///
+/// Stop a live capture. The worker uploads the footage recorded so far,
+/// analyzes the remainder and completes the draft within a few seconds.
+///
+/// Returns:
+/// - 200: Stop requested
+/// - 404: Draft not found or not a live capture
+/// - 409: Capture already ended
+pub async fn stop_live_capture(
+    State(state): State<AppState>,
+    Path(draft_id): Path<String>,
+    user: AuthUser,
+) -> ApiResult<Json<StopLiveCaptureResponse>> {
+    let draft_repo = AnalysisDraftRepository::new((*state.firestore).clone(), &user.uid);
+    let draft = draft_repo
+        .get(&draft_id)
+        .await
+        .map_err(|e| {
+            warn!("Failed to get analysis draft: {}", e);
+            ApiError::internal("Failed to stop live capture")
+        })?
+        .filter(|d| d.user_id == user.uid)
+        .ok_or_else(|| ApiError::not_found("Draft not found"))?;
+
+    let live = draft
+        .live_capture
+        .ok_or_else(|| ApiError::not_found("Draft is not a live capture"))?;
+    if !live.is_active() {
+        return Err(ApiError::Conflict("Live capture has already ended".to_string()));
+    }
+
+    state
+        .progress
+        .request_cancel(&JobId::from(live.job_id.clone()))
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to stop live capture: {}", e)))?;
+
+    info!(
+        "stop_live_capture uid={} draft_id={} job_id={}",
+        user.uid, draft_id, live.job_id
+    );
+
+    Ok(Json(StopLiveCaptureResponse {
+        draft_id,
+        job_id: live.job_id,
+        status: "stopping".to_string(),
+    }))
+}
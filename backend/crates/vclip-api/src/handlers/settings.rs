@@ -124,6 +124,9 @@ pub async fn get_settings(
     if let Some(aspect) = settings.default_target_aspect {
         settings_map.insert("default_target_aspect".to_string(), serde_json::json!(aspect));
     }
+    if let Some(provider) = settings.highlight_provider {
+        settings_map.insert("highlight_provider".to_string(), serde_json::json!(provider));
+    }
 
     // Extract values from limits before building the response
     let plan_id = limits.plan_id.clone();
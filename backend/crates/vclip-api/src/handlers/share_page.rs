@@ -0,0 +1,516 @@
+//! Embeddable player page and oEmbed metadata for shared clips.
+//!
+//! Browsers and link unfurlers (Slack, Discord, Notion, ...) opening
+//! `/c/{share_slug}` get a small HTML page with OpenGraph/Twitter card tags
+//! and a `<video>` player. Everything else, and `?raw=1`, keeps the redirect
+//! to the clip itself. The page does not count a view; the player's request
+//! for the raw clip does.
+
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use vclip_firestore::ShareSlugIndex;
+use vclip_models::encoding::THUMBNAIL_SCALE_WIDTH;
+use vclip_models::{ClipMetadata, Style};
+use vclip_storage::{DeliveryConfig, DeliveryUrlGenerator};
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::clip_delivery::{
+    find_clip_by_owner_context, load_available_share, public_app_url,
+};
+use crate::state::AppState;
+
+/// Provider name shown by unfurlers.
+const PROVIDER_NAME: &str = "ViralClip AI";
+
+/// User agents of link unfurlers, which often send `Accept: */*`.
+const UNFURLER_AGENTS: &[&str] = &[
+    "slackbot",
+    "discordbot",
+    "twitterbot",
+    "facebookexternalhit",
+    "linkedinbot",
+    "notion",
+    "whatsapp",
+    "telegrambot",
+    "skypeuripreview",
+    "embedly",
+    "iframely",
+];
+
+/// Whether a share request should get the player page instead of a redirect.
+pub fn wants_html(headers: &HeaderMap) -> bool {
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    if accept.contains("text/html") || accept.contains("application/xhtml+xml") {
+        return true;
+    }
+
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    UNFURLER_AGENTS.iter().any(|bot| user_agent.contains(bot))
+}
+
+/// Player size for a clip: landscape for the original aspect, otherwise
+/// the 9:16 portrait every other style renders.
+fn player_size(clip: &ClipMetadata) -> (u32, u32) {
+    match clip.style.parse::<Style>() {
+        Ok(Style::Original) => (640, 360),
+        _ => (360, 640),
+    }
+}
+
+/// Scale `(width, height)` down to fit the oEmbed `maxwidth`/`maxheight`.
+fn fit_size(size: (u32, u32), max_width: Option<u32>, max_height: Option<u32>) -> (u32, u32) {
+    let (width, height) = size;
+    let mut scale = 1.0f64;
+    if let Some(max) = max_width.filter(|m| *m > 0 && *m < width) {
+        scale = scale.min(max as f64 / width as f64);
+    }
+    if let Some(max) = max_height.filter(|m| *m > 0 && *m < height) {
+        scale = scale.min(max as f64 / height as f64);
+    }
+    (
+        ((width as f64 * scale).floor() as u32).max(1),
+        ((height as f64 * scale).floor() as u32).max(1),
+    )
+}
+
+/// Escape text for HTML element content and double-quoted attributes.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Public details of a shared clip.
+///
+/// Protected shares (passcode or allowed viewers) expose nothing about the
+/// clip until unlocked, so unfurlers only see a generic title.
+struct SharePreview {
+    title: String,
+    description: Option<String>,
+    duration_seconds: Option<f64>,
+    thumbnail_url: Option<String>,
+    /// Thumbnail URL lifetime, also used as the oEmbed cache age
+    thumbnail_expires_in_secs: Option<u64>,
+    size: (u32, u32),
+    passcode_required: bool,
+    sign_in_required: bool,
+    /// Whether unfurlers may fetch the video (would use up limited views)
+    expose_video: bool,
+}
+
+async fn load_preview(state: &AppState, slug_info: &ShareSlugIndex) -> ApiResult<SharePreview> {
+    let clip = find_clip_by_owner_context(
+        state,
+        &slug_info.user_id,
+        &slug_info.video_id,
+        &slug_info.clip_id,
+    )
+    .await?;
+    let size = player_size(&clip);
+
+    if slug_info.requires_unlock() {
+        return Ok(SharePreview {
+            title: "Protected clip".to_string(),
+            description: None,
+            duration_seconds: None,
+            thumbnail_url: None,
+            thumbnail_expires_in_secs: None,
+            size,
+            passcode_required: slug_info.passcode_hash.is_some(),
+            sign_in_required: !slug_info.allowed_viewers.is_empty(),
+            expose_video: false,
+        });
+    }
+
+    // Same short-lived URL as get_thumbnail_url; unfurlers fetch it right away
+    let thumbnail = match &clip.thumbnail_r2_key {
+        Some(thumb_key) => {
+            let generator =
+                DeliveryUrlGenerator::new((*state.storage).clone(), DeliveryConfig::from_env());
+            generator
+                .thumbnail_url(thumb_key, &clip.clip_id, &slug_info.user_id)
+                .await
+                .map_err(|e| {
+                    warn!(share_slug = %slug_info.share_slug, error = %e, "Failed to generate thumbnail URL");
+                })
+                .ok()
+        }
+        None => None,
+    };
+
+    Ok(SharePreview {
+        title: clip.scene_title.clone(),
+        description: clip.scene_description.clone(),
+        duration_seconds: Some(clip.duration_seconds),
+        thumbnail_expires_in_secs: thumbnail.as_ref().map(|t| t.expires_in_secs),
+        thumbnail_url: thumbnail.map(|t| t.url),
+        size,
+        passcode_required: false,
+        sign_in_required: false,
+        expose_video: slug_info.max_views.is_none(),
+    })
+}
+
+/// Render the player page for an available share.
+///
+/// `embed` renders the bare player used inside oEmbed iframes.
+pub async fn render_share_page(
+    state: &AppState,
+    slug_info: &ShareSlugIndex,
+    embed: bool,
+) -> ApiResult<Response> {
+    let preview = load_preview(state, slug_info).await?;
+    let share_url = format!("{}/c/{}", public_app_url(), slug_info.share_slug);
+    let nonce = uuid::Uuid::new_v4().simple().to_string();
+    let html = share_page_html(&preview, &slug_info.share_slug, &share_url, &nonce, embed);
+
+    // The page may be framed anywhere (oEmbed); the CSP overrides the
+    // default X-Frame-Options: DENY
+    let csp = format!(
+        "default-src 'none'; img-src 'self' https: data:; media-src 'self' https: blob:; \
+         style-src 'nonce-{nonce}'; script-src 'nonce-{nonce}'; connect-src 'self'; \
+         frame-ancestors *; base-uri 'none'; form-action 'none'"
+    );
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/html; charset=utf-8".to_string()),
+            // Thumbnail URLs in the page are short-lived
+            (header::CACHE_CONTROL, "private, max-age=60".to_string()),
+            (header::CONTENT_SECURITY_POLICY, csp),
+            (header::VARY, "Accept, User-Agent".to_string()),
+        ],
+        html,
+    )
+        .into_response())
+}
+
+fn share_page_html(
+    preview: &SharePreview,
+    share_slug: &str,
+    share_url: &str,
+    nonce: &str,
+    embed: bool,
+) -> String {
+    let title = escape_html(&preview.title);
+    let share_url_attr = escape_html(share_url);
+    let raw_url = format!("/c/{}?raw=1", share_slug);
+    let oembed_url = format!(
+        "{}/oembed?url={}",
+        public_app_url(),
+        url::form_urlencoded::byte_serialize(share_url.as_bytes()).collect::<String>()
+    );
+    let (width, height) = preview.size;
+
+    let mut meta = vec![
+        format!(r#"<meta property="og:site_name" content="{}">"#, PROVIDER_NAME),
+        r#"<meta property="og:type" content="video.other">"#.to_string(),
+        format!(r#"<meta property="og:title" content="{}">"#, title),
+        format!(r#"<meta property="og:url" content="{}">"#, share_url_attr),
+        format!(r#"<meta name="twitter:title" content="{}">"#, title),
+        format!(
+            r#"<link rel="alternate" type="application/json+oembed" href="{}" title="{}">"#,
+            escape_html(&oembed_url),
+            title
+        ),
+    ];
+    if let Some(description) = &preview.description {
+        let description = escape_html(description);
+        meta.push(format!(r#"<meta property="og:description" content="{}">"#, description));
+        meta.push(format!(r#"<meta name="twitter:description" content="{}">"#, description));
+    }
+    if let Some(duration) = preview.duration_seconds {
+        meta.push(format!(
+            r#"<meta property="video:duration" content="{}">"#,
+            duration.round() as u64
+        ));
+    }
+    if let Some(thumbnail) = &preview.thumbnail_url {
+        let thumbnail = escape_html(thumbnail);
+        meta.push(format!(r#"<meta property="og:image" content="{}">"#, thumbnail));
+        meta.push(format!(r#"<meta name="twitter:image" content="{}">"#, thumbnail));
+    }
+    if preview.expose_video {
+        let video_url = escape_html(&format!("{}?raw=1", share_url));
+        meta.push(format!(r#"<meta property="og:video" content="{}">"#, video_url));
+        meta.push(format!(r#"<meta property="og:video:secure_url" content="{}">"#, video_url));
+        meta.push(r#"<meta property="og:video:type" content="video/mp4">"#.to_string());
+        meta.push(format!(r#"<meta property="og:video:width" content="{}">"#, width));
+        meta.push(format!(r#"<meta property="og:video:height" content="{}">"#, height));
+        meta.push(r#"<meta name="twitter:card" content="player">"#.to_string());
+        meta.push(format!(
+            r#"<meta name="twitter:player" content="{}">"#,
+            escape_html(&format!("{}?embed=1", share_url))
+        ));
+        meta.push(format!(r#"<meta name="twitter:player:width" content="{}">"#, width));
+        meta.push(format!(r#"<meta name="twitter:player:height" content="{}">"#, height));
+    } else {
+        meta.push(r#"<meta name="twitter:card" content="summary">"#.to_string());
+    }
+
+    let poster = preview
+        .thumbnail_url
+        .as_deref()
+        .map(|t| format!(r#" poster="{}""#, escape_html(t)))
+        .unwrap_or_default();
+    let locked = preview.passcode_required || preview.sign_in_required;
+    let video_src = if locked {
+        String::new()
+    } else {
+        format!(r#" src="{}""#, escape_html(&raw_url))
+    };
+    let heading = if embed {
+        String::new()
+    } else {
+        format!("<h1>{}</h1>", title)
+    };
+
+    let unlock = if preview.passcode_required {
+        format!(
+            r#"<form id="unlock"><input id="passcode" type="password" placeholder="Passcode" autocomplete="off" required><button type="submit">Watch</button><p id="error" hidden></p></form>
+<script nonce="{nonce}">
+document.getElementById("unlock").addEventListener("submit", async (event) => {{
+  event.preventDefault();
+  const error = document.getElementById("error");
+  const response = await fetch("/c/{slug}/unlock", {{
+    method: "POST",
+    headers: {{ "Content-Type": "application/json" }},
+    body: JSON.stringify({{ passcode: document.getElementById("passcode").value }}),
+  }});
+  if (!response.ok) {{
+    error.textContent = response.status === 429 ? "Too many attempts, try again later" : "Incorrect passcode";
+    error.hidden = false;
+    return;
+  }}
+  const data = await response.json();
+  document.getElementById("unlock").hidden = true;
+  const video = document.querySelector("video");
+  video.src = data.url;
+  video.hidden = false;
+  video.play().catch(() => {{}});
+}});
+</script>"#,
+            nonce = nonce,
+            slug = share_slug,
+        )
+    } else if preview.sign_in_required {
+        format!(
+            r#"<p>This clip is shared with specific people. <a href="{}" target="_top">Open it in {}</a> while signed in.</p>"#,
+            share_url_attr, PROVIDER_NAME
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="robots" content="noindex">
+<title>{title}</title>
+{meta}
+<style nonce="{nonce}">
+html, body {{ margin: 0; height: 100%; background: #000; color: #fff; font-family: system-ui, sans-serif; }}
+main {{ display: flex; flex-direction: column; align-items: center; justify-content: center; gap: 12px; height: 100%; }}
+video {{ max-width: 100%; max-height: {max_height}; aspect-ratio: {width} / {height}; background: #000; }}
+h1 {{ font-size: 16px; font-weight: 500; margin: 0 16px; text-align: center; }}
+form {{ display: flex; gap: 8px; flex-wrap: wrap; justify-content: center; }}
+input, button {{ font: inherit; padding: 8px 12px; border-radius: 6px; border: 1px solid #444; }}
+a {{ color: #8ab4f8; }}
+</style>
+</head>
+<body>
+<main>
+<video controls playsinline preload="metadata"{poster}{video_src}{hidden}></video>
+{heading}
+{unlock}
+</main>
+</body>
+</html>
+"#,
+        title = title,
+        meta = meta.join("\n"),
+        nonce = nonce,
+        max_height = if embed { "100%" } else { "85vh" },
+        width = width,
+        height = height,
+        poster = poster,
+        video_src = video_src,
+        hidden = if locked { " hidden" } else { "" },
+        heading = heading,
+        unlock = unlock,
+    )
+}
+
+// ============================================================================
+// oEmbed
+// ============================================================================
+
+/// oEmbed query parameters.
+#[derive(Debug, Deserialize)]
+pub struct OEmbedQuery {
+    /// Share URL (`{PUBLIC_APP_URL}/c/{share_slug}`)
+    pub url: String,
+    pub maxwidth: Option<u32>,
+    pub maxheight: Option<u32>,
+    /// Only `json` is supported
+    pub format: Option<String>,
+}
+
+/// oEmbed `video` response.
+#[derive(Debug, Serialize)]
+pub struct OEmbedResponse {
+    pub version: &'static str,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub provider_name: &'static str,
+    pub provider_url: String,
+    pub title: String,
+    pub html: String,
+    pub width: u32,
+    pub height: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_age: Option<u64>,
+}
+
+/// Share slug of a share URL (`.../c/{share_slug}`).
+fn share_slug_from_url(share_url: &str) -> Option<String> {
+    let parsed = url::Url::parse(share_url).ok()?;
+    let mut segments = parsed.path_segments()?.filter(|s| !s.is_empty());
+    match (segments.next(), segments.next(), segments.next()) {
+        (Some("c"), Some(slug), None) => Some(slug.to_string()),
+        _ => None,
+    }
+}
+
+/// oEmbed endpoint for share links.
+///
+/// GET /oembed?url={share_url}&maxwidth=..&maxheight=..
+///
+/// Returns a `video` embed whose iframe shows the player page.
+pub async fn oembed(
+    State(state): State<AppState>,
+    Query(query): Query<OEmbedQuery>,
+) -> ApiResult<Response> {
+    if query.format.as_deref().is_some_and(|f| f != "json") {
+        return Ok((StatusCode::NOT_IMPLEMENTED, "Only the json format is supported").into_response());
+    }
+
+    let share_slug = share_slug_from_url(&query.url)
+        .ok_or_else(|| ApiError::not_found("Not a share link"))?;
+    let slug_info = load_available_share(&state, &share_slug).await?;
+    let preview = load_preview(&state, &slug_info).await?;
+
+    let base_url = public_app_url();
+    let share_url = format!("{}/c/{}", base_url, share_slug);
+    let (width, height) = fit_size(preview.size, query.maxwidth, query.maxheight);
+    let html = format!(
+        r#"<iframe src="{}" width="{}" height="{}" frameborder="0" allow="autoplay; fullscreen; picture-in-picture" allowfullscreen title="{}"></iframe>"#,
+        escape_html(&format!("{}?embed=1", share_url)),
+        width,
+        height,
+        escape_html(&preview.title)
+    );
+
+    // Thumbnails keep the clip's aspect ratio at a fixed width
+    let (player_width, player_height) = preview.size;
+    let thumbnail_height = THUMBNAIL_SCALE_WIDTH * player_height / player_width;
+    let has_thumbnail = preview.thumbnail_url.is_some();
+
+    let response = OEmbedResponse {
+        version: "1.0",
+        kind: "video",
+        provider_name: PROVIDER_NAME,
+        provider_url: base_url,
+        title: preview.title,
+        html,
+        width,
+        height,
+        thumbnail_url: preview.thumbnail_url,
+        thumbnail_width: has_thumbnail.then_some(THUMBNAIL_SCALE_WIDTH),
+        thumbnail_height: has_thumbnail.then_some(thumbnail_height),
+        cache_age: preview.thumbnail_expires_in_secs,
+    };
+
+    Ok(Json(response).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wants_html() {
+        let mut headers = HeaderMap::new();
+        assert!(!wants_html(&headers));
+
+        headers.insert(header::ACCEPT, "video/mp4,*/*".parse().unwrap());
+        assert!(!wants_html(&headers));
+
+        headers.insert(header::USER_AGENT, "Slackbot-LinkExpanding 1.0".parse().unwrap());
+        assert!(wants_html(&headers));
+
+        let mut browser = HeaderMap::new();
+        browser.insert(
+            header::ACCEPT,
+            "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8".parse().unwrap(),
+        );
+        assert!(wants_html(&browser));
+    }
+
+    #[test]
+    fn test_share_slug_from_url() {
+        assert_eq!(
+            share_slug_from_url("https://www.viralclipai.io/c/abcd1234efgh"),
+            Some("abcd1234efgh".to_string())
+        );
+        assert_eq!(
+            share_slug_from_url("https://www.viralclipai.io/c/abcd1234efgh/?raw=1"),
+            Some("abcd1234efgh".to_string())
+        );
+        assert_eq!(share_slug_from_url("https://www.viralclipai.io/videos/abc"), None);
+        assert_eq!(share_slug_from_url("not a url"), None);
+    }
+
+    #[test]
+    fn test_fit_size_and_escape() {
+        assert_eq!(fit_size((360, 640), None, None), (360, 640));
+        assert_eq!(fit_size((360, 640), Some(180), None), (180, 320));
+        assert_eq!(fit_size((640, 360), Some(600), Some(180)), (320, 180));
+        assert_eq!(
+            escape_html(r#"<b>"Tom" & 'Jerry'</b>"#),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+        );
+    }
+}
@@ -0,0 +1,482 @@
+//! Local file upload API handlers.
+//!
+//! Uploads go straight from the client to R2 as a multipart upload:
+//! 1. Start: record the upload and get its part layout
+//! 2. Parts: request presigned URLs and PUT each part (in any order, retrying
+//!    or resuming as needed; `GET /uploads/:id` lists the parts already stored)
+//! 3. Complete: assemble the parts and start an analysis of the file (safe
+//!    to retry; a failed completion resumes where it stopped)
+//!
+//! Uploaded files count toward the user's storage quota, which is checked
+//! when the upload starts and again when it completes.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use vclip_firestore::{AnalysisDraftRepository, StorageAccountingRepository, UploadRepository};
+use vclip_models::{
+    upload_title, validate_upload, AnalysisDraft, HighlightDetection, JobId, SourceUpload,
+    StartAnalysisResponse, UploadStatus,
+};
+use vclip_queue::{AnalyzeVideoJob, QueueError};
+use vclip_storage::UploadedPart;
+
+use crate::auth::AuthUser;
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::analysis::get_draft_ttl;
+use crate::security::sanitize_string;
+use crate::state::AppState;
+
+/// Most part URLs handed out per request.
+const MAX_PART_URLS_PER_REQUEST: usize = 100;
+
+/// Lifetime of a presigned part URL.
+const PART_URL_EXPIRY: Duration = Duration::from_secs(3600);
+
+/// An upload as returned by the API.
+#[derive(Serialize)]
+pub struct UploadInfo {
+    pub id: String,
+    pub filename: String,
+    pub size_bytes: u64,
+    pub part_size_bytes: u64,
+    pub part_count: u32,
+    pub status: UploadStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
+    pub created_at: String,
+}
+
+impl From<SourceUpload> for UploadInfo {
+    fn from(upload: SourceUpload) -> Self {
+        Self {
+            id: upload.id,
+            filename: upload.filename,
+            size_bytes: upload.size_bytes,
+            part_size_bytes: upload.part_size_bytes,
+            part_count: upload.part_count,
+            status: upload.status,
+            draft_id: upload.draft_id,
+            job_id: upload.job_id,
+            created_at: upload.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Request to start an upload.
+#[derive(Debug, Deserialize)]
+pub struct CreateUploadRequest {
+    /// Name of the file being uploaded
+    pub filename: String,
+    /// Exact file size in bytes
+    pub size_bytes: u64,
+}
+
+/// Start a multipart upload of a local video file.
+///
+/// The response gives the part layout; the client splits the file into
+/// `part_count` parts of `part_size_bytes` (the last may be shorter).
+/// Fails with 403 if the file does not fit in the user's storage quota.
+pub async fn create_upload(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(request): Json<CreateUploadRequest>,
+) -> ApiResult<Json<UploadInfo>> {
+    let filename = validate_upload(&request.filename, request.size_bytes).map_err(ApiError::bad_request)?;
+    state
+        .user_service
+        .check_storage_quota(&user.uid, request.size_bytes)
+        .await?;
+
+    let upload_id = Uuid::new_v4().to_string();
+    let mut upload = SourceUpload::new(&upload_id, &user.uid, filename, request.size_bytes, "");
+    upload.multipart_upload_id = state
+        .storage
+        .create_multipart_upload(&upload.r2_key, &upload.content_type)
+        .await?;
+
+    let repo = UploadRepository::new((*state.firestore).clone(), &user.uid);
+    if let Err(e) = repo.create(&upload).await {
+        if let Err(abort_err) = state
+            .storage
+            .abort_multipart_upload(&upload.r2_key, &upload.multipart_upload_id)
+            .await
+        {
+            warn!("Failed to abort orphaned upload {}: {}", upload.id, abort_err);
+        }
+        return Err(e.into());
+    }
+
+    Ok(Json(upload.into()))
+}
+
+/// A part already stored in R2.
+#[derive(Serialize)]
+pub struct UploadedPartInfo {
+    pub part_number: u32,
+    pub size_bytes: u64,
+}
+
+/// Upload status with the parts stored so far.
+#[derive(Serialize)]
+pub struct UploadStatusResponse {
+    #[serde(flatten)]
+    pub upload: UploadInfo,
+    /// Parts stored so far (pending uploads only)
+    pub uploaded_parts: Vec<UploadedPartInfo>,
+}
+
+/// Get an upload and the parts stored so far.
+///
+/// Clients resuming an interrupted upload only send the missing parts.
+pub async fn get_upload(
+    State(state): State<AppState>,
+    Path(upload_id): Path<String>,
+    user: AuthUser,
+) -> ApiResult<Json<UploadStatusResponse>> {
+    let upload = get_user_upload(&state, &user, &upload_id).await?;
+
+    let uploaded_parts = if upload.status == UploadStatus::Pending {
+        state
+            .storage
+            .list_parts(&upload.r2_key, &upload.multipart_upload_id)
+            .await?
+            .into_iter()
+            .map(|p| UploadedPartInfo {
+                part_number: p.part_number,
+                size_bytes: p.size,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(Json(UploadStatusResponse {
+        upload: upload.into(),
+        uploaded_parts,
+    }))
+}
+
+/// Request for part upload URLs.
+#[derive(Debug, Deserialize)]
+pub struct UploadPartUrlsRequest {
+    /// 1-based part numbers to upload
+    pub part_numbers: Vec<u32>,
+}
+
+/// A presigned URL for one part.
+#[derive(Serialize)]
+pub struct UploadPartUrl {
+    pub part_number: u32,
+    /// Expected size of the part in bytes
+    pub size_bytes: u64,
+    /// URL to PUT the part's bytes to
+    pub url: String,
+}
+
+/// Response with part upload URLs.
+#[derive(Serialize)]
+pub struct UploadPartUrlsResponse {
+    pub parts: Vec<UploadPartUrl>,
+    pub expires_in_secs: u64,
+}
+
+/// Get presigned URLs for uploading parts.
+pub async fn get_upload_part_urls(
+    State(state): State<AppState>,
+    Path(upload_id): Path<String>,
+    user: AuthUser,
+    Json(request): Json<UploadPartUrlsRequest>,
+) -> ApiResult<Json<UploadPartUrlsResponse>> {
+    let upload = get_pending_upload(&state, &user, &upload_id).await?;
+
+    let mut part_numbers = request.part_numbers;
+    part_numbers.sort_unstable();
+    part_numbers.dedup();
+    if part_numbers.is_empty() || part_numbers.len() > MAX_PART_URLS_PER_REQUEST {
+        return Err(ApiError::bad_request(format!(
+            "Request between 1 and {} parts at a time",
+            MAX_PART_URLS_PER_REQUEST
+        )));
+    }
+
+    let mut parts = Vec::with_capacity(part_numbers.len());
+    for part_number in part_numbers {
+        let size_bytes = upload.part_size(part_number).ok_or_else(|| {
+            ApiError::bad_request(format!(
+                "Part {} is out of range (1-{})",
+                part_number, upload.part_count
+            ))
+        })?;
+        let url = state
+            .storage
+            .presign_upload_part(&upload.r2_key, &upload.multipart_upload_id, part_number, PART_URL_EXPIRY)
+            .await?;
+        parts.push(UploadPartUrl {
+            part_number,
+            size_bytes,
+            url,
+        });
+    }
+
+    Ok(Json(UploadPartUrlsResponse {
+        parts,
+        expires_in_secs: PART_URL_EXPIRY.as_secs(),
+    }))
+}
+
+/// Request to complete an upload and analyze it.
+#[derive(Debug, Default, Deserialize)]
+pub struct CompleteUploadRequest {
+    /// Optional AI instructions
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// How highlights are detected (transcript, signals or fused)
+    #[serde(default)]
+    pub highlight_detection: HighlightDetection,
+}
+
+/// Complete an upload and start analyzing the file.
+///
+/// Fails with a conflict listing the missing parts if any part is not
+/// stored yet or has the wrong size, and with 403 if the file no longer fits
+/// in the user's storage quota (the upload stays pending).
+///
+/// Completion is idempotent: the draft and job IDs are recorded before the
+/// parts are assembled, so a retry after a partial failure resumes with the
+/// same draft and job instead of assembling or counting the file twice.
+pub async fn complete_upload(
+    State(state): State<AppState>,
+    Path(upload_id): Path<String>,
+    user: AuthUser,
+    Json(request): Json<CompleteUploadRequest>,
+) -> ApiResult<Json<StartAnalysisResponse>> {
+    let mut upload = get_user_upload(&state, &user, &upload_id).await?;
+    if !matches!(upload.status, UploadStatus::Pending | UploadStatus::Completing) {
+        return Err(ApiError::Conflict(format!(
+            "Upload is already {}",
+            upload.status.as_str()
+        )));
+    }
+    let repo = UploadRepository::new((*state.firestore).clone(), &user.uid);
+
+    // Assembling the parts consumes the multipart upload; once the file
+    // exists a retry only has to start the analysis
+    let assembled = upload.status == UploadStatus::Completing
+        && state.storage.object_size(&upload.r2_key).await?.is_some();
+
+    if !assembled {
+        let parts = state
+            .storage
+            .list_parts(&upload.r2_key, &upload.multipart_upload_id)
+            .await?;
+        let missing = missing_parts(&upload, &parts);
+        if !missing.is_empty() {
+            return Err(ApiError::Conflict(format!(
+                "Upload is incomplete; missing or wrong-sized parts: {}",
+                missing
+                    .iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        // Other uploads or clips may have used up the quota since the upload started
+        state
+            .user_service
+            .check_storage_quota(&user.uid, upload.size_bytes)
+            .await?;
+
+        if upload.status == UploadStatus::Pending {
+            upload.status = UploadStatus::Completing;
+            upload.draft_id = Some(Uuid::new_v4().to_string());
+            upload.job_id = Some(JobId::new().to_string());
+            repo.update_status(&upload).await?;
+        }
+
+        state
+            .storage
+            .complete_multipart_upload(&upload.r2_key, &upload.multipart_upload_id, &parts)
+            .await?;
+
+        if let Err(e) = StorageAccountingRepository::new((*state.firestore).clone(), &user.uid)
+            .add_uploaded_source(upload.size_bytes)
+            .await
+        {
+            // Storage reconciliation corrects the total from R2
+            warn!(
+                "Failed to update storage accounting for upload {} ({} bytes): {}",
+                upload.id, upload.size_bytes, e
+            );
+        }
+    }
+
+    let (Some(draft_id), Some(job_id)) = (upload.draft_id.clone(), upload.job_id.clone()) else {
+        return Err(ApiError::internal("Upload is missing its draft"));
+    };
+    start_upload_analysis(&state, &user, &upload, &draft_id, &job_id, &request).await?;
+
+    upload.status = UploadStatus::Completed;
+    if let Err(e) = repo.update_status(&upload).await {
+        // The analysis is running; the upload record just lags behind
+        warn!("Failed to mark upload {} completed: {}", upload.id, e);
+    }
+
+    info!(
+        "Completed upload {} ({} bytes) for user {}; started analysis job {} (draft: {})",
+        upload.id, upload.size_bytes, user.uid, job_id, draft_id
+    );
+
+    Ok(Json(StartAnalysisResponse { job_id, draft_id }))
+}
+
+/// Create the draft for an assembled upload and enqueue its analysis.
+///
+/// Skips whichever step an earlier attempt already finished.
+async fn start_upload_analysis(
+    state: &AppState,
+    user: &AuthUser,
+    upload: &SourceUpload,
+    draft_id: &str,
+    job_id: &str,
+    request: &CompleteUploadRequest,
+) -> ApiResult<()> {
+    let prompt = request.prompt.as_ref().map(|p| sanitize_string(p));
+    let source_url = upload.source_url();
+
+    let draft_repo = AnalysisDraftRepository::new((*state.firestore).clone(), &user.uid);
+    let existing = draft_repo.get(draft_id).await.map_err(|e| {
+        warn!("Failed to get analysis draft: {}", e);
+        ApiError::internal("Failed to create analysis draft")
+    })?;
+    if existing.is_none() {
+        let ttl_days = get_draft_ttl(state, &user.uid).await;
+        let mut draft = AnalysisDraft::new(draft_id, &user.uid, &source_url, ttl_days)
+            .with_request_id(Uuid::new_v4().to_string())
+            .with_title(upload_title(&upload.filename))
+            .with_source_r2_key(&upload.r2_key);
+        if let Some(ref p) = prompt {
+            draft = draft.with_prompt(p);
+        }
+
+        draft_repo.create(&draft).await.map_err(|e| {
+            warn!("Failed to create analysis draft: {}", e);
+            ApiError::internal("Failed to create analysis draft")
+        })?;
+    }
+
+    let mut job = AnalyzeVideoJob::new(&user.uid, draft_id, &source_url)
+        .with_highlight_detection(request.highlight_detection)
+        .with_source_r2_key(&upload.r2_key);
+    job.job_id = JobId::from(job_id.to_string());
+    if let Some(p) = prompt {
+        job = job.with_prompt(p);
+    }
+
+    match state.queue.enqueue_analyze(job).await {
+        Ok(_) => Ok(()),
+        // An earlier attempt already queued the analysis
+        Err(QueueError::EnqueueFailed(_)) => Ok(()),
+        Err(e) => {
+            warn!("Failed to enqueue analysis job: {}", e);
+            Err(ApiError::internal("Failed to start analysis"))
+        }
+    }
+}
+
+/// Response for aborting an upload.
+#[derive(Serialize)]
+pub struct AbortUploadResponse {
+    pub success: bool,
+    pub upload_id: String,
+}
+
+/// Abort an upload and discard the parts stored so far.
+pub async fn abort_upload(
+    State(state): State<AppState>,
+    Path(upload_id): Path<String>,
+    user: AuthUser,
+) -> ApiResult<Json<AbortUploadResponse>> {
+    let mut upload = get_pending_upload(&state, &user, &upload_id).await?;
+
+    state
+        .storage
+        .abort_multipart_upload(&upload.r2_key, &upload.multipart_upload_id)
+        .await?;
+
+    upload.status = UploadStatus::Aborted;
+    UploadRepository::new((*state.firestore).clone(), &user.uid)
+        .update_status(&upload)
+        .await?;
+
+    info!(user_id = %user.uid, upload_id = %upload_id, "Aborted upload");
+
+    Ok(Json(AbortUploadResponse {
+        success: true,
+        upload_id,
+    }))
+}
+
+async fn get_user_upload(state: &AppState, user: &AuthUser, upload_id: &str) -> ApiResult<SourceUpload> {
+    UploadRepository::new((*state.firestore).clone(), &user.uid)
+        .get(upload_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Upload not found"))
+}
+
+async fn get_pending_upload(state: &AppState, user: &AuthUser, upload_id: &str) -> ApiResult<SourceUpload> {
+    let upload = get_user_upload(state, user, upload_id).await?;
+    if upload.status != UploadStatus::Pending {
+        return Err(ApiError::Conflict(format!(
+            "Upload is already {}",
+            upload.status.as_str()
+        )));
+    }
+    Ok(upload)
+}
+
+/// Part numbers that are not stored yet or don't have their expected size.
+fn missing_parts(upload: &SourceUpload, parts: &[UploadedPart]) -> Vec<u32> {
+    let stored: HashMap<u32, u64> = parts.iter().map(|p| (p.part_number, p.size)).collect();
+    (1..=upload.part_count)
+        .filter(|n| stored.get(n) != upload.part_size(*n).as_ref())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(part_number: u32, size: u64) -> UploadedPart {
+        UploadedPart {
+            part_number,
+            etag: format!("etag-{}", part_number),
+            size,
+        }
+    }
+
+    #[test]
+    fn test_missing_parts() {
+        let mib = 1024 * 1024;
+        let upload = SourceUpload::new("up1", "u1", "vod.mp4", 150 * mib, "mp1");
+
+        let complete = [part(1, 64 * mib), part(2, 64 * mib), part(3, 22 * mib)];
+        assert!(missing_parts(&upload, &complete).is_empty());
+
+        let partial = [part(1, 64 * mib), part(3, 22 * mib)];
+        assert_eq!(missing_parts(&upload, &partial), vec![2]);
+
+        // A truncated part has to be uploaded again
+        let truncated = [part(1, 64 * mib), part(2, 10 * mib), part(3, 22 * mib)];
+        assert_eq!(missing_parts(&upload, &truncated), vec![2]);
+    }
+}
@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 
 use axum::body::Body;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::{header, HeaderMap, StatusCode};
 use axum::response::Response;
 use axum::Json;
@@ -11,10 +11,11 @@ use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
 use vclip_models::{
-    CreditContext, CreditOperationType, Style, VideoId, AspectRatio, CropMode, DetectionTier,
-    ANALYSIS_CREDIT_COST,
+    CaptionOptions, CreditContext, CreditOperationType, Style, VideoId, AspectRatio, CropMode,
+    DetectionTier, ExportProfile, ResolutionPreset, ANALYSIS_CREDIT_COST, AudioConfig,
+    LoudnessReport, HighlightDetection, QualityMetrics, SceneRankQuery,
 };
-use vclip_queue::ProcessVideoJob;
+use vclip_queue::{ProcessVideoJob, QueueLane};
 
 use crate::auth::AuthUser;
 use crate::error::{ApiError, ApiResult};
@@ -86,6 +87,12 @@ pub struct ClipInfo {
     /// When the clip was last updated (for cache busting)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<String>,
+    /// Export profiles with a downloadable variant of this clip
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub export_profiles: Vec<ExportProfile>,
+    /// Loudness measured by the audio stage, for auditing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loudness: Option<LoudnessReport>,
 }
 
 // ============================================================================
@@ -283,6 +290,8 @@ pub async fn get_video_info(
             has_thumbnail: clip_meta.thumbnail_r2_key.is_some(),
             completed_at: clip_meta.completed_at.map(|dt| dt.to_rfc3339()),
             updated_at: clip_meta.updated_at.map(|dt| dt.to_rfc3339()),
+            export_profiles: clip_meta.variants.iter().map(|v| v.profile).collect(),
+            loudness: clip_meta.loudness,
         });
     }
 
@@ -356,7 +365,7 @@ pub async fn delete_video(
         VideoId::from_string(&video_id),
     );
     let clips = clip_repo.list(None).await.unwrap_or_default();
-    let total_bytes: u64 = clips.iter().map(|c| c.file_size_bytes).sum();
+    let total_bytes: u64 = clips.iter().map(|c| c.total_size_bytes()).sum();
     let clip_count = clips.len() as u32;
 
     // Delete files from R2 (includes styled clips, raw segments, source video, neural cache)
@@ -488,7 +497,7 @@ pub async fn bulk_delete_videos(
             VideoId::from_string(video_id),
         );
         let clips = clip_repo.list(None).await.unwrap_or_default();
-        let video_bytes: u64 = clips.iter().map(|c| c.file_size_bytes).sum();
+        let video_bytes: u64 = clips.iter().map(|c| c.total_size_bytes()).sum();
         let video_clips = clips.len() as u32;
 
         // Delete files from R2
@@ -759,11 +768,8 @@ pub async fn delete_clip(
     );
 
     // Prefer Firestore metadata, but fall back to R2 object size if Firestore lookup fails.
-    let mut clip_size_bytes: Option<u64> = match clip_repo.list(None).await {
-        Ok(clips) => clips
-            .iter()
-            .find(|c| c.filename == clip_name)
-            .map(|c| c.file_size_bytes),
+    let clip_meta = match clip_repo.list(None).await {
+        Ok(clips) => clips.into_iter().find(|c| c.filename == clip_name),
         Err(e) => {
             warn!(
                 "Failed to list clips for size lookup ({} / {}): {}. Falling back to storage.",
@@ -772,6 +778,11 @@ pub async fn delete_clip(
             None
         }
     };
+    let mut clip_size_bytes: Option<u64> = clip_meta.as_ref().map(|c| c.total_size_bytes());
+    let variant_keys = clip_meta
+        .as_ref()
+        .map(|c| c.variant_r2_keys())
+        .unwrap_or_default();
 
     if clip_size_bytes.is_none() {
         let object_prefix = format!("{}/{}/clips/{}", user.uid, video_id, clip_name);
@@ -800,10 +811,10 @@ pub async fn delete_clip(
         );
     }
 
-    // Delete clip and thumbnail from R2
+    // Delete clip, thumbnail and export variants from R2
     let files_deleted = state
         .storage
-        .delete_clip(&user.uid, &video_id, &clip_name)
+        .delete_clip_with_variants(&user.uid, &video_id, &clip_name, &variant_keys)
         .await?;
 
     // Get clip_id before deleting metadata (needed for share cleanup)
@@ -966,21 +977,34 @@ pub async fn bulk_delete_clips(
     );
     let video_repo = vclip_firestore::VideoRepository::new((*state.firestore).clone(), &user.uid);
 
-    // Build a lookup of clip sizes from Firestore so we can subtract accurate storage.
-    let clip_sizes: HashMap<String, u64> = clip_repo
+    // Build a lookup of clip sizes (including export variants) from Firestore so we can
+    // subtract accurate storage and remove variant files.
+    let clip_lookup: HashMap<String, (u64, Vec<String>)> = clip_repo
         .list(None)
         .await
         .map(|clips| {
             clips
                 .into_iter()
-                .map(|c| (c.filename, c.file_size_bytes))
+                .map(|c| {
+                    let entry = (c.total_size_bytes(), c.variant_r2_keys());
+                    (c.filename, entry)
+                })
                 .collect()
         })
         .unwrap_or_default();
 
     for clip_name in &request.clip_names {
-        // Delete clip and thumbnail from R2
-        let files_deleted = match state.storage.delete_clip(&user.uid, &video_id, clip_name).await {
+        let (known_size, variant_keys) = clip_lookup
+            .get(clip_name)
+            .map(|(size, keys)| (*size, keys.as_slice()))
+            .unwrap_or((0, &[]));
+
+        // Delete clip, thumbnail and export variants from R2
+        let files_deleted = match state
+            .storage
+            .delete_clip_with_variants(&user.uid, &video_id, clip_name, variant_keys)
+            .await
+        {
             Ok(count) => count,
             Err(e) => {
                 warn!("Failed to delete files for clip {}: {}", clip_name, e);
@@ -1010,7 +1034,7 @@ pub async fn bulk_delete_clips(
         };
 
         // Determine clip size (prefer Firestore metadata, fallback to storage listing)
-        let mut clip_size = clip_sizes.get(clip_name).copied().unwrap_or(0);
+        let mut clip_size = known_size;
         if clip_size == 0 {
             let object_prefix = format!("{}/{}/clips/{}", user.uid, video_id, clip_name);
             if let Ok(objects) = state.storage.list_objects(&object_prefix).await {
@@ -1131,10 +1155,16 @@ pub async fn delete_all_clips(
     let mut failed_count = 0u32;
 
     for clip in clips {
+        let clip_size = clip.total_size_bytes();
+        let variant_keys = clip.variant_r2_keys();
         let clip_name = clip.filename;
 
-        // Delete clip and thumbnail from R2
-        let files_deleted = match state.storage.delete_clip(&user.uid, &video_id, &clip_name).await {
+        // Delete clip, thumbnail and export variants from R2
+        let files_deleted = match state
+            .storage
+            .delete_clip_with_variants(&user.uid, &video_id, &clip_name, &variant_keys)
+            .await
+        {
             Ok(count) => count,
             Err(e) => {
                 warn!("Failed to delete files for clip {}: {}", clip_name, e);
@@ -1161,7 +1191,6 @@ pub async fn delete_all_clips(
                     info!("Deleted clip {} from video {} for user {} ({} files)", 
                           clip_name, video_id, user.uid, files_deleted);
 
-                    let clip_size = clip.file_size_bytes;
                     if clip_size > 0 {
                         if let Err(e) = video_repo
                             .subtract_clip_size(&video_id_obj, clip_size)
@@ -1239,6 +1268,15 @@ pub struct HighlightInfo {
     pub reason: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Original timestamps and snap sources when boundaries were refined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refinement: Option<vclip_models::BoundaryRefinement>,
+    /// Composite virality score (0-1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virality_score: Option<f64>,
+    /// Per-metric breakdown behind the virality score
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<QualityMetrics>,
 }
 
 /// Highlights response.
@@ -1253,9 +1291,12 @@ pub struct HighlightsResponse {
 }
 
 /// Get video highlights.
+///
+/// Supports `?sort=id|start|score` and `?min_score=0.5`.
 pub async fn get_video_highlights(
     State(state): State<AppState>,
     Path(video_id): Path<String>,
+    Query(rank): Query<SceneRankQuery>,
     user: AuthUser,
 ) -> ApiResult<Json<HighlightsResponse>> {
     // Verify ownership
@@ -1271,11 +1312,13 @@ pub async fn get_video_highlights(
         &user.uid,
     );
 
-    let video_highlights = highlights_repo
+    let mut video_highlights = highlights_repo
         .get(&video_id_obj)
         .await?
         .ok_or_else(|| ApiError::not_found("Highlights not found for this video"))?;
 
+    rank.apply(&mut video_highlights.highlights);
+
     let highlights: Vec<HighlightInfo> = video_highlights
         .highlights
         .into_iter()
@@ -1288,6 +1331,9 @@ pub async fn get_video_highlights(
             hook_category: h.hook_category.map(|c| format!("{:?}", c).to_lowercase()),
             reason: h.reason,
             description: h.description,
+            refinement: h.refinement,
+            virality_score: h.virality_score,
+            quality: h.quality,
         })
         .collect();
 
@@ -1385,6 +1431,21 @@ pub struct ReprocessScenesRequest {
     /// Only used when styles includes "streamer_split".
     #[serde(skip_serializing_if = "Option::is_none")]
     pub streamer_split_params: Option<StreamerSplitParamsRequest>,
+    /// Optional burned-in captions applied to every rendered style
+    #[serde(default)]
+    pub captions: Option<CaptionOptions>,
+    /// Output aspect ratio as "W:H" (default: 9:16)
+    #[serde(default)]
+    pub target_aspect: Option<String>,
+    /// Output resolution preset (default: 1080p)
+    #[serde(default)]
+    pub resolution: ResolutionPreset,
+    /// Platform export profiles rendered for every clip
+    #[serde(default)]
+    pub export_profiles: Vec<ExportProfile>,
+    /// Optional audio stage (loudness normalization and cleanup)
+    #[serde(default)]
+    pub audio: Option<AudioConfig>,
 }
 
 /// StreamerSplit parameters from the frontend.
@@ -1445,6 +1506,20 @@ pub async fn reprocess_scenes(
     if request.styles.len() > 10 {
         return Err(ApiError::bad_request("Cannot use more than 10 styles"));
     }
+    if request.export_profiles.len() > ExportProfile::ALL.len() {
+        return Err(ApiError::bad_request("Too many export profiles"));
+    }
+    if let Some(audio) = &request.audio {
+        audio
+            .validate()
+            .map_err(|e| ApiError::bad_request(format!("Invalid audio config: {}", e)))?;
+    }
+    let target_aspect: AspectRatio = match request.target_aspect.as_deref() {
+        Some(aspect) => aspect
+            .parse()
+            .map_err(|e| ApiError::bad_request(format!("Invalid target_aspect: {}", e)))?,
+        None => AspectRatio::default(),
+    };
 
     // Verify ownership
     if !state.user_service.user_owns_video(&user.uid, &video_id).await? {
@@ -1550,9 +1625,8 @@ pub async fn reprocess_scenes(
         cost.total, num_scenes, styles.len(), user.uid, cost.style_total, cost.silent_remover_cost, cost.object_detection_cost
     );
 
-    // Parse crop mode and target aspect from highlights or use defaults
+    // Crop mode is not user-selectable for reprocessing
     let crop_mode = vclip_models::CropMode::default();
-    let target_aspect = vclip_models::AspectRatio::default();
 
     // Convert StreamerSplit params from request to model type
     let streamer_split_params = request.streamer_split_params.as_ref().map(|p| {
@@ -1584,14 +1658,25 @@ pub async fn reprocess_scenes(
     )
     .with_crop_mode(crop_mode)
     .with_target_aspect(target_aspect)
+    .with_resolution(request.resolution)
+    .with_export_profiles(request.export_profiles.clone())
+    .with_audio(request.audio.clone())
     .with_overwrite(request.overwrite)
     .with_streamer_split_params(streamer_split_params)
     .with_cut_silent_parts(request.cut_silent_parts)
     .with_object_detection(request.enable_object_detection)
-    .with_top_scenes_compilation(request.top_scenes_compilation);
+    .with_top_scenes_compilation(request.top_scenes_compilation)
+    .with_captions(request.captions.clone())
+    .with_credits_reserved(cost.total)
+    .with_lane(QueueLane::for_plan(limits.tier));
     
     let job_id = job.job_id.clone();
 
+    // Register the job so it can be polled and cancelled
+    if let Err(e) = state.progress.register_job(&job_id, &video_id, &user.uid).await {
+        warn!("Failed to register job {}: {}", job_id, e);
+    }
+
     // Enqueue the job
     state.queue.enqueue_reprocess(job).await
         .map_err(|e| ApiError::internal(format!("Failed to enqueue job: {}", e)))?;
@@ -1645,6 +1730,21 @@ pub struct ProcessVideoRequest {
     /// Target aspect ratio
     #[serde(default = "default_target_aspect")]
     pub target_aspect: String,
+    /// Output resolution preset (720p, 1080p or 4k)
+    #[serde(default)]
+    pub resolution: ResolutionPreset,
+    /// Platform export profiles rendered for every clip
+    #[serde(default)]
+    pub export_profiles: Vec<ExportProfile>,
+    /// Optional audio stage (loudness normalization and cleanup)
+    #[serde(default)]
+    pub audio: Option<AudioConfig>,
+    /// Optional burned-in captions applied to every rendered style
+    #[serde(default)]
+    pub captions: Option<CaptionOptions>,
+    /// How highlights are detected (transcript, signals or fused)
+    #[serde(default)]
+    pub highlight_detection: HighlightDetection,
 }
 
 fn default_crop_mode() -> String {
@@ -1710,6 +1810,14 @@ pub async fn process_video(
     if styles.is_empty() {
         return Err(ApiError::bad_request("No valid styles specified"));
     }
+    if request.export_profiles.len() > ExportProfile::ALL.len() {
+        return Err(ApiError::bad_request("Too many export profiles"));
+    }
+    if let Some(audio) = &request.audio {
+        audio
+            .validate()
+            .map_err(|e| ApiError::bad_request(format!("Invalid audio config: {}", e)))?;
+    }
 
     // Validate detection tiers are allowed by the user's plan
     for style in &styles {
@@ -1744,13 +1852,22 @@ pub async fn process_video(
 
     // Parse crop mode and target aspect
     let crop_mode: CropMode = request.crop_mode.parse().unwrap_or_default();
-    let target_aspect: AspectRatio = request.target_aspect.parse().unwrap_or_default();
+    let target_aspect: AspectRatio = request
+        .target_aspect
+        .parse()
+        .map_err(|e| ApiError::bad_request(format!("Invalid target_aspect: {}", e)))?;
 
     // Create job
     let job = ProcessVideoJob::new(&user.uid, &validated_url, styles)
         .with_crop_mode(crop_mode)
         .with_target_aspect(target_aspect)
-        .with_custom_prompt(sanitized_prompt.clone());
+        .with_resolution(request.resolution)
+        .with_export_profiles(request.export_profiles.clone())
+        .with_audio(request.audio.clone())
+        .with_captions(request.captions.clone())
+        .with_highlight_detection(request.highlight_detection)
+        .with_custom_prompt(sanitized_prompt.clone())
+        .with_lane(QueueLane::for_plan(limits.tier));
     
     let job_id = job.job_id.clone();
     let video_id = job.video_id.clone();
@@ -1785,7 +1902,8 @@ pub async fn process_video(
         &user.uid,
         &validated_url,
         "Analyzing...", // Placeholder title until worker gets the real one
-    );
+    )
+    .with_output_format(target_aspect, request.resolution);
     let video_repo = vclip_firestore::VideoRepository::new(
         (*state.firestore).clone(),
         &user.uid,
@@ -1798,6 +1916,11 @@ pub async fn process_video(
             ApiError::internal(format!("Failed to initialize video: {}", e))
         })?;
 
+    // Register the job so it can be polled and cancelled
+    if let Err(e) = state.progress.register_job(&job_id, video_id.as_str(), &user.uid).await {
+        warn!("Failed to register job {}: {}", job_id, e);
+    }
+
     // Enqueue job
     state.queue.enqueue_process(job).await
         .map_err(|e| ApiError::internal(format!("Failed to enqueue job: {}", e)))?;
@@ -0,0 +1,356 @@
+//! Webhook endpoint API handlers.
+//!
+//! Users on plans with API access can register HTTPS endpoints that receive
+//! signed job and clip lifecycle events. The worker delivers events and
+//! keeps a delivery log per endpoint.
+
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use uuid::Uuid;
+
+use vclip_firestore::WebhookRepository;
+use vclip_models::{
+    validate_webhook_url, WebhookDelivery, WebhookEndpoint, WebhookEventType, WebhookPayload,
+};
+use vclip_queue::PendingWebhook;
+
+use crate::auth::AuthUser;
+use crate::error::{ApiError, ApiResult};
+use crate::security::sanitize_string;
+use crate::state::AppState;
+
+/// Endpoints a user may register.
+const MAX_WEBHOOK_ENDPOINTS: usize = 5;
+
+/// Maximum length of an endpoint description.
+const MAX_DESCRIPTION_LENGTH: usize = 200;
+
+/// A webhook endpoint as returned by the API (without its secret).
+#[derive(Serialize)]
+pub struct WebhookEndpointInfo {
+    pub id: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Event types delivered; empty means all
+    pub event_types: Vec<WebhookEventType>,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<WebhookEndpoint> for WebhookEndpointInfo {
+    fn from(endpoint: WebhookEndpoint) -> Self {
+        Self {
+            id: endpoint.id,
+            url: endpoint.url,
+            description: endpoint.description,
+            event_types: endpoint.event_types,
+            enabled: endpoint.enabled,
+            created_at: endpoint.created_at.to_rfc3339(),
+            updated_at: endpoint.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Response for listing webhook endpoints.
+#[derive(Serialize)]
+pub struct ListWebhooksResponse {
+    pub endpoints: Vec<WebhookEndpointInfo>,
+}
+
+/// List the user's webhook endpoints.
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> ApiResult<Json<ListWebhooksResponse>> {
+    let repo = WebhookRepository::new((*state.firestore).clone(), &user.uid);
+    let endpoints = repo.list().await?;
+
+    Ok(Json(ListWebhooksResponse {
+        endpoints: endpoints.into_iter().map(WebhookEndpointInfo::from).collect(),
+    }))
+}
+
+/// Request to register a webhook endpoint.
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    /// HTTPS URL events are POSTed to
+    pub url: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Event types to deliver; empty or missing means all
+    #[serde(default)]
+    pub event_types: Vec<WebhookEventType>,
+}
+
+/// Response with a newly registered endpoint.
+#[derive(Serialize)]
+pub struct CreateWebhookResponse {
+    #[serde(flatten)]
+    pub endpoint: WebhookEndpointInfo,
+    /// Signing secret; shown only once
+    pub secret: String,
+}
+
+/// Register a webhook endpoint.
+///
+/// The signing secret is returned only in this response.
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(request): Json<CreateWebhookRequest>,
+) -> ApiResult<Json<CreateWebhookResponse>> {
+    let limits = state.user_service.get_plan_limits(&user.uid).await?;
+    if !limits.api_access {
+        return Err(ApiError::forbidden("Webhooks are not included in your plan"));
+    }
+
+    let url = validate_webhook_url(&request.url).map_err(ApiError::bad_request)?;
+    let event_types = validate_event_types(request.event_types)?;
+
+    let repo = WebhookRepository::new((*state.firestore).clone(), &user.uid);
+    let existing = repo.list().await?;
+    if existing.len() >= MAX_WEBHOOK_ENDPOINTS {
+        return Err(ApiError::forbidden(format!(
+            "You can register at most {} webhook endpoints",
+            MAX_WEBHOOK_ENDPOINTS
+        )));
+    }
+
+    let mut endpoint = WebhookEndpoint::new(Uuid::new_v4().to_string(), &user.uid, url)
+        .with_event_types(event_types);
+    if let Some(description) = request.description.as_deref().map(validate_description).transpose()? {
+        if !description.is_empty() {
+            endpoint = endpoint.with_description(description);
+        }
+    }
+
+    repo.create(&endpoint).await?;
+
+    info!(
+        user_id = %user.uid,
+        endpoint_id = %endpoint.id,
+        url = %endpoint.url,
+        "Registered webhook endpoint"
+    );
+
+    let secret = endpoint.secret.clone();
+    Ok(Json(CreateWebhookResponse {
+        endpoint: endpoint.into(),
+        secret,
+    }))
+}
+
+/// Get a webhook endpoint.
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    Path(endpoint_id): Path<String>,
+    user: AuthUser,
+) -> ApiResult<Json<WebhookEndpointInfo>> {
+    let repo = WebhookRepository::new((*state.firestore).clone(), &user.uid);
+    let endpoint = repo
+        .get(&endpoint_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Webhook endpoint not found"))?;
+
+    Ok(Json(endpoint.into()))
+}
+
+/// Request to change an endpoint.
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhookRequest {
+    #[serde(default)]
+    pub url: Option<String>,
+    /// New description; an empty string clears it
+    #[serde(default)]
+    pub description: Option<String>,
+    /// New event filter; an empty list delivers all events
+    #[serde(default)]
+    pub event_types: Option<Vec<WebhookEventType>>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+/// Change, pause or resume a webhook endpoint.
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    Path(endpoint_id): Path<String>,
+    user: AuthUser,
+    Json(request): Json<UpdateWebhookRequest>,
+) -> ApiResult<Json<WebhookEndpointInfo>> {
+    let repo = WebhookRepository::new((*state.firestore).clone(), &user.uid);
+    let mut endpoint = repo
+        .get(&endpoint_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Webhook endpoint not found"))?;
+
+    if let Some(url) = request.url {
+        endpoint.url = validate_webhook_url(&url).map_err(ApiError::bad_request)?;
+    }
+    if let Some(description) = request.description {
+        let description = validate_description(&description)?;
+        endpoint.description = (!description.is_empty()).then_some(description);
+    }
+    if let Some(event_types) = request.event_types {
+        endpoint.event_types = validate_event_types(event_types)?;
+    }
+    if let Some(enabled) = request.enabled {
+        endpoint.enabled = enabled;
+    }
+
+    repo.update_settings(&endpoint).await?;
+
+    info!(
+        user_id = %user.uid,
+        endpoint_id = %endpoint_id,
+        enabled = endpoint.enabled,
+        "Updated webhook endpoint"
+    );
+
+    Ok(Json(endpoint.into()))
+}
+
+/// Response for deleting an endpoint.
+#[derive(Serialize)]
+pub struct DeleteWebhookResponse {
+    pub success: bool,
+    pub endpoint_id: String,
+}
+
+/// Delete a webhook endpoint and its delivery log.
+///
+/// Scheduled retries to the endpoint are dropped when they come due.
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(endpoint_id): Path<String>,
+    user: AuthUser,
+) -> ApiResult<Json<DeleteWebhookResponse>> {
+    let repo = WebhookRepository::new((*state.firestore).clone(), &user.uid);
+    if repo.get(&endpoint_id).await?.is_none() {
+        return Err(ApiError::not_found("Webhook endpoint not found"));
+    }
+
+    repo.delete(&endpoint_id).await?;
+
+    info!(user_id = %user.uid, endpoint_id = %endpoint_id, "Deleted webhook endpoint");
+
+    Ok(Json(DeleteWebhookResponse {
+        success: true,
+        endpoint_id,
+    }))
+}
+
+/// Query parameters for the delivery log.
+#[derive(Debug, Deserialize)]
+pub struct ListDeliveriesQuery {
+    /// Maximum entries (1-100, default 50)
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// Response for the delivery log.
+#[derive(Serialize)]
+pub struct ListDeliveriesResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+/// List an endpoint's most recent deliveries, newest first.
+pub async fn list_webhook_deliveries(
+    State(state): State<AppState>,
+    Path(endpoint_id): Path<String>,
+    Query(query): Query<ListDeliveriesQuery>,
+    user: AuthUser,
+) -> ApiResult<Json<ListDeliveriesResponse>> {
+    let repo = WebhookRepository::new((*state.firestore).clone(), &user.uid);
+    if repo.get(&endpoint_id).await?.is_none() {
+        return Err(ApiError::not_found("Webhook endpoint not found"));
+    }
+
+    let deliveries = repo
+        .list_deliveries(&endpoint_id, query.limit.unwrap_or(50))
+        .await?;
+
+    Ok(Json(ListDeliveriesResponse { deliveries }))
+}
+
+/// Response for a test event.
+#[derive(Serialize)]
+pub struct TestWebhookResponse {
+    pub event_id: String,
+    pub delivery_id: String,
+    pub status: String,
+}
+
+/// Send a `test` event to an endpoint.
+///
+/// The event goes through the normal delivery path (signing, retries and
+/// the delivery log), even if the endpoint is disabled.
+pub async fn test_webhook(
+    State(state): State<AppState>,
+    Path(endpoint_id): Path<String>,
+    user: AuthUser,
+) -> ApiResult<Json<TestWebhookResponse>> {
+    let repo = WebhookRepository::new((*state.firestore).clone(), &user.uid);
+    let endpoint = repo
+        .get(&endpoint_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Webhook endpoint not found"))?;
+
+    let payload = WebhookPayload::test(Uuid::new_v4().to_string(), &endpoint.id);
+    let body = serde_json::to_string(&payload)
+        .map_err(|e| ApiError::internal(format!("Failed to serialize test event: {}", e)))?;
+    let delivery = WebhookDelivery::new(&endpoint.id, &payload);
+
+    repo.record_delivery(&delivery).await?;
+    state
+        .webhooks
+        .schedule(
+            &PendingWebhook {
+                delivery_id: delivery.id.clone(),
+                user_id: user.uid.clone(),
+                endpoint_id: endpoint.id.clone(),
+                event_type: payload.event_type,
+                body,
+                attempts: 0,
+            },
+            delivery.created_at,
+        )
+        .await?;
+
+    info!(
+        user_id = %user.uid,
+        endpoint_id = %endpoint.id,
+        delivery_id = %delivery.id,
+        "Queued webhook test event"
+    );
+
+    Ok(Json(TestWebhookResponse {
+        event_id: payload.id,
+        delivery_id: delivery.id,
+        status: delivery.status.as_str().to_string(),
+    }))
+}
+
+/// Validate an event filter; the `test` event can't be subscribed to.
+fn validate_event_types(mut event_types: Vec<WebhookEventType>) -> ApiResult<Vec<WebhookEventType>> {
+    if event_types.contains(&WebhookEventType::Test) {
+        return Err(ApiError::bad_request("The test event is always delivered and can't be filtered"));
+    }
+    event_types.sort_by_key(|t| WebhookEventType::SUBSCRIBABLE.iter().position(|s| s == t));
+    event_types.dedup();
+    Ok(event_types)
+}
+
+fn validate_description(description: &str) -> ApiResult<String> {
+    let description = sanitize_string(description.trim());
+    if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        return Err(ApiError::bad_request(format!(
+            "Description must be at most {} characters",
+            MAX_DESCRIPTION_LENGTH
+        )));
+    }
+    Ok(description)
+}
@@ -21,5 +21,5 @@ pub mod state;
 pub use config::ApiConfig;
 pub use error::{ApiError, ApiResult};
 pub use routes::create_router;
-pub use services::{StaleJobDetector, UserService};
+pub use services::{ShareStatsFlusher, StaleJobDetector, UserService};
 pub use state::AppState;
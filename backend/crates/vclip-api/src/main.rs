@@ -5,7 +5,7 @@ use std::net::SocketAddr;
 use tracing::{error, info};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
-use vclip_api::{create_router, metrics, ApiConfig, AppState, StaleJobDetector};
+use vclip_api::{create_router, metrics, ApiConfig, AppState, ShareStatsFlusher, StaleJobDetector};
 
 #[tokio::main]
 async fn main() {
@@ -87,6 +87,15 @@ async fn main() {
         stale_detector.run().await;
     });
 
+    // Start share stats flusher background task
+    let share_stats_flusher = ShareStatsFlusher::new(
+        std::sync::Arc::clone(&state.share_stats),
+        std::sync::Arc::clone(&state.firestore),
+    );
+    tokio::spawn(async move {
+        share_stats_flusher.run().await;
+    });
+
     // Create router
     let app = create_router(state, metrics_handle);
 
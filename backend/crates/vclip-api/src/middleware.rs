@@ -8,11 +8,12 @@ use std::time::Instant;
 
 use axum::body::Body;
 use axum::extract::{ConnectInfo, State};
-use axum::http::{HeaderValue, Request, Response, StatusCode};
+use axum::http::{header, Extensions, HeaderMap, HeaderValue, Request, Response, StatusCode};
 use axum::middleware::Next;
 use axum::response::IntoResponse;
 use governor::{Quota, RateLimiter};
 use governor::clock::DefaultClock;
+use governor::state::keyed::DefaultKeyedStateStore;
 use governor::state::{InMemoryState, NotKeyed};
 use tokio::sync::RwLock;
 use tower_http::cors::{Any, CorsLayer};
@@ -27,6 +28,9 @@ pub type GlobalRateLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
 /// Per-IP rate limiter using governor.
 pub type IpRateLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
 
+/// Per-API-key rate limiter, keyed by the hash of the key.
+pub type ApiKeyRateLimiter = RateLimiter<String, DefaultKeyedStateStore<String>, DefaultClock>;
+
 /// Maximum number of IPs to track in rate limiter cache.
 /// This prevents unbounded memory growth from attackers using many IPs.
 const MAX_RATE_LIMITER_ENTRIES: usize = 10_000;
@@ -53,6 +57,21 @@ impl RateLimiterCache {
         }
     }
 
+    /// Create a rate limiter cache allowing `requests_per_minute` per IP.
+    ///
+    /// Used for endpoints where attempts, not load, are the concern
+    /// (e.g. share passcode checks).
+    pub fn per_minute(requests_per_minute: u32) -> Self {
+        let quota = Quota::per_minute(
+            NonZeroU32::new(requests_per_minute).unwrap_or(NonZeroU32::new(10).unwrap()),
+        );
+        Self {
+            limiters: Arc::new(RwLock::new(HashMap::new())),
+            quota,
+            ttl: std::time::Duration::from_secs(3600), // 1 hour
+        }
+    }
+
     /// Clean up expired rate limiters to prevent memory leaks.
     async fn cleanup_expired(&self) {
         let mut limiters = self.limiters.write().await;
@@ -116,6 +135,86 @@ pub fn create_rate_limiter(requests_per_second: u32) -> Arc<GlobalRateLimiter> {
     Arc::new(RateLimiter::direct(quota))
 }
 
+/// Create the per-API-key rate limiter.
+///
+/// Keys are limited independently of the per-IP limiter, so scripts behind
+/// one egress IP don't share a budget with each other or with browser users.
+pub fn create_api_key_rate_limiter(requests_per_minute: u32, burst: u32) -> Arc<ApiKeyRateLimiter> {
+    let quota = Quota::per_minute(NonZeroU32::new(requests_per_minute).unwrap_or(NonZeroU32::new(120).unwrap()))
+        .allow_burst(NonZeroU32::new(burst).unwrap_or(NonZeroU32::new(30).unwrap()));
+    Arc::new(RateLimiter::keyed(quota))
+}
+
+/// Failed API key lookups an IP may make per window.
+const MAX_FAILED_API_KEY_LOOKUPS: u32 = 10;
+
+/// Window of the failed API key lookup limit.
+const FAILED_API_KEY_LOOKUP_WINDOW: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Per-IP limit on API key lookups that found no key.
+///
+/// Unknown keys have no bucket of their own, so guessed keys are limited by
+/// the client IP instead and refused before they reach Firestore.
+pub struct ApiKeyLookupLimiter {
+    failures: std::sync::Mutex<HashMap<IpAddr, (u32, Instant)>>,
+    max_failures: u32,
+    window: std::time::Duration,
+}
+
+impl ApiKeyLookupLimiter {
+    /// Create a limiter allowing `max_failures` misses per IP per `window`.
+    pub fn new(max_failures: u32, window: std::time::Duration) -> Self {
+        Self {
+            failures: std::sync::Mutex::new(HashMap::new()),
+            max_failures,
+            window,
+        }
+    }
+
+    /// Whether an IP has used up its failed lookups for the current window.
+    pub fn is_blocked(&self, ip: IpAddr) -> bool {
+        let failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        failures
+            .get(&ip)
+            .is_some_and(|(count, since)| *count >= self.max_failures && since.elapsed() < self.window)
+    }
+
+    /// Count a lookup that found no key.
+    pub fn record_failure(&self, ip: IpAddr) {
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        if failures.len() >= MAX_RATE_LIMITER_ENTRIES && !failures.contains_key(&ip) {
+            failures.retain(|_, (_, since)| since.elapsed() < self.window);
+            if failures.len() >= MAX_RATE_LIMITER_ENTRIES {
+                let oldest = failures.iter().min_by_key(|(_, (_, since))| *since).map(|(ip, _)| *ip);
+                if let Some(oldest) = oldest {
+                    failures.remove(&oldest);
+                }
+            }
+        }
+
+        let entry = failures.entry(ip).or_insert((0, Instant::now()));
+        if entry.1.elapsed() >= self.window {
+            *entry = (0, Instant::now());
+        }
+        entry.0 += 1;
+    }
+}
+
+impl Default for ApiKeyLookupLimiter {
+    fn default() -> Self {
+        Self::new(MAX_FAILED_API_KEY_LOOKUPS, FAILED_API_KEY_LOOKUP_WINDOW)
+    }
+}
+
+/// Check the rate limit of an API key.
+pub fn check_api_key_rate_limit(limiter: &ApiKeyRateLimiter, key_hash: &str) -> bool {
+    // Drop keys whose buckets have refilled before the map grows unbounded
+    if limiter.len() > MAX_RATE_LIMITER_ENTRIES {
+        limiter.retain_recent();
+    }
+    limiter.check_key(&key_hash.to_string()).is_ok()
+}
+
 /// Create CORS layer.
 pub fn cors_layer(origins: &[String]) -> CorsLayer {
     use axum::http::{Method, header};
@@ -185,10 +284,13 @@ pub async fn security_headers(
         "X-Content-Type-Options",
         "nosniff".parse().expect("valid header value"),
     );
-    headers.insert(
-        "X-Frame-Options",
-        "DENY".parse().expect("valid header value"),
-    );
+    // Pages that set their own CSP decide who may frame them (frame-ancestors)
+    if !headers.contains_key(header::CONTENT_SECURITY_POLICY) {
+        headers.insert(
+            "X-Frame-Options",
+            "DENY".parse().expect("valid header value"),
+        );
+    }
     headers.insert(
         "X-XSS-Protection",
         "1; mode=block".parse().expect("valid header value"),
@@ -306,8 +408,13 @@ pub async fn rate_limit_middleware(
 
 /// Extract client IP from request headers or connection info.
 fn extract_client_ip(request: &Request<Body>) -> Option<IpAddr> {
+    client_ip(request.headers(), request.extensions())
+}
+
+/// Client IP from proxy headers, falling back to the connection address.
+pub(crate) fn client_ip(headers: &HeaderMap, extensions: &Extensions) -> Option<IpAddr> {
     // Try X-Forwarded-For header first (for proxied requests)
-    if let Some(forwarded) = request.headers().get("X-Forwarded-For") {
+    if let Some(forwarded) = headers.get("X-Forwarded-For") {
         if let Ok(forwarded_str) = forwarded.to_str() {
             // Take the first IP in the chain (original client)
             if let Some(first_ip) = forwarded_str.split(',').next() {
@@ -319,7 +426,7 @@ fn extract_client_ip(request: &Request<Body>) -> Option<IpAddr> {
     }
 
     // Try X-Real-IP header
-    if let Some(real_ip) = request.headers().get("X-Real-IP") {
+    if let Some(real_ip) = headers.get("X-Real-IP") {
         if let Ok(ip_str) = real_ip.to_str() {
             if let Ok(ip) = ip_str.parse() {
                 return Some(ip);
@@ -328,8 +435,36 @@ fn extract_client_ip(request: &Request<Body>) -> Option<IpAddr> {
     }
 
     // Fall back to connection info (requires ConnectInfo extractor in router)
-    request
-        .extensions()
+    extensions
         .get::<ConnectInfo<std::net::SocketAddr>>()
         .map(|ci| ci.0.ip())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_lookup_limiter_blocks_after_failures() {
+        let limiter = ApiKeyLookupLimiter::new(3, std::time::Duration::from_secs(60));
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+        let other: IpAddr = "203.0.113.8".parse().unwrap();
+
+        for _ in 0..2 {
+            limiter.record_failure(ip);
+        }
+        assert!(!limiter.is_blocked(ip));
+        limiter.record_failure(ip);
+        assert!(limiter.is_blocked(ip));
+        assert!(!limiter.is_blocked(other));
+    }
+
+    #[test]
+    fn test_api_key_lookup_limiter_window_expires() {
+        let limiter = ApiKeyLookupLimiter::new(1, std::time::Duration::ZERO);
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+
+        limiter.record_failure(ip);
+        assert!(!limiter.is_blocked(ip));
+    }
+}
@@ -8,28 +8,42 @@ use tower_http::limit::RequestBodyLimitLayer;
 
 use crate::handlers::{health, ready};
 use crate::handlers::admin::{
+    delete_dlq_entry, list_dlq, purge_dlq, replay_dlq_batch, replay_dlq_entry,
     enqueue_synthetic_job, get_queue_status, get_system_info,
     get_admin_prompt, update_admin_prompt,
-    get_user, list_users, recalculate_user_storage, reset_video_status,
+    get_user, list_users, recalculate_user_storage, reconcile_user_storage, reset_video_status,
     update_user_plan, update_user_usage,
 };
 use crate::handlers::analysis::{
     delete_draft, estimate_processing, get_analysis_status, get_draft,
     list_drafts, process_draft, start_analysis,
 };
-use crate::handlers::jobs::{get_job_status, get_job_history};
+use crate::handlers::live::{start_live_capture, stop_live_capture};
+use crate::handlers::api_keys::{create_api_key, list_api_keys, revoke_api_key};
+use crate::handlers::jobs::{cancel_job, get_job_status, get_job_history, stream_job_events};
+use crate::handlers::channels::{
+    create_channel, delete_channel, get_channel, list_channels, update_channel,
+};
 use crate::handlers::clip_delivery::{
-    create_share, get_download_url, get_playback_url, get_thumbnail_url,
-    resolve_share, revoke_share,
+    create_share, get_download_url, get_playback_url, get_share, get_thumbnail_url,
+    get_video_share_stats, resolve_share, revoke_share, share_status, unlock_share,
 };
 use crate::handlers::credits::get_credit_history;
 use crate::handlers::settings::{get_settings, update_settings};
+use crate::handlers::share_page::oembed;
 use crate::handlers::storage::{check_storage_quota, get_storage_quota};
+use crate::handlers::uploads::{
+    abort_upload, complete_upload, create_upload, get_upload, get_upload_part_urls,
+};
 use crate::handlers::videos::{
     bulk_delete_clips, bulk_delete_videos, delete_all_clips, delete_clip, delete_video, get_video_highlights, get_video_info,
     get_video_scene_styles, list_user_videos, get_processing_status, process_video, reprocess_scenes, stream_clip, update_video_title,
     update_clip_title,
 };
+use crate::handlers::webhooks::{
+    create_webhook, delete_webhook, get_webhook, list_webhook_deliveries, list_webhooks,
+    test_webhook, update_webhook,
+};
 use crate::handlers::highlights::{
     add_scene, bulk_add_scenes, delete_scene, generate_more_scenes, update_scene_timestamps,
 };
@@ -53,7 +67,17 @@ pub fn create_router(state: AppState, metrics_handle: Option<PrometheusHandle>)
         // Process draft (submit for rendering)
         .route("/drafts/:draft_id/process", post(process_draft))
         // Cost estimation
-        .route("/drafts/:draft_id/estimate", get(estimate_processing));
+        .route("/drafts/:draft_id/estimate", get(estimate_processing))
+        // Live stream capture
+        .route("/analyze/live", post(start_live_capture))
+        .route("/drafts/:draft_id/live/stop", post(stop_live_capture));
+
+    // Local file uploads (multipart to R2, then analysis)
+    let upload_routes = Router::new()
+        .route("/uploads", post(create_upload))
+        .route("/uploads/:upload_id", get(get_upload).delete(abort_upload))
+        .route("/uploads/:upload_id/parts", post(get_upload_part_urls))
+        .route("/uploads/:upload_id/complete", post(complete_upload));
 
     let video_routes = Router::new()
         // Process new video (REST replacement for WebSocket)
@@ -85,6 +109,8 @@ pub fn create_router(state: AppState, metrics_handle: Option<PrometheusHandle>)
         .route("/videos/:video_id/clips/:clip_id/title", patch(update_clip_title))
         // Reprocess
         .route("/videos/:video_id/reprocess", post(reprocess_scenes))
+        // Share link views across the video's clips
+        .route("/videos/:video_id/share-stats", get(get_video_share_stats))
         // User videos list
         .route("/user/videos", get(list_user_videos))
         .route("/user/videos/processing-status", get(get_processing_status));
@@ -98,6 +124,7 @@ pub fn create_router(state: AppState, metrics_handle: Option<PrometheusHandle>)
         // Thumbnail URL
         .route("/clips/:clip_id/thumbnail-url", post(get_thumbnail_url))
         // Share management
+        .route("/clips/:clip_id/share", get(get_share))
         .route("/clips/:clip_id/share", post(create_share))
         .route("/clips/:clip_id/share", delete(revoke_share));
 
@@ -109,19 +136,50 @@ pub fn create_router(state: AppState, metrics_handle: Option<PrometheusHandle>)
         .route("/storage/quota", get(get_storage_quota))
         .route("/storage/check", post(check_storage_quota));
 
-    // Job status routes (for polling fallback)
+    // Job status routes (polling fallback and SSE stream)
     let job_routes = Router::new()
         .route("/jobs/:job_id/status", get(get_job_status))
-        .route("/jobs/:job_id/history", get(get_job_history));
+        .route("/jobs/:job_id/history", get(get_job_history))
+        .route("/jobs/:job_id/events", get(stream_job_events))
+        .route("/jobs/:job_id/cancel", post(cancel_job));
 
     // Credit history routes
     let credit_routes = Router::new()
         .route("/credits/history", get(get_credit_history));
 
+    // Channel monitoring routes
+    let channel_routes = Router::new()
+        .route("/channels", get(list_channels).post(create_channel))
+        .route(
+            "/channels/:channel_id",
+            get(get_channel).patch(update_channel).delete(delete_channel),
+        );
+
+    // Outbound webhook endpoints
+    let webhook_routes = Router::new()
+        .route("/webhooks", get(list_webhooks).post(create_webhook))
+        .route(
+            "/webhooks/:endpoint_id",
+            get(get_webhook).patch(update_webhook).delete(delete_webhook),
+        )
+        .route("/webhooks/:endpoint_id/deliveries", get(list_webhook_deliveries))
+        .route("/webhooks/:endpoint_id/test", post(test_webhook));
+
+    // API key management (ID tokens only)
+    let api_key_routes = Router::new()
+        .route("/api-keys", get(list_api_keys).post(create_api_key))
+        .route("/api-keys/:key_id", delete(revoke_api_key));
+
     // Admin routes for canary testing and user management (superadmin only)
     let admin_routes = Router::new()
         .route("/admin/jobs/synthetic", post(enqueue_synthetic_job))
         .route("/admin/queue/status", get(get_queue_status))
+        // Dead letter queue
+        .route("/admin/dlq", get(list_dlq))
+        .route("/admin/dlq/replay", post(replay_dlq_batch))
+        .route("/admin/dlq/purge", post(purge_dlq))
+        .route("/admin/dlq/:message_id", delete(delete_dlq_entry))
+        .route("/admin/dlq/:message_id/replay", post(replay_dlq_entry))
         .route("/admin/system/info", get(get_system_info))
         .route("/admin/prompt", get(get_admin_prompt))
         .route("/admin/prompt", post(update_admin_prompt))
@@ -132,6 +190,7 @@ pub fn create_router(state: AppState, metrics_handle: Option<PrometheusHandle>)
         .route("/admin/users/:uid/usage", patch(update_user_usage))
         // Storage management
         .route("/admin/users/:uid/storage/recalculate", post(recalculate_user_storage))
+        .route("/admin/users/:uid/storage/reconcile", post(reconcile_user_storage))
         // Video recovery (for stuck jobs)
         .route("/admin/users/:uid/videos/:video_id/reset", post(reset_video_status));
 
@@ -142,28 +201,52 @@ pub fn create_router(state: AppState, metrics_handle: Option<PrometheusHandle>)
     // This helps prevent brute-force attacks on share slugs
     let share_rate_limiter = std::sync::Arc::new(RateLimiterCache::new(5));
 
+    // Passcode attempts get their own, much stricter budget (10 per minute per IP)
+    let share_unlock_rate_limiter = std::sync::Arc::new(RateLimiterCache::per_minute(10));
+
     let api_routes = Router::new()
         .merge(analysis_routes)
+        .merge(upload_routes)
         .merge(video_routes)
         .merge(clip_routes)
         .merge(settings_routes)
         .merge(storage_routes)
         .merge(job_routes)
         .merge(credit_routes)
+        .merge(channel_routes)
+        .merge(webhook_routes)
+        .merge(api_key_routes)
         .merge(admin_routes)
         .layer(middleware::from_fn_with_state(
             rate_limiter.clone(),
             rate_limit_middleware,
         ));
 
-    // Public share resolution route (no auth required, but rate-limited)
+    // Public share resolution and oEmbed routes (no auth required, but rate-limited)
     let share_routes = Router::new()
         .route("/c/:share_slug", get(resolve_share))
+        .route("/oembed", get(oembed))
         .layer(middleware::from_fn_with_state(
             share_rate_limiter,
             rate_limit_middleware,
         ));
 
+    // Unlock interstitial for passcode/allowed-viewer protected shares
+    let share_unlock_routes = Router::new()
+        .route("/c/:share_slug/unlock", post(unlock_share))
+        .layer(middleware::from_fn_with_state(
+            share_unlock_rate_limiter,
+            rate_limit_middleware,
+        ));
+
+    // Share status for the CDN Worker (edge-cached, so the general limit applies)
+    let share_status_routes = Router::new()
+        .route("/c/:share_slug/status", get(share_status))
+        .layer(middleware::from_fn_with_state(
+            rate_limiter,
+            rate_limit_middleware,
+        ));
+
     // WebSocket routes removed - using Firebase-only architecture for status updates
 
     let health_routes = Router::new()
@@ -180,7 +263,9 @@ pub fn create_router(state: AppState, metrics_handle: Option<PrometheusHandle>)
 
     Router::new()
         .nest("/api", api_routes)
-        .merge(share_routes) // Public /c/{share_slug} route
+        .merge(share_routes) // Public /c/{share_slug} and /oembed routes
+        .merge(share_unlock_routes)
+        .merge(share_status_routes)
         .merge(health_routes)
         .merge(metrics_routes)
         // SECURITY: Request body size limit to prevent DoS attacks
@@ -1,7 +1,8 @@
 //! Security utilities for input validation and sanitization.
 //!
 //! This module provides:
-//! - URL validation with whitelist support (SSRF protection)
+//! - URL validation with whitelist support (SSRF protection), and Twitch/Kick
+//!   VOD and clip canonicalization
 //! - Input sanitization utilities
 //! - Rate limiter cache with TTL cleanup
 
@@ -11,6 +12,7 @@ use std::sync::LazyLock;
 use regex::Regex;
 use tracing::warn;
 use url::Url;
+use vclip_models::{is_stream_platform_url, live_channel_url, parse_stream_url};
 
 /// Maximum URL length to prevent DoS attacks.
 const MAX_URL_LENGTH: usize = 2048;
@@ -63,6 +65,10 @@ static ALLOWED_DOMAINS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
         "twitch.tv",
         "www.twitch.tv",
         "clips.twitch.tv",
+        "m.twitch.tv",
+        // Kick
+        "kick.com",
+        "www.kick.com",
         // Streamable
         "streamable.com",
         "www.streamable.com",
@@ -178,10 +184,38 @@ pub fn validate_video_url(url: &str) -> UrlValidationResult {
         return UrlValidationResult::DomainNotAllowed(domain);
     }
 
+    // Twitch and Kick sources must be a VOD or clip; use their canonical URL
+    if is_stream_platform_url(url) {
+        return match parse_stream_url(url) {
+            Ok(stream_ref) => UrlValidationResult::Valid(stream_ref.canonical_url()),
+            Err(e) => UrlValidationResult::Invalid(e.to_string()),
+        };
+    }
+
     // URL is valid
     UrlValidationResult::Valid(url.to_string())
 }
 
+/// Validate a live stream URL.
+///
+/// Twitch and Kick sources must be a channel and are normalized to its
+/// canonical URL; other platforms are validated like any video URL.
+pub fn validate_live_url(url: &str) -> UrlValidationResult {
+    if url.len() > MAX_URL_LENGTH {
+        return UrlValidationResult::TooLong;
+    }
+
+    let url = url.trim();
+    if is_stream_platform_url(url) {
+        return match live_channel_url(url) {
+            Ok(channel_url) => UrlValidationResult::Valid(channel_url),
+            Err(e) => UrlValidationResult::Invalid(e.to_string()),
+        };
+    }
+
+    validate_video_url(url)
+}
+
 /// Check if a domain or any of its parent domains are in the whitelist.
 fn is_domain_allowed(domain: &str) -> bool {
     // Direct match
@@ -272,6 +306,42 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_stream_platform_urls() {
+        assert!(matches!(
+            validate_video_url("https://m.twitch.tv/streamer/video/2101234567?t=10s"),
+            UrlValidationResult::Valid(url) if url == "https://www.twitch.tv/videos/2101234567"
+        ));
+        assert!(matches!(
+            validate_video_url("https://kick.com/streamer?clip=clip_01HXYZABC"),
+            UrlValidationResult::Valid(url) if url == "https://kick.com/streamer/clips/clip_01HXYZABC"
+        ));
+        assert!(matches!(
+            validate_video_url("https://www.twitch.tv/streamer"),
+            UrlValidationResult::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn test_live_urls() {
+        assert!(matches!(
+            validate_live_url("https://m.twitch.tv/Streamer"),
+            UrlValidationResult::Valid(url) if url == "https://www.twitch.tv/streamer"
+        ));
+        assert!(matches!(
+            validate_live_url("https://www.twitch.tv/videos/2101234567"),
+            UrlValidationResult::Invalid(_)
+        ));
+        assert!(matches!(
+            validate_live_url("https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+            UrlValidationResult::Valid(_)
+        ));
+        assert!(matches!(
+            validate_live_url("http://169.254.169.254/live"),
+            UrlValidationResult::Blocked(_)
+        ));
+    }
+
     #[test]
     fn test_blocked_internal_ips() {
         assert!(matches!(
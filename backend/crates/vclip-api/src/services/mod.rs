@@ -5,14 +5,17 @@
 //! - [`UserService`] - User management, plan limits, storage tracking
 //! - [`CreditService`] - Credit reservation, transaction recording, history
 //! - [`StaleJobDetector`] - Background job cleanup
-//! - [`gemini`] - Gemini AI client for scene generation
+//! - [`ShareStatsFlusher`] - Flushes buffered share link views to Firestore
+//! - [`StorageReconciler`] - R2 vs Firestore storage reconciliation
 
 pub mod credit;
-pub mod gemini;
+pub mod share_stats_flusher;
 pub mod stale_job_detector;
+pub mod storage_reconcile;
 pub mod user;
 
 pub use credit::CreditService;
-pub use gemini::GeminiClient;
+pub use share_stats_flusher::ShareStatsFlusher;
 pub use stale_job_detector::StaleJobDetector;
+pub use storage_reconcile::StorageReconciler;
 pub use user::UserService;
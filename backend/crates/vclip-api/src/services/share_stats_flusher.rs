@@ -0,0 +1,109 @@
+//! Background service that flushes buffered share link views to Firestore.
+//!
+//! `resolve_share` only increments counters in Redis. This service
+//! periodically drains the links that received views and applies each link's
+//! accumulated counters in a single Firestore write. Counters of a failed
+//! write are put back into the buffer for the next run.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+
+use vclip_firestore::{FirestoreClient, ShareRepository};
+use vclip_queue::ShareStatsBuffer;
+
+/// Interval between flushes.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Links drained per batch.
+const FLUSH_BATCH_SIZE: usize = 200;
+
+/// Share stats flusher service.
+pub struct ShareStatsFlusher {
+    buffer: Arc<ShareStatsBuffer>,
+    firestore: Arc<FirestoreClient>,
+}
+
+impl ShareStatsFlusher {
+    /// Create a new share stats flusher.
+    pub fn new(buffer: Arc<ShareStatsBuffer>, firestore: Arc<FirestoreClient>) -> Self {
+        Self { buffer, firestore }
+    }
+
+    /// Start the background flush loop.
+    ///
+    /// This function runs indefinitely and should be spawned as a background task.
+    pub async fn run(&self) {
+        info!("Starting share stats flusher (interval: {:?})", FLUSH_INTERVAL);
+
+        let mut ticker = interval(FLUSH_INTERVAL);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = self.flush().await {
+                error!("Share stats flush error: {}", e);
+            }
+        }
+    }
+
+    /// Flush all buffered counters.
+    async fn flush(&self) -> anyhow::Result<()> {
+        let share_repo = ShareRepository::new((*self.firestore).clone());
+        let mut flushed = 0usize;
+
+        loop {
+            let batch = self.buffer.drain(FLUSH_BATCH_SIZE).await?;
+            if batch.is_empty() {
+                break;
+            }
+            let last_batch = batch.len() < FLUSH_BATCH_SIZE;
+            let mut failed = false;
+
+            for pending in batch {
+                let result = share_repo
+                    .increment_stats(
+                        &pending.user_id,
+                        &pending.video_id,
+                        &pending.clip_id,
+                        &pending.share_slug,
+                        &pending.stats,
+                    )
+                    .await;
+
+                match result {
+                    Ok(()) => flushed += 1,
+                    Err(e) => {
+                        warn!(
+                            share_slug = %pending.share_slug,
+                            error = %e,
+                            "Failed to flush share stats, restoring"
+                        );
+                        // Keep going: the rest of the batch is already drained
+                        if let Err(e) = self.buffer.restore(&pending).await {
+                            error!(
+                                share_slug = %pending.share_slug,
+                                error = %e,
+                                "Failed to restore share stats, views lost"
+                            );
+                        }
+                        failed = true;
+                    }
+                }
+            }
+
+            // Restored links are dirty again; leave them for the next run
+            if last_batch || failed {
+                break;
+            }
+        }
+
+        if flushed > 0 {
+            debug!(links = flushed, "Flushed share stats");
+        }
+
+        Ok(())
+    }
+}
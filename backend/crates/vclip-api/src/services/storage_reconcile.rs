@@ -0,0 +1,544 @@
+//! Storage reconciliation between R2 and Firestore.
+//!
+//! `UserService::recalculate_storage` only recomputes totals from clip
+//! metadata, so objects left behind by failed uploads or crashed workers and
+//! clip records whose file is gone go unnoticed. Reconciliation walks every
+//! R2 prefix of a user, classifies each object into a `StorageAccounting`
+//! bucket and reports discrepancies in both directions:
+//!
+//! - Orphaned objects: files of videos that no longer exist, and styled clip
+//!   files no clip record references
+//! - Dangling clips: completed clip records whose file is missing
+//!
+//! Objects written within the last hour are never reported as orphaned, as
+//! their metadata may still be in flight.
+//!
+//! With repair enabled, orphaned objects and dangling records are deleted,
+//! styled totals are recalculated from the remaining clips and the cache and
+//! uploaded source buckets are set to what is actually stored.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use serde::Serialize;
+use tracing::{info, warn};
+
+use vclip_firestore::{
+    ClipRepository, FirestoreClient, StorageAccountingRepository, VideoRepository,
+};
+use vclip_models::{ClipMetadata, ClipStatus, StorageAccounting, VideoId};
+use vclip_storage::{ObjectInfo, R2Client};
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::UserService;
+
+/// Objects younger than this are never reported as orphaned (ms).
+const ORPHAN_GRACE_MS: u64 = 60 * 60 * 1000;
+
+/// Most keys a single S3 `DeleteObjects` request accepts.
+const DELETE_BATCH_SIZE: usize = 1000;
+
+/// `StorageAccounting` bucket an R2 object is counted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBucket {
+    StyledClips,
+    UploadedSources,
+    SourceVideos,
+    RawSegments,
+    NeuralCache,
+    /// Stored but not accounted (thumbnails, highlights, transcripts,
+    /// silence-removed segments)
+    Untracked,
+}
+
+/// Where an object of a user lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectLocation<'a> {
+    pub bucket: StorageBucket,
+    /// Video the object belongs to, if it is per-video
+    pub video_id: Option<&'a str>,
+}
+
+/// Classify an R2 key of `user_id`; `None` if it lives outside the user's prefixes.
+pub fn classify_object<'a>(user_id: &str, key: &'a str) -> Option<ObjectLocation<'a>> {
+    let location = |bucket, video_id| Some(ObjectLocation { bucket, video_id });
+
+    if let Some(rest) = key.strip_prefix("sources/") {
+        let video_id = rest
+            .strip_prefix(user_id)?
+            .strip_prefix('/')?
+            .split('/')
+            .next()?;
+        return location(StorageBucket::SourceVideos, Some(video_id));
+    }
+    if let Some(rest) = key.strip_prefix("uploads/") {
+        rest.strip_prefix(user_id)?.strip_prefix('/')?;
+        return location(StorageBucket::UploadedSources, None);
+    }
+    if let Some(rest) = key.strip_prefix("clips/") {
+        let mut parts = rest.strip_prefix(user_id)?.strip_prefix('/')?.split('/');
+        let video_id = parts.next()?;
+        let bucket = match parts.next()? {
+            "raw" => StorageBucket::RawSegments,
+            _ => StorageBucket::Untracked,
+        };
+        return location(bucket, Some(video_id));
+    }
+
+    let mut parts = key.strip_prefix(user_id)?.strip_prefix('/')?.split('/');
+    match (parts.next()?, parts.next()?) {
+        ("live", _) => location(StorageBucket::RawSegments, None),
+        ("transcripts", _) => location(StorageBucket::Untracked, None),
+        (video_id, "clips") => location(StorageBucket::StyledClips, Some(video_id)),
+        (video_id, "neural") => location(StorageBucket::NeuralCache, Some(video_id)),
+        (video_id, _) => location(StorageBucket::Untracked, Some(video_id)),
+    }
+}
+
+/// Recorded and measured size of one bucket.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BucketDiff {
+    /// Bytes in `StorageAccounting`
+    pub recorded_bytes: u64,
+    /// Bytes stored in R2, orphans included
+    pub stored_bytes: u64,
+    /// Bytes the accounting should hold once orphans and dangling clips are gone
+    pub expected_bytes: u64,
+}
+
+/// An R2 object no Firestore record accounts for.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrphanedObject {
+    pub key: String,
+    pub size: u64,
+    pub bucket: StorageBucket,
+    /// `video_missing` or `clip_missing`
+    pub reason: &'static str,
+}
+
+/// A completed clip record whose file is missing from R2.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DanglingClip {
+    pub video_id: String,
+    pub clip_id: String,
+    pub r2_key: String,
+}
+
+/// Outcome of reconciling one user's storage.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReconcileReport {
+    pub uid: String,
+    /// Whether the discrepancies below were repaired
+    pub repaired: bool,
+    pub objects_scanned: u32,
+    pub buckets: BTreeMap<StorageBucket, BucketDiff>,
+    pub recorded_clips_count: u32,
+    pub expected_clips_count: u32,
+    pub orphaned_objects: Vec<OrphanedObject>,
+    pub orphaned_bytes: u64,
+    pub dangling_clips: Vec<DanglingClip>,
+}
+
+impl ReconcileReport {
+    /// Whether R2 and Firestore agree.
+    pub fn is_consistent(&self) -> bool {
+        self.orphaned_objects.is_empty()
+            && self.dangling_clips.is_empty()
+            && self.recorded_clips_count == self.expected_clips_count
+            && self
+                .buckets
+                .iter()
+                .filter(|(bucket, _)| **bucket != StorageBucket::Untracked)
+                .all(|(_, diff)| diff.recorded_bytes == diff.expected_bytes)
+    }
+}
+
+/// Diff a user's R2 objects against their videos, clips and accounting.
+pub fn diff_storage(
+    uid: &str,
+    objects: &[ObjectInfo],
+    video_ids: &HashSet<String>,
+    clips: &[ClipMetadata],
+    accounting: &StorageAccounting,
+    now_ms: u64,
+) -> ReconcileReport {
+    let stored_keys: HashSet<&str> = objects.iter().map(|o| o.key.as_str()).collect();
+
+    // Files referenced by clip records; thumbnails are stored but not accounted
+    let mut clip_files: HashSet<&str> = HashSet::new();
+    let mut thumbnails: HashSet<String> = HashSet::new();
+    for clip in clips {
+        clip_files.insert(&clip.r2_key);
+        clip_files.extend(clip.variants.iter().map(|v| v.r2_key.as_str()));
+        thumbnails.insert(clip.r2_key.replace(".mp4", ".jpg"));
+        thumbnails.extend(clip.thumbnail_r2_key.clone());
+    }
+
+    let mut report = ReconcileReport {
+        uid: uid.to_string(),
+        recorded_clips_count: accounting.styled_clips_count,
+        ..Default::default()
+    };
+
+    for obj in objects {
+        let Some(location) = classify_object(uid, &obj.key) else {
+            continue;
+        };
+        report.objects_scanned += 1;
+
+        let bucket = match location.bucket {
+            StorageBucket::StyledClips if thumbnails.contains(&obj.key) => StorageBucket::Untracked,
+            bucket => bucket,
+        };
+        let reason = if location.video_id.is_some_and(|v| !video_ids.contains(v)) {
+            Some("video_missing")
+        } else if bucket == StorageBucket::StyledClips && !clip_files.contains(obj.key.as_str()) {
+            Some("clip_missing")
+        } else {
+            None
+        };
+        let settled = obj
+            .last_modified
+            .is_some_and(|ms| now_ms.saturating_sub(ms) >= ORPHAN_GRACE_MS);
+
+        let diff = report.buckets.entry(bucket).or_default();
+        diff.stored_bytes += obj.size;
+        match reason {
+            Some(reason) if settled => {
+                report.orphaned_bytes += obj.size;
+                report.orphaned_objects.push(OrphanedObject {
+                    key: obj.key.clone(),
+                    size: obj.size,
+                    bucket,
+                    reason,
+                });
+            }
+            // Styled bytes come from clip records below
+            _ if bucket == StorageBucket::StyledClips => {}
+            _ => diff.expected_bytes += obj.size,
+        }
+    }
+
+    for clip in clips {
+        if clip.status == ClipStatus::Completed && !stored_keys.contains(clip.r2_key.as_str()) {
+            report.dangling_clips.push(DanglingClip {
+                video_id: clip.video_id.to_string(),
+                clip_id: clip.clip_id.clone(),
+                r2_key: clip.r2_key.clone(),
+            });
+            continue;
+        }
+        report.expected_clips_count += 1;
+        report
+            .buckets
+            .entry(StorageBucket::StyledClips)
+            .or_default()
+            .expected_bytes += clip.total_size_bytes();
+    }
+
+    for (bucket, recorded) in [
+        (StorageBucket::StyledClips, accounting.styled_clips_bytes),
+        (StorageBucket::UploadedSources, accounting.uploaded_sources_bytes),
+        (StorageBucket::SourceVideos, accounting.source_videos_bytes),
+        (StorageBucket::RawSegments, accounting.raw_segments_bytes),
+        (StorageBucket::NeuralCache, accounting.neural_cache_bytes),
+    ] {
+        report.buckets.entry(bucket).or_default().recorded_bytes = recorded;
+    }
+
+    report
+}
+
+/// Reconciles a user's R2 objects with their Firestore records.
+pub struct StorageReconciler {
+    storage: Arc<R2Client>,
+    firestore: Arc<FirestoreClient>,
+}
+
+impl StorageReconciler {
+    /// Create a new reconciler.
+    pub fn new(storage: Arc<R2Client>, firestore: Arc<FirestoreClient>) -> Self {
+        Self { storage, firestore }
+    }
+
+    /// Reconcile a user's storage, repairing discrepancies if `repair` is set.
+    pub async fn reconcile_user(
+        &self,
+        user_service: &UserService,
+        uid: &str,
+        repair: bool,
+    ) -> ApiResult<ReconcileReport> {
+        let video_repo = VideoRepository::new((*self.firestore).clone(), uid);
+        let mut video_ids = HashSet::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let (videos, next) = video_repo
+                .list_page(Some(300), page_token.as_deref())
+                .await
+                .map_err(|e| ApiError::internal(format!("Failed to list videos: {}", e)))?;
+            video_ids.extend(videos.into_iter().map(|v| v.video_id.to_string()));
+            match next {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => break,
+            }
+        }
+
+        let mut clips = Vec::new();
+        for video_id in &video_ids {
+            let clip_repo = ClipRepository::new(
+                (*self.firestore).clone(),
+                uid,
+                VideoId::from(video_id.as_str()),
+            );
+            clips.extend(
+                clip_repo
+                    .list(None)
+                    .await
+                    .map_err(|e| ApiError::internal(format!("Failed to list clips: {}", e)))?,
+            );
+        }
+
+        let mut objects = Vec::new();
+        for prefix in [
+            format!("{}/", uid),
+            format!("clips/{}/", uid),
+            format!("sources/{}/", uid),
+            format!("uploads/{}/", uid),
+        ] {
+            objects.extend(
+                self.storage
+                    .list_objects(&prefix)
+                    .await
+                    .map_err(|e| ApiError::internal(format!("Failed to list objects: {}", e)))?,
+            );
+        }
+
+        let accounting_repo = StorageAccountingRepository::new((*self.firestore).clone(), uid);
+        let accounting = accounting_repo
+            .get_or_create()
+            .await
+            .map_err(|e| ApiError::internal(format!("Failed to get storage accounting: {}", e)))?;
+
+        let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        let mut report = diff_storage(uid, &objects, &video_ids, &clips, &accounting, now_ms);
+
+        info!(
+            "Reconciled storage for user {}: {} objects, {} orphaned ({} bytes), {} dangling clips",
+            uid,
+            report.objects_scanned,
+            report.orphaned_objects.len(),
+            report.orphaned_bytes,
+            report.dangling_clips.len()
+        );
+
+        if repair {
+            self.repair(user_service, &report).await?;
+            report.repaired = true;
+        }
+
+        Ok(report)
+    }
+
+    async fn repair(&self, user_service: &UserService, report: &ReconcileReport) -> ApiResult<()> {
+        let keys: Vec<String> = report
+            .orphaned_objects
+            .iter()
+            .map(|o| o.key.clone())
+            .collect();
+        for batch in keys.chunks(DELETE_BATCH_SIZE) {
+            self.storage.delete_objects(batch).await.map_err(|e| {
+                ApiError::internal(format!("Failed to delete orphaned objects: {}", e))
+            })?;
+        }
+
+        for clip in &report.dangling_clips {
+            let clip_repo = ClipRepository::new(
+                (*self.firestore).clone(),
+                &report.uid,
+                VideoId::from(clip.video_id.as_str()),
+            );
+            if let Err(e) = clip_repo.delete(&clip.clip_id).await {
+                warn!("Failed to delete dangling clip {}: {}", clip.clip_id, e);
+            }
+        }
+
+        // Styled totals (user document, videos and accounting) follow the clip records
+        user_service.recalculate_storage(&report.uid).await?;
+
+        let expected = |bucket| {
+            report
+                .buckets
+                .get(&bucket)
+                .map_or(0, |d: &BucketDiff| d.expected_bytes)
+        };
+        let accounting_repo =
+            StorageAccountingRepository::new((*self.firestore).clone(), &report.uid);
+        accounting_repo
+            .set_cache(
+                expected(StorageBucket::SourceVideos),
+                expected(StorageBucket::RawSegments),
+                expected(StorageBucket::NeuralCache),
+            )
+            .await
+            .map_err(|e| {
+                ApiError::internal(format!("Failed to update storage accounting: {}", e))
+            })?;
+        accounting_repo
+            .set_uploaded_sources(expected(StorageBucket::UploadedSources))
+            .await
+            .map_err(|e| {
+                ApiError::internal(format!("Failed to update storage accounting: {}", e))
+            })?;
+
+        info!(
+            "Repaired storage for user {}: deleted {} orphaned objects and {} dangling clips",
+            report.uid,
+            keys.len(),
+            report.dangling_clips.len()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 100 * ORPHAN_GRACE_MS;
+
+    fn object(key: &str, size: u64) -> ObjectInfo {
+        ObjectInfo {
+            key: key.to_string(),
+            size,
+            last_modified: Some(NOW - 2 * ORPHAN_GRACE_MS),
+        }
+    }
+
+    fn clip(video_id: &str, name: &str, size: u64, status: ClipStatus) -> ClipMetadata {
+        let json = serde_json::json!({
+            "clip_id": name,
+            "video_id": video_id,
+            "user_id": "u",
+            "scene_id": 1,
+            "scene_title": "Scene",
+            "filename": format!("{}.mp4", name),
+            "style": "split",
+            "start_time": "00:00:00",
+            "end_time": "00:00:10",
+            "duration_seconds": 10.0,
+            "file_size_bytes": size,
+            "file_size_mb": 0.0,
+            "has_thumbnail": true,
+            "r2_key": format!("u/{}/clips/{}.mp4", video_id, name),
+            "status": status,
+            "created_at": "2026-01-01T00:00:00Z",
+            "created_by": "u",
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_classify_object() {
+        let at = |key| classify_object("u", key).map(|l| (l.bucket, l.video_id));
+        assert_eq!(
+            at("sources/u/v1/source.mp4"),
+            Some((StorageBucket::SourceVideos, Some("v1")))
+        );
+        assert_eq!(
+            at("clips/u/v1/raw/1.mp4"),
+            Some((StorageBucket::RawSegments, Some("v1")))
+        );
+        assert_eq!(
+            at("clips/u/v1/silence_removed/1.mp4"),
+            Some((StorageBucket::Untracked, Some("v1")))
+        );
+        assert_eq!(
+            at("u/v1/clips/a.mp4"),
+            Some((StorageBucket::StyledClips, Some("v1")))
+        );
+        assert_eq!(
+            at("u/v1/neural/1.json.gz"),
+            Some((StorageBucket::NeuralCache, Some("v1")))
+        );
+        assert_eq!(
+            at("u/v1/highlights.json"),
+            Some((StorageBucket::Untracked, Some("v1")))
+        );
+        assert_eq!(
+            at("u/live/d1/seg_00000.mp4"),
+            Some((StorageBucket::RawSegments, None))
+        );
+        assert_eq!(
+            at("u/transcripts/v1.txt.gz"),
+            Some((StorageBucket::Untracked, None))
+        );
+        assert_eq!(at("uu/v1/clips/a.mp4"), None);
+        assert_eq!(
+            at("uploads/u/up1/source"),
+            Some((StorageBucket::UploadedSources, None))
+        );
+        assert_eq!(at("sources/uu/v1/source.mp4"), None);
+        assert_eq!(at("uploads/uu/up1/source"), None);
+    }
+
+    #[test]
+    fn test_diff_reports_both_directions() {
+        let objects = vec![
+            object("u/v1/clips/a.mp4", 100),
+            object("u/v1/clips/a.jpg", 5),
+            object("u/v1/clips/stray.mp4", 40),
+            object("u/v1/neural/1.json.gz", 10),
+            object("sources/u/v1/source.mp4", 1000),
+            object("clips/u/gone/raw/1.mp4", 50),
+            ObjectInfo {
+                last_modified: Some(NOW),
+                ..object("u/v1/clips/rendering.mp4", 70)
+            },
+        ];
+        let video_ids: HashSet<String> = ["v1".to_string()].into();
+        let clips = vec![
+            clip("v1", "a", 100, ClipStatus::Completed),
+            clip("v1", "b", 80, ClipStatus::Completed),
+            clip("v1", "c", 0, ClipStatus::Processing),
+        ];
+        let accounting = StorageAccounting {
+            styled_clips_bytes: 180,
+            styled_clips_count: 3,
+            uploaded_sources_bytes: 0,
+            source_videos_bytes: 1000,
+            raw_segments_bytes: 50,
+            neural_cache_bytes: 0,
+            updated_at: None,
+        };
+
+        let report = diff_storage("u", &objects, &video_ids, &clips, &accounting, NOW);
+
+        let orphans: Vec<(&str, &str)> = report
+            .orphaned_objects
+            .iter()
+            .map(|o| (o.key.as_str(), o.reason))
+            .collect();
+        assert_eq!(
+            orphans,
+            vec![
+                ("u/v1/clips/stray.mp4", "clip_missing"),
+                ("clips/u/gone/raw/1.mp4", "video_missing")
+            ]
+        );
+        assert_eq!(report.orphaned_bytes, 90);
+
+        assert_eq!(report.dangling_clips.len(), 1);
+        assert_eq!(report.dangling_clips[0].clip_id, "b");
+        assert_eq!(report.expected_clips_count, 2);
+
+        let bucket = |b| report.buckets[&b].clone();
+        assert_eq!(bucket(StorageBucket::StyledClips).stored_bytes, 210);
+        assert_eq!(bucket(StorageBucket::StyledClips).expected_bytes, 100);
+        assert_eq!(bucket(StorageBucket::Untracked).stored_bytes, 5);
+        assert_eq!(bucket(StorageBucket::RawSegments).expected_bytes, 0);
+        assert_eq!(bucket(StorageBucket::NeuralCache).expected_bytes, 10);
+        assert_eq!(bucket(StorageBucket::SourceVideos).expected_bytes, 1000);
+        assert!(!report.is_consistent());
+    }
+}
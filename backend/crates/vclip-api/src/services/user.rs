@@ -29,6 +29,9 @@ pub struct UserSettings {
     pub default_crop_mode: Option<String>,
     #[serde(default)]
     pub default_target_aspect: Option<String>,
+    /// Preferred highlight detection provider (`gemini`, `openai`, `heuristic`)
+    #[serde(default)]
+    pub highlight_provider: Option<String>,
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
 }
@@ -275,6 +278,10 @@ impl UserService {
         
         // Merge settings
         for (key, value) in settings {
+            if key == "highlight_provider" {
+                user.settings.highlight_provider = parse_highlight_provider_setting(&value)?;
+                continue;
+            }
             user.settings.extra.insert(key, value);
         }
         user.updated_at = Utc::now();
@@ -295,6 +302,9 @@ impl UserService {
         if let Some(aspect) = user.settings.default_target_aspect {
             result.insert("default_target_aspect".to_string(), serde_json::json!(aspect));
         }
+        if let Some(provider) = user.settings.highlight_provider {
+            result.insert("highlight_provider".to_string(), serde_json::json!(provider));
+        }
         
         Ok(result)
     }
@@ -900,7 +910,7 @@ impl UserService {
             );
             
             let clips = clip_repo.list(None).await.unwrap_or_default();
-            let video_size: u64 = clips.iter().map(|c| c.file_size_bytes).sum();
+            let video_size: u64 = clips.iter().map(|c| c.total_size_bytes()).sum();
             let video_clips = clips.len() as u32;
             
             total_bytes += video_size;
@@ -976,7 +986,12 @@ fn parse_user_document(doc: &vclip_firestore::Document) -> ApiResult<UserRecord>
             .get("updated_at")
             .and_then(|v| chrono::DateTime::from_firestore_value(v))
             .unwrap_or_else(Utc::now),
-        settings: UserSettings::default(), // TODO: parse nested settings
+        // TODO: parse nested settings. highlight_provider is stored top-level so the
+        // worker can read it without the settings map.
+        settings: UserSettings {
+            highlight_provider: get_string("highlight_provider"),
+            ..Default::default()
+        },
         role: get_string("role"),
         credits_used_this_month: get_u32("credits_used_this_month"),
         usage_reset_month: get_string("usage_reset_month"),
@@ -1097,5 +1112,23 @@ fn user_to_fields(user: &UserRecord) -> HashMap<String, Value> {
         "total_clips_count".to_string(),
         user.total_clips_count.to_firestore_value(),
     );
+    if let Some(ref provider) = user.settings.highlight_provider {
+        fields.insert(
+            "highlight_provider".to_string(),
+            provider.to_firestore_value(),
+        );
+    }
     fields
 }
+
+/// Validate a `highlight_provider` settings value; `null` clears the override.
+fn parse_highlight_provider_setting(value: &serde_json::Value) -> ApiResult<Option<String>> {
+    match value {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::String(name) => name
+            .parse::<vclip_highlights::HighlightProviderKind>()
+            .map(|kind| Some(kind.as_str().to_string()))
+            .map_err(|e| ApiError::bad_request(e.to_string())),
+        _ => Err(ApiError::bad_request("highlight_provider must be a string")),
+    }
+}
@@ -3,11 +3,13 @@
 use std::sync::Arc;
 
 use vclip_firestore::FirestoreClient;
-use vclip_queue::{JobQueue, ProgressChannel};
+use vclip_highlights::HighlightProviders;
+use vclip_queue::{JobQueue, ProgressChannel, ShareStatsBuffer, ShareUnlockGuard, WebhookQueue};
 use vclip_storage::R2Client;
 
 use crate::auth::JwksCache;
 use crate::config::ApiConfig;
+use crate::middleware::{create_api_key_rate_limiter, ApiKeyLookupLimiter, ApiKeyRateLimiter};
 use crate::services::UserService;
 
 /// Shared application state.
@@ -18,8 +20,14 @@ pub struct AppState {
     pub firestore: Arc<FirestoreClient>,
     pub queue: Arc<JobQueue>,
     pub progress: Arc<ProgressChannel>,
+    pub webhooks: Arc<WebhookQueue>,
+    pub share_stats: Arc<ShareStatsBuffer>,
+    pub share_unlock: Arc<ShareUnlockGuard>,
     pub jwks: Arc<JwksCache>,
+    pub api_key_limiter: Arc<ApiKeyRateLimiter>,
+    pub api_key_lookups: Arc<ApiKeyLookupLimiter>,
     pub user_service: UserService,
+    pub highlight_providers: HighlightProviders,
 }
 
 impl AppState {
@@ -31,8 +39,16 @@ impl AppState {
 
         let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
         let progress = ProgressChannel::new(&redis_url)?;
+        let webhooks = WebhookQueue::new(&redis_url)?;
+        let share_stats = ShareStatsBuffer::new(&redis_url)?;
+        let share_unlock = ShareUnlockGuard::new(&redis_url)?;
 
         let jwks = JwksCache::new().await?;
+        let api_key_limiter = create_api_key_rate_limiter(
+            config.api_key_rate_limit_rpm,
+            config.api_key_rate_limit_burst,
+        );
+        let highlight_providers = HighlightProviders::from_env()?;
         
         let storage_arc = Arc::new(storage);
         let firestore_arc = Arc::new(firestore);
@@ -44,8 +60,14 @@ impl AppState {
             firestore: firestore_arc,
             queue: Arc::new(queue),
             progress: Arc::new(progress),
+            webhooks: Arc::new(webhooks),
+            share_stats: Arc::new(share_stats),
+            share_unlock: Arc::new(share_unlock),
             jwks: Arc::new(jwks),
+            api_key_limiter,
+            api_key_lookups: Arc::new(ApiKeyLookupLimiter::default()),
             user_service,
+            highlight_providers,
         })
     }
 }
@@ -0,0 +1,51 @@
+//! Admin audit log repository for Firestore.
+//!
+//! Entries live in the top-level `admin_audit` collection and are only
+//! ever appended.
+
+use std::collections::HashMap;
+
+use tracing::info;
+
+use vclip_models::AdminAuditEntry;
+
+use crate::client::FirestoreClient;
+use crate::error::FirestoreResult;
+use crate::types::{ToFirestoreValue, Value};
+
+/// Collection holding audit entries.
+const AUDIT_COLLECTION: &str = "admin_audit";
+
+/// Repository for the admin audit log.
+pub struct AdminAuditRepository {
+    client: FirestoreClient,
+}
+
+impl AdminAuditRepository {
+    /// Create a new audit repository.
+    pub fn new(client: FirestoreClient) -> Self {
+        Self { client }
+    }
+
+    /// Record an admin action.
+    pub async fn record(&self, entry: &AdminAuditEntry) -> FirestoreResult<()> {
+        self.client
+            .create_document(AUDIT_COLLECTION, &entry.id, entry_to_fields(entry))
+            .await?;
+        info!(
+            "Recorded admin action {} by {} ({})",
+            entry.action, entry.admin_uid, entry.id
+        );
+        Ok(())
+    }
+}
+
+fn entry_to_fields(entry: &AdminAuditEntry) -> HashMap<String, Value> {
+    let mut fields = HashMap::new();
+    fields.insert("id".to_string(), entry.id.to_firestore_value());
+    fields.insert("admin_uid".to_string(), entry.admin_uid.to_firestore_value());
+    fields.insert("action".to_string(), entry.action.to_firestore_value());
+    fields.insert("details".to_string(), entry.details.to_firestore_value());
+    fields.insert("created_at".to_string(), entry.created_at.to_firestore_value());
+    fields
+}
@@ -5,11 +5,14 @@ use std::collections::HashMap;
 use chrono::Utc;
 use tracing::info;
 
-use vclip_models::{AnalysisDraft, AnalysisStatus, DraftScene};
+use vclip_models::{
+    AnalysisDraft, AnalysisStatus, DraftScene, LiveCapture, LiveCaptureMode, LiveCaptureStatus,
+};
 
 use crate::client::FirestoreClient;
 use crate::error::{FirestoreError, FirestoreResult};
-use crate::types::{FromFirestoreValue, ToFirestoreValue, Value};
+use crate::highlights_repo::{quality_metrics_to_value, value_to_quality_metrics};
+use crate::types::{FromFirestoreValue, MapValue, ToFirestoreValue, Value};
 
 /// Repository for analysis draft documents.
 pub struct AnalysisDraftRepository {
@@ -141,6 +144,49 @@ impl AnalysisDraftRepository {
         Ok(())
     }
 
+    /// Record live capture progress and the number of scenes published so far.
+    ///
+    /// The draft stays `live` while recording; finish it with
+    /// [`update_completion`](Self::update_completion).
+    pub async fn update_live_capture(
+        &self,
+        draft_id: &str,
+        live_capture: &LiveCapture,
+        scene_count: u32,
+        video_title: Option<String>,
+    ) -> FirestoreResult<()> {
+        let mut fields = HashMap::new();
+        fields.insert(
+            "live_capture".to_string(),
+            live_capture_to_value(live_capture),
+        );
+        fields.insert("scene_count".to_string(), scene_count.to_firestore_value());
+        fields.insert("updated_at".to_string(), Utc::now().to_firestore_value());
+
+        let mut update_mask = vec![
+            "live_capture".to_string(),
+            "scene_count".to_string(),
+            "updated_at".to_string(),
+        ];
+
+        if live_capture.status == LiveCaptureStatus::Recording {
+            fields.insert(
+                "status".to_string(),
+                AnalysisStatus::Live.as_str().to_firestore_value(),
+            );
+            update_mask.push("status".to_string());
+        }
+        if let Some(title) = video_title {
+            fields.insert("video_title".to_string(), title.to_firestore_value());
+            update_mask.push("video_title".to_string());
+        }
+
+        self.client
+            .update_document(&self.collection(), draft_id, fields, Some(update_mask))
+            .await?;
+        Ok(())
+    }
+
     /// List all analysis drafts for the user.
     pub async fn list(&self, limit: Option<u32>) -> FirestoreResult<Vec<AnalysisDraft>> {
         let response = self.client.list_documents(&self.collection(), limit, None).await?;
@@ -221,18 +267,25 @@ impl AnalysisDraftRepository {
         Ok(scenes)
     }
 
+    /// List drafts of this user that have expired.
+    pub async fn list_expired(&self) -> FirestoreResult<Vec<AnalysisDraft>> {
+        let now = Utc::now();
+        Ok(self
+            .list(None)
+            .await?
+            .into_iter()
+            .filter(|draft| draft.expires_at < now)
+            .collect())
+    }
+
     /// Delete expired drafts for this user.
     /// Returns the number of drafts deleted.
     pub async fn delete_expired(&self) -> FirestoreResult<u32> {
-        let drafts = self.list(None).await?;
-        let now = Utc::now();
         let mut deleted = 0;
 
-        for draft in drafts {
-            if draft.expires_at < now {
-                self.delete(&draft.id).await?;
-                deleted += 1;
-            }
+        for draft in self.list_expired().await? {
+            self.delete(&draft.id).await?;
+            deleted += 1;
         }
 
         if deleted > 0 {
@@ -261,6 +314,9 @@ fn draft_to_fields(draft: &AnalysisDraft) -> HashMap<String, Value> {
     fields.insert("updated_at".to_string(), draft.updated_at.to_firestore_value());
     fields.insert("expires_at".to_string(), draft.expires_at.to_firestore_value());
 
+    if let Some(ref r2_key) = draft.source_r2_key {
+        fields.insert("source_r2_key".to_string(), r2_key.to_firestore_value());
+    }
     if let Some(ref title) = draft.video_title {
         fields.insert("video_title".to_string(), title.to_firestore_value());
     }
@@ -273,10 +329,77 @@ fn draft_to_fields(draft: &AnalysisDraft) -> HashMap<String, Value> {
     if let Some(ref request_id) = draft.request_id {
         fields.insert("request_id".to_string(), request_id.to_firestore_value());
     }
+    if let Some(ref live_capture) = draft.live_capture {
+        fields.insert("live_capture".to_string(), live_capture_to_value(live_capture));
+    }
 
     fields
 }
 
+fn live_capture_to_value(live: &LiveCapture) -> Value {
+    let mut fields = HashMap::new();
+    let capture_mode = match live.capture_mode {
+        LiveCaptureMode::FromStart => "from_start",
+        LiveCaptureMode::FromNow => "from_now",
+    };
+
+    fields.insert("job_id".to_string(), live.job_id.to_firestore_value());
+    fields.insert("capture_mode".to_string(), capture_mode.to_firestore_value());
+    fields.insert("status".to_string(), live.status.as_str().to_firestore_value());
+    fields.insert("captured_secs".to_string(), live.captured_secs.to_firestore_value());
+    fields.insert("segment_count".to_string(), live.segment_count.to_firestore_value());
+    fields.insert(
+        "analyzed_until_secs".to_string(),
+        live.analyzed_until_secs.to_firestore_value(),
+    );
+    fields.insert(
+        "manifest_r2_key".to_string(),
+        live.manifest_r2_key.to_firestore_value(),
+    );
+    if let Some(started_at) = live.started_at {
+        fields.insert("started_at".to_string(), started_at.to_firestore_value());
+    }
+    if let Some(ended_at) = live.ended_at {
+        fields.insert("ended_at".to_string(), ended_at.to_firestore_value());
+    }
+    Value::MapValue(MapValue { fields: Some(fields) })
+}
+
+fn value_to_live_capture(value: &Value) -> Option<LiveCapture> {
+    let Value::MapValue(MapValue { fields: Some(fields) }) = value else {
+        return None;
+    };
+    let string = |key: &str| fields.get(key).and_then(String::from_firestore_value);
+    let number = |key: &str| fields.get(key).and_then(f64::from_firestore_value);
+
+    let capture_mode = match string("capture_mode").as_deref() {
+        Some("from_now") => LiveCaptureMode::FromNow,
+        _ => LiveCaptureMode::FromStart,
+    };
+
+    Some(LiveCapture {
+        job_id: string("job_id")?,
+        capture_mode,
+        status: string("status")
+            .as_deref()
+            .and_then(LiveCaptureStatus::parse)
+            .unwrap_or_default(),
+        captured_secs: number("captured_secs").unwrap_or(0.0),
+        segment_count: fields
+            .get("segment_count")
+            .and_then(u32::from_firestore_value)
+            .unwrap_or(0),
+        analyzed_until_secs: number("analyzed_until_secs").unwrap_or(0.0),
+        manifest_r2_key: string("manifest_r2_key").unwrap_or_default(),
+        started_at: fields
+            .get("started_at")
+            .and_then(chrono::DateTime::from_firestore_value),
+        ended_at: fields
+            .get("ended_at")
+            .and_then(chrono::DateTime::from_firestore_value),
+    })
+}
+
 fn document_to_draft(doc: &crate::types::Document, draft_id: &str) -> FirestoreResult<AnalysisDraft> {
     let fields = doc.fields.as_ref().ok_or_else(|| {
         FirestoreError::InvalidResponse("Document has no fields".to_string())
@@ -292,6 +415,10 @@ fn document_to_draft(doc: &crate::types::Document, draft_id: &str) -> FirestoreR
         .and_then(|v| String::from_firestore_value(v))
         .unwrap_or_default();
 
+    let source_r2_key = fields
+        .get("source_r2_key")
+        .and_then(|v| String::from_firestore_value(v));
+
     let status_str = fields
         .get("status")
         .and_then(|v| String::from_firestore_value(v))
@@ -301,6 +428,7 @@ fn document_to_draft(doc: &crate::types::Document, draft_id: &str) -> FirestoreR
         "pending" => AnalysisStatus::Pending,
         "downloading" => AnalysisStatus::Downloading,
         "analyzing" => AnalysisStatus::Analyzing,
+        "live" => AnalysisStatus::Live,
         "completed" => AnalysisStatus::Completed,
         "failed" => AnalysisStatus::Failed,
         "expired" => AnalysisStatus::Expired,
@@ -348,10 +476,13 @@ fn document_to_draft(doc: &crate::types::Document, draft_id: &str) -> FirestoreR
         .and_then(|v| chrono::DateTime::from_firestore_value(v))
         .unwrap_or_else(|| Utc::now() + chrono::Duration::days(7));
 
+    let live_capture = fields.get("live_capture").and_then(value_to_live_capture);
+
     Ok(AnalysisDraft {
         id: draft_id.to_string(),
         user_id,
         source_url,
+        source_r2_key,
         video_title,
         prompt_instructions,
         status,
@@ -362,6 +493,7 @@ fn document_to_draft(doc: &crate::types::Document, draft_id: &str) -> FirestoreR
         created_at,
         updated_at,
         expires_at,
+        live_capture,
     })
 }
 
@@ -395,6 +527,12 @@ fn scene_to_fields(scene: &DraftScene) -> HashMap<String, Value> {
     if let Some(ref category) = scene.hook_category {
         fields.insert("hook_category".to_string(), category.to_firestore_value());
     }
+    if let Some(score) = scene.virality_score {
+        fields.insert("virality_score".to_string(), score.to_firestore_value());
+    }
+    if let Some(ref quality) = scene.quality {
+        fields.insert("quality".to_string(), quality_metrics_to_value(quality));
+    }
 
     fields
 }
@@ -455,6 +593,12 @@ fn document_to_scene(doc: &crate::types::Document, draft_id: &str) -> FirestoreR
         .get("hook_category")
         .and_then(|v| String::from_firestore_value(v));
 
+    let virality_score = fields
+        .get("virality_score")
+        .and_then(f64::from_firestore_value);
+
+    let quality = fields.get("quality").and_then(value_to_quality_metrics);
+
     Ok(DraftScene {
         id,
         analysis_draft_id: draft_id.to_string(),
@@ -468,5 +612,43 @@ fn document_to_scene(doc: &crate::types::Document, draft_id: &str) -> FirestoreR
         pad_after,
         confidence,
         hook_category,
+        virality_score,
+        quality,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Document;
+
+    #[test]
+    fn test_live_draft_fields_roundtrip() {
+        let mut live = LiveCapture::new("job-1", LiveCaptureMode::FromNow, "u1/live/d1/manifest.json");
+        live.status = LiveCaptureStatus::Recording;
+        live.captured_secs = 912.5;
+        live.segment_count = 31;
+        live.analyzed_until_secs = 900.0;
+        live.started_at = Some(Utc::now());
+
+        let mut draft = AnalysisDraft::new("d1", "u1", "https://www.twitch.tv/streamer", 7)
+            .with_live_capture(live.clone());
+        draft.status = AnalysisStatus::Live;
+
+        let doc = Document::new(draft_to_fields(&draft));
+        let decoded = document_to_draft(&doc, "d1").unwrap();
+
+        assert_eq!(decoded.status, AnalysisStatus::Live);
+        let decoded_live = decoded.live_capture.unwrap();
+        assert_eq!(decoded_live.capture_mode, LiveCaptureMode::FromNow);
+        assert_eq!(decoded_live.status, LiveCaptureStatus::Recording);
+        assert_eq!(decoded_live.segment_count, 31);
+        assert_eq!(decoded_live.captured_secs, 912.5);
+        assert_eq!(decoded_live.manifest_r2_key, live.manifest_r2_key);
+        assert!(decoded_live.ended_at.is_none());
+
+        let plain = AnalysisDraft::new("d2", "u1", "https://youtu.be/dQw4w9WgXcQ", 7);
+        let doc = Document::new(draft_to_fields(&plain));
+        assert!(document_to_draft(&doc, "d2").unwrap().live_capture.is_none());
+    }
+}
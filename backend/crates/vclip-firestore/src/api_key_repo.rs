@@ -0,0 +1,354 @@
+//! API key repository.
+//!
+//! Uses a dual-document pattern:
+//! - Key doc at `users/{uid}/api_keys/{key_id}` for listing and management
+//! - Hash index at `api_key_hashes/{key_hash}` for lookups on every request
+//!
+//! Revoking a key deletes its hash index, so a revoked key stops
+//! authenticating immediately.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+use vclip_models::{ApiKey, ApiKeyScope};
+
+use crate::client::FirestoreClient;
+use crate::error::{FirestoreError, FirestoreResult};
+use crate::types::{
+    ArrayValue, Document, DocumentMask, FromFirestoreValue, Precondition, ToFirestoreValue, Value,
+    Write,
+};
+
+/// Collection ID of API keys (per user).
+const API_KEYS_COLLECTION: &str = "api_keys";
+
+/// Collection of hash index documents.
+const HASH_INDEX_COLLECTION: &str = "api_key_hashes";
+
+/// Minimal hash index document for authenticating a request.
+#[derive(Debug, Clone)]
+pub struct ApiKeyIndex {
+    pub key_hash: String,
+    pub key_id: String,
+    pub user_id: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// Repository for API keys (dual-document pattern).
+pub struct ApiKeyRepository {
+    client: FirestoreClient,
+}
+
+impl ApiKeyRepository {
+    /// Create a new API key repository.
+    pub fn new(client: FirestoreClient) -> Self {
+        Self { client }
+    }
+
+    /// Collection path for a user's keys.
+    fn collection(user_id: &str) -> String {
+        format!("users/{}/{}", user_id, API_KEYS_COLLECTION)
+    }
+
+    /// Store a new key and its hash index in one batch.
+    pub async fn create(&self, key: &ApiKey) -> FirestoreResult<()> {
+        let key_doc_name = self
+            .client
+            .full_document_name(&Self::collection(&key.user_id), &key.id);
+        let index_doc_name = self
+            .client
+            .full_document_name(HASH_INDEX_COLLECTION, &key.key_hash);
+
+        let writes = vec![
+            Write {
+                update: Some(Document {
+                    name: Some(key_doc_name),
+                    fields: Some(api_key_to_fields(key)),
+                    create_time: None,
+                    update_time: None,
+                }),
+                delete: None,
+                update_mask: None,
+                update_transforms: None,
+                current_document: None,
+            },
+            Write {
+                update: Some(Document {
+                    name: Some(index_doc_name),
+                    fields: Some(index_to_fields(key)),
+                    create_time: None,
+                    update_time: None,
+                }),
+                delete: None,
+                update_mask: None,
+                update_transforms: None,
+                current_document: Some(Precondition {
+                    exists: Some(false),
+                    update_time: None,
+                }),
+            },
+        ];
+
+        self.client.batch_write(writes).await?;
+
+        info!("Created API key {} for user {}", key.id, key.user_id);
+        Ok(())
+    }
+
+    /// Get a user's key by ID.
+    pub async fn get(&self, user_id: &str, key_id: &str) -> FirestoreResult<Option<ApiKey>> {
+        match self.client.get_document(&Self::collection(user_id), key_id).await? {
+            Some(doc) => Ok(Some(document_to_api_key(&doc, key_id)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List a user's keys (including revoked ones), newest first.
+    pub async fn list(&self, user_id: &str) -> FirestoreResult<Vec<ApiKey>> {
+        let response = self
+            .client
+            .list_documents(&Self::collection(user_id), None, None)
+            .await?;
+
+        let mut keys: Vec<ApiKey> = response
+            .documents
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|doc| {
+                let name = doc.name.as_ref()?;
+                let key_id = name.split('/').next_back()?;
+                document_to_api_key(doc, key_id).ok()
+            })
+            .collect();
+        keys.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        Ok(keys)
+    }
+
+    /// Look up an active key by the hash of its secret.
+    pub async fn get_by_hash(&self, key_hash: &str) -> FirestoreResult<Option<ApiKeyIndex>> {
+        match self.client.get_document(HASH_INDEX_COLLECTION, key_hash).await? {
+            Some(doc) => Ok(Some(document_to_index(&doc, key_hash)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Revoke a key: mark the key doc revoked and delete its hash index.
+    pub async fn revoke(&self, key: &ApiKey) -> FirestoreResult<()> {
+        let now = Utc::now();
+
+        let mut fields = HashMap::new();
+        fields.insert("revoked_at".to_string(), now.to_firestore_value());
+
+        let key_doc_name = self
+            .client
+            .full_document_name(&Self::collection(&key.user_id), &key.id);
+        let index_doc_name = self
+            .client
+            .full_document_name(HASH_INDEX_COLLECTION, &key.key_hash);
+
+        let writes = vec![
+            Write {
+                update: Some(Document {
+                    name: Some(key_doc_name),
+                    fields: Some(fields),
+                    create_time: None,
+                    update_time: None,
+                }),
+                delete: None,
+                update_mask: Some(DocumentMask {
+                    field_paths: vec!["revoked_at".to_string()],
+                }),
+                update_transforms: None,
+                current_document: Some(Precondition {
+                    exists: Some(true),
+                    update_time: None,
+                }),
+            },
+            Write {
+                update: None,
+                delete: Some(index_doc_name),
+                update_mask: None,
+                update_transforms: None,
+                current_document: None,
+            },
+        ];
+
+        self.client.batch_write(writes).await?;
+
+        info!("Revoked API key {} for user {}", key.id, key.user_id);
+        Ok(())
+    }
+
+    /// Record that a key was used.
+    ///
+    /// The index write requires the index to exist, so a key revoked
+    /// concurrently is not resurrected.
+    pub async fn touch(&self, index: &ApiKeyIndex, at: DateTime<Utc>) -> FirestoreResult<()> {
+        let mut fields = HashMap::new();
+        fields.insert("last_used_at".to_string(), at.to_firestore_value());
+
+        let mask = Some(DocumentMask {
+            field_paths: vec!["last_used_at".to_string()],
+        });
+        let exists = Some(Precondition {
+            exists: Some(true),
+            update_time: None,
+        });
+
+        let writes = vec![
+            Write {
+                update: Some(Document {
+                    name: Some(
+                        self.client
+                            .full_document_name(HASH_INDEX_COLLECTION, &index.key_hash),
+                    ),
+                    fields: Some(fields.clone()),
+                    create_time: None,
+                    update_time: None,
+                }),
+                delete: None,
+                update_mask: mask.clone(),
+                update_transforms: None,
+                current_document: exists.clone(),
+            },
+            Write {
+                update: Some(Document {
+                    name: Some(
+                        self.client
+                            .full_document_name(&Self::collection(&index.user_id), &index.key_id),
+                    ),
+                    fields: Some(fields),
+                    create_time: None,
+                    update_time: None,
+                }),
+                delete: None,
+                update_mask: mask,
+                update_transforms: None,
+                current_document: exists,
+            },
+        ];
+
+        self.client.batch_write(writes).await?;
+        Ok(())
+    }
+}
+
+fn scopes_to_value(scopes: &[ApiKeyScope]) -> Value {
+    scopes
+        .iter()
+        .map(|s| s.as_str().to_string())
+        .collect::<Vec<String>>()
+        .to_firestore_value()
+}
+
+fn scopes_from_value(value: Option<&Value>) -> Vec<ApiKeyScope> {
+    match value {
+        Some(Value::ArrayValue(ArrayValue { values: Some(values) })) => values
+            .iter()
+            .filter_map(String::from_firestore_value)
+            .filter_map(|s| ApiKeyScope::parse(&s))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn api_key_to_fields(key: &ApiKey) -> HashMap<String, Value> {
+    let mut fields = HashMap::new();
+
+    fields.insert("id".to_string(), key.id.to_firestore_value());
+    fields.insert("user_id".to_string(), key.user_id.to_firestore_value());
+    fields.insert("name".to_string(), key.name.to_firestore_value());
+    fields.insert("display_prefix".to_string(), key.display_prefix.to_firestore_value());
+    fields.insert("key_hash".to_string(), key.key_hash.to_firestore_value());
+    fields.insert("scopes".to_string(), scopes_to_value(&key.scopes));
+    fields.insert("created_at".to_string(), key.created_at.to_firestore_value());
+
+    if let Some(last_used_at) = key.last_used_at {
+        fields.insert("last_used_at".to_string(), last_used_at.to_firestore_value());
+    }
+    if let Some(revoked_at) = key.revoked_at {
+        fields.insert("revoked_at".to_string(), revoked_at.to_firestore_value());
+    }
+
+    fields
+}
+
+fn index_to_fields(key: &ApiKey) -> HashMap<String, Value> {
+    let mut fields = HashMap::new();
+
+    fields.insert("key_id".to_string(), key.id.to_firestore_value());
+    fields.insert("user_id".to_string(), key.user_id.to_firestore_value());
+    fields.insert("scopes".to_string(), scopes_to_value(&key.scopes));
+    fields.insert("created_at".to_string(), key.created_at.to_firestore_value());
+
+    fields
+}
+
+fn document_to_api_key(doc: &Document, key_id: &str) -> FirestoreResult<ApiKey> {
+    let fields = doc.fields.as_ref().ok_or_else(|| {
+        FirestoreError::InvalidResponse("API key document has no fields".to_string())
+    })?;
+
+    let string = |key: &str| fields.get(key).and_then(String::from_firestore_value);
+    let timestamp = |key: &str| fields.get(key).and_then(DateTime::from_firestore_value);
+
+    Ok(ApiKey {
+        id: key_id.to_string(),
+        user_id: string("user_id").unwrap_or_default(),
+        name: string("name").unwrap_or_default(),
+        display_prefix: string("display_prefix").unwrap_or_default(),
+        key_hash: string("key_hash").unwrap_or_default(),
+        scopes: scopes_from_value(fields.get("scopes")),
+        created_at: timestamp("created_at").unwrap_or_else(Utc::now),
+        last_used_at: timestamp("last_used_at"),
+        revoked_at: timestamp("revoked_at"),
+    })
+}
+
+fn document_to_index(doc: &Document, key_hash: &str) -> FirestoreResult<ApiKeyIndex> {
+    let fields = doc.fields.as_ref().ok_or_else(|| {
+        FirestoreError::InvalidResponse("API key index has no fields".to_string())
+    })?;
+
+    let string = |key: &str| {
+        fields
+            .get(key)
+            .and_then(String::from_firestore_value)
+            .ok_or_else(|| FirestoreError::InvalidResponse(format!("API key index missing {}", key)))
+    };
+
+    Ok(ApiKeyIndex {
+        key_hash: key_hash.to_string(),
+        key_id: string("key_id")?,
+        user_id: string("user_id")?,
+        scopes: scopes_from_value(fields.get("scopes")),
+        last_used_at: fields.get("last_used_at").and_then(DateTime::from_firestore_value),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_fields_roundtrip() {
+        let (key, _) = ApiKey::generate("u1", "ci", vec![ApiKeyScope::Read, ApiKeyScope::Render]);
+
+        let decoded = document_to_api_key(&Document::new(api_key_to_fields(&key)), &key.id).unwrap();
+        assert_eq!(decoded.user_id, "u1");
+        assert_eq!(decoded.name, "ci");
+        assert_eq!(decoded.key_hash, key.key_hash);
+        assert_eq!(decoded.scopes, vec![ApiKeyScope::Read, ApiKeyScope::Render]);
+        assert!(decoded.is_active());
+
+        let index = document_to_index(&Document::new(index_to_fields(&key)), &key.key_hash).unwrap();
+        assert_eq!(index.key_id, key.id);
+        assert_eq!(index.user_id, "u1");
+        assert_eq!(index.scopes, key.scopes);
+        assert_eq!(index.last_used_at, None);
+    }
+}
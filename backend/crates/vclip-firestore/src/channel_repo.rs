@@ -0,0 +1,281 @@
+//! Monitored channel repository for Firestore.
+//!
+//! Channels live under `users/{uid}/channels/{channel_id}`. The worker's
+//! poller reads every enabled channel with a collection group query.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use tracing::info;
+
+use vclip_models::{HighlightDetection, MonitoredChannel};
+
+use crate::client::FirestoreClient;
+use crate::error::{FirestoreError, FirestoreResult};
+use crate::types::{
+    ArrayValue, CollectionSelector, FieldFilter, FieldReference, Filter, FromFirestoreValue,
+    StructuredQuery, ToFirestoreValue, Value,
+};
+
+/// Collection ID of monitored channels (per user).
+const CHANNELS_COLLECTION: &str = "channels";
+
+/// Fields the user can change through the API.
+const SETTINGS_FIELDS: [&str; 4] = ["enabled", "highlight_detection", "prompt", "updated_at"];
+
+/// Fields written by the poller.
+const POLL_FIELDS: [&str; 6] = [
+    "title",
+    "seen_video_ids",
+    "uploads_queued",
+    "last_polled_at",
+    "last_error",
+    "updated_at",
+];
+
+/// Repository for a user's monitored channels.
+pub struct ChannelRepository {
+    client: FirestoreClient,
+    user_id: String,
+}
+
+impl ChannelRepository {
+    /// Create a new channel repository.
+    pub fn new(client: FirestoreClient, user_id: impl Into<String>) -> Self {
+        Self {
+            client,
+            user_id: user_id.into(),
+        }
+    }
+
+    /// Collection path for the user's channels.
+    fn collection(&self) -> String {
+        format!("users/{}/{}", self.user_id, CHANNELS_COLLECTION)
+    }
+
+    /// Add a channel.
+    pub async fn create(&self, channel: &MonitoredChannel) -> FirestoreResult<()> {
+        self.client
+            .create_document(&self.collection(), &channel.id, channel_to_fields(channel))
+            .await?;
+        info!(
+            "Added monitored channel {} ({}) for user {}",
+            channel.id, channel.channel_url, self.user_id
+        );
+        Ok(())
+    }
+
+    /// Get a channel by ID.
+    pub async fn get(&self, channel_id: &str) -> FirestoreResult<Option<MonitoredChannel>> {
+        match self.client.get_document(&self.collection(), channel_id).await? {
+            Some(doc) => Ok(Some(document_to_channel(&doc, channel_id)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List the user's channels, oldest first.
+    pub async fn list(&self) -> FirestoreResult<Vec<MonitoredChannel>> {
+        let response = self
+            .client
+            .list_documents(&self.collection(), None, None)
+            .await?;
+
+        let mut channels: Vec<MonitoredChannel> = response
+            .documents
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|doc| {
+                let name = doc.name.as_ref()?;
+                let channel_id = name.split('/').next_back()?;
+                document_to_channel(doc, channel_id).ok()
+            })
+            .collect();
+        channels.sort_by_key(|c| c.created_at);
+
+        Ok(channels)
+    }
+
+    /// Store user-editable settings (enabled, detection mode, prompt).
+    pub async fn update_settings(&self, channel: &MonitoredChannel) -> FirestoreResult<()> {
+        self.update_fields(channel, &SETTINGS_FIELDS).await
+    }
+
+    /// Store the result of a poll (title, seen uploads, counters, error).
+    pub async fn update_poll_state(&self, channel: &MonitoredChannel) -> FirestoreResult<()> {
+        self.update_fields(channel, &POLL_FIELDS).await
+    }
+
+    /// Delete a channel.
+    pub async fn delete(&self, channel_id: &str) -> FirestoreResult<()> {
+        self.client
+            .delete_document(&self.collection(), channel_id)
+            .await?;
+        info!("Deleted monitored channel {} for user {}", channel_id, self.user_id);
+        Ok(())
+    }
+
+    /// List enabled channels across all users.
+    pub async fn list_all_enabled(client: &FirestoreClient) -> FirestoreResult<Vec<MonitoredChannel>> {
+        let query = StructuredQuery {
+            from: vec![CollectionSelector {
+                collection_id: CHANNELS_COLLECTION.to_string(),
+                all_descendants: Some(true),
+            }],
+            r#where: Some(Filter {
+                composite_filter: None,
+                field_filter: Some(FieldFilter {
+                    field: FieldReference {
+                        field_path: "enabled".to_string(),
+                    },
+                    op: "EQUAL".to_string(),
+                    value: Value::BooleanValue(true),
+                }),
+            }),
+            order_by: None,
+            start_at: None,
+            limit: None,
+        };
+
+        let docs = client.run_query("", query).await?;
+        Ok(docs
+            .iter()
+            .filter_map(|doc| {
+                let name = doc.name.as_ref()?;
+                let channel_id = name.split('/').next_back()?;
+                document_to_channel(doc, channel_id).ok()
+            })
+            .collect())
+    }
+
+    /// Write a subset of fields; masked fields missing from the channel are cleared.
+    async fn update_fields(&self, channel: &MonitoredChannel, mask: &[&str]) -> FirestoreResult<()> {
+        let mut channel = channel.clone();
+        channel.updated_at = Utc::now();
+        let fields: HashMap<String, Value> = channel_to_fields(&channel)
+            .into_iter()
+            .filter(|(key, _)| mask.contains(&key.as_str()))
+            .collect();
+
+        self.client
+            .update_document(
+                &self.collection(),
+                &channel.id,
+                fields,
+                Some(mask.iter().map(|f| f.to_string()).collect()),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+fn channel_to_fields(channel: &MonitoredChannel) -> HashMap<String, Value> {
+    let mut fields = HashMap::new();
+
+    fields.insert("id".to_string(), channel.id.to_firestore_value());
+    fields.insert("user_id".to_string(), channel.user_id.to_firestore_value());
+    fields.insert("channel_url".to_string(), channel.channel_url.to_firestore_value());
+    fields.insert("enabled".to_string(), channel.enabled.to_firestore_value());
+    fields.insert(
+        "highlight_detection".to_string(),
+        channel.highlight_detection.as_str().to_firestore_value(),
+    );
+    fields.insert(
+        "seen_video_ids".to_string(),
+        channel.seen_video_ids.to_firestore_value(),
+    );
+    fields.insert(
+        "uploads_queued".to_string(),
+        channel.uploads_queued.to_firestore_value(),
+    );
+    fields.insert("created_at".to_string(), channel.created_at.to_firestore_value());
+    fields.insert("updated_at".to_string(), channel.updated_at.to_firestore_value());
+
+    if let Some(ref title) = channel.title {
+        fields.insert("title".to_string(), title.to_firestore_value());
+    }
+    if let Some(ref prompt) = channel.prompt {
+        fields.insert("prompt".to_string(), prompt.to_firestore_value());
+    }
+    if let Some(polled_at) = channel.last_polled_at {
+        fields.insert("last_polled_at".to_string(), polled_at.to_firestore_value());
+    }
+    if let Some(ref error) = channel.last_error {
+        fields.insert("last_error".to_string(), error.to_firestore_value());
+    }
+
+    fields
+}
+
+fn document_to_channel(
+    doc: &crate::types::Document,
+    channel_id: &str,
+) -> FirestoreResult<MonitoredChannel> {
+    let fields = doc.fields.as_ref().ok_or_else(|| {
+        FirestoreError::InvalidResponse("Channel document has no fields".to_string())
+    })?;
+
+    let string = |key: &str| fields.get(key).and_then(String::from_firestore_value);
+    let timestamp = |key: &str| fields.get(key).and_then(chrono::DateTime::from_firestore_value);
+
+    let highlight_detection = match string("highlight_detection").as_deref() {
+        Some("signals") => HighlightDetection::Signals,
+        Some("fused") => HighlightDetection::Fused,
+        _ => HighlightDetection::Transcript,
+    };
+
+    let seen_video_ids = match fields.get("seen_video_ids") {
+        Some(Value::ArrayValue(ArrayValue { values: Some(values) })) => values
+            .iter()
+            .filter_map(String::from_firestore_value)
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(MonitoredChannel {
+        id: channel_id.to_string(),
+        user_id: string("user_id").unwrap_or_default(),
+        channel_url: string("channel_url").unwrap_or_default(),
+        title: string("title"),
+        enabled: fields
+            .get("enabled")
+            .and_then(bool::from_firestore_value)
+            .unwrap_or(false),
+        highlight_detection,
+        prompt: string("prompt"),
+        seen_video_ids,
+        uploads_queued: fields
+            .get("uploads_queued")
+            .and_then(u32::from_firestore_value)
+            .unwrap_or(0),
+        last_polled_at: timestamp("last_polled_at"),
+        last_error: string("last_error"),
+        created_at: timestamp("created_at").unwrap_or_else(Utc::now),
+        updated_at: timestamp("updated_at").unwrap_or_else(Utc::now),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Document;
+
+    #[test]
+    fn test_channel_fields_roundtrip() {
+        let mut channel = MonitoredChannel::new("c1", "u1", "https://www.youtube.com/@x")
+            .with_highlight_detection(HighlightDetection::Fused)
+            .with_prompt("Focus on jokes");
+        channel.record_seen(&["v2".to_string(), "v1".to_string()]);
+        channel.last_polled_at = Some(Utc::now());
+
+        let doc = Document::new(channel_to_fields(&channel));
+        let decoded = document_to_channel(&doc, "c1").unwrap();
+
+        assert_eq!(decoded.user_id, "u1");
+        assert!(decoded.enabled);
+        assert_eq!(decoded.highlight_detection, HighlightDetection::Fused);
+        assert_eq!(decoded.prompt.as_deref(), Some("Focus on jokes"));
+        assert_eq!(decoded.seen_video_ids, vec!["v2", "v1"]);
+        assert!(decoded.has_baseline());
+        assert_eq!(decoded.last_error, None);
+    }
+}
@@ -663,12 +663,17 @@ impl FirestoreClient {
     ///
     /// The `parent_path` should be the path containing the collection, e.g.,
     /// "users/USER_ID" for querying "users/USER_ID/credit_transactions".
+    /// An empty path queries from the database root (collection group queries).
     pub async fn run_query(
         &self,
         parent_path: &str,
         query: StructuredQuery,
     ) -> FirestoreResult<Vec<Document>> {
-        let url = format!("{}/{}:runQuery", self.base_url, parent_path);
+        let url = if parent_path.is_empty() {
+            format!("{}:runQuery", self.base_url)
+        } else {
+            format!("{}/{}:runQuery", self.base_url, parent_path)
+        };
         let request = RunQueryRequest {
             structured_query: query,
         };
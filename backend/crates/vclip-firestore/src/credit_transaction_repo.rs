@@ -179,13 +179,17 @@ impl CreditTransactionRepository {
         Ok(transactions)
     }
 
-    /// Count total credits used in a given month.
+    /// Count total credits used in a given month, net of refunds.
     ///
     /// Month key format: "YYYY-MM" (e.g., "2025-01")
     /// Uses timestamp range query for efficiency instead of O(N) client-side filtering.
     pub async fn get_month_total(&self, month_key: &str) -> FirestoreResult<u32> {
         let transactions = self.list_month_transactions(month_key).await?;
-        Ok(transactions.iter().map(|tx| tx.credits_amount).sum())
+        let (refunded, charged): (Vec<_>, Vec<_>) = transactions
+            .iter()
+            .partition(|tx| tx.operation_type.is_refund());
+        let sum = |txs: Vec<&CreditTransaction>| txs.iter().map(|tx| tx.credits_amount).sum::<u32>();
+        Ok(sum(charged).saturating_sub(sum(refunded)))
     }
 
     /// Get summary of credits by operation type for a given month.
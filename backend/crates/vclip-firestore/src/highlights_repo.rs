@@ -2,8 +2,10 @@
 
 use std::collections::HashMap;
 use chrono::Utc;
-use tracing::info;
-use vclip_models::{VideoId, highlight::VideoHighlights};
+use tracing::{debug, info};
+use vclip_models::{
+    BoundaryRefinement, BoundarySnap, Highlight, QualityMetrics, VideoId, highlight::VideoHighlights,
+};
 use crate::client::FirestoreClient;
 use crate::error::{FirestoreError, FirestoreResult};
 use crate::types::{ArrayValue, FromFirestoreValue, MapValue, ToFirestoreValue, Value};
@@ -15,6 +17,9 @@ pub struct HighlightsRepository {
 }
 
 impl HighlightsRepository {
+    /// Maximum retries for concurrent highlight updates.
+    const MAX_UPDATE_RETRIES: u32 = 5;
+
     /// Create a new highlights repository.
     pub fn new(client: FirestoreClient, user_id: impl Into<String>) -> Self {
         Self {
@@ -85,6 +90,74 @@ impl HighlightsRepository {
         }
     }
 
+    /// Update a single highlight in place with optimistic concurrency.
+    ///
+    /// Scenes of one video are rendered in parallel and all live in the same
+    /// document, so a plain read-modify-write would lose concurrent updates.
+    /// Returns the updated highlight, or `None` if it does not exist.
+    pub async fn update_highlight<F>(
+        &self,
+        video_id: &VideoId,
+        highlight_id: u32,
+        mutator: F,
+    ) -> FirestoreResult<Option<Highlight>>
+    where
+        F: Fn(&mut Highlight),
+    {
+        let mut last_error = None;
+
+        for attempt in 0..Self::MAX_UPDATE_RETRIES {
+            let Some(doc) = self
+                .client
+                .get_document(&self.collection(video_id), Self::doc_id())
+                .await?
+            else {
+                return Ok(None);
+            };
+
+            let mut highlights = document_to_video_highlights(&doc, video_id)?;
+            let Some(highlight) = highlights.highlights.iter_mut().find(|h| h.id == highlight_id)
+            else {
+                return Ok(None);
+            };
+            mutator(highlight);
+            let updated = highlight.clone();
+            highlights.updated_at = Utc::now();
+
+            let fields = video_highlights_to_fields(&highlights);
+            let update_mask = vec!["highlights".to_string(), "updated_at".to_string()];
+
+            match self
+                .client
+                .update_document_with_precondition(
+                    &self.collection(video_id),
+                    Self::doc_id(),
+                    fields,
+                    Some(update_mask),
+                    doc.update_time.as_deref(),
+                )
+                .await
+            {
+                Ok(_) => return Ok(Some(updated)),
+                Err(e) if e.is_precondition_failed() => {
+                    debug!(
+                        "Highlights update precondition failed for {} (attempt {}), retrying",
+                        video_id.as_str(),
+                        attempt + 1
+                    );
+                    last_error = Some(e);
+                    tokio::time::sleep(std::time::Duration::from_millis(50 * (attempt as u64 + 1)))
+                        .await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            FirestoreError::request_failed("Failed to update highlight after retries")
+        }))
+    }
+
     /// Delete highlights for a video.
     pub async fn delete(&self, video_id: &VideoId) -> FirestoreResult<bool> {
         self.client
@@ -133,6 +206,15 @@ fn video_highlights_to_fields(highlights: &VideoHighlights) -> HashMap<String, V
             if let Some(ref description) = h.description {
                 h_fields.insert("description".to_string(), description.to_firestore_value());
             }
+            if let Some(ref refinement) = h.refinement {
+                h_fields.insert("refinement".to_string(), refinement_to_value(refinement));
+            }
+            if let Some(score) = h.virality_score {
+                h_fields.insert("virality_score".to_string(), score.to_firestore_value());
+            }
+            if let Some(ref quality) = h.quality {
+                h_fields.insert("quality".to_string(), quality_metrics_to_value(quality));
+            }
             Value::MapValue(MapValue { fields: Some(h_fields) })
         })
         .collect();
@@ -154,6 +236,72 @@ fn video_highlights_to_fields(highlights: &VideoHighlights) -> HashMap<String, V
     fields
 }
 
+fn refinement_to_value(refinement: &BoundaryRefinement) -> Value {
+    let mut fields = HashMap::new();
+    fields.insert("original_start".to_string(), refinement.original_start.to_firestore_value());
+    fields.insert("original_end".to_string(), refinement.original_end.to_firestore_value());
+    fields.insert("start_snap".to_string(), refinement.start_snap.as_str().to_firestore_value());
+    fields.insert("end_snap".to_string(), refinement.end_snap.as_str().to_firestore_value());
+    fields.insert("tolerance_secs".to_string(), refinement.tolerance_secs.to_firestore_value());
+    Value::MapValue(MapValue { fields: Some(fields) })
+}
+
+fn value_to_refinement(value: &Value) -> Option<BoundaryRefinement> {
+    let Value::MapValue(MapValue { fields: Some(fields) }) = value else {
+        return None;
+    };
+    let snap = |key: &str| {
+        fields
+            .get(key)
+            .and_then(String::from_firestore_value)
+            .map(|s| BoundarySnap::from_str_lossy(&s))
+            .unwrap_or_default()
+    };
+
+    Some(BoundaryRefinement {
+        original_start: fields.get("original_start").and_then(String::from_firestore_value)?,
+        original_end: fields.get("original_end").and_then(String::from_firestore_value)?,
+        start_snap: snap("start_snap"),
+        end_snap: snap("end_snap"),
+        tolerance_secs: fields
+            .get("tolerance_secs")
+            .and_then(f64::from_firestore_value)
+            .unwrap_or(0.0),
+    })
+}
+
+pub(crate) fn quality_metrics_to_value(quality: &QualityMetrics) -> Value {
+    let mut fields = HashMap::new();
+    let metrics = [
+        ("hook_strength", quality.hook_strength),
+        ("speech_ratio", quality.speech_ratio),
+        ("face_presence", quality.face_presence),
+        ("duration_fit", quality.duration_fit),
+        ("llm_confidence", quality.llm_confidence),
+    ];
+    for (key, value) in metrics {
+        if let Some(value) = value {
+            fields.insert(key.to_string(), value.to_firestore_value());
+        }
+    }
+    Value::MapValue(MapValue { fields: Some(fields) })
+}
+
+pub(crate) fn value_to_quality_metrics(value: &Value) -> Option<QualityMetrics> {
+    let Value::MapValue(MapValue { fields: Some(fields) }) = value else {
+        return None;
+    };
+    let metric = |key: &str| fields.get(key).and_then(f64::from_firestore_value);
+
+    Some(QualityMetrics {
+        hook_strength: metric("hook_strength"),
+        speech_ratio: metric("speech_ratio"),
+        face_presence: metric("face_presence"),
+        duration_fit: metric("duration_fit"),
+        llm_confidence: metric("llm_confidence"),
+    })
+}
+
 fn document_to_video_highlights(
     doc: &crate::types::Document,
     video_id: &VideoId,
@@ -202,6 +350,9 @@ fn document_to_video_highlights(
                 
                 let reason = fields.get("reason").and_then(|v| String::from_firestore_value(v));
                 let description = fields.get("description").and_then(|v| String::from_firestore_value(v));
+                let refinement = fields.get("refinement").and_then(value_to_refinement);
+                let virality_score = fields.get("virality_score").and_then(f64::from_firestore_value);
+                let quality = fields.get("quality").and_then(value_to_quality_metrics);
 
                 Some(vclip_models::Highlight {
                     id,
@@ -214,6 +365,9 @@ fn document_to_video_highlights(
                     hook_category,
                     reason,
                     description,
+                    refinement,
+                    virality_score,
+                    quality,
                 })
             }
             _ => None,
@@ -244,3 +398,48 @@ fn document_to_video_highlights(
         updated_at,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refinement_value_roundtrip() {
+        let refinement = BoundaryRefinement {
+            original_start: "00:01:00".to_string(),
+            original_end: "00:01:30".to_string(),
+            start_snap: BoundarySnap::Sentence,
+            end_snap: BoundarySnap::ShotCut,
+            tolerance_secs: 1.5,
+        };
+
+        let parsed = value_to_refinement(&refinement_to_value(&refinement)).unwrap();
+        assert_eq!(parsed, refinement);
+    }
+
+    #[test]
+    fn test_quality_metrics_value_roundtrip() {
+        let quality = QualityMetrics {
+            hook_strength: Some(0.8),
+            speech_ratio: None,
+            face_presence: Some(0.25),
+            duration_fit: Some(1.0),
+            llm_confidence: Some(0.9),
+        };
+
+        let value = quality_metrics_to_value(&quality);
+        let Value::MapValue(MapValue { fields: Some(ref fields) }) = value else {
+            panic!("expected map value");
+        };
+        assert!(!fields.contains_key("speech_ratio"));
+        assert_eq!(value_to_quality_metrics(&value), Some(quality));
+    }
+
+    #[test]
+    fn test_refinement_missing_originals_is_none() {
+        let value = Value::MapValue(MapValue {
+            fields: Some(HashMap::new()),
+        });
+        assert!(value_to_refinement(&value).is_none());
+    }
+}
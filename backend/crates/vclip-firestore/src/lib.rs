@@ -14,7 +14,10 @@
 //! - `repos` - Typed repositories for Videos and Clips
 //! - `types` - Firestore document types and value conversions
 
+pub mod admin_audit_repo;
 pub mod analysis_draft_repo;
+pub mod api_key_repo;
+pub mod channel_repo;
 pub mod client;
 #[cfg(test)]
 mod client_tests;
@@ -29,17 +32,24 @@ pub mod sorting;
 pub mod storage_accounting;
 pub mod token_cache;
 pub mod types;
+pub mod upload_repo;
 pub mod user_credits;
+pub mod webhook_repo;
 
+pub use admin_audit_repo::AdminAuditRepository;
 pub use analysis_draft_repo::AnalysisDraftRepository;
+pub use api_key_repo::{ApiKeyIndex, ApiKeyRepository};
+pub use channel_repo::ChannelRepository;
 pub use client::{FirestoreClient, FirestoreConfig};
 pub use credit_transaction_repo::CreditTransactionRepository;
 pub use error::{FirestoreError, FirestoreResult};
 pub use highlights_repo::HighlightsRepository;
 pub use repos::{ClipRepository, VideoRepository};
 pub use retry::RetryConfig;
-pub use share_repo::{ShareRepository, ShareSlugIndex};
+pub use share_repo::{ShareRepository, ShareSlugIndex, ShareViewClaim};
 pub use storage_accounting::StorageAccountingRepository;
-pub use types::{Document, FromFirestoreValue, ToFirestoreValue, Value};
+pub use types::{Document, FieldTransform, FromFirestoreValue, ToFirestoreValue, Value};
+pub use upload_repo::UploadRepository;
 pub use user_credits::{current_month_key, CreditChargeResult, UserCreditsRepository};
+pub use webhook_repo::WebhookRepository;
 
@@ -6,7 +6,10 @@ use chrono::Utc;
 use metrics::counter;
 use tracing::{info, warn, debug};
 
-use vclip_models::{ClipMetadata, ClipStatus, ProcessingProgress, SourceVideoStatus, VideoId, VideoMetadata, VideoStatus};
+use vclip_models::{
+    ClipMetadata, ClipStatus, ClipVariant, ExportProfile, LoudnessReport, LoudnessStats,
+    ProcessingProgress, SourceVideoStatus, VideoId, VideoMetadata, VideoStatus,
+};
 
 use crate::client::FirestoreClient;
 use crate::error::{FirestoreError, FirestoreResult};
@@ -14,7 +17,9 @@ use crate::share_repo::ShareRepository;
 use crate::sorting::{
     build_sorted_video_query, normalize_page_size, PaginationCursor, SortConfig, VideoSortField,
 };
-use crate::types::{DocumentMask, FromFirestoreValue, ToFirestoreValue, Value};
+use crate::types::{
+    ArrayValue, DocumentMask, FromFirestoreValue, MapValue, ToFirestoreValue, Value,
+};
 
 /// Repository for video documents.
 pub struct VideoRepository {
@@ -685,6 +690,7 @@ impl VideoRepository {
                     "analyzed" => Some(VideoStatus::Analyzed),
                     "completed" => Some(VideoStatus::Completed),
                     "failed" => Some(VideoStatus::Failed),
+                    "cancelled" => Some(VideoStatus::Cancelled),
                     _ => None,
                 });
 
@@ -1072,6 +1078,13 @@ impl ClipRepository {
         Ok(())
     }
 
+    /// Delete a clip record by ID.
+    pub async fn delete(&self, clip_id: &str) -> FirestoreResult<()> {
+        self.client.delete_document(&self.collection(), clip_id).await?;
+        info!("Deleted clip record: {}", clip_id);
+        Ok(())
+    }
+
     /// Delete a clip by filename.
     pub async fn delete_by_filename(&self, filename: &str) -> FirestoreResult<bool> {
         // First, list all clips to find the one with matching filename
@@ -1207,6 +1220,7 @@ fn video_metadata_to_fields(video: &VideoMetadata) -> HashMap<String, Value> {
     fields.insert("styles_processed".to_string(), video.styles_processed.to_firestore_value());
     fields.insert("crop_mode".to_string(), video.crop_mode.to_firestore_value());
     fields.insert("target_aspect".to_string(), video.target_aspect.to_firestore_value());
+    fields.insert("resolution".to_string(), video.resolution.to_firestore_value());
     fields.insert("clips_count".to_string(), video.clips_count.to_firestore_value());
     fields.insert("total_size_bytes".to_string(), video.total_size_bytes.to_firestore_value());
     fields.insert("clips_by_style".to_string(), video.clips_by_style.to_firestore_value());
@@ -1226,6 +1240,9 @@ fn video_metadata_to_fields(video: &VideoMetadata) -> HashMap<String, Value> {
     if let Some(ref error) = video.source_video_error {
         fields.insert("source_video_error".to_string(), error.to_firestore_value());
     }
+    if let Some(ref key) = video.live_manifest_r2_key {
+        fields.insert("live_manifest_r2_key".to_string(), key.to_firestore_value());
+    }
 
     // Processing progress (if present)
     if let Some(ref progress) = video.processing_progress {
@@ -1274,6 +1291,7 @@ fn document_to_video_metadata(
             "completed" => VideoStatus::Completed,
             "analyzed" => VideoStatus::Analyzed,
             "failed" => VideoStatus::Failed,
+            "cancelled" => VideoStatus::Cancelled,
             _ => VideoStatus::Processing,
         },
         created_at: fields
@@ -1310,6 +1328,10 @@ fn document_to_video_metadata(
             .unwrap_or_default(),
         crop_mode: get_string("crop_mode"),
         target_aspect: get_string("target_aspect"),
+        resolution: fields
+            .get("resolution")
+            .and_then(String::from_firestore_value)
+            .unwrap_or_else(|| vclip_models::ResolutionPreset::default().to_string()),
         clips_count: get_u32("clips_count"),
         total_size_bytes: get_u64("total_size_bytes"),
         clips_by_style: fields
@@ -1348,6 +1370,9 @@ fn document_to_video_metadata(
         source_video_error: fields
             .get("source_video_error")
             .and_then(|v| String::from_firestore_value(v)),
+        live_manifest_r2_key: fields
+            .get("live_manifest_r2_key")
+            .and_then(|v| String::from_firestore_value(v)),
         processing_progress: fields
             .get("processing_progress")
             .and_then(progress_from_firestore_value),
@@ -1442,6 +1467,18 @@ fn clip_metadata_to_fields(clip: &ClipMetadata) -> HashMap<String, Value> {
     if let Some(ref raw_key) = clip.raw_r2_key {
         fields.insert("raw_r2_key".to_string(), raw_key.to_firestore_value());
     }
+    if !clip.variants.is_empty() {
+        let variants = clip.variants.iter().map(clip_variant_to_value).collect();
+        fields.insert(
+            "variants".to_string(),
+            Value::ArrayValue(ArrayValue {
+                values: Some(variants),
+            }),
+        );
+    }
+    if let Some(ref loudness) = clip.loudness {
+        fields.insert("loudness".to_string(), loudness_report_to_value(loudness));
+    }
     fields.insert("status".to_string(), clip.status.as_str().to_firestore_value());
     fields.insert("created_at".to_string(), clip.created_at.to_firestore_value());
     if let Some(completed_at) = clip.completed_at {
@@ -1511,6 +1548,13 @@ fn document_to_clip_metadata(doc: &crate::types::Document) -> FirestoreResult<Cl
         raw_r2_key: fields
             .get("raw_r2_key")
             .and_then(|v| String::from_firestore_value(v)),
+        variants: match fields.get("variants") {
+            Some(Value::ArrayValue(ArrayValue {
+                values: Some(values),
+            })) => values.iter().filter_map(value_to_clip_variant).collect(),
+            _ => Vec::new(),
+        },
+        loudness: fields.get("loudness").and_then(value_to_loudness_report),
         status: match get_string("status").as_str() {
             "completed" => ClipStatus::Completed,
             "failed" => ClipStatus::Failed,
@@ -1529,6 +1573,145 @@ fn document_to_clip_metadata(doc: &crate::types::Document) -> FirestoreResult<Cl
         created_by: get_string("created_by"),
     })
 }
+
+fn clip_variant_to_value(variant: &ClipVariant) -> Value {
+    let mut fields = HashMap::new();
+    fields.insert(
+        "profile".to_string(),
+        variant.profile.as_str().to_firestore_value(),
+    );
+    fields.insert(
+        "filename".to_string(),
+        variant.filename.to_firestore_value(),
+    );
+    fields.insert("r2_key".to_string(), variant.r2_key.to_firestore_value());
+    fields.insert(
+        "file_size_bytes".to_string(),
+        variant.file_size_bytes.to_firestore_value(),
+    );
+    fields.insert(
+        "duration_seconds".to_string(),
+        variant.duration_seconds.to_firestore_value(),
+    );
+    fields.insert("width".to_string(), variant.width.to_firestore_value());
+    fields.insert("height".to_string(), variant.height.to_firestore_value());
+    Value::MapValue(MapValue {
+        fields: Some(fields),
+    })
+}
+
+fn value_to_clip_variant(value: &Value) -> Option<ClipVariant> {
+    let Value::MapValue(MapValue {
+        fields: Some(fields),
+    }) = value
+    else {
+        return None;
+    };
+
+    Some(ClipVariant {
+        profile: fields
+            .get("profile")
+            .and_then(String::from_firestore_value)?
+            .parse::<ExportProfile>()
+            .ok()?,
+        filename: fields
+            .get("filename")
+            .and_then(String::from_firestore_value)?,
+        r2_key: fields
+            .get("r2_key")
+            .and_then(String::from_firestore_value)?,
+        file_size_bytes: fields
+            .get("file_size_bytes")
+            .and_then(u64::from_firestore_value)
+            .unwrap_or(0),
+        duration_seconds: fields
+            .get("duration_seconds")
+            .and_then(f64::from_firestore_value)
+            .unwrap_or(0.0),
+        width: fields
+            .get("width")
+            .and_then(u32::from_firestore_value)
+            .unwrap_or(0),
+        height: fields
+            .get("height")
+            .and_then(u32::from_firestore_value)
+            .unwrap_or(0),
+    })
+}
+
+fn loudness_stats_to_value(stats: &LoudnessStats) -> Value {
+    let mut fields = HashMap::new();
+    fields.insert(
+        "integrated_lufs".to_string(),
+        stats.integrated_lufs.to_firestore_value(),
+    );
+    fields.insert(
+        "true_peak_db".to_string(),
+        stats.true_peak_db.to_firestore_value(),
+    );
+    fields.insert(
+        "loudness_range".to_string(),
+        stats.loudness_range.to_firestore_value(),
+    );
+    Value::MapValue(MapValue {
+        fields: Some(fields),
+    })
+}
+
+fn value_to_loudness_stats(value: &Value) -> Option<LoudnessStats> {
+    let Value::MapValue(MapValue {
+        fields: Some(fields),
+    }) = value
+    else {
+        return None;
+    };
+
+    Some(LoudnessStats {
+        integrated_lufs: fields
+            .get("integrated_lufs")
+            .and_then(f64::from_firestore_value)?,
+        true_peak_db: fields
+            .get("true_peak_db")
+            .and_then(f64::from_firestore_value)?,
+        loudness_range: fields
+            .get("loudness_range")
+            .and_then(f64::from_firestore_value)?,
+    })
+}
+
+fn loudness_report_to_value(report: &LoudnessReport) -> Value {
+    let mut fields = HashMap::new();
+    fields.insert(
+        "target_lufs".to_string(),
+        report.target_lufs.to_firestore_value(),
+    );
+    fields.insert("input".to_string(), loudness_stats_to_value(&report.input));
+    fields.insert(
+        "output".to_string(),
+        loudness_stats_to_value(&report.output),
+    );
+    Value::MapValue(MapValue {
+        fields: Some(fields),
+    })
+}
+
+fn value_to_loudness_report(value: &Value) -> Option<LoudnessReport> {
+    let Value::MapValue(MapValue {
+        fields: Some(fields),
+    }) = value
+    else {
+        return None;
+    };
+
+    Some(LoudnessReport {
+        target_lufs: fields
+            .get("target_lufs")
+            .and_then(f64::from_firestore_value)?,
+        input: fields.get("input").and_then(value_to_loudness_stats)?,
+        output: fields.get("output").and_then(value_to_loudness_stats)?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1554,6 +1737,8 @@ mod tests {
             r2_key: "r2/key".to_string(),
             thumbnail_r2_key: Some("r2/thumb".to_string()),
             raw_r2_key: None,
+            variants: Vec::new(),
+            loudness: None,
             status: ClipStatus::Completed,
             created_at: Utc::now(),
             completed_at: None,
@@ -1583,4 +1768,57 @@ mod tests {
             "completed_at should be included when provided"
         );
     }
+
+    #[test]
+    fn clip_variants_roundtrip() {
+        let mut clip = sample_clip();
+        assert!(!clip_metadata_to_fields(&clip).contains_key("variants"));
+
+        clip.variants.push(ClipVariant {
+            profile: ExportProfile::Shorts,
+            filename: "clip_01_1_scene_intelligent.shorts.mp4".to_string(),
+            r2_key: "r2/key.shorts".to_string(),
+            file_size_bytes: 500,
+            duration_seconds: 10.0,
+            width: 1080,
+            height: 1920,
+        });
+        let doc = crate::types::Document {
+            name: None,
+            fields: Some(clip_metadata_to_fields(&clip)),
+            create_time: None,
+            update_time: None,
+        };
+        let parsed = document_to_clip_metadata(&doc).unwrap();
+        assert_eq!(parsed.variants, clip.variants);
+        assert_eq!(parsed.total_size_bytes(), 1_500);
+    }
+
+    #[test]
+    fn clip_loudness_roundtrip() {
+        let mut clip = sample_clip();
+        assert!(!clip_metadata_to_fields(&clip).contains_key("loudness"));
+
+        clip.loudness = Some(LoudnessReport {
+            target_lufs: -14.0,
+            input: LoudnessStats {
+                integrated_lufs: -27.6,
+                true_peak_db: -4.5,
+                loudness_range: 18.1,
+            },
+            output: LoudnessStats {
+                integrated_lufs: -14.1,
+                true_peak_db: -1.5,
+                loudness_range: 14.8,
+            },
+        });
+        let doc = crate::types::Document {
+            name: None,
+            fields: Some(clip_metadata_to_fields(&clip)),
+            create_time: None,
+            update_time: None,
+        };
+        let parsed = document_to_clip_metadata(&doc).unwrap();
+        assert_eq!(parsed.loudness, clip.loudness);
+    }
 }
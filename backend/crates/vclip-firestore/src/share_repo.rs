@@ -3,17 +3,35 @@
 //! Uses a dual-document pattern:
 //! - Config doc at `users/{uid}/videos/{vid}/clips/{cid}/shares/config`
 //! - Slug index at `share_slugs/{slug}` for fast public lookups
+//!
+//! View analytics live next to the config at `.../shares/stats` and are only
+//! ever changed through server-side increments.
+//!
+//! The slug index also carries the access restrictions (passcode hash, view
+//! limit, allowed viewers) and the view count used to enforce the limit.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use chrono::Utc;
-use tracing::info;
+use tracing::{debug, info};
 
-use vclip_models::share::{ShareAccessLevel, ShareConfig};
+use vclip_models::share::{
+    ShareAccessLevel, ShareConfig, ShareDailyStats, ShareStats, MAX_SHARE_REFERRERS,
+};
 
 use crate::client::FirestoreClient;
 use crate::error::{FirestoreError, FirestoreResult};
-use crate::types::{Document, DocumentMask, FromFirestoreValue, Precondition, ToFirestoreValue, Value, Write};
+use crate::types::{
+    quote_field_path_segment, ArrayValue, Document, DocumentMask, FieldTransform,
+    FromFirestoreValue, Precondition, ToFirestoreValue, Value, Write,
+};
+
+/// Maximum retries for claiming a view on a view-limited share.
+const MAX_CLAIM_RETRIES: u32 = 5;
+
+/// Base delay between claim retries (milliseconds).
+const CLAIM_RETRY_BASE_DELAY_MS: u64 = 25;
 
 /// Minimal slug index document for fast lookup.
 #[derive(Debug, Clone)]
@@ -26,6 +44,34 @@ pub struct ShareSlugIndex {
     pub expires_at: Option<chrono::DateTime<Utc>>,
     pub disabled_at: Option<chrono::DateTime<Utc>>,
     pub created_at: chrono::DateTime<Utc>,
+    pub passcode_hash: Option<String>,
+    pub max_views: Option<u64>,
+    pub allowed_viewers: Vec<String>,
+    /// Views claimed so far (only maintained for view-limited shares)
+    pub view_count: u64,
+}
+
+impl ShareSlugIndex {
+    /// Whether viewers must pass the unlock interstitial.
+    pub fn requires_unlock(&self) -> bool {
+        self.passcode_hash.is_some() || !self.allowed_viewers.is_empty()
+    }
+
+    /// Whether the view limit has been reached.
+    pub fn views_exhausted(&self) -> bool {
+        self.max_views.is_some_and(|max| self.view_count >= max)
+    }
+}
+
+/// Outcome of claiming a view on a share.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareViewClaim {
+    /// View counted; the share has this many views left (`None` = unlimited).
+    Granted { remaining: Option<u64> },
+    /// The view limit has been reached.
+    Exhausted,
+    /// The share no longer exists (revoked).
+    NotFound,
 }
 
 /// Repository for share documents (dual-document pattern).
@@ -78,6 +124,7 @@ impl ShareRepository {
                 }),
                 delete: None,
                 update_mask: None,
+                update_transforms: None,
                 current_document: None,
             },
             Write {
@@ -89,6 +136,7 @@ impl ShareRepository {
                 }),
                 delete: None,
                 update_mask: None,
+                update_transforms: None,
                 current_document: None,
             },
         ];
@@ -138,6 +186,7 @@ impl ShareRepository {
                         "updated_at".to_string(),
                     ],
                 }),
+                update_transforms: None,
                 current_document: Some(Precondition {
                     exists: Some(true),
                     update_time: None,
@@ -147,6 +196,7 @@ impl ShareRepository {
                 update: None,
                 delete: Some(slug_doc_name),
                 update_mask: None,
+                update_transforms: None,
                 current_document: None,
             },
         ];
@@ -174,6 +224,59 @@ impl ShareRepository {
         }
     }
 
+    /// Atomically count a view against a share's view limit.
+    ///
+    /// Uses the slug index `updateTime` as a precondition, so concurrent
+    /// viewers can never push the count past `max_views`. Shares without a
+    /// limit are granted without a write.
+    pub async fn claim_view(&self, slug: &str) -> FirestoreResult<ShareViewClaim> {
+        for attempt in 0..MAX_CLAIM_RETRIES {
+            let Some(doc) = self.client.get_document(Self::slug_collection(), slug).await? else {
+                return Ok(ShareViewClaim::NotFound);
+            };
+            let index = document_to_share_slug_index(&doc)?;
+            let Some(max_views) = index.max_views else {
+                return Ok(ShareViewClaim::Granted { remaining: None });
+            };
+            if index.view_count >= max_views {
+                return Ok(ShareViewClaim::Exhausted);
+            }
+
+            let view_count = index.view_count + 1;
+            let mut fields = HashMap::new();
+            fields.insert("view_count".to_string(), view_count.to_firestore_value());
+
+            match self
+                .client
+                .update_document_with_precondition(
+                    Self::slug_collection(),
+                    slug,
+                    fields,
+                    Some(vec!["view_count".to_string()]),
+                    doc.update_time.as_deref(),
+                )
+                .await
+            {
+                Ok(_) => {
+                    return Ok(ShareViewClaim::Granted {
+                        remaining: Some(max_views - view_count),
+                    })
+                }
+                Err(e) if e.is_precondition_failed() => {
+                    debug!(share_slug = %slug, attempt = attempt + 1, "Share view claim conflicted, retrying");
+                    let delay = Duration::from_millis(CLAIM_RETRY_BASE_DELAY_MS * (attempt as u64 + 1));
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(FirestoreError::request_failed(format!(
+            "Failed to claim view on share {} due to concurrent updates",
+            slug
+        )))
+    }
+
     /// Get share config for a clip.
     pub async fn get_config(
         &self,
@@ -193,6 +296,57 @@ impl ShareRepository {
         }
     }
 
+    /// Add buffered view counters to a clip's share stats.
+    ///
+    /// Counters are applied as server-side increments, so concurrent flushes
+    /// of the same link never overwrite each other.
+    pub async fn increment_stats(
+        &self,
+        user_id: &str,
+        video_id: &str,
+        clip_id: &str,
+        share_slug: &str,
+        delta: &ShareStats,
+    ) -> FirestoreResult<()> {
+        let stats_collection = Self::config_path(user_id, video_id, clip_id);
+        let stats_doc_name = self.client.full_document_name(&stats_collection, "stats");
+
+        let mut fields = HashMap::new();
+        fields.insert("share_slug".to_string(), share_slug.to_firestore_value());
+        if let Some(last) = delta.last_viewed_at {
+            fields.insert("last_viewed_at".to_string(), last.to_firestore_value());
+        }
+        let field_paths = fields.keys().cloned().collect();
+
+        let writes = vec![Write {
+            update: Some(Document {
+                name: Some(stats_doc_name),
+                fields: Some(fields),
+                create_time: None,
+                update_time: None,
+            }),
+            delete: None,
+            update_mask: Some(DocumentMask { field_paths }),
+            update_transforms: Some(share_stats_increments(delta)),
+            current_document: None,
+        }];
+
+        self.client.batch_write(writes).await?;
+        Ok(())
+    }
+
+    /// Get the flushed share stats of a clip.
+    pub async fn get_stats(
+        &self,
+        user_id: &str,
+        video_id: &str,
+        clip_id: &str,
+    ) -> FirestoreResult<Option<ShareStats>> {
+        let stats_collection = Self::config_path(user_id, video_id, clip_id);
+        let doc = self.client.get_document(&stats_collection, "stats").await?;
+        Ok(doc.as_ref().map(document_to_share_stats))
+    }
+
     /// Delete a share slug index document.
     pub async fn delete_slug(&self, share_slug: &str) -> FirestoreResult<()> {
         self.client
@@ -293,6 +447,7 @@ fn share_config_to_fields(config: &ShareConfig) -> HashMap<String, Value> {
     if let Some(disabled) = config.disabled_at {
         fields.insert("disabled_at".to_string(), disabled.to_firestore_value());
     }
+    insert_restriction_fields(&mut fields, config);
 
     fields
 }
@@ -312,10 +467,109 @@ fn share_slug_index_to_fields(config: &ShareConfig) -> HashMap<String, Value> {
     if let Some(disabled) = config.disabled_at {
         fields.insert("disabled_at".to_string(), disabled.to_firestore_value());
     }
+    insert_restriction_fields(&mut fields, config);
+    fields.insert("view_count".to_string(), 0u64.to_firestore_value());
 
     fields
 }
 
+/// Access restrictions, stored on both the config and the slug index.
+fn insert_restriction_fields(fields: &mut HashMap<String, Value>, config: &ShareConfig) {
+    if let Some(hash) = &config.passcode_hash {
+        fields.insert("passcode_hash".to_string(), hash.to_firestore_value());
+    }
+    if let Some(max_views) = config.max_views {
+        fields.insert("max_views".to_string(), max_views.to_firestore_value());
+    }
+    if !config.allowed_viewers.is_empty() {
+        fields.insert(
+            "allowed_viewers".to_string(),
+            config.allowed_viewers.to_firestore_value(),
+        );
+    }
+}
+
+fn strings_from_value(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::ArrayValue(ArrayValue { values: Some(values) })) => values
+            .iter()
+            .filter_map(String::from_firestore_value)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn share_stats_increments(delta: &ShareStats) -> Vec<FieldTransform> {
+    let mut increments = vec![
+        ("total_views".to_string(), delta.total_views),
+        ("plays".to_string(), delta.plays),
+        ("downloads".to_string(), delta.downloads),
+        ("unique_viewers".to_string(), delta.unique_viewers),
+    ];
+    for (day, daily) in &delta.daily {
+        let day = quote_field_path_segment(day);
+        increments.push((format!("daily.{}.views", day), daily.views));
+        increments.push((format!("daily.{}.unique_viewers", day), daily.unique_viewers));
+    }
+    // Referrers are client-controlled; bound the transforms a single write carries
+    for (domain, views) in delta.top_referrers(MAX_SHARE_REFERRERS) {
+        increments.push((format!("referrers.{}", quote_field_path_segment(&domain)), views));
+    }
+
+    increments
+        .into_iter()
+        .filter(|(_, by)| *by > 0)
+        .map(|(path, by)| FieldTransform::increment(path, by as i64))
+        .collect()
+}
+
+fn document_to_share_stats(doc: &Document) -> ShareStats {
+    let empty = HashMap::new();
+    let fields = doc.fields.as_ref().unwrap_or(&empty);
+    let get_u64 = |fields: &HashMap<String, Value>, key: &str| -> u64 {
+        fields.get(key).and_then(u64::from_firestore_value).unwrap_or(0)
+    };
+    let map_fields = |key: &str| -> HashMap<String, Value> {
+        match fields.get(key) {
+            Some(Value::MapValue(map)) => map.fields.clone().unwrap_or_default(),
+            _ => HashMap::new(),
+        }
+    };
+
+    let daily = map_fields("daily")
+        .into_iter()
+        .filter_map(|(day, value)| match value {
+            Value::MapValue(map) => {
+                let counters = map.fields.unwrap_or_default();
+                Some((
+                    day,
+                    ShareDailyStats {
+                        views: get_u64(&counters, "views"),
+                        unique_viewers: get_u64(&counters, "unique_viewers"),
+                    },
+                ))
+            }
+            _ => None,
+        })
+        .collect();
+    let referrers = map_fields("referrers")
+        .into_iter()
+        .filter_map(|(domain, value)| Some((domain, u64::from_firestore_value(&value)?)))
+        .collect();
+
+    ShareStats {
+        total_views: get_u64(fields, "total_views"),
+        plays: get_u64(fields, "plays"),
+        downloads: get_u64(fields, "downloads"),
+        unique_viewers: get_u64(fields, "unique_viewers"),
+        daily,
+        referrers,
+        last_viewed_at: fields
+            .get("last_viewed_at")
+            .and_then(chrono::DateTime::from_firestore_value),
+    }
+}
+
 fn document_to_share_slug_index(doc: &Document) -> FirestoreResult<ShareSlugIndex> {
     let fields = doc.fields.as_ref().ok_or_else(|| {
         FirestoreError::InvalidResponse("Document has no fields".to_string())
@@ -336,6 +590,10 @@ fn document_to_share_slug_index(doc: &Document) -> FirestoreResult<ShareSlugInde
         created_at: fields.get("created_at")
             .and_then(|v| chrono::DateTime::from_firestore_value(v))
             .unwrap_or_else(Utc::now),
+        passcode_hash: fields.get("passcode_hash").and_then(String::from_firestore_value),
+        max_views: fields.get("max_views").and_then(u64::from_firestore_value),
+        allowed_viewers: strings_from_value(fields.get("allowed_viewers")),
+        view_count: fields.get("view_count").and_then(u64::from_firestore_value).unwrap_or(0),
     })
 }
 
@@ -365,5 +623,66 @@ fn document_to_share_config(doc: &Document) -> FirestoreResult<ShareConfig> {
             .unwrap_or_else(Utc::now),
         updated_at: fields.get("updated_at").and_then(|v| chrono::DateTime::from_firestore_value(v)),
         disabled_at: fields.get("disabled_at").and_then(|v| chrono::DateTime::from_firestore_value(v)),
+        passcode_hash: fields.get("passcode_hash").and_then(String::from_firestore_value),
+        max_views: fields.get("max_views").and_then(u64::from_firestore_value),
+        allowed_viewers: strings_from_value(fields.get("allowed_viewers")),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_stats_increments_quote_map_keys() {
+        let mut delta = ShareStats {
+            total_views: 2,
+            plays: 2,
+            unique_viewers: 1,
+            ..Default::default()
+        };
+        delta.daily.insert(
+            "2026-01-01".to_string(),
+            ShareDailyStats { views: 2, unique_viewers: 1 },
+        );
+        delta.referrers.insert("t.co".to_string(), 1);
+
+        let paths: Vec<String> = share_stats_increments(&delta)
+            .into_iter()
+            .map(|t| t.field_path)
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "total_views",
+                "plays",
+                "unique_viewers",
+                "daily.`2026-01-01`.views",
+                "daily.`2026-01-01`.unique_viewers",
+                "referrers.`t.co`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slug_index_carries_restrictions() {
+        let config = ShareConfig::new("clip-1", "user-1", "video-1", ShareAccessLevel::ViewPlayback)
+            .with_passcode("1234")
+            .with_max_views(3)
+            .with_allowed_viewers(vec!["acme.com".to_string()]);
+        let doc = Document {
+            name: None,
+            fields: Some(share_slug_index_to_fields(&config)),
+            create_time: None,
+            update_time: None,
+        };
+
+        let index = document_to_share_slug_index(&doc).unwrap();
+        assert_eq!(index.passcode_hash, config.passcode_hash);
+        assert_eq!(index.max_views, Some(3));
+        assert_eq!(index.allowed_viewers, vec!["acme.com".to_string()]);
+        assert_eq!(index.view_count, 0);
+        assert!(index.requires_unlock());
+        assert!(!index.views_exhausted());
+    }
+}
@@ -128,6 +128,24 @@ impl StorageAccountingRepository {
             .await
     }
 
+    /// Add uploaded source file storage (billable).
+    pub async fn add_uploaded_source(&self, bytes: u64) -> FirestoreResult<StorageAccounting> {
+        self.update_with_retry(|acc| acc.add_uploaded_source(bytes))
+            .await
+    }
+
+    /// Remove uploaded source file storage (billable).
+    pub async fn remove_uploaded_source(&self, bytes: u64) -> FirestoreResult<StorageAccounting> {
+        self.update_with_retry(|acc| acc.remove_uploaded_source(bytes))
+            .await
+    }
+
+    /// Overwrite uploaded source storage with the total measured in R2.
+    pub async fn set_uploaded_sources(&self, bytes: u64) -> FirestoreResult<StorageAccounting> {
+        self.update_with_retry(|acc| acc.set_uploaded_sources(bytes))
+            .await
+    }
+
     /// Add source video storage (non-billable).
     pub async fn add_source_video(&self, bytes: u64) -> FirestoreResult<StorageAccounting> {
         self.update_with_retry(|acc| acc.add_source_video(bytes))
@@ -164,6 +182,28 @@ impl StorageAccountingRepository {
             .await
     }
 
+    /// Remove swept cache storage in a single update.
+    pub async fn remove_cache(
+        &self,
+        source_bytes: u64,
+        raw_bytes: u64,
+        neural_bytes: u64,
+    ) -> FirestoreResult<StorageAccounting> {
+        self.update_with_retry(|acc| acc.remove_cache(source_bytes, raw_bytes, neural_bytes))
+            .await
+    }
+
+    /// Overwrite cache storage with totals measured in R2.
+    pub async fn set_cache(
+        &self,
+        source_bytes: u64,
+        raw_bytes: u64,
+        neural_bytes: u64,
+    ) -> FirestoreResult<StorageAccounting> {
+        self.update_with_retry(|acc| acc.set_cache(source_bytes, raw_bytes, neural_bytes))
+            .await
+    }
+
     /// Clear all non-billable cache storage for a video deletion.
     ///
     /// This zeros out source videos, raw segments, and neural cache bytes.
@@ -294,6 +334,10 @@ fn storage_accounting_to_fields(accounting: &StorageAccounting) -> HashMap<Strin
         "styled_clips_count".to_string(),
         accounting.styled_clips_count.to_firestore_value(),
     );
+    fields.insert(
+        "uploaded_sources_bytes".to_string(),
+        accounting.uploaded_sources_bytes.to_firestore_value(),
+    );
     fields.insert(
         "source_videos_bytes".to_string(),
         accounting.source_videos_bytes.to_firestore_value(),
@@ -340,6 +384,7 @@ fn document_to_storage_accounting(
     Ok(StorageAccounting {
         styled_clips_bytes: get_u64("styled_clips_bytes"),
         styled_clips_count: get_u32("styled_clips_count"),
+        uploaded_sources_bytes: get_u64("uploaded_sources_bytes"),
         source_videos_bytes: get_u64("source_videos_bytes"),
         raw_segments_bytes: get_u64("raw_segments_bytes"),
         neural_cache_bytes: get_u64("neural_cache_bytes"),
@@ -366,6 +411,7 @@ mod tests {
 
         assert!(fields.contains_key("styled_clips_bytes"));
         assert!(fields.contains_key("styled_clips_count"));
+        assert!(fields.contains_key("uploaded_sources_bytes"));
         assert!(fields.contains_key("source_videos_bytes"));
         assert!(fields.contains_key("raw_segments_bytes"));
         assert!(fields.contains_key("neural_cache_bytes"));
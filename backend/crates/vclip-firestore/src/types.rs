@@ -121,11 +121,52 @@ pub struct Write {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub update_mask: Option<DocumentMask>,
 
+    /// Server-side transforms applied after the update (e.g. increments).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_transforms: Option<Vec<FieldTransform>>,
+
     /// Precondition for the write.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub current_document: Option<Precondition>,
 }
 
+/// Server-side transform of a single field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldTransform {
+    /// Dotted field path; segments that are not plain identifiers must be
+    /// quoted with [`quote_field_path_segment`].
+    pub field_path: String,
+
+    /// Add this integer or double to the field (missing fields count as 0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub increment: Option<Value>,
+}
+
+impl FieldTransform {
+    /// Increment an integer field.
+    pub fn increment(field_path: impl Into<String>, by: i64) -> Self {
+        Self {
+            field_path: field_path.into(),
+            increment: Some(Value::IntegerValue(by.to_string())),
+        }
+    }
+}
+
+/// Quote a field path segment (such as a map key) unless it is a plain identifier.
+pub fn quote_field_path_segment(segment: &str) -> String {
+    let plain = segment
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        segment.to_string()
+    } else {
+        format!("`{}`", segment.replace('\\', "\\\\").replace('`', "\\`"))
+    }
+}
+
 /// Document field mask for partial updates.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,177 @@
+//! Source upload repository for Firestore.
+//!
+//! Uploads live under `users/{uid}/uploads/{upload_id}` and track a
+//! multipart upload to R2 from start to the draft it produced.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use tracing::info;
+
+use vclip_models::{SourceUpload, UploadStatus};
+
+use crate::client::FirestoreClient;
+use crate::error::{FirestoreError, FirestoreResult};
+use crate::types::{FromFirestoreValue, ToFirestoreValue, Value};
+
+/// Fields changed when an upload completes or is aborted.
+const STATUS_FIELDS: [&str; 4] = ["status", "draft_id", "job_id", "updated_at"];
+
+/// Repository for a user's source uploads.
+pub struct UploadRepository {
+    client: FirestoreClient,
+    user_id: String,
+}
+
+impl UploadRepository {
+    /// Create a new upload repository.
+    pub fn new(client: FirestoreClient, user_id: impl Into<String>) -> Self {
+        Self {
+            client,
+            user_id: user_id.into(),
+        }
+    }
+
+    /// Collection path for the user's uploads.
+    fn collection(&self) -> String {
+        format!("users/{}/uploads", self.user_id)
+    }
+
+    /// Record a new upload.
+    pub async fn create(&self, upload: &SourceUpload) -> FirestoreResult<()> {
+        self.client
+            .create_document(&self.collection(), &upload.id, upload_to_fields(upload))
+            .await?;
+        info!(
+            "Started upload {} ({}, {} bytes) for user {}",
+            upload.id, upload.filename, upload.size_bytes, self.user_id
+        );
+        Ok(())
+    }
+
+    /// Get an upload by ID.
+    pub async fn get(&self, upload_id: &str) -> FirestoreResult<Option<SourceUpload>> {
+        match self.client.get_document(&self.collection(), upload_id).await? {
+            Some(doc) => Ok(Some(document_to_upload(&doc, upload_id)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Store the upload's status and the draft and job it produced.
+    pub async fn update_status(&self, upload: &SourceUpload) -> FirestoreResult<()> {
+        let mut upload = upload.clone();
+        upload.updated_at = Utc::now();
+        let fields: HashMap<String, Value> = upload_to_fields(&upload)
+            .into_iter()
+            .filter(|(key, _)| STATUS_FIELDS.contains(&key.as_str()))
+            .collect();
+
+        self.client
+            .update_document(
+                &self.collection(),
+                &upload.id,
+                fields,
+                Some(STATUS_FIELDS.iter().map(|f| f.to_string()).collect()),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+fn upload_to_fields(upload: &SourceUpload) -> HashMap<String, Value> {
+    let mut fields = HashMap::new();
+
+    fields.insert("id".to_string(), upload.id.to_firestore_value());
+    fields.insert("user_id".to_string(), upload.user_id.to_firestore_value());
+    fields.insert("filename".to_string(), upload.filename.to_firestore_value());
+    fields.insert("content_type".to_string(), upload.content_type.to_firestore_value());
+    fields.insert("size_bytes".to_string(), upload.size_bytes.to_firestore_value());
+    fields.insert("r2_key".to_string(), upload.r2_key.to_firestore_value());
+    fields.insert(
+        "multipart_upload_id".to_string(),
+        upload.multipart_upload_id.to_firestore_value(),
+    );
+    fields.insert(
+        "part_size_bytes".to_string(),
+        upload.part_size_bytes.to_firestore_value(),
+    );
+    fields.insert("part_count".to_string(), upload.part_count.to_firestore_value());
+    fields.insert("status".to_string(), upload.status.as_str().to_firestore_value());
+    fields.insert("created_at".to_string(), upload.created_at.to_firestore_value());
+    fields.insert("updated_at".to_string(), upload.updated_at.to_firestore_value());
+
+    if let Some(ref draft_id) = upload.draft_id {
+        fields.insert("draft_id".to_string(), draft_id.to_firestore_value());
+    }
+    if let Some(ref job_id) = upload.job_id {
+        fields.insert("job_id".to_string(), job_id.to_firestore_value());
+    }
+
+    fields
+}
+
+fn document_to_upload(
+    doc: &crate::types::Document,
+    upload_id: &str,
+) -> FirestoreResult<SourceUpload> {
+    let fields = doc.fields.as_ref().ok_or_else(|| {
+        FirestoreError::InvalidResponse("Upload document has no fields".to_string())
+    })?;
+
+    let string = |key: &str| fields.get(key).and_then(String::from_firestore_value);
+    let number = |key: &str| fields.get(key).and_then(u64::from_firestore_value);
+    let timestamp = |key: &str| fields.get(key).and_then(chrono::DateTime::from_firestore_value);
+
+    Ok(SourceUpload {
+        id: upload_id.to_string(),
+        user_id: string("user_id").unwrap_or_default(),
+        filename: string("filename").unwrap_or_default(),
+        content_type: string("content_type").unwrap_or_default(),
+        size_bytes: number("size_bytes").unwrap_or(0),
+        r2_key: string("r2_key").unwrap_or_default(),
+        multipart_upload_id: string("multipart_upload_id").unwrap_or_default(),
+        part_size_bytes: number("part_size_bytes").unwrap_or(0),
+        part_count: fields
+            .get("part_count")
+            .and_then(u32::from_firestore_value)
+            .unwrap_or(0),
+        status: string("status")
+            .as_deref()
+            .and_then(UploadStatus::parse)
+            .unwrap_or(UploadStatus::Pending),
+        draft_id: string("draft_id"),
+        job_id: string("job_id"),
+        created_at: timestamp("created_at").unwrap_or_else(Utc::now),
+        updated_at: timestamp("updated_at").unwrap_or_else(Utc::now),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Document;
+
+    #[test]
+    fn test_upload_fields_roundtrip() {
+        let mut upload = SourceUpload::new("up1", "u1", "vod.mp4", 200 * 1024 * 1024, "mp-1");
+        upload.status = UploadStatus::Completed;
+        upload.draft_id = Some("d1".to_string());
+        upload.job_id = Some("j1".to_string());
+
+        let doc = Document::new(upload_to_fields(&upload));
+        let decoded = document_to_upload(&doc, "up1").unwrap();
+
+        assert_eq!(decoded.user_id, "u1");
+        assert_eq!(decoded.size_bytes, 200 * 1024 * 1024);
+        assert_eq!(decoded.part_count, upload.part_count);
+        assert_eq!(decoded.multipart_upload_id, "mp-1");
+        assert_eq!(decoded.status, UploadStatus::Completed);
+        assert_eq!(decoded.draft_id.as_deref(), Some("d1"));
+        assert_eq!(decoded.job_id.as_deref(), Some("j1"));
+
+        let mut completing = upload.clone();
+        completing.status = UploadStatus::Completing;
+        let doc = Document::new(upload_to_fields(&completing));
+        assert_eq!(document_to_upload(&doc, "up1").unwrap().status, UploadStatus::Completing);
+    }
+}
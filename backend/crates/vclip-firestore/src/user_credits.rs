@@ -1,7 +1,8 @@
 //! User credits repository for atomic credit charging.
 //!
 //! This module provides a shared repository for credit operations, used by both
-//! the API (upfront reservation) and worker (post-success charging).
+//! the API (upfront reservation) and worker (post-success charging and refunds
+//! for cancelled work).
 //!
 //! # Key Features
 //! - Atomic credit increment with optimistic locking
@@ -81,6 +82,34 @@ impl UserCreditsRepository {
         &self.user_id
     }
 
+    /// List the IDs of all user documents, following pagination.
+    ///
+    /// Used by maintenance jobs that walk every user's storage.
+    pub async fn list_user_ids(client: &FirestoreClient) -> FirestoreResult<Vec<String>> {
+        let mut user_ids = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let response = client
+                .list_documents("users", Some(300), page_token.as_deref())
+                .await?;
+            user_ids.extend(
+                response
+                    .documents
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|doc| doc.name.as_deref()?.split('/').next_back().map(String::from)),
+            );
+
+            match response.next_page_token {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(user_ids)
+    }
+
     /// Atomically charge credits to the user's account.
     ///
     /// Uses optimistic locking with Firestore's `updateTime` precondition
@@ -101,6 +130,50 @@ impl UserCreditsRepository {
     /// println!("User now has {} credits used this month", result.credits_used_after);
     /// ```
     pub async fn charge_credits(&self, credits: u32) -> FirestoreResult<CreditChargeResult> {
+        self.update_credits_used("charge", credits, |used, is_new_month| {
+            if is_new_month {
+                // New month - reset counter and start fresh
+                Some(credits)
+            } else {
+                Some(used.saturating_add(credits))
+            }
+        })
+        .await?
+        .ok_or_else(|| FirestoreError::request_failed("Credit charge was not applied"))
+    }
+
+    /// Atomically return credits reserved for work that was not delivered.
+    ///
+    /// Credits reserved in a previous month are not returned, since that
+    /// month's usage has already been reset.
+    ///
+    /// # Returns
+    /// * `Ok(Some(CreditChargeResult))` - The new total credits used
+    /// * `Ok(None)` - Nothing to refund (zero credits or a new month)
+    /// * `Err` - If the user is not found or the operation failed after retries
+    pub async fn refund_credits(&self, credits: u32) -> FirestoreResult<Option<CreditChargeResult>> {
+        if credits == 0 {
+            return Ok(None);
+        }
+        self.update_credits_used("refund", credits, |used, is_new_month| {
+            (!is_new_month).then(|| used.saturating_sub(credits))
+        })
+        .await
+    }
+
+    /// Apply a change to `credits_used_this_month` with optimistic locking.
+    ///
+    /// `next` maps the current usage (and whether the month rolled over) to
+    /// the new usage, or `None` to leave the document untouched.
+    async fn update_credits_used<F>(
+        &self,
+        operation: &str,
+        credits: u32,
+        next: F,
+    ) -> FirestoreResult<Option<CreditChargeResult>>
+    where
+        F: Fn(u32, bool) -> Option<u32>,
+    {
         let current_month = current_month_key();
         let mut last_error = None;
 
@@ -125,7 +198,7 @@ impl UserCreditsRepository {
                     (credits, reset_month, d.update_time.clone())
                 }
                 None => {
-                    warn!(user_id = %self.user_id, "User not found when charging credits");
+                    warn!(user_id = %self.user_id, operation, "User not found when updating credits");
                     return Err(FirestoreError::NotFound(format!(
                         "User {} not found",
                         self.user_id
@@ -137,11 +210,8 @@ impl UserCreditsRepository {
             let is_new_month = usage_reset_month.as_deref() != Some(&current_month);
 
             // Calculate new credit value
-            let new_credits = if is_new_month {
-                // New month - reset counter and start fresh
-                credits
-            } else {
-                credits_used.saturating_add(credits)
+            let Some(new_credits) = next(credits_used, is_new_month) else {
+                return Ok(None);
             };
 
             // Build update fields
@@ -177,22 +247,24 @@ impl UserCreditsRepository {
                 Ok(_) => {
                     info!(
                         user_id = %self.user_id,
+                        operation,
                         credits = credits,
                         total_used = new_credits,
                         month_reset = is_new_month,
-                        "Charged credits"
+                        "Updated credits"
                     );
-                    return Ok(CreditChargeResult {
+                    return Ok(Some(CreditChargeResult {
                         credits_used_after: new_credits,
                         month_reset: is_new_month,
-                    });
+                    }));
                 }
                 Err(e) if e.is_precondition_failed() => {
                     // Another writer updated the document; retry with exponential backoff
                     debug!(
                         user_id = %self.user_id,
+                        operation,
                         attempt = attempt + 1,
-                        "Credit charge precondition failed, retrying"
+                        "Credit update precondition failed, retrying"
                     );
                     last_error = Some(e);
                     let delay = Duration::from_millis(RETRY_BASE_DELAY_MS * (attempt as u64 + 1));
@@ -200,7 +272,7 @@ impl UserCreditsRepository {
                     continue;
                 }
                 Err(e) => {
-                    warn!(user_id = %self.user_id, error = %e, "Failed to charge credits");
+                    warn!(user_id = %self.user_id, operation, error = %e, "Failed to update credits");
                     return Err(e);
                 }
             }
@@ -209,13 +281,15 @@ impl UserCreditsRepository {
         // Exhausted retries
         warn!(
             user_id = %self.user_id,
+            operation,
             retries = MAX_CREDIT_RETRIES,
             error = ?last_error,
-            "Credit charge failed after retries"
+            "Credit update failed after retries"
         );
-        Err(FirestoreError::request_failed(
-            "Failed to charge credits due to concurrent updates",
-        ))
+        Err(FirestoreError::request_failed(format!(
+            "Failed to {} credits due to concurrent updates",
+            operation
+        )))
     }
 
     /// Get the current credits used this month.
@@ -0,0 +1,355 @@
+//! Webhook endpoint repository for Firestore.
+//!
+//! Endpoints live under `users/{uid}/webhooks/{endpoint_id}`, with their
+//! delivery log under `.../webhooks/{endpoint_id}/deliveries/{delivery_id}`.
+//! Delivery documents carry an `expires_at` field for a Firestore TTL policy.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use tracing::{info, warn};
+
+use vclip_models::{WebhookDelivery, WebhookDeliveryStatus, WebhookEndpoint, WebhookEventType};
+
+use crate::client::FirestoreClient;
+use crate::error::{FirestoreError, FirestoreResult};
+use crate::types::{
+    ArrayValue, CollectionSelector, Document, FieldReference, FromFirestoreValue, Order,
+    StructuredQuery, ToFirestoreValue, Value,
+};
+
+/// Collection ID of webhook endpoints (per user).
+const WEBHOOKS_COLLECTION: &str = "webhooks";
+
+/// Collection ID of an endpoint's delivery log.
+const DELIVERIES_COLLECTION: &str = "deliveries";
+
+/// Days delivery log entries are kept.
+const DELIVERY_LOG_RETENTION_DAYS: i64 = 14;
+
+/// Fields the user can change through the API.
+const SETTINGS_FIELDS: [&str; 5] = ["url", "description", "event_types", "enabled", "updated_at"];
+
+/// Repository for a user's webhook endpoints and their delivery logs.
+pub struct WebhookRepository {
+    client: FirestoreClient,
+    user_id: String,
+}
+
+impl WebhookRepository {
+    /// Create a new webhook repository.
+    pub fn new(client: FirestoreClient, user_id: impl Into<String>) -> Self {
+        Self {
+            client,
+            user_id: user_id.into(),
+        }
+    }
+
+    /// Collection path for the user's endpoints.
+    fn collection(&self) -> String {
+        format!("users/{}/{}", self.user_id, WEBHOOKS_COLLECTION)
+    }
+
+    /// Collection path for an endpoint's delivery log.
+    fn deliveries_collection(&self, endpoint_id: &str) -> String {
+        format!("{}/{}/{}", self.collection(), endpoint_id, DELIVERIES_COLLECTION)
+    }
+
+    /// Add an endpoint.
+    pub async fn create(&self, endpoint: &WebhookEndpoint) -> FirestoreResult<()> {
+        self.client
+            .create_document(&self.collection(), &endpoint.id, endpoint_to_fields(endpoint))
+            .await?;
+        info!("Added webhook endpoint {} for user {}", endpoint.id, self.user_id);
+        Ok(())
+    }
+
+    /// Get an endpoint by ID.
+    pub async fn get(&self, endpoint_id: &str) -> FirestoreResult<Option<WebhookEndpoint>> {
+        match self.client.get_document(&self.collection(), endpoint_id).await? {
+            Some(doc) => Ok(Some(document_to_endpoint(&doc, endpoint_id)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List the user's endpoints, oldest first.
+    pub async fn list(&self) -> FirestoreResult<Vec<WebhookEndpoint>> {
+        let response = self
+            .client
+            .list_documents(&self.collection(), None, None)
+            .await?;
+
+        let mut endpoints: Vec<WebhookEndpoint> = response
+            .documents
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|doc| {
+                let name = doc.name.as_ref()?;
+                let endpoint_id = name.split('/').next_back()?;
+                document_to_endpoint(doc, endpoint_id).ok()
+            })
+            .collect();
+        endpoints.sort_by_key(|e| e.created_at);
+
+        Ok(endpoints)
+    }
+
+    /// Store user-editable settings (URL, description, event filter, enabled).
+    pub async fn update_settings(&self, endpoint: &WebhookEndpoint) -> FirestoreResult<()> {
+        let mut endpoint = endpoint.clone();
+        endpoint.updated_at = Utc::now();
+        let fields: HashMap<String, Value> = endpoint_to_fields(&endpoint)
+            .into_iter()
+            .filter(|(key, _)| SETTINGS_FIELDS.contains(&key.as_str()))
+            .collect();
+
+        self.client
+            .update_document(
+                &self.collection(),
+                &endpoint.id,
+                fields,
+                Some(SETTINGS_FIELDS.iter().map(|f| f.to_string()).collect()),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Delete an endpoint and its delivery log.
+    pub async fn delete(&self, endpoint_id: &str) -> FirestoreResult<()> {
+        let deliveries = self.deliveries_collection(endpoint_id);
+        let response = self.client.list_documents(&deliveries, None, None).await?;
+        for doc in response.documents.unwrap_or_default() {
+            let Some(delivery_id) = doc.name.as_deref().and_then(|n| n.split('/').next_back()) else {
+                continue;
+            };
+            if let Err(e) = self.client.delete_document(&deliveries, delivery_id).await {
+                warn!("Failed to delete webhook delivery {}: {}", delivery_id, e);
+            }
+        }
+
+        self.client
+            .delete_document(&self.collection(), endpoint_id)
+            .await?;
+        info!("Deleted webhook endpoint {} for user {}", endpoint_id, self.user_id);
+        Ok(())
+    }
+
+    /// Create or update a delivery log entry.
+    pub async fn record_delivery(&self, delivery: &WebhookDelivery) -> FirestoreResult<()> {
+        self.client
+            .update_document(
+                &self.deliveries_collection(&delivery.endpoint_id),
+                &delivery.id,
+                delivery_to_fields(delivery),
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Get a delivery log entry.
+    pub async fn get_delivery(
+        &self,
+        endpoint_id: &str,
+        delivery_id: &str,
+    ) -> FirestoreResult<Option<WebhookDelivery>> {
+        match self
+            .client
+            .get_document(&self.deliveries_collection(endpoint_id), delivery_id)
+            .await?
+        {
+            Some(doc) => Ok(Some(document_to_delivery(&doc, delivery_id)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List an endpoint's most recent deliveries, newest first.
+    pub async fn list_deliveries(
+        &self,
+        endpoint_id: &str,
+        limit: u32,
+    ) -> FirestoreResult<Vec<WebhookDelivery>> {
+        let query = StructuredQuery {
+            from: vec![CollectionSelector {
+                collection_id: DELIVERIES_COLLECTION.to_string(),
+                all_descendants: None,
+            }],
+            r#where: None,
+            order_by: Some(vec![Order {
+                field: FieldReference {
+                    field_path: "created_at".to_string(),
+                },
+                direction: "DESCENDING".to_string(),
+            }]),
+            start_at: None,
+            limit: Some(limit.clamp(1, 100) as i32),
+        };
+
+        let parent_path = format!("{}/{}", self.collection(), endpoint_id);
+        let docs = self.client.run_query(&parent_path, query).await?;
+
+        Ok(docs
+            .iter()
+            .filter_map(|doc| {
+                let name = doc.name.as_ref()?;
+                let delivery_id = name.split('/').next_back()?;
+                document_to_delivery(doc, delivery_id).ok()
+            })
+            .collect())
+    }
+}
+
+fn event_types_to_value(event_types: &[WebhookEventType]) -> Value {
+    event_types
+        .iter()
+        .map(|t| t.as_str().to_string())
+        .collect::<Vec<String>>()
+        .to_firestore_value()
+}
+
+fn endpoint_to_fields(endpoint: &WebhookEndpoint) -> HashMap<String, Value> {
+    let mut fields = HashMap::new();
+
+    fields.insert("id".to_string(), endpoint.id.to_firestore_value());
+    fields.insert("user_id".to_string(), endpoint.user_id.to_firestore_value());
+    fields.insert("url".to_string(), endpoint.url.to_firestore_value());
+    fields.insert("secret".to_string(), endpoint.secret.to_firestore_value());
+    fields.insert("event_types".to_string(), event_types_to_value(&endpoint.event_types));
+    fields.insert("enabled".to_string(), endpoint.enabled.to_firestore_value());
+    fields.insert("created_at".to_string(), endpoint.created_at.to_firestore_value());
+    fields.insert("updated_at".to_string(), endpoint.updated_at.to_firestore_value());
+
+    if let Some(ref description) = endpoint.description {
+        fields.insert("description".to_string(), description.to_firestore_value());
+    }
+
+    fields
+}
+
+fn document_to_endpoint(doc: &Document, endpoint_id: &str) -> FirestoreResult<WebhookEndpoint> {
+    let fields = doc.fields.as_ref().ok_or_else(|| {
+        FirestoreError::InvalidResponse("Webhook document has no fields".to_string())
+    })?;
+
+    let string = |key: &str| fields.get(key).and_then(String::from_firestore_value);
+    let timestamp = |key: &str| fields.get(key).and_then(DateTime::from_firestore_value);
+
+    let event_types = match fields.get("event_types") {
+        Some(Value::ArrayValue(ArrayValue { values: Some(values) })) => values
+            .iter()
+            .filter_map(String::from_firestore_value)
+            .filter_map(|s| WebhookEventType::parse(&s))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(WebhookEndpoint {
+        id: endpoint_id.to_string(),
+        user_id: string("user_id").unwrap_or_default(),
+        url: string("url").unwrap_or_default(),
+        secret: string("secret").unwrap_or_default(),
+        description: string("description"),
+        event_types,
+        enabled: fields
+            .get("enabled")
+            .and_then(bool::from_firestore_value)
+            .unwrap_or(false),
+        created_at: timestamp("created_at").unwrap_or_else(Utc::now),
+        updated_at: timestamp("updated_at").unwrap_or_else(Utc::now),
+    })
+}
+
+fn delivery_to_fields(delivery: &WebhookDelivery) -> HashMap<String, Value> {
+    let mut fields = HashMap::new();
+
+    fields.insert("id".to_string(), delivery.id.to_firestore_value());
+    fields.insert("endpoint_id".to_string(), delivery.endpoint_id.to_firestore_value());
+    fields.insert("event_id".to_string(), delivery.event_id.to_firestore_value());
+    fields.insert("event_type".to_string(), delivery.event_type.as_str().to_firestore_value());
+    fields.insert("status".to_string(), delivery.status.as_str().to_firestore_value());
+    fields.insert("attempts".to_string(), delivery.attempts.to_firestore_value());
+    fields.insert("created_at".to_string(), delivery.created_at.to_firestore_value());
+    fields.insert("updated_at".to_string(), delivery.updated_at.to_firestore_value());
+    fields.insert(
+        "expires_at".to_string(),
+        (delivery.created_at + chrono::Duration::days(DELIVERY_LOG_RETENTION_DAYS))
+            .to_firestore_value(),
+    );
+
+    if let Some(status) = delivery.response_status {
+        fields.insert("response_status".to_string(), u32::from(status).to_firestore_value());
+    }
+    if let Some(ref error) = delivery.error {
+        fields.insert("error".to_string(), error.to_firestore_value());
+    }
+    if let Some(next_attempt_at) = delivery.next_attempt_at {
+        fields.insert("next_attempt_at".to_string(), next_attempt_at.to_firestore_value());
+    }
+
+    fields
+}
+
+fn document_to_delivery(doc: &Document, delivery_id: &str) -> FirestoreResult<WebhookDelivery> {
+    let fields = doc.fields.as_ref().ok_or_else(|| {
+        FirestoreError::InvalidResponse("Webhook delivery document has no fields".to_string())
+    })?;
+
+    let string = |key: &str| fields.get(key).and_then(String::from_firestore_value);
+    let timestamp = |key: &str| fields.get(key).and_then(DateTime::from_firestore_value);
+
+    let event_type = string("event_type")
+        .and_then(|s| WebhookEventType::parse(&s))
+        .ok_or_else(|| {
+            FirestoreError::InvalidResponse("Webhook delivery has no event type".to_string())
+        })?;
+
+    Ok(WebhookDelivery {
+        id: delivery_id.to_string(),
+        endpoint_id: string("endpoint_id").unwrap_or_default(),
+        event_id: string("event_id").unwrap_or_default(),
+        event_type,
+        status: WebhookDeliveryStatus::parse(&string("status").unwrap_or_default()),
+        attempts: fields
+            .get("attempts")
+            .and_then(u32::from_firestore_value)
+            .unwrap_or(0),
+        response_status: fields
+            .get("response_status")
+            .and_then(u32::from_firestore_value)
+            .and_then(|s| u16::try_from(s).ok()),
+        error: string("error"),
+        next_attempt_at: timestamp("next_attempt_at"),
+        created_at: timestamp("created_at").unwrap_or_else(Utc::now),
+        updated_at: timestamp("updated_at").unwrap_or_else(Utc::now),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vclip_models::WebhookPayload;
+
+    #[test]
+    fn test_webhook_fields_roundtrip() {
+        let endpoint = WebhookEndpoint::new("e1", "u1", "https://example.com/hook")
+            .with_description("CI")
+            .with_event_types(vec![WebhookEventType::JobDone, WebhookEventType::JobError]);
+
+        let decoded = document_to_endpoint(&Document::new(endpoint_to_fields(&endpoint)), "e1").unwrap();
+        assert_eq!(decoded.url, "https://example.com/hook");
+        assert_eq!(decoded.secret, endpoint.secret);
+        assert_eq!(decoded.description.as_deref(), Some("CI"));
+        assert_eq!(decoded.event_types, endpoint.event_types);
+        assert!(decoded.enabled);
+
+        let mut delivery = WebhookDelivery::new("e1", &WebhookPayload::test("evt", "e1"));
+        delivery.record_failure(Some(502), "bad gateway");
+        let decoded =
+            document_to_delivery(&Document::new(delivery_to_fields(&delivery)), &delivery.id).unwrap();
+        assert_eq!(decoded.event_type, WebhookEventType::Test);
+        assert_eq!(decoded.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(decoded.attempts, 1);
+        assert_eq!(decoded.response_status, Some(502));
+        assert!(decoded.next_attempt_at.is_some());
+    }
+}
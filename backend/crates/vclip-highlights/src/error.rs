@@ -0,0 +1,36 @@
+//! Highlight provider error types.
+
+use thiserror::Error;
+
+pub type HighlightResult<T> = Result<T, HighlightError>;
+
+#[derive(Debug, Error)]
+pub enum HighlightError {
+    #[error("Provider not configured: {0}")]
+    NotConfigured(String),
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Provider returned {status}: {body}")]
+    Api { status: u16, body: String },
+
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+
+    #[error("Network error: {0}")]
+    Network(#[from] reqwest::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl HighlightError {
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            HighlightError::Network(_) => true,
+            HighlightError::Api { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Google Gemini highlight provider.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::error::{HighlightError, HighlightResult};
+use crate::prompt::parse_highlights_json;
+use crate::provider::{HighlightProvider, HighlightProviderKind, HighlightRequest};
+use crate::types::HighlightsResponse;
+
+/// Models tried in order until one succeeds.
+const DEFAULT_MODELS: &[&str] = &[
+    "gemini-3-flash-preview",
+    "gemini-2.5-flash",
+    "gemini-2.5-flash-lite",
+    "gemini-2.5-pro",
+    "gemini-3-pro-preview",
+];
+
+/// Gemini API request.
+#[derive(Debug, Serialize)]
+struct GeminiRequest {
+    contents: Vec<Content>,
+    #[serde(rename = "generationConfig")]
+    generation_config: GenerationConfig,
+}
+
+#[derive(Debug, Serialize)]
+struct Content {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Serialize)]
+struct Part {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct GenerationConfig {
+    #[serde(rename = "responseMimeType")]
+    response_mime_type: String,
+}
+
+/// Gemini API response.
+#[derive(Debug, Deserialize)]
+struct GeminiResponse {
+    candidates: Vec<Candidate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Candidate {
+    content: ResponseContent,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseContent {
+    parts: Vec<ResponsePart>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponsePart {
+    text: String,
+}
+
+/// Highlight provider backed by the Gemini Generative Language API.
+pub struct GeminiProvider {
+    api_key: String,
+    models: Vec<String>,
+    client: Client,
+}
+
+impl GeminiProvider {
+    /// Create a provider with the default model fallback list.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            models: DEFAULT_MODELS.iter().map(|m| m.to_string()).collect(),
+            client: Client::builder()
+                .timeout(Duration::from_secs(300))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Create from environment variables.
+    ///
+    /// Reads `GEMINI_API_KEY` and optionally `GEMINI_MODELS` (comma-separated).
+    pub fn from_env() -> HighlightResult<Self> {
+        let api_key = std::env::var("GEMINI_API_KEY")
+            .map_err(|_| HighlightError::NotConfigured("GEMINI_API_KEY not set".to_string()))?;
+
+        let provider = Self::new(api_key);
+        match std::env::var("GEMINI_MODELS") {
+            Ok(models) => Ok(provider.with_models(models.split(',').map(str::to_string).collect())),
+            Err(_) => Ok(provider),
+        }
+    }
+
+    /// Override the model fallback list (empty entries are ignored).
+    pub fn with_models(mut self, models: Vec<String>) -> Self {
+        let models: Vec<String> = models
+            .into_iter()
+            .map(|m| m.trim().to_string())
+            .filter(|m| !m.is_empty())
+            .collect();
+        if !models.is_empty() {
+            self.models = models;
+        }
+        self
+    }
+
+    /// Call the API with one model.
+    async fn call_model(&self, model: &str, prompt: &str) -> HighlightResult<HighlightsResponse> {
+        let url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+            model, self.api_key
+        );
+
+        let request = GeminiRequest {
+            contents: vec![Content {
+                parts: vec![Part {
+                    text: prompt.to_string(),
+                }],
+            }],
+            generation_config: GenerationConfig {
+                response_mime_type: "application/json".to_string(),
+            },
+        };
+
+        let response = self.client.post(&url).json(&request).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            return Err(HighlightError::Api { status, body });
+        }
+
+        let gemini_response: GeminiResponse = response.json().await?;
+
+        let text = gemini_response
+            .candidates
+            .first()
+            .and_then(|c| c.content.parts.first())
+            .map(|p| p.text.as_str())
+            .ok_or_else(|| {
+                HighlightError::InvalidResponse("No content in Gemini response".to_string())
+            })?;
+
+        parse_highlights_json(text)
+    }
+}
+
+#[async_trait]
+impl HighlightProvider for GeminiProvider {
+    fn kind(&self) -> HighlightProviderKind {
+        HighlightProviderKind::Gemini
+    }
+
+    async fn detect_highlights(
+        &self,
+        request: &HighlightRequest,
+    ) -> HighlightResult<HighlightsResponse> {
+        let mut last_error = None;
+
+        for model in &self.models {
+            info!("Attempting Gemini API with model: {}", model);
+            match self.call_model(model, &request.prompt).await {
+                Ok(mut data) => {
+                    if data.video_url.is_none() {
+                        data.video_url = Some(request.video_url.clone());
+                    }
+                    info!("Successfully got highlights from {}", model);
+                    return Ok(data);
+                }
+                Err(e) => {
+                    warn!("Failed with model {}: {}", model, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            HighlightError::NotConfigured("No Gemini models configured".to_string())
+        }))
+    }
+}
@@ -0,0 +1,549 @@
+//! Offline heuristic highlight provider.
+//!
+//! Scores transcript lines by hook keywords and punctuation, boosts lines
+//! spoken over unusually loud audio, then picks the best non-overlapping
+//! 20-90 second windows. No network access and fully deterministic: the same
+//! transcript and audio always produce the same highlights.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use vclip_models::{format_seconds, parse_timestamp, HOOK_WINDOW_SECS};
+
+use crate::error::{HighlightError, HighlightResult};
+use crate::provider::{HighlightProvider, HighlightProviderKind, HighlightRequest};
+use crate::types::{EnergySample, HighlightCandidate, HighlightsResponse};
+
+/// Shortest highlight returned (seconds).
+const MIN_WINDOW_SECS: f64 = 20.0;
+
+/// Longest highlight returned (seconds).
+const MAX_WINDOW_SECS: f64 = 90.0;
+
+/// Preferred highlight length; windows further from it score lower (seconds).
+const TARGET_WINDOW_SECS: f64 = 45.0;
+
+/// Assumed length of the final transcript line (seconds).
+const LAST_LINE_SECS: f64 = 5.0;
+
+/// Score added per standard deviation of loudness above the video mean.
+const ENERGY_WEIGHT: f64 = 1.0;
+
+/// Hook score credited for any speech in a scene's opening.
+const HOOK_SPEECH_SCORE: f64 = 0.5;
+
+/// Maximum title length in characters.
+const MAX_TITLE_CHARS: usize = 60;
+
+/// Maximum description length in characters.
+const MAX_DESCRIPTION_CHARS: usize = 150;
+
+/// Hook phrases, the category they signal and their weight.
+const KEYWORDS: &[(&str, &str, f64)] = &[
+    ("can't believe", "surprising", 2.0),
+    ("no way", "surprising", 1.5),
+    ("shocking", "surprising", 2.0),
+    ("turns out", "surprising", 1.5),
+    ("secret", "surprising", 1.5),
+    ("plot twist", "surprising", 2.0),
+    ("crazy", "surprising", 1.0),
+    ("insane", "surprising", 1.0),
+    ("funny", "humorous", 1.0),
+    ("hilarious", "humorous", 2.0),
+    ("joke", "humorous", 1.0),
+    ("laughing", "humorous", 1.5),
+    ("haha", "humorous", 1.5),
+    ("controversial", "controversial", 2.0),
+    ("unpopular opinion", "controversial", 2.0),
+    ("disagree", "controversial", 1.5),
+    ("wrong", "controversial", 1.0),
+    ("lie", "controversial", 1.0),
+    ("scam", "controversial", 2.0),
+    ("love", "emotional", 1.0),
+    ("cried", "emotional", 2.0),
+    ("heartbreaking", "emotional", 2.0),
+    ("scared", "emotional", 1.5),
+    ("never forget", "emotional", 1.5),
+    ("here's how", "educational", 1.5),
+    ("the trick", "educational", 1.5),
+    ("mistake", "educational", 1.5),
+    ("tip", "educational", 1.0),
+    ("the reason", "educational", 1.0),
+    ("how to", "educational", 1.0),
+    ("dream", "inspirational", 1.0),
+    ("never give up", "inspirational", 2.0),
+    ("changed my life", "inspirational", 2.0),
+    ("success", "inspirational", 1.0),
+    ("fight", "dramatic", 1.0),
+    ("suddenly", "dramatic", 1.5),
+    ("finally", "dramatic", 1.0),
+    ("the moment", "dramatic", 1.0),
+];
+
+/// A parsed `[HH:MM:SS] text` transcript line.
+#[derive(Debug, Clone)]
+struct TranscriptLine {
+    start: f64,
+    end: f64,
+    text: String,
+}
+
+/// Score breakdown for one transcript line.
+#[derive(Debug, Clone, Default)]
+struct LineScore {
+    total: f64,
+    categories: HashMap<&'static str, f64>,
+    keywords: Vec<&'static str>,
+    loud: bool,
+}
+
+/// A candidate window over lines `first..=last`.
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    first: usize,
+    last: usize,
+    start: f64,
+    end: f64,
+    score: f64,
+}
+
+/// Deterministic keyword and audio energy highlight provider.
+#[derive(Debug, Clone, Default)]
+pub struct HeuristicProvider;
+
+impl HeuristicProvider {
+    /// Create a new heuristic provider.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl HighlightProvider for HeuristicProvider {
+    fn kind(&self) -> HighlightProviderKind {
+        HighlightProviderKind::Heuristic
+    }
+
+    async fn detect_highlights(
+        &self,
+        request: &HighlightRequest,
+    ) -> HighlightResult<HighlightsResponse> {
+        let lines = parse_transcript(&request.transcript);
+        if lines.is_empty() {
+            return Err(HighlightError::InvalidRequest(
+                "Transcript has no timestamped lines".to_string(),
+            ));
+        }
+
+        let scores = score_lines(&lines, &request.audio_energy);
+        let windows = select_windows(&lines, &scores, &request.exclude, request.max_highlights);
+
+        let highlights = windows
+            .iter()
+            .enumerate()
+            .map(|(i, w)| build_candidate(i as u32 + 1, w, &lines, &scores))
+            .collect();
+
+        Ok(HighlightsResponse {
+            video_url: Some(request.video_url.clone()),
+            video_title: None,
+            highlights,
+        })
+    }
+}
+
+/// Strength of the opening hook of a scene starting at `start` (0-1).
+///
+/// Lines spoken in the first [`HOOK_WINDOW_SECS`] are scored with the same
+/// keyword and punctuation rules as highlight detection; a silent opening
+/// scores 0. Returns `None` when the transcript has no timestamped lines.
+pub fn hook_strength(transcript: &str, start: f64) -> Option<f64> {
+    let lines = parse_transcript(transcript);
+    if lines.is_empty() {
+        return None;
+    }
+
+    let hook_end = start + HOOK_WINDOW_SECS;
+    let opening: Vec<TranscriptLine> = lines
+        .into_iter()
+        .filter(|line| overlaps(line.start, line.end, start, hook_end))
+        .collect();
+    if opening.is_empty() {
+        return Some(0.0);
+    }
+
+    let total: f64 = score_lines(&opening, &[]).iter().map(|s| s.total).sum();
+    let strength = 1.0 - (-(HOOK_SPEECH_SCORE + total)).exp();
+    Some((strength * 1000.0).round() / 1000.0)
+}
+
+/// Parse `[HH:MM:SS] text` lines; each line lasts until the next one starts.
+fn parse_transcript(transcript: &str) -> Vec<TranscriptLine> {
+    let mut lines: Vec<TranscriptLine> = transcript
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix('[')?;
+            let (ts, text) = rest.split_once(']')?;
+            let start = parse_timestamp(ts.trim()).ok()?;
+            let text = text.trim();
+            (!text.is_empty()).then(|| TranscriptLine {
+                start,
+                end: start,
+                text: text.to_string(),
+            })
+        })
+        .collect();
+
+    lines.sort_by(|a, b| a.start.total_cmp(&b.start));
+    for i in 0..lines.len() {
+        lines[i].end = match lines.get(i + 1) {
+            Some(next) if next.start > lines[i].start => next.start,
+            _ => lines[i].start + LAST_LINE_SECS,
+        };
+    }
+    lines
+}
+
+/// Lowercase, strip punctuation and pad with spaces for whole-word matching.
+fn normalize(text: &str) -> String {
+    let cleaned: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '\'' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect();
+    format!(
+        " {} ",
+        cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+    )
+}
+
+/// Mean loudness of samples in `[start, end)`, if any.
+fn mean_loudness(samples: &[EnergySample], start: f64, end: f64) -> Option<f64> {
+    let values: Vec<f64> = samples
+        .iter()
+        .filter(|s| s.time >= start && s.time < end && s.loudness.is_finite())
+        .map(|s| s.loudness)
+        .collect();
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Score every transcript line.
+fn score_lines(lines: &[TranscriptLine], energy: &[EnergySample]) -> Vec<LineScore> {
+    let finite: Vec<f64> = energy
+        .iter()
+        .map(|s| s.loudness)
+        .filter(|l| l.is_finite())
+        .collect();
+    let energy_stats = if finite.len() > 1 {
+        let mean = finite.iter().sum::<f64>() / finite.len() as f64;
+        let var = finite.iter().map(|l| (l - mean).powi(2)).sum::<f64>() / finite.len() as f64;
+        (var > 0.0).then(|| (mean, var.sqrt()))
+    } else {
+        None
+    };
+
+    lines
+        .iter()
+        .map(|line| {
+            let mut score = LineScore::default();
+            let text = normalize(&line.text);
+
+            for (keyword, category, weight) in KEYWORDS {
+                if text.contains(&format!(" {} ", keyword)) {
+                    score.total += weight;
+                    *score.categories.entry(category).or_default() += weight;
+                    score.keywords.push(keyword);
+                }
+            }
+
+            let exclamations = line.text.matches('!').count().min(3) as f64;
+            score.total += 0.5 * exclamations;
+            if line.text.contains('?') {
+                score.total += 0.3;
+            }
+
+            if let Some((mean, std_dev)) = energy_stats {
+                if let Some(loudness) = mean_loudness(energy, line.start, line.end) {
+                    let z = (loudness - mean) / std_dev;
+                    if z > 0.0 {
+                        score.total += z * ENERGY_WEIGHT;
+                        score.loud = z >= 1.0;
+                    }
+                }
+            }
+
+            score
+        })
+        .collect()
+}
+
+/// Whether `[a_start, a_end)` and `[b_start, b_end)` overlap.
+fn overlaps(a_start: f64, a_end: f64, b_start: f64, b_end: f64) -> bool {
+    a_start < b_end && b_start < a_end
+}
+
+/// Pick the best non-overlapping windows, returned in start order.
+fn select_windows(
+    lines: &[TranscriptLine],
+    scores: &[LineScore],
+    exclude: &[(f64, f64)],
+    max: usize,
+) -> Vec<Window> {
+    let mut prefix = vec![0.0; lines.len() + 1];
+    for (i, score) in scores.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + score.total;
+    }
+
+    // Best window starting at each line
+    let mut candidates: Vec<Window> = Vec::new();
+    for first in 0..lines.len() {
+        let mut best: Option<Window> = None;
+        for last in first..lines.len() {
+            let (start, end) = (lines[first].start, lines[last].end);
+            let duration = end - start;
+            if duration > MAX_WINDOW_SECS {
+                break;
+            }
+            if duration < MIN_WINDOW_SECS {
+                continue;
+            }
+            let shape = 1.0 - 0.3 * ((duration - TARGET_WINDOW_SECS).abs() / TARGET_WINDOW_SECS);
+            let score = (prefix[last + 1] - prefix[first]) * shape;
+            if best.is_none_or(|b| score > b.score) {
+                best = Some(Window {
+                    first,
+                    last,
+                    start,
+                    end,
+                    score,
+                });
+            }
+        }
+        if let Some(window) = best.filter(|w| w.score > 0.0) {
+            candidates.push(window);
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.start.total_cmp(&b.start))
+    });
+
+    let mut selected: Vec<Window> = Vec::new();
+    for window in candidates {
+        if selected.len() >= max {
+            break;
+        }
+        let blocked = exclude
+            .iter()
+            .any(|&(s, e)| overlaps(window.start, window.end, s, e))
+            || selected
+                .iter()
+                .any(|w| overlaps(window.start, window.end, w.start, w.end));
+        if !blocked {
+            selected.push(window);
+        }
+    }
+
+    selected.sort_by(|a, b| a.start.total_cmp(&b.start));
+    selected
+}
+
+/// Truncate at a word boundary, appending an ellipsis when shortened.
+fn truncate_words(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    for word in text.split_whitespace() {
+        if out.chars().count() + word.chars().count() + 1 > max_chars {
+            break;
+        }
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+    format!("{}…", out)
+}
+
+/// Turn a selected window into a highlight.
+fn build_candidate(
+    id: u32,
+    window: &Window,
+    lines: &[TranscriptLine],
+    scores: &[LineScore],
+) -> HighlightCandidate {
+    let range = window.first..=window.last;
+
+    let best_line = range
+        .clone()
+        .max_by(|&a, &b| scores[a].total.total_cmp(&scores[b].total).then(b.cmp(&a)))
+        .unwrap_or(window.first);
+
+    let mut categories: HashMap<&'static str, f64> = HashMap::new();
+    let mut keywords: Vec<&'static str> = Vec::new();
+    let mut loud = false;
+    for i in range.clone() {
+        for (category, weight) in &scores[i].categories {
+            *categories.entry(category).or_default() += weight;
+        }
+        for keyword in &scores[i].keywords {
+            if !keywords.contains(keyword) {
+                keywords.push(keyword);
+            }
+        }
+        loud |= scores[i].loud;
+    }
+
+    let hook_category = categories
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(a.0)))
+        .map(|(category, _)| category.to_string());
+
+    let mut reasons = Vec::new();
+    if !keywords.is_empty() {
+        reasons.push(format!("Hook phrases: {}", keywords.join(", ")));
+    }
+    if loud {
+        reasons.push("audio energy well above the video average".to_string());
+    }
+
+    let text: Vec<&str> = range.map(|i| lines[i].text.as_str()).collect();
+    let start = window.start.floor();
+    let end = window.end.ceil();
+
+    HighlightCandidate {
+        id,
+        title: truncate_words(&lines[best_line].text, MAX_TITLE_CHARS),
+        start: format_seconds(start),
+        end: format_seconds(end),
+        duration: (end - start) as u32,
+        pad_before_seconds: 1.0,
+        pad_after_seconds: 1.0,
+        hook_category,
+        reason: (!reasons.is_empty()).then(|| reasons.join("; ")),
+        description: Some(truncate_words(&text.join(" "), MAX_DESCRIPTION_CHARS)),
+        confidence: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript() -> String {
+        let mut t = String::new();
+        for i in 0..40 {
+            let secs = i * 5;
+            let text = match i {
+                12 => "You won't believe this, turns out it was a scam!",
+                13 => "I can't believe it, no way!",
+                30 => "Here's how you avoid that mistake",
+                _ => "and then we kept talking about the weather",
+            };
+            t.push_str(&format!("[{}] {}\n", format_seconds(secs as f64), text));
+        }
+        t
+    }
+
+    #[test]
+    fn test_parse_transcript() {
+        let lines = parse_transcript("[00:00:05] second\nnoise\n[00:00:01] first\n[bad] x\n");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].text, "first");
+        assert_eq!(lines[0].end, 5.0);
+        assert_eq!(lines[1].end, 5.0 + LAST_LINE_SECS);
+    }
+
+    #[tokio::test]
+    async fn test_detects_keyword_windows_deterministically() {
+        let request = HighlightRequest::new("", transcript(), "https://example.com/v");
+        let provider = HeuristicProvider::new();
+        let first = provider.detect_highlights(&request).await.unwrap();
+        let second = provider.detect_highlights(&request).await.unwrap();
+
+        assert_eq!(first.highlights.len(), 2);
+        assert_eq!(
+            serde_json::to_string(&first).unwrap(),
+            serde_json::to_string(&second).unwrap()
+        );
+
+        let top = &first.highlights[0];
+        assert_eq!(top.id, 1);
+        assert!(parse_timestamp(&top.start).unwrap() <= 60.0);
+        assert!(parse_timestamp(&top.end).unwrap() >= 70.0);
+        assert!((MIN_WINDOW_SECS as u32..=MAX_WINDOW_SECS as u32).contains(&top.duration));
+        assert_eq!(top.hook_category.as_deref(), Some("surprising"));
+        assert_eq!(
+            first.highlights[1].hook_category.as_deref(),
+            Some("educational")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exclusions_and_energy() {
+        let request = HighlightRequest::new("", transcript(), "https://example.com/v")
+            .with_exclusions(vec![(0.0, 120.0)]);
+        let response = HeuristicProvider::new()
+            .detect_highlights(&request)
+            .await
+            .unwrap();
+        assert_eq!(response.highlights.len(), 1);
+        assert!(parse_timestamp(&response.highlights[0].start).unwrap() >= 120.0);
+
+        // A loud stretch with no keywords is still picked up
+        let energy = (0..200)
+            .map(|t| EnergySample {
+                time: t as f64,
+                loudness: if (170..195).contains(&t) {
+                    -10.0
+                } else {
+                    -30.0
+                },
+            })
+            .collect();
+        let request = HighlightRequest::new("", transcript(), "https://example.com/v")
+            .with_exclusions(vec![(0.0, 160.0)])
+            .with_audio_energy(energy);
+        let response = HeuristicProvider::new()
+            .detect_highlights(&request)
+            .await
+            .unwrap();
+        assert_eq!(response.highlights.len(), 1);
+        assert!(response.highlights[0]
+            .reason
+            .as_deref()
+            .unwrap()
+            .contains("audio energy"));
+    }
+
+    #[test]
+    fn test_hook_strength() {
+        let transcript = transcript();
+        let plain = hook_strength(&transcript, 0.0).unwrap();
+        let hooked = hook_strength(&transcript, 60.0).unwrap();
+        assert!(plain > 0.0 && plain < 0.5);
+        assert!(hooked > 0.95);
+
+        // Opening after the last line is silent
+        assert_eq!(hook_strength(&transcript, 1000.0), Some(0.0));
+        assert_eq!(hook_strength("no timestamps here", 0.0), None);
+    }
+
+    #[tokio::test]
+    async fn test_empty_transcript_is_rejected() {
+        let request = HighlightRequest::new("", "no timestamps here", "u");
+        assert!(HeuristicProvider::new()
+            .detect_highlights(&request)
+            .await
+            .is_err());
+    }
+}
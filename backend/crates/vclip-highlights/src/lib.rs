@@ -0,0 +1,29 @@
+//! Highlight detection providers.
+//!
+//! This crate provides:
+//! - The [`HighlightProvider`] trait shared by the worker and API
+//! - A Gemini provider (Google Generative Language API)
+//! - An OpenAI-compatible provider for hosted or local servers (llama.cpp, vLLM)
+//! - A deterministic heuristic provider (transcript keywords + audio energy, no network)
+//! - Shared prompt builders and response parsing
+//! - Transcript hook-strength scoring for scene quality metrics
+//! - Provider selection from environment config, with per-user overrides
+//!
+//! Every provider returns the same [`HighlightsResponse`].
+
+pub mod error;
+pub mod gemini;
+pub mod heuristic;
+pub mod openai;
+pub mod prompt;
+pub mod provider;
+pub mod registry;
+pub mod types;
+
+pub use error::{HighlightError, HighlightResult};
+pub use gemini::GeminiProvider;
+pub use heuristic::{hook_strength, HeuristicProvider};
+pub use openai::OpenAiCompatibleProvider;
+pub use provider::{HighlightProvider, HighlightProviderKind, HighlightRequest};
+pub use registry::{HighlightProviders, ProviderConfig};
+pub use types::{EnergySample, HighlightCandidate, HighlightsResponse};
@@ -0,0 +1,165 @@
+//! OpenAI-compatible chat completions highlight provider.
+//!
+//! Works with hosted OpenAI-style APIs and with local servers that expose the
+//! same endpoint (llama.cpp `server`, vLLM, Ollama), so highlight detection can
+//! run without sending transcripts to a third party.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::error::{HighlightError, HighlightResult};
+use crate::prompt::parse_highlights_json;
+use crate::provider::{HighlightProvider, HighlightProviderKind, HighlightRequest};
+use crate::types::HighlightsResponse;
+
+/// System message sent ahead of the analysis prompt.
+const SYSTEM_PROMPT: &str =
+    "You find viral short-form clips in video transcripts. Reply with a single JSON object only.";
+
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    temperature: f32,
+    response_format: ResponseFormat,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct ResponseFormat {
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChoiceMessage {
+    content: Option<String>,
+}
+
+/// Highlight provider for any OpenAI-compatible `/chat/completions` endpoint.
+pub struct OpenAiCompatibleProvider {
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    client: Client,
+}
+
+impl OpenAiCompatibleProvider {
+    /// Create a provider for `base_url` (e.g. `http://localhost:8080/v1`).
+    pub fn new(base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key: None,
+            model: model.into(),
+            client: Client::builder()
+                .timeout(Duration::from_secs(600))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Create from environment variables.
+    ///
+    /// Reads `HIGHLIGHT_LLM_BASE_URL`, `HIGHLIGHT_LLM_MODEL` and optionally
+    /// `HIGHLIGHT_LLM_API_KEY` (local servers usually need none).
+    pub fn from_env() -> HighlightResult<Self> {
+        let base_url = std::env::var("HIGHLIGHT_LLM_BASE_URL").map_err(|_| {
+            HighlightError::NotConfigured("HIGHLIGHT_LLM_BASE_URL not set".to_string())
+        })?;
+        let model = std::env::var("HIGHLIGHT_LLM_MODEL").map_err(|_| {
+            HighlightError::NotConfigured("HIGHLIGHT_LLM_MODEL not set".to_string())
+        })?;
+
+        let provider = Self::new(base_url, model);
+        match std::env::var("HIGHLIGHT_LLM_API_KEY") {
+            Ok(key) if !key.is_empty() => Ok(provider.with_api_key(key)),
+            _ => Ok(provider),
+        }
+    }
+
+    /// Set the bearer token.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+}
+
+#[async_trait]
+impl HighlightProvider for OpenAiCompatibleProvider {
+    fn kind(&self) -> HighlightProviderKind {
+        HighlightProviderKind::OpenAi
+    }
+
+    async fn detect_highlights(
+        &self,
+        request: &HighlightRequest,
+    ) -> HighlightResult<HighlightsResponse> {
+        let url = format!("{}/chat/completions", self.base_url);
+        let body = ChatRequest {
+            model: &self.model,
+            messages: vec![
+                ChatMessage {
+                    role: "system",
+                    content: SYSTEM_PROMPT,
+                },
+                ChatMessage {
+                    role: "user",
+                    content: &request.prompt,
+                },
+            ],
+            temperature: 0.2,
+            response_format: ResponseFormat {
+                kind: "json_object",
+            },
+        };
+
+        info!(model = %self.model, url = %url, "Requesting highlights from OpenAI-compatible endpoint");
+
+        let mut builder = self.client.post(&url).json(&body);
+        if let Some(ref key) = self.api_key {
+            builder = builder.bearer_auth(key);
+        }
+        let response = builder.send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            return Err(HighlightError::Api { status, body });
+        }
+
+        let chat: ChatResponse = response.json().await?;
+        let text = chat
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|c| c.message.content)
+            .ok_or_else(|| {
+                HighlightError::InvalidResponse("No content in completion".to_string())
+            })?;
+
+        let mut data = parse_highlights_json(&text)?;
+        if data.video_url.is_none() {
+            data.video_url = Some(request.video_url.clone());
+        }
+        Ok(data)
+    }
+}
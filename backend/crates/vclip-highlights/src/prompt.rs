@@ -0,0 +1,176 @@
+//! Prompt builders and LLM response parsing shared by all LLM providers.
+
+use crate::error::{HighlightError, HighlightResult};
+use crate::types::HighlightsResponse;
+
+/// JSON schema every LLM provider is asked to return.
+const OUTPUT_SCHEMA: &str = r#"{
+  "video_url": "URL",
+  "video_title": "Actual title of the video",
+  "highlights": [
+    {
+      "id": 1,
+      "title": "Viral Title",
+      "start": "HH:MM:SS",
+      "end": "HH:MM:SS",
+      "duration": 0,
+      "pad_before_seconds": 1.0,
+      "pad_after_seconds": 1.0,
+      "hook_category": "Category",
+      "reason": "Why this is viral",
+      "description": "Engaging social media caption with hashtags",
+      "confidence": 0.8
+    }
+  ]
+}"#;
+
+/// Build the full analysis prompt for a new video.
+pub fn build_analysis_prompt(base_prompt: &str, transcript: &str) -> String {
+    format!(
+        r#"{base_prompt}
+
+IMPORTANT: You must strictly follow this output format.
+Return ONLY a single JSON object with this schema:
+{OUTPUT_SCHEMA}
+
+Here is the TRANSCRIPT of the video with timestamps.
+Use these exact timestamps for the 'start' and 'end' fields.
+
+TRANSCRIPT:
+{transcript}
+
+Additional instructions:
+- Return ONLY a single JSON object and nothing else.
+- Ensure all timestamps are in "HH:MM:SS" or "HH:MM:SS.mmm" format.
+- You MUST verify the quotes exist in the transcript provided above.
+- Extract 3 to 10 viral segments that are 20-90 seconds long.
+- Calculate duration in seconds for each highlight.
+- Set pad_before_seconds to 1.0 and pad_after_seconds to 1.0 for natural clip boundaries.
+- Set confidence (0.0-1.0) to how likely the segment is to perform well as a standalone clip.
+"#
+    )
+}
+
+/// Build the prompt for generating more scenes for an analyzed video.
+///
+/// The transcript is included when available so the model does not have to
+/// rely on the existing scenes alone.
+pub fn build_generate_more_prompt(
+    admin_prompt: &str,
+    user_prompt: &str,
+    existing_scenes: &str,
+    transcript: Option<&str>,
+    count: u32,
+) -> String {
+    let mut prompt = admin_prompt.to_string();
+
+    if !user_prompt.is_empty() {
+        prompt.push_str("\n\nADDITIONAL USER INSTRUCTIONS:\n");
+        prompt.push_str(user_prompt);
+    }
+
+    if !existing_scenes.is_empty() {
+        prompt.push_str("\n\n");
+        prompt.push_str(existing_scenes);
+    }
+
+    if let Some(transcript) = transcript.filter(|t| !t.is_empty()) {
+        prompt.push_str("\n\nTRANSCRIPT:\n");
+        prompt.push_str(transcript);
+    }
+
+    prompt.push_str(&format!(
+        r#"
+
+IMPORTANT: Generate exactly {count} NEW viral moments that are DIFFERENT from the existing scenes listed above.
+- Do NOT repeat or overlap with existing scenes
+- Find fresh, unique viral moments from other parts of the video
+- Scenes typically range from 30 to 90 seconds. Avoid clips shorter than 20 seconds unless they are exceptionally punchy.
+- Focus on variety - try different categories and themes
+
+IMPORTANT: You must strictly follow this output format.
+Return ONLY a single JSON object with this schema:
+{OUTPUT_SCHEMA}
+
+Return ONLY a JSON object with the 'highlights' array containing the new scenes."#
+    ));
+
+    prompt
+}
+
+/// Build context string for existing scenes.
+pub fn build_existing_scenes_context(highlights: &[vclip_models::Highlight]) -> String {
+    if highlights.is_empty() {
+        return String::new();
+    }
+
+    let mut context = String::from("EXISTING SCENES (do NOT overlap with these):\n");
+    for h in highlights {
+        context.push_str(&format!(
+            "- Scene {}: \"{}\" ({} - {})",
+            h.id, h.title, h.start, h.end
+        ));
+        if let Some(ref reason) = h.reason {
+            context.push_str(&format!(" - {}", reason));
+        }
+        context.push('\n');
+    }
+    context
+}
+
+/// Fallback base prompt when no admin prompt is configured.
+pub fn fallback_base_prompt() -> String {
+    r#"You are a viral video expert. Your task is to identify the most engaging, viral-worthy moments from video transcripts.
+
+For each viral moment, provide:
+- A catchy, attention-grabbing title (suitable for TikTok/YouTube Shorts)
+- Precise timestamps (start and end)
+- A category (emotional, educational, controversial, inspirational, humorous, dramatic, surprising)
+- A compelling reason why this moment would go viral
+- A social media caption with relevant hashtags
+
+Focus on moments with:
+- Strong emotional reactions
+- Surprising revelations or plot twists
+- Controversial or debate-worthy statements
+- Inspirational quotes or advice
+- Genuine humor or comedic timing
+- Dramatic tension or conflict resolution"#
+        .to_string()
+}
+
+/// Parse an LLM text response into highlights, tolerating markdown code fences.
+pub fn parse_highlights_json(text: &str) -> HighlightResult<HighlightsResponse> {
+    let text = text.trim();
+    let text = text
+        .strip_prefix("```json")
+        .or_else(|| text.strip_prefix("```"))
+        .unwrap_or(text);
+    let text = text.strip_suffix("```").unwrap_or(text);
+
+    serde_json::from_str(text.trim()).map_err(|e| {
+        HighlightError::InvalidResponse(format!("Failed to parse highlights JSON: {}", e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_highlights_json_strips_fences() {
+        let text = "```json\n{\"video_url\":null,\"video_title\":\"T\",\"highlights\":[{\"id\":1,\"title\":\"A\",\"start\":\"00:00:10\",\"end\":\"00:00:40\",\"duration\":30,\"hook_category\":null,\"reason\":null,\"description\":null}]}\n```";
+        let parsed = parse_highlights_json(text).unwrap();
+        assert_eq!(parsed.highlights.len(), 1);
+        assert_eq!(parsed.highlights[0].pad_before_seconds, 1.0);
+        assert!(parse_highlights_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_generate_more_prompt_includes_transcript() {
+        let prompt = build_generate_more_prompt("base", "", "", Some("[00:00:01] hi"), 3);
+        assert!(prompt.contains("TRANSCRIPT:\n[00:00:01] hi"));
+        assert!(prompt.contains("Generate exactly 3 NEW"));
+        assert!(!build_generate_more_prompt("base", "", "", None, 3).contains("TRANSCRIPT"));
+    }
+}
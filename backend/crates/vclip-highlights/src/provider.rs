@@ -0,0 +1,158 @@
+//! Highlight provider trait and request type.
+
+use std::fmt;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{HighlightError, HighlightResult};
+use crate::types::{EnergySample, HighlightsResponse};
+
+/// Highlights returned by providers that don't pick the count themselves.
+pub const DEFAULT_MAX_HIGHLIGHTS: usize = 6;
+
+/// Available highlight providers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HighlightProviderKind {
+    /// Google Gemini
+    Gemini,
+    /// Any OpenAI-compatible chat completions endpoint (e.g. llama.cpp, vLLM)
+    #[serde(rename = "openai")]
+    OpenAi,
+    /// Offline transcript keyword and audio energy scoring
+    Heuristic,
+}
+
+impl HighlightProviderKind {
+    /// All available providers.
+    pub const ALL: &'static [HighlightProviderKind] = &[
+        HighlightProviderKind::Gemini,
+        HighlightProviderKind::OpenAi,
+        HighlightProviderKind::Heuristic,
+    ];
+
+    /// Returns the provider name as used in config and user settings.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HighlightProviderKind::Gemini => "gemini",
+            HighlightProviderKind::OpenAi => "openai",
+            HighlightProviderKind::Heuristic => "heuristic",
+        }
+    }
+}
+
+impl fmt::Display for HighlightProviderKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for HighlightProviderKind {
+    type Err = HighlightError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "gemini" => Ok(HighlightProviderKind::Gemini),
+            "openai" | "openai_compatible" | "local" => Ok(HighlightProviderKind::OpenAi),
+            "heuristic" | "offline" => Ok(HighlightProviderKind::Heuristic),
+            _ => Err(HighlightError::NotConfigured(format!(
+                "Unknown highlight provider: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Input for a highlight detection call.
+#[derive(Debug, Clone)]
+pub struct HighlightRequest {
+    /// Complete instructions for LLM providers
+    pub prompt: String,
+    /// Transcript as `[HH:MM:SS] text` lines
+    pub transcript: String,
+    /// Source video URL
+    pub video_url: String,
+    /// Upper bound on returned highlights (heuristic provider)
+    pub max_highlights: usize,
+    /// Time ranges (seconds) that must not be returned again
+    pub exclude: Vec<(f64, f64)>,
+    /// Loudness timeline of the source audio, when available
+    pub audio_energy: Vec<EnergySample>,
+}
+
+impl HighlightRequest {
+    /// Create a request.
+    pub fn new(
+        prompt: impl Into<String>,
+        transcript: impl Into<String>,
+        video_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            prompt: prompt.into(),
+            transcript: transcript.into(),
+            video_url: video_url.into(),
+            max_highlights: DEFAULT_MAX_HIGHLIGHTS,
+            exclude: Vec::new(),
+            audio_energy: Vec::new(),
+        }
+    }
+
+    /// Set the maximum number of highlights (clamped to at least 1).
+    pub fn with_max_highlights(mut self, max: usize) -> Self {
+        self.max_highlights = max.max(1);
+        self
+    }
+
+    /// Set time ranges to exclude.
+    pub fn with_exclusions(mut self, ranges: Vec<(f64, f64)>) -> Self {
+        self.exclude = ranges;
+        self
+    }
+
+    /// Set the source audio loudness timeline.
+    pub fn with_audio_energy(mut self, samples: Vec<EnergySample>) -> Self {
+        self.audio_energy = samples;
+        self
+    }
+}
+
+/// A backend that finds highlight moments in a video.
+#[async_trait]
+pub trait HighlightProvider: Send + Sync {
+    /// Which provider this is.
+    fn kind(&self) -> HighlightProviderKind;
+
+    /// Detect highlights for the request.
+    async fn detect_highlights(
+        &self,
+        request: &HighlightRequest,
+    ) -> HighlightResult<HighlightsResponse>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind_parse_roundtrip() {
+        for kind in HighlightProviderKind::ALL {
+            assert_eq!(
+                kind.as_str().parse::<HighlightProviderKind>().unwrap(),
+                *kind
+            );
+        }
+        assert_eq!(
+            "Local".parse::<HighlightProviderKind>().unwrap(),
+            HighlightProviderKind::OpenAi
+        );
+        assert!("claude".parse::<HighlightProviderKind>().is_err());
+    }
+
+    #[test]
+    fn test_kind_serde() {
+        let json = serde_json::to_string(&HighlightProviderKind::OpenAi).unwrap();
+        assert_eq!(json, "\"openai\"");
+    }
+}
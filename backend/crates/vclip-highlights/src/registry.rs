@@ -0,0 +1,171 @@
+//! Provider selection.
+//!
+//! The deployment picks a default provider with `HIGHLIGHT_PROVIDER`; users
+//! may override it per account. Providers whose credentials are missing are
+//! simply unavailable, and requests for them fall back to the default. The
+//! heuristic provider needs no configuration and is always available.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tracing::{info, warn};
+
+use crate::error::{HighlightError, HighlightResult};
+use crate::gemini::GeminiProvider;
+use crate::heuristic::HeuristicProvider;
+use crate::openai::OpenAiCompatibleProvider;
+use crate::provider::{HighlightProvider, HighlightProviderKind};
+
+/// Provider configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderConfig {
+    /// Provider used when the user has no override
+    pub default_provider: HighlightProviderKind,
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            default_provider: HighlightProviderKind::Gemini,
+        }
+    }
+}
+
+impl ProviderConfig {
+    /// Create from environment variables (`HIGHLIGHT_PROVIDER`, default `gemini`).
+    pub fn from_env() -> HighlightResult<Self> {
+        match std::env::var("HIGHLIGHT_PROVIDER") {
+            Ok(name) if !name.trim().is_empty() => Ok(Self {
+                default_provider: name.parse()?,
+            }),
+            _ => Ok(Self::default()),
+        }
+    }
+}
+
+/// The set of configured highlight providers.
+#[derive(Clone)]
+pub struct HighlightProviders {
+    default_kind: HighlightProviderKind,
+    providers: HashMap<HighlightProviderKind, Arc<dyn HighlightProvider>>,
+}
+
+impl HighlightProviders {
+    /// Create a registry containing only the heuristic provider.
+    pub fn heuristic_only() -> Self {
+        let mut providers: HashMap<HighlightProviderKind, Arc<dyn HighlightProvider>> =
+            HashMap::new();
+        providers.insert(
+            HighlightProviderKind::Heuristic,
+            Arc::new(HeuristicProvider::new()),
+        );
+        Self {
+            default_kind: HighlightProviderKind::Heuristic,
+            providers,
+        }
+    }
+
+    /// Register a provider, replacing any provider of the same kind.
+    pub fn with_provider(mut self, provider: Arc<dyn HighlightProvider>) -> Self {
+        self.providers.insert(provider.kind(), provider);
+        self
+    }
+
+    /// Set the default provider; it must already be registered.
+    pub fn with_default(mut self, kind: HighlightProviderKind) -> HighlightResult<Self> {
+        if !self.providers.contains_key(&kind) {
+            return Err(HighlightError::NotConfigured(format!(
+                "Default highlight provider '{}' is not configured",
+                kind
+            )));
+        }
+        self.default_kind = kind;
+        Ok(self)
+    }
+
+    /// Build every provider that has credentials in the environment.
+    pub fn from_config(config: &ProviderConfig) -> HighlightResult<Self> {
+        let mut registry = Self::heuristic_only();
+
+        match GeminiProvider::from_env() {
+            Ok(p) => registry = registry.with_provider(Arc::new(p)),
+            Err(e) => info!("Gemini highlight provider unavailable: {}", e),
+        }
+        match OpenAiCompatibleProvider::from_env() {
+            Ok(p) => registry = registry.with_provider(Arc::new(p)),
+            Err(e) => info!("OpenAI-compatible highlight provider unavailable: {}", e),
+        }
+
+        let registry = registry.with_default(config.default_provider)?;
+        info!(
+            default = %registry.default_kind,
+            available = ?registry.available(),
+            "Configured highlight providers"
+        );
+        Ok(registry)
+    }
+
+    /// Build from environment variables.
+    pub fn from_env() -> HighlightResult<Self> {
+        Self::from_config(&ProviderConfig::from_env()?)
+    }
+
+    /// The default provider kind.
+    pub fn default_kind(&self) -> HighlightProviderKind {
+        self.default_kind
+    }
+
+    /// Configured provider kinds, in declaration order.
+    pub fn available(&self) -> Vec<HighlightProviderKind> {
+        HighlightProviderKind::ALL
+            .iter()
+            .copied()
+            .filter(|k| self.providers.contains_key(k))
+            .collect()
+    }
+
+    /// Get the requested provider, falling back to the default when it is not configured.
+    pub fn get(&self, preferred: Option<HighlightProviderKind>) -> Arc<dyn HighlightProvider> {
+        if let Some(kind) = preferred {
+            if let Some(provider) = self.providers.get(&kind) {
+                return provider.clone();
+            }
+            warn!(
+                requested = %kind,
+                default = %self.default_kind,
+                "Requested highlight provider not configured, using default"
+            );
+        }
+        self.providers[&self.default_kind].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_falls_back_to_default() {
+        let registry = HighlightProviders::heuristic_only();
+        assert_eq!(registry.available(), vec![HighlightProviderKind::Heuristic]);
+        let provider = registry.get(Some(HighlightProviderKind::Gemini));
+        assert_eq!(provider.kind(), HighlightProviderKind::Heuristic);
+        assert!(registry
+            .clone()
+            .with_default(HighlightProviderKind::OpenAi)
+            .is_err());
+
+        let registry = registry
+            .with_provider(Arc::new(OpenAiCompatibleProvider::new(
+                "http://localhost:8080/v1",
+                "m",
+            )))
+            .with_default(HighlightProviderKind::OpenAi)
+            .unwrap();
+        assert_eq!(registry.get(None).kind(), HighlightProviderKind::OpenAi);
+        assert_eq!(
+            registry.get(Some(HighlightProviderKind::Heuristic)).kind(),
+            HighlightProviderKind::Heuristic
+        );
+    }
+}
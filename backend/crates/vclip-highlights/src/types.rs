@@ -0,0 +1,46 @@
+//! Highlight provider response types.
+
+use serde::{Deserialize, Serialize};
+
+/// Highlights detected in a video transcript.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HighlightsResponse {
+    pub video_url: Option<String>,
+    pub video_title: Option<String>,
+    pub highlights: Vec<HighlightCandidate>,
+}
+
+/// A single detected highlight, before validation and storage.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HighlightCandidate {
+    pub id: u32,
+    pub title: String,
+    pub start: String,
+    pub end: String,
+    pub duration: u32,
+    /// Padding before the start timestamp (seconds)
+    #[serde(default = "default_pad")]
+    pub pad_before_seconds: f64,
+    /// Padding after the end timestamp (seconds)
+    #[serde(default = "default_pad")]
+    pub pad_after_seconds: f64,
+    pub hook_category: Option<String>,
+    pub reason: Option<String>,
+    pub description: Option<String>,
+    /// Detector confidence that this works as a standalone clip (0-1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+}
+
+fn default_pad() -> f64 {
+    1.0
+}
+
+/// Audio loudness at a point in the source video.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EnergySample {
+    /// Time in seconds from the start of the video
+    pub time: f64,
+    /// Momentary loudness (LUFS)
+    pub loudness: f64,
+}
@@ -0,0 +1,311 @@
+//! CTC forced alignment.
+//!
+//! Given per-frame log-probabilities from a character-level CTC acoustic
+//! model and the known transcript, finds the most likely monotonic path
+//! through the transcript tokens (Viterbi over the blank-interleaved label
+//! sequence) and converts it into per-word frame spans.
+
+use std::collections::HashMap;
+
+/// Character vocabulary of a CTC model.
+#[derive(Debug, Clone)]
+pub struct CtcVocabulary {
+    tokens: HashMap<char, usize>,
+    blank: usize,
+    size: usize,
+}
+
+impl CtcVocabulary {
+    /// Build a vocabulary from a `token -> id` map (HuggingFace `vocab.json` format).
+    ///
+    /// Multi-character tokens other than the blank are ignored. The blank is
+    /// `<pad>` when present, otherwise id 0.
+    pub fn from_token_map(map: &HashMap<String, usize>) -> Self {
+        let blank = map
+            .get("<pad>")
+            .or_else(|| map.get("<blank>"))
+            .copied()
+            .unwrap_or(0);
+        let size = map.values().copied().max().map(|m| m + 1).unwrap_or(0);
+        let tokens = map
+            .iter()
+            .filter_map(|(token, id)| {
+                let mut chars = token.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some((c, *id)),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        Self {
+            tokens,
+            blank,
+            size,
+        }
+    }
+
+    /// Blank token id.
+    pub fn blank(&self) -> usize {
+        self.blank
+    }
+
+    /// Number of output classes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Map a character to a token id, trying upper and lower case variants.
+    fn lookup(&self, c: char) -> Option<usize> {
+        self.tokens
+            .get(&c)
+            .or_else(|| c.to_uppercase().next().and_then(|u| self.tokens.get(&u)))
+            .or_else(|| c.to_lowercase().next().and_then(|l| self.tokens.get(&l)))
+            .copied()
+    }
+
+    /// Tokenize words, returning the token sequence and, for each token, the
+    /// index of the word it belongs to. Characters missing from the
+    /// vocabulary (punctuation, emoji) are skipped.
+    pub fn tokenize(&self, words: &[&str]) -> (Vec<usize>, Vec<usize>) {
+        let mut tokens = Vec::new();
+        let mut owners = Vec::new();
+        for (wi, word) in words.iter().enumerate() {
+            for c in word.chars() {
+                if let Some(id) = self.lookup(c) {
+                    if id != self.blank {
+                        tokens.push(id);
+                        owners.push(wi);
+                    }
+                }
+            }
+        }
+        (tokens, owners)
+    }
+}
+
+/// Frame span of a single aligned word.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WordSpan {
+    /// Index into the input word list
+    pub word_index: usize,
+    /// First frame (inclusive)
+    pub start_frame: usize,
+    /// Last frame (exclusive)
+    pub end_frame: usize,
+    /// Mean token probability over the span
+    pub score: f32,
+}
+
+/// Apply log-softmax in place to a `[frames, classes]` row-major matrix.
+pub fn log_softmax_rows(logits: &mut [f32], classes: usize) {
+    if classes == 0 {
+        return;
+    }
+    for row in logits.chunks_mut(classes) {
+        let max = row.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let sum: f32 = row.iter().map(|v| (v - max).exp()).sum();
+        let log_sum = max + sum.ln();
+        for v in row.iter_mut() {
+            *v -= log_sum;
+        }
+    }
+}
+
+/// Viterbi forced alignment of `tokens` against `log_probs` (`[frames, classes]`).
+///
+/// Returns, for each token, the `(first_frame, last_frame_exclusive)` range
+/// it was emitted in, or `None` if the transcript cannot fit in the audio
+/// (more tokens than frames allow).
+pub fn forced_align(
+    log_probs: &[f32],
+    classes: usize,
+    tokens: &[usize],
+    blank: usize,
+) -> Option<Vec<(usize, usize)>> {
+    if classes == 0 || tokens.is_empty() {
+        return None;
+    }
+    let frames = log_probs.len() / classes;
+    let states = 2 * tokens.len() + 1;
+    if frames == 0 {
+        return None;
+    }
+
+    // Label of each extended state: even = blank, odd = token
+    let label = |s: usize| if s % 2 == 0 { blank } else { tokens[s / 2] };
+    let emit = |t: usize, s: usize| log_probs[t * classes + label(s)];
+
+    let mut prev = vec![f32::NEG_INFINITY; states];
+    let mut curr = vec![f32::NEG_INFINITY; states];
+    // Backpointer: how many states we advanced (0, 1 or 2) to reach (t, s)
+    let mut back = vec![0u8; frames * states];
+
+    prev[0] = emit(0, 0);
+    if states > 1 {
+        prev[1] = emit(0, 1);
+    }
+
+    for t in 1..frames {
+        for s in 0..states {
+            let mut best = prev[s];
+            let mut step = 0u8;
+            if s >= 1 && prev[s - 1] > best {
+                best = prev[s - 1];
+                step = 1;
+            }
+            if s >= 2 && s % 2 == 1 && label(s) != label(s - 2) && prev[s - 2] > best {
+                best = prev[s - 2];
+                step = 2;
+            }
+            curr[s] = if best == f32::NEG_INFINITY {
+                f32::NEG_INFINITY
+            } else {
+                best + emit(t, s)
+            };
+            back[t * states + s] = step;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    // Path may end on the final token or the trailing blank
+    let mut s = if states >= 2 && prev[states - 2] > prev[states - 1] {
+        states - 2
+    } else {
+        states - 1
+    };
+    if prev[s] == f32::NEG_INFINITY {
+        return None;
+    }
+
+    let mut path = vec![0usize; frames];
+    for t in (0..frames).rev() {
+        path[t] = s;
+        if t > 0 {
+            s -= back[t * states + s] as usize;
+        }
+    }
+
+    let mut spans = vec![(usize::MAX, 0usize); tokens.len()];
+    for (t, &state) in path.iter().enumerate() {
+        if state % 2 == 1 {
+            let span = &mut spans[state / 2];
+            span.0 = span.0.min(t);
+            span.1 = span.1.max(t + 1);
+        }
+    }
+
+    if spans.iter().any(|(start, _)| *start == usize::MAX) {
+        return None;
+    }
+    Some(spans)
+}
+
+/// Group token spans into word spans.
+///
+/// `owners[i]` is the word index of token `i`. Words without any tokens are
+/// omitted; callers interpolate them.
+pub fn group_word_spans(
+    token_spans: &[(usize, usize)],
+    owners: &[usize],
+    log_probs: &[f32],
+    classes: usize,
+    tokens: &[usize],
+) -> Vec<WordSpan> {
+    let mut words: Vec<WordSpan> = Vec::new();
+    let mut score_sums: Vec<(f32, usize)> = Vec::new();
+
+    for (i, &(start, end)) in token_spans.iter().enumerate() {
+        let frame_score: f32 = (start..end)
+            .map(|t| log_probs[t * classes + tokens[i]].exp())
+            .sum();
+        let frame_count = end - start;
+
+        match words.last_mut() {
+            Some(word) if word.word_index == owners[i] => {
+                word.end_frame = end;
+                let acc = score_sums.last_mut().expect("score accumulator per word");
+                acc.0 += frame_score;
+                acc.1 += frame_count;
+            }
+            _ => {
+                words.push(WordSpan {
+                    word_index: owners[i],
+                    start_frame: start,
+                    end_frame: end,
+                    score: 0.0,
+                });
+                score_sums.push((frame_score, frame_count));
+            }
+        }
+    }
+
+    for (word, (sum, count)) in words.iter_mut().zip(score_sums) {
+        word.score = if count > 0 { sum / count as f32 } else { 0.0 };
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocab() -> CtcVocabulary {
+        let map: HashMap<String, usize> = [("<pad>", 0), ("|", 1), ("A", 2), ("B", 3), ("C", 4)]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+        CtcVocabulary::from_token_map(&map)
+    }
+
+    /// Build log-probs where each frame strongly prefers one class.
+    fn peaked(frames: &[usize], classes: usize) -> Vec<f32> {
+        let mut out = Vec::new();
+        for &c in frames {
+            for k in 0..classes {
+                out.push(if k == c { 5.0 } else { 0.0 });
+            }
+        }
+        log_softmax_rows(&mut out, classes);
+        out
+    }
+
+    #[test]
+    fn test_tokenize_case_insensitive_and_skips_unknown() {
+        let (tokens, owners) = vocab().tokenize(&["ab,", "c!"]);
+        assert_eq!(tokens, vec![2, 3, 4]);
+        assert_eq!(owners, vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn test_forced_align_simple_path() {
+        // frames: blank A A blank B blank C C
+        let lp = peaked(&[0, 2, 2, 0, 3, 0, 4, 4], 5);
+        let spans = forced_align(&lp, 5, &[2, 3, 4], 0).unwrap();
+        assert_eq!(spans, vec![(1, 3), (4, 5), (6, 8)]);
+    }
+
+    #[test]
+    fn test_forced_align_repeated_tokens_need_blank() {
+        // "AA" requires a blank between the two A tokens
+        let lp = peaked(&[2, 0, 2], 5);
+        let spans = forced_align(&lp, 5, &[2, 2], 0).unwrap();
+        assert_eq!(spans, vec![(0, 1), (2, 3)]);
+
+        // Not enough frames to separate repeated tokens
+        let short = peaked(&[2, 2], 5);
+        assert!(forced_align(&short, 5, &[2, 2], 0).is_none());
+    }
+
+    #[test]
+    fn test_group_word_spans() {
+        let lp = peaked(&[0, 2, 3, 0, 0, 4, 0], 5);
+        let (tokens, owners) = vocab().tokenize(&["ab", "c"]);
+        let spans = forced_align(&lp, 5, &tokens, 0).unwrap();
+        let words = group_word_spans(&spans, &owners, &lp, 5, &tokens);
+        assert_eq!(words.len(), 2);
+        assert_eq!((words[0].start_frame, words[0].end_frame), (1, 3));
+        assert_eq!((words[1].start_frame, words[1].end_frame), (5, 6));
+        assert!(words[0].score > 0.9);
+    }
+}
@@ -0,0 +1,338 @@
+//! Offline forced alignment of transcript text to audio.
+//!
+//! Transcripts arrive with coarse, segment-level timestamps. This module
+//! takes a clip's audio plus the text spoken in it and produces word-level
+//! start/end times without any network calls.
+//!
+//! # Architecture
+//!
+//! ```text
+//! ┌──────────────┐    ┌──────────────┐    ┌──────────────┐
+//! │ Audio Input  │───►│ CTC model    │───►│ Viterbi      │───► AlignedWord[]
+//! │ (16kHz mono) │    │ (ONNX, ort)  │    │ (ctc.rs)     │
+//! └──────────────┘    └──────────────┘    └──────────────┘
+//!         │                  │ model missing / alignment fails
+//!         ▼                  ▼
+//!  ┌──────────────┐    ┌──────────────┐
+//!  │ Silero VAD   │───►│ Proportional │───► AlignedWord[]
+//!  │ (speech only)│    │ distribution │
+//!  └──────────────┘    └──────────────┘
+//! ```
+//!
+//! The CTC path uses a wav2vec2-style character model on the same ONNX
+//! Runtime as the rest of the crate. When no model is installed, words are
+//! spread across VAD-detected speech regions, which is still far better than
+//! segment-level timestamps because it never places words in silence.
+
+mod ctc;
+mod model;
+
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+use tracing::{debug, info, warn};
+use vclip_models::{AlignedWord, AlignmentMethod};
+
+use crate::error::{MediaError, MediaResult};
+use crate::silence_removal::{extract_audio_samples, SileroVad, VAD_SAMPLE_RATE};
+
+pub use ctc::{forced_align, group_word_spans, log_softmax_rows, CtcVocabulary, WordSpan};
+pub use model::{CtcAlignmentModel, CtcEmissions, ALIGNMENT_MODEL_ENV};
+
+/// Configuration for forced alignment.
+#[derive(Debug, Clone)]
+pub struct AlignmentConfig {
+    /// Use the CTC model when available (falls back to VAD otherwise)
+    pub use_ctc_model: bool,
+    /// Speech probability threshold for the VAD fallback
+    pub vad_threshold: f32,
+    /// Gaps shorter than this are merged into one speech region (ms)
+    pub merge_gap_ms: u64,
+}
+
+impl Default for AlignmentConfig {
+    fn default() -> Self {
+        Self {
+            use_ctc_model: true,
+            vad_threshold: 0.5,
+            merge_gap_ms: 200,
+        }
+    }
+}
+
+impl AlignmentConfig {
+    /// Disable the CTC model and always use the VAD fallback.
+    pub fn vad_only(mut self) -> Self {
+        self.use_ctc_model = false;
+        self
+    }
+}
+
+/// Result of aligning a transcript window.
+#[derive(Debug, Clone)]
+pub struct AlignmentResult {
+    /// Method that produced the timings
+    pub method: AlignmentMethod,
+    /// Aligned words, in transcript order, relative to the input start
+    pub words: Vec<AlignedWord>,
+}
+
+/// Shared CTC model, loaded once per process.
+fn shared_model() -> Option<Arc<CtcAlignmentModel>> {
+    static MODEL: OnceLock<Option<Arc<CtcAlignmentModel>>> = OnceLock::new();
+    MODEL
+        .get_or_init(|| match CtcAlignmentModel::load_default() {
+            Ok(model) => {
+                info!("Loaded CTC alignment model");
+                Some(Arc::new(model))
+            }
+            Err(e) => {
+                info!(error = %e, "CTC alignment model unavailable, using VAD alignment");
+                None
+            }
+        })
+        .clone()
+}
+
+/// Align transcript text to the audio of a video or audio file.
+///
+/// # Arguments
+/// * `input_path` - Clip or segment to align against
+/// * `text` - Words spoken in the clip, in order
+/// * `config` - Alignment configuration
+pub async fn align_transcript(
+    input_path: &Path,
+    text: &str,
+    config: &AlignmentConfig,
+) -> MediaResult<AlignmentResult> {
+    let words: Vec<String> = text.split_whitespace().map(str::to_string).collect();
+    if words.is_empty() {
+        return Ok(AlignmentResult {
+            method: AlignmentMethod::VadProportional,
+            words: Vec::new(),
+        });
+    }
+
+    let samples = extract_audio_samples(input_path)
+        .await
+        .map_err(|e| MediaError::internal(format!("Audio extraction for alignment failed: {e}")))?;
+
+    let config = config.clone();
+    tokio::task::spawn_blocking(move || align_samples(&samples, &words, &config))
+        .await
+        .map_err(|e| MediaError::internal(format!("Alignment task failed: {e}")))?
+}
+
+/// Align words against 16kHz mono samples (blocking).
+pub fn align_samples(
+    samples: &[f32],
+    words: &[String],
+    config: &AlignmentConfig,
+) -> MediaResult<AlignmentResult> {
+    if config.use_ctc_model {
+        if let Some(model) = shared_model() {
+            match align_with_ctc(&model, samples, words) {
+                Ok(aligned) => {
+                    return Ok(AlignmentResult {
+                        method: AlignmentMethod::Ctc,
+                        words: aligned,
+                    })
+                }
+                Err(e) => warn!(error = %e, "CTC alignment failed, falling back to VAD"),
+            }
+        }
+    }
+
+    let mut regions = detect_speech_regions(samples, config.vad_threshold, config.merge_gap_ms)?;
+    if regions.is_empty() {
+        // No speech detected: treat the whole input as one region
+        let total = samples.len() as f64 / VAD_SAMPLE_RATE as f64;
+        regions.push((0.0, total));
+    }
+    debug!(
+        regions = regions.len(),
+        "Aligning words over VAD speech regions"
+    );
+    Ok(AlignmentResult {
+        method: AlignmentMethod::VadProportional,
+        words: distribute_over_regions(words, &regions),
+    })
+}
+
+/// Run CTC forced alignment for the given words.
+fn align_with_ctc(
+    model: &CtcAlignmentModel,
+    samples: &[f32],
+    words: &[String],
+) -> MediaResult<Vec<AlignedWord>> {
+    let emissions = model.emissions(samples, VAD_SAMPLE_RATE)?;
+    let word_refs: Vec<&str> = words.iter().map(String::as_str).collect();
+    let (tokens, owners) = model.vocab().tokenize(&word_refs);
+
+    let token_spans = forced_align(
+        &emissions.log_probs,
+        emissions.classes,
+        &tokens,
+        model.vocab().blank(),
+    )
+    .ok_or_else(|| MediaError::internal("Transcript does not fit the audio"))?;
+
+    let spans = group_word_spans(
+        &token_spans,
+        &owners,
+        &emissions.log_probs,
+        emissions.classes,
+        &tokens,
+    );
+
+    let spf = emissions.seconds_per_frame;
+    let mut aligned: Vec<Option<AlignedWord>> = vec![None; words.len()];
+    for span in spans {
+        aligned[span.word_index] = Some(
+            AlignedWord::new(
+                words[span.word_index].clone(),
+                span.start_frame as f64 * spf,
+                span.end_frame as f64 * spf,
+            )
+            .with_confidence(span.score),
+        );
+    }
+
+    Ok(fill_unaligned(words, aligned))
+}
+
+/// Give words without vocabulary tokens (e.g. "—", emoji) a zero-length slot
+/// at the end of the previous word so ordering is preserved.
+fn fill_unaligned(words: &[String], aligned: Vec<Option<AlignedWord>>) -> Vec<AlignedWord> {
+    let mut out = Vec::with_capacity(words.len());
+    let mut last_end = 0.0;
+    for (word, slot) in words.iter().zip(aligned) {
+        match slot {
+            Some(w) => {
+                last_end = w.end;
+                out.push(w);
+            }
+            None => out.push(AlignedWord::new(word.clone(), last_end, last_end)),
+        }
+    }
+    out
+}
+
+/// Detect speech regions `(start_s, end_s)` with Silero VAD.
+///
+/// Regions separated by gaps up to `merge_gap_ms` are merged.
+pub(crate) fn detect_speech_regions(
+    samples: &[f32],
+    vad_threshold: f32,
+    merge_gap_ms: u64,
+) -> MediaResult<Vec<(f64, f64)>> {
+    let mut vad = SileroVad::new(VAD_SAMPLE_RATE)
+        .map_err(|e| MediaError::internal(format!("VAD init failed: {e}")))?;
+    let frame_size = vad.frame_size();
+    let frame_s = frame_size as f64 / VAD_SAMPLE_RATE as f64;
+    let merge_gap_s = merge_gap_ms as f64 / 1000.0;
+
+    let mut regions: Vec<(f64, f64)> = Vec::new();
+    for (i, chunk) in samples.chunks(frame_size).enumerate() {
+        if chunk.len() < frame_size {
+            break;
+        }
+        let prob = vad
+            .analyze_frame(chunk)
+            .map_err(|e| MediaError::internal(format!("VAD inference failed: {e}")))?;
+        if prob < vad_threshold {
+            continue;
+        }
+
+        let start = i as f64 * frame_s;
+        let end = start + frame_s;
+        match regions.last_mut() {
+            Some(last) if start - last.1 <= merge_gap_s => last.1 = end,
+            _ => regions.push((start, end)),
+        }
+    }
+
+    Ok(regions)
+}
+
+/// Spread words across speech regions proportionally to their length.
+///
+/// Words are laid out on a "speech-only" timeline (regions concatenated) and
+/// mapped back, so no word starts inside a silence.
+pub fn distribute_over_regions(words: &[String], regions: &[(f64, f64)]) -> Vec<AlignedWord> {
+    let total_speech: f64 = regions.iter().map(|(s, e)| e - s).sum();
+    let weights: Vec<f64> = words
+        .iter()
+        .map(|w| w.chars().count() as f64 + 1.0)
+        .collect();
+    let total_weight: f64 = weights.iter().sum();
+    if words.is_empty() || total_speech <= 0.0 || total_weight <= 0.0 {
+        return Vec::new();
+    }
+
+    let to_real = |speech_t: f64| -> f64 {
+        let mut acc = 0.0;
+        for &(s, e) in regions {
+            let len = e - s;
+            if speech_t <= acc + len {
+                return s + (speech_t - acc);
+            }
+            acc += len;
+        }
+        regions.last().map(|r| r.1).unwrap_or(0.0)
+    };
+
+    let mut cursor = 0.0;
+    words
+        .iter()
+        .zip(weights)
+        .map(|(word, weight)| {
+            let start = cursor;
+            cursor += total_speech * weight / total_weight;
+            // Nudge the start forward so a word on a region boundary lands in the next region
+            let real_start = to_real(start + 1e-6).max(to_real(start));
+            let real_end = to_real(cursor).max(real_start);
+            AlignedWord::new(word.clone(), real_start, real_end)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_distribute_over_regions_skips_silence() {
+        let aligned = distribute_over_regions(&words(&["aaa", "aaa"]), &[(0.0, 1.0), (3.0, 4.0)]);
+        assert_eq!(aligned.len(), 2);
+        assert!(aligned[0].start.abs() < 1e-3);
+        assert!((aligned[0].end - 1.0).abs() < 1e-3);
+        assert!((aligned[1].start - 3.0).abs() < 1e-3);
+        assert!((aligned[1].end - 4.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_distribute_empty_inputs() {
+        assert!(distribute_over_regions(&[], &[(0.0, 1.0)]).is_empty());
+        assert!(distribute_over_regions(&words(&["a"]), &[]).is_empty());
+    }
+
+    #[test]
+    fn test_fill_unaligned_preserves_order() {
+        let input = words(&["hello", "—", "world"]);
+        let aligned = vec![
+            Some(AlignedWord::new("hello", 0.0, 0.5)),
+            None,
+            Some(AlignedWord::new("world", 0.7, 1.0)),
+        ];
+        let out = fill_unaligned(&input, aligned);
+        assert_eq!(out.len(), 3);
+        assert_eq!(out[1].text, "—");
+        assert_eq!(out[1].start, 0.5);
+        assert_eq!(out[1].end, 0.5);
+    }
+}
@@ -0,0 +1,177 @@
+//! ONNX Runtime wrapper for a character-level CTC acoustic model.
+//!
+//! Expects a wav2vec2-style export: input `[1, samples]` of 16kHz mono f32
+//! audio, output `[1, frames, classes]` logits. The vocabulary is read from a
+//! HuggingFace `vocab.json` next to the model.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::{Tensor, Value};
+
+use super::ctc::{log_softmax_rows, CtcVocabulary};
+use crate::error::{MediaError, MediaResult};
+
+/// Environment variable overriding the alignment model path.
+pub const ALIGNMENT_MODEL_ENV: &str = "ALIGNMENT_MODEL_PATH";
+
+/// Default model search paths.
+const MODEL_CANDIDATES: &[&str] = &[
+    "./backend/models/alignment/wav2vec2_ctc.onnx",
+    "/app/backend/models/alignment/wav2vec2_ctc.onnx",
+    "/app/models/alignment/wav2vec2_ctc.onnx",
+];
+
+/// Per-frame log-probabilities produced by the model.
+pub struct CtcEmissions {
+    /// Row-major `[frames, classes]` log-probabilities
+    pub log_probs: Vec<f32>,
+    /// Number of output classes
+    pub classes: usize,
+    /// Seconds of audio per output frame
+    pub seconds_per_frame: f64,
+}
+
+impl CtcEmissions {
+    /// Number of output frames.
+    pub fn frames(&self) -> usize {
+        if self.classes == 0 {
+            0
+        } else {
+            self.log_probs.len() / self.classes
+        }
+    }
+}
+
+/// CTC acoustic model session plus its vocabulary.
+pub struct CtcAlignmentModel {
+    session: Mutex<Session>,
+    vocab: CtcVocabulary,
+}
+
+impl CtcAlignmentModel {
+    /// Load the model from the default search paths (or `ALIGNMENT_MODEL_PATH`).
+    pub fn load_default() -> MediaResult<Self> {
+        let model_path = find_default_model_path().ok_or_else(|| {
+            MediaError::ModelNotFound(
+                "wav2vec2_ctc.onnx not found; place it under backend/models/alignment/".to_string(),
+            )
+        })?;
+        Self::load(&model_path)
+    }
+
+    /// Load the model and the `vocab.json` stored next to it.
+    pub fn load(model_path: &Path) -> MediaResult<Self> {
+        if !model_path.exists() {
+            return Err(MediaError::ModelNotFound(format!(
+                "Alignment model not found at {}",
+                model_path.display()
+            )));
+        }
+
+        let vocab_path = model_path.with_file_name("vocab.json");
+        let vocab_json = std::fs::read_to_string(&vocab_path).map_err(|e| {
+            MediaError::ModelNotFound(format!(
+                "Alignment vocabulary not found at {}: {e}",
+                vocab_path.display()
+            ))
+        })?;
+        let token_map: HashMap<String, usize> = serde_json::from_str(&vocab_json)?;
+        let vocab = CtcVocabulary::from_token_map(&token_map);
+
+        let model_bytes = std::fs::read(model_path)
+            .map_err(|e| MediaError::internal(format!("ORT read model file: {e}")))?;
+
+        let session = Session::builder()
+            .map_err(|e| MediaError::internal(format!("ORT session builder: {e}")))?
+            .with_optimization_level(GraphOptimizationLevel::Level3)
+            .map_err(|e| MediaError::internal(format!("ORT opt level: {e}")))?
+            .commit_from_memory(model_bytes.as_slice())
+            .map_err(|e| MediaError::internal(format!("ORT load model: {e}")))?;
+
+        Ok(Self {
+            session: Mutex::new(session),
+            vocab,
+        })
+    }
+
+    /// Model vocabulary.
+    pub fn vocab(&self) -> &CtcVocabulary {
+        &self.vocab
+    }
+
+    /// Run the model on 16kHz mono samples and return log-probabilities.
+    pub fn emissions(&self, samples: &[f32], sample_rate: usize) -> MediaResult<CtcEmissions> {
+        if samples.is_empty() {
+            return Err(MediaError::internal("No audio samples for alignment"));
+        }
+
+        // wav2vec2 expects zero-mean, unit-variance input
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        let var = samples.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / samples.len() as f32;
+        let std = (var + 1e-7).sqrt();
+        let normalized: Vec<f32> = samples.iter().map(|s| (s - mean) / std).collect();
+
+        let input: Value =
+            Tensor::from_array((vec![1usize, samples.len()], normalized.into_boxed_slice()))
+                .map(Value::from)
+                .map_err(|e| MediaError::internal(format!("Failed to create tensor: {e}")))?;
+
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|_| MediaError::internal("ORT session poisoned"))?;
+        let outputs = session
+            .run(ort::inputs![input])
+            .map_err(|e| MediaError::internal(format!("ORT run failed: {e}")))?;
+
+        let (shape, data) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| MediaError::internal(format!("ORT extract: {e}")))?;
+
+        // Accept [1, frames, classes] or [frames, classes]
+        let (frames, classes) = match shape.len() {
+            3 if shape[0] == 1 => (shape[1] as usize, shape[2] as usize),
+            2 => (shape[0] as usize, shape[1] as usize),
+            _ => {
+                return Err(MediaError::internal(format!(
+                    "Unexpected alignment model output shape: {:?}",
+                    shape
+                )))
+            }
+        };
+        if frames == 0 || classes < self.vocab.size() {
+            return Err(MediaError::internal(format!(
+                "Alignment model output ({frames}x{classes}) does not match vocabulary ({})",
+                self.vocab.size()
+            )));
+        }
+
+        let mut log_probs: Vec<f32> = data.iter().take(frames * classes).copied().collect();
+        log_softmax_rows(&mut log_probs, classes);
+
+        Ok(CtcEmissions {
+            log_probs,
+            classes,
+            seconds_per_frame: samples.len() as f64 / sample_rate as f64 / frames as f64,
+        })
+    }
+}
+
+fn find_default_model_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var(ALIGNMENT_MODEL_ENV) {
+        let path = PathBuf::from(path);
+        if path.exists() {
+            return Some(path);
+        }
+    }
+
+    MODEL_CANDIDATES
+        .iter()
+        .map(Path::new)
+        .find(|p| p.exists())
+        .map(Path::to_path_buf)
+}
@@ -0,0 +1,447 @@
+//! Audio post-processing: cleanup, EBU R128 loudness normalization and limiting.
+//!
+//! Runs as a pass on the finished clip, like captions, so every style gets it
+//! without per-renderer changes. Normalization uses FFmpeg's `loudnorm` in two
+//! passes: the first measures the clip, the second applies a linear gain
+//! computed from those measurements. Video is stream-copied; only audio is
+//! re-encoded.
+//!
+//! # Architecture
+//!
+//! - `cleanup_filters`: Optional high-pass and noise gate ahead of loudnorm
+//! - `measure_loudnorm`: First pass, returns the raw `loudnorm` measurement
+//! - `build_normalize_filter`: Second-pass filter chain (cleanup, loudnorm, limiter)
+//! - `apply_audio_stage`: Runs both passes on a clip in place
+//! - `measure_loudness_timeline`: Per-second momentary loudness of a source video
+
+use std::path::Path;
+
+use serde::Deserialize;
+use tracing::{debug, info};
+use vclip_models::{AudioConfig, EncodingConfig, LoudnessReport, LoudnessStats};
+
+use crate::error::{MediaError, MediaResult};
+use crate::probe::has_audio_stream;
+
+/// Sample rate restored after `loudnorm` (which resamples to 192 kHz internally).
+const OUTPUT_SAMPLE_RATE: u32 = 48_000;
+
+/// Clips quieter than this are treated as silent and left untouched (LUFS).
+const SILENCE_FLOOR_LUFS: f64 = -70.0;
+
+/// Measurement printed by `loudnorm` with `print_format=json`.
+///
+/// FFmpeg prints every value as a string (silence is reported as `"-inf"`).
+#[derive(Debug, Clone, Deserialize)]
+struct LoudnormJson {
+    input_i: String,
+    input_tp: String,
+    input_lra: String,
+    input_thresh: String,
+    output_i: String,
+    output_tp: String,
+    output_lra: String,
+    target_offset: String,
+}
+
+/// Parsed `loudnorm` measurement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoudnormMeasurement {
+    /// Loudness of the filter input
+    pub input: LoudnessStats,
+    /// Input gating threshold (LUFS)
+    pub input_thresh: f64,
+    /// Loudness of the filter output
+    pub output: LoudnessStats,
+    /// Offset gain to reach the target in the second pass (LU)
+    pub target_offset: f64,
+}
+
+impl LoudnormMeasurement {
+    /// Whether the input has enough signal to normalize.
+    pub fn is_silent(&self) -> bool {
+        !self.input.integrated_lufs.is_finite() || self.input.integrated_lufs < SILENCE_FLOOR_LUFS
+    }
+}
+
+/// Parse the JSON block `loudnorm` prints at the end of FFmpeg's stderr.
+pub fn parse_loudnorm_output(stderr: &str) -> Option<LoudnormMeasurement> {
+    let start = stderr.rfind('{')?;
+    let end = start + stderr[start..].find('}')?;
+    let json: LoudnormJson = serde_json::from_str(&stderr[start..=end]).ok()?;
+
+    let num = |s: &str| s.trim().parse::<f64>().ok();
+    Some(LoudnormMeasurement {
+        input: LoudnessStats {
+            integrated_lufs: num(&json.input_i)?,
+            true_peak_db: num(&json.input_tp)?,
+            loudness_range: num(&json.input_lra)?,
+        },
+        input_thresh: num(&json.input_thresh)?,
+        output: LoudnessStats {
+            integrated_lufs: num(&json.output_i)?,
+            true_peak_db: num(&json.output_tp)?,
+            loudness_range: num(&json.output_lra)?,
+        },
+        target_offset: num(&json.target_offset)?,
+    })
+}
+
+/// Convert a level in dB to a linear amplitude.
+fn db_to_linear(db: f64) -> f64 {
+    10f64.powf(db / 20.0)
+}
+
+/// Cleanup filters applied before loudness measurement and normalization.
+pub fn cleanup_filters(config: &AudioConfig) -> Vec<String> {
+    let mut filters = Vec::new();
+    if let Some(hz) = config.high_pass_hz {
+        filters.push(format!("highpass=f={}", hz));
+    }
+    if let Some(db) = config.noise_gate_db {
+        filters.push(format!(
+            "agate=threshold={:.6}:ratio=4:attack=5:release=150",
+            db_to_linear(db)
+        ));
+    }
+    filters
+}
+
+/// Target parameters shared by both `loudnorm` passes.
+fn loudnorm_targets(config: &AudioConfig) -> String {
+    format!(
+        "loudnorm=I={}:TP={}:LRA={}",
+        config.target_lufs, config.true_peak_db, config.loudness_range
+    )
+}
+
+/// First-pass filter chain: cleanup followed by a measuring `loudnorm`.
+pub fn build_measure_filter(config: &AudioConfig) -> String {
+    let mut filters = cleanup_filters(config);
+    filters.push(format!("{}:print_format=json", loudnorm_targets(config)));
+    filters.join(",")
+}
+
+/// Second-pass filter chain using the first-pass measurement.
+pub fn build_normalize_filter(config: &AudioConfig, measured: &LoudnormMeasurement) -> String {
+    let mut filters = cleanup_filters(config);
+    filters.push(format!(
+        "{}:measured_I={}:measured_TP={}:measured_LRA={}:measured_thresh={}:offset={}:linear=true:print_format=json",
+        loudnorm_targets(config),
+        measured.input.integrated_lufs,
+        measured.input.true_peak_db,
+        measured.input.loudness_range,
+        measured.input_thresh,
+        measured.target_offset
+    ));
+    if config.limiter {
+        filters.push(format!(
+            "alimiter=limit={:.6}:level=0",
+            db_to_linear(config.true_peak_db)
+        ));
+    }
+    filters.push(format!("aresample={}", OUTPUT_SAMPLE_RATE));
+    filters.join(",")
+}
+
+/// Run the measuring pass over the audio of `path`.
+///
+/// Returns `None` when FFmpeg produced no measurement (e.g. no audio stream).
+pub async fn measure_loudnorm(
+    path: &Path,
+    config: &AudioConfig,
+) -> MediaResult<Option<LoudnormMeasurement>> {
+    let path_str = path.to_string_lossy();
+    let filter = build_measure_filter(config);
+
+    let output = crate::command::create_ffmpeg_command()
+        .args([
+            "-hide_banner",
+            "-nostats",
+            "-i",
+            &path_str,
+            "-vn",
+            "-af",
+            &filter,
+            "-f",
+            "null",
+            "-",
+        ])
+        .output()
+        .await
+        .map_err(|e| {
+            MediaError::ffmpeg_failed(format!("Failed to spawn FFmpeg: {}", e), None, None)
+        })?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(MediaError::ffmpeg_failed(
+            "Loudness measurement failed",
+            Some(stderr.into_owned()),
+            output.status.code(),
+        ));
+    }
+
+    Ok(parse_loudnorm_output(&stderr))
+}
+
+/// Run the audio stage on a video file in place.
+///
+/// Returns `None` without touching the file when the clip has no audio or is
+/// effectively silent.
+///
+/// # Arguments
+/// * `video_path` - Path to the rendered clip (will be modified in place)
+/// * `config` - Audio stage options
+/// * `encoding` - Encoding settings for the audio re-encode
+pub async fn apply_audio_stage(
+    video_path: &Path,
+    config: &AudioConfig,
+    encoding: &EncodingConfig,
+) -> MediaResult<Option<LoudnessReport>> {
+    if !has_audio_stream(video_path).await? {
+        debug!(video = %video_path.display(), "No audio stream, skipping audio stage");
+        return Ok(None);
+    }
+
+    let measured = match measure_loudnorm(video_path, config).await? {
+        Some(m) if !m.is_silent() => m,
+        _ => {
+            debug!(video = %video_path.display(), "Audio is silent, skipping audio stage");
+            return Ok(None);
+        }
+    };
+
+    let video_str = video_path.to_string_lossy();
+    let temp_output = video_path.with_extension("audio.mp4");
+    let temp_output_str = temp_output.to_string_lossy();
+    let filter = build_normalize_filter(config, &measured);
+
+    info!(
+        video = %video_str,
+        target_lufs = config.target_lufs,
+        input_lufs = measured.input.integrated_lufs,
+        input_tp = measured.input.true_peak_db,
+        "Normalizing clip loudness"
+    );
+
+    let output = crate::command::create_ffmpeg_command()
+        .args([
+            "-y",
+            "-hide_banner",
+            "-nostats",
+            "-i",
+            &video_str,
+            "-map",
+            "0:v?",
+            "-map",
+            "0:a",
+            "-af",
+            &filter,
+            "-c:v",
+            "copy",
+            "-c:a",
+            &encoding.audio_codec,
+            "-b:a",
+            &encoding.audio_bitrate,
+            "-movflags",
+            "+faststart",
+            &temp_output_str,
+        ])
+        .output()
+        .await
+        .map_err(|e| {
+            MediaError::ffmpeg_failed(format!("Failed to spawn FFmpeg: {}", e), None, None)
+        })?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        let _ = tokio::fs::remove_file(&temp_output).await;
+        return Err(MediaError::ffmpeg_failed(
+            "Loudness normalization failed",
+            Some(stderr.into_owned()),
+            output.status.code(),
+        ));
+    }
+
+    // The second pass reports what it produced; fall back to the first-pass estimate
+    let output_stats = parse_loudnorm_output(&stderr)
+        .map(|m| m.output)
+        .unwrap_or(measured.output);
+
+    tokio::fs::rename(&temp_output, video_path)
+        .await
+        .map_err(|e| {
+            MediaError::InvalidVideo(format!(
+                "Failed to replace video with normalized version: {}",
+                e
+            ))
+        })?;
+
+    info!(
+        video = %video_str,
+        output_lufs = output_stats.integrated_lufs,
+        output_tp = output_stats.true_peak_db,
+        "Audio stage applied successfully"
+    );
+
+    Ok(Some(LoudnessReport {
+        target_lufs: config.target_lufs,
+        input: measured.input,
+        output: output_stats,
+    }))
+}
+
+/// Parse `ebur128` frame log lines into per-second momentary loudness.
+///
+/// Returns `(second, LUFS)` pairs in time order. Frames reported as silent
+/// (`-inf` or below the silence floor) are clamped to the floor.
+pub fn parse_ebur128_framelog(stderr: &str) -> Vec<(f64, f64)> {
+    let mut buckets: Vec<(f64, u32)> = Vec::new();
+
+    for line in stderr.lines() {
+        let Some(t_idx) = line.find(" t: ") else {
+            continue;
+        };
+        let Some(m_idx) = line.find(" M:") else {
+            continue;
+        };
+        let time = line[t_idx + 4..]
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.parse::<f64>().ok());
+        let momentary = line[m_idx + 3..]
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.parse::<f64>().ok());
+        let (Some(time), Some(momentary)) = (time, momentary) else {
+            continue;
+        };
+
+        let second = time.floor().max(0.0) as usize;
+        if buckets.len() <= second {
+            buckets.resize(second + 1, (0.0, 0));
+        }
+        let loudness = if momentary.is_finite() {
+            momentary.max(SILENCE_FLOOR_LUFS)
+        } else {
+            SILENCE_FLOOR_LUFS
+        };
+        buckets[second].0 += loudness;
+        buckets[second].1 += 1;
+    }
+
+    buckets
+        .into_iter()
+        .enumerate()
+        .filter(|(_, (_, count))| *count > 0)
+        .map(|(second, (sum, count))| (second as f64, sum / count as f64))
+        .collect()
+}
+
+/// Measure per-second momentary loudness (EBU R128) of a video's audio.
+///
+/// Returns an empty timeline when the video has no audio stream.
+pub async fn measure_loudness_timeline(video_path: &Path) -> MediaResult<Vec<(f64, f64)>> {
+    if !has_audio_stream(video_path).await? {
+        return Ok(Vec::new());
+    }
+
+    let path_str = video_path.to_string_lossy();
+    let output = crate::command::create_ffmpeg_command()
+        .args([
+            "-hide_banner",
+            "-nostats",
+            "-i",
+            &path_str,
+            "-vn",
+            "-af",
+            "ebur128=framelog=verbose",
+            "-f",
+            "null",
+            "-",
+        ])
+        .output()
+        .await
+        .map_err(|e| {
+            MediaError::ffmpeg_failed(format!("Failed to spawn FFmpeg: {}", e), None, None)
+        })?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(MediaError::ffmpeg_failed(
+            "Loudness timeline measurement failed",
+            Some(stderr.into_owned()),
+            output.status.code(),
+        ));
+    }
+
+    Ok(parse_ebur128_framelog(&stderr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_STDERR: &str = r#"size=N/A time=00:00:29.98 bitrate=N/A speed= 212x
+[Parsed_loudnorm_1 @ 0x55d0c8a1e2c0]
+{
+	"input_i" : "-27.61",
+	"input_tp" : "-4.47",
+	"input_lra" : "18.06",
+	"input_thresh" : "-39.20",
+	"output_i" : "-14.58",
+	"output_tp" : "-1.50",
+	"output_lra" : "14.78",
+	"output_thresh" : "-25.71",
+	"normalization_type" : "dynamic",
+	"target_offset" : "0.58"
+}
+"#;
+
+    #[test]
+    fn test_parse_loudnorm_output() {
+        let m = parse_loudnorm_output(SAMPLE_STDERR).unwrap();
+        assert_eq!(m.input.integrated_lufs, -27.61);
+        assert_eq!(m.input.true_peak_db, -4.47);
+        assert_eq!(m.input_thresh, -39.20);
+        assert_eq!(m.output.integrated_lufs, -14.58);
+        assert_eq!(m.target_offset, 0.58);
+        assert!(!m.is_silent());
+
+        let silent = SAMPLE_STDERR.replace("\"-27.61\"", "\"-inf\"");
+        assert!(parse_loudnorm_output(&silent).unwrap().is_silent());
+        assert!(parse_loudnorm_output("no json here").is_none());
+    }
+
+    #[test]
+    fn test_filter_chains() {
+        let config = AudioConfig::new(-16.0)
+            .with_high_pass(80)
+            .with_noise_gate(-50.0);
+        let measure = build_measure_filter(&config);
+        assert!(measure.starts_with("highpass=f=80,agate=threshold=0.003162"));
+        assert!(measure.ends_with("loudnorm=I=-16:TP=-1.5:LRA=11:print_format=json"));
+
+        let measured = parse_loudnorm_output(SAMPLE_STDERR).unwrap();
+        let normalize = build_normalize_filter(&config, &measured);
+        assert!(normalize.contains("measured_I=-27.61:measured_TP=-4.47"));
+        assert!(normalize.contains("offset=0.58:linear=true"));
+        assert!(normalize.contains("alimiter=limit=0.841395:level=0"));
+        assert!(normalize.ends_with("aresample=48000"));
+
+        let no_limiter = build_normalize_filter(&config.with_limiter(false), &measured);
+        assert!(!no_limiter.contains("alimiter"));
+    }
+
+    #[test]
+    fn test_parse_ebur128_framelog() {
+        let stderr = "[Parsed_ebur128_0 @ 0x1] t: 0.4      TARGET:-23 LUFS    M: -20.0 S:-120.7     I: -20.0 LUFS       LRA:   0.0 LU
+[Parsed_ebur128_0 @ 0x1] t: 0.5      TARGET:-23 LUFS    M: -30.0 S:-120.7     I: -24.0 LUFS       LRA:   0.0 LU
+[Parsed_ebur128_0 @ 0x1] t: 2.1      TARGET:-23 LUFS    M: -inf S:-120.7     I: -24.0 LUFS       LRA:   0.0 LU
+  Summary:
+    I:         -24.0 LUFS";
+        let timeline = parse_ebur128_framelog(stderr);
+        assert_eq!(timeline, vec![(0.0, -25.0), (2.0, SILENCE_FLOOR_LUFS)]);
+    }
+}
@@ -0,0 +1,487 @@
+//! Highlight boundary refinement.
+//!
+//! AI-picked highlight timestamps often land mid-sentence. This module moves
+//! each edge to the nearest natural boundary within a tolerance:
+//!
+//! - **Sentence breaks** from the `[HH:MM:SS] text` transcript
+//! - **Silences** from Silero VAD (`silence_removal::analyze_audio_segments`)
+//! - **Shot cuts** from histogram shot detection (`cinematic::ShotDetector`)
+//!
+//! # Architecture
+//!
+//! - `BoundaryCandidates`: Candidate boundary times in source time
+//! - `detect_media_candidates`: Silence and shot candidates from a segment
+//! - `refine_boundaries`: Pure snapping of a start/end pair
+//! - `trim_segment`: Frame-accurate trim of a segment to the refined window
+
+use std::path::Path;
+use tracing::{debug, info, warn};
+
+use crate::captions::parse_transcript_line;
+use crate::error::{MediaError, MediaResult};
+use crate::intelligent::cinematic::ShotSignals;
+use crate::silence_removal::{analyze_audio_segments, Segment, SegmentLabel, SilenceRemovalConfig};
+use vclip_models::{BoundarySnap, EncodingConfig};
+
+/// Extra cost (in seconds of distance) per priority step.
+///
+/// Sentence breaks win over a silence that is up to this much closer, and
+/// silences win over shot cuts the same way.
+const PRIORITY_BIAS_SECS: f64 = 0.25;
+
+/// Edges closer than this to their original position are not moved.
+const MIN_SNAP_DISTANCE_SECS: f64 = 0.05;
+
+/// Configuration for boundary refinement.
+#[derive(Debug, Clone)]
+pub struct BoundaryRefinementConfig {
+    /// Maximum distance an edge may move (seconds)
+    pub tolerance_secs: f64,
+    /// Refined highlights shorter than this keep their original edges (seconds)
+    pub min_duration_secs: f64,
+    /// Snap to transcript sentence breaks
+    pub use_sentences: bool,
+    /// Snap to VAD silences
+    pub use_silence: bool,
+    /// Snap to shot cuts
+    pub use_shots: bool,
+}
+
+impl Default for BoundaryRefinementConfig {
+    fn default() -> Self {
+        Self {
+            tolerance_secs: 1.0,
+            min_duration_secs: 3.0,
+            use_sentences: true,
+            use_silence: true,
+            use_shots: true,
+        }
+    }
+}
+
+impl BoundaryRefinementConfig {
+    /// Set the snapping tolerance.
+    pub fn with_tolerance(mut self, tolerance_secs: f64) -> Self {
+        self.tolerance_secs = tolerance_secs.max(0.0);
+        self
+    }
+
+    /// Set the minimum refined duration.
+    pub fn with_min_duration(mut self, min_duration_secs: f64) -> Self {
+        self.min_duration_secs = min_duration_secs.max(0.0);
+        self
+    }
+
+    /// Enable or disable shot-cut snapping.
+    pub fn with_shots(mut self, use_shots: bool) -> Self {
+        self.use_shots = use_shots;
+        self
+    }
+
+    /// Whether refinement can move anything at all.
+    pub fn is_enabled(&self) -> bool {
+        self.tolerance_secs > 0.0 && (self.use_sentences || self.use_silence || self.use_shots)
+    }
+}
+
+/// Candidate boundaries, all in source time (seconds).
+#[derive(Debug, Clone, Default)]
+pub struct BoundaryCandidates {
+    /// Times where a new sentence starts
+    pub sentence_breaks: Vec<f64>,
+    /// Silent spans `(start, end)`
+    pub silences: Vec<(f64, f64)>,
+    /// Times of hard shot cuts
+    pub shot_cuts: Vec<f64>,
+}
+
+impl BoundaryCandidates {
+    /// Set sentence breaks.
+    pub fn with_sentence_breaks(mut self, breaks: Vec<f64>) -> Self {
+        self.sentence_breaks = breaks;
+        self
+    }
+
+    /// Whether there is nothing to snap to.
+    pub fn is_empty(&self) -> bool {
+        self.sentence_breaks.is_empty() && self.silences.is_empty() && self.shot_cuts.is_empty()
+    }
+}
+
+/// Refined start/end pair in source time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefinedBoundaries {
+    /// Refined start (seconds)
+    pub start: f64,
+    /// Refined end (seconds)
+    pub end: f64,
+    /// What the start was snapped to
+    pub start_snap: BoundarySnap,
+    /// What the end was snapped to
+    pub end_snap: BoundarySnap,
+}
+
+impl RefinedBoundaries {
+    fn unchanged(start: f64, end: f64) -> Self {
+        Self {
+            start,
+            end,
+            start_snap: BoundarySnap::Unchanged,
+            end_snap: BoundarySnap::Unchanged,
+        }
+    }
+
+    /// Whether either edge moved.
+    pub fn changed(&self) -> bool {
+        self.start_snap.is_snapped() || self.end_snap.is_snapped()
+    }
+}
+
+/// Which edge of a highlight is being snapped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edge {
+    Start,
+    End,
+}
+
+/// Extract sentence-start times from a `[HH:MM:SS] text` transcript.
+///
+/// A line starts a sentence when the previous line ended with terminal
+/// punctuation. Only breaks inside `[window_start, window_end]` are returned.
+pub fn sentence_breaks_from_transcript(
+    transcript: &str,
+    window_start: f64,
+    window_end: f64,
+) -> Vec<f64> {
+    let mut breaks = Vec::new();
+    let mut previous_ended_sentence = true;
+
+    for (time, text) in transcript.lines().filter_map(parse_transcript_line) {
+        if text.is_empty() {
+            continue;
+        }
+        if previous_ended_sentence && time >= window_start && time <= window_end {
+            breaks.push(time);
+        }
+        previous_ended_sentence = ends_sentence(text);
+    }
+
+    breaks
+}
+
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end_matches(['"', '\'', ')', ']', '”', '’'])
+        .ends_with(['.', '!', '?', '…'])
+}
+
+/// Convert VAD segments of a segment starting at `offset` into silent spans.
+pub fn silences_from_segments(segments: &[Segment], offset: f64) -> Vec<(f64, f64)> {
+    segments
+        .iter()
+        .filter(|s| s.label == SegmentLabel::Cut)
+        .map(|s| {
+            (
+                offset + s.start_ms as f64 / 1000.0,
+                offset + s.end_ms as f64 / 1000.0,
+            )
+        })
+        .collect()
+}
+
+/// Snap a highlight's start/end to the nearest candidate boundaries.
+///
+/// Each edge moves to the lowest-cost candidate within the tolerance, where
+/// cost is distance plus a small bias favouring sentences over silences over
+/// shot cuts. Starts snap to speech onsets (silence ends) and ends to speech
+/// offsets (silence starts). If the result would be shorter than the minimum
+/// duration, the original edges are kept.
+pub fn refine_boundaries(
+    start: f64,
+    end: f64,
+    candidates: &BoundaryCandidates,
+    config: &BoundaryRefinementConfig,
+) -> RefinedBoundaries {
+    if !config.is_enabled() || end <= start {
+        return RefinedBoundaries::unchanged(start, end);
+    }
+
+    let (new_start, start_snap) = snap_edge(start, Edge::Start, candidates, config);
+    let (new_end, end_snap) = snap_edge(end, Edge::End, candidates, config);
+
+    let refined = RefinedBoundaries {
+        start: new_start.max(0.0),
+        end: new_end,
+        start_snap,
+        end_snap,
+    };
+
+    if refined.end - refined.start < config.min_duration_secs.min(end - start) {
+        debug!(
+            start,
+            end,
+            refined_start = refined.start,
+            refined_end = refined.end,
+            "Refined highlight too short, keeping original boundaries"
+        );
+        return RefinedBoundaries::unchanged(start, end);
+    }
+
+    refined
+}
+
+fn snap_edge(
+    time: f64,
+    edge: Edge,
+    candidates: &BoundaryCandidates,
+    config: &BoundaryRefinementConfig,
+) -> (f64, BoundarySnap) {
+    let silence_points: Vec<f64> = candidates
+        .silences
+        .iter()
+        .map(|&(silence_start, silence_end)| match edge {
+            Edge::Start => silence_end,
+            Edge::End => silence_start,
+        })
+        .collect();
+
+    let sources: [(BoundarySnap, bool, &[f64]); 3] = [
+        (
+            BoundarySnap::Sentence,
+            config.use_sentences,
+            &candidates.sentence_breaks,
+        ),
+        (BoundarySnap::Silence, config.use_silence, &silence_points),
+        (BoundarySnap::ShotCut, config.use_shots, &candidates.shot_cuts),
+    ];
+
+    let mut best: Option<(f64, f64, BoundarySnap)> = None;
+    for (priority, (snap, enabled, times)) in sources.iter().enumerate() {
+        if !enabled {
+            continue;
+        }
+        for &candidate in times.iter() {
+            let distance = (candidate - time).abs();
+            if distance > config.tolerance_secs {
+                continue;
+            }
+            let cost = distance + priority as f64 * PRIORITY_BIAS_SECS;
+            if best.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                best = Some((cost, candidate, *snap));
+            }
+        }
+    }
+
+    match best {
+        Some((_, candidate, snap)) if (candidate - time).abs() >= MIN_SNAP_DISTANCE_SECS => {
+            (candidate, snap)
+        }
+        _ => (time, BoundarySnap::Unchanged),
+    }
+}
+
+/// Detect silence and shot-cut candidates in a segment.
+///
+/// `offset` is the source time of the segment's first frame; returned
+/// candidates are in source time. Detection failures are logged and yield
+/// no candidates for that source, so refinement degrades gracefully.
+pub async fn detect_media_candidates(
+    segment_path: &Path,
+    offset: f64,
+    config: &BoundaryRefinementConfig,
+) -> BoundaryCandidates {
+    let mut candidates = BoundaryCandidates::default();
+
+    if config.use_silence {
+        match analyze_audio_segments(segment_path, SilenceRemovalConfig::default()).await {
+            Ok(segments) => candidates.silences = silences_from_segments(&segments, offset),
+            Err(e) => warn!(error = %e, "VAD analysis for boundary refinement failed"),
+        }
+    }
+
+    if config.use_shots {
+        match crate::probe::get_duration(segment_path).await {
+            Ok(duration) => match ShotSignals::new().extract(segment_path, 0.0, duration).await {
+                Ok(shots) => {
+                    candidates.shot_cuts = shots
+                        .iter()
+                        .skip(1)
+                        .map(|shot| offset + shot.start_time)
+                        .collect();
+                }
+                Err(e) => warn!(error = %e, "Shot detection for boundary refinement failed"),
+            },
+            Err(e) => warn!(error = %e, "Failed to probe segment for shot detection"),
+        }
+    }
+
+    debug!(
+        silences = candidates.silences.len(),
+        shot_cuts = candidates.shot_cuts.len(),
+        "Detected media boundary candidates"
+    );
+    candidates
+}
+
+/// Trim a segment to `[start, end]` (seconds relative to the segment start).
+///
+/// Re-encodes so the cut is frame-accurate; stream copy would snap to keyframes
+/// and undo the refinement.
+pub async fn trim_segment(
+    input: &Path,
+    output: &Path,
+    start: f64,
+    end: f64,
+    encoding: &EncodingConfig,
+) -> MediaResult<()> {
+    if end <= start {
+        return Err(MediaError::InvalidVideo(format!(
+            "Invalid trim range {:.3}s-{:.3}s",
+            start, end
+        )));
+    }
+
+    info!(
+        input = %input.display(),
+        start = start,
+        end = end,
+        "Trimming segment to refined boundaries"
+    );
+
+    let output_status = crate::command::create_ffmpeg_command()
+        .args(["-y", "-hide_banner", "-loglevel", "error", "-i"])
+        .arg(input)
+        .args([
+            "-ss",
+            &format!("{:.3}", start),
+            "-t",
+            &format!("{:.3}", end - start),
+            "-c:v",
+            &encoding.codec,
+            "-preset",
+            &encoding.preset,
+            "-crf",
+            &encoding.crf.to_string(),
+            "-c:a",
+            &encoding.audio_codec,
+            "-b:a",
+            &encoding.audio_bitrate,
+            "-movflags",
+            "+faststart",
+        ])
+        .arg(output)
+        .output()
+        .await
+        .map_err(|e| {
+            MediaError::ffmpeg_failed(format!("Failed to spawn FFmpeg: {}", e), None, None)
+        })?;
+
+    if !output_status.status.success() {
+        let _ = tokio::fs::remove_file(output).await;
+        let stderr = String::from_utf8_lossy(&output_status.stderr);
+        return Err(MediaError::ffmpeg_failed(
+            "Segment trim failed",
+            Some(stderr.into_owned()),
+            output_status.status.code(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> BoundaryRefinementConfig {
+        BoundaryRefinementConfig::default().with_tolerance(1.0)
+    }
+
+    #[test]
+    fn test_sentence_breaks_from_transcript() {
+        let transcript = "[00:00:01] Hello there.\n\
+                          [00:00:03] This is a long\n\
+                          [00:00:05] sentence that continues!\n\
+                          [00:00:08] Next one";
+        let breaks = sentence_breaks_from_transcript(transcript, 0.0, 100.0);
+        assert_eq!(breaks, vec![1.0, 3.0, 8.0]);
+
+        let windowed = sentence_breaks_from_transcript(transcript, 2.0, 6.0);
+        assert_eq!(windowed, vec![3.0]);
+    }
+
+    #[test]
+    fn test_refine_prefers_sentence_over_slightly_closer_silence() {
+        let candidates = BoundaryCandidates {
+            sentence_breaks: vec![9.5],
+            silences: vec![(9.0, 9.7)],
+            shot_cuts: vec![],
+        };
+        let refined = refine_boundaries(10.0, 40.0, &candidates, &config());
+        assert_eq!(refined.start, 9.5);
+        assert_eq!(refined.start_snap, BoundarySnap::Sentence);
+        assert_eq!(refined.end_snap, BoundarySnap::Unchanged);
+        assert!(refined.changed());
+    }
+
+    #[test]
+    fn test_refine_end_snaps_to_silence_start() {
+        let candidates = BoundaryCandidates {
+            sentence_breaks: vec![],
+            silences: vec![(40.4, 41.5)],
+            shot_cuts: vec![39.2],
+        };
+        let refined = refine_boundaries(10.0, 40.0, &candidates, &config());
+        assert_eq!(refined.end, 40.4);
+        assert_eq!(refined.end_snap, BoundarySnap::Silence);
+    }
+
+    #[test]
+    fn test_refine_ignores_candidates_outside_tolerance() {
+        let candidates = BoundaryCandidates {
+            sentence_breaks: vec![7.0, 43.0],
+            silences: vec![],
+            shot_cuts: vec![10.8],
+        };
+        let refined = refine_boundaries(10.0, 40.0, &candidates, &config());
+        assert_eq!(refined.start, 10.8);
+        assert_eq!(refined.start_snap, BoundarySnap::ShotCut);
+        assert_eq!(refined.end, 40.0);
+        assert_eq!(refined.end_snap, BoundarySnap::Unchanged);
+    }
+
+    #[test]
+    fn test_refine_keeps_original_when_too_short() {
+        let candidates = BoundaryCandidates {
+            sentence_breaks: vec![10.9, 13.1],
+            silences: vec![],
+            shot_cuts: vec![],
+        };
+        let config = config().with_min_duration(3.0);
+        let refined = refine_boundaries(10.0, 14.0, &candidates, &config);
+        assert_eq!(refined, RefinedBoundaries::unchanged(10.0, 14.0));
+    }
+
+    #[test]
+    fn test_refine_disabled_with_zero_tolerance() {
+        let candidates = BoundaryCandidates::default().with_sentence_breaks(vec![9.9]);
+        let config = BoundaryRefinementConfig::default().with_tolerance(0.0);
+        assert!(!refine_boundaries(10.0, 40.0, &candidates, &config).changed());
+    }
+
+    #[test]
+    fn test_silences_from_segments_offsets() {
+        let segments = vec![
+            Segment {
+                start_ms: 0,
+                end_ms: 500,
+                label: SegmentLabel::Cut,
+            },
+            Segment {
+                start_ms: 500,
+                end_ms: 3000,
+                label: SegmentLabel::Keep,
+            },
+        ];
+        assert_eq!(silences_from_segments(&segments, 9.0), vec![(9.0, 9.5)]);
+    }
+}
@@ -0,0 +1,744 @@
+//! Burned-in animated captions.
+//!
+//! Captions are rendered as a post-processing pass on the finished clip so
+//! every style processor gets them without per-renderer changes. Timed words
+//! are converted into an ASS script which FFmpeg's `ass` filter burns into
+//! the video.
+//!
+//! # Architecture
+//!
+//! - `CaptionConfig`: Builder pattern for preset and placement hints
+//! - `build_ass_document`: Renders timed words into an ASS script
+//! - `estimate_word_timings`: Word timings from a `[HH:MM:SS] text` transcript
+//! - `remap_words_through_segments`: Shifts word timings after silence removal
+//! - `apply_captions`: Low-level FFmpeg burn-in function (in-place)
+
+use std::fmt::Write as _;
+use std::path::Path;
+use tracing::{debug, info};
+
+use crate::error::{MediaError, MediaResult};
+use crate::silence_removal::{Segment, SegmentLabel};
+use crate::watermark::escape_filter_path;
+use vclip_models::{
+    CaptionOptions, CaptionPosition, CaptionPreset, CaptionWord, EncodingConfig,
+    SceneNeuralAnalysis, StreamerSplitParams, Style,
+};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Font used for captions (bundled with the production container).
+pub const DEFAULT_CAPTION_FONT: &str = "DejaVu Sans";
+
+/// Normalized vertical anchor for top captions.
+const TOP_ANCHOR_Y: f64 = 0.20;
+
+/// Normalized vertical anchor for bottom captions.
+const BOTTOM_ANCHOR_Y: f64 = 0.78;
+
+/// Approximate normalized half-height of a two-line caption block.
+const CAPTION_HALF_HEIGHT: f64 = 0.07;
+
+/// Font size as a fraction of output height.
+const FONT_SIZE_RATIO: f64 = 0.055;
+
+/// A pause longer than this starts a new caption group (seconds).
+const GROUP_BREAK_GAP: f64 = 0.6;
+
+/// Maximum time a group lingers after its last word (seconds).
+const GROUP_LINGER: f64 = 0.3;
+
+/// Speaking rate used when a transcript line has no following timestamp.
+const FALLBACK_SECONDS_PER_WORD: f64 = 0.35;
+
+/// ASS colours (&HAABBGGRR).
+const COLOUR_WHITE: &str = "&H00FFFFFF";
+const COLOUR_HIGHLIGHT: &str = "&H0000E5FF";
+const COLOUR_OUTLINE: &str = "&H00000000";
+const COLOUR_SHADOW: &str = "&H80000000";
+
+/// Inline override colours (&HBBGGRR&).
+const INLINE_WHITE: &str = "&HFFFFFF&";
+const INLINE_HIGHLIGHT: &str = "&H00E5FF&";
+
+// =============================================================================
+// Configuration (Builder Pattern)
+// =============================================================================
+
+/// Configuration for caption rendering.
+///
+/// Use the builder pattern for flexible configuration:
+/// ```ignore
+/// let config = CaptionConfig::new(options)
+///     .with_face_band(0.15, 0.55)
+///     .with_split_seam(0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CaptionConfig {
+    /// User-facing caption options
+    pub options: CaptionOptions,
+    /// Font family name
+    pub font_name: String,
+    /// Normalized vertical extent of detected faces (top, bottom)
+    pub face_band: Option<(f64, f64)>,
+    /// Normalized y of the seam between split panels
+    pub split_seam: Option<f64>,
+}
+
+impl CaptionConfig {
+    /// Create config from caption options.
+    pub fn new(options: CaptionOptions) -> Self {
+        Self {
+            options,
+            font_name: DEFAULT_CAPTION_FONT.to_string(),
+            face_band: None,
+            split_seam: None,
+        }
+    }
+
+    /// Set font family.
+    pub fn with_font(mut self, font_name: impl Into<String>) -> Self {
+        self.font_name = font_name.into();
+        self
+    }
+
+    /// Set the normalized vertical region occupied by faces.
+    pub fn with_face_band(mut self, top: f64, bottom: f64) -> Self {
+        let (top, bottom) = if top <= bottom {
+            (top, bottom)
+        } else {
+            (bottom, top)
+        };
+        self.face_band = Some((top.clamp(0.0, 1.0), bottom.clamp(0.0, 1.0)));
+        self
+    }
+
+    /// Set the normalized y position of the split-view seam.
+    pub fn with_split_seam(mut self, seam: f64) -> Self {
+        self.split_seam = Some(seam.clamp(0.1, 0.9));
+        self
+    }
+
+    /// Derive placement hints for a style.
+    ///
+    /// Split views anchor captions on the seam between panels. Single views
+    /// use the face region from cached neural analysis, if available. The
+    /// analysis is in source-frame coordinates; vertical extents carry over
+    /// because portrait crops keep the full source height.
+    pub fn for_style(
+        options: CaptionOptions,
+        style: Style,
+        split_params: Option<&StreamerSplitParams>,
+        analysis: Option<&SceneNeuralAnalysis>,
+    ) -> Self {
+        let config = Self::new(options);
+        if style.is_split_view() {
+            let ratio = split_params
+                .and_then(|p| p.split_ratio)
+                .map(|r| r as f64)
+                .unwrap_or(0.5);
+            return config.with_split_seam(ratio);
+        }
+        match analysis.and_then(face_band_from_analysis) {
+            Some((top, bottom)) => config.with_face_band(top, bottom),
+            None => config,
+        }
+    }
+
+    /// Resolve the normalized vertical anchor for the caption block.
+    pub fn anchor_y(&self) -> f64 {
+        match self.options.position {
+            CaptionPosition::Top => TOP_ANCHOR_Y,
+            CaptionPosition::Middle => 0.5,
+            CaptionPosition::Bottom => BOTTOM_ANCHOR_Y,
+            CaptionPosition::Auto => self.auto_anchor_y(),
+        }
+    }
+
+    fn auto_anchor_y(&self) -> f64 {
+        if let Some(seam) = self.split_seam {
+            return seam;
+        }
+        let Some((top, bottom)) = self.face_band else {
+            return BOTTOM_ANCHOR_Y;
+        };
+
+        let overlaps = |y: f64| y + CAPTION_HALF_HEIGHT > top && y - CAPTION_HALF_HEIGHT < bottom;
+        if !overlaps(BOTTOM_ANCHOR_Y) {
+            BOTTOM_ANCHOR_Y
+        } else if !overlaps(TOP_ANCHOR_Y) {
+            TOP_ANCHOR_Y
+        } else {
+            // Faces cover both candidates; pick the side farther from the face center
+            let center = (top + bottom) / 2.0;
+            if (BOTTOM_ANCHOR_Y - center).abs() >= (center - TOP_ANCHOR_Y).abs() {
+                BOTTOM_ANCHOR_Y
+            } else {
+                TOP_ANCHOR_Y
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Helper Functions
+// =============================================================================
+
+/// Compute the normalized vertical band covered by faces across all frames.
+///
+/// Returns `None` when no faces were detected.
+pub fn face_band_from_analysis(analysis: &SceneNeuralAnalysis) -> Option<(f64, f64)> {
+    let mut count = 0usize;
+    let mut top_sum = 0.0;
+    let mut bottom_sum = 0.0;
+
+    for face in analysis.frames.iter().flat_map(|f| f.faces.iter()) {
+        top_sum += face.bbox.y as f64;
+        bottom_sum += (face.bbox.y + face.bbox.height) as f64;
+        count += 1;
+    }
+
+    if count == 0 {
+        return None;
+    }
+    Some((top_sum / count as f64, bottom_sum / count as f64))
+}
+
+/// Estimate per-word timings from a `[HH:MM:SS] text` transcript.
+///
+/// Each line spans from its timestamp to the next line's timestamp, and its
+/// words are spread across that span proportionally to their length. Only
+/// words whose midpoint falls in `[window_start, window_end)` are returned,
+/// shifted so that `window_start` becomes zero.
+pub fn estimate_word_timings(
+    transcript: &str,
+    window_start: f64,
+    window_end: f64,
+) -> Vec<CaptionWord> {
+    let lines: Vec<(f64, &str)> = transcript
+        .lines()
+        .filter_map(parse_transcript_line)
+        .collect();
+    let mut words = Vec::new();
+
+    for (i, (line_start, text)) in lines.iter().enumerate() {
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        if tokens.is_empty() {
+            continue;
+        }
+
+        let fallback_end = line_start + tokens.len() as f64 * FALLBACK_SECONDS_PER_WORD;
+        let line_end = lines
+            .get(i + 1)
+            .map(|(next, _)| *next)
+            .filter(|next| *next > *line_start)
+            .unwrap_or(fallback_end);
+        if line_end <= window_start || *line_start >= window_end {
+            continue;
+        }
+
+        let weights: Vec<f64> = tokens
+            .iter()
+            .map(|t| t.chars().count() as f64 + 1.0)
+            .collect();
+        let total_weight: f64 = weights.iter().sum();
+        let span = line_end - line_start;
+
+        let mut cursor = *line_start;
+        for (token, weight) in tokens.iter().zip(weights) {
+            let start = cursor;
+            let end = cursor + span * weight / total_weight;
+            cursor = end;
+
+            let mid = (start + end) / 2.0;
+            if mid < window_start || mid >= window_end {
+                continue;
+            }
+            words.push(CaptionWord::new(
+                *token,
+                (start - window_start).max(0.0),
+                (end.min(window_end) - window_start).max(0.0),
+            ));
+        }
+    }
+
+    words
+}
+
+pub(crate) fn parse_transcript_line(line: &str) -> Option<(f64, &str)> {
+    let line = line.trim();
+    let rest = line.strip_prefix('[')?;
+    let (ts, text) = rest.split_once(']')?;
+    let seconds = crate::intelligent::parse_timestamp(ts.trim()).ok()?;
+    Some((seconds, text.trim()))
+}
+
+/// Map word timings through silence-removal segments.
+///
+/// Times inside a cut region snap to the start of the next kept region. Words
+/// that fall entirely inside cut regions are dropped.
+pub fn remap_words_through_segments(
+    words: &[CaptionWord],
+    segments: &[Segment],
+) -> Vec<CaptionWord> {
+    if segments.is_empty() {
+        return words.to_vec();
+    }
+
+    let map_time = |t: f64| -> f64 {
+        let t_ms = (t * 1000.0).max(0.0) as u64;
+        let mut kept_ms = 0u64;
+        for seg in segments {
+            if seg.label != SegmentLabel::Keep {
+                continue;
+            }
+            if t_ms < seg.start_ms {
+                break;
+            }
+            if t_ms < seg.end_ms {
+                return (kept_ms + (t_ms - seg.start_ms)) as f64 / 1000.0;
+            }
+            kept_ms += seg.duration_ms();
+        }
+        kept_ms as f64 / 1000.0
+    };
+
+    words
+        .iter()
+        .filter_map(|w| {
+            let start = map_time(w.start);
+            let end = map_time(w.end);
+            (end - start > 0.01).then(|| CaptionWord::new(w.text.clone(), start, end))
+        })
+        .collect()
+}
+
+/// Split words into on-screen groups.
+fn group_words(words: &[CaptionWord], max_words: usize) -> Vec<&[CaptionWord]> {
+    let max_words = max_words.max(1);
+    let mut groups = Vec::new();
+    let mut start = 0;
+
+    for i in 1..=words.len() {
+        let at_end = i == words.len();
+        let full = i - start >= max_words;
+        let gap = !at_end && words[i].start - words[i - 1].end > GROUP_BREAK_GAP;
+        if at_end || full || gap {
+            groups.push(&words[start..i]);
+            start = i;
+        }
+    }
+
+    groups
+}
+
+fn sanitize_ass_text(text: &str, uppercase: bool) -> String {
+    let cleaned: String = text
+        .chars()
+        .map(|c| match c {
+            '{' => '(',
+            '}' => ')',
+            '\\' => '/',
+            c => c,
+        })
+        .collect();
+    if uppercase {
+        cleaned.to_uppercase()
+    } else {
+        cleaned
+    }
+}
+
+fn format_ass_time(seconds: f64) -> String {
+    let cs = (seconds.max(0.0) * 100.0).round() as u64;
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        cs / 360_000,
+        (cs / 6000) % 60,
+        (cs / 100) % 60,
+        cs % 100
+    )
+}
+
+// =============================================================================
+// Core Functions
+// =============================================================================
+
+/// Render timed words into an ASS subtitle document.
+pub fn build_ass_document(
+    words: &[CaptionWord],
+    config: &CaptionConfig,
+    width: u32,
+    height: u32,
+) -> String {
+    let opts = &config.options;
+    let font_size = ((height as f64) * FONT_SIZE_RATIO).round().max(12.0);
+    let outline = (font_size * 0.08).round().max(2.0);
+    let pos_x = width / 2;
+    let pos_y = (config.anchor_y() * height as f64).round() as u32;
+
+    // Karaoke fills from SecondaryColour to PrimaryColour as words are spoken
+    let (primary, secondary) = match opts.preset {
+        CaptionPreset::Karaoke => (COLOUR_HIGHLIGHT, COLOUR_WHITE),
+        _ => (COLOUR_WHITE, COLOUR_HIGHLIGHT),
+    };
+
+    let mut doc = String::new();
+    let _ = writeln!(doc, "[Script Info]");
+    let _ = writeln!(doc, "ScriptType: v4.00+");
+    let _ = writeln!(doc, "PlayResX: {}", width);
+    let _ = writeln!(doc, "PlayResY: {}", height);
+    let _ = writeln!(doc, "WrapStyle: 2");
+    let _ = writeln!(doc, "ScaledBorderAndShadow: yes");
+    let _ = writeln!(doc);
+    let _ = writeln!(doc, "[V4+ Styles]");
+    let _ = writeln!(
+        doc,
+        "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding"
+    );
+    let _ = writeln!(
+        doc,
+        "Style: Caption,{},{},{},{},{},{},-1,0,0,0,100,100,0,0,1,{},2,5,40,40,0,1",
+        config.font_name, font_size, primary, secondary, COLOUR_OUTLINE, COLOUR_SHADOW, outline
+    );
+    let _ = writeln!(doc);
+    let _ = writeln!(doc, "[Events]");
+    let _ = writeln!(
+        doc,
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text"
+    );
+
+    let prefix = format!("{{\\an5\\pos({},{})}}", pos_x, pos_y);
+    let max_words = opts.max_words_per_line as usize;
+    let groups = match opts.preset {
+        CaptionPreset::TwoLineBlock => group_words(words, max_words * 2),
+        _ => group_words(words, max_words),
+    };
+
+    for (gi, group) in groups.iter().enumerate() {
+        let next_start = groups.get(gi + 1).map(|g| g[0].start);
+        let last_end = group[group.len() - 1].end;
+        let group_end = match next_start {
+            Some(next) => (last_end + GROUP_LINGER).min(next).max(last_end),
+            None => last_end + GROUP_LINGER,
+        };
+        let texts: Vec<String> = group
+            .iter()
+            .map(|w| sanitize_ass_text(&w.text, opts.uppercase))
+            .collect();
+
+        match opts.preset {
+            CaptionPreset::WordHighlight => {
+                for (wi, word) in group.iter().enumerate() {
+                    let end = group.get(wi + 1).map(|w| w.start).unwrap_or(group_end);
+                    let line = texts
+                        .iter()
+                        .enumerate()
+                        .map(|(i, t)| {
+                            if i == wi {
+                                format!("{{\\c{}}}{}{{\\c{}}}", INLINE_HIGHLIGHT, t, INLINE_WHITE)
+                            } else {
+                                t.clone()
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    push_dialogue(&mut doc, word.start, end, &prefix, &line);
+                }
+            }
+            CaptionPreset::TwoLineBlock => {
+                let split = texts.len().div_ceil(2);
+                let line = if texts.len() > max_words {
+                    format!(
+                        "{}\\N{}",
+                        texts[..split].join(" "),
+                        texts[split..].join(" ")
+                    )
+                } else {
+                    texts.join(" ")
+                };
+                push_dialogue(&mut doc, group[0].start, group_end, &prefix, &line);
+            }
+            CaptionPreset::Karaoke => {
+                let line = group
+                    .iter()
+                    .enumerate()
+                    .map(|(wi, w)| {
+                        let until = group.get(wi + 1).map(|n| n.start).unwrap_or(w.end);
+                        let cs = ((until - w.start).max(0.0) * 100.0).round() as u64;
+                        format!("{{\\k{}}}{}", cs, texts[wi])
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                push_dialogue(&mut doc, group[0].start, group_end, &prefix, &line);
+            }
+        }
+    }
+
+    doc
+}
+
+fn push_dialogue(doc: &mut String, start: f64, end: f64, prefix: &str, text: &str) {
+    let _ = writeln!(
+        doc,
+        "Dialogue: 0,{},{},Caption,,0,0,0,,{}{}",
+        format_ass_time(start),
+        format_ass_time(end),
+        prefix,
+        text
+    );
+}
+
+/// Burn captions into a video file in place.
+///
+/// # Arguments
+/// * `video_path` - Path to the rendered clip (will be modified in place)
+/// * `words` - Timed words relative to the clip start
+/// * `config` - Caption configuration
+/// * `encoding` - Encoding settings for re-encode
+pub async fn apply_captions(
+    video_path: &Path,
+    words: &[CaptionWord],
+    config: &CaptionConfig,
+    encoding: &EncodingConfig,
+) -> MediaResult<()> {
+    if words.is_empty() {
+        debug!(video = %video_path.display(), "No caption words, skipping caption pass");
+        return Ok(());
+    }
+
+    let info = crate::probe::probe_video(video_path).await?;
+    let document = build_ass_document(words, config, info.width, info.height);
+
+    let ass_path = video_path.with_extension("captions.ass");
+    tokio::fs::write(&ass_path, document).await?;
+
+    let video_str = video_path.to_string_lossy();
+    let temp_output = video_path.with_extension("captioned.mp4");
+    let temp_output_str = temp_output.to_string_lossy();
+    let filter = format!("ass='{}'", escape_filter_path(&ass_path.to_string_lossy()));
+
+    info!(
+        video = %video_str,
+        preset = %config.options.preset,
+        words = words.len(),
+        "Burning in captions"
+    );
+
+    let output = crate::command::create_ffmpeg_command()
+        .args([
+            "-y",
+            "-hide_banner",
+            "-loglevel",
+            "warning",
+            "-i",
+            &video_str,
+            "-vf",
+            &filter,
+            "-c:v",
+            &encoding.codec,
+            "-preset",
+            &encoding.preset,
+            "-crf",
+            &encoding.crf.to_string(),
+            "-c:a",
+            "copy",
+            "-movflags",
+            "+faststart",
+            &temp_output_str,
+        ])
+        .output()
+        .await
+        .map_err(|e| {
+            MediaError::ffmpeg_failed(format!("Failed to spawn FFmpeg: {}", e), None, None)
+        })?;
+
+    let _ = tokio::fs::remove_file(&ass_path).await;
+
+    if !output.status.success() {
+        let _ = tokio::fs::remove_file(&temp_output).await;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MediaError::ffmpeg_failed(
+            "Caption burn-in failed",
+            Some(stderr.into_owned()),
+            output.status.code(),
+        ));
+    }
+
+    tokio::fs::rename(&temp_output, video_path)
+        .await
+        .map_err(|e| {
+            MediaError::InvalidVideo(format!(
+                "Failed to replace video with captioned version: {}",
+                e
+            ))
+        })?;
+
+    info!(video = %video_str, "Captions applied successfully");
+    Ok(())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vclip_models::{BoundingBox, FaceDetection, FrameAnalysis};
+
+    fn words(spec: &[(&str, f64, f64)]) -> Vec<CaptionWord> {
+        spec.iter()
+            .map(|(t, s, e)| CaptionWord::new(*t, *s, *e))
+            .collect()
+    }
+
+    #[test]
+    fn test_format_ass_time() {
+        assert_eq!(format_ass_time(0.0), "0:00:00.00");
+        assert_eq!(format_ass_time(61.256), "0:01:01.26");
+        assert_eq!(format_ass_time(3725.5), "1:02:05.50");
+    }
+
+    #[test]
+    fn test_estimate_word_timings_windowed() {
+        let transcript = "[00:00:10] hello there world\n[00:00:13] second line\n";
+        let words = estimate_word_timings(transcript, 10.0, 13.0);
+        assert_eq!(words.len(), 3);
+        assert_eq!(words[0].text, "hello");
+        assert!(words[0].start.abs() < 1e-9);
+        assert!((words[2].end - 3.0).abs() < 1e-9);
+        assert!(words.windows(2).all(|w| w[0].end <= w[1].start + 1e-9));
+    }
+
+    #[test]
+    fn test_estimate_word_timings_last_line_fallback() {
+        let words = estimate_word_timings("[00:00:05] one two\n", 0.0, 60.0);
+        assert_eq!(words.len(), 2);
+        assert!((words[1].end - (5.0 + 2.0 * FALLBACK_SECONDS_PER_WORD)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_remap_drops_words_in_cuts() {
+        let segments = vec![
+            Segment {
+                start_ms: 0,
+                end_ms: 1000,
+                label: SegmentLabel::Keep,
+            },
+            Segment {
+                start_ms: 1000,
+                end_ms: 3000,
+                label: SegmentLabel::Cut,
+            },
+            Segment {
+                start_ms: 3000,
+                end_ms: 5000,
+                label: SegmentLabel::Keep,
+            },
+        ];
+        let input = words(&[("a", 0.2, 0.8), ("b", 1.5, 2.5), ("c", 3.5, 4.0)]);
+        let out = remap_words_through_segments(&input, &segments);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[1].text, "c");
+        assert!((out[1].start - 1.5).abs() < 1e-9);
+        assert!((out[1].end - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_group_words_breaks_on_gap_and_size() {
+        let input = words(&[
+            ("a", 0.0, 0.2),
+            ("b", 0.2, 0.4),
+            ("c", 0.4, 0.6),
+            ("d", 2.0, 2.2),
+        ]);
+        let groups = group_words(&input, 2);
+        let sizes: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+        assert_eq!(sizes, vec![2, 1, 1]);
+    }
+
+    #[test]
+    fn test_auto_placement_avoids_faces() {
+        let opts = CaptionOptions::default();
+        let low_faces = CaptionConfig::new(opts.clone()).with_face_band(0.6, 0.9);
+        assert_eq!(low_faces.anchor_y(), TOP_ANCHOR_Y);
+
+        let high_faces = CaptionConfig::new(opts.clone()).with_face_band(0.1, 0.4);
+        assert_eq!(high_faces.anchor_y(), BOTTOM_ANCHOR_Y);
+
+        let none = CaptionConfig::new(opts);
+        assert_eq!(none.anchor_y(), BOTTOM_ANCHOR_Y);
+    }
+
+    #[test]
+    fn test_split_style_uses_seam() {
+        let params = StreamerSplitParams {
+            split_ratio: Some(0.35),
+            ..Default::default()
+        };
+        let config = CaptionConfig::for_style(
+            CaptionOptions::default(),
+            Style::StreamerSplit,
+            Some(&params),
+            None,
+        );
+        assert!((config.anchor_y() - 0.35).abs() < 1e-6);
+
+        let split = CaptionConfig::for_style(CaptionOptions::default(), Style::Split, None, None);
+        assert_eq!(split.anchor_y(), 0.5);
+    }
+
+    #[test]
+    fn test_face_band_from_analysis() {
+        let mut analysis = SceneNeuralAnalysis::new("v", 1);
+        let mut frame = FrameAnalysis::new(0.0);
+        frame.faces.push(FaceDetection::new(
+            BoundingBox::new(0.4, 0.6, 0.2, 0.2),
+            0.9,
+        ));
+        analysis.frames.push(frame);
+        let (top, bottom) = face_band_from_analysis(&analysis).unwrap();
+        assert!((top - 0.6).abs() < 1e-6);
+        assert!((bottom - 0.8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_build_ass_document_presets() {
+        let input = words(&[("hi", 0.0, 0.5), ("there", 0.5, 1.0)]);
+
+        let highlight = build_ass_document(
+            &input,
+            &CaptionConfig::new(CaptionOptions::new(CaptionPreset::WordHighlight)),
+            1080,
+            1920,
+        );
+        assert_eq!(highlight.matches("Dialogue:").count(), 2);
+        assert!(highlight.contains("PlayResY: 1920"));
+        assert!(highlight.contains(INLINE_HIGHLIGHT));
+
+        let karaoke = build_ass_document(
+            &input,
+            &CaptionConfig::new(CaptionOptions::new(CaptionPreset::Karaoke)),
+            1080,
+            1920,
+        );
+        assert_eq!(karaoke.matches("Dialogue:").count(), 1);
+        assert!(karaoke.contains("{\\k50}HI"));
+
+        let block = build_ass_document(
+            &input,
+            &CaptionConfig::new(
+                CaptionOptions::new(CaptionPreset::TwoLineBlock)
+                    .with_max_words_per_line(1)
+                    .with_uppercase(false),
+            ),
+            1080,
+            1920,
+        );
+        assert!(block.contains("hi\\Nthere"));
+    }
+}
@@ -0,0 +1,197 @@
+//! Chat replay ingestion for stream VODs.
+//!
+//! On Twitch and Kick, bursts of chat messages mark the moments viewers
+//! reacted to. This module reads a chat replay from a local file (a chat
+//! downloader export, or a test fixture) into message times, which
+//! `excitement` turns into a chat-rate signal. Nothing is fetched over the
+//! network.
+//!
+//! Accepted layouts are a JSON object with a `comments` or `messages` array,
+//! a bare JSON array, or JSON Lines with one message per line. Each message
+//! is timed by the first field present:
+//!
+//! - `content_offset_seconds` or `offset_seconds`: seconds into the VOD
+//!   (Twitch exports)
+//! - `created_at`: RFC 3339 wall-clock time (Kick exports), relative to the
+//!   root `started_at`/`start_time` when present, else to the earliest message
+//!
+//! Messages without a usable time are skipped.
+
+use std::path::Path;
+
+use chrono::{DateTime, FixedOffset};
+use serde_json::Value;
+use tracing::debug;
+
+use crate::error::{MediaError, MediaResult};
+
+/// Root fields holding the message list.
+const MESSAGE_LIST_KEYS: [&str; 2] = ["comments", "messages"];
+
+/// Message fields holding an offset into the VOD in seconds.
+const OFFSET_KEYS: [&str; 2] = ["content_offset_seconds", "offset_seconds"];
+
+/// Root fields holding the broadcast start time.
+const START_TIME_KEYS: [&str; 2] = ["started_at", "start_time"];
+
+/// Chat messages of one VOD, as times from its start.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChatReplay {
+    /// Message times in seconds from the start of the VOD, sorted
+    pub message_times: Vec<f64>,
+}
+
+impl ChatReplay {
+    /// Parse a chat replay export.
+    pub fn parse(content: &str) -> MediaResult<Self> {
+        let trimmed = content.trim_start();
+        let (messages, start) = if trimmed.starts_with('{') && !is_json_lines(trimmed) {
+            let root: Value = serde_json::from_str(trimmed)?;
+            let messages = MESSAGE_LIST_KEYS
+                .iter()
+                .find_map(|key| root.get(key).and_then(Value::as_array))
+                .cloned()
+                .ok_or_else(|| {
+                    MediaError::UnsupportedFormat(
+                        "Chat replay object has no comments or messages array".to_string(),
+                    )
+                })?;
+            let start = START_TIME_KEYS
+                .iter()
+                .find_map(|key| root.get(key).and_then(Value::as_str))
+                .and_then(parse_time);
+            (messages, start)
+        } else if trimmed.starts_with('[') {
+            (serde_json::from_str::<Vec<Value>>(trimmed)?, None)
+        } else {
+            let messages = trimmed
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(serde_json::from_str)
+                .collect::<Result<Vec<Value>, _>>()?;
+            (messages, None)
+        };
+
+        let mut offsets: Vec<f64> = Vec::with_capacity(messages.len());
+        let mut stamps: Vec<DateTime<FixedOffset>> = Vec::new();
+        for message in &messages {
+            let offset = OFFSET_KEYS
+                .iter()
+                .find_map(|key| message.get(key).and_then(Value::as_f64));
+            match offset {
+                Some(offset) if offset.is_finite() && offset >= 0.0 => offsets.push(offset),
+                Some(_) => {}
+                None => {
+                    if let Some(time) = message
+                        .get("created_at")
+                        .and_then(Value::as_str)
+                        .and_then(parse_time)
+                    {
+                        stamps.push(time);
+                    }
+                }
+            }
+        }
+
+        if let Some(origin) = start.or_else(|| stamps.iter().min().copied()) {
+            offsets.extend(
+                stamps
+                    .iter()
+                    .map(|time| (*time - origin).num_milliseconds() as f64 / 1000.0)
+                    .filter(|offset| *offset >= 0.0),
+            );
+        }
+        offsets.sort_by(f64::total_cmp);
+
+        debug!(
+            messages = messages.len(),
+            timed = offsets.len(),
+            "Parsed chat replay"
+        );
+        Ok(Self {
+            message_times: offsets,
+        })
+    }
+
+    /// Read and parse a chat replay file.
+    pub async fn load(path: &Path) -> MediaResult<Self> {
+        if !path.exists() {
+            return Err(MediaError::FileNotFound(path.to_path_buf()));
+        }
+        let content = tokio::fs::read_to_string(path).await?;
+        Self::parse(&content)
+    }
+
+    /// Number of timed messages.
+    pub fn len(&self) -> usize {
+        self.message_times.len()
+    }
+
+    /// Whether no message could be timed.
+    pub fn is_empty(&self) -> bool {
+        self.message_times.is_empty()
+    }
+}
+
+/// Whether an object-looking document is really JSON Lines.
+fn is_json_lines(content: &str) -> bool {
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+    lines.next().is_some_and(|first| {
+        serde_json::from_str::<Value>(first).is_ok() && lines.next().is_some()
+    })
+}
+
+fn parse_time(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_twitch_export() {
+        let content = r#"{
+            "video": {"id": "2101234567"},
+            "comments": [
+                {"content_offset_seconds": 12.5, "message": {"body": "PogChamp"}},
+                {"content_offset_seconds": 3.0, "message": {"body": "hi"}},
+                {"message": {"body": "no time"}}
+            ]
+        }"#;
+        let replay = ChatReplay::parse(content).unwrap();
+        assert_eq!(replay.message_times, vec![3.0, 12.5]);
+    }
+
+    #[test]
+    fn test_parse_kick_export_relative_to_start() {
+        let content = r#"{
+            "started_at": "2026-03-01T20:00:00Z",
+            "messages": [
+                {"created_at": "2026-03-01T20:00:30Z", "content": "W"},
+                {"created_at": "2026-03-01T21:00:00.500+01:00", "content": "LUL"},
+                {"created_at": "2026-03-01T19:59:00Z", "content": "before start"}
+            ]
+        }"#;
+        let replay = ChatReplay::parse(content).unwrap();
+        assert_eq!(replay.message_times, vec![0.5, 30.0]);
+    }
+
+    #[test]
+    fn test_parse_array_and_json_lines() {
+        let array = r#"[{"offset_seconds": 2}, {"offset_seconds": 1}]"#;
+        assert_eq!(ChatReplay::parse(array).unwrap().message_times, vec![1.0, 2.0]);
+
+        // Without a start time, wall-clock messages count from the earliest one
+        let lines = "{\"created_at\": \"2026-03-01T20:00:10Z\"}\n\n{\"created_at\": \"2026-03-01T20:00:00Z\"}\n";
+        let replay = ChatReplay::parse(lines).unwrap();
+        assert_eq!(replay.message_times, vec![0.0, 10.0]);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_layout() {
+        assert!(ChatReplay::parse(r#"{"chat": []}"#).is_err());
+        assert!(ChatReplay::parse("not json").is_err());
+        assert!(ChatReplay::parse("[]").unwrap().is_empty());
+    }
+}
@@ -27,6 +27,7 @@ use vclip_models::{ClipTask, CropMode, EncodingConfig, Style};
 use crate::command::{FfmpegCommand, FfmpegRunner};
 use crate::error::{MediaError, MediaResult};
 use crate::filters::build_video_filter;
+use crate::intelligent::OutputFormat;
 use crate::progress::FfmpegProgress;
 use crate::thumbnail::generate_thumbnail;
 use crate::watermark::{build_vf_with_watermark, WatermarkConfig};
@@ -218,7 +219,7 @@ where
 
         // Traditional styles (Split, LeftFocus, CenterFocus, RightFocus)
         _ => {
-            let filter = build_video_filter(task.style);
+            let filter = build_video_filter(task.style, &OutputFormat::for_task(task));
             create_basic_clip(
                 input,
                 output,
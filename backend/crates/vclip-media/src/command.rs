@@ -100,6 +100,9 @@ mod cpu_affinity {
 /// - **Linux**: Uses `taskset -c <cores> ffmpeg ...`
 /// - **macOS/Windows**: Returns plain `ffmpeg` command (no affinity support)
 ///
+/// The process is killed when its handle is dropped, so cancelling the future
+/// that awaits it (e.g. a cancelled job) also stops FFmpeg.
+///
 /// # Example
 ///
 /// ```bash
@@ -110,12 +113,17 @@ pub fn create_ffmpeg_command() -> Command {
     #[cfg(target_os = "linux")]
     if let Some(affinity) = cpu_affinity::get() {
         let mut cmd = Command::new("taskset");
-        cmd.arg("-c").arg(&affinity.cores).arg("ffmpeg");
+        cmd.arg("-c")
+            .arg(&affinity.cores)
+            .arg("ffmpeg")
+            .kill_on_drop(true);
         debug!("FFmpeg spawned with CPU affinity: cores {}", affinity.cores);
         return cmd;
     }
 
-    Command::new("ffmpeg")
+    let mut cmd = Command::new("ffmpeg");
+    cmd.kill_on_drop(true);
+    cmd
 }
 
 /// Builder for FFmpeg commands.
@@ -361,39 +369,37 @@ impl FfmpegRunner {
     }
 
     /// Wait for child process with cancellation and timeout.
+    ///
+    /// Cancellation and timeout kill the process as soon as they fire.
     async fn wait_for_completion(&self, child: &mut Child) -> MediaResult<()> {
-        let wait_future = child.wait();
-
-        // Apply timeout if set
-        let wait_future = if let Some(timeout_secs) = self.timeout_secs {
-            let timeout =
-                tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), wait_future);
-            match timeout.await {
-                Ok(result) => result,
-                Err(_) => {
-                    // Timeout - kill the process
-                    warn!(
-                        "FFmpeg timed out after {} seconds, killing process",
-                        timeout_secs
-                    );
-                    let _ = child.kill().await;
-                    return Err(MediaError::Timeout(timeout_secs));
-                }
-            }
-        } else {
-            wait_future.await
+        enum Outcome {
+            Exited(std::io::Result<std::process::ExitStatus>),
+            Cancelled,
+            TimedOut(u64),
+        }
+
+        let outcome = tokio::select! {
+            status = child.wait() => Outcome::Exited(status),
+            _ = cancelled(self.cancel_rx.clone()) => Outcome::Cancelled,
+            secs = timed_out(self.timeout_secs) => Outcome::TimedOut(secs),
         };
 
-        // Check cancellation
-        if let Some(ref cancel_rx) = self.cancel_rx {
-            if *cancel_rx.borrow() {
+        let status = match outcome {
+            Outcome::Exited(status) => status?,
+            Outcome::Cancelled => {
                 info!("FFmpeg cancelled, killing process");
                 let _ = child.kill().await;
                 return Err(MediaError::Cancelled);
             }
-        }
-
-        let status = wait_future?;
+            Outcome::TimedOut(timeout_secs) => {
+                warn!(
+                    "FFmpeg timed out after {} seconds, killing process",
+                    timeout_secs
+                );
+                let _ = child.kill().await;
+                return Err(MediaError::Timeout(timeout_secs));
+            }
+        };
 
         if status.success() {
             Ok(())
@@ -407,6 +413,27 @@ impl FfmpegRunner {
     }
 }
 
+/// Resolve once cancellation is signalled; never resolves without a signal.
+async fn cancelled(cancel_rx: Option<watch::Receiver<bool>>) {
+    if let Some(mut rx) = cancel_rx {
+        if rx.wait_for(|cancelled| *cancelled).await.is_ok() {
+            return;
+        }
+    }
+    std::future::pending().await
+}
+
+/// Resolve with the timeout once it elapses; never resolves without one.
+async fn timed_out(timeout_secs: Option<u64>) -> u64 {
+    match timeout_secs {
+        Some(secs) => {
+            tokio::time::sleep(std::time::Duration::from_secs(secs)).await;
+            secs
+        }
+        None => std::future::pending().await,
+    }
+}
+
 /// Parse a progress line from FFmpeg's -progress output.
 fn parse_progress_line(line: &str, current: &mut FfmpegProgress) -> Option<FfmpegProgress> {
     let line = line.trim();
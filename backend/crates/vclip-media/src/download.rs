@@ -3,6 +3,9 @@
 //! This module provides functions to download videos and segments from YouTube
 //! and other platforms using yt-dlp. Includes IPv6 rotation support for
 //! avoiding rate limiting.
+//!
+//! Twitch and Kick VODs and clips skip the YouTube-specific client, cookie
+//! and IPv6 settings and download their muxed HLS renditions directly.
 
 use std::path::Path;
 use std::process::Stdio;
@@ -10,10 +13,14 @@ use std::sync::OnceLock;
 use tokio::process::Command;
 use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
+use vclip_models::is_stream_platform_url;
 
 use crate::error::{MediaError, MediaResult};
 use crate::ipv6_rotation::{get_random_ipv6_address, record_ipv6_failure, record_ipv6_success};
 
+/// Format selection for Twitch and Kick, which serve muxed HLS renditions.
+const STREAM_PLATFORM_FORMAT: &str = "best[ext=mp4]/best";
+
 /// Minimum video file size threshold (50MB) to consider download complete.
 const MIN_VIDEO_FILE_SIZE: u64 = 50 * 1024 * 1024;
 
@@ -187,37 +194,51 @@ pub async fn download_video(url: &str, output_path: impl AsRef<Path>) -> MediaRe
         output_path.display()
     );
 
+    let stream_platform = is_stream_platform_url(url);
+
     // Use cookies file if available for YouTube authentication (copy to writable location)
-    let cookies_path = get_writable_cookies_path().await;
+    let cookies_path = if stream_platform {
+        None
+    } else {
+        get_writable_cookies_path().await
+    };
     let output_path_str = output_path.to_string_lossy();
 
-    let mut args = vec![
-        "--verbose",
-        "--remote-components", "ejs:github",
-        "--sleep-subtitles", "5",
-        "--sleep-requests", "0.75", 
-        "--sleep-interval", "10",
-        "--max-sleep-interval", "20",
-        "--user-agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
-        "--add-header", "Accept:text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
-        "--add-header", "Accept-Language:en-US,en;q=0.5",
-        "--add-header", "Accept-Encoding:gzip, deflate",
-        "--add-header", "DNT:1",
-        "--add-header", "Connection:keep-alive",
-        "--add-header", "Upgrade-Insecure-Requests:1",
-        "--limit-rate", "2M",
-        "--concurrent-fragments", "1",
-        "--extractor-args", "youtube:player_client=web",
-        "--force-ipv6",
-        "-f", "bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]/best",
-        "-o",
-    ];
+    let mut args = if stream_platform {
+        vec!["--verbose", "-f", STREAM_PLATFORM_FORMAT, "-o"]
+    } else {
+        vec![
+            "--verbose",
+            "--remote-components", "ejs:github",
+            "--sleep-subtitles", "5",
+            "--sleep-requests", "0.75", 
+            "--sleep-interval", "10",
+            "--max-sleep-interval", "20",
+            "--user-agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+            "--add-header", "Accept:text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+            "--add-header", "Accept-Language:en-US,en;q=0.5",
+            "--add-header", "Accept-Encoding:gzip, deflate",
+            "--add-header", "DNT:1",
+            "--add-header", "Connection:keep-alive",
+            "--add-header", "Upgrade-Insecure-Requests:1",
+            "--limit-rate", "2M",
+            "--concurrent-fragments", "1",
+            "--extractor-args", "youtube:player_client=web",
+            "--force-ipv6",
+            "-f", "bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]/best",
+            "-o",
+        ]
+    };
 
     args.push(&output_path_str);
 
     // IPv6 rotation: select random source address if available
     // Uses cached address pool from ipv6_rotation module
-    let ipv6_source = get_random_ipv6_address();
+    let ipv6_source = if stream_platform {
+        None
+    } else {
+        get_random_ipv6_address()
+    };
     let ipv6_ref = ipv6_source.as_deref();
     if let Some(ip) = ipv6_ref {
         args.push("--source-address");
@@ -234,6 +255,7 @@ pub async fn download_video(url: &str, output_path: impl AsRef<Path>) -> MediaRe
 
     let output = Command::new("yt-dlp")
         .args(&args)
+        .kill_on_drop(true)
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -305,6 +327,7 @@ pub fn is_supported_url(url: &str) -> bool {
         "twitter.com",
         "x.com",
         "twitch.tv",
+        "kick.com",
         "tiktok.com",
     ];
 
@@ -364,50 +387,68 @@ pub async fn download_segment(
         "Attempting segment download with yt-dlp --download-sections"
     );
 
+    let stream_platform = is_stream_platform_url(url);
+
     // Use cookies file if available for YouTube authentication (copy to writable location)
-    let cookies_path = get_writable_cookies_path().await;
+    let cookies_path = if stream_platform {
+        None
+    } else {
+        get_writable_cookies_path().await
+    };
     let output_path_str = output_path.to_string_lossy();
 
-    let mut args = vec![
-        "--remote-components".to_string(),
-        "ejs:github".to_string(),
-        "--sleep-subtitles".to_string(),
-        "5".to_string(),
-        "--sleep-requests".to_string(),
-        "0.75".to_string(),
-        "--sleep-interval".to_string(),
-        "10".to_string(),
-        "--max-sleep-interval".to_string(),
-        "20".to_string(),
-        "--user-agent".to_string(),
-        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36".to_string(),
-        "--add-header".to_string(),
-        "Accept:text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8".to_string(),
-        "--add-header".to_string(),
-        "Accept-Language:en-US,en;q=0.5".to_string(),
-        "--add-header".to_string(),
-        "Accept-Encoding:gzip, deflate".to_string(),
-        "--add-header".to_string(),
-        "DNT:1".to_string(),
-        "--add-header".to_string(),
-        "Connection:keep-alive".to_string(),
-        "--add-header".to_string(),
-        "Upgrade-Insecure-Requests:1".to_string(),
-        "--limit-rate".to_string(),
-        "2M".to_string(),
-        "--concurrent-fragments".to_string(),
-        "1".to_string(),
-        "--extractor-args".to_string(),
-        "youtube:player_client=web".to_string(),
-        "--force-ipv6".to_string(),
-        "--download-sections".to_string(),
-        section_arg,
-        // Prefer HLS format which supports segment downloads
-        "-f".to_string(),
-        "bestvideo[ext=mp4][protocol=m3u8_native]+bestaudio[ext=m4a][protocol=m3u8_native]/bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]/best".to_string(),
-        "-o".to_string(),
-        output_path_str.to_string(),
-    ];
+    let mut args = if stream_platform {
+        // Twitch and Kick VODs are HLS, so sections download without fetching the whole VOD
+        vec![
+            "--download-sections".to_string(),
+            section_arg,
+            "-f".to_string(),
+            STREAM_PLATFORM_FORMAT.to_string(),
+            "-o".to_string(),
+            output_path_str.to_string(),
+        ]
+    } else {
+        vec![
+            "--remote-components".to_string(),
+            "ejs:github".to_string(),
+            "--sleep-subtitles".to_string(),
+            "5".to_string(),
+            "--sleep-requests".to_string(),
+            "0.75".to_string(),
+            "--sleep-interval".to_string(),
+            "10".to_string(),
+            "--max-sleep-interval".to_string(),
+            "20".to_string(),
+            "--user-agent".to_string(),
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36".to_string(),
+            "--add-header".to_string(),
+            "Accept:text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8".to_string(),
+            "--add-header".to_string(),
+            "Accept-Language:en-US,en;q=0.5".to_string(),
+            "--add-header".to_string(),
+            "Accept-Encoding:gzip, deflate".to_string(),
+            "--add-header".to_string(),
+            "DNT:1".to_string(),
+            "--add-header".to_string(),
+            "Connection:keep-alive".to_string(),
+            "--add-header".to_string(),
+            "Upgrade-Insecure-Requests:1".to_string(),
+            "--limit-rate".to_string(),
+            "2M".to_string(),
+            "--concurrent-fragments".to_string(),
+            "1".to_string(),
+            "--extractor-args".to_string(),
+            "youtube:player_client=web".to_string(),
+            "--force-ipv6".to_string(),
+            "--download-sections".to_string(),
+            section_arg,
+            // Prefer HLS format which supports segment downloads
+            "-f".to_string(),
+            "bestvideo[ext=mp4][protocol=m3u8_native]+bestaudio[ext=m4a][protocol=m3u8_native]/bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]/best".to_string(),
+            "-o".to_string(),
+            output_path_str.to_string(),
+        ]
+    };
 
     // Add force-keyframes for accurate cuts (re-encodes, slower but more accurate)
     if force_keyframes {
@@ -416,7 +457,11 @@ pub async fn download_segment(
 
     // IPv6 rotation: select random source address if available
     // Uses cached address pool from ipv6_rotation module
-    let ipv6_source = get_random_ipv6_address();
+    let ipv6_source = if stream_platform {
+        None
+    } else {
+        get_random_ipv6_address()
+    };
     let using_ipv6 = ipv6_source.is_some();
     if let Some(ip) = &ipv6_source {
         args.push("--source-address".to_string());
@@ -432,6 +477,7 @@ pub async fn download_segment(
 
     let output = Command::new("yt-dlp")
         .args(&args)
+        .kill_on_drop(true)
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -496,10 +542,11 @@ pub async fn download_segment(
 /// Check if a URL likely supports segment downloads (HLS).
 ///
 /// This is a heuristic check - actual support depends on the video.
-/// YouTube typically supports HLS for most videos.
+/// YouTube typically supports HLS for most videos; Twitch and Kick VODs and
+/// clips are always HLS.
 pub fn likely_supports_segment_download(url: &str) -> bool {
     // YouTube generally supports HLS
-    url.contains("youtube.com") || url.contains("youtu.be")
+    url.contains("youtube.com") || url.contains("youtu.be") || is_stream_platform_url(url)
 }
 
 #[cfg(test)]
@@ -512,9 +559,18 @@ mod tests {
         assert!(is_supported_url("https://youtube.com/watch?v=abc"));
         assert!(is_supported_url("https://youtu.be/abc"));
         assert!(is_supported_url("https://vimeo.com/123"));
+        assert!(is_supported_url("https://kick.com/streamer/clips/clip_01HXYZ"));
         assert!(!is_supported_url("https://example.com/video"));
     }
 
+    #[test]
+    fn test_likely_supports_segment_download() {
+        assert!(likely_supports_segment_download("https://youtu.be/abc"));
+        assert!(likely_supports_segment_download("https://www.twitch.tv/videos/2101234567"));
+        assert!(likely_supports_segment_download("https://clips.twitch.tv/FunnyClipSlug"));
+        assert!(!likely_supports_segment_download("https://vimeo.com/123"));
+    }
+
     #[test]
     fn test_extract_youtube_id() {
         use vclip_models::YoutubeIdError;
@@ -0,0 +1,927 @@
+//! Signal-based highlight detection.
+//!
+//! Ranks moments by audio/visual excitement instead of transcript content,
+//! for videos (gaming streams, sports) whose transcript is empty or says
+//! little. Four per-second signals are combined:
+//!
+//! - **Loudness** from EBU R128 momentary loudness (`audio::measure_loudness_timeline`)
+//! - **Speech density** from Silero VAD (`silence_removal::analyze_audio_segments`)
+//! - **Shot-cut frequency** from histogram shot detection (`cinematic::ShotDetector`)
+//! - **Motion** from downscaled frame differencing (`intelligent::motion`)
+//!
+//! Stream VODs with a chat replay (`chat_replay`) add a fifth signal, the
+//! chat message rate, shifted back by a typical reaction delay.
+//!
+//! Each signal is z-normalized against the whole video, so "exciting" always
+//! means "more than usual for this video". Signals that cannot be extracted
+//! (no audio stream, VAD failure) are dropped and the remaining weights
+//! renormalized.
+//!
+//! # Architecture
+//!
+//! - `SignalTimeline`: Raw per-second signals for a whole video
+//! - `extract_signals`: Builds the timeline with FFmpeg and the VAD
+//! - `ExcitementProfile`: Normalized, weighted per-second excitement
+//! - `ExcitementProfile::candidates`: Ranked non-overlapping highlight windows
+//! - `ExcitementProfile::score_range`: Scores an arbitrary range (for fusing with LLM picks)
+
+use std::path::Path;
+use std::process::Stdio;
+
+use tokio::io::AsyncReadExt;
+use tracing::{debug, info, warn};
+use vclip_models::{format_seconds, Highlight};
+
+use crate::audio::measure_loudness_timeline;
+use crate::chat_replay::ChatReplay;
+use crate::error::{MediaError, MediaResult};
+use crate::intelligent::cinematic::ShotDetector;
+use crate::intelligent::motion::{changed_pixel_fraction, rgb_to_gray, MOTION_PIXEL_THRESHOLD};
+use crate::silence_removal::{analyze_audio_segments, Segment, SegmentLabel, SilenceRemovalConfig};
+
+/// Sampled frame width for shot and motion signals.
+const FRAME_WIDTH: u32 = 160;
+
+/// Sampled frame height for shot and motion signals.
+const FRAME_HEIGHT: u32 = 90;
+
+/// Histograms held in memory at once; shot detection runs per chunk.
+const SHOT_CHUNK_FRAMES: usize = 1200;
+
+/// Window over which shot cuts and chat messages are counted into a rate (seconds).
+const EVENT_RATE_WINDOW_SECS: usize = 10;
+
+/// Typical delay between a moment and chat reacting to it (seconds).
+const CHAT_REACTION_DELAY_SECS: f64 = 5.0;
+
+/// Z-scores are clamped to this magnitude so one spike cannot dominate.
+const MAX_Z: f64 = 3.0;
+
+/// Step between candidate window lengths (seconds).
+const WINDOW_STEP_SECS: usize = 5;
+
+/// Mean z-score a signal needs within a window to be named in its reason.
+const REASON_MIN_Z: f64 = 0.5;
+
+/// Relative weight of each signal in the combined score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExcitementWeights {
+    /// Audio loudness
+    pub loudness: f64,
+    /// Speech density
+    pub speech: f64,
+    /// Shot-cut frequency
+    pub shot_cuts: f64,
+    /// On-screen motion
+    pub motion: f64,
+    /// Chat message rate (stream VODs with a chat replay)
+    pub chat: f64,
+}
+
+impl Default for ExcitementWeights {
+    fn default() -> Self {
+        Self {
+            loudness: 0.4,
+            speech: 0.15,
+            shot_cuts: 0.2,
+            motion: 0.25,
+            // Only counts when a chat replay is available
+            chat: 0.35,
+        }
+    }
+}
+
+/// Configuration for excitement detection.
+#[derive(Debug, Clone)]
+pub struct ExcitementConfig {
+    /// Signal weights
+    pub weights: ExcitementWeights,
+    /// Shortest candidate (seconds)
+    pub min_duration_secs: f64,
+    /// Longest candidate (seconds)
+    pub max_duration_secs: f64,
+    /// Preferred candidate length; other lengths are slightly penalized (seconds)
+    pub target_duration_secs: f64,
+    /// Maximum number of candidates returned
+    pub max_candidates: usize,
+    /// Frame sample rate for shot and motion signals
+    pub sample_fps: f64,
+}
+
+impl Default for ExcitementConfig {
+    fn default() -> Self {
+        Self {
+            weights: ExcitementWeights::default(),
+            min_duration_secs: 20.0,
+            max_duration_secs: 60.0,
+            target_duration_secs: 35.0,
+            max_candidates: 8,
+            sample_fps: 2.0,
+        }
+    }
+}
+
+impl ExcitementConfig {
+    /// Set the signal weights.
+    pub fn with_weights(mut self, weights: ExcitementWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Set the candidate length range; the target length is clamped into it.
+    pub fn with_duration_range(mut self, min_secs: f64, max_secs: f64) -> Self {
+        self.min_duration_secs = min_secs.max(1.0);
+        self.max_duration_secs = max_secs.max(self.min_duration_secs);
+        self.target_duration_secs = self
+            .target_duration_secs
+            .clamp(self.min_duration_secs, self.max_duration_secs);
+        self
+    }
+
+    /// Set the maximum number of candidates.
+    pub fn with_max_candidates(mut self, max_candidates: usize) -> Self {
+        self.max_candidates = max_candidates;
+        self
+    }
+}
+
+/// Raw per-second signals for a video.
+///
+/// Per-second vectors are indexed by whole seconds; missing seconds are `NaN`.
+/// An empty vector means the signal is unavailable.
+#[derive(Debug, Clone, Default)]
+pub struct SignalTimeline {
+    /// Video duration (seconds)
+    pub duration: f64,
+    /// Momentary loudness per second (LUFS)
+    pub loudness: Vec<f64>,
+    /// Fraction of each second containing speech (0-1)
+    pub speech: Vec<f64>,
+    /// Fraction of pixels changing between sampled frames, per second (0-1)
+    pub motion: Vec<f64>,
+    /// Shot cut times (seconds)
+    pub shot_cuts: Vec<f64>,
+    /// Chat message times (seconds)
+    pub chat_messages: Vec<f64>,
+}
+
+impl SignalTimeline {
+    /// Create an empty timeline for a video of `duration` seconds.
+    pub fn new(duration: f64) -> Self {
+        Self {
+            duration: duration.max(0.0),
+            ..Default::default()
+        }
+    }
+
+    /// Set loudness from `(second, LUFS)` samples.
+    pub fn with_loudness_samples(mut self, samples: &[(f64, f64)]) -> Self {
+        let mut loudness = vec![f64::NAN; self.seconds()];
+        for &(time, value) in samples {
+            if let Some(slot) = loudness.get_mut(time.max(0.0) as usize) {
+                *slot = value;
+            }
+        }
+        self.loudness = loudness;
+        self
+    }
+
+    /// Set speech density from VAD segments (speech is `Keep`).
+    pub fn with_speech_segments(mut self, segments: &[Segment]) -> Self {
+        let mut speech = vec![0.0; self.seconds()];
+        for segment in segments.iter().filter(|s| s.label == SegmentLabel::Keep) {
+            let start = segment.start_ms as f64 / 1000.0;
+            let end = segment.end_ms as f64 / 1000.0;
+            let first = start.floor() as usize;
+            let last = (end.ceil() as usize).min(speech.len());
+            for (second, slot) in speech.iter_mut().enumerate().take(last).skip(first) {
+                let overlap = end.min(second as f64 + 1.0) - start.max(second as f64);
+                if overlap > 0.0 {
+                    *slot += overlap;
+                }
+            }
+        }
+        speech.iter_mut().for_each(|s| *s = s.min(1.0));
+        self.speech = speech;
+        self
+    }
+
+    /// Set chat message times from a chat replay.
+    pub fn with_chat_replay(mut self, replay: &ChatReplay) -> Self {
+        self.chat_messages = replay.message_times.clone();
+        self
+    }
+
+    /// Number of whole seconds covered.
+    pub fn seconds(&self) -> usize {
+        self.duration.ceil() as usize
+    }
+
+    /// Whether no signal was extracted.
+    pub fn is_empty(&self) -> bool {
+        self.loudness.is_empty()
+            && self.speech.is_empty()
+            && self.motion.is_empty()
+            && self.shot_cuts.is_empty()
+            && self.chat_messages.is_empty()
+    }
+}
+
+/// Mean z-score of each signal over a range.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SignalBreakdown {
+    /// Audio loudness
+    pub loudness: f64,
+    /// Speech density
+    pub speech: f64,
+    /// Shot-cut frequency
+    pub shot_cuts: f64,
+    /// On-screen motion
+    pub motion: f64,
+    /// Chat message rate
+    pub chat: f64,
+}
+
+/// A ranked highlight proposal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExcitementCandidate {
+    /// Start (seconds)
+    pub start: f64,
+    /// End (seconds)
+    pub end: f64,
+    /// Excitement score (0-1, 0.5 is the video average)
+    pub score: f64,
+    /// Per-signal contribution
+    pub breakdown: SignalBreakdown,
+    /// Human-readable reason
+    pub reason: String,
+}
+
+impl ExcitementCandidate {
+    /// Duration in seconds.
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+
+    /// Convert to a highlight with the given ID.
+    pub fn to_highlight(&self, id: u32) -> Highlight {
+        let mut highlight = Highlight::new(
+            id,
+            format!("Excitement peak at {}", format_seconds(self.start)),
+            format_seconds(self.start),
+            format_seconds(self.end),
+        )
+        .with_calculated_duration();
+        highlight.reason = Some(self.reason.clone());
+        highlight
+    }
+}
+
+/// Normalized, weighted per-second excitement for a video.
+#[derive(Debug, Clone, Default)]
+pub struct ExcitementProfile {
+    weights: ExcitementWeights,
+    loudness: Option<Vec<f64>>,
+    speech: Option<Vec<f64>>,
+    shot_cuts: Option<Vec<f64>>,
+    motion: Option<Vec<f64>>,
+    chat: Option<Vec<f64>>,
+    combined: Vec<f64>,
+    cut_times: Vec<f64>,
+    chat_times: Vec<f64>,
+}
+
+impl ExcitementProfile {
+    /// Normalize and combine a timeline's signals.
+    pub fn new(timeline: &SignalTimeline, weights: ExcitementWeights) -> Self {
+        let len = timeline.seconds();
+        let loudness = z_scores(&timeline.loudness, len);
+        let speech = z_scores(&timeline.speech, len);
+        let shot_cuts = if timeline.shot_cuts.is_empty() {
+            None
+        } else {
+            z_scores(&event_rate(&timeline.shot_cuts, len), len)
+        };
+        let motion = z_scores(&timeline.motion, len);
+        // Credit chat bursts to the moment viewers were reacting to
+        let chat_times: Vec<f64> = timeline
+            .chat_messages
+            .iter()
+            .map(|t| (t - CHAT_REACTION_DELAY_SECS).max(0.0))
+            .collect();
+        let chat = if chat_times.is_empty() {
+            None
+        } else {
+            z_scores(&event_rate(&chat_times, len), len)
+        };
+
+        let signals = [
+            (&loudness, weights.loudness),
+            (&speech, weights.speech),
+            (&shot_cuts, weights.shot_cuts),
+            (&motion, weights.motion),
+            (&chat, weights.chat),
+        ];
+        let total_weight: f64 = signals
+            .iter()
+            .filter(|(z, _)| z.is_some())
+            .map(|(_, w)| w.max(0.0))
+            .sum();
+
+        let mut combined = vec![0.0; len];
+        if total_weight > 0.0 {
+            for (z, weight) in signals {
+                if let Some(z) = z {
+                    for (slot, value) in combined.iter_mut().zip(z) {
+                        *slot += weight.max(0.0) * value / total_weight;
+                    }
+                }
+            }
+        }
+
+        Self {
+            weights,
+            loudness,
+            speech,
+            shot_cuts,
+            motion,
+            chat,
+            combined,
+            cut_times: timeline.shot_cuts.clone(),
+            chat_times,
+        }
+    }
+
+    /// Number of whole seconds covered.
+    pub fn len(&self) -> usize {
+        self.combined.len()
+    }
+
+    /// Whether the profile covers no time.
+    pub fn is_empty(&self) -> bool {
+        self.combined.is_empty()
+    }
+
+    /// Excitement score of `[start, end)` (0-1, 0.5 is the video average).
+    pub fn score_range(&self, start: f64, end: f64) -> f64 {
+        let (first, last) = self.second_range(start, end);
+        normalize_score(mean(&self.combined[first..last]))
+    }
+
+    /// Mean z-score of each signal over `[start, end)`.
+    pub fn breakdown(&self, start: f64, end: f64) -> SignalBreakdown {
+        let (first, last) = self.second_range(start, end);
+        let avg = |z: &Option<Vec<f64>>| z.as_ref().map_or(0.0, |z| mean(&z[first..last]));
+        SignalBreakdown {
+            loudness: avg(&self.loudness),
+            speech: avg(&self.speech),
+            shot_cuts: avg(&self.shot_cuts),
+            motion: avg(&self.motion),
+            chat: avg(&self.chat),
+        }
+    }
+
+    /// Propose the most exciting non-overlapping windows, best first.
+    ///
+    /// Windows overlapping any `exclude` range are skipped. Only windows more
+    /// exciting than the video average are returned.
+    pub fn candidates(
+        &self,
+        config: &ExcitementConfig,
+        exclude: &[(f64, f64)],
+    ) -> Vec<ExcitementCandidate> {
+        let len = self.len();
+        let min_len = config.min_duration_secs.ceil().max(1.0) as usize;
+        let max_len = (config.max_duration_secs.floor() as usize).max(min_len);
+        let target = config.target_duration_secs.max(1.0);
+        if len < min_len || config.max_candidates == 0 {
+            return Vec::new();
+        }
+
+        let mut prefix = vec![0.0; len + 1];
+        for (i, value) in self.combined.iter().enumerate() {
+            prefix[i + 1] = prefix[i] + value;
+        }
+
+        // Best window starting at each second
+        let mut windows: Vec<(usize, usize, f64)> = Vec::new();
+        for first in 0..=len - min_len {
+            let mut best: Option<(usize, usize, f64)> = None;
+            let mut length = min_len;
+            while length <= max_len && first + length <= len {
+                let mean = (prefix[first + length] - prefix[first]) / length as f64;
+                let shape = 1.0 - 0.2 * ((length as f64 - target).abs() / target);
+                let score = mean * shape;
+                if best.is_none_or(|(_, _, b)| score > b) {
+                    best = Some((first, first + length, score));
+                }
+                length += WINDOW_STEP_SECS;
+            }
+            if let Some(window) = best.filter(|w| w.2 > 0.0) {
+                windows.push(window);
+            }
+        }
+
+        windows.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.0.cmp(&b.0)));
+
+        let mut selected: Vec<(f64, f64)> = Vec::new();
+        for (first, last, _) in windows {
+            if selected.len() >= config.max_candidates {
+                break;
+            }
+            let (start, end) = (first as f64, last as f64);
+            let blocked = exclude
+                .iter()
+                .chain(selected.iter())
+                .any(|&(s, e)| start < e && s < end);
+            if !blocked {
+                selected.push((start, end));
+            }
+        }
+
+        let mut candidates: Vec<ExcitementCandidate> = selected
+            .into_iter()
+            .map(|(start, end)| {
+                let breakdown = self.breakdown(start, end);
+                ExcitementCandidate {
+                    start,
+                    end,
+                    score: self.score_range(start, end),
+                    reason: self.describe(&breakdown, start, end),
+                    breakdown,
+                }
+            })
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(a.start.total_cmp(&b.start))
+        });
+        candidates
+    }
+
+    /// Clamp a time range to whole-second indices `first..last` (never empty).
+    fn second_range(&self, start: f64, end: f64) -> (usize, usize) {
+        let len = self.len();
+        if len == 0 {
+            return (0, 0);
+        }
+        let first = (start.max(0.0).floor() as usize).min(len - 1);
+        let last = (end.max(0.0).ceil() as usize).clamp(first + 1, len);
+        (first, last)
+    }
+
+    /// Name the signals that make a window stand out, strongest first.
+    fn describe(&self, breakdown: &SignalBreakdown, start: f64, end: f64) -> String {
+        let weights = &self.weights;
+        let mut parts: Vec<(f64, String)> = Vec::new();
+        if breakdown.chat >= REASON_MIN_Z {
+            let messages = self
+                .chat_times
+                .iter()
+                .filter(|&&t| t >= start && t < end)
+                .count();
+            let per_minute = messages as f64 * 60.0 / (end - start).max(1.0);
+            parts.push((
+                weights.chat * breakdown.chat,
+                format!("chat spike ({:.0} msgs/min)", per_minute),
+            ));
+        }
+        if breakdown.loudness >= REASON_MIN_Z {
+            parts.push((
+                weights.loudness * breakdown.loudness,
+                format!("loud audio (+{:.1}σ)", breakdown.loudness),
+            ));
+        }
+        if breakdown.motion >= REASON_MIN_Z {
+            parts.push((
+                weights.motion * breakdown.motion,
+                "high on-screen motion".to_string(),
+            ));
+        }
+        if breakdown.shot_cuts >= REASON_MIN_Z {
+            let cuts = self
+                .cut_times
+                .iter()
+                .filter(|&&t| t >= start && t < end)
+                .count();
+            let per_minute = cuts as f64 * 60.0 / (end - start).max(1.0);
+            parts.push((
+                weights.shot_cuts * breakdown.shot_cuts,
+                format!("rapid cuts ({:.0}/min)", per_minute),
+            ));
+        }
+        if breakdown.speech >= REASON_MIN_Z {
+            parts.push((
+                weights.speech * breakdown.speech,
+                "dense speech".to_string(),
+            ));
+        }
+
+        if parts.is_empty() {
+            return "Above-average audio and visual activity".to_string();
+        }
+        parts.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let parts: Vec<String> = parts.into_iter().map(|(_, text)| text).collect();
+        format!("Excitement peak: {}", parts.join(", "))
+    }
+}
+
+/// Mean of a slice (0 when empty).
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Map a mean z-score to 0-1 (0 maps to 0.5).
+fn normalize_score(z: f64) -> f64 {
+    1.0 / (1.0 + (-2.0 * z).exp())
+}
+
+/// Z-normalize a per-second signal to `len` seconds.
+///
+/// Non-finite and missing seconds become 0 (average). Returns `None` when the
+/// signal is unavailable or constant.
+fn z_scores(values: &[f64], len: usize) -> Option<Vec<f64>> {
+    let finite: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if finite.len() < 2 {
+        return None;
+    }
+    let mean = mean(&finite);
+    let var = finite.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / finite.len() as f64;
+    let std_dev = var.sqrt();
+    if std_dev < 1e-9 {
+        return None;
+    }
+
+    Some(
+        (0..len)
+            .map(|i| match values.get(i) {
+                Some(v) if v.is_finite() => ((v - mean) / std_dev).clamp(-MAX_Z, MAX_Z),
+                _ => 0.0,
+            })
+            .collect(),
+    )
+}
+
+/// Events (shot cuts, chat messages) per minute in a window centred on each second.
+fn event_rate(times: &[f64], len: usize) -> Vec<f64> {
+    let mut counts = vec![0.0; len];
+    for &time in times {
+        if let Some(slot) = counts.get_mut(time.max(0.0) as usize) {
+            *slot += 1.0;
+        }
+    }
+
+    let half = EVENT_RATE_WINDOW_SECS / 2;
+    (0..len)
+        .map(|i| {
+            let first = i.saturating_sub(half);
+            let last = (i + half).min(len);
+            counts[first..last].iter().sum::<f64>() * 60.0 / (last - first) as f64
+        })
+        .collect()
+}
+
+/// Extract all signals from a video.
+///
+/// Audio loudness, VAD and the visual pass run concurrently. A signal that
+/// fails is logged and left empty; an error is returned only when nothing
+/// could be extracted.
+pub async fn extract_signals(
+    video_path: &Path,
+    config: &ExcitementConfig,
+) -> MediaResult<SignalTimeline> {
+    let duration = crate::probe::get_duration(video_path).await?;
+    info!(
+        video = %video_path.display(),
+        duration,
+        sample_fps = config.sample_fps,
+        "Extracting excitement signals"
+    );
+
+    let (loudness, speech, visual) = tokio::join!(
+        measure_loudness_timeline(video_path),
+        analyze_audio_segments(video_path, SilenceRemovalConfig::default()),
+        extract_visual_signals(video_path, config.sample_fps),
+    );
+
+    let mut timeline = SignalTimeline::new(duration);
+    match loudness {
+        Ok(samples) if !samples.is_empty() => {
+            timeline = timeline.with_loudness_samples(&samples);
+        }
+        Ok(_) => debug!("No audio stream, skipping loudness signal"),
+        Err(e) => warn!(error = %e, "Loudness extraction for excitement scoring failed"),
+    }
+    match speech {
+        Ok(segments) => timeline = timeline.with_speech_segments(&segments),
+        Err(e) => warn!(error = %e, "VAD analysis for excitement scoring failed"),
+    }
+    match visual {
+        Ok((motion, shot_cuts)) => {
+            timeline.motion = motion;
+            timeline.shot_cuts = shot_cuts;
+        }
+        Err(e) => warn!(error = %e, "Visual signal extraction for excitement scoring failed"),
+    }
+
+    if timeline.is_empty() {
+        return Err(MediaError::detection_failed(
+            "No excitement signals could be extracted",
+        ));
+    }
+
+    debug!(
+        loudness = timeline.loudness.len(),
+        speech = timeline.speech.len(),
+        motion = timeline.motion.len(),
+        shot_cuts = timeline.shot_cuts.len(),
+        "Extracted excitement signals"
+    );
+    Ok(timeline)
+}
+
+/// Extract signals and build the excitement profile of a video.
+///
+/// A chat replay, when available, adds the chat-rate signal.
+pub async fn analyze_excitement(
+    video_path: &Path,
+    chat: Option<&ChatReplay>,
+    config: &ExcitementConfig,
+) -> MediaResult<ExcitementProfile> {
+    let mut timeline = extract_signals(video_path, config).await?;
+    if let Some(replay) = chat.filter(|replay| !replay.is_empty()) {
+        debug!(messages = replay.len(), "Adding chat replay to excitement signals");
+        timeline = timeline.with_chat_replay(replay);
+    }
+    Ok(ExcitementProfile::new(&timeline, config.weights))
+}
+
+/// Per-second motion and shot cut times from one streaming pass.
+///
+/// Frames are decoded at `sample_fps` and processed as they arrive, so memory
+/// stays bounded on multi-hour streams.
+async fn extract_visual_signals(
+    video_path: &Path,
+    sample_fps: f64,
+) -> MediaResult<(Vec<f64>, Vec<f64>)> {
+    let bytes_per_frame = (FRAME_WIDTH * FRAME_HEIGHT * 3) as usize;
+
+    let mut cmd = crate::command::create_ffmpeg_command();
+    cmd.args(["-hide_banner", "-loglevel", "error", "-i"])
+        .arg(video_path)
+        .args([
+            "-an",
+            "-vf",
+            &format!("fps={},scale={}:{}", sample_fps, FRAME_WIDTH, FRAME_HEIGHT),
+            "-pix_fmt",
+            "rgb24",
+            "-f",
+            "rawvideo",
+            "-",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+
+    let mut child = cmd.spawn().map_err(|e| {
+        MediaError::ffmpeg_failed(format!("Failed to spawn FFmpeg: {}", e), None, None)
+    })?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| MediaError::ffmpeg_failed("Failed to capture FFmpeg stdout", None, None))?;
+    let mut reader = tokio::io::BufReader::new(stdout);
+
+    let detector = ShotDetector::new().with_min_frames((0.5 * sample_fps).ceil().max(1.0) as usize);
+    let mut frame = vec![0u8; bytes_per_frame];
+    let mut prev_gray: Option<Vec<u8>> = None;
+    let mut motion: Vec<(f64, u32)> = Vec::new();
+    let mut histograms: Vec<Vec<f64>> = Vec::new();
+    let mut chunk_start = 0usize;
+    let mut cuts = Vec::new();
+    let mut index = 0usize;
+
+    loop {
+        match reader.read_exact(&mut frame).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => {
+                return Err(MediaError::ffmpeg_failed(
+                    format!("Failed to read FFmpeg output: {}", e),
+                    None,
+                    None,
+                ))
+            }
+        }
+
+        let gray = rgb_to_gray(&frame);
+        if let Some(prev) = &prev_gray {
+            let second = (index as f64 / sample_fps) as usize;
+            if motion.len() <= second {
+                motion.resize(second + 1, (0.0, 0));
+            }
+            motion[second].0 += changed_pixel_fraction(prev, &gray, MOTION_PIXEL_THRESHOLD);
+            motion[second].1 += 1;
+        }
+        prev_gray = Some(gray);
+
+        histograms.push(detector.compute_histogram(&frame, FRAME_WIDTH, FRAME_HEIGHT));
+        if histograms.len() >= SHOT_CHUNK_FRAMES {
+            collect_cuts(&detector, &histograms, chunk_start, sample_fps, &mut cuts);
+            // Keep the last frame so the cut between chunks is still compared
+            let last = histograms.pop().unwrap_or_default();
+            chunk_start += histograms.len();
+            histograms = vec![last];
+        }
+        index += 1;
+    }
+    if histograms.len() > 1 {
+        collect_cuts(&detector, &histograms, chunk_start, sample_fps, &mut cuts);
+    }
+
+    let status = child.wait().await.map_err(|e| {
+        MediaError::ffmpeg_failed(format!("FFmpeg process error: {}", e), None, None)
+    })?;
+    if !status.success() && index == 0 {
+        return Err(MediaError::ffmpeg_failed(
+            "Frame extraction for excitement scoring failed",
+            None,
+            status.code(),
+        ));
+    }
+
+    debug!(
+        frames = index,
+        cuts = cuts.len(),
+        "Extracted visual signals"
+    );
+
+    let motion = motion
+        .into_iter()
+        .map(|(sum, count)| {
+            if count > 0 {
+                sum / count as f64
+            } else {
+                f64::NAN
+            }
+        })
+        .collect();
+    Ok((motion, cuts))
+}
+
+/// Append the cut times found in a chunk of histograms starting at frame `chunk_start`.
+fn collect_cuts(
+    detector: &ShotDetector,
+    histograms: &[Vec<f64>],
+    chunk_start: usize,
+    fps: f64,
+    cuts: &mut Vec<f64>,
+) {
+    cuts.extend(
+        detector
+            .detect_from_histograms(histograms, fps)
+            .iter()
+            .skip(1)
+            .map(|shot| (chunk_start + shot.start_frame) as f64 / fps),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 300s of quiet, still footage with a loud, busy stretch at 200-230s.
+    fn timeline() -> SignalTimeline {
+        let loud = 200..230;
+        let mut timeline = SignalTimeline::new(300.0);
+        timeline.loudness = (0..300)
+            .map(|s| {
+                if loud.contains(&s) {
+                    -12.0
+                } else {
+                    -30.0 + (s % 3) as f64
+                }
+            })
+            .collect();
+        timeline.motion = (0..300)
+            .map(|s| if loud.contains(&s) { 0.4 } else { 0.05 })
+            .collect();
+        timeline.shot_cuts = vec![40.0, 204.0, 209.0, 214.0, 219.0, 224.0];
+        timeline
+    }
+
+    #[test]
+    fn test_top_candidate_covers_peak() {
+        let profile = ExcitementProfile::new(&timeline(), ExcitementWeights::default());
+        let candidates = profile.candidates(&ExcitementConfig::default(), &[]);
+
+        let top = &candidates[0];
+        assert!(top.start < 230.0 && top.end > 200.0);
+        assert!((20.0..=60.0).contains(&top.duration()));
+        assert!(top.score > 0.8);
+        assert!(top.reason.starts_with("Excitement peak: loud audio"));
+        assert!(top.reason.contains("rapid cuts"));
+        assert!(candidates.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(profile.score_range(0.0, 60.0) < 0.5);
+
+        let highlight = top.to_highlight(1);
+        assert_eq!(highlight.duration, top.duration() as u32);
+        assert_eq!(highlight.reason.as_deref(), Some(top.reason.as_str()));
+    }
+
+    #[test]
+    fn test_candidates_respect_exclusions_and_limit() {
+        let profile = ExcitementProfile::new(&timeline(), ExcitementWeights::default());
+        let config = ExcitementConfig::default().with_max_candidates(2);
+        let candidates = profile.candidates(&config, &[(190.0, 240.0)]);
+
+        assert!(candidates.len() <= 2);
+        for c in &candidates {
+            assert!(c.end <= 190.0 || c.start >= 240.0);
+        }
+        for (i, a) in candidates.iter().enumerate() {
+            for b in &candidates[i + 1..] {
+                assert!(a.end <= b.start || b.end <= a.start);
+            }
+        }
+    }
+
+    #[test]
+    fn test_missing_signals_are_dropped() {
+        let empty = SignalTimeline::new(120.0);
+        assert!(empty.is_empty());
+        let profile = ExcitementProfile::new(&empty, ExcitementWeights::default());
+        assert!(profile
+            .candidates(&ExcitementConfig::default(), &[])
+            .is_empty());
+        assert_eq!(profile.score_range(0.0, 30.0), 0.5);
+
+        // Loudness alone still ranks
+        let mut loud_only = timeline();
+        loud_only.motion.clear();
+        loud_only.shot_cuts.clear();
+        let profile = ExcitementProfile::new(&loud_only, ExcitementWeights::default());
+        let top = &profile.candidates(&ExcitementConfig::default(), &[])[0];
+        assert!(top.start < 230.0 && top.end > 200.0);
+        assert!(!top.reason.contains("rapid cuts"));
+    }
+
+    #[test]
+    fn test_chat_spike_is_a_signal() {
+        // Quiet audio throughout; chat bursts at 125-150s, reacting to 120-145s
+        let mut timeline = SignalTimeline::new(300.0);
+        timeline.loudness = (0..300).map(|s| -30.0 + (s % 3) as f64).collect();
+        let mut messages: Vec<f64> = (0..300).step_by(10).map(|s| s as f64).collect();
+        messages.extend((0..250).map(|i| 125.0 + i as f64 * 0.1));
+        let timeline = timeline.with_chat_replay(&ChatReplay {
+            message_times: messages,
+        });
+
+        let profile = ExcitementProfile::new(&timeline, ExcitementWeights::default());
+        let top = &profile.candidates(&ExcitementConfig::default(), &[])[0];
+        assert!(top.start < 145.0 && top.end > 120.0);
+        // Credited to the moment, not to the delayed burst itself
+        assert!(top.end <= 145.0);
+        assert!(top.reason.starts_with("Excitement peak: chat spike"));
+        assert!(top.breakdown.chat > top.breakdown.loudness);
+
+        // Without chat the same timeline has no clear peak there
+        let mut no_chat = timeline.clone();
+        no_chat.chat_messages.clear();
+        let profile = ExcitementProfile::new(&no_chat, ExcitementWeights::default());
+        assert!(profile.score_range(120.0, 145.0) < 0.6);
+    }
+
+    #[test]
+    fn test_speech_segments_and_motion_helpers() {
+        let segments = [
+            Segment {
+                start_ms: 500,
+                end_ms: 2000,
+                label: SegmentLabel::Keep,
+            },
+            Segment {
+                start_ms: 2000,
+                end_ms: 4000,
+                label: SegmentLabel::Cut,
+            },
+        ];
+        let timeline = SignalTimeline::new(3.5).with_speech_segments(&segments);
+        assert_eq!(timeline.speech, vec![0.5, 1.0, 0.0, 0.0]);
+
+        let timeline =
+            SignalTimeline::new(3.0).with_loudness_samples(&[(0.0, -20.0), (2.0, -10.0)]);
+        assert_eq!(timeline.loudness[0], -20.0);
+        assert!(timeline.loudness[1].is_nan());
+
+        assert_eq!(rgb_to_gray(&[255, 255, 255, 0, 0, 0]), vec![255, 0]);
+        assert_eq!(
+            changed_pixel_fraction(&[0, 0, 0, 0], &[0, 30, 100, 10], 25),
+            0.5
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Platform export variants.
+//!
+//! Transcodes a finished clip into the rendition described by an
+//! [`ExportProfile`]: cut to the platform's maximum duration, scaled to the
+//! profile resolution at the clip's aspect ratio, loudness-normalized and
+//! encoded with the profile's bitrate cap and container flags.
+
+use std::path::Path;
+
+use tracing::info;
+use vclip_models::{AspectRatio, ExportProfile};
+
+use crate::audio::{build_normalize_filter, measure_loudnorm};
+use crate::command::{FfmpegCommand, FfmpegRunner};
+use crate::error::MediaResult;
+use crate::intelligent::OutputFormat;
+use crate::probe::probe_video;
+
+/// Result of rendering one export variant.
+#[derive(Debug, Clone, Copy)]
+pub struct ExportedVariant {
+    /// Duration of the variant in seconds
+    pub duration_seconds: f64,
+    /// File size in bytes
+    pub file_size_bytes: u64,
+    /// Output width in pixels
+    pub width: u32,
+    /// Output height in pixels
+    pub height: u32,
+}
+
+/// Build the FFmpeg output arguments for a variant.
+///
+/// `audio_filter` is the second-pass loudness filter, or `None` when the clip
+/// has no measurable audio.
+fn build_export_args(
+    profile: ExportProfile,
+    format: &OutputFormat,
+    duration: f64,
+    audio_filter: Option<String>,
+) -> Vec<String> {
+    let mut args = vec![
+        "-t".to_string(),
+        format!("{:.3}", duration),
+        "-vf".to_string(),
+        format.scale_filter(),
+    ];
+    if let Some(filter) = audio_filter {
+        args.push("-af".to_string());
+        args.push(filter);
+    }
+    args.push("-pix_fmt".to_string());
+    args.push("yuv420p".to_string());
+    args.extend(profile.encoding().to_ffmpeg_args());
+    args
+}
+
+/// Render `input` as an export variant for `profile`.
+///
+/// `aspect` is the aspect ratio the clip was rendered at; the variant keeps it
+/// and only changes the resolution.
+pub async fn render_export_variant(
+    input: &Path,
+    output: &Path,
+    profile: ExportProfile,
+    aspect: AspectRatio,
+) -> MediaResult<ExportedVariant> {
+    let source_duration = probe_video(input).await?.duration;
+    let duration = source_duration.min(profile.max_duration_secs());
+    let format = OutputFormat::new(aspect, profile.resolution());
+
+    // Two-pass loudness normalization to the profile target
+    let audio = profile.audio();
+    let audio_filter = match measure_loudnorm(input, &audio).await? {
+        Some(measured) if !measured.is_silent() => Some(build_normalize_filter(&audio, &measured)),
+        _ => None,
+    };
+
+    info!(
+        profile = %profile,
+        width = format.width,
+        height = format.height,
+        duration,
+        trimmed = duration < source_duration,
+        "Rendering export variant"
+    );
+
+    let cmd = FfmpegCommand::new(input, output)
+        .output_args(build_export_args(profile, &format, duration, audio_filter))
+        .log_level("error");
+    FfmpegRunner::new().run(&cmd).await?;
+
+    let file_size_bytes = tokio::fs::metadata(output).await?.len();
+
+    Ok(ExportedVariant {
+        duration_seconds: duration,
+        file_size_bytes,
+        width: format.width,
+        height: format.height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vclip_models::ResolutionPreset;
+
+    #[test]
+    fn test_export_args() {
+        let format = OutputFormat::new(AspectRatio::PORTRAIT, ResolutionPreset::P1080);
+        let args = build_export_args(
+            ExportProfile::Reels,
+            &format,
+            90.0,
+            Some("loudnorm=I=-14".to_string()),
+        );
+
+        assert!(args.windows(2).any(|w| w == ["-t", "90.000"]));
+        assert!(args.contains(&"scale=1080:1920:flags=lanczos,setsar=1".to_string()));
+        assert!(args.windows(2).any(|w| w == ["-af", "loudnorm=I=-14"]));
+        assert!(args.windows(2).any(|w| w == ["-maxrate", "5000k"]));
+        assert!(args.windows(2).any(|w| w == ["-movflags", "+faststart"]));
+
+        let silent = build_export_args(ExportProfile::Reels, &format, 90.0, None);
+        assert!(!silent.contains(&"-af".to_string()));
+    }
+}
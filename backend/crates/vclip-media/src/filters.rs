@@ -1,9 +1,13 @@
 //! FFmpeg video filter definitions.
 //!
-//! These filters match the Python implementation exactly.
+//! The `FILTER_*` constants match the Python implementation exactly and
+//! describe the default 1080×1920 output; [`build_video_filter`] generates the
+//! same filters for any [`OutputFormat`].
 
 use vclip_models::Style;
 
+use crate::intelligent::OutputFormat;
+
 /// Split view filter (left and right halves stacked vertically).
 pub const FILTER_SPLIT: &str = concat!(
     "scale=1920:-2,split=2[full][full2];",
@@ -45,13 +49,21 @@ pub const FILTER_CENTER_FOCUS: &str = concat!(
 /// Default portrait crop filter.
 pub const FILTER_DEFAULT_PORTRAIT: &str = "scale=-2:1920,crop=1080:1920";
 
-/// Build video filter for a style.
-pub fn build_video_filter(style: Style) -> Option<String> {
+/// Build video filter for a style at the given output dimensions.
+pub fn build_video_filter(style: Style, format: &OutputFormat) -> Option<String> {
     match style {
-        Style::Split => Some(FILTER_SPLIT.to_string()),
-        Style::LeftFocus => Some(FILTER_LEFT_FOCUS.to_string()),
-        Style::RightFocus => Some(FILTER_RIGHT_FOCUS.to_string()),
-        Style::CenterFocus => Some(FILTER_CENTER_FOCUS.to_string()),
+        Style::Split => Some(filter_split(format)),
+        Style::LeftFocus => Some(filter_focus("crop=910:1080:0:0", format)),
+        Style::RightFocus => Some(filter_focus("crop=960:1080:960:0", format)),
+        Style::CenterFocus => {
+            let aspect = format.crop_aspect();
+            let crop = format!(
+                "crop=min(iw\\,ih*{w}/{h}):ih:max((iw-ih*{w}/{h})/2\\,0):0",
+                w = aspect.width,
+                h = aspect.height
+            );
+            Some(filter_focus(&crop, format))
+        }
         Style::Original => None, // No filter for original
         // SplitFast uses FastSplitEngine - no filter here
         Style::SplitFast => None,
@@ -70,6 +82,38 @@ pub fn build_video_filter(style: Style) -> Option<String> {
     }
 }
 
+/// Split view filter: left and right halves stacked vertically.
+fn filter_split(format: &OutputFormat) -> String {
+    let (panel_width, panel_height) = format.split_panel();
+    let mut filter = format!(
+        "scale=1920:-2,split=2[full][full2];\
+         [full]crop=910:1080:0:0[left];\
+         [full2]crop=960:1080:960:0[right];\
+         [left]scale={pw}:-2,crop={pw}:{ph}[left_scaled];\
+         [right]scale={pw}:-2,crop={pw}:{ph}[right_scaled];\
+         [left_scaled][right_scaled]vstack=inputs=2",
+        pw = panel_width,
+        ph = panel_height,
+    );
+    if panel_height * 2 != format.height {
+        filter.push(',');
+        filter.push_str(&format.scale_filter());
+    }
+    filter
+}
+
+/// Focus filter: crop a region, fit it to the output and pad only below.
+fn filter_focus(crop: &str, format: &OutputFormat) -> String {
+    format!(
+        "scale=1920:-2,{crop},\
+         scale={w}:{h}:force_original_aspect_ratio=decrease,\
+         pad={w}:{h}:(ow-iw)/2:0",
+        crop = crop,
+        w = format.width,
+        h = format.height,
+    )
+}
+
 /// Build filter for cropping left half of video.
 pub fn filter_crop_left_half() -> &'static str {
     "crop=iw/2:ih:0:0"
@@ -104,12 +148,40 @@ pub fn filter_thumbnail(width: u32) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use vclip_models::{AspectRatio, ResolutionPreset};
 
     #[test]
     fn test_build_video_filter() {
-        assert!(build_video_filter(Style::Split).is_some());
-        assert!(build_video_filter(Style::Original).is_none());
-        assert!(build_video_filter(Style::CenterFocus).is_some());
+        let format = OutputFormat::default();
+        assert!(build_video_filter(Style::Split, &format).is_some());
+        assert!(build_video_filter(Style::Original, &format).is_none());
+        assert!(build_video_filter(Style::CenterFocus, &format).is_some());
+    }
+
+    #[test]
+    fn test_default_format_matches_reference_filters() {
+        let format = OutputFormat::default();
+        let filter = |style| build_video_filter(style, &format).unwrap();
+        assert_eq!(filter(Style::Split), FILTER_SPLIT);
+        assert_eq!(filter(Style::LeftFocus), FILTER_LEFT_FOCUS);
+        assert_eq!(filter(Style::RightFocus), FILTER_RIGHT_FOCUS);
+    }
+
+    #[test]
+    fn test_build_video_filter_for_other_formats() {
+        let square = OutputFormat::new(AspectRatio::SQUARE, ResolutionPreset::P720);
+        let split = build_video_filter(Style::Split, &square).unwrap();
+        assert!(split.contains("crop=720:360[left_scaled]"));
+        assert!(split.ends_with("vstack=inputs=2"));
+
+        let center = build_video_filter(Style::CenterFocus, &square).unwrap();
+        assert!(center.contains("ih*1/1"));
+        assert!(center.contains("pad=720:720"));
+
+        // Odd half-height panels are scaled back to the exact output height
+        let insta = OutputFormat::new(AspectRatio::INSTAGRAM_PORTRAIT, ResolutionPreset::P1080);
+        let split = build_video_filter(Style::Split, &insta).unwrap();
+        assert!(split.ends_with("scale=1080:1350:flags=lanczos,setsar=1"));
     }
 
     #[test]
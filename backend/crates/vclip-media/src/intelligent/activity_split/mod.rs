@@ -16,6 +16,7 @@ use crate::intelligent::config::IntelligentCropConfig;
 use crate::intelligent::detection_adapter::get_detections;
 use crate::intelligent::detector::FaceDetector;
 use crate::intelligent::models::FrameDetections;
+use crate::intelligent::output_format::OutputFormat;
 use crate::probe::probe_video;
 use crate::thumbnail::generate_thumbnail;
 use crate::watermark::WatermarkConfig;
@@ -197,7 +198,8 @@ where
         height,
         sample_interval,
         watermark.cloned(),
-    );
+    )
+    .with_output_format(OutputFormat::for_task(task));
     renderer
         .render(&segment_path, output, &detections, &spans)
         .await?;
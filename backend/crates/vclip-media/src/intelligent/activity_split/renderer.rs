@@ -1,8 +1,8 @@
 //! Rendering for Smart Split (Activity).
 //!
 //! Renders planned layout spans by generating panel crops and stacking them into
-//! a 9:16 portrait output. All spans are scaled to 1080x1920 to keep outputs
-//! consistent for concatenation.
+//! the requested output format. All spans are scaled to the same output size
+//! (1080x1920 by default) to keep outputs consistent for concatenation.
 
 use std::path::{Path, PathBuf};
 
@@ -13,6 +13,7 @@ use crate::error::{MediaError, MediaResult};
 use crate::intelligent::config::IntelligentCropConfig;
 use crate::intelligent::crop_planner::CropPlanner;
 use crate::intelligent::models::{AspectRatio, CropWindow, Detection, FrameDetections};
+use crate::intelligent::output_format::OutputFormat;
 use crate::intelligent::single_pass_renderer::SinglePassRenderer;
 use crate::intelligent::smoother::CameraSmoother;
 use crate::watermark::WatermarkConfig;
@@ -26,6 +27,7 @@ pub(crate) struct ActivitySplitRenderer {
     frame_height: u32,
     sample_interval: f64,
     watermark: Option<WatermarkConfig>,
+    output_format: OutputFormat,
 }
 
 impl ActivitySplitRenderer {
@@ -44,9 +46,15 @@ impl ActivitySplitRenderer {
             frame_height,
             sample_interval,
             watermark,
+            output_format: OutputFormat::default(),
         }
     }
 
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
     pub async fn render(
         &self,
         segment: &Path,
@@ -83,10 +91,14 @@ impl ActivitySplitRenderer {
                         primary = primary,
                         "Rendering Full layout span with SINGLE-PASS"
                     );
-                    let windows =
-                        self.track_windows(detections, primary, span, AspectRatio::PORTRAIT)?;
-
-                    // Use SinglePassRenderer to render directly to target size (1080x1920)
+                    let windows = self.track_windows(
+                        detections,
+                        primary,
+                        span,
+                        self.output_format.crop_aspect(),
+                    )?;
+
+                    // Use SinglePassRenderer to render directly to target size
                     // This combines crop, scale and PAD/SAR in one pass
 
                     // Extract span source first (stream copy)
@@ -96,7 +108,8 @@ impl ActivitySplitRenderer {
                     self.extract_span_source(segment, &span_segment, span.start, span_duration)
                         .await?;
 
-                    let mut renderer = SinglePassRenderer::new(self.config.clone());
+                    let mut renderer = SinglePassRenderer::new(self.config.clone())
+                        .with_output_format(self.output_format);
                     if let Some(config) = self.watermark.as_ref() {
                         renderer = renderer.with_watermark(config.clone());
                     }
@@ -120,7 +133,8 @@ impl ActivitySplitRenderer {
                         .await?;
 
                     // Use SinglePassRenderer for split (SINGLE ENCODE instead of 3)
-                    let mut renderer = SinglePassRenderer::new(self.config.clone());
+                    let mut renderer = SinglePassRenderer::new(self.config.clone())
+                        .with_output_format(self.output_format);
                     if let Some(config) = self.watermark.as_ref() {
                         renderer = renderer.with_watermark(config.clone());
                     }
@@ -34,7 +34,8 @@ use crate::error::MediaResult;
 use crate::intelligent::config::IntelligentCropConfig;
 use crate::intelligent::crop_planner::CropPlanner;
 use crate::intelligent::detection_adapter::get_detections;
-use crate::intelligent::models::{AspectRatio, BoundingBox, CameraKeyframe, Detection};
+use crate::intelligent::models::{BoundingBox, CameraKeyframe, Detection};
+use crate::intelligent::output_format::OutputFormat;
 use crate::intelligent::single_pass_renderer::SinglePassRenderer;
 use crate::probe::probe_video;
 use crate::thumbnail::generate_thumbnail;
@@ -51,6 +52,8 @@ pub struct CinematicProcessor {
     base_config: IntelligentCropConfig,
     /// Object detector for YOLOv8 inference (optional - requires model)
     object_detector: Option<Arc<ObjectDetector>>,
+    /// Output dimensions
+    output_format: OutputFormat,
 }
 
 impl CinematicProcessor {
@@ -63,6 +66,7 @@ impl CinematicProcessor {
             config,
             base_config: IntelligentCropConfig::for_tier(DetectionTier::Cinematic),
             object_detector,
+            output_format: OutputFormat::default(),
         }
     }
 
@@ -74,6 +78,7 @@ impl CinematicProcessor {
             config,
             base_config: IntelligentCropConfig::for_tier(DetectionTier::Cinematic),
             object_detector,
+            output_format: OutputFormat::default(),
         }
     }
 
@@ -86,9 +91,16 @@ impl CinematicProcessor {
             config,
             base_config: IntelligentCropConfig::for_tier(DetectionTier::Cinematic),
             object_detector,
+            output_format: OutputFormat::default(),
         }
     }
 
+    /// Set the output dimensions (default: 1080×1920).
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
     /// Try to load the object detector model.
     fn try_load_object_detector(config: &CinematicConfig) -> Option<Arc<ObjectDetector>> {
         if !config.enable_object_detection {
@@ -501,14 +513,15 @@ impl CinematicProcessor {
         let step_start = std::time::Instant::now();
         info!("[CINEMATIC] Step 6/8: Computing crops and rendering...");
 
-        let target_aspect = AspectRatio::new(9, 16);
+        let target_aspect = self.output_format.crop_aspect();
         let planner = CropPlanner::new(self.base_config.clone(), width, height);
         let crop_windows = planner.compute_crop_windows(&all_smoothed_keyframes, &target_aspect);
 
         info!("[CINEMATIC] Generated {} crop windows", crop_windows.len());
 
         // Render with single pass
-        let mut renderer = SinglePassRenderer::new(self.base_config.clone());
+        let mut renderer = SinglePassRenderer::new(self.base_config.clone())
+            .with_output_format(self.output_format);
         if let Some(config) = watermark {
             renderer = renderer.with_watermark(config.clone());
         }
@@ -928,7 +941,7 @@ where
     // Step 2: Process with cinematic pipeline
     info!("[PIPELINE] Step 2/2: Cinematic processing (SINGLE ENCODE)...");
 
-    let processor = CinematicProcessor::new();
+    let processor = CinematicProcessor::new().with_output_format(OutputFormat::for_task(task));
     let result = processor
         .process_with_cache(
             segment_path.as_path(),
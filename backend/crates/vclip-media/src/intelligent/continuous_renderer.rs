@@ -11,9 +11,7 @@
 
 use super::config::IntelligentCropConfig;
 use super::models::CropWindow;
-use super::output_format::{
-    PORTRAIT_HEIGHT, PORTRAIT_WIDTH, SPLIT_PANEL_HEIGHT, SPLIT_PANEL_WIDTH,
-};
+use super::output_format::OutputFormat;
 use crate::error::{MediaError, MediaResult};
 use std::path::Path;
 use std::process::Stdio;
@@ -26,12 +24,22 @@ use tracing::{debug, info};
 /// all crop transitions within a single pass.
 pub struct ContinuousRenderer {
     config: IntelligentCropConfig,
+    output_format: OutputFormat,
 }
 
 impl ContinuousRenderer {
     /// Create a new continuous renderer.
     pub fn new(config: IntelligentCropConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            output_format: OutputFormat::default(),
+        }
+    }
+
+    /// Set the output dimensions for split and hybrid layouts (default: 1080×1920).
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
     }
 
     /// Render with dynamic cropping using sendcmd filter.
@@ -329,9 +337,8 @@ impl ContinuousRenderer {
         let left_crop = self.compute_median_crop(left_crops);
         let right_crop = self.compute_median_crop(right_crops);
 
-        // Use centralized panel dimensions for consistent 9:16 output
-        let panel_width = SPLIT_PANEL_WIDTH;
-        let panel_height = SPLIT_PANEL_HEIGHT;
+        // Use centralized panel dimensions for consistent output
+        let (panel_width, panel_height) = self.output_format.split_panel();
 
         format!(
             // Normalize input
@@ -349,7 +356,7 @@ impl ContinuousRenderer {
              pad={pw}:{ph}:(ow-iw)/2:(oh-ih)/2,\
              setsar=1[bottom];\
              \
-             [top][bottom]vstack=inputs=2[vout]",
+             {stack}",
             // Left crop
             left_w = left_crop.width,
             left_h = left_crop.height,
@@ -363,6 +370,7 @@ impl ContinuousRenderer {
             // Panel size
             pw = panel_width,
             ph = panel_height,
+            stack = self.output_format.stack_filter("top", "bottom", "vout"),
         )
     }
 
@@ -464,7 +472,7 @@ impl ContinuousRenderer {
     /// Build a hybrid filter that switches between full and split layouts.
     ///
     /// Uses overlay with `enable` expression to toggle between:
-    /// - Full view: Single crop scaled to 1080x1920
+    /// - Full view: Single crop scaled to the output size
     /// - Split view: Two crops stacked vertically
     fn build_hybrid_filter(
         &self,
@@ -483,9 +491,8 @@ impl ContinuousRenderer {
         let split_enable = self.build_enable_expression(layout_spans, LayoutType::Split);
 
         // Use centralized dimensions for consistent output
-        let panel_width = SPLIT_PANEL_WIDTH;
-        let panel_height = SPLIT_PANEL_HEIGHT;
-        let output_height = PORTRAIT_HEIGHT;
+        let (panel_width, panel_height) = self.output_format.split_panel();
+        let output_height = self.output_format.height;
 
         format!(
             // Input normalization with PTS reset
@@ -508,7 +515,7 @@ impl ContinuousRenderer {
              scale={pw}:{ph}:force_original_aspect_ratio=decrease,\
              pad={pw}:{ph}:(ow-iw)/2:(oh-ih)/2,setsar=1[bottom_panel];\
              \
-             [top_panel][bottom_panel]vstack=inputs=2[split_view];\
+             {stack};\
              \
              [canvas][full_scaled]overlay=0:0:enable='{full_enable}'[with_full];\
              [with_full][split_view]overlay=0:0:enable='{split_enable}'[vout]",
@@ -516,6 +523,9 @@ impl ContinuousRenderer {
             pw = panel_width,
             ph = panel_height,
             oh = output_height,
+            stack = self
+                .output_format
+                .stack_filter("top_panel", "bottom_panel", "split_view"),
             // Full view crop
             full_w = full_crop.width,
             full_h = full_crop.height,
@@ -560,7 +570,13 @@ impl ContinuousRenderer {
     /// Compute median crop from windows.
     fn compute_median_crop(&self, windows: &[CropWindow]) -> CropWindow {
         if windows.is_empty() {
-            return CropWindow::new(0.0, 0, 0, PORTRAIT_WIDTH as i32, PORTRAIT_HEIGHT as i32);
+            return CropWindow::new(
+                0.0,
+                0,
+                0,
+                self.output_format.width as i32,
+                self.output_format.height as i32,
+            );
         }
 
         if windows.len() == 1 {
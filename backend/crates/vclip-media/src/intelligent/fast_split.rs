@@ -9,8 +9,8 @@
 //! Uses ONE FFmpeg command with a combined filter graph:
 //! ```text
 //! [0:v] → split → [left][right]
-//! [left] → crop left 45% → scale 1080x960 → [top]
-//! [right] → crop right 45% → scale 1080x960 → [bottom]
+//! [left] → crop left 45% → scale to panel (1080x960 by default) → [top]
+//! [right] → crop right 45% → scale to panel (1080x960 by default) → [bottom]
 //! [top][bottom] → vstack → [out]
 //! ```
 //!
@@ -20,6 +20,7 @@ use std::path::Path;
 use tracing::info;
 
 use super::config::IntelligentCropConfig;
+use super::output_format::OutputFormat;
 use super::single_pass_renderer::SinglePassRenderer;
 use crate::error::MediaResult;
 use crate::probe::probe_video;
@@ -60,19 +61,27 @@ impl Default for FastSplitConfig {
 /// - Podcast-style videos with consistent speaker positions
 pub struct FastSplitEngine {
     config: FastSplitConfig,
+    output_format: OutputFormat,
 }
 
 impl FastSplitEngine {
     /// Create a new fast split engine with default configuration.
     pub fn new() -> Self {
-        Self {
-            config: FastSplitConfig::default(),
-        }
+        Self::with_config(FastSplitConfig::default())
     }
 
     /// Create with custom configuration.
     pub fn with_config(config: FastSplitConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            output_format: OutputFormat::default(),
+        }
+    }
+
+    /// Set the output dimensions (default: 1080×1920).
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
     }
 
     /// Process a video segment with fast split.
@@ -116,7 +125,7 @@ impl FastSplitEngine {
         );
 
         let config = IntelligentCropConfig::default();
-        let mut renderer = SinglePassRenderer::new(config);
+        let mut renderer = SinglePassRenderer::new(config).with_output_format(self.output_format);
         if let Some(config) = watermark {
             renderer = renderer.with_watermark(config.clone());
         }
@@ -72,7 +72,6 @@ pub mod letterbox;
 pub mod mapping;
 pub mod model_config;
 pub mod models;
-#[cfg(feature = "opencv")]
 pub mod motion;
 pub mod optimized_detector;
 pub mod output_format;
@@ -131,7 +130,7 @@ pub use temporal::{DecimatorStats, DetectionTrigger, TemporalConfig, TemporalDec
 pub use continuous_renderer::{ContinuousRenderer, LayoutSpan, LayoutType};
 pub use enhanced_smoother::{EnhancedCameraSmoother, SmoothingPreset};
 pub use output_format::{
-    clamp_crop_to_frame, make_even, portrait_scale_filter, split_panel_scale_filter,
+    clamp_crop_to_frame, make_even, portrait_scale_filter, split_panel_scale_filter, OutputFormat,
     PORTRAIT_HEIGHT, PORTRAIT_WIDTH, SPLIT_PANEL_HEIGHT, SPLIT_PANEL_WIDTH,
 };
 pub use premium::{
@@ -201,6 +200,7 @@ pub fn parse_timestamp(ts: &str) -> MediaResult<f64> {
 pub struct IntelligentCropper {
     config: IntelligentCropConfig,
     detector: FaceDetector,
+    output_format: OutputFormat,
 }
 
 impl IntelligentCropper {
@@ -209,9 +209,16 @@ impl IntelligentCropper {
         Self {
             detector: FaceDetector::new(config.clone()),
             config,
+            output_format: OutputFormat::default(),
         }
     }
 
+    /// Set the output dimensions (default: 1080×1920).
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
     /// Create with default configuration.
     pub fn default() -> Self {
         Self::new(IntelligentCropConfig::default())
@@ -263,13 +270,14 @@ impl IntelligentCropper {
         // 4. Compute crop windows
         info!("Step 3/3: Computing crop windows...");
         let planner = CropPlanner::new(self.config.clone(), width, height);
-        let target_aspect = AspectRatio::new(9, 16); // Portrait 9:16
+        let target_aspect = self.output_format.crop_aspect();
         let crop_windows = planner.compute_crop_windows(&camera_keyframes, &target_aspect);
         info!("  Generated {} crop windows", crop_windows.len());
 
         // 5. Render the output
         info!("Rendering output...");
-        let renderer = IntelligentRenderer::new(self.config.clone());
+        let renderer =
+            IntelligentRenderer::new(self.config.clone()).with_output_format(self.output_format);
         renderer
             .render(input, output, &crop_windows, start_time, duration)
             .await?;
@@ -331,7 +339,7 @@ where
 
     // Step 2: Apply intelligent cropping to the segment
     let config = IntelligentCropConfig::default();
-    let cropper = IntelligentCropper::new(config);
+    let cropper = IntelligentCropper::new(config).with_output_format(OutputFormat::for_task(task));
     let result = cropper.process(segment_path.as_path(), output).await;
 
     // Step 3: Cleanup temporary segment file
@@ -1,5 +1,8 @@
 //! Lightweight heuristic motion detection for Tier 1 (MotionAware).
 //! Uses frame differencing on a downscaled grid to find the center of motion.
+//!
+//! The pixel-difference helpers are plain byte-slice functions so signal
+//! extraction can measure motion without OpenCV.
 
 #[cfg(feature = "opencv")]
 use opencv::{
@@ -11,6 +14,31 @@ use opencv::{
 #[cfg(feature = "opencv")]
 use crate::error::{MediaError, MediaResult};
 
+/// Minimum pixel intensity change to count as motion (0-255).
+pub const MOTION_PIXEL_THRESHOLD: u8 = 25;
+
+/// Convert packed RGB24 pixels to 8-bit luma (BT.601).
+pub fn rgb_to_gray(rgb: &[u8]) -> Vec<u8> {
+    rgb.chunks_exact(3)
+        .map(|p| ((77 * p[0] as u32 + 150 * p[1] as u32 + 29 * p[2] as u32) >> 8) as u8)
+        .collect()
+}
+
+/// Fraction of pixels whose intensity changed by more than `threshold`
+/// between two grayscale frames of the same size.
+pub fn changed_pixel_fraction(prev: &[u8], curr: &[u8], threshold: u8) -> f64 {
+    let len = prev.len().min(curr.len());
+    if len == 0 {
+        return 0.0;
+    }
+    let changed = prev
+        .iter()
+        .zip(curr)
+        .filter(|(a, b)| a.abs_diff(**b) > threshold)
+        .count();
+    changed as f64 / len as f64
+}
+
 /// Simple frame-diff motion detector.
 #[cfg(feature = "opencv")]
 pub struct MotionDetector {
@@ -34,7 +62,7 @@ impl MotionDetector {
         Self {
             prev_frame: None,
             proc_size: Size::new(proc_width, proc_height),
-            threshold: 25.0, // Ignore subtle noise
+            threshold: MOTION_PIXEL_THRESHOLD as f64, // Ignore subtle noise
         }
     }
 
@@ -1,14 +1,21 @@
-//! Output format constants for portrait video rendering.
+//! Output format for rendered clips.
 //!
-//! This module provides a single source of truth for target output dimensions.
-//! All renderers should use these constants to ensure consistent 9:16 portrait
-//! output at exactly 1080×1920 pixels.
+//! Output dimensions come from the job's target aspect ratio and resolution
+//! preset (see [`OutputFormat`]). The `PORTRAIT_*` and `SPLIT_PANEL_*`
+//! constants describe the default 9:16 output at 1080×1920 pixels.
+//!
+//! Split layouts always stack two panels vertically, each panel taking the
+//! full output width and half of its height.
 //!
 //! # FFmpeg Constraints
 //!
 //! - libx264 requires width/height to be divisible by 2
 //! - SAR (Sample Aspect Ratio) must be 1:1 for square pixels
-//! - DAR (Display Aspect Ratio) should be 9:16
+//! - DAR (Display Aspect Ratio) should match the target aspect ratio
+
+use vclip_models::{ClipTask, ResolutionPreset};
+
+use super::models::AspectRatio;
 
 /// Target width for portrait (9:16) output.
 pub const PORTRAIT_WIDTH: u32 = 1080;
@@ -34,10 +41,7 @@ pub const SPLIT_PANEL_HEIGHT: u32 = 960;
 /// ```
 #[inline]
 pub fn portrait_scale_filter() -> String {
-    format!(
-        "scale={}:{}:flags=lanczos,setsar=1",
-        PORTRAIT_WIDTH, PORTRAIT_HEIGHT
-    )
+    OutputFormat::default().scale_filter()
 }
 
 /// Builds an FFmpeg scale filter for split-view panels (1080×960 each).
@@ -51,10 +55,101 @@ pub fn portrait_scale_filter() -> String {
 /// ```
 #[inline]
 pub fn split_panel_scale_filter() -> String {
-    format!(
-        "scale={}:{}:flags=lanczos,setsar=1",
-        SPLIT_PANEL_WIDTH, SPLIT_PANEL_HEIGHT
-    )
+    OutputFormat::default().split_panel_scale_filter()
+}
+
+/// Output frame dimensions for a rendered clip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputFormat {
+    /// Output width in pixels (even)
+    pub width: u32,
+    /// Output height in pixels (even)
+    pub height: u32,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        Self {
+            width: PORTRAIT_WIDTH,
+            height: PORTRAIT_HEIGHT,
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Output format for an aspect ratio at a resolution preset.
+    pub fn new(aspect: vclip_models::AspectRatio, resolution: ResolutionPreset) -> Self {
+        let (width, height) = resolution.dimensions(aspect);
+        Self { width, height }
+    }
+
+    /// Output format requested by a clip task.
+    pub fn for_task(task: &ClipTask) -> Self {
+        Self::new(task.target_aspect, task.resolution)
+    }
+
+    /// Aspect ratio of the output frame, reduced to lowest terms.
+    ///
+    /// Used by crop planners to size crop windows.
+    pub fn crop_aspect(&self) -> AspectRatio {
+        let divisor = gcd(self.width, self.height).max(1);
+        AspectRatio::new(self.width / divisor, self.height / divisor)
+    }
+
+    /// Dimensions `(width, height)` of one split-view panel.
+    pub fn split_panel(&self) -> (u32, u32) {
+        (
+            self.width,
+            make_even((self.height / 2) as i32).max(2) as u32,
+        )
+    }
+
+    /// Width/height ratio of one split-view panel.
+    pub fn split_panel_ratio(&self) -> f64 {
+        let (w, h) = self.split_panel();
+        w as f64 / h as f64
+    }
+
+    /// FFmpeg scale filter to the exact output dimensions with square pixels.
+    pub fn scale_filter(&self) -> String {
+        format!(
+            "scale={}:{}:flags=lanczos,setsar=1",
+            self.width, self.height
+        )
+    }
+
+    /// FFmpeg scale filter to the exact split-view panel dimensions.
+    pub fn split_panel_scale_filter(&self) -> String {
+        let (w, h) = self.split_panel();
+        format!("scale={}:{}:flags=lanczos,setsar=1", w, h)
+    }
+
+    /// FFmpeg filter stacking two panels into the output frame.
+    ///
+    /// When two panels fall short of the output height (e.g. 4:5 at 1080p, where
+    /// half of 1350 is odd), the stacked frame is scaled to the exact output size.
+    pub fn stack_filter(&self, top: &str, bottom: &str, out: &str) -> String {
+        let (_, panel_height) = self.split_panel();
+        if panel_height * 2 == self.height {
+            format!("[{}][{}]vstack=inputs=2[{}]", top, bottom, out)
+        } else {
+            format!(
+                "[{}][{}]vstack=inputs=2,{}[{}]",
+                top,
+                bottom,
+                self.scale_filter(),
+                out
+            )
+        }
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
 }
 
 /// Ensures crop dimensions are even (required by libx264).
@@ -163,4 +258,50 @@ mod tests {
         // No padding - zoom-to-fill ensures exact aspect ratio
         assert!(!filter.contains("pad="));
     }
+
+    #[test]
+    fn test_output_format_from_aspect_and_resolution() {
+        let default = OutputFormat::default();
+        assert_eq!(
+            OutputFormat::new(vclip_models::AspectRatio::PORTRAIT, ResolutionPreset::P1080),
+            default
+        );
+        assert_eq!(
+            default.split_panel(),
+            (SPLIT_PANEL_WIDTH, SPLIT_PANEL_HEIGHT)
+        );
+        assert_eq!(default.crop_aspect(), AspectRatio::new(9, 16));
+
+        let square = OutputFormat::new(vclip_models::AspectRatio::SQUARE, ResolutionPreset::P720);
+        assert_eq!((square.width, square.height), (720, 720));
+        assert_eq!(square.split_panel(), (720, 360));
+        assert_eq!(square.crop_aspect(), AspectRatio::new(1, 1));
+
+        let landscape = OutputFormat::new(
+            vclip_models::AspectRatio::new(16, 9),
+            ResolutionPreset::P2160,
+        );
+        assert_eq!((landscape.width, landscape.height), (3840, 2160));
+        assert_eq!(landscape.crop_aspect(), AspectRatio::new(16, 9));
+        assert!(landscape.scale_filter().starts_with("scale=3840:2160"));
+    }
+
+    #[test]
+    fn test_stack_filter_fills_output_height() {
+        let default = OutputFormat::default();
+        assert_eq!(
+            default.stack_filter("top", "bottom", "vout"),
+            "[top][bottom]vstack=inputs=2[vout]"
+        );
+
+        // 4:5 at 1080p is 1080x1350; two 674px panels need a final scale
+        let insta = OutputFormat::new(
+            vclip_models::AspectRatio::INSTAGRAM_PORTRAIT,
+            ResolutionPreset::P1080,
+        );
+        assert_eq!(insta.split_panel(), (1080, 674));
+        let filter = insta.stack_filter("top", "bottom", "vout");
+        assert!(filter.contains("vstack=inputs=2,scale=1080:1350"));
+        assert!(filter.ends_with("[vout]"));
+    }
 }
@@ -5,7 +5,7 @@
 use super::config::IntelligentCropConfig;
 use super::crop_planner::is_static_crop;
 use super::models::CropWindow;
-use super::output_format::{PORTRAIT_HEIGHT, PORTRAIT_WIDTH};
+use super::output_format::OutputFormat;
 use crate::error::{MediaError, MediaResult};
 use std::path::Path;
 use std::process::Stdio;
@@ -15,12 +15,22 @@ use tracing::{debug, info};
 /// Renderer for intelligent cropped videos.
 pub struct IntelligentRenderer {
     config: IntelligentCropConfig,
+    output_format: OutputFormat,
 }
 
 impl IntelligentRenderer {
     /// Create a new renderer.
     pub fn new(config: IntelligentCropConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            output_format: OutputFormat::default(),
+        }
+    }
+
+    /// Set the output dimensions (default: 1080×1920).
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
     }
 
     /// Render a video with intelligent cropping.
@@ -76,10 +86,15 @@ impl IntelligentRenderer {
         let crop = self.compute_median_crop(crop_windows);
 
         // Build FFmpeg filter
-        // Crop to 9:16 region, then scale to exact 1080×1920 portrait output
+        // Crop to the target aspect region, then scale to the exact output size
         let vf = format!(
             "crop={}:{}:{}:{},scale={}:{}:flags=lanczos,setsar=1",
-            crop.width, crop.height, crop.x, crop.y, PORTRAIT_WIDTH, PORTRAIT_HEIGHT
+            crop.width,
+            crop.height,
+            crop.x,
+            crop.y,
+            self.output_format.width,
+            self.output_format.height
         );
 
         let mut cmd = crate::command::create_ffmpeg_command();
@@ -198,7 +213,7 @@ impl IntelligentRenderer {
         // 3. sendcmd - Dynamically update crop parameters
         // 4. format=yuv420p - Ensure compatible pixel format
         // Build filter graph with sendcmd for dynamic crop updates
-        // Final scale ensures exact 1080×1920 portrait output
+        // Final scale ensures the exact output size
         let filter_complex = format!(
             "[0:v]setsar=1,setpts=PTS-STARTPTS,format=yuv420p,\
              sendcmd=f='{script}',\
@@ -209,8 +224,8 @@ impl IntelligentRenderer {
             h = initial.height,
             x = initial.x,
             y = initial.y,
-            out_w = PORTRAIT_WIDTH,
-            out_h = PORTRAIT_HEIGHT,
+            out_w = self.output_format.width,
+            out_h = self.output_format.height,
         );
 
         debug!("Continuous crop filter:\n{}", filter_complex);
@@ -384,10 +399,15 @@ impl IntelligentRenderer {
         let duration = segment.end - segment.start;
 
         // Key fix: setpts=PTS-STARTPTS normalizes timestamps
-        // Scale to exact 1080×1920 portrait output with square pixels
+        // Scale to the exact output size with square pixels
         let vf = format!(
             "crop={}:{}:{}:{},setpts=PTS-STARTPTS,scale={}:{}:flags=lanczos,setsar=1",
-            crop.width, crop.height, crop.x, crop.y, PORTRAIT_WIDTH, PORTRAIT_HEIGHT
+            crop.width,
+            crop.height,
+            crop.x,
+            crop.y,
+            self.output_format.width,
+            self.output_format.height
         );
 
         let mut cmd = crate::command::create_ffmpeg_command();
@@ -494,10 +514,15 @@ impl IntelligentRenderer {
         let start = base_start + segment.start;
         let duration = segment.end - segment.start;
 
-        // Crop to 9:16 region, then scale to exact 1080×1920 portrait output
+        // Crop to the target aspect region, then scale to the exact output size
         let vf = format!(
             "crop={}:{}:{}:{},scale={}:{}:flags=lanczos,setsar=1",
-            crop.width, crop.height, crop.x, crop.y, PORTRAIT_WIDTH, PORTRAIT_HEIGHT
+            crop.width,
+            crop.height,
+            crop.x,
+            crop.y,
+            self.output_format.width,
+            self.output_format.height
         );
 
         let mut cmd = crate::command::create_ffmpeg_command();
@@ -547,7 +572,13 @@ impl IntelligentRenderer {
     /// Compute median crop from windows.
     fn compute_median_crop(&self, windows: &[CropWindow]) -> CropWindow {
         if windows.is_empty() {
-            return CropWindow::new(0.0, 0, 0, PORTRAIT_WIDTH as i32, PORTRAIT_HEIGHT as i32);
+            return CropWindow::new(
+                0.0,
+                0,
+                0,
+                self.output_format.width as i32,
+                self.output_format.height as i32,
+            );
         }
 
         let mut x_vals: Vec<i32> = windows.iter().map(|w| w.x).collect();
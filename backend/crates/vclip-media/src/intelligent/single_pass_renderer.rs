@@ -25,9 +25,7 @@ use tracing::{debug, info};
 
 use super::config::IntelligentCropConfig;
 use super::models::CropWindow;
-use super::output_format::{
-    PORTRAIT_HEIGHT, PORTRAIT_WIDTH, SPLIT_PANEL_HEIGHT, SPLIT_PANEL_WIDTH,
-};
+use super::output_format::{OutputFormat, PORTRAIT_HEIGHT, PORTRAIT_WIDTH};
 use crate::command::create_ffmpeg_command;
 use crate::error::{MediaError, MediaResult};
 use crate::watermark::{append_watermark_filter_complex, build_vf_with_watermark, WatermarkConfig};
@@ -38,6 +36,7 @@ pub struct SinglePassRenderer {
     #[allow(dead_code)]
     config: IntelligentCropConfig,
     watermark: Option<WatermarkConfig>,
+    output_format: OutputFormat,
 }
 
 impl SinglePassRenderer {
@@ -46,6 +45,7 @@ impl SinglePassRenderer {
         Self {
             config,
             watermark: None,
+            output_format: OutputFormat::default(),
         }
     }
 
@@ -54,6 +54,12 @@ impl SinglePassRenderer {
         self
     }
 
+    /// Set the output dimensions (default: 1080×1920).
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
     /// Render intelligent full-frame crop in a single encode pass.
     ///
     /// Input should be a **pre-extracted segment** (stream copy from source).
@@ -100,11 +106,15 @@ impl SinglePassRenderer {
         );
 
         // Build filter: crop → scale to exact output dimensions → set SAR
-        // The crop window is computed with exact 9:16 aspect ratio (zoom-to-fill),
+        // The crop window is computed with the exact output aspect ratio (zoom-to-fill),
         // so we can scale directly without padding - no black bars, no stretching
         let base_filter = format!(
-            "crop={}:{}:{}:{},scale={}:{}:flags=lanczos,setsar=1",
-            crop.width, crop.height, crop.x, crop.y, PORTRAIT_WIDTH, PORTRAIT_HEIGHT
+            "crop={}:{}:{}:{},{}",
+            crop.width,
+            crop.height,
+            crop.x,
+            crop.y,
+            self.output_format.scale_filter()
         );
         let filter = if let Some(config) = self.watermark.as_ref() {
             build_vf_with_watermark(Some(&base_filter), config).unwrap_or(base_filter)
@@ -185,8 +195,8 @@ impl SinglePassRenderer {
     /// # Filter Graph
     /// ```text
     /// [0:v] → split → [left_in][right_in]
-    /// [left_in] → crop left → scale 1080x960 → [top]
-    /// [right_in] → crop right → scale 1080x960 → [bottom]
+    /// [left_in] → crop left → scale to panel → [top]
+    /// [right_in] → crop right → scale to panel → [bottom]
     /// [top][bottom] → vstack → [out]
     /// ```
     ///
@@ -216,11 +226,12 @@ impl SinglePassRenderer {
 
         let half_width = source_width / 2;
 
-        // Calculate 9:8 tile dimensions (each panel is 1080x960)
-        let panel_ratio = SPLIT_PANEL_WIDTH as f64 / SPLIT_PANEL_HEIGHT as f64; // 9:8 = 1.125
+        // Each panel is full output width by half its height (9:8 for 1080x1920)
+        let (panel_width, panel_height) = self.output_format.split_panel();
+        let panel_ratio = self.output_format.split_panel_ratio();
 
         // Compute crop dimensions for each panel
-        // We need to fit a 9:8 crop within each half of the frame
+        // We need to fit a panel-shaped crop within each half of the frame
         let max_crop_from_half = half_width;
         let ideal_crop_height = (max_crop_from_half as f64 / panel_ratio).round() as u32;
         let crop_height = ideal_crop_height.min(source_height);
@@ -268,15 +279,16 @@ impl SinglePassRenderer {
             "[0:v]split=2[left_in][right_in];\
              [left_in]crop={cw}:{ch}:{lx}:{ly},scale={pw}:{ph}:flags=lanczos,setsar=1,format=yuv420p[top];\
              [right_in]crop={cw}:{ch}:{rx}:{ry},scale={pw}:{ph}:flags=lanczos,setsar=1,format=yuv420p[bottom];\
-             [top][bottom]vstack=inputs=2[vout]",
+             {stack}",
             cw = crop_width,
             ch = crop_height,
             lx = left_crop_x,
             ly = left_crop_y,
             rx = right_crop_x,
             ry = right_crop_y,
-            pw = SPLIT_PANEL_WIDTH,
-            ph = SPLIT_PANEL_HEIGHT,
+            pw = panel_width,
+            ph = panel_height,
+            stack = self.output_format.stack_filter("top", "bottom", "vout"),
         );
         let (filter_complex, map_label) = if let Some(config) = self.watermark.as_ref() {
             if let Some(watermarked) =
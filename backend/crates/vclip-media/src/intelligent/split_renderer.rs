@@ -10,7 +10,7 @@ use vclip_models::EncodingConfig;
 
 use super::detection_adapter::SplitLayoutInfo;
 use super::models::BoundingBox;
-use super::output_format::{clamp_crop_to_frame, SPLIT_PANEL_HEIGHT, SPLIT_PANEL_WIDTH};
+use super::output_format::{clamp_crop_to_frame, OutputFormat};
 use crate::error::{MediaError, MediaResult};
 use crate::watermark::{append_watermark_filter_complex, WatermarkConfig};
 
@@ -29,6 +29,7 @@ use crate::watermark::{append_watermark_filter_complex, WatermarkConfig};
 /// * `left_box` - Bounding box for left speaker
 /// * `right_box` - Bounding box for right speaker
 /// * `encoding` - Encoding configuration
+/// * `output_format` - Output dimensions (each panel is half the output height)
 pub async fn render_speaker_split(
     segment: &Path,
     output: &Path,
@@ -38,10 +39,12 @@ pub async fn render_speaker_split(
     right_box: &BoundingBox,
     encoding: &EncodingConfig,
     watermark: Option<&WatermarkConfig>,
+    output_format: OutputFormat,
 ) -> MediaResult<()> {
     let center_x = width as f64 / 2.0;
     let half_width = center_x;
-    let panel_ratio = SPLIT_PANEL_WIDTH as f64 / SPLIT_PANEL_HEIGHT as f64; // 9:8 = 1.125
+    let (panel_width, panel_height) = output_format.split_panel();
+    let panel_ratio = output_format.split_panel_ratio(); // 9:8 = 1.125 for 1080x1920
 
     // Compute UNIFORM crop dimensions for both panels based on frame size.
     // This ensures consistent sizing and that crops are large enough to capture
@@ -179,10 +182,9 @@ pub async fn render_speaker_split(
         right_crop_y
     );
 
-    // Adjust crop dimensions to exactly match 9:8 panel aspect ratio (zoom to fill)
-    let panel_ratio = SPLIT_PANEL_WIDTH as f64 / SPLIT_PANEL_HEIGHT as f64; // 9:8 = 1.125
+    // Adjust crop dimensions to exactly match the panel aspect ratio (zoom to fill)
 
-    // Left panel: adjust to 9:8
+    // Left panel: adjust to panel ratio
     let left_source_ratio = crop_width_left_u32 as f64 / tile_height_left_u32 as f64;
     let (final_left_w, final_left_h, left_x_adj, left_y_adj) = if left_source_ratio > panel_ratio {
         // Source is wider - crop width
@@ -198,7 +200,7 @@ pub async fn render_speaker_split(
         (w as i32, h, 0, y_adj)
     };
 
-    // Right panel: adjust to 9:8
+    // Right panel: adjust to panel ratio
     let right_source_ratio = crop_width_right_u32 as f64 / tile_height_right_u32 as f64;
     let (final_right_w, final_right_h, right_x_adj, right_y_adj) =
         if right_source_ratio > panel_ratio {
@@ -217,7 +219,7 @@ pub async fn render_speaker_split(
         "[0:v]split=2[left_in][right_in];\
          [left_in]crop={lw}:{lth}:{lx}:{ly},scale={pw}:{ph}:flags=lanczos,setsar=1,format=yuv420p[top];\
          [right_in]crop={rw}:{rth}:{rx}:{ry},scale={pw}:{ph}:flags=lanczos,setsar=1,format=yuv420p[bottom];\
-         [top][bottom]vstack=inputs=2[vout]",
+         {stack}",
         lw = final_left_w,
         lx = left_crop_x + left_x_adj,
         lth = final_left_h,
@@ -226,8 +228,9 @@ pub async fn render_speaker_split(
         rx = right_crop_x + right_x_adj,
         rth = final_right_h,
         ry = right_crop_y + right_y_adj,
-        pw = SPLIT_PANEL_WIDTH,
-        ph = SPLIT_PANEL_HEIGHT,
+        pw = panel_width,
+        ph = panel_height,
+        stack = output_format.stack_filter("top", "bottom", "vout"),
     );
 
     let (filter_complex, map_label) = if let Some(config) = watermark {
@@ -254,6 +257,7 @@ pub async fn render_speaker_split(
 /// * `height` - Video height
 /// * `split_info` - Split layout information with face positions
 /// * `encoding` - Encoding configuration
+/// * `output_format` - Output dimensions (each panel is half the output height)
 pub async fn render_standard_split(
     segment: &Path,
     output: &Path,
@@ -262,6 +266,7 @@ pub async fn render_standard_split(
     split_info: &SplitLayoutInfo,
     encoding: &EncodingConfig,
     watermark: Option<&WatermarkConfig>,
+    output_format: OutputFormat,
 ) -> MediaResult<()> {
     let left_bias = split_info.left_vertical_bias(height);
     let right_bias = split_info.right_vertical_bias(height);
@@ -276,7 +281,8 @@ pub async fn render_standard_split(
     // Use SinglePassRenderer for standard split
     let mut renderer = super::single_pass_renderer::SinglePassRenderer::new(
         super::config::IntelligentCropConfig::default(),
-    );
+    )
+    .with_output_format(output_format);
     if let Some(config) = watermark {
         renderer = renderer.with_watermark(config.clone());
     }
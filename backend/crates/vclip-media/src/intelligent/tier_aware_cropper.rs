@@ -29,7 +29,7 @@ use vclip_models::{ClipTask, DetectionTier, EncodingConfig};
 use super::config::IntelligentCropConfig;
 use super::crop_planner::CropPlanner;
 use super::detection_adapter::get_detections;
-use super::models::AspectRatio;
+use super::output_format::OutputFormat;
 use super::premium::{PremiumCameraPlanner, PremiumSpeakerConfig};
 use super::single_pass_renderer::SinglePassRenderer;
 use super::tier_aware_smoother::TierAwareCameraSmoother;
@@ -45,12 +45,23 @@ use crate::watermark::WatermarkConfig;
 pub struct TierAwareIntelligentCropper {
     config: IntelligentCropConfig,
     tier: DetectionTier,
+    output_format: OutputFormat,
 }
 
 impl TierAwareIntelligentCropper {
     /// Create a new tier-aware cropper.
     pub fn new(config: IntelligentCropConfig, tier: DetectionTier) -> Self {
-        Self { config, tier }
+        Self {
+            config,
+            tier,
+            output_format: OutputFormat::default(),
+        }
+    }
+
+    /// Set the output dimensions (default: 1080×1920).
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
     }
 
     /// Create with tier-appropriate configuration.
@@ -165,7 +176,7 @@ impl TierAwareIntelligentCropper {
         let step_start = std::time::Instant::now();
         info!("[INTELLIGENT_FULL] Step 3/4: Computing smooth camera path...");
 
-        let target_aspect = AspectRatio::new(9, 16);
+        let target_aspect = self.output_format.crop_aspect();
 
         // Use premium camera planner for SpeakerAware tier (intelligent_speaker)
         let (camera_keyframes, crop_windows) = if matches!(self.tier, DetectionTier::SpeakerAware) {
@@ -217,7 +228,8 @@ impl TierAwareIntelligentCropper {
             encoding.codec, encoding.preset, encoding.crf
         );
 
-        let mut renderer = SinglePassRenderer::new(self.config.clone());
+        let mut renderer =
+            SinglePassRenderer::new(self.config.clone()).with_output_format(self.output_format);
         if let Some(config) = watermark {
             renderer = renderer.with_watermark(config.clone());
         }
@@ -362,7 +374,8 @@ where
     info!("[PIPELINE] Step 2/2: Process segment (SINGLE ENCODE)...");
 
     let config = IntelligentCropConfig::for_tier(tier);
-    let cropper = TierAwareIntelligentCropper::new(config, tier);
+    let cropper = TierAwareIntelligentCropper::new(config, tier)
+        .with_output_format(OutputFormat::for_task(task));
     let result = cropper
         .process_with_cached_detections(
             segment_path.as_path(),
@@ -27,6 +27,7 @@ use super::detection_adapter::{
     compute_speaker_split_boxes, compute_split_info_from_detections, extract_split_info,
     get_detections,
 };
+use super::output_format::OutputFormat;
 use super::single_pass_renderer::SinglePassRenderer;
 use super::split_renderer;
 use crate::clip::extract_segment;
@@ -39,12 +40,23 @@ use crate::watermark::WatermarkConfig;
 pub struct TierAwareSplitProcessor {
     config: IntelligentCropConfig,
     tier: DetectionTier,
+    output_format: OutputFormat,
 }
 
 impl TierAwareSplitProcessor {
     /// Create a new tier-aware split processor.
     pub fn new(config: IntelligentCropConfig, tier: DetectionTier) -> Self {
-        Self { config, tier }
+        Self {
+            config,
+            tier,
+            output_format: OutputFormat::default(),
+        }
+    }
+
+    /// Set the output dimensions (default: 1080×1920).
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
     }
 
     /// Create with tier-appropriate configuration.
@@ -163,7 +175,8 @@ impl TierAwareSplitProcessor {
             let cropper = super::tier_aware_cropper::TierAwareIntelligentCropper::new(
                 self.config.clone(),
                 self.tier,
-            );
+            )
+            .with_output_format(self.output_format);
             cropper
                 .process_with_cached_detections(
                     segment,
@@ -201,7 +214,15 @@ impl TierAwareSplitProcessor {
                     compute_speaker_split_boxes(analysis, width, height)
                 {
                     match split_renderer::render_speaker_split(
-                        segment, output, width, height, &left_box, &right_box, encoding, watermark,
+                        segment,
+                        output,
+                        width,
+                        height,
+                        &left_box,
+                        &right_box,
+                        encoding,
+                        watermark,
+                        self.output_format,
                     )
                     .await
                     {
@@ -244,7 +265,8 @@ impl TierAwareSplitProcessor {
             encoding.codec, encoding.preset, encoding.crf
         );
 
-        let mut renderer = SinglePassRenderer::new(self.config.clone());
+        let mut renderer =
+            SinglePassRenderer::new(self.config.clone()).with_output_format(self.output_format);
         if let Some(config) = watermark {
             renderer = renderer.with_watermark(config.clone());
         }
@@ -382,7 +404,8 @@ where
     info!("[PIPELINE] Step 2/2: Process segment (SINGLE ENCODE)...");
 
     let config = IntelligentCropConfig::for_tier(tier);
-    let processor = TierAwareSplitProcessor::new(config, tier);
+    let processor =
+        TierAwareSplitProcessor::new(config, tier).with_output_format(OutputFormat::for_task(task));
     let result = processor
         .process_with_cached_detections(
             segment_path.as_path(),
@@ -21,7 +21,7 @@ use vclip_models::{ClipTask, DetectionTier, EncodingConfig, SceneNeuralAnalysis}
 use super::config::IntelligentCropConfig;
 use super::crop_planner::CropPlanner;
 use super::detection_adapter::get_detections;
-use super::models::AspectRatio;
+use super::output_format::OutputFormat;
 use super::renderer::IntelligentRenderer;
 use super::tier_aware_smoother::TierAwareCameraSmoother;
 use crate::clip::extract_segment;
@@ -36,12 +36,23 @@ use crate::thumbnail::generate_thumbnail;
 pub struct VisualActivityCropper {
     config: IntelligentCropConfig,
     tier: DetectionTier,
+    output_format: OutputFormat,
 }
 
 impl VisualActivityCropper {
     /// Create a new visual activity cropper.
     pub fn new(config: IntelligentCropConfig, tier: DetectionTier) -> Self {
-        Self { config, tier }
+        Self {
+            config,
+            tier,
+            output_format: OutputFormat::default(),
+        }
+    }
+
+    /// Set the output dimensions (default: 1080×1920).
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
     }
 
     /// Create with default configuration for MotionAware tier.
@@ -129,13 +140,14 @@ impl VisualActivityCropper {
         // 4. Compute crop windows
         info!("Step 3/3: Computing crop windows...");
         let planner = CropPlanner::new(self.config.clone(), width, height);
-        let target_aspect = AspectRatio::new(9, 16); // Portrait 9:16
+        let target_aspect = self.output_format.crop_aspect();
         let crop_windows = planner.compute_crop_windows(&camera_keyframes, &target_aspect);
         info!("  Generated {} crop windows", crop_windows.len());
 
         // 5. Render the output
         info!("Rendering output...");
-        let renderer = IntelligentRenderer::new(self.config.clone());
+        let renderer =
+            IntelligentRenderer::new(self.config.clone()).with_output_format(self.output_format);
         renderer
             .render(input, output, &crop_windows, start_time, duration)
             .await?;
@@ -226,7 +238,8 @@ where
 
     // Step 2: Apply visual activity cropping (using cache if available)
     let config = IntelligentCropConfig::default();
-    let cropper = VisualActivityCropper::new(config, tier);
+    let cropper =
+        VisualActivityCropper::new(config, tier).with_output_format(OutputFormat::for_task(task));
     let result = cropper
         .process_with_cached_detections(segment_path.as_path(), output, cached_analysis)
         .await;
@@ -5,25 +5,41 @@
 //! - Type-safe FFmpeg command building
 //! - Progress parsing from `-progress pipe:2`
 //! - Cancellation support via tokio
-//! - All video operations (clip, segment, stack, thumbnail)
+//! - All video operations (clip, segment, stack, thumbnail, captions)
 //! - Intelligent cropping with face detection and tracking
+//! - Offline forced alignment for word-level transcript timings
+//! - Offline speech-to-text for sources without captions
+//! - Live stream recording into rolling segments
+//! - Highlight boundary refinement (sentence, silence and shot snapping)
+//! - Platform export variants (duration, resolution, bitrate and loudness limits)
+//! - Audio post-processing (two-pass EBU R128 loudness normalization, cleanup, limiter)
+//! - Signal-based highlight detection (loudness, speech, shot cuts, motion and stream chat rate)
 //! - Modular style processing architecture with security, performance, and observability
 
+pub mod alignment;
+pub mod audio;
+pub mod boundaries;
+pub mod captions;
+pub mod chat_replay;
 pub mod clip;
 pub mod command;
 pub mod core;
 pub mod detection;
 pub mod download;
 pub mod error;
+pub mod excitement;
+pub mod export;
 pub mod filters;
 pub mod fs_utils;
 pub mod intelligent;
 pub mod ipv6_rotation;
+pub mod live;
 pub mod probe;
 pub mod progress;
 pub mod silence_removal;
 pub mod styles;
 pub mod thumbnail;
+pub mod transcription;
 pub mod watermark;
 
 // Core architecture exports
@@ -43,6 +59,9 @@ pub use core::{
 pub use styles::StyleProcessorFactory;
 
 // Existing exports for backward compatibility
+pub use audio::{apply_audio_stage, measure_loudness_timeline};
+pub use captions::{apply_captions, CaptionConfig};
+pub use chat_replay::ChatReplay;
 pub use clip::{create_clip, extract_segment};
 pub use command::{create_ffmpeg_command, FfmpegCommand, FfmpegRunner};
 pub use download::{
@@ -50,16 +69,25 @@ pub use download::{
     likely_supports_segment_download, SegmentDownloadNotSupported,
 };
 pub use error::{MediaError, MediaResult};
+pub use excitement::{
+    analyze_excitement, ExcitementCandidate, ExcitementConfig, ExcitementProfile,
+};
+pub use export::{render_export_variant, ExportedVariant};
 pub use intelligent::create_intelligent_clip;
+pub use live::{concat_segments, LiveRecorder, LiveRecorderConfig, RecordedSegment};
 // Note: create_intelligent_split_clip is deprecated - use create_tier_aware_split_clip_with_cache instead
 #[deprecated(
     since = "0.1.0",
     note = "Use create_tier_aware_split_clip_with_cache from intelligent module instead"
 )]
 pub use intelligent::create_intelligent_split_clip;
-pub use probe::{probe_video, VideoInfo};
+pub use probe::{has_audio_stream, probe_video, VideoInfo};
 pub use progress::{FfmpegProgress, ProgressCallback};
 pub use thumbnail::generate_thumbnail;
+pub use transcription::{
+    format_transcript, is_transcription_available, transcribe_file, TranscriptSegment,
+    TranscriptionConfig,
+};
 pub use watermark::{
     apply_watermark, apply_watermark_if_available, WatermarkConfig, DEFAULT_WATERMARK_PATH,
 };
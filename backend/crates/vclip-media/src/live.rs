@@ -0,0 +1,316 @@
+//! Live stream recording into rolling segments.
+//!
+//! yt-dlp pulls the live HLS stream and pipes it into FFmpeg's segment
+//! muxer, which cuts it into standalone MP4 files of roughly equal length
+//! on keyframes without re-encoding. FFmpeg appends each finished segment
+//! to a CSV list with its start and end time, which is how callers find out
+//! what is safe to upload.
+//!
+//! Killing yt-dlp ends the input; FFmpeg then closes the open segment and
+//! exits, so [`LiveRecorder::stop`] never loses recorded footage.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::process::{Child, Command};
+use tracing::{debug, info, warn};
+use vclip_models::is_stream_platform_url;
+
+use crate::download::get_writable_cookies_path;
+use crate::error::{MediaError, MediaResult};
+
+/// File FFmpeg lists finished segments in.
+const SEGMENT_LIST_FILE: &str = "segments.csv";
+
+/// How long FFmpeg gets to close the last segment after the input ends.
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Configuration for recording a live stream.
+#[derive(Debug, Clone)]
+pub struct LiveRecorderConfig {
+    /// Target segment length in seconds (segments are cut on keyframes)
+    pub segment_secs: u32,
+    /// Record from the start of the broadcast instead of the live edge
+    pub from_start: bool,
+}
+
+impl Default for LiveRecorderConfig {
+    fn default() -> Self {
+        Self {
+            segment_secs: 30,
+            from_start: false,
+        }
+    }
+}
+
+/// A finished segment on disk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedSegment {
+    pub path: PathBuf,
+    /// Start in seconds from the beginning of the recording
+    pub start_secs: f64,
+    /// End in seconds from the beginning of the recording
+    pub end_secs: f64,
+}
+
+/// A running live stream recording.
+pub struct LiveRecorder {
+    yt_dlp: Child,
+    ffmpeg: Child,
+    out_dir: PathBuf,
+}
+
+impl LiveRecorder {
+    /// Start recording `url` into segments in `out_dir`.
+    pub async fn start(
+        url: &str,
+        out_dir: &Path,
+        config: &LiveRecorderConfig,
+    ) -> MediaResult<Self> {
+        which::which("yt-dlp").map_err(|_| MediaError::YtDlpNotFound)?;
+        which::which("ffmpeg").map_err(|_| MediaError::FfmpegNotFound)?;
+        tokio::fs::create_dir_all(out_dir).await?;
+
+        let mut yt_dlp_args: Vec<String> = [
+            "--no-part",
+            "--no-playlist",
+            "--hls-use-mpegts",
+            "-f",
+            "best",
+            "-o",
+            "-",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        if config.from_start {
+            yt_dlp_args.push("--live-from-start".to_string());
+        }
+        if !is_stream_platform_url(url) {
+            if let Some(cookies) = get_writable_cookies_path().await {
+                yt_dlp_args.push("--cookies".to_string());
+                yt_dlp_args.push(cookies);
+            }
+        }
+        yt_dlp_args.push(url.to_string());
+
+        let mut yt_dlp = Command::new("yt-dlp")
+            .args(&yt_dlp_args)
+            .kill_on_drop(true)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(log_file(out_dir, "yt-dlp.log")?)
+            .spawn()?;
+        let stream: Stdio = yt_dlp
+            .stdout
+            .take()
+            .ok_or_else(|| MediaError::internal("yt-dlp stdout not captured"))?
+            .try_into()?;
+
+        let ffmpeg = crate::command::create_ffmpeg_command()
+            .args(segment_args(out_dir, config.segment_secs))
+            .stdin(stream)
+            .stdout(Stdio::null())
+            .stderr(log_file(out_dir, "ffmpeg.log")?)
+            .spawn()?;
+
+        info!(
+            url = %url,
+            segment_secs = config.segment_secs,
+            from_start = config.from_start,
+            "Started live stream recording"
+        );
+        Ok(Self {
+            yt_dlp,
+            ffmpeg,
+            out_dir: out_dir.to_path_buf(),
+        })
+    }
+
+    /// Segments FFmpeg has finished writing, in recording order.
+    pub async fn finished_segments(&self) -> MediaResult<Vec<RecordedSegment>> {
+        match tokio::fs::read_to_string(self.out_dir.join(SEGMENT_LIST_FILE)).await {
+            Ok(content) => Ok(parse_segment_list(&content, &self.out_dir)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Whether the recording has ended on its own (the stream went offline
+    /// or the download failed).
+    pub fn has_ended(&mut self) -> MediaResult<bool> {
+        Ok(self.ffmpeg.try_wait()?.is_some())
+    }
+
+    /// Why the recording ended, from the tail of the yt-dlp and FFmpeg logs.
+    pub async fn failure_reason(&self) -> Option<String> {
+        for log in ["yt-dlp.log", "ffmpeg.log"] {
+            let content = tokio::fs::read_to_string(self.out_dir.join(log))
+                .await
+                .unwrap_or_default();
+            let error = content
+                .lines()
+                .rev()
+                .find(|line| line.contains("ERROR") || line.contains("Error"));
+            if let Some(line) = error {
+                return Some(line.trim().to_string());
+            }
+        }
+        None
+    }
+
+    /// Stop recording and wait for FFmpeg to close the last segment.
+    ///
+    /// Returns every finished segment, including the last one.
+    pub async fn stop(mut self) -> MediaResult<Vec<RecordedSegment>> {
+        if let Err(e) = self.yt_dlp.start_kill() {
+            debug!("yt-dlp already exited: {}", e);
+        }
+        let _ = self.yt_dlp.wait().await;
+
+        match tokio::time::timeout(STOP_GRACE_PERIOD, self.ffmpeg.wait()).await {
+            Ok(status) => {
+                debug!(status = ?status?, "Live recording stopped");
+            }
+            Err(_) => {
+                warn!("FFmpeg did not finish the last segment in time, killing it");
+                self.ffmpeg.kill().await?;
+            }
+        }
+        self.finished_segments().await
+    }
+}
+
+fn log_file(dir: &Path, name: &str) -> MediaResult<Stdio> {
+    Ok(Stdio::from(std::fs::File::create(dir.join(name))?))
+}
+
+/// FFmpeg arguments cutting stdin into MP4 segments listed in a CSV file.
+fn segment_args(out_dir: &Path, segment_secs: u32) -> Vec<String> {
+    vec![
+        "-hide_banner".to_string(),
+        "-loglevel".to_string(),
+        "error".to_string(),
+        "-i".to_string(),
+        "pipe:0".to_string(),
+        "-map".to_string(),
+        "0:v:0?".to_string(),
+        "-map".to_string(),
+        "0:a:0?".to_string(),
+        "-c".to_string(),
+        "copy".to_string(),
+        "-f".to_string(),
+        "segment".to_string(),
+        "-segment_time".to_string(),
+        segment_secs.max(1).to_string(),
+        "-segment_format".to_string(),
+        "mp4".to_string(),
+        "-reset_timestamps".to_string(),
+        "1".to_string(),
+        "-segment_list".to_string(),
+        out_dir.join(SEGMENT_LIST_FILE).to_string_lossy().to_string(),
+        "-segment_list_type".to_string(),
+        "csv".to_string(),
+        out_dir.join("seg_%05d.mp4").to_string_lossy().to_string(),
+    ]
+}
+
+/// Parse FFmpeg's CSV segment list (`file,start,end` per line).
+///
+/// Paths are resolved against `dir`; malformed lines are skipped.
+pub fn parse_segment_list(content: &str, dir: &Path) -> Vec<RecordedSegment> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().rsplitn(3, ',');
+            let end_secs: f64 = fields.next()?.parse().ok()?;
+            let start_secs: f64 = fields.next()?.parse().ok()?;
+            let file = fields.next()?.trim_matches('"');
+            (!file.is_empty() && end_secs >= start_secs).then(|| RecordedSegment {
+                path: dir.join(file),
+                start_secs,
+                end_secs,
+            })
+        })
+        .collect()
+}
+
+/// Join segments into one MP4 without re-encoding.
+pub async fn concat_segments(inputs: &[PathBuf], output: &Path) -> MediaResult<()> {
+    if inputs.is_empty() {
+        return Err(MediaError::internal("No segments to join"));
+    }
+
+    let list_path = output.with_extension("concat.txt");
+    let list: String = inputs
+        .iter()
+        .map(|path| format!("file '{}'\n", path.to_string_lossy().replace('\'', "'\\''")))
+        .collect();
+    tokio::fs::write(&list_path, list).await?;
+
+    let list_arg = list_path.to_string_lossy().to_string();
+    let output_arg = output.to_string_lossy().to_string();
+    let result = crate::command::create_ffmpeg_command()
+        .args([
+            "-y",
+            "-hide_banner",
+            "-loglevel",
+            "error",
+            "-f",
+            "concat",
+            "-safe",
+            "0",
+            "-i",
+            list_arg.as_str(),
+            "-c",
+            "copy",
+            "-movflags",
+            "+faststart",
+            output_arg.as_str(),
+        ])
+        .output()
+        .await;
+    let _ = tokio::fs::remove_file(&list_path).await;
+
+    let result = result?;
+    if !result.status.success() {
+        return Err(MediaError::ffmpeg_failed(
+            "Joining live segments failed",
+            Some(String::from_utf8_lossy(&result.stderr).to_string()),
+            result.status.code(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_segment_list() {
+        let dir = Path::new("/work/live");
+        let content = "seg_00000.mp4,0.000000,30.033333\nseg_00001.mp4,30.033333,60.100000\ngarbage\nseg_00002.mp4,60.1,\n";
+        let segments = parse_segment_list(content, dir);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].path, dir.join("seg_00000.mp4"));
+        assert_eq!(segments[1].start_secs, 30.033333);
+        assert_eq!(segments[1].end_secs, 60.1);
+    }
+
+    #[test]
+    fn test_segment_args_list_finished_segments() {
+        let args = segment_args(Path::new("/work/live"), 0);
+        let value = |flag: &str| {
+            let i = args.iter().position(|a| a == flag).unwrap();
+            args[i + 1].clone()
+        };
+
+        assert_eq!(value("-segment_time"), "1");
+        assert_eq!(value("-segment_list"), "/work/live/segments.csv");
+        assert_eq!(value("-c"), "copy");
+        assert_eq!(args.last().unwrap(), "/work/live/seg_%05d.mp4");
+    }
+}
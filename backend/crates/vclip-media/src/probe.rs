@@ -142,6 +142,44 @@ pub async fn get_duration(path: impl AsRef<Path>) -> MediaResult<f64> {
     Ok(info.duration)
 }
 
+/// Check whether a media file has at least one audio stream.
+pub async fn has_audio_stream(path: impl AsRef<Path>) -> MediaResult<bool> {
+    let path = path.as_ref();
+
+    if !path.exists() {
+        return Err(MediaError::FileNotFound(path.to_path_buf()));
+    }
+
+    which::which("ffprobe").map_err(|_| MediaError::FfprobeNotFound)?;
+
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "quiet",
+            "-select_streams",
+            "a",
+            "-show_entries",
+            "stream=index",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        return Err(MediaError::FfprobeFailed {
+            message: "FFprobe failed".to_string(),
+            stderr: Some(String::from_utf8_lossy(&output.stderr).to_string()),
+        });
+    }
+
+    Ok(!String::from_utf8_lossy(&output.stdout).trim().is_empty())
+}
+
 /// Parse frame rate string (e.g., "30/1" or "29.97").
 fn parse_frame_rate(s: &str) -> Option<f64> {
     if let Some((num, den)) = s.split_once('/') {
@@ -39,7 +39,7 @@ pub enum AnalysisError {
 pub type AnalysisResult<T> = Result<T, AnalysisError>;
 
 /// Sample rate for VAD processing (Silero VAD v5 works best at 16kHz).
-const VAD_SAMPLE_RATE: usize = 16000;
+pub(crate) const VAD_SAMPLE_RATE: usize = 16000;
 
 /// Analyze audio from a video/audio file and return Keep/Cut segments.
 ///
@@ -126,6 +126,42 @@ pub async fn analyze_audio_segments(
     Ok(segments)
 }
 
+/// Extract audio from a video/audio file as 16kHz mono f32 samples.
+///
+/// Shared with other audio models (e.g. forced alignment) that expect the
+/// same input format as the VAD.
+pub(crate) async fn extract_audio_samples(input_path: &Path) -> AnalysisResult<Vec<f32>> {
+    let temp_audio = NamedTempFile::new()?;
+    extract_audio_for_vad(input_path, temp_audio.path()).await?;
+    let samples = load_audio_samples(temp_audio.path()).await?;
+
+    if samples.is_empty() {
+        return Err(AnalysisError::NoAudioData);
+    }
+    Ok(samples)
+}
+
+/// Extract audio to a raw 16kHz mono f32le file without loading it.
+///
+/// Returns the number of samples written. Long inputs are read back in
+/// pieces with [`load_audio_chunk`].
+pub(crate) async fn extract_audio_file(input_path: &Path, output: &Path) -> AnalysisResult<usize> {
+    extract_audio_for_vad(input_path, output).await?;
+    let bytes = tokio::fs::metadata(output).await?.len();
+    Ok((bytes / 4) as usize)
+}
+
+/// Load up to `count` samples starting at sample `start` from a raw f32le file.
+pub(crate) async fn load_audio_chunk(path: &Path, start: usize, count: usize) -> AnalysisResult<Vec<f32>> {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    let mut file = tokio::fs::File::open(path).await?;
+    file.seek(std::io::SeekFrom::Start(start as u64 * 4)).await?;
+    let mut bytes = Vec::with_capacity(count * 4);
+    file.take(count as u64 * 4).read_to_end(&mut bytes).await?;
+    Ok(samples_from_bytes(&bytes))
+}
+
 /// Extract audio from a video file to 16kHz mono raw PCM.
 ///
 /// Uses FFmpeg to convert any input format to the format expected by VAD.
@@ -178,14 +214,15 @@ async fn extract_audio_for_vad(input: &Path, output: &Path) -> AnalysisResult<()
 /// Load raw f32le audio samples from a file.
 async fn load_audio_samples(path: &Path) -> AnalysisResult<Vec<f32>> {
     let bytes = tokio::fs::read(path).await?;
+    Ok(samples_from_bytes(&bytes))
+}
 
-    // Convert bytes to f32 samples (4 bytes per sample, little-endian)
-    let samples: Vec<f32> = bytes
+/// Convert raw bytes to f32 samples (4 bytes per sample, little-endian).
+fn samples_from_bytes(bytes: &[u8]) -> Vec<f32> {
+    bytes
         .chunks_exact(4)
         .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
-        .collect();
-
-    Ok(samples)
+        .collect()
 }
 
 /// Debug helper: dump VAD output to a JSON file for analysis.
@@ -46,6 +46,9 @@ pub use analyze::analyze_audio_segments;
 pub use apply::{apply_silence_removal, should_apply_silence_removal};
 pub use config::SilenceRemovalConfig;
 pub use segmenter::{compute_segment_stats, Segment, SegmentLabel, SegmentStats, SilenceRemover};
+pub use vad::SileroVad;
+
+pub(crate) use analyze::{extract_audio_file, extract_audio_samples, load_audio_chunk, VAD_SAMPLE_RATE};
 
 /// Default configuration optimized for streamer content.
 ///
@@ -109,12 +109,17 @@ mod tests {
                 style: Style::Split,
                 crop_mode: Default::default(),
                 target_aspect: Default::default(),
+                resolution: Default::default(),
                 priority: 1,
                 pad_before: 0.0,
                 pad_after: 0.0,
                 streamer_split_params: None,
                 streamer_params: None,
                 cut_silent_parts: false,
+                captions: None,
+                caption_words: Vec::new(),
+                export_profiles: Vec::new(),
+                audio: None,
             },
             input_path,
             temp_dir.path().join("output.mp4"),
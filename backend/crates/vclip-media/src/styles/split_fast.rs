@@ -13,7 +13,7 @@ use crate::core::{
     ProcessingComplexity, ProcessingContext, ProcessingRequest, ProcessingResult, StyleProcessor,
 };
 use crate::error::MediaResult;
-use crate::intelligent::{parse_timestamp, FastSplitEngine};
+use crate::intelligent::{parse_timestamp, FastSplitEngine, OutputFormat};
 use crate::probe::probe_video;
 use crate::thumbnail::generate_thumbnail;
 use vclip_models::Style;
@@ -99,7 +99,8 @@ impl StyleProcessor for SplitFastProcessor {
 
         // Process with FastSplitEngine - convert Arc<Path> to Path ref
         let output_pathbuf = request.output_path.to_path_buf();
-        let engine = FastSplitEngine::new();
+        let engine =
+            FastSplitEngine::new().with_output_format(OutputFormat::for_task(&request.task));
         engine
             .process(
                 &segment_path,
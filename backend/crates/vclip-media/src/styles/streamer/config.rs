@@ -1,5 +1,7 @@
 //! Configuration for Streamer style processor.
 
+use crate::intelligent::OutputFormat;
+
 /// Configuration for the Streamer style processor.
 #[derive(Debug, Clone)]
 pub struct StreamerConfig {
@@ -46,11 +48,30 @@ impl StreamerConfig {
         }
     }
 
-    /// Calculate the main video dimensions to fit within the output width.
+    /// Use the dimensions of an output format.
+    pub fn with_output_format(mut self, format: &OutputFormat) -> Self {
+        self.output_width = format.width;
+        self.output_height = format.height;
+        self
+    }
+
+    /// Calculate the main video dimensions to fit within the output frame.
+    ///
+    /// The source fills the output width unless that would overflow the
+    /// output height (e.g. 4:3 sources in a landscape output).
     pub fn calculate_main_video_dimensions(&self, src_width: u32, src_height: u32) -> (u32, u32) {
         let scale_factor = self.output_width as f64 / src_width as f64;
         let main_height = (src_height as f64 * scale_factor) as u32;
 
+        if main_height > self.output_height {
+            let scale_factor = self.output_height as f64 / src_height as f64;
+            let main_width = (src_width as f64 * scale_factor) as u32;
+            return (
+                main_width - (main_width % 2),
+                self.output_height - (self.output_height % 2),
+            );
+        }
+
         // Ensure even dimensions for h264 encoding
         let main_width = self.output_width - (self.output_width % 2);
         let main_height = main_height - (main_height % 2);
@@ -58,6 +79,21 @@ impl StreamerConfig {
         (main_width, main_height)
     }
 
+    /// Calculate the zoomed background dimensions before cropping to the output.
+    ///
+    /// The source is zoomed by `background_zoom`, or more if needed to cover the
+    /// output frame. Dimensions are rounded up to even values.
+    pub fn calculate_background_dimensions(&self, src_width: u32, src_height: u32) -> (u32, u32) {
+        let zoom = (self.background_zoom as f64)
+            .max(self.output_width as f64 / src_width as f64)
+            .max(self.output_height as f64 / src_height as f64);
+        let even_up = |v: f64| (v.ceil() as u32).div_ceil(2) * 2;
+        (
+            even_up(src_width as f64 * zoom).max(self.output_width),
+            even_up(src_height as f64 * zoom).max(self.output_height),
+        )
+    }
+
     /// Calculate the vertical offset to center the main video.
     pub fn calculate_y_offset(&self, main_height: u32) -> u32 {
         (self.output_height - main_height) / 2
@@ -88,6 +124,37 @@ mod tests {
         assert_eq!(h, 606);
     }
 
+    #[test]
+    fn test_dimensions_for_other_formats() {
+        use vclip_models::{AspectRatio, ResolutionPreset};
+
+        // 4:3 source in a 16:9 output is fitted to the output height
+        let config = StreamerConfig::default().with_output_format(&OutputFormat::new(
+            AspectRatio::new(16, 9),
+            ResolutionPreset::P1080,
+        ));
+        assert_eq!(
+            config.calculate_main_video_dimensions(1440, 1080),
+            (1440, 1080)
+        );
+
+        // Default zoom on a 1080p source covers a 1080x1920 output
+        let config = StreamerConfig::default();
+        assert_eq!(
+            config.calculate_background_dimensions(1920, 1080),
+            (3456, 1944)
+        );
+
+        // 4K portrait needs more than the default zoom to cover the frame
+        let config = StreamerConfig::default().with_output_format(&OutputFormat::new(
+            AspectRatio::PORTRAIT,
+            ResolutionPreset::P2160,
+        ));
+        let (bg_w, bg_h) = config.calculate_background_dimensions(1920, 1080);
+        assert!(bg_w >= 2160 && bg_h >= 3840);
+        assert_eq!((bg_w % 2, bg_h % 2), (0, 0));
+    }
+
     #[test]
     fn test_calculate_y_offset() {
         let config = StreamerConfig::default();
@@ -21,19 +21,21 @@ pub fn build_streamer_filter(
     scene_title: Option<&str>,
 ) -> String {
     let (main_width, main_height) = config.calculate_main_video_dimensions(src_width, src_height);
+    let (bg_width, bg_height) = config.calculate_background_dimensions(src_width, src_height);
     let y_offset = config.calculate_y_offset(main_height);
 
     // Build filter complex for landscape-in-portrait with blurred background
     let mut filter = format!(
-        // Background: zoom, blur, and scale to fill portrait
-        "[0:v]scale=iw*{zoom}:ih*{zoom},\
+        // Background: zoom, blur, and scale to fill the output
+        "[0:v]scale={bw}:{bh},\
          crop={ow}:{oh}:(iw-{ow})/2:(ih-{oh})/2,\
          gblur=sigma={blur},\
          format=yuv420p[bg];\
          [0:v]scale={mw}:{mh}:flags=lanczos,\
          format=yuv420p[main];\
          [bg][main]overlay=(W-w)/2:{y_offset}:format=auto",
-        zoom = config.background_zoom,
+        bw = bg_width,
+        bh = bg_height,
         ow = config.output_width,
         oh = config.output_height,
         blur = config.background_blur,
@@ -185,7 +187,7 @@ mod tests {
 
         assert!(filter.contains("crop=720:1280"));
         assert!(filter.contains("sigma=20"));
-        assert!(filter.contains("scale=iw*2:ih*2"));
+        assert!(filter.contains("scale=3840:2160"));
     }
 
     #[test]
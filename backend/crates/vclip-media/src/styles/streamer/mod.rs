@@ -1,6 +1,6 @@
 //! Streamer style processor (Full View).
 //!
-//! Creates a 9:16 portrait video with:
+//! Creates a video in the task's output format (9:16 portrait by default) with:
 //! - Original landscape video centered in the middle
 //! - Blurred/zoomed version of the same video as background
 //! - Background moves in correlation with the main video (no AI detection)
@@ -21,7 +21,7 @@ use vclip_models::{DetectionTier, Style};
 use crate::core::observability::ProcessingLogger;
 use crate::core::{ProcessingContext, ProcessingRequest, ProcessingResult, StyleProcessor};
 use crate::error::MediaResult;
-use crate::intelligent::parse_timestamp;
+use crate::intelligent::{parse_timestamp, OutputFormat};
 
 use super::utils;
 pub use config::StreamerConfig;
@@ -109,6 +109,10 @@ impl StyleProcessor for StreamerProcessor {
 
         // Get streamer params
         let params = request.task.streamer_params.clone().unwrap_or_default();
+        let config = self
+            .config
+            .clone()
+            .with_output_format(&OutputFormat::for_task(&request.task));
 
         if is_top_scenes && params.top_scenes_enabled && !params.top_scenes.is_empty() {
             info!(
@@ -120,7 +124,7 @@ impl StyleProcessor for StreamerProcessor {
                 request.output_path.as_ref(),
                 &request.encoding,
                 &params,
-                &self.config,
+                &config,
                 request.watermark.as_ref(),
             )
             .await?;
@@ -131,7 +135,7 @@ impl StyleProcessor for StreamerProcessor {
                 request.output_path.as_ref(),
                 &request.task,
                 &request.encoding,
-                &self.config,
+                &config,
                 request.watermark.as_ref(),
             )
             .await?;
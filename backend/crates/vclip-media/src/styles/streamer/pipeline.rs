@@ -7,7 +7,7 @@ use vclip_models::{ClipTask, EncodingConfig, StreamerParams, TopSceneEntry};
 
 use crate::clip::extract_segment;
 use crate::error::{MediaError, MediaResult};
-use crate::intelligent::parse_timestamp;
+use crate::intelligent::{parse_timestamp, OutputFormat};
 use crate::probe::probe_video;
 use crate::thumbnail::generate_thumbnail;
 use crate::watermark::{append_watermark_filter_complex, WatermarkConfig};
@@ -425,15 +425,17 @@ async fn log_completion(prefix: &str, start: std::time::Instant, output: &Path)
 /// * `output` - Output path for the compilation video
 /// * `encoding` - Encoding configuration
 /// * `params` - Streamer params containing the TopSceneEntry list
+/// * `output_format` - Output dimensions of the compilation
 pub async fn process_top_scenes_from_segments(
     segment_paths: &[std::path::PathBuf],
     output: &Path,
     encoding: &EncodingConfig,
     params: &StreamerParams,
     watermark: Option<&WatermarkConfig>,
+    output_format: &OutputFormat,
 ) -> MediaResult<()> {
     let pipeline_start = std::time::Instant::now();
-    let config = super::config::StreamerConfig::default();
+    let config = StreamerConfig::default().with_output_format(output_format);
 
     info!("[STREAMER_TOP_SCENES] ========================================");
     info!(
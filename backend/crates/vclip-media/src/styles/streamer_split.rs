@@ -18,17 +18,13 @@ use vclip_models::{DetectionTier, EncodingConfig, StreamerSplitParams, Style};
 use crate::core::observability::ProcessingLogger;
 use crate::core::{ProcessingContext, ProcessingRequest, ProcessingResult, StyleProcessor};
 use crate::error::{MediaError, MediaResult};
-use crate::intelligent::output_format::{SPLIT_PANEL_HEIGHT, SPLIT_PANEL_WIDTH};
+use crate::intelligent::output_format::{make_even, OutputFormat};
 use crate::probe::probe_video;
 use crate::thumbnail::generate_thumbnail;
 use crate::watermark::{append_watermark_filter_complex, WatermarkConfig};
 
 use super::utils;
 
-/// Panel dimensions (each panel is 9:8 aspect ratio)
-const PANEL_WIDTH: u32 = SPLIT_PANEL_WIDTH;
-const PANEL_HEIGHT: u32 = SPLIT_PANEL_HEIGHT;
-
 /// Processor for streamer split video style.
 ///
 /// Creates a split view with:
@@ -153,7 +149,7 @@ impl StyleProcessor for StreamerSplitProcessor {
 
 /// Process a video into streamer split format.
 ///
-/// Creates a 9:16 output with:
+/// Creates an output in the task's format (9:16 by default) with:
 /// - Top panel (9:8): User-specified webcam region
 /// - Bottom panel (9:8): Center-cropped gaming content (no black bars)
 async fn process_streamer_split(
@@ -193,7 +189,8 @@ async fn process_streamer_split(
     let seg_height = segment_info.height;
 
     // Step 3: Compute crop region from user params
-    let crop_region = compute_crop_from_params(params, seg_width, seg_height);
+    let output_format = OutputFormat::for_task(task);
+    let crop_region = compute_crop_from_params(params, seg_width, seg_height, &output_format);
 
     info!(
         "[STREAMER_SPLIT] Crop region: {}x{} at ({}, {}), zoom: {:.1}x",
@@ -210,6 +207,7 @@ async fn process_streamer_split(
         encoding,
         params,
         watermark,
+        &output_format,
     )
     .await?;
 
@@ -256,7 +254,14 @@ struct CropRegion {
 /// - Manual crop (if provided)
 /// - OR Position (horizontal: left/center/right, vertical: top/middle/bottom)
 /// - Zoom level (1.0 = full frame, 2.0 = 2x zoom, etc.)
-fn compute_crop_from_params(params: &StreamerSplitParams, width: u32, height: u32) -> CropRegion {
+///
+/// Preset crops match the shape of a split panel in the output format.
+fn compute_crop_from_params(
+    params: &StreamerSplitParams,
+    width: u32,
+    height: u32,
+    format: &OutputFormat,
+) -> CropRegion {
     // Priority: Manual crop > Position presets
     if let Some(rect) = params.manual_crop {
         // Clamp to 0.0-1.0 to be safe
@@ -282,7 +287,7 @@ fn compute_crop_from_params(params: &StreamerSplitParams, width: u32, height: u3
         };
     }
 
-    let panel_ratio = PANEL_WIDTH as f64 / PANEL_HEIGHT as f64; // 9:8 = 1.125
+    let panel_ratio = format.split_panel_ratio(); // 9:8 = 1.125 for 1080x1920
 
     // Calculate crop size based on zoom level
     // zoom = 1.0 means full frame, zoom = 2.0 means half the frame, etc.
@@ -329,16 +334,16 @@ async fn render_streamer_split(
     encoding: &EncodingConfig,
     params: &StreamerSplitParams,
     watermark: Option<&WatermarkConfig>,
+    format: &OutputFormat,
 ) -> MediaResult<()> {
     // Calculate split dimensions
     // Default 50/50 split (0.5), range 0.1 to 0.9
     let ratio = params.split_ratio.unwrap_or(0.5).clamp(0.1, 0.9);
 
-    // Total height is 1920 (PORTRAIT_HEIGHT)
+    // Total height is the output height (1920 by default)
     // We must ensure heights are even for libx264
-    let total_height = crate::intelligent::output_format::PORTRAIT_HEIGHT;
-    let top_height =
-        crate::intelligent::output_format::make_even((total_height as f32 * ratio) as i32) as u32;
+    let total_height = format.height;
+    let top_height = make_even((total_height as f32 * ratio) as i32) as u32;
 
     // Build filter complex:
     // - Top panel: webcam region scaled preserving aspect ratio (no stretching)
@@ -347,10 +352,10 @@ async fn render_streamer_split(
 
     // Calculate actual webcam scaled height (preserving aspect ratio)
     // The webcam crop has aspect ratio crop.width / crop.height
-    // When scaled to PANEL_WIDTH, the height will be:
-    // scaled_height = PANEL_WIDTH * crop.height / crop.width
+    // When scaled to the output width, the height will be:
+    // scaled_height = format.width * crop.height / crop.width
     let webcam_aspect = crop.width as f64 / crop.height as f64;
-    let scaled_webcam_height = (PANEL_WIDTH as f64 / webcam_aspect).round() as u32;
+    let scaled_webcam_height = (format.width as f64 / webcam_aspect).round() as u32;
     // Clamp to top_height max (in case webcam is very wide)
     let actual_webcam_height = scaled_webcam_height.min(top_height);
 
@@ -359,10 +364,8 @@ async fn render_streamer_split(
     let actual_bottom_height = total_height - actual_webcam_height;
 
     // Ensure even heights for libx264
-    let actual_webcam_height =
-        crate::intelligent::output_format::make_even(actual_webcam_height as i32) as u32;
-    let actual_bottom_height =
-        crate::intelligent::output_format::make_even(actual_bottom_height as i32) as u32;
+    let actual_webcam_height = make_even(actual_webcam_height as i32) as u32;
+    let actual_bottom_height = make_even(actual_bottom_height as i32) as u32;
 
     let base_filter_complex = format!(
         "[0:v]crop={cw}:{ch}:{cx}:{cy},\
@@ -372,7 +375,7 @@ async fn render_streamer_split(
          scale={pw}:{bh}:flags=lanczos,\
          setsar=1,format=yuv420p[bottom];\
          [top][bottom]vstack=inputs=2[vout]",
-        pw = PANEL_WIDTH,
+        pw = format.width,
         wh = actual_webcam_height,
         bh = actual_bottom_height,
         cw = crop.width,
@@ -467,7 +470,7 @@ mod tests {
             manual_crop: None,
             split_ratio: None,
         };
-        let crop = compute_crop_from_params(&params, 1920, 1080);
+        let crop = compute_crop_from_params(&params, 1920, 1080, &OutputFormat::default());
 
         // With 2x zoom, crop should be half the frame width
         assert_eq!(crop.width, 960);
@@ -486,7 +489,7 @@ mod tests {
             manual_crop: None,
             split_ratio: None,
         };
-        let crop = compute_crop_from_params(&params, 1920, 1080);
+        let crop = compute_crop_from_params(&params, 1920, 1080, &OutputFormat::default());
 
         // With 1x zoom, crop should be full frame width
         assert_eq!(crop.width, 1920);
@@ -504,7 +507,7 @@ mod tests {
             manual_crop: None,
             split_ratio: None,
         };
-        let crop = compute_crop_from_params(&params, 1920, 1080);
+        let crop = compute_crop_from_params(&params, 1920, 1080, &OutputFormat::default());
 
         // With 2x zoom, crop should be half the frame width
         assert_eq!(crop.width, 960);
@@ -526,7 +529,7 @@ mod tests {
             }),
             split_ratio: None,
         };
-        let crop = compute_crop_from_params(&params, 1000, 1000);
+        let crop = compute_crop_from_params(&params, 1000, 1000, &OutputFormat::default());
 
         // Should use manual crop (0.1 start, 0.5 width = 100px x, 500px w)
         assert_eq!(crop.x, 100);
@@ -546,7 +549,7 @@ mod tests {
             manual_crop: None,
             split_ratio: None,
         };
-        let crop_low = compute_crop_from_params(&params_low, 1920, 1080);
+        let crop_low = compute_crop_from_params(&params_low, 1920, 1080, &OutputFormat::default());
         // Should be clamped to 1.0 (full frame)
         assert_eq!(crop_low.width, 1920);
 
@@ -558,7 +561,8 @@ mod tests {
             manual_crop: None,
             split_ratio: None,
         };
-        let crop_high = compute_crop_from_params(&params_high, 1920, 1080);
+        let crop_high =
+            compute_crop_from_params(&params_high, 1920, 1080, &OutputFormat::default());
         // Should be clamped to 4.0 (quarter frame)
         assert_eq!(crop_high.width, 480); // 1920 / 4
     }
@@ -0,0 +1,192 @@
+//! Whisper log-mel spectrogram.
+//!
+//! Mirrors `whisper.audio.log_mel_spectrogram`: 25ms Hann windows every 10ms
+//! over 16kHz audio padded or trimmed to 30 seconds, projected onto a
+//! Slaney-normalized mel filterbank and scaled to roughly `[-1, 1]`.
+
+use std::sync::Arc;
+
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+
+/// Sample rate the model was trained on.
+pub const SAMPLE_RATE: usize = 16_000;
+
+/// FFT window length (25ms).
+pub const N_FFT: usize = 400;
+
+/// Hop between frames (10ms).
+pub const HOP_LENGTH: usize = 160;
+
+/// Samples in one 30-second model window.
+pub const CHUNK_SAMPLES: usize = 30 * SAMPLE_RATE;
+
+/// Mel frames in one model window.
+pub const N_FRAMES: usize = CHUNK_SAMPLES / HOP_LENGTH;
+
+/// Frequency bins of a real FFT of `N_FFT` samples.
+const N_BINS: usize = N_FFT / 2 + 1;
+
+/// Computes log-mel spectrograms for one model window at a time.
+pub struct MelSpectrogram {
+    n_mels: usize,
+    /// Row-major `[n_mels, N_BINS]` filterbank
+    filters: Vec<f32>,
+    window: Vec<f32>,
+    fft: Arc<dyn Fft<f32>>,
+}
+
+impl MelSpectrogram {
+    /// Create a spectrogram with `n_mels` bands (80, or 128 for large-v3).
+    pub fn new(n_mels: usize) -> Self {
+        let window = (0..N_FFT)
+            .map(|i| {
+                // Periodic Hann window, as torch.hann_window
+                let phase = 2.0 * std::f32::consts::PI * i as f32 / N_FFT as f32;
+                0.5 - 0.5 * phase.cos()
+            })
+            .collect();
+
+        Self {
+            n_mels,
+            filters: mel_filters(n_mels),
+            window,
+            fft: FftPlanner::new().plan_fft_forward(N_FFT),
+        }
+    }
+
+    /// Number of mel bands.
+    pub fn n_mels(&self) -> usize {
+        self.n_mels
+    }
+
+    /// Compute the row-major `[n_mels, N_FRAMES]` features of up to 30s of audio.
+    ///
+    /// Shorter input is zero-padded; longer input is trimmed.
+    pub fn compute(&self, samples: &[f32]) -> Vec<f32> {
+        let mut audio = vec![0.0f32; CHUNK_SAMPLES];
+        let len = samples.len().min(CHUNK_SAMPLES);
+        audio[..len].copy_from_slice(&samples[..len]);
+
+        // Centered frames: reflect-pad half a window on each side
+        let pad = N_FFT / 2;
+        let mut padded = Vec::with_capacity(CHUNK_SAMPLES + 2 * pad);
+        padded.extend((1..=pad).rev().map(|i| audio[i]));
+        padded.extend_from_slice(&audio);
+        padded.extend((1..=pad).map(|i| audio[CHUNK_SAMPLES - 1 - i]));
+
+        let mut mel = vec![0.0f32; self.n_mels * N_FRAMES];
+        let mut buffer = vec![Complex::new(0.0f32, 0.0); N_FFT];
+        let mut power = vec![0.0f32; N_BINS];
+
+        for frame in 0..N_FRAMES {
+            let offset = frame * HOP_LENGTH;
+            for (i, slot) in buffer.iter_mut().enumerate() {
+                *slot = Complex::new(padded[offset + i] * self.window[i], 0.0);
+            }
+            self.fft.process(&mut buffer);
+            for (bin, p) in power.iter_mut().enumerate() {
+                *p = buffer[bin].norm_sqr();
+            }
+
+            for band in 0..self.n_mels {
+                let filter = &self.filters[band * N_BINS..(band + 1) * N_BINS];
+                let energy: f32 = filter.iter().zip(&power).map(|(w, p)| w * p).sum();
+                mel[band * N_FRAMES + frame] = energy.max(1e-10).log10();
+            }
+        }
+
+        // Limit the dynamic range to 80dB below the peak, then rescale
+        let peak = mel.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        for value in &mut mel {
+            *value = (value.max(peak - 8.0) + 4.0) / 4.0;
+        }
+        mel
+    }
+}
+
+/// Slaney-style mel filterbank (librosa's default), row-major `[n_mels, N_BINS]`.
+fn mel_filters(n_mels: usize) -> Vec<f32> {
+    let max_mel = hz_to_mel(SAMPLE_RATE as f64 / 2.0);
+    let mel_points: Vec<f64> = (0..n_mels + 2)
+        .map(|i| mel_to_hz(max_mel * i as f64 / (n_mels + 1) as f64))
+        .collect();
+    let bin_hz = |bin: usize| bin as f64 * SAMPLE_RATE as f64 / N_FFT as f64;
+
+    let mut filters = vec![0.0f32; n_mels * N_BINS];
+    for band in 0..n_mels {
+        let (lower, center, upper) = (mel_points[band], mel_points[band + 1], mel_points[band + 2]);
+        let norm = 2.0 / (upper - lower);
+        for bin in 0..N_BINS {
+            let hz = bin_hz(bin);
+            let rising = (hz - lower) / (center - lower);
+            let falling = (upper - hz) / (upper - center);
+            let weight = rising.min(falling).max(0.0);
+            filters[band * N_BINS + bin] = (weight * norm) as f32;
+        }
+    }
+    filters
+}
+
+/// Slaney mel scale: linear below 1kHz, logarithmic above.
+fn hz_to_mel(hz: f64) -> f64 {
+    const F_SP: f64 = 200.0 / 3.0;
+    const MIN_LOG_HZ: f64 = 1000.0;
+    let min_log_mel = MIN_LOG_HZ / F_SP;
+    let log_step = 6.4f64.ln() / 27.0;
+    if hz < MIN_LOG_HZ {
+        hz / F_SP
+    } else {
+        min_log_mel + (hz / MIN_LOG_HZ).ln() / log_step
+    }
+}
+
+fn mel_to_hz(mel: f64) -> f64 {
+    const F_SP: f64 = 200.0 / 3.0;
+    const MIN_LOG_HZ: f64 = 1000.0;
+    let min_log_mel = MIN_LOG_HZ / F_SP;
+    let log_step = 6.4f64.ln() / 27.0;
+    if mel < min_log_mel {
+        mel * F_SP
+    } else {
+        MIN_LOG_HZ * (log_step * (mel - min_log_mel)).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mel_scale_roundtrip() {
+        for hz in [0.0, 440.0, 1000.0, 4000.0, 8000.0] {
+            assert!((mel_to_hz(hz_to_mel(hz)) - hz).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_filters_cover_spectrum() {
+        let filters = mel_filters(80);
+        assert_eq!(filters.len(), 80 * N_BINS);
+        // Every band has some weight, and none is negative
+        for band in filters.chunks(N_BINS) {
+            assert!(band.iter().any(|w| *w > 0.0));
+            assert!(band.iter().all(|w| *w >= 0.0));
+        }
+    }
+
+    #[test]
+    fn test_compute_shape_and_range() {
+        let mel = MelSpectrogram::new(80);
+        let tone: Vec<f32> = (0..SAMPLE_RATE)
+            .map(|i| (2.0 * std::f32::consts::PI * 440.0 * i as f32 / SAMPLE_RATE as f32).sin())
+            .collect();
+        let features = mel.compute(&tone);
+
+        assert_eq!(features.len(), 80 * N_FRAMES);
+        let peak = features.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let floor = features.iter().copied().fold(f32::INFINITY, f32::min);
+        // Dynamic range is clamped to 8 (log10) before scaling by 1/4
+        assert!(peak - floor <= 2.0 + 1e-4);
+    }
+}
@@ -0,0 +1,425 @@
+//! Offline speech-to-text for sources without captions.
+//!
+//! Uploaded files and videos without captions still need a timestamped
+//! transcript for highlight detection. This module produces one locally,
+//! without any network calls.
+//!
+//! # Architecture
+//!
+//! ```text
+//! ┌──────────────┐    ┌──────────────┐    ┌──────────────┐    ┌──────────────┐
+//! │ Audio Input  │───►│ Silero VAD   │───►│ Log-mel      │───►│ Whisper      │───► TranscriptSegment[]
+//! │ (16kHz mono) │    │ (≤30s speech │    │ spectrogram  │    │ (ONNX, ort)  │
+//! └──────────────┘    │  windows)    │    └──────────────┘    └──────────────┘
+//!                     └──────────────┘
+//! ```
+//!
+//! Only speech is sent to the model, which keeps music and silence from
+//! turning into hallucinated text and skips most of a long stream's runtime.
+//! Files are decoded to disk and transcribed in 10-minute chunks, so memory
+//! stays flat no matter how long the input is.
+//! The model is a Whisper-family ONNX export on the same ONNX Runtime as the
+//! rest of the crate; see [`WhisperModel`] for the expected files.
+
+mod mel;
+mod whisper;
+
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+use tempfile::NamedTempFile;
+use tracing::{debug, info};
+
+use crate::alignment::detect_speech_regions;
+use crate::error::{MediaError, MediaResult};
+use crate::silence_removal::{extract_audio_file, load_audio_chunk, VAD_SAMPLE_RATE};
+
+pub use mel::MelSpectrogram;
+pub use whisper::{WhisperModel, WhisperTokenizer, WHISPER_MODEL_ENV};
+
+/// Configuration for transcription.
+#[derive(Debug, Clone)]
+pub struct TranscriptionConfig {
+    /// ISO language code (e.g. "en"); detected per window when `None`
+    pub language: Option<String>,
+    /// Speech probability threshold for the VAD
+    pub vad_threshold: f32,
+    /// Pauses shorter than this stay in one window (ms)
+    pub merge_gap_ms: u64,
+    /// Audio kept around each speech window so word edges aren't clipped (ms)
+    pub padding_ms: u64,
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            language: None,
+            vad_threshold: 0.5,
+            merge_gap_ms: 1000,
+            padding_ms: 200,
+        }
+    }
+}
+
+/// A timed piece of transcribed speech.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptSegment {
+    /// Start in seconds from the beginning of the input
+    pub start: f64,
+    /// End in seconds from the beginning of the input
+    pub end: f64,
+    pub text: String,
+}
+
+/// Longest audio window the model accepts, in seconds.
+const MAX_WINDOW_SECS: f64 = 30.0;
+
+/// Audio decoded and transcribed at a time, in seconds.
+const CHUNK_SECS: usize = 600;
+
+/// Extra audio read past a chunk so speech crossing the boundary is complete.
+const CHUNK_OVERLAP_SECS: usize = 15;
+
+/// A piece of the input in samples. Segments starting at or after `keep_end`
+/// belong to the next chunk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AudioChunk {
+    start: usize,
+    end: usize,
+    keep_end: usize,
+}
+
+/// Shared Whisper model, loaded once per process.
+fn shared_model() -> Option<Arc<WhisperModel>> {
+    static MODEL: OnceLock<Option<Arc<WhisperModel>>> = OnceLock::new();
+    MODEL
+        .get_or_init(|| match WhisperModel::load_default() {
+            Ok(model) => {
+                info!("Loaded Whisper speech-to-text model");
+                Some(Arc::new(model))
+            }
+            Err(e) => {
+                info!(error = %e, "Whisper model unavailable, offline transcription disabled");
+                None
+            }
+        })
+        .clone()
+}
+
+/// Whether a speech-to-text model is installed.
+pub fn is_transcription_available() -> bool {
+    shared_model().is_some()
+}
+
+/// Transcribe the speech in a video or audio file.
+pub async fn transcribe_file(
+    input_path: &Path,
+    config: &TranscriptionConfig,
+) -> MediaResult<Vec<TranscriptSegment>> {
+    let audio = NamedTempFile::new()?;
+    let total_samples = extract_audio_file(input_path, audio.path())
+        .await
+        .map_err(|e| {
+            MediaError::internal(format!("Audio extraction for transcription failed: {e}"))
+        })?;
+
+    let chunks = audio_chunks(
+        total_samples,
+        CHUNK_SECS * VAD_SAMPLE_RATE,
+        CHUNK_OVERLAP_SECS * VAD_SAMPLE_RATE,
+    );
+    debug!(chunks = chunks.len(), "Transcribing audio in chunks");
+
+    let mut segments = Vec::new();
+    for chunk in chunks {
+        let samples = load_audio_chunk(audio.path(), chunk.start, chunk.end - chunk.start)
+            .await
+            .map_err(|e| {
+                MediaError::internal(format!("Reading audio for transcription failed: {e}"))
+            })?;
+
+        let config = config.clone();
+        let chunk_segments =
+            tokio::task::spawn_blocking(move || transcribe_samples(&samples, &config))
+                .await
+                .map_err(|e| MediaError::internal(format!("Transcription task failed: {e}")))??;
+
+        let offset = chunk.start as f64 / VAD_SAMPLE_RATE as f64;
+        let keep_end = chunk.keep_end as f64 / VAD_SAMPLE_RATE as f64;
+        append_chunk_segments(&mut segments, chunk_segments, offset, keep_end);
+    }
+
+    Ok(segments)
+}
+
+/// Split `total` samples into chunks of `size` that read `overlap` further.
+///
+/// A remainder that fits in the overlap is folded into the last chunk.
+fn audio_chunks(total: usize, size: usize, overlap: usize) -> Vec<AudioChunk> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < total {
+        if total - start <= size + overlap {
+            chunks.push(AudioChunk {
+                start,
+                end: total,
+                keep_end: total,
+            });
+            break;
+        }
+        chunks.push(AudioChunk {
+            start,
+            end: start + size + overlap,
+            keep_end: start + size,
+        });
+        start += size;
+    }
+    chunks
+}
+
+/// Shift a chunk's segments to input time and append the ones it owns.
+///
+/// Segments starting in the overlap are left to the next chunk, and speech
+/// the previous chunk already covered past its boundary is not repeated.
+fn append_chunk_segments(
+    segments: &mut Vec<TranscriptSegment>,
+    chunk_segments: Vec<TranscriptSegment>,
+    offset: f64,
+    keep_end: f64,
+) {
+    let covered_until = segments.last().map_or(0.0, |last| last.end);
+    for segment in chunk_segments {
+        let start = offset + segment.start;
+        let end = offset + segment.end;
+        // Mostly inside speech the previous chunk transcribed: a repeat
+        if start >= keep_end || (start + end) / 2.0 < covered_until {
+            continue;
+        }
+        if segments.last().is_some_and(|last| last.text == segment.text) {
+            continue;
+        }
+        segments.push(TranscriptSegment {
+            start,
+            end,
+            text: segment.text,
+        });
+    }
+}
+
+/// Transcribe 16kHz mono samples (blocking).
+pub fn transcribe_samples(
+    samples: &[f32],
+    config: &TranscriptionConfig,
+) -> MediaResult<Vec<TranscriptSegment>> {
+    let model = shared_model().ok_or_else(|| {
+        MediaError::ModelNotFound("No speech-to-text model installed".to_string())
+    })?;
+
+    let regions = detect_speech_regions(samples, config.vad_threshold, config.merge_gap_ms)?;
+    let total_secs = samples.len() as f64 / VAD_SAMPLE_RATE as f64;
+    let windows = speech_windows(&regions, config.padding_ms as f64 / 1000.0, total_secs);
+    debug!(
+        regions = regions.len(),
+        windows = windows.len(),
+        "Transcribing speech windows"
+    );
+
+    let mut segments: Vec<TranscriptSegment> = Vec::new();
+    for (start, end) in windows {
+        let from = (start * VAD_SAMPLE_RATE as f64) as usize;
+        let to = ((end * VAD_SAMPLE_RATE as f64) as usize).min(samples.len());
+        if to <= from {
+            continue;
+        }
+
+        for segment in model.transcribe_window(&samples[from..to], config.language.as_deref())? {
+            // Repeated lines are a typical decoding loop on noisy audio
+            if segments.last().is_some_and(|last| last.text == segment.text) {
+                continue;
+            }
+            segments.push(TranscriptSegment {
+                start: start + segment.start,
+                end: (start + segment.end).min(end),
+                text: segment.text,
+            });
+        }
+    }
+
+    info!(
+        segments = segments.len(),
+        duration_secs = total_secs,
+        "Transcribed audio offline"
+    );
+    Ok(segments)
+}
+
+/// Pack speech regions into padded windows of at most 30 seconds.
+///
+/// Regions are grouped greedily; a region longer than a window is split.
+fn speech_windows(regions: &[(f64, f64)], padding_secs: f64, total_secs: f64) -> Vec<(f64, f64)> {
+    let mut windows: Vec<(f64, f64)> = Vec::new();
+    for &(start, end) in regions {
+        let start = (start - padding_secs).max(0.0);
+        let end = (end + padding_secs).min(total_secs);
+
+        match windows.last_mut() {
+            Some(last) if end - last.0 <= MAX_WINDOW_SECS => last.1 = last.1.max(end),
+            _ => {
+                // Start from the previous window's end so padding doesn't overlap
+                let mut cursor = windows.last().map_or(start, |last| start.max(last.1));
+                while end - cursor > MAX_WINDOW_SECS {
+                    windows.push((cursor, cursor + MAX_WINDOW_SECS));
+                    cursor += MAX_WINDOW_SECS;
+                }
+                if end > cursor {
+                    windows.push((cursor, end));
+                }
+            }
+        }
+    }
+    windows
+}
+
+/// Format segments as a `[HH:MM:SS] text` transcript, one segment per line.
+///
+/// This is the format caption-based transcripts use, so the result can go
+/// straight to highlight detection and the transcript cache.
+pub fn format_transcript(segments: &[TranscriptSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| {
+            let secs = segment.start.max(0.0) as u64;
+            format!(
+                "[{:02}:{:02}:{:02}] {}\n",
+                secs / 3600,
+                (secs % 3600) / 60,
+                secs % 60,
+                segment.text
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speech_windows_groups_and_splits() {
+        let regions = [(1.0, 5.0), (8.0, 20.0), (40.0, 105.0)];
+        let windows = speech_windows(&regions, 0.5, 200.0);
+
+        // First two regions fit one window
+        assert_eq!(windows[0], (0.5, 20.5));
+        // The long region is split into 30-second pieces
+        assert_eq!(windows[1], (39.5, 69.5));
+        assert_eq!(windows[2], (69.5, 99.5));
+        assert_eq!(windows[3], (99.5, 105.5));
+        assert!(windows.iter().all(|(s, e)| e - s <= MAX_WINDOW_SECS + 1e-9));
+    }
+
+    #[test]
+    fn test_speech_windows_clamped_to_input() {
+        assert!(speech_windows(&[], 0.2, 10.0).is_empty());
+        assert_eq!(speech_windows(&[(0.1, 9.9)], 0.2, 10.0), vec![(0.0, 10.0)]);
+    }
+
+    #[test]
+    fn test_audio_chunks_overlap_and_fold_remainder() {
+        let chunks = audio_chunks(2500, 1000, 100);
+        assert_eq!(
+            chunks,
+            vec![
+                AudioChunk {
+                    start: 0,
+                    end: 1100,
+                    keep_end: 1000
+                },
+                AudioChunk {
+                    start: 1000,
+                    end: 2100,
+                    keep_end: 2000
+                },
+                AudioChunk {
+                    start: 2000,
+                    end: 2500,
+                    keep_end: 2500
+                },
+            ]
+        );
+
+        // A short remainder doesn't get a chunk of its own
+        assert_eq!(
+            audio_chunks(1050, 1000, 100),
+            vec![AudioChunk {
+                start: 0,
+                end: 1050,
+                keep_end: 1050
+            }]
+        );
+        assert!(audio_chunks(0, 1000, 100).is_empty());
+    }
+
+    #[test]
+    fn test_append_chunk_segments_offsets_and_trims_overlap() {
+        let segment = |start: f64, end: f64, text: &str| TranscriptSegment {
+            start,
+            end,
+            text: text.to_string(),
+        };
+
+        let mut segments = Vec::new();
+        append_chunk_segments(
+            &mut segments,
+            vec![
+                segment(10.0, 14.0, "first"),
+                segment(598.0, 603.0, "across"),
+                segment(605.0, 608.0, "late"),
+            ],
+            0.0,
+            600.0,
+        );
+        // "late" starts in the overlap and belongs to the next chunk
+        assert_eq!(segments.len(), 2);
+
+        append_chunk_segments(
+            &mut segments,
+            vec![
+                segment(0.0, 3.0, "cross tail"),
+                segment(5.0, 8.0, "late"),
+                segment(20.0, 25.0, "next"),
+            ],
+            600.0,
+            1200.0,
+        );
+        assert_eq!(
+            segments,
+            vec![
+                segment(10.0, 14.0, "first"),
+                segment(598.0, 603.0, "across"),
+                segment(605.0, 608.0, "late"),
+                segment(620.0, 625.0, "next"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_transcript() {
+        let segments = [
+            TranscriptSegment {
+                start: 5.4,
+                end: 8.0,
+                text: "Welcome back".to_string(),
+            },
+            TranscriptSegment {
+                start: 3725.0,
+                end: 3730.0,
+                text: "That was close!".to_string(),
+            },
+        ];
+        assert_eq!(
+            format_transcript(&segments),
+            "[00:00:05] Welcome back\n[01:02:05] That was close!\n"
+        );
+    }
+}
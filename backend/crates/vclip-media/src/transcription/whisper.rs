@@ -0,0 +1,515 @@
+//! ONNX Runtime wrapper for a Whisper-family speech recognition model.
+//!
+//! Expects a HuggingFace Optimum export in one directory:
+//! - `encoder_model.onnx`: `input_features [1, n_mels, 3000]` → hidden states
+//! - `decoder_model.onnx`: `input_ids [1, tokens]` + `encoder_hidden_states` → logits
+//! - `tokenizer.json`: byte-level BPE vocabulary and special tokens
+//! - `preprocessor_config.json` (optional): `feature_size` (mel bands, default 80)
+//! - `generation_config.json` (optional): `is_multilingual` (default true)
+//!
+//! Decoding is greedy with timestamp tokens, so each 30-second window comes
+//! back as a few timed segments.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::{Tensor, Value};
+use serde::Deserialize;
+
+use super::mel::{MelSpectrogram, N_FRAMES};
+use super::TranscriptSegment;
+use crate::error::{MediaError, MediaResult};
+
+/// Environment variable overriding the Whisper model directory.
+pub const WHISPER_MODEL_ENV: &str = "WHISPER_MODEL_DIR";
+
+/// Default model directory search paths.
+const MODEL_DIR_CANDIDATES: &[&str] = &[
+    "./backend/models/whisper",
+    "/app/backend/models/whisper",
+    "/app/models/whisper",
+];
+
+/// Decoder context length of every Whisper model.
+const MAX_TOKENS: usize = 448;
+
+/// Text tokens generated per window (Whisper's own sampling limit).
+const MAX_NEW_TOKENS: usize = 224;
+
+/// Seconds per timestamp token step.
+const TIMESTAMP_STEP_SECS: f64 = 0.02;
+
+#[derive(Deserialize)]
+struct TokenizerFile {
+    model: TokenizerModel,
+    #[serde(default)]
+    added_tokens: Vec<AddedToken>,
+}
+
+#[derive(Deserialize)]
+struct TokenizerModel {
+    vocab: HashMap<String, u32>,
+}
+
+#[derive(Deserialize)]
+struct AddedToken {
+    id: u32,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct PreprocessorConfig {
+    feature_size: usize,
+}
+
+#[derive(Deserialize)]
+struct GenerationConfig {
+    #[serde(default = "default_multilingual")]
+    is_multilingual: bool,
+}
+
+fn default_multilingual() -> bool {
+    true
+}
+
+/// Whisper vocabulary and special token IDs.
+pub struct WhisperTokenizer {
+    /// Byte-level BPE pieces, indexed by token ID
+    pieces: Vec<Option<Vec<u8>>>,
+    /// Language code → token ID
+    languages: HashMap<String, u32>,
+    eot: u32,
+    sot: u32,
+    transcribe: Option<u32>,
+    /// First timestamp token (`<|0.00|>`), right after `<|notimestamps|>`
+    timestamp_begin: u32,
+}
+
+impl WhisperTokenizer {
+    /// Parse a HuggingFace `tokenizer.json`.
+    pub fn from_json(json: &str) -> MediaResult<Self> {
+        let file: TokenizerFile = serde_json::from_str(json)?;
+        let byte_decoder = byte_decoder();
+
+        let mut special: HashMap<String, u32> = HashMap::new();
+        let mut max_id = file.model.vocab.values().copied().max().unwrap_or(0);
+        for token in &file.added_tokens {
+            special.insert(token.content.clone(), token.id);
+            max_id = max_id.max(token.id);
+        }
+
+        let mut pieces: Vec<Option<Vec<u8>>> = vec![None; max_id as usize + 1];
+        for (piece, &id) in &file.model.vocab {
+            if special.contains_key(piece) {
+                continue;
+            }
+            let bytes: Option<Vec<u8>> = piece.chars().map(|c| byte_decoder.get(&c).copied()).collect();
+            pieces[id as usize] = bytes;
+        }
+
+        let required = |name: &str| {
+            special.get(name).copied().ok_or_else(|| {
+                MediaError::ModelNotFound(format!("Whisper tokenizer is missing {}", name))
+            })
+        };
+        let no_timestamps = required("<|notimestamps|>")?;
+
+        let languages = special
+            .iter()
+            .filter_map(|(content, &id)| {
+                let code = content.strip_prefix("<|")?.strip_suffix("|>")?;
+                let is_language =
+                    (2..=3).contains(&code.len()) && code.chars().all(|c| c.is_ascii_lowercase());
+                is_language.then(|| (code.to_string(), id))
+            })
+            .collect();
+
+        Ok(Self {
+            pieces,
+            languages,
+            eot: required("<|endoftext|>")?,
+            sot: required("<|startoftranscript|>")?,
+            transcribe: special.get("<|transcribe|>").copied(),
+            timestamp_begin: no_timestamps + 1,
+        })
+    }
+
+    /// Whether a token is a timestamp.
+    fn is_timestamp(&self, token: u32) -> bool {
+        token >= self.timestamp_begin
+    }
+
+    /// Whether a token is plain text (not special, not a timestamp).
+    fn is_text(&self, token: u32) -> bool {
+        token < self.eot
+    }
+
+    /// Decode text tokens, skipping anything special.
+    pub fn decode(&self, tokens: &[u32]) -> String {
+        let bytes: Vec<u8> = tokens
+            .iter()
+            .filter(|&&t| self.is_text(t))
+            .filter_map(|&t| self.pieces.get(t as usize).and_then(|p| p.as_deref()))
+            .flatten()
+            .copied()
+            .collect();
+        String::from_utf8_lossy(&bytes).trim().to_string()
+    }
+
+    /// Split generated tokens into segments timed relative to the window start.
+    ///
+    /// Text between a pair of timestamps becomes one segment; trailing text
+    /// without a closing timestamp runs to `window_secs`.
+    pub fn segments(&self, tokens: &[u32], window_secs: f64) -> Vec<TranscriptSegment> {
+        let mut segments = Vec::new();
+        let mut start = 0.0;
+        let mut text: Vec<u32> = Vec::new();
+
+        for &token in tokens {
+            if self.is_timestamp(token) {
+                let time = (token - self.timestamp_begin) as f64 * TIMESTAMP_STEP_SECS;
+                let decoded = self.decode(&text);
+                if !decoded.is_empty() {
+                    segments.push(TranscriptSegment {
+                        start,
+                        end: time.max(start),
+                        text: decoded,
+                    });
+                }
+                text.clear();
+                start = time;
+            } else if self.is_text(token) {
+                text.push(token);
+            }
+        }
+
+        let decoded = self.decode(&text);
+        if !decoded.is_empty() {
+            segments.push(TranscriptSegment {
+                start,
+                end: window_secs.max(start),
+                text: decoded,
+            });
+        }
+        segments
+    }
+}
+
+/// Inverse of GPT-2's `bytes_to_unicode` table.
+fn byte_decoder() -> HashMap<char, u8> {
+    let mut printable: Vec<u32> = (b'!' as u32..=b'~' as u32)
+        .chain(0xA1..=0xAC)
+        .chain(0xAE..=0xFF)
+        .collect();
+    let mut chars = printable.clone();
+    let mut extra = 0;
+    for byte in 0..256u32 {
+        if !printable.contains(&byte) {
+            printable.push(byte);
+            chars.push(256 + extra);
+            extra += 1;
+        }
+    }
+
+    printable
+        .into_iter()
+        .zip(chars)
+        .filter_map(|(byte, c)| Some((char::from_u32(c)?, byte as u8)))
+        .collect()
+}
+
+/// Whisper encoder and decoder sessions plus the tokenizer.
+pub struct WhisperModel {
+    encoder: Mutex<Session>,
+    decoder: Mutex<Session>,
+    tokenizer: WhisperTokenizer,
+    mel: MelSpectrogram,
+    multilingual: bool,
+}
+
+impl WhisperModel {
+    /// Load the model from the default search paths (or `WHISPER_MODEL_DIR`).
+    pub fn load_default() -> MediaResult<Self> {
+        let model_dir = find_default_model_dir().ok_or_else(|| {
+            MediaError::ModelNotFound(
+                "Whisper model not found; place an ONNX export under backend/models/whisper/".to_string(),
+            )
+        })?;
+        Self::load(&model_dir)
+    }
+
+    /// Load the model files from a directory.
+    pub fn load(model_dir: &Path) -> MediaResult<Self> {
+        let tokenizer_path = model_dir.join("tokenizer.json");
+        let tokenizer_json = std::fs::read_to_string(&tokenizer_path).map_err(|e| {
+            MediaError::ModelNotFound(format!(
+                "Whisper tokenizer not found at {}: {e}",
+                tokenizer_path.display()
+            ))
+        })?;
+        let tokenizer = WhisperTokenizer::from_json(&tokenizer_json)?;
+
+        let n_mels = read_optional_json::<PreprocessorConfig>(&model_dir.join("preprocessor_config.json"))
+            .map(|c| c.feature_size)
+            .unwrap_or(80);
+        let multilingual = read_optional_json::<GenerationConfig>(&model_dir.join("generation_config.json"))
+            .map(|c| c.is_multilingual)
+            .unwrap_or(true);
+
+        Ok(Self {
+            encoder: Mutex::new(load_session(&model_dir.join("encoder_model.onnx"))?),
+            decoder: Mutex::new(load_session(&model_dir.join("decoder_model.onnx"))?),
+            tokenizer,
+            mel: MelSpectrogram::new(n_mels),
+            multilingual,
+        })
+    }
+
+    /// Transcribe up to 30 seconds of 16kHz mono audio.
+    ///
+    /// `language` is an ISO code such as `"en"`; `None` detects it from the
+    /// audio. English-only models ignore it.
+    pub fn transcribe_window(&self, samples: &[f32], language: Option<&str>) -> MediaResult<Vec<TranscriptSegment>> {
+        let window_secs = samples.len() as f64 / super::mel::SAMPLE_RATE as f64;
+        let (hidden_shape, hidden) = self.encode(samples)?;
+
+        let tok = &self.tokenizer;
+        let mut prompt = vec![tok.sot];
+        if self.multilingual {
+            let language = match language.and_then(|code| tok.languages.get(code)) {
+                Some(&id) => id,
+                None => self.detect_language(&hidden_shape, &hidden)?,
+            };
+            prompt.push(language);
+            prompt.extend(tok.transcribe);
+        }
+
+        let mut tokens = prompt.clone();
+        let mut last_timestamp = tok.timestamp_begin;
+        while tokens.len() - prompt.len() < MAX_NEW_TOKENS && tokens.len() < MAX_TOKENS {
+            let mut logits = self.next_token_logits(&tokens, &hidden_shape, &hidden)?;
+            self.apply_timestamp_rules(&mut logits, &tokens[prompt.len()..], last_timestamp);
+
+            let next = argmax(&logits) as u32;
+            if next == tok.eot {
+                break;
+            }
+            if tok.is_timestamp(next) {
+                last_timestamp = next;
+            }
+            tokens.push(next);
+        }
+
+        Ok(tok.segments(&tokens[prompt.len()..], window_secs))
+    }
+
+    /// Run the encoder, returning the hidden state shape and data.
+    fn encode(&self, samples: &[f32]) -> MediaResult<(Vec<usize>, Vec<f32>)> {
+        let features = self.mel.compute(samples);
+        let input: Value = Tensor::from_array((
+            vec![1usize, self.mel.n_mels(), N_FRAMES],
+            features.into_boxed_slice(),
+        ))
+        .map(Value::from)
+        .map_err(|e| MediaError::internal(format!("Failed to create tensor: {e}")))?;
+
+        let mut encoder = self
+            .encoder
+            .lock()
+            .map_err(|_| MediaError::internal("ORT session poisoned"))?;
+        let outputs = encoder
+            .run(ort::inputs!["input_features" => input])
+            .map_err(|e| MediaError::internal(format!("Whisper encoder failed: {e}")))?;
+        let (shape, data) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| MediaError::internal(format!("ORT extract: {e}")))?;
+
+        Ok((shape.iter().map(|&d| d as usize).collect(), data.to_vec()))
+    }
+
+    /// Logits for the token following `tokens`.
+    fn next_token_logits(&self, tokens: &[u32], hidden_shape: &[usize], hidden: &[f32]) -> MediaResult<Vec<f32>> {
+        let ids: Vec<i64> = tokens.iter().map(|&t| t as i64).collect();
+        let input_ids: Value = Tensor::from_array((vec![1usize, ids.len()], ids.into_boxed_slice()))
+            .map(Value::from)
+            .map_err(|e| MediaError::internal(format!("Failed to create tensor: {e}")))?;
+        let states: Value = Tensor::from_array((hidden_shape.to_vec(), hidden.to_vec().into_boxed_slice()))
+            .map(Value::from)
+            .map_err(|e| MediaError::internal(format!("Failed to create tensor: {e}")))?;
+
+        let mut decoder = self
+            .decoder
+            .lock()
+            .map_err(|_| MediaError::internal("ORT session poisoned"))?;
+        let outputs = decoder
+            .run(ort::inputs!["input_ids" => input_ids, "encoder_hidden_states" => states])
+            .map_err(|e| MediaError::internal(format!("Whisper decoder failed: {e}")))?;
+        let (shape, data) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| MediaError::internal(format!("ORT extract: {e}")))?;
+
+        // [1, tokens, vocab]: keep the last position
+        let vocab = *shape.last().unwrap_or(&0) as usize;
+        if vocab == 0 || data.len() < vocab {
+            return Err(MediaError::internal(format!(
+                "Unexpected Whisper decoder output shape: {:?}",
+                shape
+            )));
+        }
+        Ok(data[data.len() - vocab..].to_vec())
+    }
+
+    /// Pick the most likely language token after `<|startoftranscript|>`.
+    fn detect_language(&self, hidden_shape: &[usize], hidden: &[f32]) -> MediaResult<u32> {
+        let logits = self.next_token_logits(&[self.tokenizer.sot], hidden_shape, hidden)?;
+        self.tokenizer
+            .languages
+            .values()
+            .copied()
+            .filter(|&id| (id as usize) < logits.len())
+            .max_by(|&a, &b| logits[a as usize].total_cmp(&logits[b as usize]))
+            .ok_or_else(|| MediaError::internal("Whisper tokenizer has no language tokens"))
+    }
+
+    /// Constrain the next token so timestamps come in ordered pairs.
+    ///
+    /// A simplified version of Whisper's `ApplyTimestampRules`.
+    fn apply_timestamp_rules(&self, logits: &mut [f32], generated: &[u32], last_timestamp: u32) {
+        let tok = &self.tokenizer;
+        let ts_begin = (tok.timestamp_begin as usize).min(logits.len());
+
+        // Only text, timestamps and end-of-text can be generated
+        for (id, logit) in logits.iter_mut().enumerate().take(ts_begin) {
+            if id as u32 != tok.eot && !tok.is_text(id as u32) {
+                *logit = f32::NEG_INFINITY;
+            }
+        }
+
+        let last_was_ts = generated.last().is_some_and(|&t| tok.is_timestamp(t));
+        let penultimate_was_ts = generated.len() < 2 || tok.is_timestamp(generated[generated.len() - 2]);
+
+        if generated.is_empty() {
+            // Start with a timestamp
+            logits[..ts_begin].fill(f32::NEG_INFINITY);
+        } else if last_was_ts && penultimate_was_ts {
+            // A closed pair is followed by text
+            logits[ts_begin..].fill(f32::NEG_INFINITY);
+        } else if last_was_ts {
+            // An opening timestamp is closed by another timestamp or the end
+            logits[..tok.eot as usize].fill(f32::NEG_INFINITY);
+        }
+
+        // Time never goes backwards
+        let floor = (last_timestamp as usize).min(logits.len());
+        logits[ts_begin..floor].fill(f32::NEG_INFINITY);
+    }
+}
+
+fn load_session(path: &Path) -> MediaResult<Session> {
+    if !path.exists() {
+        return Err(MediaError::ModelNotFound(format!(
+            "Whisper model file not found at {}",
+            path.display()
+        )));
+    }
+
+    let model_bytes = std::fs::read(path)
+        .map_err(|e| MediaError::internal(format!("ORT read model file: {e}")))?;
+    Session::builder()
+        .map_err(|e| MediaError::internal(format!("ORT session builder: {e}")))?
+        .with_optimization_level(GraphOptimizationLevel::Level3)
+        .map_err(|e| MediaError::internal(format!("ORT opt level: {e}")))?
+        .commit_from_memory(model_bytes.as_slice())
+        .map_err(|e| MediaError::internal(format!("ORT load model: {e}")))
+}
+
+fn read_optional_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Option<T> {
+    let json = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+fn argmax(values: &[f32]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+fn find_default_model_dir() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var(WHISPER_MODEL_ENV) {
+        let path = PathBuf::from(path);
+        if path.join("encoder_model.onnx").exists() {
+            return Some(path);
+        }
+    }
+
+    MODEL_DIR_CANDIDATES
+        .iter()
+        .map(Path::new)
+        .find(|p| p.join("encoder_model.onnx").exists())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tiny tokenizer: "Ġhello" = 0, "Ġworld" = 1, then the special tokens.
+    fn tokenizer() -> WhisperTokenizer {
+        let json = r#"{
+            "model": {"vocab": {"Ġhello": 0, "Ġworld": 1, "!": 2}},
+            "added_tokens": [
+                {"id": 3, "content": "<|endoftext|>"},
+                {"id": 4, "content": "<|startoftranscript|>"},
+                {"id": 5, "content": "<|en|>"},
+                {"id": 6, "content": "<|de|>"},
+                {"id": 7, "content": "<|transcribe|>"},
+                {"id": 8, "content": "<|notimestamps|>"}
+            ]
+        }"#;
+        WhisperTokenizer::from_json(json).unwrap()
+    }
+
+    #[test]
+    fn test_tokenizer_special_tokens() {
+        let tok = tokenizer();
+        assert_eq!(tok.eot, 3);
+        assert_eq!(tok.sot, 4);
+        assert_eq!(tok.transcribe, Some(7));
+        assert_eq!(tok.timestamp_begin, 9);
+        assert_eq!(tok.languages.get("en"), Some(&5));
+        assert_eq!(tok.languages.len(), 2);
+        assert_eq!(tok.decode(&[0, 1, 2, 3]), "hello world!");
+    }
+
+    #[test]
+    fn test_segments_from_timestamps() {
+        let tok = tokenizer();
+        // <|0.00|> hello <|1.00|><|1.00|> world! <|2.40|><|2.40|> hello
+        let tokens = [9, 0, 9 + 50, 9 + 50, 1, 2, 9 + 120, 9 + 120, 0];
+        let segments = tok.segments(&tokens, 30.0);
+
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].text, "hello");
+        assert!((segments[0].end - 1.0).abs() < 1e-9);
+        assert_eq!(segments[1].text, "world!");
+        assert!((segments[1].start - 1.0).abs() < 1e-9);
+        assert!((segments[1].end - 2.4).abs() < 1e-9);
+        // Unterminated text runs to the end of the window
+        assert!((segments[2].start - 2.4).abs() < 1e-9);
+        assert!((segments[2].end - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_byte_decoder_covers_all_bytes() {
+        let decoder = byte_decoder();
+        assert_eq!(decoder.len(), 256);
+        assert_eq!(decoder.get(&'Ġ'), Some(&b' '));
+        assert_eq!(decoder.get(&'a'), Some(&b'a'));
+    }
+}
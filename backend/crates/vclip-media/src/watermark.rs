@@ -146,7 +146,7 @@ fn build_overlay_filter(config: &WatermarkConfig) -> String {
     }
 }
 
-fn escape_filter_path(path: &str) -> String {
+pub(crate) fn escape_filter_path(path: &str) -> String {
     path.replace('\\', "\\\\")
         .replace('\'', "\\'")
         .replace(':', "\\:")
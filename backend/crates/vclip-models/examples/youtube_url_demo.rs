@@ -1,8 +1,8 @@
-//! Demo: YouTube URL Configuration Generator
+//! Demo: Video URL Configuration Generator
 //!
 //! Run with: cargo run -p vclip-models --example youtube_url_demo
 
-use vclip_models::{analyze_youtube_url, LiveCaptureMode, YoutubeUrlInput};
+use vclip_models::{analyze_video_url, LiveCaptureMode, YoutubeUrlInput};
 
 fn main() {
     let test_urls = [
@@ -12,6 +12,9 @@ fn main() {
         "https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PLrAXtmRdnEQy",
         "https://vimeo.com/123456789",
         "https://www.youtube.com/playlist?list=PLrAXtmRdnEQy",
+        "https://www.twitch.tv/videos/2101234567",
+        "https://clips.twitch.tv/FunnyClipSlug",
+        "https://kick.com/streamer/videos/5b1c9f2e-8a3d-4e6f-9b0a-1c2d3e4f5a6b",
     ];
 
     for url in test_urls {
@@ -27,7 +30,7 @@ fn main() {
             max_expected_duration_sec: 21600,
         };
 
-        let config = analyze_youtube_url(&input);
+        let config = analyze_video_url(&input);
 
         println!(
             "{}",
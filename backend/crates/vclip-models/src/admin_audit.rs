@@ -0,0 +1,48 @@
+//! Audit log of admin actions.
+//!
+//! Admin operations that change shared state (queue recovery, cleanup)
+//! record who did what, with the parameters and outcome as string details.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A recorded admin action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminAuditEntry {
+    /// Unique identifier (UUID)
+    pub id: String,
+
+    /// UID of the admin who performed the action
+    pub admin_uid: String,
+
+    /// Action name, e.g. `dlq.replay`
+    pub action: String,
+
+    /// Parameters and outcome of the action
+    #[serde(default)]
+    pub details: HashMap<String, String>,
+
+    /// When the action was performed
+    pub created_at: DateTime<Utc>,
+}
+
+impl AdminAuditEntry {
+    /// Create an entry for an action performed now.
+    pub fn new(admin_uid: impl Into<String>, action: impl Into<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            admin_uid: admin_uid.into(),
+            action: action.into(),
+            details: HashMap::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Add a detail.
+    pub fn with_detail(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.details.insert(key.into(), value.to_string());
+        self
+    }
+}
@@ -8,6 +8,11 @@ use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    AspectRatio, AudioConfig, CaptionOptions, ExportProfile, LiveCapture, QualityMetrics,
+    ResolutionPreset,
+};
+
 /// Status of an analysis job.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "snake_case")]
@@ -19,6 +24,8 @@ pub enum AnalysisStatus {
     Downloading,
     /// AI is analyzing transcript for highlights
     Analyzing,
+    /// Recording a live stream; scenes are added as they are found
+    Live,
     /// Analysis completed successfully
     Completed,
     /// Analysis failed
@@ -34,6 +41,7 @@ impl AnalysisStatus {
             Self::Pending => "pending",
             Self::Downloading => "downloading",
             Self::Analyzing => "analyzing",
+            Self::Live => "live",
             Self::Completed => "completed",
             Self::Failed => "failed",
             Self::Expired => "expired",
@@ -47,7 +55,10 @@ impl AnalysisStatus {
 
     /// Returns true if the status indicates the job is still in progress.
     pub fn is_in_progress(&self) -> bool {
-        matches!(self, Self::Pending | Self::Downloading | Self::Analyzing)
+        matches!(
+            self,
+            Self::Pending | Self::Downloading | Self::Analyzing | Self::Live
+        )
     }
 }
 
@@ -66,6 +77,10 @@ pub struct AnalysisDraft {
     /// Source video URL (YouTube, etc.)
     pub source_url: String,
 
+    /// R2 key of an uploaded source file (uploads only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_r2_key: Option<String>,
+
     /// Video title extracted from source
     #[serde(skip_serializing_if = "Option::is_none")]
     pub video_title: Option<String>,
@@ -99,6 +114,10 @@ pub struct AnalysisDraft {
 
     /// When the draft expires (TTL)
     pub expires_at: DateTime<Utc>,
+
+    /// Live capture feeding this draft (live streams only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub live_capture: Option<LiveCapture>,
 }
 
 impl AnalysisDraft {
@@ -114,6 +133,7 @@ impl AnalysisDraft {
             id: id.into(),
             user_id: user_id.into(),
             source_url: source_url.into(),
+            source_r2_key: None,
             video_title: None,
             prompt_instructions: None,
             status: AnalysisStatus::Pending,
@@ -124,6 +144,7 @@ impl AnalysisDraft {
             created_at: now,
             updated_at: now,
             expires_at: now + chrono::Duration::days(ttl_days),
+            live_capture: None,
         }
     }
 
@@ -139,12 +160,24 @@ impl AnalysisDraft {
         self
     }
 
+    /// Set the R2 key of an uploaded source file.
+    pub fn with_source_r2_key(mut self, r2_key: impl Into<String>) -> Self {
+        self.source_r2_key = Some(r2_key.into());
+        self
+    }
+
     /// Set the request ID.
     pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
         self.request_id = Some(request_id.into());
         self
     }
 
+    /// Feed the draft from a live capture.
+    pub fn with_live_capture(mut self, live_capture: LiveCapture) -> Self {
+        self.live_capture = Some(live_capture);
+        self
+    }
+
     /// Check if the draft has expired.
     pub fn is_expired(&self) -> bool {
         Utc::now() > self.expires_at
@@ -198,6 +231,14 @@ pub struct DraftScene {
     /// Hook category (emotional, educational, etc.)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hook_category: Option<String>,
+
+    /// Composite virality score (0-1) derived from `quality`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub virality_score: Option<f64>,
+
+    /// Per-scene quality metrics behind the virality score
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<QualityMetrics>,
 }
 
 fn default_pad() -> f64 {
@@ -227,6 +268,8 @@ impl DraftScene {
             pad_after: 1.0,
             confidence: None,
             hook_category: None,
+            virality_score: None,
+            quality: None,
         }
     }
 
@@ -246,13 +289,21 @@ impl DraftScene {
             duration_secs: highlight.duration,
             pad_before: highlight.pad_before,
             pad_after: highlight.pad_after,
-            confidence: None, // Highlights don't have confidence yet
+            confidence: highlight.quality.and_then(|q| q.llm_confidence),
             hook_category: highlight
                 .hook_category
                 .as_ref()
                 .map(|c| format!("{:?}", c).to_lowercase()),
+            virality_score: highlight.virality_score,
+            quality: highlight.quality,
         }
     }
+
+    /// Store quality metrics and recompute the virality score.
+    pub fn set_quality(&mut self, quality: QualityMetrics) {
+        self.virality_score = quality.virality_score();
+        self.quality = (!quality.is_empty()).then_some(quality);
+    }
 }
 
 /// Per-scene selection with render toggles.
@@ -285,9 +336,37 @@ pub struct ProcessDraftRequest {
 
     /// Client-generated idempotency key
     pub idempotency_key: String,
+
+    /// Optional burned-in captions applied to every rendered clip
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captions: Option<CaptionOptions>,
+
+    /// Output aspect ratio as "W:H" (default: 9:16)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_aspect: Option<String>,
+
+    /// Output resolution preset (default: 1080p)
+    #[serde(default)]
+    pub resolution: ResolutionPreset,
+
+    /// Platform export profiles rendered for every clip
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub export_profiles: Vec<ExportProfile>,
+
+    /// Optional audio stage (loudness normalization and cleanup)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioConfig>,
 }
 
 impl ProcessDraftRequest {
+    /// Parsed output aspect ratio (default when not specified).
+    pub fn aspect_ratio(&self) -> Result<AspectRatio, String> {
+        match self.target_aspect.as_deref() {
+            Some(aspect) => aspect.parse().map_err(|e| format!("{}", e)),
+            None => Ok(AspectRatio::default()),
+        }
+    }
+
     /// Count total render jobs that would be created.
     pub fn total_jobs(&self) -> usize {
         self.selected_scenes
@@ -324,6 +403,16 @@ impl ProcessDraftRequest {
             return Err("At least one render toggle must be enabled".to_string());
         }
 
+        self.aspect_ratio()?;
+
+        if self.export_profiles.len() > ExportProfile::ALL.len() {
+            return Err("Too many export profiles".to_string());
+        }
+
+        if let Some(audio) = &self.audio {
+            audio.validate()?;
+        }
+
         Ok(())
     }
 
@@ -365,6 +454,10 @@ pub struct AnalysisStatusResponse {
 
     /// Warning count (if completed)
     pub warning_count: u32,
+
+    /// Live capture progress (live streams only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub live_capture: Option<LiveCapture>,
 }
 
 /// Processing cost estimate.
@@ -404,6 +497,8 @@ mod tests {
         assert!(!AnalysisStatus::Pending.is_terminal());
         assert!(!AnalysisStatus::Downloading.is_terminal());
         assert!(!AnalysisStatus::Analyzing.is_terminal());
+        assert!(!AnalysisStatus::Live.is_terminal());
+        assert!(AnalysisStatus::Live.is_in_progress());
         assert!(AnalysisStatus::Completed.is_terminal());
         assert!(AnalysisStatus::Failed.is_terminal());
         assert!(AnalysisStatus::Expired.is_terminal());
@@ -428,6 +523,11 @@ mod tests {
             full_style: "intelligent".to_string(),
             split_style: "intelligent_split".to_string(),
             idempotency_key: "key-123".to_string(),
+            captions: None,
+            target_aspect: None,
+            resolution: ResolutionPreset::default(),
+            export_profiles: Vec::new(),
+            audio: None,
         };
         assert!(valid_request.validate().is_ok());
         assert_eq!(valid_request.total_jobs(), 1);
@@ -447,5 +547,24 @@ mod tests {
             ..valid_request.clone()
         };
         assert!(no_renders.validate().is_err());
+
+        let square = ProcessDraftRequest {
+            target_aspect: Some("1:1".to_string()),
+            ..valid_request.clone()
+        };
+        assert_eq!(square.aspect_ratio().unwrap(), AspectRatio::SQUARE);
+
+        let bad_aspect = ProcessDraftRequest {
+            target_aspect: Some("wide".to_string()),
+            ..valid_request.clone()
+        };
+        assert!(bad_aspect.validate().is_err());
+
+        let extreme_aspect = ProcessDraftRequest {
+            target_aspect: Some("1:100000".to_string()),
+            ..valid_request.clone()
+        };
+        assert!(extreme_aspect.aspect_ratio().is_err());
+        assert!(extreme_aspect.validate().is_err());
     }
 }
@@ -0,0 +1,179 @@
+//! Personal API keys.
+//!
+//! Users on plans with API access can create keys to call the REST API from
+//! their own backends. The secret is shown once at creation; only its
+//! SHA-256 hash is stored. Each key carries the scopes it may use and can be
+//! revoked at any time.
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Prefix of every API key secret, so keys are recognizable in headers and logs.
+pub const API_KEY_PREFIX: &str = "vck_";
+
+/// Characters of the secret kept as a display hint (prefix included).
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+/// What an API key is allowed to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Read videos, clips, drafts, jobs and delivery URLs
+    Read,
+    /// Start analyses and edit highlights
+    Analyze,
+    /// Render clips and cancel render jobs
+    Render,
+    /// Delete videos, clips and drafts
+    Delete,
+}
+
+impl ApiKeyScope {
+    /// All scopes.
+    pub const ALL: [ApiKeyScope; 4] = [
+        ApiKeyScope::Read,
+        ApiKeyScope::Analyze,
+        ApiKeyScope::Render,
+        ApiKeyScope::Delete,
+    ];
+
+    /// Get string representation of the scope.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::Read => "read",
+            ApiKeyScope::Analyze => "analyze",
+            ApiKeyScope::Render => "render",
+            ApiKeyScope::Delete => "delete",
+        }
+    }
+
+    /// Parse a scope name.
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|scope| scope.as_str() == s)
+    }
+}
+
+impl std::fmt::Display for ApiKeyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A user's API key (without the secret).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApiKey {
+    /// Unique identifier (UUID)
+    pub id: String,
+
+    /// User who owns the key
+    pub user_id: String,
+
+    /// User-chosen label
+    pub name: String,
+
+    /// First characters of the secret, to tell keys apart
+    pub display_prefix: String,
+
+    /// Hex-encoded SHA-256 hash of the secret
+    pub key_hash: String,
+
+    /// Scopes the key may use
+    pub scopes: Vec<ApiKeyScope>,
+
+    /// When the key was created
+    pub created_at: DateTime<Utc>,
+
+    /// When the key was last used (updated at most once a minute)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+
+    /// When the key was revoked (null = active)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Create a key and return it along with its secret.
+    ///
+    /// The secret is not stored anywhere; it must be shown to the user now.
+    pub fn generate(
+        user_id: impl Into<String>,
+        name: impl Into<String>,
+        scopes: Vec<ApiKeyScope>,
+    ) -> (Self, String) {
+        // Two UUID v4s give 244 bits of OS randomness
+        let secret = format!(
+            "{}{}{}",
+            API_KEY_PREFIX,
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        );
+
+        let key = Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            user_id: user_id.into(),
+            name: name.into(),
+            display_prefix: secret[..DISPLAY_PREFIX_LEN].to_string(),
+            key_hash: hash_api_key(&secret),
+            scopes,
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+
+        (key, secret)
+    }
+
+    /// Whether the key has not been revoked.
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+
+    /// Whether the key may use a scope.
+    pub fn has_scope(&self, scope: ApiKeyScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+/// Whether a credential looks like an API key rather than an ID token.
+pub fn is_api_key(credential: &str) -> bool {
+    credential.starts_with(API_KEY_PREFIX)
+}
+
+/// Hex-encoded SHA-256 hash of an API key secret.
+pub fn hash_api_key(secret: &str) -> String {
+    Sha256::digest(secret.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_stores_only_hash() {
+        let (key, secret) = ApiKey::generate("u1", "ci", vec![ApiKeyScope::Read]);
+
+        assert!(is_api_key(&secret));
+        assert_eq!(secret.len(), API_KEY_PREFIX.len() + 64);
+        assert!(secret.starts_with(&key.display_prefix));
+        assert_eq!(key.key_hash, hash_api_key(&secret));
+        assert_ne!(key.key_hash, secret);
+        assert_eq!(key.key_hash.len(), 64);
+        assert!(key.is_active());
+        assert!(key.has_scope(ApiKeyScope::Read));
+        assert!(!key.has_scope(ApiKeyScope::Delete));
+    }
+
+    #[test]
+    fn test_scope_parse_roundtrip() {
+        for scope in ApiKeyScope::ALL {
+            assert_eq!(ApiKeyScope::parse(scope.as_str()), Some(scope));
+        }
+        assert_eq!(ApiKeyScope::parse("admin"), None);
+    }
+}
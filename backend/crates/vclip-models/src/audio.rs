@@ -0,0 +1,200 @@
+//! Audio post-processing configuration.
+//!
+//! Clips cut from different sources play back at very different volumes.
+//! When [`AudioConfig`] is set on a clip task, the rendered clip goes through
+//! an audio stage: optional cleanup (high-pass, noise gate), two-pass EBU R128
+//! loudness normalization and a true-peak limiter. The loudness measured
+//! before and after the stage is kept on the clip as [`LoudnessReport`].
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Lowest integrated loudness target accepted by FFmpeg's `loudnorm` (LUFS).
+pub const MIN_TARGET_LUFS: f64 = -70.0;
+
+/// Highest integrated loudness target accepted by FFmpeg's `loudnorm` (LUFS).
+pub const MAX_TARGET_LUFS: f64 = -5.0;
+
+/// Audio stage options attached to a render request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AudioConfig {
+    /// Integrated loudness target (LUFS)
+    #[serde(default = "default_target_lufs")]
+    pub target_lufs: f64,
+
+    /// Maximum true peak (dBTP)
+    #[serde(default = "default_true_peak_db")]
+    pub true_peak_db: f64,
+
+    /// Loudness range target (LU)
+    #[serde(default = "default_loudness_range")]
+    pub loudness_range: f64,
+
+    /// Cut rumble below this frequency (Hz)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub high_pass_hz: Option<u32>,
+
+    /// Gate audio quieter than this level (dBFS)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub noise_gate_db: Option<f64>,
+
+    /// Apply a brick-wall limiter at the true-peak ceiling
+    #[serde(default = "default_limiter")]
+    pub limiter: bool,
+}
+
+fn default_target_lufs() -> f64 {
+    -14.0
+}
+
+fn default_true_peak_db() -> f64 {
+    -1.5
+}
+
+fn default_loudness_range() -> f64 {
+    11.0
+}
+
+fn default_limiter() -> bool {
+    true
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            target_lufs: default_target_lufs(),
+            true_peak_db: default_true_peak_db(),
+            loudness_range: default_loudness_range(),
+            high_pass_hz: None,
+            noise_gate_db: None,
+            limiter: default_limiter(),
+        }
+    }
+}
+
+impl AudioConfig {
+    /// Create a config normalizing to `target_lufs` with default peak and range.
+    pub fn new(target_lufs: f64) -> Self {
+        Self {
+            target_lufs,
+            ..Default::default()
+        }
+    }
+
+    /// Set the high-pass cutoff frequency.
+    pub fn with_high_pass(mut self, hz: u32) -> Self {
+        self.high_pass_hz = Some(hz);
+        self
+    }
+
+    /// Set the noise gate threshold.
+    pub fn with_noise_gate(mut self, threshold_db: f64) -> Self {
+        self.noise_gate_db = Some(threshold_db);
+        self
+    }
+
+    /// Enable or disable the output limiter.
+    pub fn with_limiter(mut self, limiter: bool) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Validate values against the ranges FFmpeg accepts.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_TARGET_LUFS..=MAX_TARGET_LUFS).contains(&self.target_lufs) {
+            return Err(format!(
+                "target_lufs must be between {} and {}",
+                MIN_TARGET_LUFS, MAX_TARGET_LUFS
+            ));
+        }
+        if !(-9.0..=0.0).contains(&self.true_peak_db) {
+            return Err("true_peak_db must be between -9 and 0".to_string());
+        }
+        if !(1.0..=50.0).contains(&self.loudness_range) {
+            return Err("loudness_range must be between 1 and 50".to_string());
+        }
+        if let Some(hz) = self.high_pass_hz {
+            if !(20..=500).contains(&hz) {
+                return Err("high_pass_hz must be between 20 and 500".to_string());
+            }
+        }
+        if let Some(db) = self.noise_gate_db {
+            if !(-90.0..=-10.0).contains(&db) {
+                return Err("noise_gate_db must be between -90 and -10".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Short stable description used in idempotency keys.
+    pub fn cache_key(&self) -> String {
+        let mut key = format!(
+            "i{}_tp{}_lra{}",
+            self.target_lufs, self.true_peak_db, self.loudness_range
+        );
+        if let Some(hz) = self.high_pass_hz {
+            key.push_str(&format!("_hp{}", hz));
+        }
+        if let Some(db) = self.noise_gate_db {
+            key.push_str(&format!("_gate{}", db));
+        }
+        if self.limiter {
+            key.push_str("_lim");
+        }
+        key
+    }
+}
+
+/// EBU R128 loudness statistics for one measurement.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LoudnessStats {
+    /// Integrated loudness (LUFS)
+    pub integrated_lufs: f64,
+    /// True peak (dBTP)
+    pub true_peak_db: f64,
+    /// Loudness range (LU)
+    pub loudness_range: f64,
+}
+
+/// Loudness of a clip before and after the audio stage.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LoudnessReport {
+    /// Integrated loudness target the clip was normalized to (LUFS)
+    pub target_lufs: f64,
+    /// Measured on the rendered clip before normalization
+    pub input: LoudnessStats,
+    /// Measured on the normalized output
+    pub output: LoudnessStats,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_serde_defaults() {
+        let config: AudioConfig = serde_json::from_str(r#"{"target_lufs":-16}"#).unwrap();
+        assert_eq!(config.target_lufs, -16.0);
+        assert_eq!(config.true_peak_db, -1.5);
+        assert_eq!(config.loudness_range, 11.0);
+        assert!(config.limiter);
+        assert!(config.high_pass_hz.is_none());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(AudioConfig::new(-3.0).validate().is_err());
+        assert!(AudioConfig::default().with_high_pass(5).validate().is_err());
+        let gated = AudioConfig::default().with_noise_gate(-50.0);
+        assert!(gated.validate().is_ok());
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let config = AudioConfig::default()
+            .with_high_pass(80)
+            .with_limiter(false);
+        assert_eq!(config.cache_key(), "i-14_tp-1.5_lra11_hp80");
+    }
+}
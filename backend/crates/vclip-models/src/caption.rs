@@ -0,0 +1,251 @@
+//! Burned-in caption configuration.
+//!
+//! Captions are an optional render stage applied after any [`Style`](crate::Style)
+//! processor. The options live on the clip task so every renderer gets the same
+//! behaviour without per-style plumbing.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Animated caption look.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionPreset {
+    /// Short phrase on screen with the currently spoken word highlighted
+    #[default]
+    WordHighlight,
+    /// Up to two lines of text shown as a static block
+    TwoLineBlock,
+    /// Karaoke-style progressive fill as each word is spoken
+    Karaoke,
+}
+
+impl CaptionPreset {
+    /// All available presets.
+    pub const ALL: &'static [CaptionPreset] = &[
+        CaptionPreset::WordHighlight,
+        CaptionPreset::TwoLineBlock,
+        CaptionPreset::Karaoke,
+    ];
+
+    /// Returns the preset name as used in API requests.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CaptionPreset::WordHighlight => "word_highlight",
+            CaptionPreset::TwoLineBlock => "two_line_block",
+            CaptionPreset::Karaoke => "karaoke",
+        }
+    }
+}
+
+impl fmt::Display for CaptionPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for CaptionPreset {
+    type Err = CaptionPresetParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "word_highlight" | "highlight" => Ok(CaptionPreset::WordHighlight),
+            "two_line_block" | "block" => Ok(CaptionPreset::TwoLineBlock),
+            "karaoke" => Ok(CaptionPreset::Karaoke),
+            _ => Err(CaptionPresetParseError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Unknown caption preset: {0}")]
+pub struct CaptionPresetParseError(String);
+
+/// Where captions are placed on the output frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionPosition {
+    /// Pick a position that avoids detected faces (and the split seam for split styles)
+    #[default]
+    Auto,
+    /// Upper third of the frame
+    Top,
+    /// Vertical center of the frame
+    Middle,
+    /// Lower third of the frame
+    Bottom,
+}
+
+impl CaptionPosition {
+    /// Returns the position name as used in API requests.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CaptionPosition::Auto => "auto",
+            CaptionPosition::Top => "top",
+            CaptionPosition::Middle => "middle",
+            CaptionPosition::Bottom => "bottom",
+        }
+    }
+}
+
+impl fmt::Display for CaptionPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Caption options attached to a render request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CaptionOptions {
+    /// Visual preset
+    #[serde(default)]
+    pub preset: CaptionPreset,
+
+    /// Placement on the frame
+    #[serde(default)]
+    pub position: CaptionPosition,
+
+    /// Maximum words shown at once
+    #[serde(default = "default_max_words_per_line")]
+    pub max_words_per_line: u32,
+
+    /// Render text in upper case
+    #[serde(default = "default_uppercase")]
+    pub uppercase: bool,
+}
+
+fn default_max_words_per_line() -> u32 {
+    4
+}
+
+fn default_uppercase() -> bool {
+    true
+}
+
+impl Default for CaptionOptions {
+    fn default() -> Self {
+        Self {
+            preset: CaptionPreset::default(),
+            position: CaptionPosition::default(),
+            max_words_per_line: default_max_words_per_line(),
+            uppercase: default_uppercase(),
+        }
+    }
+}
+
+impl CaptionOptions {
+    /// Create options for a preset with default placement.
+    pub fn new(preset: CaptionPreset) -> Self {
+        Self {
+            preset,
+            ..Default::default()
+        }
+    }
+
+    /// Set caption position.
+    pub fn with_position(mut self, position: CaptionPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set maximum words per line (clamped to at least 1).
+    pub fn with_max_words_per_line(mut self, max_words: u32) -> Self {
+        self.max_words_per_line = max_words.max(1);
+        self
+    }
+
+    /// Set upper-case rendering.
+    pub fn with_uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// Short stable description used in idempotency keys.
+    ///
+    /// Covers every option, so renders that differ in any of them are never
+    /// deduplicated into one.
+    pub fn cache_key(&self) -> String {
+        let mut key = format!(
+            "{}_{}_w{}",
+            self.preset, self.position, self.max_words_per_line
+        );
+        if self.uppercase {
+            key.push_str("_uc");
+        }
+        key
+    }
+}
+
+/// A single timed word, in seconds relative to the clip start.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CaptionWord {
+    /// Word text (punctuation preserved)
+    pub text: String,
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+}
+
+impl CaptionWord {
+    /// Create a new timed word.
+    pub fn new(text: impl Into<String>, start: f64, end: f64) -> Self {
+        Self {
+            text: text.into(),
+            start,
+            end,
+        }
+    }
+
+    /// Duration of the word in seconds.
+    pub fn duration(&self) -> f64 {
+        (self.end - self.start).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_roundtrip() {
+        for preset in CaptionPreset::ALL {
+            let parsed: CaptionPreset = preset.as_str().parse().unwrap();
+            assert_eq!(parsed, *preset);
+        }
+        assert!("comic_sans".parse::<CaptionPreset>().is_err());
+    }
+
+    #[test]
+    fn test_options_serde_defaults() {
+        let opts: CaptionOptions = serde_json::from_str(r#"{"preset":"karaoke"}"#).unwrap();
+        assert_eq!(opts.preset, CaptionPreset::Karaoke);
+        assert_eq!(opts.position, CaptionPosition::Auto);
+        assert_eq!(opts.max_words_per_line, 4);
+        assert!(opts.uppercase);
+    }
+
+    #[test]
+    fn test_cache_key_covers_all_options() {
+        let base = CaptionOptions::new(CaptionPreset::Karaoke);
+        assert_eq!(base.cache_key(), "karaoke_auto_w4_uc");
+
+        let variants = [
+            base.clone().with_position(CaptionPosition::Top),
+            base.clone().with_max_words_per_line(2),
+            base.clone().with_uppercase(false),
+        ];
+        for variant in &variants {
+            assert_ne!(variant.cache_key(), base.cache_key());
+        }
+    }
+
+    #[test]
+    fn test_max_words_clamped() {
+        let opts = CaptionOptions::default().with_max_words_per_line(0);
+        assert_eq!(opts.max_words_per_line, 1);
+    }
+}
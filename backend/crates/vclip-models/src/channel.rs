@@ -0,0 +1,227 @@
+//! Channel monitoring data models.
+//!
+//! A monitored channel is a YouTube channel whose new uploads are analyzed
+//! automatically. The worker polls each enabled channel, compares the
+//! uploads listing against the IDs it has already seen, and enqueues an
+//! analysis for every new upload.
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::utils::is_youtube_domain;
+use crate::HighlightDetection;
+
+/// Upload IDs remembered per channel.
+///
+/// Must exceed the number of uploads listed per poll, so uploads that drop
+/// out of the listing are not mistaken for new ones.
+pub const MAX_SEEN_UPLOADS: usize = 100;
+
+/// A YouTube channel watched for new uploads.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MonitoredChannel {
+    /// Unique identifier (UUID)
+    pub id: String,
+
+    /// User who owns this channel subscription
+    pub user_id: String,
+
+    /// Canonical channel URL (e.g. `https://www.youtube.com/@handle`)
+    pub channel_url: String,
+
+    /// Channel name, filled in on the first poll
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Whether new uploads are analyzed
+    pub enabled: bool,
+
+    /// How highlights are detected for new uploads
+    #[serde(default)]
+    pub highlight_detection: HighlightDetection,
+
+    /// Optional AI instructions applied to every upload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+
+    /// Upload IDs already seen, newest first
+    #[serde(default)]
+    pub seen_video_ids: Vec<String>,
+
+    /// Uploads analyzed since the channel was added
+    #[serde(default)]
+    pub uploads_queued: u32,
+
+    /// Last completed poll; `None` until the first poll records a baseline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_polled_at: Option<DateTime<Utc>>,
+
+    /// Error from the last poll, cleared on success
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+
+    /// When the channel was added
+    pub created_at: DateTime<Utc>,
+
+    /// When the channel was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MonitoredChannel {
+    /// Create an enabled channel that has not been polled yet.
+    pub fn new(
+        id: impl Into<String>,
+        user_id: impl Into<String>,
+        channel_url: impl Into<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: id.into(),
+            user_id: user_id.into(),
+            channel_url: channel_url.into(),
+            title: None,
+            enabled: true,
+            highlight_detection: HighlightDetection::default(),
+            prompt: None,
+            seen_video_ids: Vec::new(),
+            uploads_queued: 0,
+            last_polled_at: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Set the highlight detection mode.
+    pub fn with_highlight_detection(mut self, mode: HighlightDetection) -> Self {
+        self.highlight_detection = mode;
+        self
+    }
+
+    /// Set AI instructions.
+    pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// URL listing the channel's uploads, newest first.
+    pub fn uploads_url(&self) -> String {
+        format!("{}/videos", self.channel_url)
+    }
+
+    /// Whether the first poll has recorded the existing uploads.
+    ///
+    /// Uploads present when a channel is added are never analyzed.
+    pub fn has_baseline(&self) -> bool {
+        self.last_polled_at.is_some()
+    }
+
+    /// Uploads from a newest-first listing that have not been seen, oldest first.
+    pub fn new_uploads<'a>(&self, listed: &'a [String]) -> Vec<&'a String> {
+        if !self.has_baseline() {
+            return Vec::new();
+        }
+        listed
+            .iter()
+            .rev()
+            .filter(|id| !self.seen_video_ids.contains(id))
+            .collect()
+    }
+
+    /// Remember a newest-first listing, keeping the most recent IDs.
+    pub fn record_seen(&mut self, listed: &[String]) {
+        let mut seen: Vec<String> = listed.to_vec();
+        for id in &self.seen_video_ids {
+            if !seen.contains(id) {
+                seen.push(id.clone());
+            }
+        }
+        seen.truncate(MAX_SEEN_UPLOADS);
+        self.seen_video_ids = seen;
+    }
+}
+
+/// Normalize a YouTube channel URL to `https://www.youtube.com/<channel path>`.
+///
+/// Accepts `@handle`, `/channel/UC...`, `/c/name` and `/user/name` URLs, with
+/// or without a trailing tab such as `/videos`. Returns `None` for anything
+/// else, including video and playlist URLs.
+pub fn normalize_channel_url(url: &str) -> Option<String> {
+    let trimmed = url.trim();
+    if !is_youtube_domain(trimmed) {
+        return None;
+    }
+    let parsed = Url::parse(trimmed)
+        .or_else(|_| Url::parse(&format!("https://{}", trimmed)))
+        .ok()?;
+
+    let mut segments = parsed.path_segments()?.filter(|s| !s.is_empty());
+    let first = segments.next()?;
+    let path = if first.starts_with('@') && first.len() > 1 {
+        first.to_string()
+    } else if matches!(first, "channel" | "c" | "user") {
+        format!("{}/{}", first, segments.next()?)
+    } else {
+        return None;
+    };
+
+    let valid = path
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '@' | '/' | '_' | '-' | '.'));
+    valid.then(|| format!("https://www.youtube.com/{}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_channel_url() {
+        assert_eq!(
+            normalize_channel_url("https://youtube.com/@SomeCreator/videos").as_deref(),
+            Some("https://www.youtube.com/@SomeCreator")
+        );
+        assert_eq!(
+            normalize_channel_url("www.youtube.com/channel/UC1234567890abcdef").as_deref(),
+            Some("https://www.youtube.com/channel/UC1234567890abcdef")
+        );
+        assert_eq!(
+            normalize_channel_url("https://m.youtube.com/user/oldname?x=1").as_deref(),
+            Some("https://www.youtube.com/user/oldname")
+        );
+        assert_eq!(normalize_channel_url("https://youtube.com/watch?v=dQw4w9WgXcQ"), None);
+        assert_eq!(normalize_channel_url("https://youtube.com/channel/"), None);
+        assert_eq!(normalize_channel_url("https://example.com/@someone"), None);
+    }
+
+    #[test]
+    fn test_first_poll_only_records_baseline() {
+        let mut channel = MonitoredChannel::new("c1", "u1", "https://www.youtube.com/@x");
+        let listed = ids(&["v3", "v2", "v1"]);
+        assert!(channel.new_uploads(&listed).is_empty());
+
+        channel.record_seen(&listed);
+        channel.last_polled_at = Some(Utc::now());
+
+        let listed = ids(&["v5", "v4", "v3", "v2"]);
+        assert_eq!(channel.new_uploads(&listed), vec!["v4", "v5"]);
+
+        channel.record_seen(&listed);
+        assert_eq!(channel.seen_video_ids, ids(&["v5", "v4", "v3", "v2", "v1"]));
+    }
+
+    #[test]
+    fn test_record_seen_is_bounded() {
+        let mut channel = MonitoredChannel::new("c1", "u1", "https://www.youtube.com/@x");
+        let listed: Vec<String> = (0..MAX_SEEN_UPLOADS + 20).map(|i| format!("v{}", i)).collect();
+        channel.record_seen(&listed);
+        assert_eq!(channel.seen_video_ids.len(), MAX_SEEN_UPLOADS);
+        assert_eq!(channel.seen_video_ids[0], "v0");
+    }
+}
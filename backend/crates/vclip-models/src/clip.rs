@@ -4,7 +4,10 @@ use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::{AspectRatio, CropMode, Style, VideoId};
+use crate::{
+    AspectRatio, AudioConfig, CaptionOptions, CaptionWord, ClipVariant, CropMode, ExportProfile,
+    LoudnessReport, ResolutionPreset, Style, VideoId,
+};
 
 /// Horizontal position for StreamerSplit top panel webcam crop.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
@@ -289,6 +292,14 @@ pub struct ClipMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raw_r2_key: Option<String>,
 
+    /// Platform export variants rendered from this clip
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<ClipVariant>,
+
+    /// Loudness measured by the audio stage, when it ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loudness: Option<LoudnessReport>,
+
     /// Processing status
     #[serde(default)]
     pub status: ClipStatus,
@@ -330,6 +341,16 @@ impl ClipMetadata {
         self.updated_at = Some(Utc::now());
         self
     }
+
+    /// Size of the clip plus all of its export variants (bytes).
+    pub fn total_size_bytes(&self) -> u64 {
+        self.file_size_bytes + self.variants.iter().map(|v| v.file_size_bytes).sum::<u64>()
+    }
+
+    /// R2 keys of all export variants.
+    pub fn variant_r2_keys(&self) -> Vec<String> {
+        self.variants.iter().map(|v| v.r2_key.clone()).collect()
+    }
 }
 
 /// A task to create a single clip.
@@ -362,6 +383,10 @@ pub struct ClipTask {
     #[serde(default)]
     pub target_aspect: AspectRatio,
 
+    /// Output resolution preset (short side of the output frame)
+    #[serde(default)]
+    pub resolution: ResolutionPreset,
+
     /// Priority (lower = more important, used in filename)
     #[serde(default = "default_priority")]
     pub priority: u32,
@@ -387,6 +412,23 @@ pub struct ClipTask {
     /// Whether to cut silent parts using VAD (default: true)
     #[serde(default = "default_cut_silent_parts")]
     pub cut_silent_parts: bool,
+
+    /// Optional burned-in caption options (applies to any style)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captions: Option<CaptionOptions>,
+
+    /// Timed words for captions, relative to the clip start.
+    /// Populated by the worker before rendering; empty means no caption text.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub caption_words: Vec<CaptionWord>,
+
+    /// Platform export profiles to render from the finished clip
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub export_profiles: Vec<ExportProfile>,
+
+    /// Optional audio stage (loudness normalization and cleanup)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioConfig>,
 }
 
 fn default_cut_silent_parts() -> bool {
@@ -411,15 +453,50 @@ impl ClipTask {
             style,
             crop_mode: CropMode::default(),
             target_aspect: AspectRatio::default(),
+            resolution: ResolutionPreset::default(),
             priority: 99,
             pad_before: 0.0,
             pad_after: 0.0,
             streamer_split_params: None,
             streamer_params: None,
             cut_silent_parts: false,
+            captions: None,
+            caption_words: Vec::new(),
+            export_profiles: Vec::new(),
+            audio: None,
         }
     }
 
+    /// Set the output aspect ratio and resolution preset.
+    pub fn with_output_format(mut self, aspect: AspectRatio, resolution: ResolutionPreset) -> Self {
+        self.target_aspect = aspect;
+        self.resolution = resolution;
+        self
+    }
+
+    /// Set the platform export profiles (duplicates are dropped).
+    pub fn with_export_profiles(mut self, profiles: &[ExportProfile]) -> Self {
+        self.export_profiles.clear();
+        for profile in profiles {
+            if !self.export_profiles.contains(profile) {
+                self.export_profiles.push(*profile);
+            }
+        }
+        self
+    }
+
+    /// Set audio stage options.
+    pub fn with_audio(mut self, audio: Option<AudioConfig>) -> Self {
+        self.audio = audio;
+        self
+    }
+
+    /// Set burned-in caption options.
+    pub fn with_captions(mut self, captions: Option<CaptionOptions>) -> Self {
+        self.captions = captions;
+        self
+    }
+
     /// Set Streamer parameters.
     pub fn with_streamer_params(mut self, params: StreamerParams) -> Self {
         self.streamer_params = Some(params);
@@ -481,12 +558,17 @@ mod tests {
             style: Style::Split,
             crop_mode: CropMode::None,
             target_aspect: AspectRatio::PORTRAIT,
+            resolution: ResolutionPreset::P1080,
             priority: 1,
             pad_before: 0.0,
             pad_after: 0.0,
             streamer_split_params: None,
             streamer_params: None,
             cut_silent_parts: false,
+            captions: None,
+            caption_words: Vec::new(),
+            export_profiles: Vec::new(),
+            audio: None,
         };
 
         let filename = task.output_filename();
@@ -494,6 +576,20 @@ mod tests {
         assert!(filename.ends_with("_split.mp4"));
     }
 
+    #[test]
+    fn test_with_export_profiles_drops_duplicates() {
+        let task = ClipTask::new(1, "Scene", "00:00:00", "00:00:30", Style::Split)
+            .with_export_profiles(&[
+                ExportProfile::Tiktok,
+                ExportProfile::Shorts,
+                ExportProfile::Tiktok,
+            ]);
+        assert_eq!(
+            task.export_profiles,
+            vec![ExportProfile::Tiktok, ExportProfile::Shorts]
+        );
+    }
+
     #[test]
     fn test_sanitize_title() {
         assert_eq!(sanitize_filename_title("Hello World!"), "hello_world");
@@ -30,6 +30,8 @@ pub enum CreditOperationType {
     GenerateMoreScenes,
     /// Manual admin adjustment (refund, correction, etc.)
     AdminAdjustment,
+    /// Reserved credits returned for cancelled work
+    Refund,
 }
 
 impl CreditOperationType {
@@ -44,6 +46,7 @@ impl CreditOperationType {
             Self::SceneOriginals => "scene_originals",
             Self::GenerateMoreScenes => "generate_more_scenes",
             Self::AdminAdjustment => "admin_adjustment",
+            Self::Refund => "refund",
         }
     }
 
@@ -58,6 +61,7 @@ impl CreditOperationType {
             Self::SceneOriginals => "Scene Originals",
             Self::GenerateMoreScenes => "Generate More Scenes",
             Self::AdminAdjustment => "Admin Adjustment",
+            Self::Refund => "Refund",
         }
     }
 
@@ -72,9 +76,15 @@ impl CreditOperationType {
             "scene_originals" => Some(Self::SceneOriginals),
             "generate_more_scenes" => Some(Self::GenerateMoreScenes),
             "admin_adjustment" => Some(Self::AdminAdjustment),
+            "refund" => Some(Self::Refund),
             _ => None,
         }
     }
+
+    /// Whether this operation returns credits instead of consuming them.
+    pub fn is_refund(&self) -> bool {
+        matches!(self, Self::Refund)
+    }
 }
 
 /// A credit transaction record.
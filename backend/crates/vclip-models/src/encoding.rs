@@ -49,6 +49,14 @@ pub struct EncodingConfig {
     #[serde(default)]
     pub use_nvenc: bool,
 
+    /// Peak video bitrate cap in kbit/s (`-maxrate`, with a 2x `-bufsize`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bitrate_kbps: Option<u32>,
+
+    /// Move the moov atom to the start of the file (`-movflags +faststart`)
+    #[serde(default)]
+    pub faststart: bool,
+
     /// Additional FFmpeg output arguments
     #[serde(default)]
     pub extra_args: Vec<String>,
@@ -79,6 +87,8 @@ impl Default for EncodingConfig {
             audio_codec: DEFAULT_AUDIO_CODEC.to_string(),
             audio_bitrate: DEFAULT_AUDIO_BITRATE.to_string(),
             use_nvenc: false,
+            max_bitrate_kbps: None,
+            faststart: false,
             extra_args: Vec::new(),
         }
     }
@@ -122,6 +132,18 @@ impl EncodingConfig {
         self
     }
 
+    /// Cap the peak video bitrate (kbit/s).
+    pub fn with_max_bitrate(mut self, kbps: u32) -> Self {
+        self.max_bitrate_kbps = Some(kbps);
+        self
+    }
+
+    /// Enable `+faststart` for progressive playback.
+    pub fn with_faststart(mut self) -> Self {
+        self.faststart = true;
+        self
+    }
+
     /// Convert to FFmpeg command arguments.
     pub fn to_ffmpeg_args(&self) -> Vec<String> {
         let mut args = vec![
@@ -138,6 +160,15 @@ impl EncodingConfig {
             args.extend_from_slice(&["-crf".to_string(), self.crf.to_string()]);
         }
 
+        if let Some(kbps) = self.max_bitrate_kbps {
+            args.extend_from_slice(&[
+                "-maxrate".to_string(),
+                format!("{}k", kbps),
+                "-bufsize".to_string(),
+                format!("{}k", kbps * 2),
+            ]);
+        }
+
         args.extend_from_slice(&[
             "-c:a".to_string(),
             self.audio_codec.clone(),
@@ -145,6 +176,10 @@ impl EncodingConfig {
             self.audio_bitrate.clone(),
         ]);
 
+        if self.faststart {
+            args.extend_from_slice(&["-movflags".to_string(), "+faststart".to_string()]);
+        }
+
         args.extend(self.extra_args.clone());
 
         args
@@ -179,4 +214,19 @@ mod tests {
         assert!(args.contains(&"h264_nvenc".to_string()));
         assert!(args.contains(&"-cq".to_string())); // NVENC uses -cq instead of -crf
     }
+
+    #[test]
+    fn test_bitrate_cap_and_faststart() {
+        let args = EncodingConfig::default().to_ffmpeg_args();
+        assert!(!args.contains(&"-maxrate".to_string()));
+        assert!(!args.contains(&"-movflags".to_string()));
+
+        let args = EncodingConfig::default()
+            .with_max_bitrate(4000)
+            .with_faststart()
+            .to_ffmpeg_args();
+        assert!(args.windows(2).any(|w| w == ["-maxrate", "4000k"]));
+        assert!(args.windows(2).any(|w| w == ["-bufsize", "8000k"]));
+        assert!(args.windows(2).any(|w| w == ["-movflags", "+faststart"]));
+    }
 }
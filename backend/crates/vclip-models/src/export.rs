@@ -0,0 +1,189 @@
+//! Platform export profiles.
+//!
+//! A clip is rendered once per style. Each export profile requested on the
+//! clip task is then transcoded from that master into a [`ClipVariant`] that
+//! meets the destination's limits (duration, resolution, bitrate, loudness,
+//! container flags). Variants are stored next to the master clip in R2 and
+//! counted in storage accounting like any other clip file.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::{AudioConfig, EncodingConfig, ResolutionPreset};
+
+/// Named delivery profile for a destination platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportProfile {
+    /// TikTok upload
+    Tiktok,
+    /// Instagram Reels upload
+    Reels,
+    /// YouTube Shorts upload
+    Shorts,
+}
+
+impl ExportProfile {
+    /// All available profiles.
+    pub const ALL: &'static [ExportProfile] = &[
+        ExportProfile::Tiktok,
+        ExportProfile::Reels,
+        ExportProfile::Shorts,
+    ];
+
+    /// Returns the profile name as used in API requests and filenames.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportProfile::Tiktok => "tiktok",
+            ExportProfile::Reels => "reels",
+            ExportProfile::Shorts => "shorts",
+        }
+    }
+
+    /// Longest clip the platform accepts; longer masters are cut (seconds).
+    pub fn max_duration_secs(&self) -> f64 {
+        match self {
+            ExportProfile::Tiktok => 600.0,
+            ExportProfile::Reels => 90.0,
+            ExportProfile::Shorts => 180.0,
+        }
+    }
+
+    /// Output resolution preset.
+    pub fn resolution(&self) -> ResolutionPreset {
+        ResolutionPreset::P1080
+    }
+
+    /// Peak video bitrate (kbit/s).
+    pub fn max_video_bitrate_kbps(&self) -> u32 {
+        match self {
+            ExportProfile::Tiktok => 6_000,
+            ExportProfile::Reels => 5_000,
+            ExportProfile::Shorts => 10_000,
+        }
+    }
+
+    /// Audio bitrate.
+    pub fn audio_bitrate(&self) -> &'static str {
+        match self {
+            ExportProfile::Tiktok | ExportProfile::Reels => "128k",
+            ExportProfile::Shorts => "192k",
+        }
+    }
+
+    /// Integrated loudness target (LUFS).
+    pub fn loudness_target_lufs(&self) -> f64 {
+        -14.0
+    }
+
+    /// Audio stage settings for this profile.
+    pub fn audio(&self) -> AudioConfig {
+        AudioConfig::new(self.loudness_target_lufs())
+    }
+
+    /// Encoding settings for this profile.
+    ///
+    /// All profiles move the `moov` atom to the front so uploads can start
+    /// processing before the file is fully received.
+    pub fn encoding(&self) -> EncodingConfig {
+        let mut encoding = EncodingConfig::for_intelligent_crop()
+            .with_crf(21)
+            .with_max_bitrate(self.max_video_bitrate_kbps())
+            .with_faststart();
+        encoding.audio_bitrate = self.audio_bitrate().to_string();
+        encoding
+    }
+
+    /// Filename of this profile's variant of a clip.
+    ///
+    /// `clip_01_intro_split.mp4` becomes `clip_01_intro_split.tiktok.mp4`.
+    pub fn variant_filename(&self, clip_filename: &str) -> String {
+        let stem = clip_filename.strip_suffix(".mp4").unwrap_or(clip_filename);
+        format!("{}.{}.mp4", stem, self.as_str())
+    }
+}
+
+impl fmt::Display for ExportProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ExportProfile {
+    type Err = ExportProfileParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tiktok" => Ok(ExportProfile::Tiktok),
+            "reels" | "instagram" | "instagram_reels" => Ok(ExportProfile::Reels),
+            "shorts" | "youtube_shorts" => Ok(ExportProfile::Shorts),
+            _ => Err(ExportProfileParseError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Unknown export profile: {0}")]
+pub struct ExportProfileParseError(String);
+
+/// A platform-specific rendition of a clip, stored alongside the master.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ClipVariant {
+    /// Profile the variant was rendered for
+    pub profile: ExportProfile,
+
+    /// Output filename
+    pub filename: String,
+
+    /// R2 key for the variant file
+    pub r2_key: String,
+
+    /// File size in bytes
+    pub file_size_bytes: u64,
+
+    /// Duration in seconds (may be shorter than the master)
+    pub duration_seconds: f64,
+
+    /// Output width in pixels
+    pub width: u32,
+
+    /// Output height in pixels
+    pub height: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_parse_roundtrip() {
+        for profile in ExportProfile::ALL {
+            assert_eq!(profile.as_str().parse::<ExportProfile>().unwrap(), *profile);
+        }
+        assert_eq!(
+            "Instagram".parse::<ExportProfile>().unwrap(),
+            ExportProfile::Reels
+        );
+        assert!("vimeo".parse::<ExportProfile>().is_err());
+    }
+
+    #[test]
+    fn test_profile_encoding() {
+        let args = ExportProfile::Shorts.encoding().to_ffmpeg_args();
+        assert!(args.windows(2).any(|w| w == ["-maxrate", "10000k"]));
+        assert!(args.windows(2).any(|w| w == ["-bufsize", "20000k"]));
+        assert!(args.windows(2).any(|w| w == ["-movflags", "+faststart"]));
+        assert!(args.windows(2).any(|w| w == ["-b:a", "192k"]));
+    }
+
+    #[test]
+    fn test_variant_filename() {
+        assert_eq!(
+            ExportProfile::Reels.variant_filename("clip_01_intro_split.mp4"),
+            "clip_01_intro_split.reels.mp4"
+        );
+    }
+}
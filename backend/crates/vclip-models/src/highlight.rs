@@ -4,6 +4,8 @@ use chrono;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::virality::QualityMetrics;
+
 /// Hook category for a highlight.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -19,6 +21,131 @@ pub enum HighlightCategory {
     Other,
 }
 
+/// Boundary a highlight edge was snapped to during refinement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundarySnap {
+    /// Edge left at the AI-provided timestamp
+    #[default]
+    Unchanged,
+    /// Sentence break in the transcript
+    Sentence,
+    /// Start or end of a silence (VAD)
+    Silence,
+    /// Shot cut
+    ShotCut,
+}
+
+impl BoundarySnap {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BoundarySnap::Unchanged => "unchanged",
+            BoundarySnap::Sentence => "sentence",
+            BoundarySnap::Silence => "silence",
+            BoundarySnap::ShotCut => "shot_cut",
+        }
+    }
+
+    /// Parse from the string representation (unknown values map to `Unchanged`).
+    pub fn from_str_lossy(s: &str) -> Self {
+        match s {
+            "sentence" => BoundarySnap::Sentence,
+            "silence" => BoundarySnap::Silence,
+            "shot_cut" => BoundarySnap::ShotCut,
+            _ => BoundarySnap::Unchanged,
+        }
+    }
+
+    /// Whether the edge was moved.
+    pub fn is_snapped(&self) -> bool {
+        *self != BoundarySnap::Unchanged
+    }
+}
+
+impl std::fmt::Display for BoundarySnap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Record of a boundary refinement pass on a highlight.
+///
+/// The highlight's `start`/`end` hold the refined timestamps; the AI-provided
+/// timestamps are kept here so users can see what changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BoundaryRefinement {
+    /// Start timestamp before refinement
+    pub original_start: String,
+
+    /// End timestamp before refinement
+    pub original_end: String,
+
+    /// What the start edge was snapped to
+    #[serde(default)]
+    pub start_snap: BoundarySnap,
+
+    /// What the end edge was snapped to
+    #[serde(default)]
+    pub end_snap: BoundarySnap,
+
+    /// Search tolerance used (seconds)
+    pub tolerance_secs: f64,
+}
+
+impl BoundaryRefinement {
+    /// Record for user-set timestamps, which are never snapped.
+    pub fn manual(start: impl Into<String>, end: impl Into<String>) -> Self {
+        Self {
+            original_start: start.into(),
+            original_end: end.into(),
+            start_snap: BoundarySnap::Unchanged,
+            end_snap: BoundarySnap::Unchanged,
+            tolerance_secs: 0.0,
+        }
+    }
+}
+
+/// How highlights are detected during analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HighlightDetection {
+    /// Transcript analysis by the highlight provider (default)
+    #[default]
+    Transcript,
+    /// Audio/visual excitement signals only; no transcript needed
+    Signals,
+    /// Transcript picks plus non-overlapping signal picks, ranked together
+    Fused,
+}
+
+impl HighlightDetection {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HighlightDetection::Transcript => "transcript",
+            HighlightDetection::Signals => "signals",
+            HighlightDetection::Fused => "fused",
+        }
+    }
+
+    /// Whether the source video must be scanned for excitement signals.
+    pub fn uses_signals(&self) -> bool {
+        !matches!(self, HighlightDetection::Transcript)
+    }
+
+    /// Whether the transcript highlight provider is consulted.
+    pub fn uses_transcript(&self) -> bool {
+        !matches!(self, HighlightDetection::Signals)
+    }
+}
+
+impl std::fmt::Display for HighlightDetection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// A highlight/scene detected in the video.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Highlight {
@@ -56,6 +183,18 @@ pub struct Highlight {
     /// Description of the scene
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// Boundary refinement record (original timestamps and snap sources)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refinement: Option<BoundaryRefinement>,
+
+    /// Composite virality score (0-1) derived from `quality`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub virality_score: Option<f64>,
+
+    /// Per-scene quality metrics behind the virality score
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<QualityMetrics>,
 }
 
 fn default_pad_before() -> f64 {
@@ -85,9 +224,62 @@ impl Highlight {
             hook_category: None,
             reason: None,
             description: None,
+            refinement: None,
+            virality_score: None,
+            quality: None,
+        }
+    }
+
+    /// Start timestamp as produced by analysis (before any refinement).
+    pub fn original_start(&self) -> &str {
+        self.refinement
+            .as_ref()
+            .map(|r| r.original_start.as_str())
+            .unwrap_or(&self.start)
+    }
+
+    /// End timestamp as produced by analysis (before any refinement).
+    pub fn original_end(&self) -> &str {
+        self.refinement
+            .as_ref()
+            .map(|r| r.original_end.as_str())
+            .unwrap_or(&self.end)
+    }
+
+    /// Apply refined timestamps, keeping the current ones as the originals.
+    ///
+    /// Re-applying keeps the first originals, so refinement is never compounded.
+    pub fn apply_refinement(
+        &mut self,
+        start: impl Into<String>,
+        end: impl Into<String>,
+        start_snap: BoundarySnap,
+        end_snap: BoundarySnap,
+        tolerance_secs: f64,
+    ) {
+        let refinement = BoundaryRefinement {
+            original_start: self.original_start().to_string(),
+            original_end: self.original_end().to_string(),
+            start_snap,
+            end_snap,
+            tolerance_secs,
+        };
+        self.start = start.into();
+        self.end = end.into();
+        self.refinement = Some(refinement);
+        if let (Ok(start_secs), Ok(end_secs)) =
+            (parse_timestamp(&self.start), parse_timestamp(&self.end))
+        {
+            self.duration = (end_secs - start_secs).max(0.0) as u32;
         }
     }
 
+    /// Store quality metrics and recompute the virality score.
+    pub fn set_quality(&mut self, quality: QualityMetrics) {
+        self.virality_score = quality.virality_score();
+        self.quality = (!quality.is_empty()).then_some(quality);
+    }
+
     /// Calculate duration from start/end timestamps.
     pub fn with_calculated_duration(mut self) -> Self {
         if let (Ok(start_secs), Ok(end_secs)) =
@@ -247,4 +439,55 @@ mod tests {
         let h = Highlight::new(1, "Test", "53:53", "58:12").with_calculated_duration();
         assert_eq!(h.duration, 259);
     }
+
+    #[test]
+    fn test_apply_refinement_keeps_first_originals() {
+        let mut h = Highlight::new(1, "Test", "00:00:10", "00:00:40");
+        assert_eq!(h.original_start(), "00:00:10");
+
+        h.apply_refinement(
+            "00:00:09.400",
+            "00:00:41.200",
+            BoundarySnap::Sentence,
+            BoundarySnap::Silence,
+            1.5,
+        );
+        assert_eq!(h.start, "00:00:09.400");
+        assert_eq!(h.original_start(), "00:00:10");
+        assert_eq!(h.original_end(), "00:00:40");
+        assert_eq!(h.duration, 31);
+
+        h.apply_refinement(
+            "00:00:09",
+            "00:00:41",
+            BoundarySnap::ShotCut,
+            BoundarySnap::Unchanged,
+            1.5,
+        );
+        let refinement = h.refinement.as_ref().unwrap();
+        assert_eq!(refinement.original_start, "00:00:10");
+        assert_eq!(refinement.start_snap, BoundarySnap::ShotCut);
+    }
+
+    #[test]
+    fn test_highlight_without_refinement_deserializes() {
+        let json = r#"{"id":1,"title":"T","start":"00:00:01","end":"00:00:05","duration":4}"#;
+        let h: Highlight = serde_json::from_str(json).unwrap();
+        assert!(h.refinement.is_none());
+        assert_eq!(BoundarySnap::from_str_lossy("shot_cut"), BoundarySnap::ShotCut);
+        assert_eq!(BoundarySnap::from_str_lossy("bogus"), BoundarySnap::Unchanged);
+    }
+
+    #[test]
+    fn test_highlight_detection_serde() {
+        let mode: HighlightDetection = serde_json::from_str(r#""fused""#).unwrap();
+        assert_eq!(mode, HighlightDetection::Fused);
+        assert!(mode.uses_signals() && mode.uses_transcript());
+        assert!(!HighlightDetection::Signals.uses_transcript());
+        assert!(!HighlightDetection::default().uses_signals());
+        assert_eq!(
+            serde_json::to_string(&HighlightDetection::Signals).unwrap(),
+            r#""signals""#
+        );
+    }
 }
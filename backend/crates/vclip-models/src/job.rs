@@ -69,6 +69,8 @@ pub enum JobState {
     Failed,
     /// Job sent to DLQ after max retries
     DeadLettered,
+    /// Job cancelled by the user
+    Cancelled,
 }
 
 impl JobState {
@@ -79,11 +81,15 @@ impl JobState {
             JobState::Completed => "completed",
             JobState::Failed => "failed",
             JobState::DeadLettered => "dead_lettered",
+            JobState::Cancelled => "cancelled",
         }
     }
 
     pub fn is_terminal(&self) -> bool {
-        matches!(self, JobState::Completed | JobState::DeadLettered)
+        matches!(
+            self,
+            JobState::Completed | JobState::DeadLettered | JobState::Cancelled
+        )
     }
 }
 
@@ -55,6 +55,8 @@ pub enum JobStatus {
     Failed,
     /// Worker stopped responding (stale)
     Stale,
+    /// Job was cancelled by the user
+    Cancelled,
 }
 
 impl JobStatus {
@@ -66,12 +68,16 @@ impl JobStatus {
             JobStatus::Completed => "completed",
             JobStatus::Failed => "failed",
             JobStatus::Stale => "stale",
+            JobStatus::Cancelled => "cancelled",
         }
     }
 
     /// Check if this is a terminal state (no more updates expected).
     pub fn is_terminal(&self) -> bool {
-        matches!(self, JobStatus::Completed | JobStatus::Failed)
+        matches!(
+            self,
+            JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled
+        )
     }
 }
 
@@ -143,6 +149,14 @@ impl JobStatusCache {
         self.event_seq += 1;
     }
 
+    /// Mark job as cancelled by the user.
+    pub fn cancel(&mut self) {
+        self.status = JobStatus::Cancelled;
+        self.current_step = Some("Cancelled".into());
+        self.updated_at = Utc::now();
+        self.event_seq += 1;
+    }
+
     /// Mark job as stale (worker timeout).
     pub fn mark_stale(&mut self) {
         self.status = JobStatus::Stale;
@@ -199,6 +213,18 @@ mod tests {
         assert!(cache.is_terminal());
     }
 
+    #[test]
+    fn test_job_status_cancel_is_terminal() {
+        let mut cache = JobStatusCache::new("job-1", "video-1", "user-1");
+        cache.set_status(JobStatus::Processing);
+        cache.record_heartbeat();
+
+        cache.cancel();
+        assert_eq!(cache.status, JobStatus::Cancelled);
+        assert!(cache.is_terminal());
+        assert!(!cache.is_stale(0, 0));
+    }
+
     #[test]
     fn test_job_status_stale_detection() {
         let mut cache = JobStatusCache::new("job-1", "video-1", "user-1");
@@ -3,43 +3,79 @@
 //! This crate provides Serde-serializable types for:
 //! - Jobs and clip tasks
 //! - Video styles and crop modes
-//! - Encoding configuration
+//! - Encoding configuration and platform export profiles
+//! - Burned-in caption options
+//! - Audio loudness normalization options
 //! - Detection tiers for intelligent processing
 //! - Redis pub/sub progress message schemas (ws.rs, used for worker progress)
 //! - Plan configuration and storage limits
+//! - Retention policies for cached artifacts
 //! - Share link configuration
+//! - Personal API keys and scopes
 //! - Analysis workflow (drafts and scenes)
+//! - Channel monitoring (watched channels and seen uploads)
+//! - Local file uploads (multipart upload sessions)
+//! - Twitch and Kick VOD/clip URL parsing
+//! - Live-stream rolling capture (capture state and segment manifest)
+//! - Outbound webhook endpoints, payloads and delivery log
+//! - Per-scene quality metrics and virality scores
 //! - Cinematic analysis status tracking
 
+pub mod admin_audit;
 pub mod analysis;
+pub mod api_key;
+pub mod audio;
+pub mod caption;
+pub mod channel;
 pub mod cinematic_analysis;
 pub mod clip;
 pub mod credit_cost;
 pub mod credit_transaction;
 pub mod detection_tier;
 pub mod encoding;
+pub mod export;
 pub mod highlight;
 pub mod job;
 pub mod job_status;
+pub mod live_capture;
 pub mod neural_analysis;
 pub mod plan;
+pub mod retention;
 pub mod share;
+pub mod stream_url;
 pub mod style;
 pub mod timestamp;
+pub mod upload;
 pub mod utils;
 pub mod video;
+pub mod virality;
+pub mod webhook;
+pub mod word_alignment;
 pub mod ws;
 pub mod youtube_url_config;
 
 // Re-export common types
+pub use admin_audit::AdminAuditEntry;
+pub use api_key::{hash_api_key, is_api_key, ApiKey, ApiKeyScope, API_KEY_PREFIX};
+pub use audio::{AudioConfig, LoudnessReport, LoudnessStats};
+pub use caption::{CaptionOptions, CaptionPosition, CaptionPreset, CaptionWord};
+pub use channel::{normalize_channel_url, MonitoredChannel, MAX_SEEN_UPLOADS};
 pub use clip::{
     ClipMetadata, ClipStatus, ClipTask, HorizontalPosition, StreamerParams, StreamerSplitParams,
     TopSceneEntry, VerticalPosition, sanitize_filename_title,
 };
 pub use detection_tier::DetectionTier;
 pub use encoding::EncodingConfig;
-pub use highlight::{Highlight, HighlightCategory, HighlightsData, VideoHighlights};
+pub use export::{ClipVariant, ExportProfile};
+pub use highlight::{
+    BoundaryRefinement, BoundarySnap, Highlight, HighlightCategory, HighlightDetection,
+    HighlightsData, VideoHighlights,
+};
 pub use job::{Job, JobId, JobState, JobType};
+pub use live_capture::{LiveCapture, LiveCaptureManifest, LiveCaptureStatus, LiveSegment};
+pub use retention::{
+    parse_retention_overrides, RetentionArtifact, RetentionOverride, RetentionPolicy,
+};
 pub use plan::{format_bytes, PlanLimits, PlanTier, StorageAccounting, StorageUsage};
 pub use plan::{FREE_STORAGE_LIMIT_BYTES, PRO_STORAGE_LIMIT_BYTES, STUDIO_STORAGE_LIMIT_BYTES};
 pub use plan::{
@@ -48,14 +84,39 @@ pub use plan::{
     STREAMER_SPLIT_STYLE_COST, SILENT_REMOVER_ADDON_COST, OBJECT_DETECTION_ADDON_COST,
     credits_for_detection_tier,
 };
-pub use share::{CreateShareRequest, ShareAccessLevel, ShareConfig, ShareResponse, is_valid_share_slug, MAX_SHARE_EXPIRY_HOURS};
-pub use style::{AspectRatio, CropMode, Style};
+pub use share::{
+    CreateShareRequest, ShareAccessLevel, ShareConfig, ShareDailyStats, ShareResponse, ShareStats,
+    ShareViewAction, UnlockShareRequest, hash_share_passcode, is_allowed_viewer,
+    is_valid_share_slug, normalize_allowed_viewer, referrer_domain, share_viewer_hash,
+    verify_share_passcode, MAX_SHARE_ALLOWED_VIEWERS, MAX_SHARE_EXPIRY_HOURS,
+    MAX_SHARE_PASSCODE_LEN, MAX_SHARE_REFERRERS, MIN_SHARE_PASSCODE_LEN, OTHER_REFERRER,
+};
+pub use stream_url::{
+    is_stream_platform_url, live_channel_url, parse_stream_url, StreamContentKind,
+    StreamPlatform, StreamUrlError, StreamVideoRef,
+};
+pub use style::{AspectRatio, CropMode, ResolutionPreset, Style};
+pub use upload::{
+    is_upload_source, upload_part_size, upload_r2_key, upload_title, validate_upload, SourceUpload,
+    UploadStatus, MAX_UPLOAD_PARTS, MAX_UPLOAD_SIZE_BYTES, UPLOAD_PART_SIZE_BYTES,
+};
 pub use utils::{extract_youtube_id, extract_youtube_id_legacy, YoutubeIdError, YoutubeIdResult};
+pub use virality::{
+    duration_fit, QualityMetrics, SceneRankQuery, SceneSort, ScoreWeights, ScoredScene,
+    HOOK_WINDOW_SECS,
+};
+pub use webhook::{
+    is_public_ip, sign_webhook, validate_webhook_url, webhook_retry_delay, WebhookDelivery,
+    WebhookDeliveryStatus, WebhookEndpoint, WebhookEventType, WebhookPayload,
+    WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER, WEBHOOK_MAX_ATTEMPTS,
+    WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER,
+};
 pub use video::{ProcessingProgress, SourceVideoStatus, VideoId, VideoMetadata, VideoStatus};
 pub use ws::{ClipProcessingStep, WsMessage, WsMessageType};
 pub use youtube_url_config::{
-    analyze_youtube_url, analyze_youtube_url_json, LiveCaptureMode, LiveHandling, SubtitlePlan,
-    UrlType, ValidationResult, VideoDownloadPlan, YoutubeUrlConfig, YoutubeUrlInput,
+    analyze_stream_url, analyze_video_url, analyze_video_url_json, analyze_youtube_url,
+    analyze_youtube_url_json, LiveCaptureMode, LiveHandling, SubtitlePlan, UrlType,
+    ValidationResult, VideoDownloadPlan, VideoPlatform, YoutubeUrlConfig, YoutubeUrlInput,
 };
 pub use analysis::{
     AnalysisDraft, AnalysisStatus, AnalysisStatusResponse, DraftScene, ProcessDraftRequest,
@@ -66,6 +127,7 @@ pub use neural_analysis::{
     FrameAnalysis, FrameObjectDetections, ObjectDetectionsCache, SceneNeuralAnalysis,
     ShotBoundaryCache, CINEMATIC_SIGNALS_VERSION, NEURAL_ANALYSIS_VERSION,
 };
+pub use word_alignment::{AlignedWord, AlignmentMethod, SceneWordAlignment, WORD_ALIGNMENT_VERSION};
 pub use cinematic_analysis::{
     CinematicAnalysisStatus, cinematic_analysis_key, CINEMATIC_ANALYSIS_TIMEOUT_SECS,
 };
@@ -0,0 +1,223 @@
+//! Live-stream rolling capture.
+//!
+//! A live capture records a broadcast into fixed-length segments in R2 while
+//! it is still running. Highlight detection runs on a sliding window over the
+//! newest segments and publishes draft scenes as it goes, so clips can be
+//! rendered minutes after a moment happens instead of after the VOD appears.
+//!
+//! Times are seconds on the capture timeline, which starts at the first
+//! recorded frame. The segment manifest maps that timeline to R2 objects so
+//! renders fetch only the segments a scene covers.
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::LiveCaptureMode;
+
+/// State of a live capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveCaptureStatus {
+    /// Waiting for a worker to start recording
+    #[default]
+    Starting,
+    /// Recording and analyzing the stream
+    Recording,
+    /// The stream ended or the user stopped the capture
+    Ended,
+}
+
+impl LiveCaptureStatus {
+    /// Returns the status as a string for display.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Starting => "starting",
+            Self::Recording => "recording",
+            Self::Ended => "ended",
+        }
+    }
+
+    /// Parse the string form, as stored in Firestore.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "starting" => Some(Self::Starting),
+            "recording" => Some(Self::Recording),
+            "ended" => Some(Self::Ended),
+            _ => None,
+        }
+    }
+}
+
+/// Live capture progress, stored on the analysis draft it feeds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LiveCapture {
+    /// Capture job, used to stop the capture
+    pub job_id: String,
+
+    /// Whether recording starts at the beginning of the broadcast or now
+    pub capture_mode: LiveCaptureMode,
+
+    /// Current state
+    pub status: LiveCaptureStatus,
+
+    /// Seconds recorded and uploaded so far
+    pub captured_secs: f64,
+
+    /// Number of segments uploaded so far
+    pub segment_count: u32,
+
+    /// End of the last analyzed window, in seconds
+    pub analyzed_until_secs: f64,
+
+    /// R2 key of the segment manifest
+    pub manifest_r2_key: String,
+
+    /// When recording started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+
+    /// When recording ended
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl LiveCapture {
+    /// Create the record of a capture that has not started yet.
+    pub fn new(
+        job_id: impl Into<String>,
+        capture_mode: LiveCaptureMode,
+        manifest_r2_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            job_id: job_id.into(),
+            capture_mode,
+            status: LiveCaptureStatus::Starting,
+            captured_secs: 0.0,
+            segment_count: 0,
+            analyzed_until_secs: 0.0,
+            manifest_r2_key: manifest_r2_key.into(),
+            started_at: None,
+            ended_at: None,
+        }
+    }
+
+    /// Whether the capture is still running or about to.
+    pub fn is_active(&self) -> bool {
+        self.status != LiveCaptureStatus::Ended
+    }
+}
+
+/// One recorded segment of a live capture.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiveSegment {
+    /// Position in the capture (0-based)
+    pub index: u32,
+    /// R2 key of the segment file
+    pub r2_key: String,
+    /// Start on the capture timeline (seconds)
+    pub start_secs: f64,
+    /// End on the capture timeline (seconds)
+    pub end_secs: f64,
+    /// File size in bytes
+    pub size_bytes: u64,
+}
+
+/// Segments of a live capture in recording order, stored as JSON in R2.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LiveCaptureManifest {
+    pub segments: Vec<LiveSegment>,
+}
+
+impl LiveCaptureManifest {
+    /// End of the recorded timeline (seconds).
+    pub fn end_secs(&self) -> f64 {
+        self.segments.last().map_or(0.0, |s| s.end_secs)
+    }
+
+    /// Index the next recorded segment gets.
+    pub fn next_index(&self) -> u32 {
+        self.segments.last().map_or(0, |s| s.index + 1)
+    }
+
+    /// Total size of all segments in bytes.
+    pub fn total_bytes(&self) -> u64 {
+        self.segments.iter().map(|s| s.size_bytes).sum()
+    }
+
+    /// Contiguous run of segments overlapping `start..end`.
+    pub fn covering(&self, start_secs: f64, end_secs: f64) -> &[LiveSegment] {
+        let first = self
+            .segments
+            .iter()
+            .position(|s| s.end_secs > start_secs)
+            .unwrap_or(self.segments.len());
+        let last = self
+            .segments
+            .iter()
+            .rposition(|s| s.start_secs < end_secs)
+            .map_or(0, |i| i + 1);
+        if first >= last {
+            return &[];
+        }
+        &self.segments[first..last]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(bounds: &[(f64, f64)]) -> LiveCaptureManifest {
+        LiveCaptureManifest {
+            segments: bounds
+                .iter()
+                .enumerate()
+                .map(|(i, &(start, end))| LiveSegment {
+                    index: i as u32,
+                    r2_key: format!("seg_{}.mp4", i),
+                    start_secs: start,
+                    end_secs: end,
+                    size_bytes: 100,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_covering_selects_overlapping_segments() {
+        let m = manifest(&[(0.0, 30.2), (30.2, 60.1), (60.1, 90.0), (90.0, 120.4)]);
+
+        let hit: Vec<u32> = m.covering(45.0, 75.0).iter().map(|s| s.index).collect();
+        assert_eq!(hit, vec![1, 2]);
+
+        // Touching a boundary does not pull in the neighbor
+        let hit: Vec<u32> = m.covering(60.1, 90.0).iter().map(|s| s.index).collect();
+        assert_eq!(hit, vec![2]);
+
+        assert!(m.covering(130.0, 150.0).is_empty());
+        assert_eq!(m.covering(100.0, 500.0).len(), 1);
+        assert!(LiveCaptureManifest::default().covering(0.0, 10.0).is_empty());
+    }
+
+    #[test]
+    fn test_manifest_totals() {
+        let m = manifest(&[(0.0, 30.0), (30.0, 61.5)]);
+        assert_eq!(m.end_secs(), 61.5);
+        assert_eq!(m.next_index(), 2);
+        assert_eq!(m.total_bytes(), 200);
+        assert_eq!(LiveCaptureManifest::default().next_index(), 0);
+    }
+
+    #[test]
+    fn test_status_roundtrip() {
+        for status in [
+            LiveCaptureStatus::Starting,
+            LiveCaptureStatus::Recording,
+            LiveCaptureStatus::Ended,
+        ] {
+            assert_eq!(LiveCaptureStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(LiveCaptureStatus::parse("paused"), None);
+    }
+}
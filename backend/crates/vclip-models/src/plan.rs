@@ -344,19 +344,24 @@ impl StorageUsage {
 
 /// Detailed storage accounting with per-category breakdown.
 ///
-/// Phase 5 storage tracking split: only styled clips count toward quota.
-/// Source videos, raw segments, and neural cache are non-billable.
+/// Phase 5 storage tracking split: styled clips and uploaded source files
+/// count toward quota. Cached source videos, raw segments, and neural cache
+/// are non-billable.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct StorageAccounting {
     // === Billable Storage (counts toward quota) ===
 
     /// Styled clip storage in bytes (the final rendered clips).
-    /// This is the only category that counts toward the user's quota.
     pub styled_clips_bytes: u64,
 
     /// Number of styled clips.
     pub styled_clips_count: u32,
 
+    /// Source files uploaded by the user in bytes.
+    /// Unlike cached source videos these are the only copy and are never swept.
+    #[serde(default)]
+    pub uploaded_sources_bytes: u64,
+
     // === Non-Billable Storage (does not count toward quota) ===
 
     /// Source video cache storage in bytes.
@@ -384,9 +389,10 @@ impl StorageAccounting {
         Self::default()
     }
 
-    /// Get total billable storage (styled clips only).
+    /// Get total billable storage (styled clips and uploaded sources).
     pub fn billable_bytes(&self) -> u64 {
         self.styled_clips_bytes
+            .saturating_add(self.uploaded_sources_bytes)
     }
 
     /// Get total non-billable storage (cache).
@@ -401,7 +407,7 @@ impl StorageAccounting {
         self.billable_bytes().saturating_add(self.cache_bytes())
     }
 
-    /// Check if adding bytes to styled clips would exceed the limit.
+    /// Check if adding billable bytes would exceed the limit.
     pub fn would_exceed_quota(&self, additional_bytes: u64, limit_bytes: u64) -> bool {
         self.billable_bytes().saturating_add(additional_bytes) > limit_bytes
     }
@@ -419,6 +425,24 @@ impl StorageAccounting {
         self.updated_at = Some(chrono::Utc::now());
     }
 
+    /// Add uploaded source file storage.
+    pub fn add_uploaded_source(&mut self, bytes: u64) {
+        self.uploaded_sources_bytes = self.uploaded_sources_bytes.saturating_add(bytes);
+        self.updated_at = Some(chrono::Utc::now());
+    }
+
+    /// Remove uploaded source file storage.
+    pub fn remove_uploaded_source(&mut self, bytes: u64) {
+        self.uploaded_sources_bytes = self.uploaded_sources_bytes.saturating_sub(bytes);
+        self.updated_at = Some(chrono::Utc::now());
+    }
+
+    /// Overwrite uploaded source storage with the total measured in R2.
+    pub fn set_uploaded_sources(&mut self, bytes: u64) {
+        self.uploaded_sources_bytes = bytes;
+        self.updated_at = Some(chrono::Utc::now());
+    }
+
     /// Add source video storage.
     pub fn add_source_video(&mut self, bytes: u64) {
         self.source_videos_bytes = self.source_videos_bytes.saturating_add(bytes);
@@ -472,6 +496,22 @@ impl StorageAccounting {
         self.updated_at = Some(chrono::Utc::now());
     }
 
+    /// Remove swept cache storage across the non-billable categories.
+    pub fn remove_cache(&mut self, source_bytes: u64, raw_bytes: u64, neural_bytes: u64) {
+        self.source_videos_bytes = self.source_videos_bytes.saturating_sub(source_bytes);
+        self.raw_segments_bytes = self.raw_segments_bytes.saturating_sub(raw_bytes);
+        self.neural_cache_bytes = self.neural_cache_bytes.saturating_sub(neural_bytes);
+        self.updated_at = Some(chrono::Utc::now());
+    }
+
+    /// Overwrite the non-billable categories with reconciled totals.
+    pub fn set_cache(&mut self, source_bytes: u64, raw_bytes: u64, neural_bytes: u64) {
+        self.source_videos_bytes = source_bytes;
+        self.raw_segments_bytes = raw_bytes;
+        self.neural_cache_bytes = neural_bytes;
+        self.updated_at = Some(chrono::Utc::now());
+    }
+
     /// Clear all non-billable storage for a video deletion.
     /// This zeros out source, raw, and neural cache bytes.
     pub fn clear_video_cache(&mut self) {
@@ -645,6 +685,21 @@ mod tests {
         assert_eq!(usage.remaining_bytes(), 0);
     }
 
+    #[test]
+    fn test_uploaded_sources_are_billable() {
+        let mut accounting = StorageAccounting::new();
+        accounting.add_styled_clip(100);
+        accounting.add_uploaded_source(1000);
+        accounting.add_source_video(5000);
+
+        assert_eq!(accounting.billable_bytes(), 1100);
+        assert!(accounting.would_exceed_quota(1, 1100));
+        assert_eq!(accounting.to_quota_usage(2000).total_bytes, 1100);
+
+        accounting.remove_uploaded_source(1000);
+        assert_eq!(accounting.billable_bytes(), 100);
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(500), "500 B");
@@ -0,0 +1,211 @@
+//! Retention policies for cached artifacts.
+//!
+//! Rendering leaves intermediate files in R2 (source copies, raw and
+//! silence-removed segments, neural analysis caches, transcripts, live capture
+//! segments). None of them count toward the storage quota, so they are kept
+//! only as long as the owner's plan allows and then swept.
+//!
+//! Each plan has a default policy; operators can override single entries with
+//! a spec such as `pro.raw_segment=14d,free.transcript=72h`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::PlanTier;
+
+/// Kind of cached artifact a retention policy applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionArtifact {
+    /// Cached copy of the source video (`sources/...`)
+    SourceVideo,
+    /// Extracted scene segment before styling (`clips/.../raw/...`)
+    RawSegment,
+    /// Raw segment with silent parts removed (`clips/.../silence_removed/...`)
+    SilenceRemovedSegment,
+    /// Neural analysis and word alignment caches (`.../neural/...`)
+    NeuralCache,
+    /// Cached transcripts (`.../transcripts/...`)
+    Transcript,
+    /// Recorded live stream segments and their manifest (`.../live/...`)
+    LiveSegment,
+}
+
+impl RetentionArtifact {
+    /// All artifact kinds.
+    pub const ALL: [RetentionArtifact; 6] = [
+        Self::SourceVideo,
+        Self::RawSegment,
+        Self::SilenceRemovedSegment,
+        Self::NeuralCache,
+        Self::Transcript,
+        Self::LiveSegment,
+    ];
+
+    /// Returns the artifact kind as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SourceVideo => "source_video",
+            Self::RawSegment => "raw_segment",
+            Self::SilenceRemovedSegment => "silence_removed",
+            Self::NeuralCache => "neural_cache",
+            Self::Transcript => "transcript",
+            Self::LiveSegment => "live_segment",
+        }
+    }
+
+    /// Parse the string form.
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.as_str() == s)
+    }
+}
+
+/// How long each artifact kind is kept, in hours.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    max_age_hours: BTreeMap<RetentionArtifact, u32>,
+}
+
+impl RetentionPolicy {
+    /// Default policy of a plan.
+    ///
+    /// Source copies always expire after a day, matching their
+    /// `source_video_expires_at`; segment and analysis caches live as long
+    /// as the plan's analysis drafts.
+    pub fn for_tier(tier: PlanTier) -> Self {
+        let cache_days = match tier {
+            PlanTier::Free => 7,
+            PlanTier::Pro => 30,
+            PlanTier::Studio => 90,
+        };
+        let max_age_hours = RetentionArtifact::ALL
+            .into_iter()
+            .map(|artifact| {
+                let hours = match artifact {
+                    RetentionArtifact::SourceVideo => 24,
+                    _ => cache_days * 24,
+                };
+                (artifact, hours)
+            })
+            .collect();
+        Self { max_age_hours }
+    }
+
+    /// Default policy of a plan with the overrides for that plan applied.
+    pub fn resolve(tier: PlanTier, overrides: &[RetentionOverride]) -> Self {
+        let mut policy = Self::for_tier(tier);
+        for o in overrides.iter().filter(|o| o.tier == tier) {
+            policy.max_age_hours.insert(o.artifact, o.max_age_hours);
+        }
+        policy
+    }
+
+    /// How long an artifact is kept, in hours.
+    pub fn max_age_hours(&self, artifact: RetentionArtifact) -> u32 {
+        self.max_age_hours.get(&artifact).copied().unwrap_or(24)
+    }
+
+    /// Whether an artifact last written `age_hours` ago has expired.
+    pub fn is_expired(&self, artifact: RetentionArtifact, age_hours: f64) -> bool {
+        age_hours > self.max_age_hours(artifact) as f64
+    }
+}
+
+/// Retention of one artifact kind for one plan, replacing the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionOverride {
+    pub tier: PlanTier,
+    pub artifact: RetentionArtifact,
+    pub max_age_hours: u32,
+}
+
+/// Parse a comma-separated override spec such as
+/// `pro.raw_segment=14d,free.transcript=72h`.
+///
+/// Durations are a number of days (`d`) or hours (`h`).
+pub fn parse_retention_overrides(spec: &str) -> Result<Vec<RetentionOverride>, String> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (target, duration) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Missing '=' in retention override '{}'", entry))?;
+            let (tier, artifact) = target.trim().split_once('.').ok_or_else(|| {
+                format!("Expected 'plan.artifact' in retention override '{}'", entry)
+            })?;
+
+            let tier = match tier {
+                "free" => PlanTier::Free,
+                "pro" => PlanTier::Pro,
+                "studio" => PlanTier::Studio,
+                _ => return Err(format!("Unknown plan '{}' in retention override", tier)),
+            };
+            let artifact = RetentionArtifact::parse(artifact)
+                .ok_or_else(|| format!("Unknown artifact '{}' in retention override", artifact))?;
+            let max_age_hours = parse_hours(duration.trim())
+                .ok_or_else(|| format!("Invalid duration '{}' in retention override", duration))?;
+
+            Ok(RetentionOverride {
+                tier,
+                artifact,
+                max_age_hours,
+            })
+        })
+        .collect()
+}
+
+fn parse_hours(duration: &str) -> Option<u32> {
+    if let Some(days) = duration.strip_suffix('d') {
+        return days.parse::<u32>().ok()?.checked_mul(24);
+    }
+    duration.strip_suffix('h')?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_defaults() {
+        let free = RetentionPolicy::for_tier(PlanTier::Free);
+        let studio = RetentionPolicy::for_tier(PlanTier::Studio);
+
+        assert_eq!(free.max_age_hours(RetentionArtifact::SourceVideo), 24);
+        assert_eq!(studio.max_age_hours(RetentionArtifact::SourceVideo), 24);
+        assert_eq!(free.max_age_hours(RetentionArtifact::RawSegment), 7 * 24);
+        assert_eq!(
+            studio.max_age_hours(RetentionArtifact::NeuralCache),
+            90 * 24
+        );
+
+        assert!(free.is_expired(RetentionArtifact::SourceVideo, 25.0));
+        assert!(!free.is_expired(RetentionArtifact::Transcript, 100.0));
+    }
+
+    #[test]
+    fn test_overrides_apply_to_their_plan_only() {
+        let overrides =
+            parse_retention_overrides("pro.raw_segment=14d, free.transcript=72h,").unwrap();
+        assert_eq!(overrides.len(), 2);
+
+        let pro = RetentionPolicy::resolve(PlanTier::Pro, &overrides);
+        assert_eq!(pro.max_age_hours(RetentionArtifact::RawSegment), 14 * 24);
+        assert_eq!(pro.max_age_hours(RetentionArtifact::Transcript), 30 * 24);
+
+        let free = RetentionPolicy::resolve(PlanTier::Free, &overrides);
+        assert_eq!(free.max_age_hours(RetentionArtifact::Transcript), 72);
+        assert_eq!(free.max_age_hours(RetentionArtifact::RawSegment), 7 * 24);
+    }
+
+    #[test]
+    fn test_parse_overrides_rejects_bad_entries() {
+        assert!(parse_retention_overrides("").unwrap().is_empty());
+        assert!(parse_retention_overrides("pro.raw_segment").is_err());
+        assert!(parse_retention_overrides("gold.raw_segment=1d").is_err());
+        assert!(parse_retention_overrides("pro.clips=1d").is_err());
+        assert!(parse_retention_overrides("pro.raw_segment=2w").is_err());
+        assert!(parse_retention_overrides("pro.raw_segment=d").is_err());
+    }
+}
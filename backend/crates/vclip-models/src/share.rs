@@ -1,8 +1,14 @@
 //! Share link models for clip sharing.
 
+use std::collections::BTreeMap;
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
 use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 
 /// Public access level for shared clips.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Default)]
@@ -82,6 +88,18 @@ pub struct ShareConfig {
     /// When the share was disabled/revoked (null = active).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub disabled_at: Option<DateTime<Utc>>,
+
+    /// Argon2 hash of the passcode viewers must enter (null = no passcode).
+    #[serde(default, skip_serializing)]
+    pub passcode_hash: Option<String>,
+
+    /// Number of resolutions after which the link stops working.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_views: Option<u64>,
+
+    /// Emails or email domains allowed to view (empty = anyone with the link).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_viewers: Vec<String>,
 }
 
 impl ShareConfig {
@@ -103,6 +121,9 @@ impl ShareConfig {
             created_at: Utc::now(),
             updated_at: None,
             disabled_at: None,
+            passcode_hash: None,
+            max_views: None,
+            allowed_viewers: Vec::new(),
         }
     }
 
@@ -151,6 +172,27 @@ impl ShareConfig {
         self.updated_at = Some(Utc::now());
         self
     }
+
+    /// Require a passcode (stored hashed).
+    pub fn with_passcode(mut self, passcode: &str) -> Self {
+        self.passcode_hash = Some(hash_share_passcode(passcode));
+        self.updated_at = Some(Utc::now());
+        self
+    }
+
+    /// Stop resolving after `max_views` views.
+    pub fn with_max_views(mut self, max_views: u64) -> Self {
+        self.max_views = Some(max_views);
+        self.updated_at = Some(Utc::now());
+        self
+    }
+
+    /// Restrict viewing to signed-in users matching these emails or domains.
+    pub fn with_allowed_viewers(mut self, allowed_viewers: Vec<String>) -> Self {
+        self.allowed_viewers = allowed_viewers;
+        self.updated_at = Some(Utc::now());
+        self
+    }
 }
 
 /// Maximum allowed expiry for share links (30 days).
@@ -187,6 +229,74 @@ fn generate_share_slug() -> String {
     result
 }
 
+/// Shortest accepted share passcode.
+pub const MIN_SHARE_PASSCODE_LEN: usize = 4;
+
+/// Longest accepted share passcode.
+pub const MAX_SHARE_PASSCODE_LEN: usize = 128;
+
+/// Maximum entries in a share's allowed-viewer list.
+pub const MAX_SHARE_ALLOWED_VIEWERS: usize = 50;
+
+/// Hash a share passcode with Argon2id and a random salt (PHC string).
+pub fn hash_share_passcode(passcode: &str) -> String {
+    // UUID v4 bytes come from OS randomness, like share slugs
+    let salt = SaltString::encode_b64(uuid::Uuid::new_v4().as_bytes())
+        .expect("16 bytes is a valid salt length");
+    Argon2::default()
+        .hash_password(passcode.as_bytes(), &salt)
+        .expect("Argon2 accepts passcodes of any length")
+        .to_string()
+}
+
+/// Check a passcode against a hash from [`hash_share_passcode`].
+pub fn verify_share_passcode(passcode: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(passcode.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// Normalize an allowed-viewer entry: a full email (`jane@acme.com`) or an
+/// email domain (`acme.com` or `@acme.com`).
+///
+/// Returns `None` if the entry is neither.
+pub fn normalize_allowed_viewer(entry: &str) -> Option<String> {
+    let entry = entry.trim().to_ascii_lowercase();
+    let (local, domain) = match entry.rsplit_once('@') {
+        Some((local, domain)) => (local, domain),
+        None => ("", entry.as_str()),
+    };
+    let valid_domain = domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && domain
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+    if !valid_domain || local.contains('@') || local.chars().any(char::is_whitespace) {
+        return None;
+    }
+    Some(if local.is_empty() {
+        domain.to_string()
+    } else {
+        format!("{}@{}", local, domain)
+    })
+}
+
+/// Whether `email` matches an allowed-viewer list of normalized entries.
+pub fn is_allowed_viewer(allowed_viewers: &[String], email: &str) -> bool {
+    let email = email.trim().to_ascii_lowercase();
+    let Some((_, domain)) = email.rsplit_once('@') else {
+        return false;
+    };
+    allowed_viewers
+        .iter()
+        .any(|entry| *entry == email || *entry == domain)
+}
+
 /// Validate a share slug format.
 pub fn is_valid_share_slug(slug: &str) -> bool {
     // Share slugs are 8-16 alphanumeric characters
@@ -196,6 +306,152 @@ pub fn is_valid_share_slug(slug: &str) -> bool {
     slug.chars().all(|c| c.is_ascii_alphanumeric())
 }
 
+// ============================================================================
+// Share Analytics
+// ============================================================================
+
+/// What a viewer did with a resolved share link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareViewAction {
+    /// Streamed the clip.
+    Play,
+    /// Downloaded the clip.
+    Download,
+}
+
+impl ShareViewAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShareViewAction::Play => "play",
+            ShareViewAction::Download => "download",
+        }
+    }
+}
+
+/// Most referring domains tracked per share link; the rest count as [`OTHER_REFERRER`].
+pub const MAX_SHARE_REFERRERS: usize = 50;
+
+/// Referrer bucket for domains past [`MAX_SHARE_REFERRERS`].
+pub const OTHER_REFERRER: &str = "other";
+
+/// Views of a share link on one day (UTC).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ShareDailyStats {
+    pub views: u64,
+    /// Distinct viewers (hashed IP and user agent) that day.
+    pub unique_viewers: u64,
+}
+
+/// Aggregated views of a share link.
+///
+/// Also used for not-yet-flushed increments, which are added with [`merge`].
+///
+/// [`merge`]: ShareStats::merge
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ShareStats {
+    /// Successful resolutions of the link.
+    pub total_views: u64,
+
+    /// Resolutions that streamed the clip.
+    pub plays: u64,
+
+    /// Resolutions that downloaded the clip.
+    pub downloads: u64,
+
+    /// Sum of the daily distinct viewers.
+    pub unique_viewers: u64,
+
+    /// Per-day breakdown keyed by `YYYY-MM-DD`.
+    #[serde(default)]
+    pub daily: BTreeMap<String, ShareDailyStats>,
+
+    /// Views per referring domain; direct visits are not listed.
+    #[serde(default)]
+    pub referrers: BTreeMap<String, u64>,
+
+    /// Most recent view.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_viewed_at: Option<DateTime<Utc>>,
+}
+
+impl ShareStats {
+    /// Add another set of counters to this one.
+    pub fn merge(&mut self, other: &ShareStats) {
+        self.total_views += other.total_views;
+        self.plays += other.plays;
+        self.downloads += other.downloads;
+        self.unique_viewers += other.unique_viewers;
+        for (day, stats) in &other.daily {
+            let entry = self.daily.entry(day.clone()).or_default();
+            entry.views += stats.views;
+            entry.unique_viewers += stats.unique_viewers;
+        }
+        for (domain, views) in &other.referrers {
+            *self.referrers.entry(domain.clone()).or_default() += views;
+        }
+        self.last_viewed_at = self.last_viewed_at.max(other.last_viewed_at);
+    }
+
+    /// Whether nothing has been counted.
+    pub fn is_empty(&self) -> bool {
+        self.total_views == 0
+    }
+
+    /// The `max` most viewed referrers, with the rest folded into [`OTHER_REFERRER`].
+    pub fn top_referrers(&self, max: usize) -> BTreeMap<String, u64> {
+        let mut ranked: Vec<(&String, &u64)> = self
+            .referrers
+            .iter()
+            .filter(|(domain, _)| domain.as_str() != OTHER_REFERRER)
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        let mut top: BTreeMap<String, u64> = ranked
+            .iter()
+            .take(max)
+            .map(|(domain, views)| ((*domain).clone(), **views))
+            .collect();
+        let other = self.referrers.get(OTHER_REFERRER).copied().unwrap_or(0)
+            + ranked.iter().skip(max).map(|(_, views)| **views).sum::<u64>();
+        if other > 0 {
+            top.insert(OTHER_REFERRER.to_string(), other);
+        }
+        top
+    }
+}
+
+/// Domain of a `Referer` header, lowercased and without `www.`.
+///
+/// Returns `None` for missing or unparseable referrers.
+pub fn referrer_domain(referer: &str) -> Option<String> {
+    let url = url::Url::parse(referer.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?.to_ascii_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    // Longer names are not valid DNS names
+    (!host.is_empty() && host.len() <= 253).then(|| host.to_string())
+}
+
+/// Anonymous viewer ID for unique-viewer counting.
+///
+/// Keyed HMAC of IP, user agent and day, so the same visitor hashes
+/// differently every day and the raw IP cannot be recovered without the key.
+pub fn share_viewer_hash(ip: &str, user_agent: &str, day: &str, key: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    for part in [ip, user_agent, day] {
+        mac.update(part.as_bytes());
+        mac.update(b"\n");
+    }
+    mac.finalize().into_bytes()[..12]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
 // ============================================================================
 // API Request/Response Types
 // ============================================================================
@@ -214,6 +470,18 @@ pub struct CreateShareRequest {
     /// Enable watermark.
     #[serde(default)]
     pub watermark_enabled: bool,
+
+    /// Passcode viewers must enter before the clip plays.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passcode: Option<String>,
+
+    /// Stop resolving after this many views.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_views: Option<u64>,
+
+    /// Emails or email domains allowed to view (viewers must sign in).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_viewers: Vec<String>,
 }
 
 impl Default for CreateShareRequest {
@@ -222,10 +490,25 @@ impl Default for CreateShareRequest {
             access_level: ShareAccessLevel::ViewPlayback,
             expires_in_hours: None,
             watermark_enabled: false,
+            passcode: None,
+            max_views: None,
+            allowed_viewers: Vec::new(),
         }
     }
 }
 
+/// Request to unlock a protected share link.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct UnlockShareRequest {
+    /// Passcode, if the share requires one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passcode: Option<String>,
+
+    /// Request a download URL instead of playback.
+    #[serde(default)]
+    pub download: bool,
+}
+
 /// Response for share creation.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ShareResponse {
@@ -245,6 +528,17 @@ pub struct ShareResponse {
     /// Watermark enabled.
     pub watermark_enabled: bool,
 
+    /// Whether viewers must enter a passcode.
+    pub passcode_protected: bool,
+
+    /// View limit (null = unlimited).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_views: Option<u64>,
+
+    /// Emails or email domains allowed to view (empty = anyone).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_viewers: Vec<String>,
+
     /// When created.
     pub created_at: String,
 }
@@ -258,6 +552,9 @@ impl ShareResponse {
             access_level: config.access_level,
             expires_at: config.expires_at.map(|e| e.to_rfc3339()),
             watermark_enabled: config.watermark_enabled,
+            passcode_protected: config.passcode_hash.is_some(),
+            max_views: config.max_views,
+            allowed_viewers: config.allowed_viewers.clone(),
             created_at: config.created_at.to_rfc3339(),
         }
     }
@@ -299,6 +596,65 @@ mod tests {
         assert!(!no_access.is_active());
     }
 
+    #[test]
+    fn test_referrer_domain() {
+        assert_eq!(referrer_domain("https://www.Twitter.com/x/status/1"), Some("twitter.com".to_string()));
+        assert_eq!(referrer_domain("http://news.ycombinator.com:8080/item"), Some("news.ycombinator.com".to_string()));
+        assert_eq!(referrer_domain("android-app://com.slack"), None);
+        assert_eq!(referrer_domain("not a url"), None);
+        assert_eq!(referrer_domain(""), None);
+        assert_eq!(referrer_domain(&format!("https://{}.com/", "a".repeat(300))), None);
+    }
+
+    #[test]
+    fn test_share_viewer_hash() {
+        let a = share_viewer_hash("1.2.3.4", "Mozilla", "2026-01-01", "key");
+        assert_eq!(a.len(), 24);
+        assert_eq!(a, share_viewer_hash("1.2.3.4", "Mozilla", "2026-01-01", "key"));
+        assert_ne!(a, share_viewer_hash("1.2.3.4", "Mozilla", "2026-01-02", "key"));
+        assert_ne!(a, share_viewer_hash("1.2.3.4", "Mozilla", "2026-01-01", "other"));
+    }
+
+    #[test]
+    fn test_share_stats_merge() {
+        let mut stats = ShareStats::default();
+        let mut delta = ShareStats {
+            total_views: 3,
+            plays: 2,
+            downloads: 1,
+            unique_viewers: 2,
+            ..Default::default()
+        };
+        delta.daily.insert("2026-01-01".to_string(), ShareDailyStats { views: 3, unique_viewers: 2 });
+        delta.referrers.insert("reddit.com".to_string(), 2);
+
+        stats.merge(&delta);
+        stats.merge(&delta);
+
+        assert_eq!(stats.total_views, 6);
+        assert_eq!(stats.downloads, 2);
+        assert_eq!(stats.daily["2026-01-01"], ShareDailyStats { views: 6, unique_viewers: 4 });
+        assert_eq!(stats.referrers["reddit.com"], 4);
+    }
+
+    #[test]
+    fn test_top_referrers_folds_the_rest_into_other() {
+        let mut stats = ShareStats::default();
+        stats.referrers.insert("reddit.com".to_string(), 5);
+        stats.referrers.insert("t.co".to_string(), 3);
+        stats.referrers.insert("a.example".to_string(), 1);
+        stats.referrers.insert("b.example".to_string(), 1);
+        stats.referrers.insert(OTHER_REFERRER.to_string(), 2);
+
+        let top = stats.top_referrers(2);
+        assert_eq!(top.len(), 3);
+        assert_eq!(top["reddit.com"], 5);
+        assert_eq!(top["t.co"], 3);
+        assert_eq!(top[OTHER_REFERRER], 4);
+
+        assert_eq!(stats.top_referrers(10), stats.referrers);
+    }
+
     #[test]
     fn test_access_level_permissions() {
         assert!(!ShareAccessLevel::None.allows_playback());
@@ -310,4 +666,35 @@ mod tests {
         assert!(ShareAccessLevel::Download.allows_playback());
         assert!(ShareAccessLevel::Download.allows_download());
     }
+
+    #[test]
+    fn test_share_passcode_hash_verify() {
+        let config = ShareConfig::new("clip-1", "user-1", "video-1", ShareAccessLevel::ViewPlayback)
+            .with_passcode("open sesame");
+        let hash = config.passcode_hash.as_deref().unwrap();
+        assert!(hash.starts_with("$argon2"));
+        assert!(verify_share_passcode("open sesame", hash));
+        assert!(!verify_share_passcode("open sesame ", hash));
+        assert!(!verify_share_passcode("open sesame", "not-a-hash"));
+
+        // The hash never leaves the server
+        let json = serde_json::to_value(&config).unwrap();
+        assert!(json.get("passcode_hash").is_none());
+    }
+
+    #[test]
+    fn test_allowed_viewers() {
+        assert_eq!(normalize_allowed_viewer(" @Acme.com "), Some("acme.com".to_string()));
+        assert_eq!(normalize_allowed_viewer("Jane@Acme.com"), Some("jane@acme.com".to_string()));
+        assert_eq!(normalize_allowed_viewer("localhost"), None);
+        assert_eq!(normalize_allowed_viewer("a@b@c.com"), None);
+        assert_eq!(normalize_allowed_viewer("acme.com/x"), None);
+
+        let allowed = vec!["acme.com".to_string(), "jane@client.io".to_string()];
+        assert!(is_allowed_viewer(&allowed, "Bob@ACME.com"));
+        assert!(is_allowed_viewer(&allowed, "jane@client.io"));
+        assert!(!is_allowed_viewer(&allowed, "bob@client.io"));
+        assert!(!is_allowed_viewer(&allowed, "bob@sub.acme.com"));
+        assert!(!is_allowed_viewer(&allowed, "acme.com"));
+    }
 }
@@ -0,0 +1,460 @@
+//! Twitch and Kick VOD/clip URL parsing.
+//!
+//! Stream VODs and clips are first-class video sources next to YouTube.
+//! This module recognizes their URL shapes, validates the IDs and builds the
+//! canonical URL handed to yt-dlp. Live channel pages are rejected by
+//! [`parse_stream_url`]; a channel URL says nothing about which broadcast to
+//! analyze. [`live_channel_url`] accepts them for live capture, which records
+//! whatever the channel is broadcasting.
+//!
+//! Supported shapes:
+//! - `twitch.tv/videos/{id}`, `twitch.tv/{channel}/video/{id}`
+//! - `clips.twitch.tv/{slug}`, `twitch.tv/{channel}/clip/{slug}`
+//! - `kick.com/video/{uuid}`, `kick.com/{channel}/videos/{uuid}`
+//! - `kick.com/{channel}/clips/{clip_id}`, `kick.com/{channel}?clip={clip_id}`
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{extract_host, parse_http_url};
+
+/// Longest accepted channel name.
+const MAX_CHANNEL_LEN: usize = 32;
+
+/// Longest accepted clip slug.
+const MAX_CLIP_SLUG_LEN: usize = 100;
+
+/// Site pages that look like a Twitch or Kick channel but are not.
+const RESERVED_CHANNEL_PATHS: [&str; 10] = [
+    "categories",
+    "directory",
+    "downloads",
+    "login",
+    "search",
+    "settings",
+    "signup",
+    "subscriptions",
+    "video",
+    "videos",
+];
+
+/// Streaming platform hosting a VOD or clip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamPlatform {
+    Twitch,
+    Kick,
+}
+
+impl StreamPlatform {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreamPlatform::Twitch => "twitch",
+            StreamPlatform::Kick => "kick",
+        }
+    }
+
+    /// Platform serving a lowercase host, if any.
+    pub fn from_host(host: &str) -> Option<Self> {
+        let matches = |base: &str| host == base || host.ends_with(&format!(".{}", base));
+        if matches("twitch.tv") {
+            Some(StreamPlatform::Twitch)
+        } else if matches("kick.com") {
+            Some(StreamPlatform::Kick)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for StreamPlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Kind of stream content a URL points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamContentKind {
+    /// Recording of a past broadcast
+    Vod,
+    /// Short viewer- or streamer-made clip
+    Clip,
+}
+
+impl StreamContentKind {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreamContentKind::Vod => "vod",
+            StreamContentKind::Clip => "clip",
+        }
+    }
+}
+
+/// Errors that can occur while parsing a stream URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamUrlError {
+    /// URL is not on Twitch or Kick
+    NotStreamUrl,
+    /// Channel page without a specific VOD or clip
+    ChannelPage,
+    /// VOD or clip ID has an invalid format
+    InvalidId,
+    /// Path is not a VOD or clip
+    UnsupportedPath,
+}
+
+impl std::fmt::Display for StreamUrlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamUrlError::NotStreamUrl => write!(f, "URL is not a Twitch or Kick URL"),
+            StreamUrlError::ChannelPage => {
+                write!(f, "Channel URL without a specific VOD or clip")
+            }
+            StreamUrlError::InvalidId => write!(f, "VOD or clip ID has invalid format"),
+            StreamUrlError::UnsupportedPath => write!(f, "URL is not a VOD or clip"),
+        }
+    }
+}
+
+impl std::error::Error for StreamUrlError {}
+
+/// A parsed Twitch or Kick VOD/clip reference.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamVideoRef {
+    pub platform: StreamPlatform,
+    pub kind: StreamContentKind,
+    /// VOD ID (Twitch digits, Kick UUID) or clip slug
+    pub id: String,
+    /// Channel name, when the URL includes it
+    pub channel: Option<String>,
+}
+
+impl StreamVideoRef {
+    /// Canonical URL for yt-dlp.
+    pub fn canonical_url(&self) -> String {
+        match (self.platform, self.kind, &self.channel) {
+            (StreamPlatform::Twitch, StreamContentKind::Vod, _) => {
+                format!("https://www.twitch.tv/videos/{}", self.id)
+            }
+            (StreamPlatform::Twitch, StreamContentKind::Clip, _) => {
+                format!("https://clips.twitch.tv/{}", self.id)
+            }
+            (StreamPlatform::Kick, StreamContentKind::Vod, Some(channel)) => {
+                format!("https://kick.com/{}/videos/{}", channel, self.id)
+            }
+            (StreamPlatform::Kick, StreamContentKind::Vod, None) => {
+                format!("https://kick.com/video/{}", self.id)
+            }
+            (StreamPlatform::Kick, StreamContentKind::Clip, channel) => format!(
+                "https://kick.com/{}/clips/{}",
+                channel.as_deref().unwrap_or("clips"),
+                self.id
+            ),
+        }
+    }
+
+    /// Stable file-name-safe key, e.g. `twitch-vod-2101234567`.
+    pub fn key(&self) -> String {
+        format!("{}-{}-{}", self.platform, self.kind.as_str(), self.id)
+    }
+}
+
+/// Check whether a URL is hosted on Twitch or Kick.
+pub fn is_stream_platform_url(url: &str) -> bool {
+    extract_host(url)
+        .and_then(|host| StreamPlatform::from_host(&host))
+        .is_some()
+}
+
+/// Parse a Twitch or Kick VOD/clip URL.
+pub fn parse_stream_url(url: &str) -> Result<StreamVideoRef, StreamUrlError> {
+    let parsed = parse_http_url(url).ok_or(StreamUrlError::NotStreamUrl)?;
+    let host = parsed
+        .host_str()
+        .map(|h| h.to_ascii_lowercase())
+        .ok_or(StreamUrlError::NotStreamUrl)?;
+    let platform = StreamPlatform::from_host(&host).ok_or(StreamUrlError::NotStreamUrl)?;
+
+    let segments: Vec<&str> = parsed
+        .path_segments()
+        .map(|s| s.filter(|segment| !segment.is_empty()).collect())
+        .unwrap_or_default();
+    let query = |key: &str| {
+        parsed
+            .query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.into_owned())
+    };
+
+    match platform {
+        StreamPlatform::Twitch if host == "clips.twitch.tv" => {
+            let slug = match segments.as_slice() {
+                ["embed"] => query("clip").ok_or(StreamUrlError::UnsupportedPath)?,
+                [slug] => slug.to_string(),
+                _ => return Err(StreamUrlError::UnsupportedPath),
+            };
+            twitch_clip(&slug, None)
+        }
+        StreamPlatform::Twitch => match segments.as_slice() {
+            ["videos", id] => twitch_vod(id, None),
+            [channel, "video" | "v", id] => twitch_vod(id, Some(channel)),
+            [channel, "clip", slug] => twitch_clip(slug, Some(channel)),
+            [_] | [_, "videos"] | [_, "clips"] => Err(StreamUrlError::ChannelPage),
+            _ => Err(StreamUrlError::UnsupportedPath),
+        },
+        StreamPlatform::Kick => match segments.as_slice() {
+            ["video", id] => kick_vod(id, None),
+            [channel, "videos", id] => kick_vod(id, Some(channel)),
+            [channel, "clips", id] => kick_clip(id, channel),
+            [channel] => match query("clip") {
+                Some(id) => kick_clip(&id, channel),
+                None => Err(StreamUrlError::ChannelPage),
+            },
+            [_, "videos"] | [_, "clips"] => Err(StreamUrlError::ChannelPage),
+            _ => Err(StreamUrlError::UnsupportedPath),
+        },
+    }
+}
+
+/// Canonical live URL of a Twitch or Kick channel page.
+///
+/// `twitch.tv/{channel}` and `kick.com/{channel}` become
+/// `https://www.twitch.tv/{channel}` and `https://kick.com/{channel}`;
+/// VOD, clip and other pages are rejected.
+pub fn live_channel_url(url: &str) -> Result<String, StreamUrlError> {
+    let parsed = parse_http_url(url).ok_or(StreamUrlError::NotStreamUrl)?;
+    let host = parsed
+        .host_str()
+        .map(|h| h.to_ascii_lowercase())
+        .ok_or(StreamUrlError::NotStreamUrl)?;
+    let platform = StreamPlatform::from_host(&host).ok_or(StreamUrlError::NotStreamUrl)?;
+    if host == "clips.twitch.tv" {
+        return Err(StreamUrlError::UnsupportedPath);
+    }
+
+    let segments: Vec<&str> = parsed
+        .path_segments()
+        .map(|s| s.filter(|segment| !segment.is_empty()).collect())
+        .unwrap_or_default();
+    let channel = match segments.as_slice() {
+        [channel] => channel.to_ascii_lowercase(),
+        _ => return Err(StreamUrlError::UnsupportedPath),
+    };
+    if channel.len() > MAX_CHANNEL_LEN
+        || !is_slug(&channel)
+        || RESERVED_CHANNEL_PATHS.contains(&channel.as_str())
+    {
+        return Err(StreamUrlError::UnsupportedPath);
+    }
+
+    Ok(match platform {
+        StreamPlatform::Twitch => format!("https://www.twitch.tv/{}", channel),
+        StreamPlatform::Kick => format!("https://kick.com/{}", channel),
+    })
+}
+
+fn twitch_vod(id: &str, channel: Option<&str>) -> Result<StreamVideoRef, StreamUrlError> {
+    // Twitch sometimes prefixes VOD IDs with "v"
+    let id = id.strip_prefix('v').unwrap_or(id);
+    if id.is_empty() || id.len() > 20 || !id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(StreamUrlError::InvalidId);
+    }
+    build(StreamPlatform::Twitch, StreamContentKind::Vod, id, channel)
+}
+
+fn twitch_clip(slug: &str, channel: Option<&str>) -> Result<StreamVideoRef, StreamUrlError> {
+    if slug.is_empty() || slug.len() > MAX_CLIP_SLUG_LEN || !is_slug(slug) {
+        return Err(StreamUrlError::InvalidId);
+    }
+    build(StreamPlatform::Twitch, StreamContentKind::Clip, slug, channel)
+}
+
+fn kick_vod(id: &str, channel: Option<&str>) -> Result<StreamVideoRef, StreamUrlError> {
+    if !is_uuid(id) {
+        return Err(StreamUrlError::InvalidId);
+    }
+    build(
+        StreamPlatform::Kick,
+        StreamContentKind::Vod,
+        &id.to_ascii_lowercase(),
+        channel,
+    )
+}
+
+fn kick_clip(id: &str, channel: &str) -> Result<StreamVideoRef, StreamUrlError> {
+    let valid = id
+        .strip_prefix("clip_")
+        .is_some_and(|rest| !rest.is_empty() && is_slug(rest))
+        && id.len() <= MAX_CLIP_SLUG_LEN;
+    if !valid {
+        return Err(StreamUrlError::InvalidId);
+    }
+    build(StreamPlatform::Kick, StreamContentKind::Clip, id, Some(channel))
+}
+
+fn build(
+    platform: StreamPlatform,
+    kind: StreamContentKind,
+    id: &str,
+    channel: Option<&str>,
+) -> Result<StreamVideoRef, StreamUrlError> {
+    let channel = match channel {
+        Some(name) if name.len() <= MAX_CHANNEL_LEN && is_slug(name) => {
+            Some(name.to_ascii_lowercase())
+        }
+        Some(_) => return Err(StreamUrlError::UnsupportedPath),
+        None => None,
+    };
+    Ok(StreamVideoRef {
+        platform,
+        kind,
+        id: id.to_string(),
+        channel,
+    })
+}
+
+/// ASCII alphanumeric, `-` and `_` only.
+fn is_slug(s: &str) -> bool {
+    !s.is_empty()
+        && s
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Hyphenated 8-4-4-4-12 hex UUID.
+fn is_uuid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KICK_UUID: &str = "5b1c9f2e-8a3d-4e6f-9b0a-1c2d3e4f5a6b";
+
+    #[test]
+    fn test_twitch_urls() {
+        let vod = parse_stream_url("https://www.twitch.tv/videos/2101234567?t=1h2m3s").unwrap();
+        assert_eq!(vod.platform, StreamPlatform::Twitch);
+        assert_eq!(vod.kind, StreamContentKind::Vod);
+        assert_eq!(vod.id, "2101234567");
+        assert_eq!(vod.canonical_url(), "https://www.twitch.tv/videos/2101234567");
+        assert_eq!(vod.key(), "twitch-vod-2101234567");
+
+        let vod = parse_stream_url("https://m.twitch.tv/SomeStreamer/video/v2101234567").unwrap();
+        assert_eq!(vod.id, "2101234567");
+        assert_eq!(vod.channel.as_deref(), Some("somestreamer"));
+
+        let clip = parse_stream_url("https://clips.twitch.tv/FunnyClipSlug-AbC_123").unwrap();
+        assert_eq!(clip.kind, StreamContentKind::Clip);
+        assert_eq!(clip.canonical_url(), "https://clips.twitch.tv/FunnyClipSlug-AbC_123");
+
+        let clip = parse_stream_url("twitch.tv/streamer/clip/FunnyClipSlug").unwrap();
+        assert_eq!(clip.id, "FunnyClipSlug");
+        assert_eq!(clip.channel.as_deref(), Some("streamer"));
+
+        let embed = parse_stream_url("https://clips.twitch.tv/embed?clip=FunnyClipSlug").unwrap();
+        assert_eq!(embed.id, "FunnyClipSlug");
+    }
+
+    #[test]
+    fn test_kick_urls() {
+        let vod = parse_stream_url(&format!("https://kick.com/streamer/videos/{}", KICK_UUID)).unwrap();
+        assert_eq!(vod.platform, StreamPlatform::Kick);
+        assert_eq!(vod.kind, StreamContentKind::Vod);
+        assert_eq!(
+            vod.canonical_url(),
+            format!("https://kick.com/streamer/videos/{}", KICK_UUID)
+        );
+
+        let vod = parse_stream_url(&format!("https://kick.com/video/{}", KICK_UUID.to_uppercase())).unwrap();
+        assert_eq!(vod.id, KICK_UUID);
+        assert_eq!(vod.canonical_url(), format!("https://kick.com/video/{}", KICK_UUID));
+
+        let clip = parse_stream_url("https://kick.com/streamer/clips/clip_01HXYZABC").unwrap();
+        assert_eq!(clip.kind, StreamContentKind::Clip);
+        assert_eq!(clip.canonical_url(), "https://kick.com/streamer/clips/clip_01HXYZABC");
+
+        let clip = parse_stream_url("https://kick.com/streamer?clip=clip_01HXYZABC").unwrap();
+        assert_eq!(clip.id, "clip_01HXYZABC");
+    }
+
+    #[test]
+    fn test_rejected_urls() {
+        assert_eq!(
+            parse_stream_url("https://youtube.com/watch?v=dQw4w9WgXcQ"),
+            Err(StreamUrlError::NotStreamUrl)
+        );
+        assert_eq!(
+            parse_stream_url("https://nottwitch.tv/videos/123"),
+            Err(StreamUrlError::NotStreamUrl)
+        );
+        assert_eq!(
+            parse_stream_url("https://www.twitch.tv/streamer"),
+            Err(StreamUrlError::ChannelPage)
+        );
+        assert_eq!(
+            parse_stream_url("https://kick.com/streamer"),
+            Err(StreamUrlError::ChannelPage)
+        );
+        assert_eq!(
+            parse_stream_url("https://www.twitch.tv/videos/12ab"),
+            Err(StreamUrlError::InvalidId)
+        );
+        assert_eq!(
+            parse_stream_url("https://kick.com/video/not-a-uuid"),
+            Err(StreamUrlError::InvalidId)
+        );
+        assert_eq!(
+            parse_stream_url("https://kick.com/streamer/clips/01HXYZABC"),
+            Err(StreamUrlError::InvalidId)
+        );
+        assert_eq!(
+            parse_stream_url("https://www.twitch.tv/directory/game/Chess"),
+            Err(StreamUrlError::UnsupportedPath)
+        );
+        assert_eq!(
+            parse_stream_url("ftp://twitch.tv/videos/123"),
+            Err(StreamUrlError::NotStreamUrl)
+        );
+    }
+
+    #[test]
+    fn test_live_channel_url() {
+        assert_eq!(
+            live_channel_url("https://m.twitch.tv/SomeStreamer?referrer=raid").unwrap(),
+            "https://www.twitch.tv/somestreamer"
+        );
+        assert_eq!(
+            live_channel_url("https://www.kick.com/streamer_1/").unwrap(),
+            "https://kick.com/streamer_1"
+        );
+
+        for url in [
+            "https://www.twitch.tv/videos/2101234567",
+            "https://www.twitch.tv/directory",
+            "https://clips.twitch.tv/SomeSlug",
+            "https://kick.com/streamer/clips/clip_01HXYZ",
+            "https://www.twitch.tv/",
+            "https://www.youtube.com/@creator/live",
+        ] {
+            assert!(live_channel_url(url).is_err(), "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_is_stream_platform_url() {
+        assert!(is_stream_platform_url("https://www.twitch.tv/videos/1"));
+        assert!(is_stream_platform_url("https://kick.com/streamer"));
+        assert!(!is_stream_platform_url("https://youtube.com/watch?v=dQw4w9WgXcQ"));
+        assert!(!is_stream_platform_url("https://evil-twitch.tv/videos/1"));
+    }
+}
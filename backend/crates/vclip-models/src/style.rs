@@ -419,6 +419,9 @@ impl AspectRatio {
         height: 8,
     };
 
+    /// Widest supported ratio between the long and the short side (4:1 or 1:4).
+    pub const MAX_ELONGATION: u32 = 4;
+
     /// Create a new aspect ratio.
     pub const fn new(width: u32, height: u32) -> Self {
         Self { width, height }
@@ -428,6 +431,13 @@ impl AspectRatio {
     pub fn as_f64(&self) -> f64 {
         self.width as f64 / self.height as f64
     }
+
+    /// Whether the ratio lies between 1:4 and 4:1.
+    pub fn is_supported(&self) -> bool {
+        let (w, h) = (self.width as u64, self.height as u64);
+        let max = Self::MAX_ELONGATION as u64;
+        w > 0 && h > 0 && w <= h * max && h <= w * max
+    }
 }
 
 impl fmt::Display for AspectRatio {
@@ -456,7 +466,12 @@ impl FromStr for AspectRatio {
             return Err(AspectRatioParseError::ZeroValue);
         }
 
-        Ok(AspectRatio { width, height })
+        let aspect = AspectRatio { width, height };
+        if !aspect.is_supported() {
+            return Err(AspectRatioParseError::OutOfRange(s.to_string()));
+        }
+
+        Ok(aspect)
     }
 }
 
@@ -474,8 +489,108 @@ pub enum AspectRatioParseError {
     InvalidNumber(String),
     #[error("Aspect ratio cannot have zero values")]
     ZeroValue,
+    #[error("Unsupported aspect ratio: {0}, expected between 1:4 and 4:1")]
+    OutOfRange(String),
+}
+
+/// Output resolution preset.
+///
+/// The preset fixes the length of the *short* side of the output frame;
+/// the long side follows from the target aspect ratio. A 9:16 clip at
+/// `1080p` is 1080×1920, a 16:9 clip at `1080p` is 1920×1080.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, JsonSchema)]
+pub enum ResolutionPreset {
+    /// 720 pixels on the short side
+    #[serde(rename = "720p")]
+    P720,
+    /// 1080 pixels on the short side (default)
+    #[default]
+    #[serde(rename = "1080p")]
+    P1080,
+    /// 2160 pixels on the short side
+    #[serde(rename = "4k", alias = "2160p")]
+    P2160,
+}
+
+impl ResolutionPreset {
+    /// All presets, smallest first.
+    pub const ALL: [ResolutionPreset; 3] = [Self::P720, Self::P1080, Self::P2160];
+
+    /// Longest output side in pixels (well inside libx264's frame size limits).
+    pub const MAX_LONG_SIDE: u32 = 8192;
+
+    /// Length of the short side of the output frame in pixels.
+    pub const fn short_side(&self) -> u32 {
+        match self {
+            Self::P720 => 720,
+            Self::P1080 => 1080,
+            Self::P2160 => 2160,
+        }
+    }
+
+    /// Output dimensions `(width, height)` for an aspect ratio.
+    ///
+    /// Both dimensions are even (required by libx264). Ratios outside
+    /// 1:4..4:1 are clamped to that range, and the long side never exceeds
+    /// `MAX_LONG_SIDE` (the short side shrinks to keep the ratio).
+    pub fn dimensions(&self, aspect: AspectRatio) -> (u32, u32) {
+        let max_elongation = AspectRatio::MAX_ELONGATION as f64;
+        let (long_units, short_units) = if aspect.width <= aspect.height {
+            (aspect.height, aspect.width)
+        } else {
+            (aspect.width, aspect.height)
+        };
+        let elongation =
+            (long_units as f64 / short_units.max(1) as f64).clamp(1.0, max_elongation);
+
+        let mut short = self.short_side() as f64;
+        let mut long = short * elongation;
+        if long > Self::MAX_LONG_SIDE as f64 {
+            long = Self::MAX_LONG_SIDE as f64;
+            short = long / elongation;
+        }
+
+        let even = |v: f64| ((v.round() as u32) / 2 * 2).max(2);
+        if aspect.width <= aspect.height {
+            (even(short), even(long))
+        } else {
+            (even(long), even(short))
+        }
+    }
+
+    /// Returns the canonical string form.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::P720 => "720p",
+            Self::P1080 => "1080p",
+            Self::P2160 => "4k",
+        }
+    }
+}
+
+impl fmt::Display for ResolutionPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
+impl FromStr for ResolutionPreset {
+    type Err = ResolutionPresetParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "720p" | "720" | "hd" => Ok(Self::P720),
+            "1080p" | "1080" | "fhd" => Ok(Self::P1080),
+            "4k" | "2160p" | "2160" | "uhd" => Ok(Self::P2160),
+            _ => Err(ResolutionPresetParseError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Unknown resolution preset: {0}, expected 720p, 1080p or 4k")]
+pub struct ResolutionPresetParseError(String);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -548,6 +663,64 @@ mod tests {
         assert_eq!("1:1".parse::<AspectRatio>().unwrap(), AspectRatio::SQUARE);
         assert!("invalid".parse::<AspectRatio>().is_err());
         assert!("0:16".parse::<AspectRatio>().is_err());
+        assert_eq!("4:1".parse::<AspectRatio>().unwrap(), AspectRatio::new(4, 1));
+        assert!(matches!(
+            "1:100000".parse::<AspectRatio>(),
+            Err(AspectRatioParseError::OutOfRange(_))
+        ));
+        assert!(matches!(
+            "4294967295:1".parse::<AspectRatio>(),
+            Err(AspectRatioParseError::OutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolution_preset_dimensions() {
+        let p = ResolutionPreset::P1080;
+        assert_eq!(p.dimensions(AspectRatio::PORTRAIT), (1080, 1920));
+        assert_eq!(p.dimensions(AspectRatio::SQUARE), (1080, 1080));
+        assert_eq!(p.dimensions(AspectRatio::INSTAGRAM_PORTRAIT), (1080, 1350));
+        assert_eq!(p.dimensions(AspectRatio::new(16, 9)), (1920, 1080));
+        assert_eq!(
+            ResolutionPreset::P720.dimensions(AspectRatio::PORTRAIT),
+            (720, 1280)
+        );
+        assert_eq!(
+            ResolutionPreset::P2160.dimensions(AspectRatio::new(16, 9)),
+            (3840, 2160)
+        );
+    }
+
+    #[test]
+    fn test_resolution_preset_dimensions_are_bounded() {
+        // The long side is capped and the short side shrinks with it
+        assert_eq!(
+            ResolutionPreset::P2160.dimensions(AspectRatio::new(4, 1)),
+            (8192, 2048)
+        );
+        // Ratios that bypassed parsing are clamped to 1:4
+        assert_eq!(
+            ResolutionPreset::P1080.dimensions(AspectRatio::new(1, u32::MAX)),
+            (1080, 4320)
+        );
+    }
+
+    #[test]
+    fn test_resolution_preset_parse() {
+        assert_eq!(
+            "4K".parse::<ResolutionPreset>().unwrap(),
+            ResolutionPreset::P2160
+        );
+        assert_eq!(
+            "720p".parse::<ResolutionPreset>().unwrap(),
+            ResolutionPreset::P720
+        );
+        assert!("8k".parse::<ResolutionPreset>().is_err());
+        assert_eq!(
+            serde_json::to_string(&ResolutionPreset::P2160).unwrap(),
+            "\"4k\""
+        );
+        assert_eq!(ResolutionPreset::default(), ResolutionPreset::P1080);
     }
 
     #[test]
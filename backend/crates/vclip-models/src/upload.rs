@@ -0,0 +1,275 @@
+//! Local file upload data models.
+//!
+//! Users can upload their own recordings as a video source instead of a
+//! URL. The file goes straight to R2 as a multipart upload: the API starts
+//! the upload and hands out presigned part URLs, the client PUTs the parts
+//! (retrying or resuming individual parts as needed), and the API completes
+//! the upload and starts an analysis of the stored file.
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Largest file that can be uploaded (matches the worker's media size limit).
+pub const MAX_UPLOAD_SIZE_BYTES: u64 = 2048 * 1024 * 1024;
+
+/// Default size of each uploaded part.
+pub const UPLOAD_PART_SIZE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Maximum number of parts in a multipart upload (S3/R2 limit).
+pub const MAX_UPLOAD_PARTS: u64 = 10_000;
+
+/// Scheme of the source URL recorded for uploaded videos.
+pub const UPLOAD_SOURCE_SCHEME: &str = "upload://";
+
+/// File extensions accepted for upload.
+pub const UPLOAD_EXTENSIONS: [&str; 8] = ["mp4", "mov", "avi", "mkv", "webm", "flv", "wmv", "m4v"];
+
+/// Lifecycle of an upload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadStatus {
+    /// Parts are being uploaded
+    Pending,
+    /// Parts are being assembled and the analysis started; completing again resumes
+    Completing,
+    /// Upload completed and analysis started
+    Completed,
+    /// Upload abandoned by the user
+    Aborted,
+}
+
+impl UploadStatus {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UploadStatus::Pending => "pending",
+            UploadStatus::Completing => "completing",
+            UploadStatus::Completed => "completed",
+            UploadStatus::Aborted => "aborted",
+        }
+    }
+
+    /// Parse a stored status.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(UploadStatus::Pending),
+            "completing" => Some(UploadStatus::Completing),
+            "completed" => Some(UploadStatus::Completed),
+            "aborted" => Some(UploadStatus::Aborted),
+            _ => None,
+        }
+    }
+}
+
+/// A video file uploaded by a user as an analysis source.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SourceUpload {
+    /// Unique identifier (UUID)
+    pub id: String,
+
+    /// User who owns the upload
+    pub user_id: String,
+
+    /// Original file name, used as the video title
+    pub filename: String,
+
+    /// MIME type the parts are uploaded with
+    pub content_type: String,
+
+    /// Declared file size in bytes
+    pub size_bytes: u64,
+
+    /// R2 key the file is stored at
+    pub r2_key: String,
+
+    /// R2 multipart upload ID
+    pub multipart_upload_id: String,
+
+    /// Size of every part except the last
+    pub part_size_bytes: u64,
+
+    /// Number of parts the file is split into
+    pub part_count: u32,
+
+    /// Current status
+    pub status: UploadStatus,
+
+    /// Draft created when the upload completed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft_id: Option<String>,
+
+    /// Analysis job started when the upload completed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
+
+    /// When the upload was started
+    pub created_at: DateTime<Utc>,
+
+    /// When the upload was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SourceUpload {
+    /// Create a pending upload.
+    pub fn new(
+        id: impl Into<String>,
+        user_id: impl Into<String>,
+        filename: impl Into<String>,
+        size_bytes: u64,
+        multipart_upload_id: impl Into<String>,
+    ) -> Self {
+        let id = id.into();
+        let user_id = user_id.into();
+        let filename = filename.into();
+        let part_size_bytes = upload_part_size(size_bytes);
+        let now = Utc::now();
+
+        Self {
+            r2_key: upload_r2_key(&user_id, &id),
+            content_type: upload_content_type(&filename).to_string(),
+            part_count: size_bytes.div_ceil(part_size_bytes).max(1) as u32,
+            id,
+            user_id,
+            filename,
+            size_bytes,
+            multipart_upload_id: multipart_upload_id.into(),
+            part_size_bytes,
+            status: UploadStatus::Pending,
+            draft_id: None,
+            job_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Source URL recorded on drafts and videos made from this upload.
+    pub fn source_url(&self) -> String {
+        format!("{}{}", UPLOAD_SOURCE_SCHEME, self.id)
+    }
+
+    /// Expected size of a part, or `None` if the part number is out of range.
+    pub fn part_size(&self, part_number: u32) -> Option<u64> {
+        if part_number == 0 || part_number > self.part_count {
+            return None;
+        }
+        let offset = (part_number as u64 - 1) * self.part_size_bytes;
+        Some((self.size_bytes - offset).min(self.part_size_bytes))
+    }
+}
+
+/// R2 key of an uploaded source file.
+///
+/// Keyed by upload rather than video, so every video rendered from the
+/// upload shares one copy.
+pub fn upload_r2_key(user_id: &str, upload_id: &str) -> String {
+    format!("uploads/{}/{}/source", user_id, upload_id)
+}
+
+/// Whether a source URL refers to an uploaded file.
+pub fn is_upload_source(source_url: &str) -> bool {
+    source_url.starts_with(UPLOAD_SOURCE_SCHEME)
+}
+
+/// Part size for a file, growing past the default to stay within the part limit.
+pub fn upload_part_size(size_bytes: u64) -> u64 {
+    UPLOAD_PART_SIZE_BYTES.max(size_bytes.div_ceil(MAX_UPLOAD_PARTS))
+}
+
+/// Validate an upload's file name and size.
+///
+/// Returns the trimmed file name.
+pub fn validate_upload(filename: &str, size_bytes: u64) -> Result<String, String> {
+    let filename = filename.trim();
+    if filename.is_empty() || filename.chars().count() > 255 {
+        return Err("File name must be 1-255 characters".to_string());
+    }
+    if filename.contains(['/', '\\']) || filename.chars().any(char::is_control) {
+        return Err("File name must not contain path separators".to_string());
+    }
+
+    let extension = upload_extension(filename)
+        .ok_or_else(|| format!("Unsupported file type; expected one of: {}", UPLOAD_EXTENSIONS.join(", ")))?;
+    if !UPLOAD_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(format!(
+            "Unsupported file type .{}; expected one of: {}",
+            extension,
+            UPLOAD_EXTENSIONS.join(", ")
+        ));
+    }
+
+    if size_bytes == 0 {
+        return Err("File is empty".to_string());
+    }
+    if size_bytes > MAX_UPLOAD_SIZE_BYTES {
+        return Err(format!(
+            "File exceeds the maximum upload size of {} MB",
+            MAX_UPLOAD_SIZE_BYTES / (1024 * 1024)
+        ));
+    }
+
+    Ok(filename.to_string())
+}
+
+/// Title for a video made from an uploaded file (its name without extension).
+pub fn upload_title(filename: &str) -> &str {
+    match filename.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => filename,
+    }
+}
+
+fn upload_extension(filename: &str) -> Option<String> {
+    filename
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+}
+
+fn upload_content_type(filename: &str) -> &'static str {
+    match upload_extension(filename).as_deref() {
+        Some("mp4") | Some("m4v") => "video/mp4",
+        Some("mov") => "video/quicktime",
+        Some("webm") => "video/webm",
+        Some("mkv") => "video/x-matroska",
+        Some("avi") => "video/x-msvideo",
+        Some("flv") => "video/x-flv",
+        Some("wmv") => "video/x-ms-wmv",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_layout() {
+        let upload = SourceUpload::new("up1", "u1", "stream.mkv", 150 * 1024 * 1024, "mp1");
+        assert_eq!(upload.part_size_bytes, UPLOAD_PART_SIZE_BYTES);
+        assert_eq!(upload.part_count, 3);
+        assert_eq!(upload.part_size(1), Some(UPLOAD_PART_SIZE_BYTES));
+        assert_eq!(upload.part_size(3), Some(22 * 1024 * 1024));
+        assert_eq!(upload.part_size(4), None);
+        assert_eq!(upload.part_size(0), None);
+        assert_eq!(upload.content_type, "video/x-matroska");
+        assert_eq!(upload.r2_key, "uploads/u1/up1/source");
+        assert!(is_upload_source(&upload.source_url()));
+
+        // Huge files get bigger parts instead of more than the part limit
+        let size: u64 = 1_000_000 * 1024 * 1024;
+        assert!(size.div_ceil(upload_part_size(size)) <= MAX_UPLOAD_PARTS);
+    }
+
+    #[test]
+    fn test_validate_upload() {
+        assert_eq!(validate_upload(" my vod.MP4 ", 1024).unwrap(), "my vod.MP4");
+        assert!(validate_upload("notes.txt", 1024).is_err());
+        assert!(validate_upload("noextension", 1024).is_err());
+        assert!(validate_upload("../etc/passwd.mp4", 1024).is_err());
+        assert!(validate_upload("clip.mp4", 0).is_err());
+        assert!(validate_upload("clip.mp4", MAX_UPLOAD_SIZE_BYTES + 1).is_err());
+
+        assert_eq!(upload_title("my vod.mp4"), "my vod");
+        assert_eq!(upload_title(".mp4"), ".mp4");
+    }
+}
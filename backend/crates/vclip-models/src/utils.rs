@@ -94,7 +94,7 @@ fn is_allowed_youtube_host(host: &str) -> bool {
         .any(|base| host == *base || host.ends_with(&format!(".{}", base)))
 }
 
-fn parse_http_url(url: &str) -> Option<Url> {
+pub(crate) fn parse_http_url(url: &str) -> Option<Url> {
     let trimmed = url.trim();
 
     let parsed = Url::parse(trimmed)
@@ -68,6 +68,8 @@ pub enum VideoStatus {
     Completed,
     /// Processing failed
     Failed,
+    /// Processing was cancelled by the user
+    Cancelled,
 }
 
 impl VideoStatus {
@@ -77,6 +79,7 @@ impl VideoStatus {
             VideoStatus::Analyzed => "analyzed",
             VideoStatus::Completed => "completed",
             VideoStatus::Failed => "failed",
+            VideoStatus::Cancelled => "cancelled",
         }
     }
 }
@@ -268,6 +271,10 @@ pub struct VideoMetadata {
     #[serde(default = "default_aspect")]
     pub target_aspect: String,
 
+    /// Output resolution preset (e.g. "1080p")
+    #[serde(default = "default_resolution")]
+    pub resolution: String,
+
     /// Number of clips generated
     #[serde(default)]
     pub clips_count: u32,
@@ -304,6 +311,11 @@ pub struct VideoMetadata {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source_video_error: Option<String>,
 
+    /// R2 key of the segment manifest when the source is a live capture;
+    /// renders cut scenes from the recorded segments instead of the URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub live_manifest_r2_key: Option<String>,
+
     // === Processing Progress Fields ===
 
     /// Current processing progress (for frontend polling).
@@ -316,6 +328,10 @@ fn default_aspect() -> String {
     "9:16".to_string()
 }
 
+fn default_resolution() -> String {
+    crate::ResolutionPreset::default().to_string()
+}
+
 impl VideoMetadata {
     /// Create a new video metadata record.
     pub fn new(
@@ -345,6 +361,7 @@ impl VideoMetadata {
             styles_processed: Vec::new(),
             crop_mode: "none".to_string(),
             target_aspect: "9:16".to_string(),
+            resolution: default_resolution(),
             clips_count: 0,
             total_size_bytes: 0,
             clips_by_style: HashMap::new(),
@@ -355,11 +372,23 @@ impl VideoMetadata {
             source_video_status: None,
             source_video_expires_at: None,
             source_video_error: None,
+            live_manifest_r2_key: None,
             // Processing progress - initialized as None
             processing_progress: None,
         }
     }
 
+    /// Set the output format the clips are rendered in.
+    pub fn with_output_format(
+        mut self,
+        target_aspect: crate::AspectRatio,
+        resolution: crate::ResolutionPreset,
+    ) -> Self {
+        self.target_aspect = target_aspect.to_string();
+        self.resolution = resolution.to_string();
+        self
+    }
+
     /// Mark as completed.
     pub fn complete(mut self) -> Self {
         self.status = VideoStatus::Completed;
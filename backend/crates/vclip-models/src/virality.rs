@@ -0,0 +1,329 @@
+//! Per-scene quality metrics and the composite virality score.
+//!
+//! Metrics are filled in as they become available: duration fit, detector
+//! confidence and hook strength at analysis time, speech ratio and face
+//! presence once the scene has been rendered. The virality score is the
+//! weighted mean of whichever metrics are present, so scenes can be ranked
+//! from the moment they are detected.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::DraftScene;
+use crate::highlight::Highlight;
+use crate::timestamp::parse_timestamp;
+
+/// Length of the opening scored for hook strength (seconds).
+pub const HOOK_WINDOW_SECS: f64 = 3.0;
+
+/// Durations in this range fit every short-form platform (seconds).
+const IDEAL_DURATION_SECS: (f64, f64) = (20.0, 60.0);
+
+/// Durations outside this range get no duration credit (seconds).
+const USABLE_DURATION_SECS: (f64, f64) = (5.0, 180.0);
+
+/// Quality metrics for one scene, each normalized to 0-1.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct QualityMetrics {
+    /// Strength of the opening hook (first 3 seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hook_strength: Option<f64>,
+
+    /// Fraction of the scene containing speech (VAD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speech_ratio: Option<f64>,
+
+    /// Fraction of analyzed frames with at least one face
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub face_presence: Option<f64>,
+
+    /// How well the duration fits short-form platforms
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_fit: Option<f64>,
+
+    /// Confidence reported by the highlight detector
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm_confidence: Option<f64>,
+}
+
+/// Relative weight of each metric in the virality score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreWeights {
+    pub hook_strength: f64,
+    pub speech_ratio: f64,
+    pub face_presence: f64,
+    pub duration_fit: f64,
+    pub llm_confidence: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            hook_strength: 0.3,
+            speech_ratio: 0.15,
+            face_presence: 0.15,
+            duration_fit: 0.15,
+            llm_confidence: 0.25,
+        }
+    }
+}
+
+impl QualityMetrics {
+    /// Set the duration fit for a scene of `duration_secs`.
+    pub fn with_duration(mut self, duration_secs: f64) -> Self {
+        self.duration_fit = Some(duration_fit(duration_secs));
+        self
+    }
+
+    /// Whether no metric is known.
+    pub fn is_empty(&self) -> bool {
+        self.entries(&ScoreWeights::default())
+            .iter()
+            .all(|(value, _)| value.is_none())
+    }
+
+    /// Whether the render-time metrics (speech and faces) are known.
+    pub fn has_render_metrics(&self) -> bool {
+        self.speech_ratio.is_some() && self.face_presence.is_some()
+    }
+
+    /// Weighted mean of the known metrics (0-1), or `None` if none are known.
+    pub fn composite(&self, weights: &ScoreWeights) -> Option<f64> {
+        let (sum, total_weight) = self
+            .entries(weights)
+            .iter()
+            .filter_map(|(value, weight)| value.map(|v| (v.clamp(0.0, 1.0), weight.max(0.0))))
+            .fold((0.0, 0.0), |(sum, total), (v, w)| (sum + v * w, total + w));
+        (total_weight > 0.0).then(|| round_score(sum / total_weight))
+    }
+
+    /// Virality score with the default weights.
+    pub fn virality_score(&self) -> Option<f64> {
+        self.composite(&ScoreWeights::default())
+    }
+
+    /// Combine with newer metrics; known values in `newer` win.
+    pub fn merge(self, newer: QualityMetrics) -> Self {
+        Self {
+            hook_strength: newer.hook_strength.or(self.hook_strength),
+            speech_ratio: newer.speech_ratio.or(self.speech_ratio),
+            face_presence: newer.face_presence.or(self.face_presence),
+            duration_fit: newer.duration_fit.or(self.duration_fit),
+            llm_confidence: newer.llm_confidence.or(self.llm_confidence),
+        }
+    }
+
+    /// Metrics after the scene's timestamps changed.
+    ///
+    /// Only the detector confidence still applies; time-based metrics are
+    /// dropped and recomputed on the next render.
+    pub fn retimed(&self, duration_secs: f64) -> Self {
+        Self {
+            llm_confidence: self.llm_confidence,
+            ..Default::default()
+        }
+        .with_duration(duration_secs)
+    }
+
+    fn entries(&self, weights: &ScoreWeights) -> [(Option<f64>, f64); 5] {
+        [
+            (self.hook_strength, weights.hook_strength),
+            (self.speech_ratio, weights.speech_ratio),
+            (self.face_presence, weights.face_presence),
+            (self.duration_fit, weights.duration_fit),
+            (self.llm_confidence, weights.llm_confidence),
+        ]
+    }
+}
+
+/// Duration fit: 1.0 for 20-60s, falling linearly to 0 at 5s and 180s.
+pub fn duration_fit(duration_secs: f64) -> f64 {
+    let (ideal_min, ideal_max) = IDEAL_DURATION_SECS;
+    let (usable_min, usable_max) = USABLE_DURATION_SECS;
+    let fit = if duration_secs < ideal_min {
+        (duration_secs - usable_min) / (ideal_min - usable_min)
+    } else if duration_secs > ideal_max {
+        (usable_max - duration_secs) / (usable_max - ideal_max)
+    } else {
+        1.0
+    };
+    round_score(fit.clamp(0.0, 1.0))
+}
+
+/// Round to three decimals so stored scores stay readable.
+fn round_score(score: f64) -> f64 {
+    (score * 1000.0).round() / 1000.0
+}
+
+/// Scene ordering for highlight and draft listings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SceneSort {
+    /// Scene ID, ascending (default)
+    #[default]
+    Id,
+    /// Start time, ascending
+    Start,
+    /// Virality score, best first; unscored scenes last
+    Score,
+}
+
+/// A scene that can be ranked by virality score.
+pub trait ScoredScene {
+    /// Scene ID
+    fn scene_id(&self) -> u32;
+    /// Start time (seconds)
+    fn start_secs(&self) -> f64;
+    /// Composite virality score (0-1)
+    fn score(&self) -> Option<f64>;
+}
+
+impl ScoredScene for Highlight {
+    fn scene_id(&self) -> u32 {
+        self.id
+    }
+
+    fn start_secs(&self) -> f64 {
+        parse_timestamp(&self.start).unwrap_or(0.0)
+    }
+
+    fn score(&self) -> Option<f64> {
+        self.virality_score
+    }
+}
+
+impl ScoredScene for DraftScene {
+    fn scene_id(&self) -> u32 {
+        self.id
+    }
+
+    fn start_secs(&self) -> f64 {
+        parse_timestamp(&self.start).unwrap_or(0.0)
+    }
+
+    fn score(&self) -> Option<f64> {
+        self.virality_score
+    }
+}
+
+/// Query parameters for sorting and filtering scenes by score.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct SceneRankQuery {
+    /// Ordering; `None` keeps the stored order
+    #[serde(default)]
+    pub sort: Option<SceneSort>,
+    /// Drop scenes scoring below this (0-1); unscored scenes are dropped too
+    #[serde(default)]
+    pub min_score: Option<f64>,
+}
+
+impl SceneRankQuery {
+    /// Filter and sort scenes in place.
+    pub fn apply<T: ScoredScene>(&self, scenes: &mut Vec<T>) {
+        if let Some(min_score) = self.min_score {
+            scenes.retain(|s| s.score().is_some_and(|score| score >= min_score));
+        }
+        match self.sort {
+            None => {}
+            Some(SceneSort::Id) => scenes.sort_by_key(|s| s.scene_id()),
+            Some(SceneSort::Start) => scenes.sort_by(|a, b| {
+                a.start_secs()
+                    .total_cmp(&b.start_secs())
+                    .then(a.scene_id().cmp(&b.scene_id()))
+            }),
+            Some(SceneSort::Score) => scenes.sort_by(|a, b| {
+                let a_score = a.score().unwrap_or(f64::NEG_INFINITY);
+                let b_score = b.score().unwrap_or(f64::NEG_INFINITY);
+                b_score
+                    .total_cmp(&a_score)
+                    .then(a.scene_id().cmp(&b.scene_id()))
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_composite_renormalizes_missing_metrics() {
+        assert_eq!(QualityMetrics::default().virality_score(), None);
+        assert!(QualityMetrics::default().is_empty());
+
+        let metrics = QualityMetrics {
+            duration_fit: Some(1.0),
+            llm_confidence: Some(0.6),
+            ..Default::default()
+        };
+        // (0.15 * 1.0 + 0.25 * 0.6) / 0.4
+        assert_eq!(metrics.virality_score(), Some(0.75));
+        assert!(!metrics.has_render_metrics());
+    }
+
+    #[test]
+    fn test_duration_fit() {
+        assert_eq!(duration_fit(30.0), 1.0);
+        assert_eq!(duration_fit(12.5), 0.5);
+        assert_eq!(duration_fit(120.0), 0.5);
+        assert_eq!(duration_fit(2.0), 0.0);
+        assert_eq!(duration_fit(600.0), 0.0);
+    }
+
+    #[test]
+    fn test_merge_and_retime() {
+        let analysis = QualityMetrics {
+            hook_strength: Some(0.8),
+            llm_confidence: Some(0.9),
+            ..Default::default()
+        }
+        .with_duration(30.0);
+        let render = QualityMetrics {
+            speech_ratio: Some(0.7),
+            face_presence: Some(0.5),
+            ..Default::default()
+        };
+
+        let merged = analysis.merge(render);
+        assert_eq!(merged.hook_strength, Some(0.8));
+        assert_eq!(merged.speech_ratio, Some(0.7));
+        assert!(merged.has_render_metrics());
+
+        let retimed = merged.retimed(200.0);
+        assert_eq!(retimed.llm_confidence, Some(0.9));
+        assert_eq!(retimed.hook_strength, None);
+        assert_eq!(retimed.speech_ratio, None);
+        assert_eq!(retimed.duration_fit, Some(0.0));
+    }
+
+    #[test]
+    fn test_rank_query_sorts_and_filters() {
+        let scene = |id: u32, start: &str, score: Option<f64>| {
+            let mut h = Highlight::new(id, "t", start, "00:10:00");
+            h.virality_score = score;
+            h
+        };
+        let scenes = vec![
+            scene(1, "00:05:00", Some(0.4)),
+            scene(2, "00:01:00", None),
+            scene(3, "00:03:00", Some(0.9)),
+        ];
+
+        let mut by_score = scenes.clone();
+        let query: SceneRankQuery = serde_json::from_str(r#"{"sort":"score"}"#).unwrap();
+        query.apply(&mut by_score);
+        assert_eq!(by_score.iter().map(|s| s.id).collect::<Vec<_>>(), vec![3, 1, 2]);
+
+        let mut filtered = scenes.clone();
+        SceneRankQuery {
+            sort: Some(SceneSort::Start),
+            min_score: Some(0.3),
+        }
+        .apply(&mut filtered);
+        assert_eq!(filtered.iter().map(|s| s.id).collect::<Vec<_>>(), vec![3, 1]);
+
+        let mut unchanged = scenes;
+        SceneRankQuery::default().apply(&mut unchanged);
+        assert_eq!(unchanged.iter().map(|s| s.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,532 @@
+//! Outbound webhooks.
+//!
+//! Users register HTTPS endpoints that receive job and clip lifecycle events
+//! as JSON. Each request is signed with the endpoint's secret:
+//!
+//! ```text
+//! X-Vclip-Timestamp: 1767225600
+//! X-Vclip-Signature: v1=<base64url(HMAC-SHA256(secret, "{timestamp}.{body}"))>
+//! ```
+//!
+//! Receivers should recompute the signature over the raw body and reject
+//! stale timestamps. Failed deliveries are retried with exponential backoff.
+
+use std::net::IpAddr;
+use std::time::Duration;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use url::Url;
+
+use crate::ws::WsMessage;
+
+/// Header carrying the request signature.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Vclip-Signature";
+
+/// Header carrying the signing timestamp (Unix seconds).
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "X-Vclip-Timestamp";
+
+/// Header carrying the event type.
+pub const WEBHOOK_EVENT_HEADER: &str = "X-Vclip-Event";
+
+/// Header carrying the delivery ID (stable across retries).
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-Vclip-Delivery";
+
+/// Delivery attempts before a delivery is marked failed.
+pub const WEBHOOK_MAX_ATTEMPTS: u32 = 8;
+
+/// Delay before the first retry; each further retry waits three times longer.
+const WEBHOOK_RETRY_BASE: Duration = Duration::from_secs(30);
+
+/// Longest delay between retries.
+const WEBHOOK_RETRY_MAX: Duration = Duration::from_secs(6 * 3600);
+
+/// Prefix of endpoint signing secrets.
+const WEBHOOK_SECRET_PREFIX: &str = "whsec_";
+
+/// Event types delivered to webhook endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum WebhookEventType {
+    /// A clip finished rendering and was uploaded
+    #[serde(rename = "clip.uploaded")]
+    ClipUploaded,
+    /// All styles of a scene finished
+    #[serde(rename = "scene.completed")]
+    SceneCompleted,
+    /// A job finished successfully
+    #[serde(rename = "job.done")]
+    JobDone,
+    /// A job reported an error
+    #[serde(rename = "job.error")]
+    JobError,
+    /// A job was cancelled by the user
+    #[serde(rename = "job.cancelled")]
+    JobCancelled,
+    /// Test event sent on request; delivered regardless of filters
+    #[serde(rename = "test")]
+    Test,
+}
+
+impl WebhookEventType {
+    /// Event types endpoints can subscribe to.
+    pub const SUBSCRIBABLE: [WebhookEventType; 5] = [
+        WebhookEventType::ClipUploaded,
+        WebhookEventType::SceneCompleted,
+        WebhookEventType::JobDone,
+        WebhookEventType::JobError,
+        WebhookEventType::JobCancelled,
+    ];
+
+    /// Get string representation of the event type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::ClipUploaded => "clip.uploaded",
+            WebhookEventType::SceneCompleted => "scene.completed",
+            WebhookEventType::JobDone => "job.done",
+            WebhookEventType::JobError => "job.error",
+            WebhookEventType::JobCancelled => "job.cancelled",
+            WebhookEventType::Test => "test",
+        }
+    }
+
+    /// Parse an event type name.
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::SUBSCRIBABLE
+            .into_iter()
+            .chain([WebhookEventType::Test])
+            .find(|t| t.as_str() == s)
+    }
+
+    /// Event type of a progress message, if it is delivered to webhooks.
+    pub fn from_message(message: &WsMessage) -> Option<Self> {
+        match message {
+            WsMessage::ClipUploaded { .. } => Some(WebhookEventType::ClipUploaded),
+            WsMessage::SceneCompleted { .. } => Some(WebhookEventType::SceneCompleted),
+            WsMessage::Done { .. } => Some(WebhookEventType::JobDone),
+            WsMessage::Error { .. } => Some(WebhookEventType::JobError),
+            WsMessage::Cancelled { .. } => Some(WebhookEventType::JobCancelled),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for WebhookEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A user-registered webhook endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookEndpoint {
+    /// Unique identifier (UUID)
+    pub id: String,
+
+    /// User who owns the endpoint
+    pub user_id: String,
+
+    /// HTTPS URL events are POSTed to
+    pub url: String,
+
+    /// Secret used to sign requests
+    pub secret: String,
+
+    /// Optional label
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Event types delivered; empty means all
+    #[serde(default)]
+    pub event_types: Vec<WebhookEventType>,
+
+    /// Whether events are delivered
+    pub enabled: bool,
+
+    /// When the endpoint was created
+    pub created_at: DateTime<Utc>,
+
+    /// When the endpoint was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookEndpoint {
+    /// Create an enabled endpoint with a fresh signing secret.
+    pub fn new(id: impl Into<String>, user_id: impl Into<String>, url: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: id.into(),
+            user_id: user_id.into(),
+            url: url.into(),
+            secret: generate_webhook_secret(),
+            description: None,
+            event_types: Vec::new(),
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Set the label.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the event types delivered.
+    pub fn with_event_types(mut self, event_types: Vec<WebhookEventType>) -> Self {
+        self.event_types = event_types;
+        self
+    }
+
+    /// Whether an event of this type should be delivered to the endpoint.
+    pub fn wants(&self, event_type: WebhookEventType) -> bool {
+        event_type == WebhookEventType::Test
+            || (self.enabled
+                && (self.event_types.is_empty() || self.event_types.contains(&event_type)))
+    }
+}
+
+/// Body POSTed to webhook endpoints.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookPayload {
+    /// Event ID, identical for every endpoint receiving the event
+    pub id: String,
+
+    /// Event type
+    #[serde(rename = "type")]
+    pub event_type: WebhookEventType,
+
+    /// When the event happened
+    pub created_at: DateTime<Utc>,
+
+    /// Job that emitted the event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
+
+    /// The progress message, as sent to the web app
+    pub data: serde_json::Value,
+}
+
+impl WebhookPayload {
+    /// Payload for a progress message, if it is delivered to webhooks.
+    pub fn from_message(
+        id: impl Into<String>,
+        job_id: impl Into<String>,
+        message: &WsMessage,
+        created_at: DateTime<Utc>,
+    ) -> Option<Self> {
+        let event_type = WebhookEventType::from_message(message)?;
+        Some(Self {
+            id: id.into(),
+            event_type,
+            created_at,
+            job_id: Some(job_id.into()),
+            data: serde_json::to_value(message).ok()?,
+        })
+    }
+
+    /// Payload of a test event.
+    pub fn test(id: impl Into<String>, endpoint_id: &str) -> Self {
+        Self {
+            id: id.into(),
+            event_type: WebhookEventType::Test,
+            created_at: Utc::now(),
+            job_id: None,
+            data: serde_json::json!({ "endpointId": endpoint_id }),
+        }
+    }
+}
+
+/// Outcome of a delivery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// Not delivered yet; more attempts are scheduled
+    #[default]
+    Pending,
+    /// The endpoint answered with a 2xx status
+    Succeeded,
+    /// All attempts failed
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    /// Get string representation of the status.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Succeeded => "succeeded",
+            WebhookDeliveryStatus::Failed => "failed",
+        }
+    }
+
+    /// Parse a status name (unknown names are pending).
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "succeeded" => WebhookDeliveryStatus::Succeeded,
+            "failed" => WebhookDeliveryStatus::Failed,
+            _ => WebhookDeliveryStatus::Pending,
+        }
+    }
+}
+
+/// Delivery log entry of one event to one endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookDelivery {
+    /// Unique identifier (UUID), sent in `X-Vclip-Delivery`
+    pub id: String,
+
+    /// Endpoint the event is delivered to
+    pub endpoint_id: String,
+
+    /// Event being delivered
+    pub event_id: String,
+
+    /// Event type
+    pub event_type: WebhookEventType,
+
+    /// Current outcome
+    #[serde(default)]
+    pub status: WebhookDeliveryStatus,
+
+    /// Attempts made so far
+    #[serde(default)]
+    pub attempts: u32,
+
+    /// HTTP status of the last attempt, if the endpoint answered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_status: Option<u16>,
+
+    /// Error of the last attempt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// When the next attempt is due (pending deliveries only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+
+    /// When the delivery was created
+    pub created_at: DateTime<Utc>,
+
+    /// When the delivery was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    /// Create a pending delivery of an event to an endpoint.
+    pub fn new(endpoint_id: impl Into<String>, payload: &WebhookPayload) -> Self {
+        let now = Utc::now();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            endpoint_id: endpoint_id.into(),
+            event_id: payload.id.clone(),
+            event_type: payload.event_type,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            response_status: None,
+            error: None,
+            next_attempt_at: Some(now),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Record a successful attempt.
+    pub fn record_success(&mut self, response_status: u16) {
+        self.attempts += 1;
+        self.status = WebhookDeliveryStatus::Succeeded;
+        self.response_status = Some(response_status);
+        self.error = None;
+        self.next_attempt_at = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Record a failed attempt and schedule the next one, if any is left.
+    ///
+    /// Returns when the next attempt is due.
+    pub fn record_failure(
+        &mut self,
+        response_status: Option<u16>,
+        error: impl Into<String>,
+    ) -> Option<DateTime<Utc>> {
+        let now = Utc::now();
+        self.attempts += 1;
+        self.response_status = response_status;
+        self.error = Some(error.into());
+        self.updated_at = now;
+
+        if self.attempts >= WEBHOOK_MAX_ATTEMPTS {
+            self.status = WebhookDeliveryStatus::Failed;
+            self.next_attempt_at = None;
+        } else {
+            let delay = chrono::Duration::from_std(webhook_retry_delay(self.attempts))
+                .unwrap_or_else(|_| chrono::Duration::hours(6));
+            self.status = WebhookDeliveryStatus::Pending;
+            self.next_attempt_at = Some(now + delay);
+        }
+        self.next_attempt_at
+    }
+}
+
+/// Delay before retrying after `attempts` failed attempts.
+pub fn webhook_retry_delay(attempts: u32) -> Duration {
+    let factor = 3u32.saturating_pow(attempts.saturating_sub(1));
+    WEBHOOK_RETRY_BASE
+        .checked_mul(factor)
+        .unwrap_or(WEBHOOK_RETRY_MAX)
+        .min(WEBHOOK_RETRY_MAX)
+}
+
+/// Sign a webhook body: base64url HMAC-SHA256 of `"{timestamp}.{body}"`.
+pub fn sign_webhook(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+}
+
+/// Validate a webhook URL and return it normalized.
+///
+/// Only HTTPS URLs on public hosts are accepted, so endpoints can't be
+/// pointed at the service's own network. Host names are only checked here;
+/// the addresses they resolve to are checked when delivering.
+pub fn validate_webhook_url(input: &str) -> Result<String, &'static str> {
+    let url = Url::parse(input.trim()).map_err(|_| "Invalid URL")?;
+    if url.scheme() != "https" {
+        return Err("Webhook URLs must use https");
+    }
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err("Webhook URLs must not contain credentials");
+    }
+
+    let host = url.host_str().ok_or("Webhook URL has no host")?;
+    let host = host.trim_start_matches('[').trim_end_matches(']').to_lowercase();
+    if host == "localhost"
+        || host.ends_with(".localhost")
+        || host.ends_with(".local")
+        || host.ends_with(".internal")
+    {
+        return Err("Webhook URLs must point to a public host");
+    }
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        if !is_public_ip(&ip) {
+            return Err("Webhook URLs must point to a public host");
+        }
+    }
+
+    Ok(url.to_string())
+}
+
+/// Whether an address is publicly routable (not private, loopback,
+/// link-local, CGNAT or otherwise reserved).
+pub fn is_public_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_documentation()
+                // Carrier-grade NAT (100.64.0.0/10)
+                || (v4.octets()[0] == 100 && (v4.octets()[1] & 0xc0) == 64))
+        }
+        IpAddr::V6(v6) => {
+            let segments = v6.segments();
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                // Unique local (fc00::/7) and link-local (fe80::/10)
+                || (segments[0] & 0xfe00) == 0xfc00
+                || (segments[0] & 0xffc0) == 0xfe80
+                || v6.to_ipv4_mapped().is_some_and(|v4| !is_public_ip(&IpAddr::V4(v4))))
+        }
+    }
+}
+
+fn generate_webhook_secret() -> String {
+    format!(
+        "{}{}{}",
+        WEBHOOK_SECRET_PREFIX,
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_type_from_message() {
+        assert_eq!(
+            WebhookEventType::from_message(&WsMessage::done("v1")),
+            Some(WebhookEventType::JobDone)
+        );
+        assert_eq!(
+            WebhookEventType::from_message(&WsMessage::error("boom")),
+            Some(WebhookEventType::JobError)
+        );
+        assert_eq!(
+            WebhookEventType::from_message(&WsMessage::cancelled(Some("v1".to_string()))),
+            Some(WebhookEventType::JobCancelled)
+        );
+        assert_eq!(WebhookEventType::from_message(&WsMessage::progress(10)), None);
+        assert_eq!(WebhookEventType::parse("scene.completed"), Some(WebhookEventType::SceneCompleted));
+        assert_eq!(WebhookEventType::parse("done"), None);
+    }
+
+    #[test]
+    fn test_endpoint_filters_events() {
+        let mut endpoint = WebhookEndpoint::new("e1", "u1", "https://example.com/hook")
+            .with_event_types(vec![WebhookEventType::JobDone]);
+        assert!(endpoint.secret.starts_with(WEBHOOK_SECRET_PREFIX));
+        assert!(endpoint.wants(WebhookEventType::JobDone));
+        assert!(!endpoint.wants(WebhookEventType::ClipUploaded));
+
+        endpoint.enabled = false;
+        assert!(!endpoint.wants(WebhookEventType::JobDone));
+        assert!(endpoint.wants(WebhookEventType::Test));
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        assert_eq!(webhook_retry_delay(1), Duration::from_secs(30));
+        assert_eq!(webhook_retry_delay(2), Duration::from_secs(90));
+        assert_eq!(webhook_retry_delay(7), WEBHOOK_RETRY_MAX);
+        assert_eq!(webhook_retry_delay(40), WEBHOOK_RETRY_MAX);
+
+        let payload = WebhookPayload::test("evt", "e1");
+        let mut delivery = WebhookDelivery::new("e1", &payload);
+        for _ in 1..WEBHOOK_MAX_ATTEMPTS {
+            assert!(delivery.record_failure(Some(500), "server error").is_some());
+        }
+        assert_eq!(delivery.record_failure(None, "timeout"), None);
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Failed);
+    }
+
+    #[test]
+    fn test_sign_webhook_is_deterministic() {
+        let a = sign_webhook("whsec_x", 1_700_000_000, "{\"id\":\"1\"}");
+        assert_eq!(a, sign_webhook("whsec_x", 1_700_000_000, "{\"id\":\"1\"}"));
+        assert_ne!(a, sign_webhook("whsec_x", 1_700_000_001, "{\"id\":\"1\"}"));
+        assert_ne!(a, sign_webhook("whsec_y", 1_700_000_000, "{\"id\":\"1\"}"));
+    }
+
+    #[test]
+    fn test_validate_webhook_url() {
+        assert!(validate_webhook_url("https://hooks.example.com/vclip").is_ok());
+        assert!(validate_webhook_url("http://hooks.example.com/vclip").is_err());
+        assert!(validate_webhook_url("https://localhost/hook").is_err());
+        assert!(validate_webhook_url("https://10.0.0.5/hook").is_err());
+        assert!(validate_webhook_url("https://169.254.169.254/latest").is_err());
+        assert!(validate_webhook_url("https://[::1]/hook").is_err());
+        assert!(validate_webhook_url("https://user:pw@example.com/hook").is_err());
+        assert!(validate_webhook_url("not a url").is_err());
+    }
+}
@@ -0,0 +1,195 @@
+//! Word-level transcript alignment cache.
+//!
+//! Forced alignment turns coarse, segment-timestamped transcript text into
+//! per-word start/end times. Results are cached per scene next to
+//! `SceneNeuralAnalysis` so reprocessing does not recompute them.
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::caption::CaptionWord;
+
+/// Current version of the word alignment format.
+/// Increment when the alignment algorithm or schema changes to invalidate old caches.
+pub const WORD_ALIGNMENT_VERSION: u32 = 1;
+
+/// How word timings were obtained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlignmentMethod {
+    /// CTC acoustic model forced alignment (most precise)
+    Ctc,
+    /// Words distributed over VAD-detected speech regions
+    VadProportional,
+}
+
+/// A single aligned word.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AlignedWord {
+    /// Word text as it appears in the transcript
+    pub text: String,
+    /// Start time in seconds from segment start
+    pub start: f64,
+    /// End time in seconds from segment start
+    pub end: f64,
+    /// Alignment confidence (0.0 to 1.0), if the method provides one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+impl AlignedWord {
+    /// Create a new aligned word.
+    pub fn new(text: impl Into<String>, start: f64, end: f64) -> Self {
+        Self {
+            text: text.into(),
+            start,
+            end,
+            confidence: None,
+        }
+    }
+
+    /// Set alignment confidence.
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.confidence = Some(confidence.clamp(0.0, 1.0));
+        self
+    }
+}
+
+impl From<&AlignedWord> for CaptionWord {
+    fn from(word: &AlignedWord) -> Self {
+        CaptionWord::new(word.text.clone(), word.start, word.end)
+    }
+}
+
+/// Cached word alignment for one scene's raw segment.
+///
+/// Times are relative to the start of the padded raw segment (before any
+/// silence removal).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SceneWordAlignment {
+    /// User who owns this alignment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
+    /// Video ID this alignment belongs to
+    pub video_id: String,
+
+    /// Scene ID within the video
+    pub scene_id: u32,
+
+    /// Padded segment start in source time (seconds)
+    pub window_start: f64,
+
+    /// Padded segment end in source time (seconds)
+    pub window_end: f64,
+
+    /// Method used to produce the timings
+    pub method: AlignmentMethod,
+
+    /// Aligned words in order
+    pub words: Vec<AlignedWord>,
+
+    /// Version of the alignment format for cache invalidation
+    pub alignment_version: u32,
+
+    /// When this alignment was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl SceneWordAlignment {
+    /// Create a new alignment for a scene window.
+    pub fn new(
+        video_id: impl Into<String>,
+        scene_id: u32,
+        window_start: f64,
+        window_end: f64,
+        method: AlignmentMethod,
+        words: Vec<AlignedWord>,
+    ) -> Self {
+        Self {
+            user_id: None,
+            video_id: video_id.into(),
+            scene_id,
+            window_start,
+            window_end,
+            method,
+            words,
+            alignment_version: WORD_ALIGNMENT_VERSION,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Create with user ID.
+    pub fn with_user(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Check if this alignment is compatible with the current version.
+    pub fn is_current_version(&self) -> bool {
+        self.alignment_version == WORD_ALIGNMENT_VERSION
+    }
+
+    /// Check whether this alignment was computed for the given window.
+    ///
+    /// Scene boundaries can be edited; a cached alignment for a different
+    /// window must not be reused.
+    pub fn matches_window(&self, window_start: f64, window_end: f64) -> bool {
+        const TOLERANCE: f64 = 0.05;
+        (self.window_start - window_start).abs() < TOLERANCE
+            && (self.window_end - window_end).abs() < TOLERANCE
+    }
+
+    /// Convert to caption words.
+    pub fn to_caption_words(&self) -> Vec<CaptionWord> {
+        self.words.iter().map(CaptionWord::from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alignment_roundtrip() {
+        let alignment = SceneWordAlignment::new(
+            "video",
+            3,
+            10.0,
+            40.0,
+            AlignmentMethod::Ctc,
+            vec![AlignedWord::new("hello", 0.5, 0.9).with_confidence(0.8)],
+        )
+        .with_user("user");
+
+        let json = serde_json::to_string(&alignment).unwrap();
+        let parsed: SceneWordAlignment = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.scene_id, 3);
+        assert_eq!(parsed.method, AlignmentMethod::Ctc);
+        assert_eq!(parsed.words[0].confidence, Some(0.8));
+        assert!(parsed.is_current_version());
+    }
+
+    #[test]
+    fn test_matches_window() {
+        let alignment =
+            SceneWordAlignment::new("v", 1, 10.0, 40.0, AlignmentMethod::VadProportional, vec![]);
+        assert!(alignment.matches_window(10.01, 39.99));
+        assert!(!alignment.matches_window(12.0, 40.0));
+    }
+
+    #[test]
+    fn test_to_caption_words() {
+        let alignment = SceneWordAlignment::new(
+            "v",
+            1,
+            0.0,
+            5.0,
+            AlignmentMethod::Ctc,
+            vec![AlignedWord::new("hi", 1.0, 1.2)],
+        );
+        let words = alignment.to_caption_words();
+        assert_eq!(words, vec![CaptionWord::new("hi", 1.0, 1.2)]);
+    }
+}
@@ -24,6 +24,8 @@ pub enum WsMessageType {
     Error,
     /// Processing complete
     Done,
+    /// Job cancelled by the user
+    Cancelled,
     /// Clip uploaded notification
     ClipUploaded,
     /// Job started notification (for polling fallback)
@@ -37,6 +39,7 @@ impl WsMessageType {
             WsMessageType::Progress => "progress",
             WsMessageType::Error => "error",
             WsMessageType::Done => "done",
+            WsMessageType::Cancelled => "cancelled",
             WsMessageType::ClipUploaded => "clip_uploaded",
             WsMessageType::JobStarted => "job_started",
         }
@@ -70,6 +73,13 @@ pub enum WsMessage {
         video_id: String,
     },
 
+    /// Job cancelled by the user (final, like `Done` and `Error`)
+    Cancelled {
+        #[serde(rename = "videoId", skip_serializing_if = "Option::is_none")]
+        video_id: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+
     /// Clip uploaded notification
     ClipUploaded {
         #[serde(rename = "videoId")]
@@ -235,6 +245,14 @@ impl WsMessage {
         }
     }
 
+    /// Create a cancelled message.
+    pub fn cancelled(video_id: Option<String>) -> Self {
+        WsMessage::Cancelled {
+            video_id,
+            timestamp: Utc::now(),
+        }
+    }
+
     /// Create a clip uploaded message.
     pub fn clip_uploaded(video_id: impl Into<String>, clip_count: u32, total_clips: u32) -> Self {
         WsMessage::ClipUploaded {
@@ -335,6 +353,7 @@ impl WsMessage {
             WsMessage::Progress { .. } => WsMessageType::Progress,
             WsMessage::Error { .. } => WsMessageType::Error,
             WsMessage::Done { .. } => WsMessageType::Done,
+            WsMessage::Cancelled { .. } => WsMessageType::Cancelled,
             WsMessage::ClipUploaded { .. } => WsMessageType::ClipUploaded,
             WsMessage::ClipProgress { .. } => WsMessageType::Progress,
             WsMessage::SceneStarted { .. } => WsMessageType::Progress,
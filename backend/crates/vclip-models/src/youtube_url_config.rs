@@ -1,16 +1,21 @@
-//! YouTube URL parsing, validation, and yt-dlp configuration generation.
+//! Video URL parsing, validation, and yt-dlp configuration generation.
 //!
 //! This module provides comprehensive YouTube URL analysis and generates
 //! safe yt-dlp configuration plans for transcript and video download.
+//! [`analyze_video_url`] extends the same analysis to Twitch and Kick VODs
+//! and clips (see [`crate::stream_url`]).
 //!
 //! # Security
 //! - URLs are treated as untrusted input
-//! - Only YouTube domains are accepted
-//! - Video IDs are strictly validated (11 chars, alphanumeric + `-_`)
+//! - Only YouTube, Twitch and Kick domains are accepted
+//! - Video IDs are strictly validated (11 chars, alphanumeric + `-_` on YouTube)
 //! - No shell command execution or external API calls
 
 use serde::{Deserialize, Serialize};
 
+use crate::stream_url::{
+    is_stream_platform_url, parse_stream_url, StreamContentKind, StreamPlatform, StreamUrlError,
+};
 use crate::utils::{extract_host, extract_youtube_id, is_youtube_domain, YoutubeIdError};
 
 // ============================================================================
@@ -68,9 +73,13 @@ pub struct YoutubeUrlConfig {
     /// Normalized canonical watch URL
     pub normalized_url: Option<String>,
 
-    /// Extracted 11-character video ID
+    /// Extracted video ID (11-character YouTube ID, Twitch/Kick VOD ID or clip slug)
     pub video_id: Option<String>,
 
+    /// Platform hosting the video
+    #[serde(default)]
+    pub platform: VideoPlatform,
+
     /// Classification of the URL type
     pub url_type: UrlType,
 
@@ -97,7 +106,26 @@ pub struct YoutubeUrlConfig {
     pub validation: ValidationResult,
 }
 
-/// Classification of YouTube URL types.
+/// Platform hosting an analyzed video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VideoPlatform {
+    #[default]
+    Youtube,
+    Twitch,
+    Kick,
+}
+
+impl From<StreamPlatform> for VideoPlatform {
+    fn from(platform: StreamPlatform) -> Self {
+        match platform {
+            StreamPlatform::Twitch => VideoPlatform::Twitch,
+            StreamPlatform::Kick => VideoPlatform::Kick,
+        }
+    }
+}
+
+/// Classification of video URL types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum UrlType {
@@ -111,8 +139,11 @@ pub enum UrlType {
     PlaylistItem,
     /// Active livestream (reserved for future metadata-aware classification)
     Live,
-    /// Finished livestream VOD (reserved for future metadata-aware classification)
+    /// Finished livestream VOD (Twitch/Kick VODs; reserved on YouTube for
+    /// future metadata-aware classification)
     LiveVod,
+    /// Twitch or Kick clip of a stream
+    Clip,
     /// URL that cannot be processed
     Unsupported,
 }
@@ -126,13 +157,14 @@ impl std::fmt::Display for UrlType {
             UrlType::PlaylistItem => write!(f, "playlist_item"),
             UrlType::Live => write!(f, "live"),
             UrlType::LiveVod => write!(f, "live_vod"),
+            UrlType::Clip => write!(f, "clip"),
             UrlType::Unsupported => write!(f, "unsupported"),
         }
     }
 }
 
 /// Live capture mode for streaming content.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum LiveCaptureMode {
     /// Capture from the beginning of the stream
@@ -202,6 +234,10 @@ pub struct ValidationResult {
     /// Whether this is a valid, supported YouTube URL
     pub is_supported_youtube_url: bool,
 
+    /// Whether this is a valid, supported URL on any platform
+    #[serde(default)]
+    pub is_supported_url: bool,
+
     /// List of validation errors or warnings
     pub errors: Vec<String>,
 }
@@ -249,6 +285,7 @@ pub fn analyze_youtube_url(input: &YoutubeUrlInput) -> YoutubeUrlConfig {
         return YoutubeUrlConfig {
             normalized_url: None,
             video_id: None,
+            platform: VideoPlatform::Youtube,
             url_type: UrlType::Unsupported,
             is_shorts: false,
             is_live: false,
@@ -261,6 +298,7 @@ pub fn analyze_youtube_url(input: &YoutubeUrlInput) -> YoutubeUrlConfig {
             },
             validation: ValidationResult {
                 is_supported_youtube_url: false,
+                is_supported_url: false,
                 errors,
             },
         };
@@ -292,6 +330,7 @@ pub fn analyze_youtube_url(input: &YoutubeUrlInput) -> YoutubeUrlConfig {
             return YoutubeUrlConfig {
                 normalized_url: None,
                 video_id: None,
+                platform: VideoPlatform::Youtube,
                 url_type: UrlType::Unsupported,
                 is_shorts: false,
                 is_live: false,
@@ -304,6 +343,7 @@ pub fn analyze_youtube_url(input: &YoutubeUrlInput) -> YoutubeUrlConfig {
                 },
                 validation: ValidationResult {
                     is_supported_youtube_url: false,
+                    is_supported_url: false,
                     errors,
                 },
             };
@@ -327,6 +367,7 @@ pub fn analyze_youtube_url(input: &YoutubeUrlInput) -> YoutubeUrlConfig {
     YoutubeUrlConfig {
         normalized_url: Some(normalized_url),
         video_id: Some(video_id),
+        platform: VideoPlatform::Youtube,
         url_type: url_classification.url_type,
         is_shorts: url_classification.is_shorts,
         is_live: url_classification.is_live,
@@ -339,11 +380,116 @@ pub fn analyze_youtube_url(input: &YoutubeUrlInput) -> YoutubeUrlConfig {
         },
         validation: ValidationResult {
             is_supported_youtube_url: true,
+            is_supported_url: true,
             errors,
         },
     }
 }
 
+/// Analyzes a YouTube, Twitch or Kick URL and generates yt-dlp configuration.
+///
+/// Twitch and Kick VODs and clips are analyzed by [`analyze_stream_url`];
+/// everything else goes through [`analyze_youtube_url`].
+///
+/// # Example
+/// ```
+/// use vclip_models::youtube_url_config::{analyze_video_url, UrlType, YoutubeUrlInput};
+///
+/// let input = YoutubeUrlInput {
+///     raw_url: "https://www.twitch.tv/videos/2101234567".to_string(),
+///     preferred_sub_langs: vec!["en".to_string()],
+///     allow_auto_subs: true,
+///     live_capture_mode: Default::default(),
+///     max_expected_duration_sec: 21600,
+/// };
+///
+/// let config = analyze_video_url(&input);
+/// assert!(config.validation.is_supported_url);
+/// assert_eq!(config.url_type, UrlType::LiveVod);
+/// ```
+pub fn analyze_video_url(input: &YoutubeUrlInput) -> YoutubeUrlConfig {
+    if is_stream_platform_url(input.raw_url.trim()) {
+        analyze_stream_url(input)
+    } else {
+        analyze_youtube_url(input)
+    }
+}
+
+/// Analyzes a Twitch or Kick VOD/clip URL and generates yt-dlp configuration.
+///
+/// Stream VODs and clips have no captions, so the subtitle plan is disabled;
+/// transcripts come from offline speech-to-text instead. Live channel pages
+/// are rejected.
+pub fn analyze_stream_url(input: &YoutubeUrlInput) -> YoutubeUrlConfig {
+    let trimmed_url = input.raw_url.trim();
+    let platform = extract_host(trimmed_url)
+        .and_then(|host| StreamPlatform::from_host(&host))
+        .map(VideoPlatform::from)
+        .unwrap_or_default();
+
+    let stream_ref = match parse_stream_url(trimmed_url) {
+        Ok(stream_ref) => stream_ref,
+        Err(e) => {
+            let error_msg = match e {
+                StreamUrlError::ChannelPage => {
+                    "Channel URL without a specific VOD or clip".to_string()
+                }
+                StreamUrlError::InvalidId => "VOD or clip ID has invalid format".to_string(),
+                StreamUrlError::NotStreamUrl | StreamUrlError::UnsupportedPath => {
+                    "URL is not a Twitch or Kick VOD or clip".to_string()
+                }
+            };
+
+            return YoutubeUrlConfig {
+                normalized_url: None,
+                video_id: None,
+                platform,
+                url_type: UrlType::Unsupported,
+                is_shorts: false,
+                is_live: false,
+                has_subtitles: None,
+                subtitle_plan: build_disabled_subtitle_plan(),
+                video_download_plan: build_disabled_video_plan(),
+                live_handling: LiveHandling {
+                    live_capture_mode: input.live_capture_mode,
+                    download_sections: None,
+                },
+                validation: ValidationResult {
+                    is_supported_youtube_url: false,
+                    is_supported_url: false,
+                    errors: vec![error_msg],
+                },
+            };
+        }
+    };
+
+    let url_type = match stream_ref.kind {
+        StreamContentKind::Vod => UrlType::LiveVod,
+        StreamContentKind::Clip => UrlType::Clip,
+    };
+
+    YoutubeUrlConfig {
+        normalized_url: Some(stream_ref.canonical_url()),
+        video_id: Some(stream_ref.id),
+        platform: stream_ref.platform.into(),
+        url_type,
+        is_shorts: false,
+        is_live: false,
+        has_subtitles: Some(false),
+        subtitle_plan: build_disabled_subtitle_plan(),
+        video_download_plan: build_stream_video_download_plan(),
+        live_handling: LiveHandling {
+            live_capture_mode: input.live_capture_mode,
+            download_sections: None,
+        },
+        validation: ValidationResult {
+            is_supported_youtube_url: false,
+            is_supported_url: true,
+            errors: Vec::new(),
+        },
+    }
+}
+
 // ============================================================================
 // URL Classification
 // ============================================================================
@@ -596,6 +742,26 @@ fn build_video_download_plan(
     }
 }
 
+/// Build video download plan for Twitch and Kick VODs and clips.
+///
+/// Both platforms serve muxed HLS renditions, which also makes
+/// `--download-sections` segment downloads cheap.
+fn build_stream_video_download_plan() -> VideoDownloadPlan {
+    VideoDownloadPlan {
+        enabled: true,
+        preferred_container: "mp4".to_string(),
+        preferred_codecs: vec!["h264".to_string(), "aac".to_string()],
+        is_chunked_live_capture: false,
+        yt_dlp_flags: vec![
+            "-f".to_string(),
+            "best[ext=mp4]/best".to_string(),
+            "--remux-video".to_string(),
+            "mp4".to_string(),
+            "--no-playlist".to_string(),
+        ],
+    }
+}
+
 /// Build disabled video download plan for unsupported URLs.
 fn build_disabled_video_plan() -> VideoDownloadPlan {
     VideoDownloadPlan {
@@ -656,6 +822,15 @@ pub fn analyze_youtube_url_json(input_json: &str) -> Result<String, serde_json::
     serde_json::to_string(&config)
 }
 
+/// Analyze a YouTube, Twitch or Kick URL from raw JSON input and return JSON output.
+///
+/// Same input and output schema as [`analyze_youtube_url_json`].
+pub fn analyze_video_url_json(input_json: &str) -> Result<String, serde_json::Error> {
+    let input: YoutubeUrlInput = serde_json::from_str(input_json)?;
+    let config = analyze_video_url(&input);
+    serde_json::to_string(&config)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -1218,4 +1393,70 @@ mod tests {
         let json = config.to_json().unwrap();
         assert!(json.contains(r#""live_capture_mode":"from_now""#));
     }
+
+    // ========================================================================
+    // Twitch and Kick
+    // ========================================================================
+
+    #[test]
+    fn test_twitch_vod_and_clip() {
+        let config = analyze_video_url(&make_input("https://www.twitch.tv/videos/2101234567?t=1h"));
+        assert!(config.validation.is_supported_url);
+        assert!(!config.validation.is_supported_youtube_url);
+        assert_eq!(config.platform, VideoPlatform::Twitch);
+        assert_eq!(config.url_type, UrlType::LiveVod);
+        assert_eq!(config.video_id.as_deref(), Some("2101234567"));
+        assert_eq!(
+            config.normalized_url.as_deref(),
+            Some("https://www.twitch.tv/videos/2101234567")
+        );
+        assert!(!config.subtitle_plan.enabled);
+        assert!(config.video_download_plan.enabled);
+        assert!(!config.video_download_plan.is_chunked_live_capture);
+
+        let config = analyze_video_url(&make_input("https://clips.twitch.tv/FunnyClipSlug"));
+        assert_eq!(config.url_type, UrlType::Clip);
+        assert!(config.to_json().unwrap().contains(r#""platform":"twitch""#));
+    }
+
+    #[test]
+    fn test_kick_vod_and_clip() {
+        let config = analyze_video_url(&make_input(
+            "https://kick.com/streamer/videos/5b1c9f2e-8a3d-4e6f-9b0a-1c2d3e4f5a6b",
+        ));
+        assert!(config.validation.is_supported_url);
+        assert_eq!(config.platform, VideoPlatform::Kick);
+        assert_eq!(config.url_type, UrlType::LiveVod);
+
+        let config =
+            analyze_video_url(&make_input("https://kick.com/streamer?clip=clip_01HXYZABC"));
+        assert_eq!(config.url_type, UrlType::Clip);
+        assert_eq!(
+            config.normalized_url.as_deref(),
+            Some("https://kick.com/streamer/clips/clip_01HXYZABC")
+        );
+    }
+
+    #[test]
+    fn test_stream_channel_page_rejected() {
+        let config = analyze_video_url(&make_input("https://www.twitch.tv/somestreamer"));
+        assert!(!config.validation.is_supported_url);
+        assert_eq!(config.platform, VideoPlatform::Twitch);
+        assert_eq!(config.url_type, UrlType::Unsupported);
+        assert!(config.validation.errors[0].contains("without a specific VOD or clip"));
+        assert!(!config.video_download_plan.enabled);
+    }
+
+    #[test]
+    fn test_analyze_video_url_keeps_youtube_behavior() {
+        let config = analyze_video_url(&make_input("https://youtu.be/dQw4w9WgXcQ"));
+        assert!(config.validation.is_supported_youtube_url);
+        assert!(config.validation.is_supported_url);
+        assert_eq!(config.platform, VideoPlatform::Youtube);
+        assert!(config.subtitle_plan.enabled);
+
+        // YouTube-only analysis still rejects stream URLs
+        let config = analyze_youtube_url(&make_input("https://www.twitch.tv/videos/2101234567"));
+        assert!(!config.validation.is_supported_url);
+    }
 }
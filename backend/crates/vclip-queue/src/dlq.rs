@@ -0,0 +1,163 @@
+//! Dead letter queue entries and filters.
+//!
+//! Jobs that exhaust their retries (or fail permanently) are moved to the
+//! DLQ stream with the error that stopped them. Admins list, replay and
+//! purge them through `JobQueue`.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::job::QueueJob;
+
+/// A job in the dead letter queue.
+#[derive(Debug, Clone, Serialize)]
+pub struct DlqEntry {
+    /// DLQ stream message ID
+    pub message_id: String,
+    /// Message ID the job had on the worker stream
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original_id: Option<String>,
+    /// Error that moved the job to the DLQ
+    pub error: String,
+    /// When the job was dead-lettered
+    pub failed_at: DateTime<Utc>,
+    /// The job, unless its payload no longer parses
+    pub job: Option<QueueJob>,
+    /// Raw job payload
+    pub payload: String,
+}
+
+impl DlqEntry {
+    /// Build an entry from a DLQ stream message.
+    pub fn from_stream(message_id: &str, fields: &HashMap<String, redis::Value>) -> Self {
+        let field = |key: &str| match fields.get(key) {
+            Some(redis::Value::BulkString(bytes)) => Some(String::from_utf8_lossy(bytes).into_owned()),
+            Some(redis::Value::SimpleString(s)) => Some(s.clone()),
+            _ => None,
+        };
+
+        let payload = field("job").unwrap_or_default();
+        Self {
+            message_id: message_id.to_string(),
+            original_id: field("original_id"),
+            error: field("error").unwrap_or_default(),
+            failed_at: stream_id_time(message_id).unwrap_or_else(Utc::now),
+            job: serde_json::from_str(&payload).ok(),
+            payload,
+        }
+    }
+}
+
+/// Selects DLQ entries for batch replay. Unset criteria match everything.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DlqFilter {
+    /// Job type, e.g. `render_scene_style`
+    #[serde(default)]
+    pub job_type: Option<String>,
+    /// Owner of the job
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Case-insensitive substring of the error
+    #[serde(default)]
+    pub error_contains: Option<String>,
+}
+
+impl DlqFilter {
+    /// Whether the entry matches every set criterion.
+    ///
+    /// Entries whose payload does not parse only match filters without a
+    /// job type or user.
+    pub fn matches(&self, entry: &DlqEntry) -> bool {
+        if let Some(ref job_type) = self.job_type {
+            if entry.job.as_ref().map(QueueJob::kind) != Some(job_type.as_str()) {
+                return false;
+            }
+        }
+        if let Some(ref user_id) = self.user_id {
+            if entry.job.as_ref().map(QueueJob::user_id) != Some(user_id.as_str()) {
+                return false;
+            }
+        }
+        if let Some(ref needle) = self.error_contains {
+            if !entry.error.to_lowercase().contains(&needle.to_lowercase()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Time encoded in a stream message ID (`<ms>-<seq>`).
+pub fn stream_id_time(message_id: &str) -> Option<DateTime<Utc>> {
+    let ms: i64 = message_id.split('-').next()?.parse().ok()?;
+    Utc.timestamp_millis_opt(ms).single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::AnalyzeVideoJob;
+
+    fn entry(error: &str) -> DlqEntry {
+        let job = QueueJob::AnalyzeVideo(AnalyzeVideoJob::new("user_1", "draft_1", "url"));
+        let mut fields = HashMap::new();
+        fields.insert(
+            "job".to_string(),
+            redis::Value::BulkString(serde_json::to_vec(&job).unwrap()),
+        );
+        fields.insert("error".to_string(), redis::Value::BulkString(error.as_bytes().to_vec()));
+        fields.insert("original_id".to_string(), redis::Value::BulkString(b"1700000000000-0".to_vec()));
+        DlqEntry::from_stream("1700000001000-0", &fields)
+    }
+
+    #[test]
+    fn test_entry_from_stream() {
+        let entry = entry("Download failed: HTTP 403");
+        assert_eq!(entry.original_id.as_deref(), Some("1700000000000-0"));
+        assert_eq!(entry.failed_at.timestamp_millis(), 1_700_000_001_000);
+        assert_eq!(entry.job.as_ref().map(QueueJob::kind), Some("analyze_video"));
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let entry = entry("Download failed: HTTP 403");
+        assert!(DlqFilter::default().matches(&entry));
+
+        let filter = DlqFilter {
+            job_type: Some("analyze_video".to_string()),
+            user_id: Some("user_1".to_string()),
+            error_contains: Some("http 403".to_string()),
+        };
+        assert!(filter.matches(&entry));
+
+        let other_type = DlqFilter {
+            job_type: Some("render_scene_style".to_string()),
+            ..Default::default()
+        };
+        assert!(!other_type.matches(&entry));
+
+        let other_user = DlqFilter {
+            user_id: Some("user_2".to_string()),
+            ..Default::default()
+        };
+        assert!(!other_user.matches(&entry));
+    }
+
+    #[test]
+    fn test_unparseable_payload_only_matches_error_filters() {
+        let mut entry = entry("bad payload");
+        entry.job = None;
+        let by_error = DlqFilter {
+            error_contains: Some("payload".to_string()),
+            ..Default::default()
+        };
+        assert!(by_error.matches(&entry));
+        let by_user = DlqFilter {
+            user_id: Some("user_1".to_string()),
+            ..Default::default()
+        };
+        assert!(!by_user.matches(&entry));
+    }
+}
@@ -2,7 +2,12 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use vclip_models::{AspectRatio, CropMode, DetectionTier, JobId, StreamerSplitParams, Style, VideoId};
+use crate::lane::QueueLane;
+use vclip_models::{
+    AspectRatio, AudioConfig, CaptionOptions, CropMode, DetectionTier, ExportProfile,
+    HighlightDetection, JobId, LiveCaptureMode, ResolutionPreset, StreamerSplitParams, Style,
+    VideoId,
+};
 
 fn default_neural_detection_tier() -> DetectionTier {
     // Backward compatibility: previously we always computed the highest tier.
@@ -24,8 +29,17 @@ pub struct AnalyzeVideoJob {
     pub draft_id: String,
     /// Video URL to analyze
     pub video_url: String,
+    /// R2 key of an uploaded source file; analyzed instead of downloading the URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_r2_key: Option<String>,
     /// Optional AI instructions from user
     pub prompt_instructions: Option<String>,
+    /// How highlights are detected
+    #[serde(default)]
+    pub highlight_detection: HighlightDetection,
+    /// Priority lane (defaults by job kind when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lane: Option<QueueLane>,
     /// When the job was created
     pub created_at: DateTime<Utc>,
 }
@@ -42,7 +56,10 @@ impl AnalyzeVideoJob {
             user_id: user_id.into(),
             draft_id: draft_id.into(),
             video_url: video_url.into(),
+            source_r2_key: None,
             prompt_instructions: None,
+            highlight_detection: HighlightDetection::default(),
+            lane: None,
             created_at: Utc::now(),
         }
     }
@@ -53,12 +70,111 @@ impl AnalyzeVideoJob {
         self
     }
 
+    /// Set the highlight detection mode.
+    pub fn with_highlight_detection(mut self, mode: HighlightDetection) -> Self {
+        self.highlight_detection = mode;
+        self
+    }
+
+    /// Analyze an uploaded file stored in R2.
+    pub fn with_source_r2_key(mut self, r2_key: impl Into<String>) -> Self {
+        self.source_r2_key = Some(r2_key.into());
+        self
+    }
+
+    /// Set the priority lane.
+    pub fn with_lane(mut self, lane: QueueLane) -> Self {
+        self.lane = Some(lane);
+        self
+    }
+
     /// Generate idempotency key for deduplication.
     pub fn idempotency_key(&self) -> String {
         format!("analyze:{}:{}", self.user_id, self.draft_id)
     }
 }
 
+/// Job to record a live stream and publish draft scenes while it runs.
+///
+/// The worker records the stream into rolling R2 segments, analyzes a
+/// sliding window of the newest ones and adds scenes to the draft as they
+/// are found. The job ends when the stream does, when the user stops it or
+/// at the worker's capture length limit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureLiveJob {
+    /// Unique job ID
+    pub job_id: JobId,
+    /// User ID
+    pub user_id: String,
+    /// Pre-generated draft ID
+    pub draft_id: String,
+    /// Live stream or channel URL
+    pub video_url: String,
+    /// Record from the start of the broadcast or from now
+    #[serde(default)]
+    pub capture_mode: LiveCaptureMode,
+    /// Optional AI instructions from user
+    pub prompt_instructions: Option<String>,
+    /// How highlights are detected
+    #[serde(default)]
+    pub highlight_detection: HighlightDetection,
+    /// Priority lane (defaults by job kind when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lane: Option<QueueLane>,
+    /// When the job was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl CaptureLiveJob {
+    /// Create a new live capture job.
+    pub fn new(
+        user_id: impl Into<String>,
+        draft_id: impl Into<String>,
+        video_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            job_id: JobId::new(),
+            user_id: user_id.into(),
+            draft_id: draft_id.into(),
+            video_url: video_url.into(),
+            capture_mode: LiveCaptureMode::default(),
+            prompt_instructions: None,
+            highlight_detection: HighlightDetection::default(),
+            lane: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Set the capture mode.
+    pub fn with_capture_mode(mut self, mode: LiveCaptureMode) -> Self {
+        self.capture_mode = mode;
+        self
+    }
+
+    /// Set prompt instructions.
+    pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt_instructions = Some(prompt.into());
+        self
+    }
+
+    /// Set the highlight detection mode.
+    pub fn with_highlight_detection(mut self, mode: HighlightDetection) -> Self {
+        self.highlight_detection = mode;
+        self
+    }
+
+    /// Set the priority lane.
+    pub fn with_lane(mut self, lane: QueueLane) -> Self {
+        self.lane = Some(lane);
+        self
+    }
+
+    /// Generate idempotency key for deduplication.
+    pub fn idempotency_key(&self) -> String {
+        format!("capture_live:{}:{}", self.user_id, self.draft_id)
+    }
+}
+
 /// Job to process a new video.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessVideoJob {
@@ -76,8 +192,26 @@ pub struct ProcessVideoJob {
     pub crop_mode: CropMode,
     /// Target aspect ratio
     pub target_aspect: AspectRatio,
+    /// Output resolution preset
+    #[serde(default)]
+    pub resolution: ResolutionPreset,
+    /// Platform export profiles rendered for every clip
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub export_profiles: Vec<ExportProfile>,
+    /// Optional audio stage (loudness normalization and cleanup)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioConfig>,
+    /// Optional burned-in captions applied to every rendered style
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captions: Option<CaptionOptions>,
+    /// How highlights are detected
+    #[serde(default)]
+    pub highlight_detection: HighlightDetection,
     /// Custom prompt for AI analysis
     pub custom_prompt: Option<String>,
+    /// Priority lane (defaults by job kind when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lane: Option<QueueLane>,
 }
 
 impl ProcessVideoJob {
@@ -94,7 +228,13 @@ impl ProcessVideoJob {
             styles,
             crop_mode: CropMode::default(),
             target_aspect: AspectRatio::default(),
+            resolution: ResolutionPreset::default(),
+            export_profiles: Vec::new(),
+            audio: None,
+            captions: None,
+            highlight_detection: HighlightDetection::default(),
             custom_prompt: None,
+            lane: None,
         }
     }
 
@@ -110,12 +250,48 @@ impl ProcessVideoJob {
         self
     }
 
+    /// Set output resolution preset.
+    pub fn with_resolution(mut self, resolution: ResolutionPreset) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Set platform export profiles.
+    pub fn with_export_profiles(mut self, profiles: Vec<ExportProfile>) -> Self {
+        self.export_profiles = profiles;
+        self
+    }
+
+    /// Set audio stage options.
+    pub fn with_audio(mut self, audio: Option<AudioConfig>) -> Self {
+        self.audio = audio;
+        self
+    }
+
+    /// Set burned-in caption options.
+    pub fn with_captions(mut self, captions: Option<CaptionOptions>) -> Self {
+        self.captions = captions;
+        self
+    }
+
+    /// Set the highlight detection mode.
+    pub fn with_highlight_detection(mut self, mode: HighlightDetection) -> Self {
+        self.highlight_detection = mode;
+        self
+    }
+
     /// Set custom prompt.
     pub fn with_custom_prompt(mut self, prompt: Option<String>) -> Self {
         self.custom_prompt = prompt;
         self
     }
 
+    /// Set the priority lane.
+    pub fn with_lane(mut self, lane: QueueLane) -> Self {
+        self.lane = Some(lane);
+        self
+    }
+
     /// Generate idempotency key for deduplication.
     pub fn idempotency_key(&self) -> String {
         format!("process:{}:{}", self.user_id, self.video_id)
@@ -139,6 +315,15 @@ pub struct ReprocessScenesJob {
     pub crop_mode: CropMode,
     /// Target aspect ratio
     pub target_aspect: AspectRatio,
+    /// Output resolution preset
+    #[serde(default)]
+    pub resolution: ResolutionPreset,
+    /// Platform export profiles rendered for every clip
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub export_profiles: Vec<ExportProfile>,
+    /// Optional audio stage (loudness normalization and cleanup)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioConfig>,
     /// Enable object detection for Cinematic tier (default: false)
     #[serde(default)]
     pub enable_object_detection: bool,
@@ -154,6 +339,15 @@ pub struct ReprocessScenesJob {
     /// Cut silent parts from clips using VAD (default: true for more dynamic content)
     #[serde(default = "default_cut_silent_parts")]
     pub cut_silent_parts: bool,
+    /// Optional burned-in captions applied to every rendered style
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captions: Option<CaptionOptions>,
+    /// Credits reserved by the API when the job was enqueued
+    #[serde(default)]
+    pub credits_reserved: u32,
+    /// Priority lane (defaults by job kind when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lane: Option<QueueLane>,
 }
 
 fn default_cut_silent_parts() -> bool {
@@ -175,11 +369,17 @@ impl ReprocessScenesJob {
             styles,
             crop_mode: CropMode::default(),
             target_aspect: AspectRatio::default(),
+            resolution: ResolutionPreset::default(),
+            export_profiles: Vec::new(),
+            audio: None,
             enable_object_detection: false,
             overwrite: false,
             streamer_split_params: None,
             top_scenes_compilation: false,
             cut_silent_parts: false,
+            captions: None,
+            credits_reserved: 0,
+            lane: None,
         }
     }
 
@@ -218,6 +418,24 @@ impl ReprocessScenesJob {
         self
     }
 
+    /// Set output resolution preset.
+    pub fn with_resolution(mut self, resolution: ResolutionPreset) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Set platform export profiles.
+    pub fn with_export_profiles(mut self, profiles: Vec<ExportProfile>) -> Self {
+        self.export_profiles = profiles;
+        self
+    }
+
+    /// Set audio stage options.
+    pub fn with_audio(mut self, audio: Option<AudioConfig>) -> Self {
+        self.audio = audio;
+        self
+    }
+
     /// Set overwrite mode (re-render existing clips).
     pub fn with_overwrite(mut self, overwrite: bool) -> Self {
         self.overwrite = overwrite;
@@ -230,6 +448,24 @@ impl ReprocessScenesJob {
         self
     }
 
+    /// Set burned-in caption options.
+    pub fn with_captions(mut self, captions: Option<CaptionOptions>) -> Self {
+        self.captions = captions;
+        self
+    }
+
+    /// Record the credits reserved for this job (refunded on cancellation).
+    pub fn with_credits_reserved(mut self, credits: u32) -> Self {
+        self.credits_reserved = credits;
+        self
+    }
+
+    /// Set the priority lane.
+    pub fn with_lane(mut self, lane: QueueLane) -> Self {
+        self.lane = Some(lane);
+        self
+    }
+
     /// Generate idempotency key for deduplication.
     pub fn idempotency_key(&self) -> String {
         // Sort scene_ids and styles for consistent ordering
@@ -238,7 +474,7 @@ impl ReprocessScenesJob {
         let mut styles: Vec<String> = self.styles.iter().map(|s| s.to_string()).collect();
         styles.sort();
         
-        format!(
+        let mut key = format!(
             "reprocess:{}:{}:{:?}:{:?}:{}:{}",
             self.user_id,
             self.video_id,
@@ -246,7 +482,21 @@ impl ReprocessScenesJob {
             styles,
             self.crop_mode,
             self.target_aspect
-        )
+        );
+        if self.resolution != ResolutionPreset::default() {
+            key.push_str(&format!(":res={}", self.resolution));
+        }
+        if !self.export_profiles.is_empty() {
+            let profiles: Vec<&str> = self.export_profiles.iter().map(|p| p.as_str()).collect();
+            key.push_str(&format!(":export={}", profiles.join("+")));
+        }
+        if let Some(audio) = &self.audio {
+            key.push_str(&format!(":audio={}", audio.cache_key()));
+        }
+        if let Some(captions) = &self.captions {
+            key.push_str(&format!(":captions={}", captions.cache_key()));
+        }
+        key
     }
 }
 
@@ -273,6 +523,15 @@ pub struct RenderSceneStyleJob {
     pub crop_mode: CropMode,
     /// Target aspect ratio
     pub target_aspect: AspectRatio,
+    /// Output resolution preset
+    #[serde(default)]
+    pub resolution: ResolutionPreset,
+    /// Platform export profiles rendered for every clip
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub export_profiles: Vec<ExportProfile>,
+    /// Optional audio stage (loudness normalization and cleanup)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioConfig>,
     /// Highlight start timestamp (format: "MM:SS" or "HH:MM:SS")
     pub start: String,
     /// Highlight end timestamp
@@ -286,6 +545,12 @@ pub struct RenderSceneStyleJob {
     /// Enable object detection for Cinematic tier (default: false)
     #[serde(default)]
     pub enable_object_detection: bool,
+    /// Optional burned-in captions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captions: Option<CaptionOptions>,
+    /// Priority lane (defaults by job kind when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lane: Option<QueueLane>,
 }
 
 impl RenderSceneStyleJob {
@@ -308,12 +573,17 @@ impl RenderSceneStyleJob {
             style,
             crop_mode: CropMode::default(),
             target_aspect: AspectRatio::default(),
+            resolution: ResolutionPreset::default(),
+            export_profiles: Vec::new(),
+            audio: None,
             start: start.into(),
             end: end.into(),
             pad_before_seconds: None,
             pad_after_seconds: None,
             parent_job_id: None,
             enable_object_detection: false,
+            captions: None,
+            lane: None,
         }
     }
 
@@ -329,6 +599,24 @@ impl RenderSceneStyleJob {
         self
     }
 
+    /// Set output resolution preset.
+    pub fn with_resolution(mut self, resolution: ResolutionPreset) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Set platform export profiles.
+    pub fn with_export_profiles(mut self, profiles: Vec<ExportProfile>) -> Self {
+        self.export_profiles = profiles;
+        self
+    }
+
+    /// Set audio stage options.
+    pub fn with_audio(mut self, audio: Option<AudioConfig>) -> Self {
+        self.audio = audio;
+        self
+    }
+
     /// Set padding before.
     pub fn with_pad_before(mut self, seconds: Option<f64>) -> Self {
         self.pad_before_seconds = seconds;
@@ -347,12 +635,24 @@ impl RenderSceneStyleJob {
         self
     }
 
+    /// Set burned-in caption options.
+    pub fn with_captions(mut self, captions: Option<CaptionOptions>) -> Self {
+        self.captions = captions;
+        self
+    }
+
+    /// Set the priority lane.
+    pub fn with_lane(mut self, lane: QueueLane) -> Self {
+        self.lane = Some(lane);
+        self
+    }
+
     /// Generate idempotency key for deduplication.
     ///
     /// The key uniquely identifies a specific (video, scene, style, settings)
     /// combination to prevent duplicate processing.
     pub fn idempotency_key(&self) -> String {
-        format!(
+        let mut key = format!(
             "render:{}:{}:{}:{}:{}:{}",
             self.user_id,
             self.video_id,
@@ -360,7 +660,21 @@ impl RenderSceneStyleJob {
             self.style,
             self.crop_mode,
             self.target_aspect
-        )
+        );
+        if self.resolution != ResolutionPreset::default() {
+            key.push_str(&format!(":res={}", self.resolution));
+        }
+        if !self.export_profiles.is_empty() {
+            let profiles: Vec<&str> = self.export_profiles.iter().map(|p| p.as_str()).collect();
+            key.push_str(&format!(":export={}", profiles.join("+")));
+        }
+        if let Some(audio) = &self.audio {
+            key.push_str(&format!(":audio={}", audio.cache_key()));
+        }
+        if let Some(captions) = &self.captions {
+            key.push_str(&format!(":captions={}", captions.cache_key()));
+        }
+        key
     }
 }
 
@@ -406,6 +720,9 @@ pub struct NeuralAnalysisJob {
     /// Defaults to SpeakerAware for backward compatibility with older serialized jobs.
     #[serde(default = "default_neural_detection_tier")]
     pub detection_tier: DetectionTier,
+    /// Parent job ID (the job whose cancellation also stops this one)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_job_id: Option<JobId>,
     /// When the job was created
     pub created_at: DateTime<Utc>,
 }
@@ -424,6 +741,7 @@ impl NeuralAnalysisJob {
             scene_id,
             source_hint_r2_key: None,
             detection_tier: default_neural_detection_tier(),
+            parent_job_id: None,
             created_at: Utc::now(),
         }
     }
@@ -440,6 +758,12 @@ impl NeuralAnalysisJob {
         self
     }
 
+    /// Set parent job ID.
+    pub fn with_parent_job(mut self, parent_id: JobId) -> Self {
+        self.parent_job_id = Some(parent_id);
+        self
+    }
+
     /// Generate idempotency key for deduplication.
     pub fn idempotency_key(&self) -> String {
         format!(
@@ -465,6 +789,8 @@ pub enum QueueJob {
     ReprocessScenes(ReprocessScenesJob),
     /// Fine-grained job: render a single (scene, style) clip
     RenderSceneStyle(RenderSceneStyleJob),
+    /// Long-running job: record a live stream and publish draft scenes
+    CaptureLive(CaptureLiveJob),
 }
 
 impl QueueJob {
@@ -476,6 +802,7 @@ impl QueueJob {
             QueueJob::NeuralAnalysis(j) => &j.job_id,
             QueueJob::ReprocessScenes(j) => &j.job_id,
             QueueJob::RenderSceneStyle(j) => &j.job_id,
+            QueueJob::CaptureLive(j) => &j.job_id,
         }
     }
 
@@ -487,14 +814,15 @@ impl QueueJob {
             QueueJob::NeuralAnalysis(j) => &j.user_id,
             QueueJob::ReprocessScenes(j) => &j.user_id,
             QueueJob::RenderSceneStyle(j) => &j.user_id,
+            QueueJob::CaptureLive(j) => &j.user_id,
         }
     }
 
     /// Returns the video_id if applicable.
-    /// AnalyzeVideo doesn't have a video_id yet (draft_id instead).
+    /// AnalyzeVideo and CaptureLive don't have a video_id yet (draft_id instead).
     pub fn video_id(&self) -> Option<&VideoId> {
         match self {
-            QueueJob::AnalyzeVideo(_) => None,
+            QueueJob::AnalyzeVideo(_) | QueueJob::CaptureLive(_) => None,
             QueueJob::ProcessVideo(j) => Some(&j.video_id),
             QueueJob::DownloadSource(j) => Some(&j.video_id),
             QueueJob::NeuralAnalysis(j) => Some(&j.video_id),
@@ -503,10 +831,11 @@ impl QueueJob {
         }
     }
 
-    /// Returns the draft_id if this is an AnalyzeVideo job.
+    /// Returns the draft_id if this is an AnalyzeVideo or CaptureLive job.
     pub fn draft_id(&self) -> Option<&str> {
         match self {
             QueueJob::AnalyzeVideo(j) => Some(&j.draft_id),
+            QueueJob::CaptureLive(j) => Some(&j.draft_id),
             _ => None,
         }
     }
@@ -519,6 +848,36 @@ impl QueueJob {
             QueueJob::NeuralAnalysis(j) => j.idempotency_key(),
             QueueJob::ReprocessScenes(j) => j.idempotency_key(),
             QueueJob::RenderSceneStyle(j) => j.idempotency_key(),
+            QueueJob::CaptureLive(j) => j.idempotency_key(),
+        }
+    }
+
+    /// Job type name, as used in the serialized `type` tag.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            QueueJob::AnalyzeVideo(_) => "analyze_video",
+            QueueJob::ProcessVideo(_) => "process_video",
+            QueueJob::DownloadSource(_) => "download_source",
+            QueueJob::NeuralAnalysis(_) => "neural_analysis",
+            QueueJob::ReprocessScenes(_) => "reprocess_scenes",
+            QueueJob::RenderSceneStyle(_) => "render_scene_style",
+            QueueJob::CaptureLive(_) => "capture_live",
+        }
+    }
+
+    /// Priority lane the job waits in.
+    ///
+    /// Analyses and live captures default to the interactive lane,
+    /// orchestration and render jobs to the free lane, and background jobs
+    /// always use the backfill lane.
+    pub fn lane(&self) -> QueueLane {
+        match self {
+            QueueJob::AnalyzeVideo(j) => j.lane.unwrap_or(QueueLane::Interactive),
+            QueueJob::CaptureLive(j) => j.lane.unwrap_or(QueueLane::Interactive),
+            QueueJob::ProcessVideo(j) => j.lane.unwrap_or(QueueLane::Free),
+            QueueJob::ReprocessScenes(j) => j.lane.unwrap_or(QueueLane::Free),
+            QueueJob::RenderSceneStyle(j) => j.lane.unwrap_or(QueueLane::Free),
+            QueueJob::DownloadSource(_) | QueueJob::NeuralAnalysis(_) => QueueLane::Backfill,
         }
     }
 
@@ -532,10 +891,38 @@ impl QueueJob {
         matches!(self, QueueJob::ProcessVideo(_) | QueueJob::ReprocessScenes(_))
     }
 
+    /// Parent job of a fanned-out render or neural analysis job.
+    ///
+    /// Cancelling the parent also stops the job.
+    pub fn parent_job_id(&self) -> Option<&JobId> {
+        match self {
+            QueueJob::RenderSceneStyle(j) => j.parent_job_id.as_ref(),
+            QueueJob::NeuralAnalysis(j) => j.parent_job_id.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Job whose cancellation flag stops this job.
+    ///
+    /// Orchestration jobs are cancelled directly; fanned-out jobs follow
+    /// their parent. Other jobs can't be cancelled.
+    pub fn cancellation_job_id(&self) -> Option<&JobId> {
+        if self.is_orchestration() {
+            Some(self.job_id())
+        } else {
+            self.parent_job_id()
+        }
+    }
+
     /// Returns true if this is a fine-grained render job.
     pub fn is_render(&self) -> bool {
         matches!(self, QueueJob::RenderSceneStyle(_))
     }
+
+    /// Returns true if this is a live capture job.
+    pub fn is_live_capture(&self) -> bool {
+        matches!(self, QueueJob::CaptureLive(_))
+    }
 }
 
 #[cfg(test)]
@@ -567,4 +954,122 @@ mod tests {
             other => panic!("unexpected variant: {other:?}"),
         }
     }
+
+    #[test]
+    fn render_job_idempotency_key_includes_export_profiles() {
+        let job = RenderSceneStyleJob::new(
+            "user_1",
+            VideoId::from_string("video_1"),
+            1,
+            "Scene",
+            Style::Split,
+            "00:00:00",
+            "00:00:30",
+        );
+        let base_key = job.idempotency_key();
+        assert!(!base_key.contains(":export="));
+
+        let job = job.with_export_profiles(vec![ExportProfile::Tiktok, ExportProfile::Shorts]);
+        assert_eq!(
+            job.idempotency_key(),
+            format!("{}:export=tiktok+shorts", base_key)
+        );
+    }
+
+    #[test]
+    fn queue_job_lane_defaults_by_kind() {
+        let analyze = QueueJob::AnalyzeVideo(AnalyzeVideoJob::new("user_1", "draft_1", "url"));
+        assert_eq!(analyze.lane(), QueueLane::Interactive);
+
+        let render = RenderSceneStyleJob::new(
+            "user_1",
+            VideoId::from_string("video_1"),
+            1,
+            "Scene",
+            Style::Split,
+            "00:00:00",
+            "00:00:30",
+        );
+        assert_eq!(QueueJob::RenderSceneStyle(render.clone()).lane(), QueueLane::Free);
+
+        // The lane survives serialization so rescheduled renders keep it
+        let json = serde_json::to_string(&QueueJob::RenderSceneStyle(render.with_lane(QueueLane::Paid)))
+            .expect("serialize QueueJob");
+        let decoded: QueueJob = serde_json::from_str(&json).expect("deserialize QueueJob");
+        assert_eq!(decoded.lane(), QueueLane::Paid);
+    }
+
+    #[test]
+    fn queue_job_capture_live_serde_roundtrip() {
+        let job = CaptureLiveJob::new("user_1", "draft_1", "https://www.twitch.tv/streamer")
+            .with_capture_mode(LiveCaptureMode::FromNow)
+            .with_highlight_detection(HighlightDetection::Fused);
+
+        let json = serde_json::to_string(&QueueJob::CaptureLive(job.clone())).expect("serialize QueueJob");
+        assert!(json.contains("\"type\":\"capture_live\""));
+        let decoded: QueueJob = serde_json::from_str(&json).expect("deserialize QueueJob");
+
+        assert_eq!(decoded.kind(), "capture_live");
+        assert_eq!(decoded.draft_id(), Some("draft_1"));
+        assert_eq!(decoded.idempotency_key(), "capture_live:user_1:draft_1");
+        assert_eq!(decoded.lane(), QueueLane::Interactive);
+        assert!(decoded.video_id().is_none());
+        match decoded {
+            QueueJob::CaptureLive(j) => {
+                assert_eq!(j.capture_mode, LiveCaptureMode::FromNow);
+                assert_eq!(j.highlight_detection, HighlightDetection::Fused);
+            }
+            other => panic!("unexpected variant: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn render_job_idempotency_key_includes_audio() {
+        let job = RenderSceneStyleJob::new(
+            "user_1",
+            VideoId::from_string("video_1"),
+            1,
+            "Scene",
+            Style::Split,
+            "00:00:00",
+            "00:00:30",
+        );
+        let base_key = job.idempotency_key();
+
+        let job = job.with_audio(Some(AudioConfig::new(-16.0)));
+        assert_eq!(
+            job.idempotency_key(),
+            format!("{}:audio=i-16_tp-1.5_lra11_lim", base_key)
+        );
+    }
+
+    #[test]
+    fn queue_job_cancellation_follows_parent() {
+        let parent = JobId::new();
+        let render = RenderSceneStyleJob::new(
+            "user_1",
+            VideoId::from_string("video_1"),
+            1,
+            "Scene",
+            Style::Split,
+            "00:00:00",
+            "00:00:30",
+        );
+        let render = QueueJob::RenderSceneStyle(render.with_parent_job(parent.clone()));
+        assert_eq!(render.cancellation_job_id(), Some(&parent));
+
+        // The parent survives serialization so queued neural jobs still follow it
+        let neural = NeuralAnalysisJob::new("user_1", VideoId::from_string("video_1"), 1)
+            .with_parent_job(parent.clone());
+        let json = serde_json::to_string(&QueueJob::NeuralAnalysis(neural)).expect("serialize QueueJob");
+        let decoded: QueueJob = serde_json::from_str(&json).expect("deserialize QueueJob");
+        assert_eq!(decoded.cancellation_job_id(), Some(&parent));
+
+        let orphan = QueueJob::NeuralAnalysis(NeuralAnalysisJob::new(
+            "user_1",
+            VideoId::from_string("video_1"),
+            1,
+        ));
+        assert!(orphan.cancellation_job_id().is_none());
+    }
 }
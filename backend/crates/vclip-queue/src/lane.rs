@@ -0,0 +1,158 @@
+//! Priority lanes and per-user fair scheduling.
+//!
+//! Jobs do not go straight onto the worker stream. Each job waits in the
+//! sorted set of its [`QueueLane`] and is admitted to the stream when a
+//! worker has a free slot:
+//!
+//! - Lanes are read by weight with smooth weighted round-robin, so busy
+//!   high-priority lanes get most slots without starving the others.
+//! - Within a lane, jobs are ordered by their owner's virtual clock. Every
+//!   job a user queues advances their clock by [`FAIR_SHARE_QUANTUM_MS`],
+//!   starting from the current time, so a user who queues 200 renders at
+//!   once is interleaved round-robin with everyone who queues after them
+//!   instead of running first.
+
+use serde::{Deserialize, Serialize};
+use vclip_models::PlanTier;
+
+/// Virtual time a queued job costs its owner, in milliseconds.
+pub const FAIR_SHARE_QUANTUM_MS: u64 = 1000;
+
+/// Priority lane a job waits in until a worker admits it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueLane {
+    /// User-facing analyses the user is waiting on
+    Interactive,
+    /// Renders for paying users
+    Paid,
+    /// Renders for free users
+    #[default]
+    Free,
+    /// Background work (source downloads, neural analysis, channel uploads)
+    Backfill,
+}
+
+impl QueueLane {
+    /// All lanes, highest priority first.
+    pub const ALL: [QueueLane; 4] = [
+        QueueLane::Interactive,
+        QueueLane::Paid,
+        QueueLane::Free,
+        QueueLane::Backfill,
+    ];
+
+    /// Get string representation of the lane.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueueLane::Interactive => "interactive",
+            QueueLane::Paid => "paid",
+            QueueLane::Free => "free",
+            QueueLane::Backfill => "backfill",
+        }
+    }
+
+    /// Share of worker slots the lane gets while every lane has work.
+    pub fn weight(&self) -> u32 {
+        match self {
+            QueueLane::Interactive => 8,
+            QueueLane::Paid => 4,
+            QueueLane::Free => 2,
+            QueueLane::Backfill => 1,
+        }
+    }
+
+    /// Render lane for a user's plan.
+    pub fn for_plan(tier: PlanTier) -> Self {
+        match tier {
+            PlanTier::Free => QueueLane::Free,
+            _ => QueueLane::Paid,
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            QueueLane::Interactive => 0,
+            QueueLane::Paid => 1,
+            QueueLane::Free => 2,
+            QueueLane::Backfill => 3,
+        }
+    }
+}
+
+impl std::fmt::Display for QueueLane {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Smooth weighted round-robin over the lanes that have waiting jobs.
+///
+/// With weights 8/4/2/1 and every lane busy, 15 consecutive picks give the
+/// lanes 8, 4, 2 and 1 slots, spread out rather than in bursts.
+#[derive(Debug, Default)]
+pub struct LaneScheduler {
+    current: [i64; 4],
+}
+
+impl LaneScheduler {
+    /// Create a scheduler.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pick the lane to admit from next, or `None` if no lane is ready.
+    pub fn next(&mut self, ready: &[QueueLane]) -> Option<QueueLane> {
+        let total: i64 = ready.iter().map(|l| i64::from(l.weight())).sum();
+        let mut best: Option<QueueLane> = None;
+        for lane in QueueLane::ALL.iter().filter(|l| ready.contains(l)) {
+            self.current[lane.index()] += i64::from(lane.weight());
+            if best.is_none_or(|b| self.current[lane.index()] > self.current[b.index()]) {
+                best = Some(*lane);
+            }
+        }
+        let best = best?;
+        self.current[best.index()] -= total;
+        Some(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduler_follows_weights() {
+        let mut scheduler = LaneScheduler::new();
+        let mut picks = [0u32; 4];
+        for _ in 0..15 {
+            let lane = scheduler.next(&QueueLane::ALL).unwrap();
+            picks[lane.index()] += 1;
+        }
+        assert_eq!(picks, [8, 4, 2, 1]);
+    }
+
+    #[test]
+    fn test_scheduler_interleaves_lanes() {
+        let mut scheduler = LaneScheduler::new();
+        let ready = [QueueLane::Paid, QueueLane::Free];
+        let picks: Vec<QueueLane> = (0..3).map(|_| scheduler.next(&ready).unwrap()).collect();
+        assert_eq!(picks, vec![QueueLane::Paid, QueueLane::Free, QueueLane::Paid]);
+    }
+
+    #[test]
+    fn test_scheduler_skips_empty_lanes() {
+        let mut scheduler = LaneScheduler::new();
+        assert_eq!(scheduler.next(&[]), None);
+        for _ in 0..5 {
+            assert_eq!(scheduler.next(&[QueueLane::Backfill]), Some(QueueLane::Backfill));
+        }
+    }
+
+    #[test]
+    fn test_lane_for_plan() {
+        assert_eq!(QueueLane::for_plan(PlanTier::Free), QueueLane::Free);
+        assert_eq!(QueueLane::for_plan(PlanTier::Pro), QueueLane::Paid);
+        assert_eq!(QueueLane::for_plan(PlanTier::Studio), QueueLane::Paid);
+    }
+}
@@ -4,17 +4,30 @@
 //! - Job enqueueing via Redis Streams
 //! - Worker consumption with retry/DLQ
 //! - Progress events via Redis Pub/Sub
+//! - Webhook event stream and delivery schedule
+//! - Buffered share link analytics
+//! - Share passcode attempt lockout
 
+pub mod dlq;
 pub mod error;
 pub mod job;
+pub mod lane;
 pub mod progress;
 pub mod queue;
+pub mod share_stats;
+pub mod share_unlock;
+pub mod webhook;
 
+pub use dlq::{DlqEntry, DlqFilter};
 pub use error::{QueueError, QueueResult};
-pub use job::{AnalyzeVideoJob, DownloadSourceJob, NeuralAnalysisJob, ProcessVideoJob, QueueJob, RenderSceneStyleJob, ReprocessScenesJob};
+pub use job::{AnalyzeVideoJob, CaptureLiveJob, DownloadSourceJob, NeuralAnalysisJob, ProcessVideoJob, QueueJob, RenderSceneStyleJob, ReprocessScenesJob};
+pub use lane::{LaneScheduler, QueueLane, FAIR_SHARE_QUANTUM_MS};
 pub use progress::{
     ProgressChannel, ProgressEvent,
-    HEARTBEAT_TTL_SECS, PROGRESS_HISTORY_TTL_SECS, JOB_STATUS_TTL_SECS,
+    HEARTBEAT_TTL_SECS, PROGRESS_HISTORY_TTL_SECS, JOB_STATUS_TTL_SECS, JOB_OWNER_TTL_SECS,
     STALE_GRACE_PERIOD_SECS, STALE_THRESHOLD_SECS,
 };
 pub use queue::{JobQueue, QueueConfig};
+pub use share_stats::{share_stats_day, PendingShareStats, ShareStatsBuffer, ShareView};
+pub use share_unlock::ShareUnlockGuard;
+pub use webhook::{PendingWebhook, WebhookQueue, WEBHOOK_EVENTS_STREAM};
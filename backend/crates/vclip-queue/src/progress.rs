@@ -5,15 +5,20 @@
 //! - Persistent progress history via Redis Sorted Sets
 //! - Worker heartbeat tracking for stale job detection
 //! - Job status caching for fast polling
+//! - Cancellation flags polled by workers
+//! - Webhook event stream feeding outbound webhooks
 
 use chrono::Utc;
 use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
-use vclip_models::{ClipProcessingStep, JobId, JobStatus, JobStatusCache, WsMessage};
+use vclip_models::{
+    ClipProcessingStep, JobId, JobStatus, JobStatusCache, WebhookEventType, WsMessage,
+};
 
 use crate::error::QueueResult;
+use crate::webhook::{WEBHOOK_EVENTS_MAXLEN, WEBHOOK_EVENTS_STREAM};
 
 // ============================================================================
 // Redis Key Prefixes and TTLs
@@ -25,9 +30,18 @@ const HEARTBEAT_KEY_PREFIX: &str = "heartbeat:";
 /// Prefix for progress history sorted sets: `progress:history:{job_id}`
 const PROGRESS_HISTORY_PREFIX: &str = "progress:history:";
 
+/// Prefix for progress sequence counters: `progress:seq:{job_id}`
+const PROGRESS_SEQ_PREFIX: &str = "progress:seq:";
+
 /// Prefix for job status cache: `job:status:{job_id}`
 const JOB_STATUS_PREFIX: &str = "job:status:";
 
+/// Prefix for job cancellation flags: `job:cancel:{job_id}`
+const JOB_CANCEL_PREFIX: &str = "job:cancel:";
+
+/// Prefix for job owner keys: `job:owner:{job_id}`
+pub(crate) const JOB_OWNER_PREFIX: &str = "job:owner:";
+
 /// Prefix for active jobs set: `jobs:active`
 const ACTIVE_JOBS_KEY: &str = "jobs:active";
 
@@ -40,6 +54,9 @@ pub const PROGRESS_HISTORY_TTL_SECS: u64 = 3600; // 1 hour
 /// Job status cache TTL (seconds)
 pub const JOB_STATUS_TTL_SECS: u64 = 86400; // 24 hours
 
+/// Job owner TTL - owners are remembered this long after enqueue (seconds)
+pub const JOB_OWNER_TTL_SECS: u64 = 172_800; // 48 hours
+
 /// Grace period before marking a job without heartbeat as stale (seconds)
 pub const STALE_GRACE_PERIOD_SECS: i64 = 120;
 
@@ -125,8 +142,29 @@ impl ProgressChannel {
     /// This performs a dual-write:
     /// 1. Pub/Sub for real-time delivery to connected clients
     /// 2. Sorted set for history/recovery (scored by timestamp)
+    ///
+    /// Events without a sequence number get the job's next one, so clients
+    /// can resume from the last event they saw. Events delivered to webhooks
+    /// are also appended to the webhook stream.
     pub async fn publish_with_history(&self, event: &ProgressEvent) -> QueueResult<()> {
         let mut conn = self.client.get_multiplexed_async_connection().await?;
+
+        let sequenced;
+        let event = if event.seq == 0 {
+            let seq_key = format!("{}{}", PROGRESS_SEQ_PREFIX, event.job_id);
+            let (seq,): (u64,) = redis::pipe()
+                .atomic()
+                .incr(&seq_key, 1)
+                .expire(&seq_key, JOB_STATUS_TTL_SECS as i64)
+                .ignore()
+                .query_async(&mut conn)
+                .await?;
+            sequenced = event.clone().with_seq(seq);
+            &sequenced
+        } else {
+            event
+        };
+
         let channel = Self::channel_name(&event.job_id);
         let history_key = format!("{}{}", PROGRESS_HISTORY_PREFIX, event.job_id);
         let payload = serde_json::to_string(event)?;
@@ -135,15 +173,27 @@ impl ProgressChannel {
         debug!("Publishing progress event to {} with history", channel);
 
         // Dual-write: Pub/Sub + Sorted Set
-        redis::pipe()
-            .publish(&channel, &payload)
+        let mut pipe = redis::pipe();
+        pipe.publish(&channel, &payload)
             .ignore()
             .zadd(&history_key, &payload, score)
             .ignore()
             .expire(&history_key, PROGRESS_HISTORY_TTL_SECS as i64)
-            .ignore()
-            .exec_async(&mut conn)
-            .await?;
+            .ignore();
+
+        if WebhookEventType::from_message(&event.message).is_some() {
+            pipe.cmd("XADD")
+                .arg(WEBHOOK_EVENTS_STREAM)
+                .arg("MAXLEN")
+                .arg("~")
+                .arg(WEBHOOK_EVENTS_MAXLEN)
+                .arg("*")
+                .arg("event")
+                .arg(&payload)
+                .ignore();
+        }
+
+        pipe.exec_async(&mut conn).await?;
 
         Ok(())
     }
@@ -182,6 +232,15 @@ impl ProgressChannel {
             .await
     }
 
+    /// Publish the final message of a cancelled job.
+    pub async fn cancelled(&self, job_id: &JobId, video_id: Option<&str>) -> QueueResult<()> {
+        self.publish_with_history(&ProgressEvent::new(
+            job_id.clone(),
+            WsMessage::cancelled(video_id.map(str::to_string)),
+        ))
+        .await
+    }
+
     /// Publish error message.
     pub async fn error(&self, job_id: &JobId, message: impl Into<String>) -> QueueResult<()> {
         self.publish_with_history(&ProgressEvent::new(job_id.clone(), WsMessage::error(message)))
@@ -349,6 +408,24 @@ impl ProgressChannel {
         Ok(parsed)
     }
 
+    /// Get progress history after a given sequence number, in sequence order.
+    ///
+    /// Used to resume a stream from the last event a client received.
+    pub async fn get_history_after_seq(
+        &self,
+        job_id: &JobId,
+        after_seq: u64,
+    ) -> QueueResult<Vec<ProgressEvent>> {
+        let mut events: Vec<ProgressEvent> = self
+            .get_full_history(job_id)
+            .await?
+            .into_iter()
+            .filter(|e| e.seq > after_seq)
+            .collect();
+        events.sort_by_key(|e| e.seq);
+        Ok(events)
+    }
+
     /// Get all progress history for a job.
     pub async fn get_full_history(&self, job_id: &JobId) -> QueueResult<Vec<ProgressEvent>> {
         self.get_history_since(job_id, 0).await
@@ -471,6 +548,101 @@ impl ProgressChannel {
         Ok(())
     }
 
+    /// Get the user who enqueued a job.
+    ///
+    /// Recorded for every job at enqueue time, including jobs without a
+    /// status cache entry.
+    pub async fn get_job_owner(&self, job_id: &JobId) -> QueueResult<Option<String>> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let key = format!("{}{}", JOB_OWNER_PREFIX, job_id);
+
+        let owner: Option<String> = conn.get(&key).await?;
+        Ok(owner)
+    }
+
+    /// Register a queued job so its owner can poll and cancel it.
+    pub async fn register_job(
+        &self,
+        job_id: &JobId,
+        video_id: &str,
+        user_id: &str,
+    ) -> QueueResult<()> {
+        let status = JobStatusCache::new(job_id.to_string(), video_id, user_id);
+        self.update_job_status(job_id, &status).await
+    }
+
+    /// Mark a registered job as picked up by a worker.
+    ///
+    /// The job joins the active set, so the stale detector watches its heartbeat.
+    pub async fn start_job_status(&self, job_id: &JobId) -> QueueResult<()> {
+        if let Some(mut status) = self.get_job_status(job_id).await? {
+            status.set_status(JobStatus::Processing);
+            status.started_at = Utc::now();
+            status.event_seq += 1;
+            self.update_job_status(job_id, &status).await?;
+            self.add_to_active_jobs(job_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Return a job to the queued state while it waits to be retried.
+    pub async fn requeue_job_status(&self, job_id: &JobId) -> QueueResult<()> {
+        if let Some(mut status) = self.get_job_status(job_id).await? {
+            status.set_status(JobStatus::Queued);
+            status.event_seq += 1;
+            self.update_job_status(job_id, &status).await?;
+            self.remove_from_active_jobs(job_id).await?;
+            self.clear_heartbeat(job_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Mark job as cancelled in status cache.
+    pub async fn cancel_job_status(&self, job_id: &JobId) -> QueueResult<()> {
+        if let Some(mut status) = self.get_job_status(job_id).await? {
+            status.cancel();
+            self.update_job_status(job_id, &status).await?;
+            self.remove_from_active_jobs(job_id).await?;
+            self.clear_heartbeat(job_id).await?;
+        }
+        Ok(())
+    }
+
+    // ========================================================================
+    // Cancellation
+    // ========================================================================
+
+    /// Ask workers to cancel a job.
+    ///
+    /// Queued jobs are dropped when a worker picks them up; running jobs
+    /// are stopped at the worker's next poll of this flag.
+    pub async fn request_cancel(&self, job_id: &JobId) -> QueueResult<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let key = format!("{}{}", JOB_CANCEL_PREFIX, job_id);
+
+        conn.set_ex::<_, _, ()>(&key, Utc::now().timestamp(), JOB_STATUS_TTL_SECS)
+            .await?;
+        Ok(())
+    }
+
+    /// Check whether cancellation was requested for a job.
+    pub async fn is_cancel_requested(&self, job_id: &JobId) -> QueueResult<bool> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let key = format!("{}{}", JOB_CANCEL_PREFIX, job_id);
+
+        let exists: bool = conn.exists(&key).await?;
+        Ok(exists)
+    }
+
+    /// Clear the cancellation flag once the job has been cancelled.
+    pub async fn clear_cancel(&self, job_id: &JobId) -> QueueResult<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let key = format!("{}{}", JOB_CANCEL_PREFIX, job_id);
+
+        conn.del::<_, ()>(&key).await?;
+        Ok(())
+    }
+
     // ========================================================================
     // Active Jobs Tracking
     // ========================================================================
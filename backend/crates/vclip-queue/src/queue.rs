@@ -1,12 +1,45 @@
 //! Job queue using Redis Streams.
+//!
+//! Enqueued jobs wait in priority lanes (see [`crate::lane`]) and are admitted
+//! to the worker stream as workers free up, so the stream only ever holds
+//! jobs that are about to run.
 
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use redis::AsyncCommands;
 use tracing::{debug, info, warn};
 
+use crate::dlq::{DlqEntry, DlqFilter};
 use crate::error::{QueueError, QueueResult};
-use crate::job::{AnalyzeVideoJob, DownloadSourceJob, NeuralAnalysisJob, ProcessVideoJob, QueueJob, RenderSceneStyleJob, ReprocessScenesJob};
+use crate::job::{AnalyzeVideoJob, CaptureLiveJob, DownloadSourceJob, NeuralAnalysisJob, ProcessVideoJob, QueueJob, RenderSceneStyleJob, ReprocessScenesJob};
+use crate::lane::{LaneScheduler, QueueLane, FAIR_SHARE_QUANTUM_MS};
+use crate::progress::{JOB_OWNER_PREFIX, JOB_OWNER_TTL_SECS};
+
+/// TTL of a user's per-lane virtual clock (renewed on every enqueue).
+const FAIR_CLOCK_TTL_SECS: u64 = 86400;
+
+/// Add a job to a lane at its owner's next virtual time.
+///
+/// KEYS: lane sorted set, user's clock. ARGV: now (ms), quantum (ms), payload, clock TTL.
+const FAIR_ENQUEUE_SCRIPT: &str = r"
+local last = tonumber(redis.call('GET', KEYS[2]) or '0')
+local score = math.max(tonumber(ARGV[1]), last) + tonumber(ARGV[2])
+redis.call('SET', KEYS[2], score, 'EX', ARGV[4])
+redis.call('ZADD', KEYS[1], score, ARGV[3])
+return score
+";
+
+/// Move a lane's next job onto the worker stream; returns the message ID or nil.
+///
+/// KEYS: lane sorted set, worker stream.
+const ADMIT_SCRIPT: &str = r"
+local popped = redis.call('ZPOPMIN', KEYS[1])
+if #popped == 0 then
+    return false
+end
+return redis.call('XADD', KEYS[2], '*', 'job', popped[1])
+";
 
 /// Queue configuration.
 #[derive(Debug, Clone)]
@@ -69,13 +102,18 @@ impl QueueConfig {
 pub struct JobQueue {
     client: redis::Client,
     config: QueueConfig,
+    scheduler: Arc<Mutex<LaneScheduler>>,
 }
 
 impl JobQueue {
     /// Create a new job queue.
     pub fn new(config: QueueConfig) -> QueueResult<Self> {
         let client = redis::Client::open(config.redis_url.as_str())?;
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            scheduler: Arc::new(Mutex::new(LaneScheduler::new())),
+        })
     }
 
     /// Create from environment variables.
@@ -246,6 +284,11 @@ impl JobQueue {
         self.enqueue(QueueJob::AnalyzeVideo(job)).await
     }
 
+    /// Enqueue a live capture job.
+    pub async fn enqueue_capture_live(&self, job: CaptureLiveJob) -> QueueResult<String> {
+        self.enqueue(QueueJob::CaptureLive(job)).await
+    }
+
     /// Enqueue a download source job.
     pub async fn enqueue_download_source(&self, job: DownloadSourceJob) -> QueueResult<String> {
         self.enqueue(QueueJob::DownloadSource(job)).await
@@ -304,13 +347,13 @@ impl JobQueue {
 
     /// Enqueue multiple render jobs efficiently.
     ///
-    /// Returns the message IDs of all successfully enqueued jobs.
+    /// Returns the job IDs of all successfully enqueued jobs.
     /// If any job fails to enqueue (e.g., duplicate), it is skipped.
     pub async fn enqueue_render_batch(&self, jobs: Vec<RenderSceneStyleJob>) -> QueueResult<Vec<String>> {
-        let mut message_ids = Vec::with_capacity(jobs.len());
+        let mut job_ids = Vec::with_capacity(jobs.len());
         for job in jobs {
             match self.enqueue_render(job).await {
-                Ok(id) => message_ids.push(id),
+                Ok(id) => job_ids.push(id),
                 Err(QueueError::EnqueueFailed { .. }) => {
                     // Skip duplicates but continue with others
                     continue;
@@ -318,10 +361,12 @@ impl JobQueue {
                 Err(e) => return Err(e),
             }
         }
-        Ok(message_ids)
+        Ok(job_ids)
     }
 
-    /// Enqueue a job.
+    /// Enqueue a job into its priority lane.
+    ///
+    /// Returns the job ID; the stream message ID is assigned on admission.
     async fn enqueue(&self, job: QueueJob) -> QueueResult<String> {
         let mut conn = self.client.get_multiplexed_async_connection().await?;
 
@@ -336,27 +381,114 @@ impl JobQueue {
             return Err(QueueError::enqueue_failed("Duplicate job"));
         }
 
-        // Add to stream
-        let message_id: String = redis::cmd("XADD")
-            .arg(&self.config.stream_name)
-            .arg("*")
-            .arg("job")
+        // Add to the lane at the user's fair-share position
+        let lane = job.lane();
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        redis::Script::new(FAIR_ENQUEUE_SCRIPT)
+            .key(self.lane_key(lane))
+            .key(self.fair_clock_key(lane, job.user_id()))
+            .arg(now_ms)
+            .arg(FAIR_SHARE_QUANTUM_MS)
             .arg(&payload)
-            .arg("key")
-            .arg(&idempotency_key)
-            .query_async(&mut conn)
+            .arg(FAIR_CLOCK_TTL_SECS)
+            .invoke_async::<f64>(&mut conn)
             .await?;
 
         // Set dedup key with TTL (1 hour)
         conn.set_ex::<_, _, ()>(&dedup_key, "1", 3600).await?;
 
-        info!(
-            "Enqueued job {} with message ID {}",
-            job.job_id(),
-            message_id
-        );
+        // Remember the owner so job events can be routed to them
+        let owner_key = format!("{}{}", JOB_OWNER_PREFIX, job.job_id());
+        conn.set_ex::<_, _, ()>(&owner_key, job.user_id(), JOB_OWNER_TTL_SECS)
+            .await?;
+
+        info!("Enqueued job {} in {} lane", job.job_id(), lane);
+
+        Ok(job.job_id().to_string())
+    }
 
-        Ok(message_id)
+    /// Sorted set holding a lane's waiting jobs.
+    fn lane_key(&self, lane: QueueLane) -> String {
+        format!("{}:lane:{}", self.config.stream_name, lane)
+    }
+
+    /// A user's virtual clock in a lane.
+    fn fair_clock_key(&self, lane: QueueLane, user_id: &str) -> String {
+        format!("{}:lane:{}:clock:{}", self.config.stream_name, lane, user_id)
+    }
+
+    /// Number of jobs waiting in each lane, highest priority first.
+    pub async fn lane_depths(&self) -> QueueResult<Vec<(QueueLane, u64)>> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let mut depths = Vec::with_capacity(QueueLane::ALL.len());
+        for lane in QueueLane::ALL {
+            let depth: u64 = conn.zcard(self.lane_key(lane)).await?;
+            depths.push((lane, depth));
+        }
+        Ok(depths)
+    }
+
+    /// Admit up to `slots` waiting jobs to the worker stream.
+    ///
+    /// Jobs already on the stream but not yet delivered count against the
+    /// slots, so the stream backlog stays bounded by the free worker slots.
+    async fn admit(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        slots: usize,
+    ) -> QueueResult<usize> {
+        let stream_len: u64 = conn.xlen(&self.config.stream_name).await?;
+        let pending: u64 = redis::cmd("XPENDING")
+            .arg(&self.config.stream_name)
+            .arg(&self.config.consumer_group)
+            .query_async(conn)
+            .await
+            .map(|reply: redis::streams::StreamPendingReply| reply.count() as u64)
+            .unwrap_or(0);
+        let backlog = stream_len.saturating_sub(pending) as usize;
+        let wanted = slots.saturating_sub(backlog);
+        if wanted == 0 {
+            return Ok(0);
+        }
+
+        let mut ready = Vec::with_capacity(QueueLane::ALL.len());
+        for lane in QueueLane::ALL {
+            let depth: u64 = conn.zcard(self.lane_key(lane)).await?;
+            if depth > 0 {
+                ready.push(lane);
+            }
+        }
+
+        let script = redis::Script::new(ADMIT_SCRIPT);
+        let mut admitted = 0;
+        while admitted < wanted {
+            let Some(lane) = self.next_lane(&ready) else {
+                break;
+            };
+            let message_id: Option<String> = script
+                .key(self.lane_key(lane))
+                .key(&self.config.stream_name)
+                .invoke_async(conn)
+                .await?;
+            match message_id {
+                Some(message_id) => {
+                    debug!("Admitted message {} from {} lane", message_id, lane);
+                    admitted += 1;
+                }
+                // Drained by another worker in the meantime
+                None => ready.retain(|l| *l != lane),
+            }
+        }
+
+        Ok(admitted)
+    }
+
+    /// Pick the next lane to admit from.
+    fn next_lane(&self, ready: &[QueueLane]) -> Option<QueueLane> {
+        self.scheduler
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .next(ready)
     }
 
     /// Acknowledge a job (mark as completed).
@@ -418,11 +550,12 @@ impl JobQueue {
         Ok(())
     }
 
-    /// Get queue length.
+    /// Get queue length (jobs waiting in lanes plus jobs on the stream).
     pub async fn len(&self) -> QueueResult<u64> {
         let mut conn = self.client.get_multiplexed_async_connection().await?;
         let len: u64 = conn.xlen(&self.config.stream_name).await?;
-        Ok(len)
+        let waiting: u64 = self.lane_depths().await?.iter().map(|(_, d)| d).sum();
+        Ok(len + waiting)
     }
 
     /// Get DLQ length.
@@ -432,8 +565,147 @@ impl JobQueue {
         Ok(len)
     }
 
+    // ========================================================================
+    // Dead Letter Queue
+    // ========================================================================
+
+    /// List DLQ entries, newest first.
+    ///
+    /// `before` is the last message ID of the previous page.
+    pub async fn list_dlq(&self, before: Option<&str>, count: usize) -> QueueResult<Vec<DlqEntry>> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let end = before.map_or_else(|| "+".to_string(), |id| format!("({}", id));
+
+        let reply: redis::streams::StreamRangeReply = redis::cmd("XREVRANGE")
+            .arg(&self.config.dlq_stream_name)
+            .arg(end)
+            .arg("-")
+            .arg("COUNT")
+            .arg(count)
+            .query_async(&mut conn)
+            .await?;
+
+        Ok(reply
+            .ids
+            .iter()
+            .map(|entry| DlqEntry::from_stream(&entry.id, &entry.map))
+            .collect())
+    }
+
+    /// Get a DLQ entry by message ID.
+    pub async fn get_dlq(&self, message_id: &str) -> QueueResult<Option<DlqEntry>> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let reply: redis::streams::StreamRangeReply = redis::cmd("XRANGE")
+            .arg(&self.config.dlq_stream_name)
+            .arg(message_id)
+            .arg(message_id)
+            .query_async(&mut conn)
+            .await?;
+
+        Ok(reply
+            .ids
+            .first()
+            .map(|entry| DlqEntry::from_stream(&entry.id, &entry.map)))
+    }
+
+    /// Find DLQ entries matching a filter, oldest first.
+    pub async fn find_dlq(&self, filter: &DlqFilter, limit: usize) -> QueueResult<Vec<DlqEntry>> {
+        const PAGE_SIZE: usize = 100;
+
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let mut matches = Vec::new();
+        let mut start = "-".to_string();
+
+        while matches.len() < limit {
+            let reply: redis::streams::StreamRangeReply = redis::cmd("XRANGE")
+                .arg(&self.config.dlq_stream_name)
+                .arg(&start)
+                .arg("+")
+                .arg("COUNT")
+                .arg(PAGE_SIZE)
+                .query_async(&mut conn)
+                .await?;
+
+            let Some(last) = reply.ids.last() else {
+                break;
+            };
+            start = format!("({}", last.id);
+
+            matches.extend(
+                reply
+                    .ids
+                    .iter()
+                    .map(|entry| DlqEntry::from_stream(&entry.id, &entry.map))
+                    .filter(|entry| filter.matches(entry)),
+            );
+            if reply.ids.len() < PAGE_SIZE {
+                break;
+            }
+        }
+
+        matches.truncate(limit);
+        Ok(matches)
+    }
+
+    /// Put a DLQ entry back on the queue and remove it from the DLQ.
+    ///
+    /// The job is enqueued into its lane as a new message, so it starts
+    /// with a fresh retry count. Returns the job ID; fails with
+    /// `EnqueueFailed` if an identical job is already queued.
+    pub async fn replay_dlq(&self, entry: &DlqEntry) -> QueueResult<String> {
+        let job = entry.job.clone().ok_or_else(|| {
+            QueueError::Serialization(format!("DLQ entry {} has an unreadable job", entry.message_id))
+        })?;
+
+        let job_id = self.enqueue(job).await?;
+
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        if let Some(ref original_id) = entry.original_id {
+            conn.del::<_, ()>(format!("vclip:retry:{}", original_id)).await?;
+        }
+        redis::cmd("XDEL")
+            .arg(&self.config.dlq_stream_name)
+            .arg(&entry.message_id)
+            .query_async::<()>(&mut conn)
+            .await?;
+
+        info!("Replayed DLQ entry {} as job {}", entry.message_id, job_id);
+        Ok(job_id)
+    }
+
+    /// Delete a single DLQ entry. Returns whether it existed.
+    pub async fn delete_dlq(&self, message_id: &str) -> QueueResult<bool> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let deleted: u64 = redis::cmd("XDEL")
+            .arg(&self.config.dlq_stream_name)
+            .arg(message_id)
+            .query_async(&mut conn)
+            .await?;
+        Ok(deleted > 0)
+    }
+
+    /// Delete DLQ entries dead-lettered before `older_than`.
+    ///
+    /// Returns the number of entries removed.
+    pub async fn purge_dlq(&self, older_than: chrono::DateTime<chrono::Utc>) -> QueueResult<u64> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let removed: u64 = redis::cmd("XTRIM")
+            .arg(&self.config.dlq_stream_name)
+            .arg("MINID")
+            .arg(format!("{}-0", older_than.timestamp_millis()))
+            .query_async(&mut conn)
+            .await?;
+
+        if removed > 0 {
+            info!("Purged {} DLQ entries older than {}", removed, older_than);
+        }
+        Ok(removed)
+    }
+
     /// Consume jobs from the queue.
     /// Returns a stream of (message_id, job) pairs.
+    ///
+    /// Up to `count` waiting jobs are admitted from the lanes first.
     pub async fn consume(
         &self,
         consumer_name: &str,
@@ -442,6 +714,10 @@ impl JobQueue {
     ) -> QueueResult<Vec<(String, QueueJob)>> {
         let mut conn = self.client.get_multiplexed_async_connection().await?;
 
+        if let Err(e) = self.admit(&mut conn, count).await {
+            warn!("Failed to admit jobs from lanes: {}", e);
+        }
+
         // Read from consumer group
         let result: redis::streams::StreamReadReply = redis::cmd("XREADGROUP")
             .arg("GROUP")
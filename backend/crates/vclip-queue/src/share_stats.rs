@@ -0,0 +1,356 @@
+//! Buffered share link analytics.
+//!
+//! Every resolution of a public share link is counted here first: one Lua
+//! call increments the link's pending counters in a Redis hash and marks the
+//! link dirty. A flusher periodically drains dirty links and applies each
+//! link's accumulated counters to Firestore in a single write, so a popular
+//! link costs one Firestore write per flush instead of one per view.
+//!
+//! Unique viewers are counted per day with a set of viewer hashes that
+//! expires after the day is over. Referrer headers are client-controlled, so
+//! each link tracks at most `MAX_SHARE_REFERRERS` domains (the first ones
+//! seen); views from any other domain count as `other`.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, TimeZone, Utc};
+use redis::AsyncCommands;
+
+use vclip_models::{ShareStats, ShareViewAction, MAX_SHARE_REFERRERS, OTHER_REFERRER};
+
+use crate::error::QueueResult;
+
+/// Set of share slugs with unflushed counters.
+const SHARE_STATS_DIRTY_KEY: &str = "vclip:share_stats:dirty";
+
+/// How long a day's viewer set is kept (covers the day plus clock skew).
+const VIEWER_SET_TTL_SECS: u64 = 2 * 24 * 3600;
+
+/// How long a link's tracked referrer set is kept after its last referred view.
+const REFERRER_SET_TTL_SECS: u64 = 90 * 24 * 3600;
+
+/// Count one view.
+/// KEYS[1] = pending hash, KEYS[2] = viewer set, KEYS[3] = dirty set,
+/// KEYS[4] = tracked referrer set
+/// ARGV[1] = slug, ARGV[2..4] = user/video/clip ID, ARGV[5] = viewer hash,
+/// ARGV[6] = day, ARGV[7] = action counter, ARGV[8] = referrer ('' for none),
+/// ARGV[9] = now (ms), ARGV[10] = viewer set TTL (s), ARGV[11] = max referrers,
+/// ARGV[12] = overflow referrer, ARGV[13] = referrer set TTL (s)
+const RECORD_VIEW_SCRIPT: &str = r#"
+local new = redis.call('SADD', KEYS[2], ARGV[5])
+redis.call('EXPIRE', KEYS[2], tonumber(ARGV[10]))
+redis.call('HSET', KEYS[1], 'user_id', ARGV[2], 'video_id', ARGV[3], 'clip_id', ARGV[4], 'last_viewed_at', ARGV[9])
+redis.call('HINCRBY', KEYS[1], 'views', 1)
+redis.call('HINCRBY', KEYS[1], ARGV[7], 1)
+redis.call('HINCRBY', KEYS[1], 'day:' .. ARGV[6] .. ':views', 1)
+if new == 1 then
+    redis.call('HINCRBY', KEYS[1], 'unique', 1)
+    redis.call('HINCRBY', KEYS[1], 'day:' .. ARGV[6] .. ':unique', 1)
+end
+if ARGV[8] ~= '' then
+    local ref = ARGV[8]
+    if redis.call('SISMEMBER', KEYS[4], ref) == 0 then
+        if redis.call('SCARD', KEYS[4]) < tonumber(ARGV[11]) then
+            redis.call('SADD', KEYS[4], ref)
+        else
+            ref = ARGV[12]
+        end
+    end
+    redis.call('EXPIRE', KEYS[4], tonumber(ARGV[13]))
+    redis.call('HINCRBY', KEYS[1], 'ref:' .. ref, 1)
+end
+redis.call('SADD', KEYS[3], ARGV[1])
+return new
+"#;
+
+/// Read and clear a pending hash.
+/// KEYS[1] = pending hash
+const TAKE_PENDING_SCRIPT: &str = r#"
+local fields = redis.call('HGETALL', KEYS[1])
+redis.call('DEL', KEYS[1])
+return fields
+"#;
+
+/// One resolution of a share link.
+#[derive(Debug, Clone)]
+pub struct ShareView<'a> {
+    pub share_slug: &'a str,
+    pub user_id: &'a str,
+    pub video_id: &'a str,
+    pub clip_id: &'a str,
+    /// Anonymous viewer ID (see `vclip_models::share_viewer_hash`)
+    pub viewer_hash: &'a str,
+    /// UTC day of the view (`YYYY-MM-DD`)
+    pub day: &'a str,
+    pub referrer: Option<&'a str>,
+    pub action: ShareViewAction,
+}
+
+/// Counters of one share link drained from the buffer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingShareStats {
+    pub share_slug: String,
+    pub user_id: String,
+    pub video_id: String,
+    pub clip_id: String,
+    pub stats: ShareStats,
+}
+
+/// Redis buffer of share view counters.
+#[derive(Clone)]
+pub struct ShareStatsBuffer {
+    client: redis::Client,
+}
+
+impl ShareStatsBuffer {
+    /// Create a share stats buffer.
+    pub fn new(redis_url: &str) -> QueueResult<Self> {
+        let client = redis::Client::open(redis_url)?;
+        Ok(Self { client })
+    }
+
+    fn pending_key(share_slug: &str) -> String {
+        format!("vclip:share_stats:pending:{}", share_slug)
+    }
+
+    fn viewers_key(share_slug: &str, day: &str) -> String {
+        format!("vclip:share_stats:viewers:{}:{}", share_slug, day)
+    }
+
+    fn referrers_key(share_slug: &str) -> String {
+        format!("vclip:share_stats:referrers:{}", share_slug)
+    }
+
+    /// Count a view. Returns whether the viewer is new today.
+    pub async fn record_view(&self, view: &ShareView<'_>) -> QueueResult<bool> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let action_field = match view.action {
+            ShareViewAction::Play => "plays",
+            ShareViewAction::Download => "downloads",
+        };
+        let new: i64 = redis::Script::new(RECORD_VIEW_SCRIPT)
+            .key(Self::pending_key(view.share_slug))
+            .key(Self::viewers_key(view.share_slug, view.day))
+            .key(SHARE_STATS_DIRTY_KEY)
+            .key(Self::referrers_key(view.share_slug))
+            .arg(view.share_slug)
+            .arg(view.user_id)
+            .arg(view.video_id)
+            .arg(view.clip_id)
+            .arg(view.viewer_hash)
+            .arg(view.day)
+            .arg(action_field)
+            .arg(view.referrer.unwrap_or(""))
+            .arg(Utc::now().timestamp_millis())
+            .arg(VIEWER_SET_TTL_SECS)
+            .arg(MAX_SHARE_REFERRERS)
+            .arg(OTHER_REFERRER)
+            .arg(REFERRER_SET_TTL_SECS)
+            .invoke_async(&mut conn)
+            .await?;
+        Ok(new == 1)
+    }
+
+    /// Counters of a link not yet flushed to Firestore.
+    pub async fn pending(&self, share_slug: &str) -> QueueResult<ShareStats> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let fields: HashMap<String, String> = conn.hgetall(Self::pending_key(share_slug)).await?;
+        Ok(stats_from_fields(&fields))
+    }
+
+    /// Remove up to `limit` dirty links and return their counters.
+    ///
+    /// Counters recorded after a link is drained start a new pending hash,
+    /// so concurrent flushers never apply the same increments twice.
+    pub async fn drain(&self, limit: usize) -> QueueResult<Vec<PendingShareStats>> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let slugs: Vec<String> = redis::cmd("SPOP")
+            .arg(SHARE_STATS_DIRTY_KEY)
+            .arg(limit)
+            .query_async(&mut conn)
+            .await?;
+
+        let mut drained = Vec::with_capacity(slugs.len());
+        for share_slug in slugs {
+            let fields: HashMap<String, String> = redis::Script::new(TAKE_PENDING_SCRIPT)
+                .key(Self::pending_key(&share_slug))
+                .invoke_async(&mut conn)
+                .await?;
+            let stats = stats_from_fields(&fields);
+            if stats.is_empty() {
+                continue;
+            }
+
+            let get = |key: &str| fields.get(key).cloned().unwrap_or_default();
+            drained.push(PendingShareStats {
+                user_id: get("user_id"),
+                video_id: get("video_id"),
+                clip_id: get("clip_id"),
+                share_slug,
+                stats,
+            });
+        }
+
+        Ok(drained)
+    }
+
+    /// Put drained counters back after a failed flush.
+    pub async fn restore(&self, pending: &PendingShareStats) -> QueueResult<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let key = Self::pending_key(&pending.share_slug);
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .hset_multiple(
+                &key,
+                &[
+                    ("user_id", pending.user_id.as_str()),
+                    ("video_id", pending.video_id.as_str()),
+                    ("clip_id", pending.clip_id.as_str()),
+                ],
+            )
+            .ignore();
+        for (field, value) in stats_to_increments(&pending.stats) {
+            pipe.hincr(&key, field, value).ignore();
+        }
+        if let Some(last) = pending.stats.last_viewed_at {
+            pipe.hset(&key, "last_viewed_at", last.timestamp_millis())
+                .ignore();
+        }
+        pipe.sadd(SHARE_STATS_DIRTY_KEY, &pending.share_slug)
+            .ignore();
+        pipe.exec_async(&mut conn).await?;
+        Ok(())
+    }
+}
+
+/// Parse a pending hash into counters.
+fn stats_from_fields(fields: &HashMap<String, String>) -> ShareStats {
+    let mut stats = ShareStats::default();
+    for (field, value) in fields {
+        if field == "last_viewed_at" {
+            stats.last_viewed_at = value
+                .parse::<i64>()
+                .ok()
+                .and_then(|ms| Utc.timestamp_millis_opt(ms).single());
+            continue;
+        }
+        let Ok(count) = value.parse::<u64>() else {
+            continue;
+        };
+
+        match field.as_str() {
+            "views" => stats.total_views = count,
+            "plays" => stats.plays = count,
+            "downloads" => stats.downloads = count,
+            "unique" => stats.unique_viewers = count,
+            _ => {
+                if let Some(domain) = field.strip_prefix("ref:") {
+                    stats.referrers.insert(domain.to_string(), count);
+                } else if let Some((day, counter)) = field
+                    .strip_prefix("day:")
+                    .and_then(|rest| rest.rsplit_once(':'))
+                {
+                    let daily = stats.daily.entry(day.to_string()).or_default();
+                    match counter {
+                        "views" => daily.views = count,
+                        "unique" => daily.unique_viewers = count,
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    stats
+}
+
+/// Hash increments that reproduce `stats` (inverse of `stats_from_fields`).
+fn stats_to_increments(stats: &ShareStats) -> Vec<(String, u64)> {
+    let mut increments = vec![
+        ("views".to_string(), stats.total_views),
+        ("plays".to_string(), stats.plays),
+        ("downloads".to_string(), stats.downloads),
+        ("unique".to_string(), stats.unique_viewers),
+    ];
+    for (day, daily) in &stats.daily {
+        increments.push((format!("day:{}:views", day), daily.views));
+        increments.push((format!("day:{}:unique", day), daily.unique_viewers));
+    }
+    for (domain, views) in stats.top_referrers(MAX_SHARE_REFERRERS) {
+        increments.push((format!("ref:{}", domain), views));
+    }
+    increments.retain(|(_, value)| *value > 0);
+    increments
+}
+
+/// UTC day of a timestamp as used for daily counters.
+pub fn share_stats_day(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_fields_round_trip() {
+        let fields: HashMap<String, String> = [
+            ("user_id", "u1"),
+            ("views", "5"),
+            ("plays", "4"),
+            ("downloads", "1"),
+            ("unique", "3"),
+            ("day:2026-01-01:views", "2"),
+            ("day:2026-01-01:unique", "1"),
+            ("day:2026-01-02:views", "3"),
+            ("day:2026-01-02:unique", "2"),
+            ("ref:news.ycombinator.com", "2"),
+            ("last_viewed_at", "1767225600000"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let stats = stats_from_fields(&fields);
+        assert_eq!(stats.total_views, 5);
+        assert_eq!(stats.unique_viewers, 3);
+        assert_eq!(stats.daily["2026-01-02"].views, 3);
+        assert_eq!(stats.daily["2026-01-02"].unique_viewers, 2);
+        assert_eq!(stats.referrers["news.ycombinator.com"], 2);
+        assert_eq!(
+            stats.last_viewed_at.map(|t| t.timestamp_millis()),
+            Some(1_767_225_600_000)
+        );
+
+        let restored: HashMap<String, String> = stats_to_increments(&stats)
+            .into_iter()
+            .map(|(k, v)| (k, v.to_string()))
+            .collect();
+        let mut expected = stats.clone();
+        expected.last_viewed_at = None;
+        assert_eq!(stats_from_fields(&restored), expected);
+    }
+
+    #[test]
+    fn test_increments_cap_referrers() {
+        let mut stats = ShareStats {
+            total_views: 100,
+            ..Default::default()
+        };
+        for i in 0..100 {
+            stats.referrers.insert(format!("spam{}.example", i), 1);
+        }
+
+        let referrer_fields = stats_to_increments(&stats)
+            .into_iter()
+            .filter(|(field, _)| field.starts_with("ref:"))
+            .count();
+        assert_eq!(referrer_fields, MAX_SHARE_REFERRERS + 1);
+    }
+
+    #[test]
+    fn test_share_stats_day() {
+        let at = Utc.with_ymd_and_hms(2026, 3, 9, 23, 59, 0).unwrap();
+        assert_eq!(share_stats_day(at), "2026-03-09");
+    }
+}
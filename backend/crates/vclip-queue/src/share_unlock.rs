@@ -0,0 +1,101 @@
+//! Failed passcode attempts per share link.
+//!
+//! The per-IP limit on the unlock route does not stop an attacker who
+//! rotates addresses, so failures are also counted per share slug. After
+//! `FREE_FAILED_ATTEMPTS` failures the link refuses further attempts for a
+//! lockout that doubles with every additional failure. The counter is
+//! cleared by a successful unlock and expires after a quiet period.
+
+use redis::AsyncCommands;
+
+use crate::error::QueueResult;
+
+/// Failures allowed before the first lockout.
+pub const FREE_FAILED_ATTEMPTS: u64 = 10;
+
+/// First lockout, doubled for every further failure.
+const BASE_LOCKOUT_SECS: u64 = 60;
+
+/// Longest lockout.
+const MAX_LOCKOUT_SECS: u64 = 3600;
+
+/// How long the failure counter is kept after the last failure.
+const FAILURE_WINDOW_SECS: u64 = 24 * 3600;
+
+/// Redis guard against passcode brute force on share links.
+#[derive(Clone)]
+pub struct ShareUnlockGuard {
+    client: redis::Client,
+}
+
+impl ShareUnlockGuard {
+    /// Create a share unlock guard.
+    pub fn new(redis_url: &str) -> QueueResult<Self> {
+        let client = redis::Client::open(redis_url)?;
+        Ok(Self { client })
+    }
+
+    fn failures_key(share_slug: &str) -> String {
+        format!("vclip:share_unlock:failures:{}", share_slug)
+    }
+
+    fn lock_key(share_slug: &str) -> String {
+        format!("vclip:share_unlock:lock:{}", share_slug)
+    }
+
+    /// Seconds until the link accepts passcodes again, or `None` if it is not locked.
+    pub async fn locked_for(&self, share_slug: &str) -> QueueResult<Option<u64>> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let ttl: i64 = conn.ttl(Self::lock_key(share_slug)).await?;
+        Ok((ttl > 0).then_some(ttl as u64))
+    }
+
+    /// Count a wrong passcode. Returns the lockout it triggered, if any.
+    pub async fn record_failure(&self, share_slug: &str) -> QueueResult<Option<u64>> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let key = Self::failures_key(share_slug);
+        let (failures,): (u64,) = redis::pipe()
+            .atomic()
+            .incr(&key, 1)
+            .expire(&key, FAILURE_WINDOW_SECS as i64)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+
+        let lockout = lockout_secs(failures);
+        if let Some(secs) = lockout {
+            let _: () = conn.set_ex(Self::lock_key(share_slug), 1, secs).await?;
+        }
+        Ok(lockout)
+    }
+
+    /// Forget the failures of a link after a correct passcode.
+    pub async fn clear(&self, share_slug: &str) -> QueueResult<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let _: () = conn.del(Self::failures_key(share_slug)).await?;
+        Ok(())
+    }
+}
+
+/// Lockout after the given number of consecutive failures.
+fn lockout_secs(failures: u64) -> Option<u64> {
+    let over = failures.checked_sub(FREE_FAILED_ATTEMPTS)?;
+    let secs = BASE_LOCKOUT_SECS.saturating_mul(1u64 << over.min(16));
+    Some(secs.min(MAX_LOCKOUT_SECS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lockout_secs_backs_off() {
+        assert_eq!(lockout_secs(0), None);
+        assert_eq!(lockout_secs(FREE_FAILED_ATTEMPTS - 1), None);
+        assert_eq!(lockout_secs(FREE_FAILED_ATTEMPTS), Some(60));
+        assert_eq!(lockout_secs(FREE_FAILED_ATTEMPTS + 1), Some(120));
+        assert_eq!(lockout_secs(FREE_FAILED_ATTEMPTS + 5), Some(1920));
+        assert_eq!(lockout_secs(FREE_FAILED_ATTEMPTS + 6), Some(MAX_LOCKOUT_SECS));
+        assert_eq!(lockout_secs(u64::MAX), Some(MAX_LOCKOUT_SECS));
+    }
+}
@@ -0,0 +1,284 @@
+//! Webhook event stream and delivery schedule.
+//!
+//! Progress events that webhooks care about are appended to a capped Redis
+//! stream by [`ProgressChannel::publish_with_history`]. A dispatcher reads
+//! the stream through a consumer group (so each event is fanned out once
+//! across workers), resolves the user's endpoints and schedules one
+//! [`PendingWebhook`] per endpoint.
+//!
+//! Scheduled deliveries live in a sorted set scored by due time, with their
+//! payloads in a hash. Claiming a delivery pushes its score out by a lease,
+//! so a delivery whose worker dies mid-request is retried once the lease
+//! expires.
+//!
+//! [`ProgressChannel::publish_with_history`]: crate::ProgressChannel::publish_with_history
+
+use chrono::{DateTime, Utc};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use vclip_models::WebhookEventType;
+
+use crate::error::{QueueError, QueueResult};
+use crate::progress::ProgressEvent;
+
+/// Stream of progress events to deliver to webhooks.
+pub const WEBHOOK_EVENTS_STREAM: &str = "vclip:webhooks:events";
+
+/// Approximate cap on the events stream length.
+pub const WEBHOOK_EVENTS_MAXLEN: usize = 10_000;
+
+/// Consumer group of webhook dispatchers.
+const WEBHOOK_DISPATCH_GROUP: &str = "vclip-webhook-dispatchers";
+
+/// Sorted set of scheduled deliveries, scored by due time (ms).
+const WEBHOOK_DUE_KEY: &str = "vclip:webhooks:due";
+
+/// Hash of scheduled delivery payloads by delivery ID.
+const WEBHOOK_PENDING_KEY: &str = "vclip:webhooks:pending";
+
+/// How long a claimed delivery stays hidden from other workers.
+pub const WEBHOOK_DELIVERY_LEASE_MS: i64 = 60_000;
+
+/// Events pending this long in a crashed dispatcher are claimed by another.
+const EVENT_CLAIM_MIN_IDLE_MS: u64 = 60_000;
+
+/// Claim due deliveries: ZRANGEBYSCORE due, then push each out by the lease.
+/// KEYS[1] = due set, KEYS[2] = pending hash
+/// ARGV[1] = now (ms), ARGV[2] = lease (ms), ARGV[3] = limit
+const CLAIM_DUE_SCRIPT: &str = r#"
+local ids = redis.call('ZRANGEBYSCORE', KEYS[1], '-inf', ARGV[1], 'LIMIT', 0, tonumber(ARGV[3]))
+local result = {}
+for _, id in ipairs(ids) do
+    redis.call('ZADD', KEYS[1], tonumber(ARGV[1]) + tonumber(ARGV[2]), id)
+    local payload = redis.call('HGET', KEYS[2], id)
+    if payload then
+        table.insert(result, payload)
+    else
+        redis.call('ZREM', KEYS[1], id)
+    end
+end
+return result
+"#;
+
+/// A delivery waiting to be (re)attempted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingWebhook {
+    /// Delivery ID (stable across retries)
+    pub delivery_id: String,
+    /// Owner of the endpoint
+    pub user_id: String,
+    /// Endpoint to deliver to
+    pub endpoint_id: String,
+    /// Event type
+    pub event_type: WebhookEventType,
+    /// Serialized `WebhookPayload`; identical on every attempt
+    pub body: String,
+    /// Attempts made so far
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+/// Redis client for webhook events and scheduled deliveries.
+#[derive(Clone)]
+pub struct WebhookQueue {
+    client: redis::Client,
+}
+
+impl WebhookQueue {
+    /// Create a webhook queue.
+    pub fn new(redis_url: &str) -> QueueResult<Self> {
+        let client = redis::Client::open(redis_url)?;
+        Ok(Self { client })
+    }
+
+    /// Create from environment variables.
+    pub fn from_env() -> QueueResult<Self> {
+        let redis_url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+        Self::new(&redis_url)
+    }
+
+    /// Create the dispatcher consumer group if it does not exist.
+    pub async fn init(&self) -> QueueResult<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+
+        let result: Result<(), redis::RedisError> = redis::cmd("XGROUP")
+            .arg("CREATE")
+            .arg(WEBHOOK_EVENTS_STREAM)
+            .arg(WEBHOOK_DISPATCH_GROUP)
+            .arg("$")
+            .arg("MKSTREAM")
+            .query_async(&mut conn)
+            .await;
+
+        match result {
+            Ok(_) => info!("Created consumer group: {}", WEBHOOK_DISPATCH_GROUP),
+            Err(e) if e.to_string().contains("BUSYGROUP") => {
+                debug!("Consumer group already exists: {}", WEBHOOK_DISPATCH_GROUP);
+            }
+            Err(e) => return Err(QueueError::Redis(e)),
+        }
+
+        Ok(())
+    }
+
+    /// Read events to fan out, reclaiming events a crashed dispatcher left pending.
+    pub async fn read_events(
+        &self,
+        consumer_name: &str,
+        count: usize,
+        block_ms: u64,
+    ) -> QueueResult<Vec<(String, ProgressEvent)>> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+
+        let claimed: redis::streams::StreamAutoClaimReply = redis::cmd("XAUTOCLAIM")
+            .arg(WEBHOOK_EVENTS_STREAM)
+            .arg(WEBHOOK_DISPATCH_GROUP)
+            .arg(consumer_name)
+            .arg(EVENT_CLAIM_MIN_IDLE_MS)
+            .arg("0-0")
+            .arg("COUNT")
+            .arg(count)
+            .query_async(&mut conn)
+            .await?;
+
+        let entries = if !claimed.claimed.is_empty() {
+            claimed.claimed
+        } else {
+            let reply: redis::streams::StreamReadReply = redis::cmd("XREADGROUP")
+                .arg("GROUP")
+                .arg(WEBHOOK_DISPATCH_GROUP)
+                .arg(consumer_name)
+                .arg("COUNT")
+                .arg(count)
+                .arg("BLOCK")
+                .arg(block_ms)
+                .arg("STREAMS")
+                .arg(WEBHOOK_EVENTS_STREAM)
+                .arg(">")
+                .query_async(&mut conn)
+                .await?;
+            reply.keys.into_iter().flat_map(|k| k.ids).collect()
+        };
+
+        let mut events = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let parsed = match entry.map.get("event") {
+                Some(redis::Value::BulkString(payload)) => {
+                    serde_json::from_slice::<ProgressEvent>(payload).ok()
+                }
+                _ => None,
+            };
+            match parsed {
+                Some(event) => events.push((entry.id, event)),
+                None => {
+                    warn!("Dropping malformed webhook event {}", entry.id);
+                    self.ack_event(&entry.id).await.ok();
+                }
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Acknowledge a fanned-out event.
+    pub async fn ack_event(&self, message_id: &str) -> QueueResult<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        redis::pipe()
+            .cmd("XACK")
+            .arg(WEBHOOK_EVENTS_STREAM)
+            .arg(WEBHOOK_DISPATCH_GROUP)
+            .arg(message_id)
+            .ignore()
+            .cmd("XDEL")
+            .arg(WEBHOOK_EVENTS_STREAM)
+            .arg(message_id)
+            .ignore()
+            .exec_async(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Schedule a delivery attempt.
+    pub async fn schedule(&self, pending: &PendingWebhook, due: DateTime<Utc>) -> QueueResult<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let payload = serde_json::to_string(pending)?;
+        redis::pipe()
+            .atomic()
+            .hset(WEBHOOK_PENDING_KEY, &pending.delivery_id, payload)
+            .ignore()
+            .zadd(WEBHOOK_DUE_KEY, &pending.delivery_id, due.timestamp_millis())
+            .ignore()
+            .exec_async(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Claim up to `limit` due deliveries for this worker.
+    pub async fn claim_due(&self, limit: usize) -> QueueResult<Vec<PendingWebhook>> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let payloads: Vec<String> = redis::Script::new(CLAIM_DUE_SCRIPT)
+            .key(WEBHOOK_DUE_KEY)
+            .key(WEBHOOK_PENDING_KEY)
+            .arg(Utc::now().timestamp_millis())
+            .arg(WEBHOOK_DELIVERY_LEASE_MS)
+            .arg(limit)
+            .invoke_async(&mut conn)
+            .await?;
+
+        Ok(payloads
+            .iter()
+            .filter_map(|p| match serde_json::from_str(p) {
+                Ok(pending) => Some(pending),
+                Err(e) => {
+                    warn!("Skipping malformed scheduled webhook: {}", e);
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// Remove a delivery that succeeded or ran out of attempts.
+    pub async fn complete(&self, delivery_id: &str) -> QueueResult<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        redis::pipe()
+            .atomic()
+            .zrem(WEBHOOK_DUE_KEY, delivery_id)
+            .ignore()
+            .hdel(WEBHOOK_PENDING_KEY, delivery_id)
+            .ignore()
+            .exec_async(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Number of scheduled deliveries.
+    pub async fn scheduled_count(&self) -> QueueResult<u64> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        Ok(conn.zcard(WEBHOOK_DUE_KEY).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_webhook_roundtrip() {
+        let pending = PendingWebhook {
+            delivery_id: "d1".to_string(),
+            user_id: "u1".to_string(),
+            endpoint_id: "e1".to_string(),
+            event_type: WebhookEventType::ClipUploaded,
+            body: "{}".to_string(),
+            attempts: 2,
+        };
+        let json = serde_json::to_string(&pending).unwrap();
+        assert!(json.contains("\"clip.uploaded\""));
+        let decoded: PendingWebhook = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.attempts, 2);
+        assert_eq!(decoded.event_type, WebhookEventType::ClipUploaded);
+    }
+}
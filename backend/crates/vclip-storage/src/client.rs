@@ -305,6 +305,150 @@ impl R2Client {
         Ok(presigned.uri().to_string())
     }
 
+    /// Start a multipart upload, returning its upload ID.
+    pub async fn create_multipart_upload(
+        &self,
+        key: &str,
+        content_type: &str,
+    ) -> StorageResult<String> {
+        let response = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .send()
+            .await
+            .map_err(|e| StorageError::upload_failed(e.to_string()))?;
+
+        let upload_id = response
+            .upload_id()
+            .ok_or_else(|| StorageError::upload_failed("No upload ID returned"))?
+            .to_string();
+
+        debug!("Started multipart upload {} for {}", upload_id, key);
+        Ok(upload_id)
+    }
+
+    /// Generate a presigned URL for PUTting one part of a multipart upload.
+    pub async fn presign_upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: u32,
+        expires_in: Duration,
+    ) -> StorageResult<String> {
+        let presign_config = PresigningConfig::expires_in(expires_in)
+            .map_err(|e| StorageError::PresignFailed(e.to_string()))?;
+
+        let presigned = self
+            .client
+            .upload_part()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .part_number(part_number as i32)
+            .presigned(presign_config)
+            .await
+            .map_err(|e| StorageError::PresignFailed(e.to_string()))?;
+
+        Ok(presigned.uri().to_string())
+    }
+
+    /// List the parts uploaded so far, ordered by part number.
+    pub async fn list_parts(&self, key: &str, upload_id: &str) -> StorageResult<Vec<UploadedPart>> {
+        let mut parts = Vec::new();
+        let mut marker: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .client
+                .list_parts()
+                .bucket(&self.bucket)
+                .key(key)
+                .upload_id(upload_id);
+
+            if let Some(marker) = marker {
+                request = request.part_number_marker(marker);
+            }
+
+            let response = request
+                .send()
+                .await
+                .map_err(|e| StorageError::ListFailed(e.to_string()))?;
+
+            for part in response.parts() {
+                parts.push(UploadedPart {
+                    part_number: part.part_number().unwrap_or(0) as u32,
+                    etag: part.e_tag().unwrap_or_default().to_string(),
+                    size: part.size().unwrap_or(0) as u64,
+                });
+            }
+
+            match response.next_part_number_marker() {
+                Some(next) if response.is_truncated() == Some(true) => {
+                    marker = Some(next.to_string());
+                }
+                _ => break,
+            }
+        }
+
+        parts.sort_by_key(|p| p.part_number);
+        Ok(parts)
+    }
+
+    /// Assemble uploaded parts into the final object.
+    pub async fn complete_multipart_upload(
+        &self,
+        key: &str,
+        upload_id: &str,
+        parts: &[UploadedPart],
+    ) -> StorageResult<()> {
+        use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+
+        let completed = CompletedMultipartUpload::builder()
+            .set_parts(Some(
+                parts
+                    .iter()
+                    .map(|p| {
+                        CompletedPart::builder()
+                            .part_number(p.part_number as i32)
+                            .e_tag(&p.etag)
+                            .build()
+                    })
+                    .collect(),
+            ))
+            .build();
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .multipart_upload(completed)
+            .send()
+            .await
+            .map_err(|e| StorageError::upload_failed(e.to_string()))?;
+
+        info!("Completed multipart upload {} ({} parts)", key, parts.len());
+        Ok(())
+    }
+
+    /// Abort a multipart upload, discarding its parts.
+    pub async fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> StorageResult<()> {
+        self.client
+            .abort_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .send()
+            .await
+            .map_err(|e| StorageError::delete_failed(e.to_string()))?;
+
+        debug!("Aborted multipart upload {} for {}", upload_id, key);
+        Ok(())
+    }
+
     /// Delete an object.
     pub async fn delete_object(&self, key: &str) -> StorageResult<()> {
         debug!("Deleting {}", key);
@@ -442,6 +586,34 @@ impl R2Client {
         }
     }
 
+    /// Size of an object in bytes, or `None` if it does not exist.
+    pub async fn object_size(&self, key: &str) -> StorageResult<Option<u64>> {
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(head) => Ok(Some(head.content_length().unwrap_or(0).max(0) as u64)),
+            Err(e) => {
+                let err_str = e.to_string();
+                if err_str.contains("NotFound")
+                    || err_str.contains("NoSuchKey")
+                    || err_str.contains("404")
+                {
+                    Ok(None)
+                } else {
+                    Err(StorageError::AwsSdk(format!(
+                        "key={}, bucket={}, error={}",
+                        key, self.bucket, err_str
+                    )))
+                }
+            }
+        }
+    }
+
     /// Check connectivity to R2 by performing a head bucket operation.
     pub async fn check_connectivity(&self) -> StorageResult<()> {
         self.client
@@ -464,3 +636,14 @@ pub struct ObjectInfo {
     /// Last modified timestamp (milliseconds since epoch)
     pub last_modified: Option<u64>,
 }
+
+/// A part of an in-progress multipart upload.
+#[derive(Debug, Clone)]
+pub struct UploadedPart {
+    /// Part number (1-based)
+    pub part_number: u32,
+    /// ETag returned when the part was uploaded
+    pub etag: String,
+    /// Size in bytes
+    pub size: u64,
+}
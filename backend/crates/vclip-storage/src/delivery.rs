@@ -146,6 +146,10 @@ pub struct DeliveryToken {
     /// Optional: watermark flag.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wm: Option<bool>,
+    /// Optional: share slug the token was issued for. The Worker rejects
+    /// the token once the share is revoked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ss: Option<String>,
 }
 
 impl DeliveryToken {
@@ -164,6 +168,7 @@ impl DeliveryToken {
             r2_key: None,
             share: None,
             wm: None,
+            ss: None,
         }
     }
 
@@ -182,6 +187,7 @@ impl DeliveryToken {
             r2_key: Some(r2_key.to_string()),
             share: None,
             wm: None,
+            ss: None,
         }
     }
 
@@ -191,6 +197,13 @@ impl DeliveryToken {
         self
     }
 
+    /// Bind to a share link, so revoking the share invalidates the token.
+    pub fn with_share_slug(mut self, share_slug: &str) -> Self {
+        self.share = Some(true);
+        self.ss = Some(share_slug.to_string());
+        self
+    }
+
     /// Mark for watermarking.
     pub fn with_watermark(mut self) -> Self {
         self.wm = Some(true);
@@ -482,6 +495,40 @@ impl DeliveryUrlGenerator {
         r2_key: Option<&str>,
         scope: DeliveryScope,
         expiry: Duration,
+    ) -> StorageResult<DeliveryUrl> {
+        // Create token with or without R2 key
+        let token = if let Some(key) = r2_key {
+            DeliveryToken::with_r2_key(clip_id, user_id, key, scope, expiry)
+        } else {
+            DeliveryToken::new(clip_id, user_id, scope, expiry)
+        };
+        self.worker_url_for_token(&token, scope, expiry)
+    }
+
+    /// Generate Worker-fronted URL for a public share link.
+    ///
+    /// The token is bound to the share slug, so the Worker stops serving it
+    /// as soon as the share is revoked, even before it expires.
+    pub fn generate_share_worker_url(
+        &self,
+        clip_id: &str,
+        user_id: &str,
+        r2_key: &str,
+        share_slug: &str,
+        scope: DeliveryScope,
+        expiry: Duration,
+    ) -> StorageResult<DeliveryUrl> {
+        let token = DeliveryToken::with_r2_key(clip_id, user_id, r2_key, scope, expiry)
+            .with_share_slug(share_slug);
+        self.worker_url_for_token(&token, scope, expiry)
+    }
+
+    /// Sign a token and build its Worker URL.
+    fn worker_url_for_token(
+        &self,
+        token: &DeliveryToken,
+        scope: DeliveryScope,
+        expiry: Duration,
     ) -> StorageResult<DeliveryUrl> {
         let secret = self.config.signing_secret.as_ref().ok_or_else(|| {
             StorageError::ConfigError("DELIVERY_SIGNING_SECRET not configured".to_string())
@@ -491,12 +538,7 @@ impl DeliveryUrlGenerator {
             StorageError::ConfigError("CDN_WORKER_URL not configured".to_string())
         })?;
 
-        // Create token with or without R2 key
-        let token = if let Some(key) = r2_key {
-            DeliveryToken::with_r2_key(clip_id, user_id, key, scope, expiry)
-        } else {
-            DeliveryToken::new(clip_id, user_id, scope, expiry)
-        };
+        let clip_id = token.cid.as_str();
         let signed = token.sign(secret)?;
 
         let path = match scope {
@@ -578,6 +620,26 @@ mod tests {
         assert!(result.is_none(), "expired token should not verify");
     }
 
+    #[test]
+    fn test_delivery_token_share_slug() {
+        let secret = "test-secret-key-32-bytes-long!!!";
+        let token = DeliveryToken::with_r2_key(
+            "clip-123",
+            "user-456",
+            "user-456/v/clips/clip-123.mp4",
+            DeliveryScope::Playback,
+            Duration::from_secs(3600),
+        )
+        .with_share_slug("abcd1234efgh");
+        let signed = token.sign(secret).expect("should sign");
+
+        let verified = DeliveryToken::verify(&signed, secret)
+            .expect("should not error")
+            .expect("should verify");
+        assert_eq!(verified.share, Some(true));
+        assert_eq!(verified.ss.as_deref(), Some("abcd1234efgh"));
+    }
+
     #[test]
     fn test_delivery_scope_str() {
         assert_eq!(DeliveryScope::Playback.as_str(), "play");
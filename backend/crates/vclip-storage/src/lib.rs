@@ -3,21 +3,30 @@
 //! This crate provides:
 //! - File upload/download to R2
 //! - Presigned URL generation
+//! - Multipart uploads with presigned part URLs
 //! - Clip and highlight listing
 //! - File deletion
 //! - Secure video delivery (playback/download/share URLs)
 //! - Neural analysis cache (gzip-compressed JSON)
+//! - Word alignment cache (gzip-compressed JSON)
+//! - Live capture segments and their manifest
 
 pub mod client;
 pub mod delivery;
 pub mod error;
+pub mod live_capture;
 pub mod neural_cache;
 pub mod operations;
 pub mod transcript_cache;
+pub mod word_alignment_cache;
 
-pub use client::R2Client;
+pub use client::{ObjectInfo, R2Client, UploadedPart};
 pub use delivery::{DeliveryConfig, DeliveryScope, DeliveryToken, DeliveryUrl, DeliveryUrlGenerator};
 pub use error::{StorageError, StorageResult};
+pub use live_capture::{
+    live_capture_prefix, live_manifest_key, live_segment_key, load_live_manifest,
+    store_live_manifest, LIVE_SEGMENT_CONTENT_TYPE,
+};
 pub use neural_cache::{
     compress_neural_analysis, decompress_neural_analysis, delete_neural_analysis,
     load_neural_analysis, neural_analysis_exists, neural_cache_key, store_neural_analysis,
@@ -28,4 +37,9 @@ pub use transcript_cache::{
     store_transcript, transcript_cache_id_from_url, transcript_cache_key, transcript_exists,
     StoreResult as TranscriptCacheStoreResult,
 };
+pub use word_alignment_cache::{
+    compress_word_alignment, decompress_word_alignment, delete_word_alignment,
+    load_word_alignment, store_word_alignment, word_alignment_cache_key,
+    StoreResult as WordAlignmentStoreResult,
+};
 pub use operations::HighlightsData;
@@ -0,0 +1,78 @@
+//! Live capture segment storage.
+//!
+//! A live capture uploads each recorded segment under
+//! `{user_id}/live/{draft_id}/` next to a JSON manifest that maps the capture
+//! timeline to the segment objects.
+
+use tracing::debug;
+
+use crate::client::R2Client;
+use crate::error::{StorageError, StorageResult};
+use vclip_models::LiveCaptureManifest;
+
+/// Content type for recorded segments.
+pub const LIVE_SEGMENT_CONTENT_TYPE: &str = "video/mp4";
+
+/// R2 prefix holding everything a live capture recorded.
+///
+/// Format: `{user_id}/live/{draft_id}/`
+pub fn live_capture_prefix(user_id: &str, draft_id: &str) -> String {
+    format!("{}/live/{}/", user_id, draft_id)
+}
+
+/// Generate the R2 key of a recorded segment.
+///
+/// Format: `{user_id}/live/{draft_id}/seg_{index:05}.mp4`
+pub fn live_segment_key(user_id: &str, draft_id: &str, index: u32) -> String {
+    format!("{}seg_{:05}.mp4", live_capture_prefix(user_id, draft_id), index)
+}
+
+/// Generate the R2 key of the segment manifest.
+///
+/// Format: `{user_id}/live/{draft_id}/manifest.json`
+pub fn live_manifest_key(user_id: &str, draft_id: &str) -> String {
+    format!("{}manifest.json", live_capture_prefix(user_id, draft_id))
+}
+
+/// Store the segment manifest.
+pub async fn store_live_manifest(
+    r2: &R2Client,
+    key: &str,
+    manifest: &LiveCaptureManifest,
+) -> StorageResult<()> {
+    let data = serde_json::to_vec(manifest)?;
+    debug!(key = %key, segments = manifest.segments.len(), "Storing live capture manifest");
+    r2.upload_bytes(data, key, "application/json").await
+}
+
+/// Load the segment manifest.
+///
+/// Returns `None` if nothing has been recorded yet.
+pub async fn load_live_manifest(
+    r2: &R2Client,
+    key: &str,
+) -> StorageResult<Option<LiveCaptureManifest>> {
+    match r2.download_bytes(key).await {
+        Ok(data) => Ok(Some(serde_json::from_slice(&data)?)),
+        Err(StorageError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_capture_keys() {
+        assert_eq!(live_capture_prefix("user123", "draft456"), "user123/live/draft456/");
+        assert_eq!(
+            live_segment_key("user123", "draft456", 42),
+            "user123/live/draft456/seg_00042.mp4"
+        );
+        assert_eq!(
+            live_manifest_key("user123", "draft456"),
+            "user123/live/draft456/manifest.json"
+        );
+    }
+}
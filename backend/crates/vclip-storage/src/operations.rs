@@ -147,4 +147,20 @@ impl R2Client {
         let keys = vec![clip_key, thumb_key];
         self.delete_objects(&keys).await
     }
+
+    /// Delete a single clip, its thumbnail and its export variants.
+    pub async fn delete_clip_with_variants(
+        &self,
+        user_id: &str,
+        video_id: &str,
+        clip_name: &str,
+        variant_keys: &[String],
+    ) -> StorageResult<u32> {
+        let clip_key = format!("{}/{}/clips/{}", user_id, video_id, clip_name);
+        let thumb_key = clip_key.replace(".mp4", ".jpg");
+
+        let mut keys = vec![clip_key, thumb_key];
+        keys.extend_from_slice(variant_keys);
+        self.delete_objects(&keys).await
+    }
 }
@@ -0,0 +1,202 @@
+//! Word alignment cache helpers.
+//!
+//! Stores `SceneWordAlignment` in R2 as gzip-compressed JSON next to the
+//! scene's neural analysis cache entry, so forced alignment runs once per
+//! scene across reprocessing passes.
+
+use std::io::{Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use tracing::{debug, warn};
+
+use crate::client::R2Client;
+use crate::error::{StorageError, StorageResult};
+use vclip_models::{SceneWordAlignment, WORD_ALIGNMENT_VERSION};
+
+/// Content type for gzip-compressed JSON.
+const CONTENT_TYPE_GZIP: &str = "application/gzip";
+
+/// Generate the R2 key for a word alignment cache entry.
+///
+/// Format: `{user_id}/{video_id}/neural/{scene_id}.words.json.gz`
+pub fn word_alignment_cache_key(user_id: &str, video_id: &str, scene_id: u32) -> String {
+    format!("{}/{}/neural/{}.words.json.gz", user_id, video_id, scene_id)
+}
+
+/// Compress `SceneWordAlignment` to gzip JSON bytes.
+pub fn compress_word_alignment(alignment: &SceneWordAlignment) -> StorageResult<Vec<u8>> {
+    let json = serde_json::to_string(alignment).map_err(|e| {
+        StorageError::Serialization(format!("Failed to serialize word alignment: {}", e))
+    })?;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(json.as_bytes()).map_err(|e| {
+        StorageError::Serialization(format!("Failed to gzip word alignment: {}", e))
+    })?;
+
+    encoder.finish().map_err(|e| {
+        StorageError::Serialization(format!("Failed to finish gzip encoding: {}", e))
+    })
+}
+
+/// Decompress gzip JSON bytes to `SceneWordAlignment`.
+///
+/// Returns `None` if decompression or deserialization fails, or if the
+/// cached version is outdated (treated as cache miss).
+pub fn decompress_word_alignment(data: &[u8]) -> Option<SceneWordAlignment> {
+    let mut decoder = GzDecoder::new(data);
+    let mut json = String::new();
+
+    if let Err(e) = decoder.read_to_string(&mut json) {
+        warn!(error = %e, "Failed to decompress word alignment cache");
+        return None;
+    }
+
+    match serde_json::from_str::<SceneWordAlignment>(&json) {
+        Ok(alignment) if alignment.is_current_version() => Some(alignment),
+        Ok(alignment) => {
+            debug!(
+                cached_version = alignment.alignment_version,
+                current_version = WORD_ALIGNMENT_VERSION,
+                "Word alignment cache version mismatch, treating as miss"
+            );
+            None
+        }
+        Err(e) => {
+            warn!(error = %e, "Failed to deserialize word alignment cache");
+            None
+        }
+    }
+}
+
+/// Result of storing a word alignment.
+pub struct StoreResult {
+    /// R2 key where the alignment was stored
+    pub key: String,
+    /// Compressed size in bytes
+    pub compressed_size: u64,
+    /// Size of the entry this store overwrote, if there was one
+    pub replaced_size: Option<u64>,
+}
+
+/// Store word alignment to R2.
+pub async fn store_word_alignment(
+    r2: &R2Client,
+    user_id: &str,
+    video_id: &str,
+    scene_id: u32,
+    alignment: &SceneWordAlignment,
+) -> StorageResult<StoreResult> {
+    let key = word_alignment_cache_key(user_id, video_id, scene_id);
+    let compressed = compress_word_alignment(alignment)?;
+    let compressed_size = compressed.len() as u64;
+
+    debug!(
+        key = %key,
+        words = alignment.words.len(),
+        compressed_size = compressed_size,
+        "Storing word alignment to R2"
+    );
+
+    // Re-alignments overwrite the entry; callers account for the difference
+    let replaced_size = match r2.object_size(&key).await {
+        Ok(size) => size,
+        Err(e) => {
+            debug!(key = %key, error = %e, "Failed to get size of existing word alignment");
+            None
+        }
+    };
+
+    r2.upload_bytes(compressed, &key, CONTENT_TYPE_GZIP).await?;
+
+    Ok(StoreResult {
+        key,
+        compressed_size,
+        replaced_size,
+    })
+}
+
+/// Load word alignment from R2.
+///
+/// Missing, corrupt, or outdated entries are all treated as cache misses.
+pub async fn load_word_alignment(
+    r2: &R2Client,
+    user_id: &str,
+    video_id: &str,
+    scene_id: u32,
+) -> Option<SceneWordAlignment> {
+    let key = word_alignment_cache_key(user_id, video_id, scene_id);
+
+    let data = match r2.download_bytes(&key).await {
+        Ok(data) => data,
+        Err(e) => {
+            debug!(key = %key, error = %e, "Word alignment cache miss (download failed)");
+            return None;
+        }
+    };
+
+    let alignment = decompress_word_alignment(&data);
+    match &alignment {
+        Some(a) => debug!(key = %key, words = a.words.len(), "Word alignment cache hit"),
+        None => debug!(key = %key, "Word alignment cache miss (corrupt or outdated)"),
+    }
+    alignment
+}
+
+/// Delete word alignment from cache.
+pub async fn delete_word_alignment(
+    r2: &R2Client,
+    user_id: &str,
+    video_id: &str,
+    scene_id: u32,
+) -> StorageResult<()> {
+    let key = word_alignment_cache_key(user_id, video_id, scene_id);
+    r2.delete_object(&key).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vclip_models::{AlignedWord, AlignmentMethod};
+
+    #[test]
+    fn test_word_alignment_cache_key() {
+        let key = word_alignment_cache_key("user123", "video456", 7);
+        assert_eq!(key, "user123/video456/neural/7.words.json.gz");
+    }
+
+    #[test]
+    fn test_compress_decompress_roundtrip() {
+        let alignment = SceneWordAlignment::new(
+            "video",
+            2,
+            5.0,
+            35.0,
+            AlignmentMethod::Ctc,
+            (0..50)
+                .map(|i| AlignedWord::new("word", i as f64 * 0.4, i as f64 * 0.4 + 0.3))
+                .collect(),
+        );
+
+        let compressed = compress_word_alignment(&alignment).unwrap();
+        let decompressed = decompress_word_alignment(&compressed).unwrap();
+        assert_eq!(decompressed.words.len(), 50);
+        assert_eq!(decompressed.scene_id, 2);
+    }
+
+    #[test]
+    fn test_decompress_outdated_version() {
+        let mut alignment =
+            SceneWordAlignment::new("v", 1, 0.0, 1.0, AlignmentMethod::VadProportional, vec![]);
+        alignment.alignment_version = WORD_ALIGNMENT_VERSION + 1;
+        let compressed = compress_word_alignment(&alignment).unwrap();
+        assert!(decompress_word_alignment(&compressed).is_none());
+    }
+
+    #[test]
+    fn test_decompress_corrupt_data() {
+        assert!(decompress_word_alignment(b"not gzip").is_none());
+    }
+}
@@ -0,0 +1,33 @@
+//! One-off retention sweep.
+//!
+//! Reports what the scheduled sweep would delete; pass `--apply` to delete.
+//! Uses the same policy overrides as the worker (`WORKER_RETENTION_OVERRIDES`).
+
+use vclip_firestore::FirestoreClient;
+use vclip_storage::R2Client;
+use vclip_worker::retention::RetentionSweeper;
+use vclip_worker::WorkerConfig;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    rustls::crypto::ring::default_provider()
+        .install_default()
+        .map_err(|_| anyhow::anyhow!("failed to install rustls crypto provider"))?;
+    dotenvy::dotenv().ok();
+
+    let apply = std::env::args().skip(1).any(|arg| arg == "--apply");
+    let config = WorkerConfig::from_env();
+
+    let firestore = FirestoreClient::from_env().await?;
+    let storage = R2Client::from_env().await?;
+    let sweeper = RetentionSweeper::new(
+        firestore,
+        storage,
+        config.retention_overrides.as_deref(),
+        !apply,
+    )?;
+
+    let report = sweeper.sweep_all().await?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
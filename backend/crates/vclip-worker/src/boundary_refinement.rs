@@ -0,0 +1,235 @@
+//! Highlight boundary refinement before rendering.
+//!
+//! The raw segment for a scene always covers the *original* padded window, so
+//! the raw segment cache stays valid across refinements. Refinement snaps the
+//! highlight edges (within `WorkerConfig::boundary_snap_tolerance_secs`) to a
+//! sentence break, VAD silence or shot cut found in that segment, records the
+//! original and refined timestamps on the Firestore highlight, and trims the
+//! raw segment to the refined window.
+//!
+//! Snapped edges are used as-is; edges that stay put keep their padding.
+//! Refinement runs once per highlight: later renders reuse the stored result.
+//! Failures are never fatal; the scene renders from the untrimmed segment.
+
+use std::path::{Path, PathBuf};
+
+use tracing::{debug, info, warn};
+
+use vclip_media::boundaries::{
+    detect_media_candidates, refine_boundaries, sentence_breaks_from_transcript, trim_segment,
+    BoundaryCandidates, BoundaryRefinementConfig, RefinedBoundaries,
+};
+use vclip_media::intelligent::parse_timestamp;
+use vclip_models::{EncodingConfig, Highlight, VideoId};
+use vclip_storage::{load_transcript, transcript_cache_id_from_url};
+
+use crate::processor::EnhancedProcessingContext;
+use crate::scene_renderer::format_timestamp;
+
+/// Windows closer than this to the raw window are rendered untrimmed (seconds).
+const TRIM_EPSILON_SECS: f64 = 0.05;
+
+/// Segment trimmed to refined highlight boundaries.
+#[derive(Debug, Clone)]
+pub struct RefinedSegment {
+    /// Trimmed segment
+    pub path: PathBuf,
+    /// Window start in source time (seconds)
+    pub start: f64,
+    /// Window end in source time (seconds)
+    pub end: f64,
+}
+
+/// Where refinement reads from and writes to.
+pub struct RefinementSource<'a> {
+    /// Owner of the video
+    pub user_id: &'a str,
+    /// Video ID (for persisting the refinement)
+    pub video_id: &'a VideoId,
+    /// Source video URL used to locate the cached transcript
+    pub video_url: Option<&'a str>,
+    /// Raw segment covering `raw_window`
+    pub raw_segment: &'a Path,
+    /// Source-time window of `raw_segment`
+    pub raw_window: (f64, f64),
+    /// Padding kept before an unsnapped start (seconds)
+    pub pad_before: f64,
+    /// Padding kept after an unsnapped end (seconds)
+    pub pad_after: f64,
+    /// Directory for the trimmed segment
+    pub work_dir: &'a Path,
+}
+
+/// Refine a scene's boundaries and trim its raw segment accordingly.
+///
+/// Returns `None` when the scene should render from the untrimmed raw segment.
+pub async fn refine_scene_segment(
+    ctx: &EnhancedProcessingContext,
+    highlight: &Highlight,
+    source: &RefinementSource<'_>,
+) -> Option<RefinedSegment> {
+    let refined = match stored_refinement(highlight) {
+        Some(refined) => refined,
+        None => {
+            let config = BoundaryRefinementConfig::default()
+                .with_tolerance(ctx.config.boundary_snap_tolerance_secs);
+            if !config.is_enabled() {
+                return None;
+            }
+            let refined = compute_refinement(ctx, highlight, source, &config).await?;
+            persist_refinement(ctx, highlight, source, &refined, config.tolerance_secs).await;
+            refined
+        }
+    };
+
+    if !refined.changed() {
+        return None;
+    }
+
+    let (raw_start, raw_end) = source.raw_window;
+    let start = if refined.start_snap.is_snapped() {
+        refined.start
+    } else {
+        refined.start - source.pad_before
+    }
+    .clamp(raw_start, raw_end);
+    let end = if refined.end_snap.is_snapped() {
+        refined.end
+    } else {
+        refined.end + source.pad_after
+    }
+    .clamp(start, raw_end);
+
+    if (start - raw_start).abs() < TRIM_EPSILON_SECS && (end - raw_end).abs() < TRIM_EPSILON_SECS {
+        return None;
+    }
+
+    let path = source
+        .work_dir
+        .join(format!("raw_{}_refined.mp4", highlight.id));
+    if !path.exists() {
+        if let Err(e) = trim_segment(
+            source.raw_segment,
+            &path,
+            start - raw_start,
+            end - raw_start,
+            &EncodingConfig::default(),
+        )
+        .await
+        {
+            warn!(
+                scene_id = highlight.id,
+                error = %e,
+                "Failed to trim segment to refined boundaries, using raw segment"
+            );
+            return None;
+        }
+    }
+
+    Some(RefinedSegment { path, start, end })
+}
+
+/// Refinement already stored on the highlight, if any.
+fn stored_refinement(highlight: &Highlight) -> Option<RefinedBoundaries> {
+    let refinement = highlight.refinement.as_ref()?;
+    Some(RefinedBoundaries {
+        start: parse_timestamp(&highlight.start).ok()?,
+        end: parse_timestamp(&highlight.end).ok()?,
+        start_snap: refinement.start_snap,
+        end_snap: refinement.end_snap,
+    })
+}
+
+/// Collect candidates and snap the highlight's original edges.
+async fn compute_refinement(
+    ctx: &EnhancedProcessingContext,
+    highlight: &Highlight,
+    source: &RefinementSource<'_>,
+    config: &BoundaryRefinementConfig,
+) -> Option<RefinedBoundaries> {
+    let start = parse_timestamp(highlight.original_start()).ok()?;
+    let end = parse_timestamp(highlight.original_end()).ok()?;
+    let (raw_start, raw_end) = source.raw_window;
+
+    let sentence_breaks = match source.video_url.filter(|u| !u.is_empty()) {
+        Some(url) => {
+            let cache_id = transcript_cache_id_from_url(url);
+            match load_transcript(&ctx.storage, source.user_id, &cache_id).await {
+                Some(transcript) => {
+                    sentence_breaks_from_transcript(&transcript, raw_start, raw_end)
+                }
+                None => Vec::new(),
+            }
+        }
+        None => Vec::new(),
+    };
+
+    let candidates: BoundaryCandidates =
+        detect_media_candidates(source.raw_segment, raw_start, config)
+            .await
+            .with_sentence_breaks(sentence_breaks);
+
+    let refined = refine_boundaries(start, end, &candidates, config);
+    info!(
+        scene_id = highlight.id,
+        original_start = start,
+        original_end = end,
+        refined_start = refined.start,
+        refined_end = refined.end,
+        start_snap = %refined.start_snap,
+        end_snap = %refined.end_snap,
+        "Refined highlight boundaries"
+    );
+    Some(refined)
+}
+
+/// Store the refinement on the Firestore highlight (non-critical).
+///
+/// Unchanged results are stored too, so the analysis is not repeated.
+async fn persist_refinement(
+    ctx: &EnhancedProcessingContext,
+    highlight: &Highlight,
+    source: &RefinementSource<'_>,
+    refined: &RefinedBoundaries,
+    tolerance_secs: f64,
+) {
+    let start_ts = if refined.start_snap.is_snapped() {
+        format_timestamp(refined.start)
+    } else {
+        highlight.original_start().to_string()
+    };
+    let end_ts = if refined.end_snap.is_snapped() {
+        format_timestamp(refined.end)
+    } else {
+        highlight.original_end().to_string()
+    };
+
+    let repo = vclip_firestore::HighlightsRepository::new(ctx.firestore.clone(), source.user_id);
+    let result = repo
+        .update_highlight(source.video_id, highlight.id, |h| {
+            // Another render may have refined (or the user edited) in the meantime
+            if h.refinement.is_none() {
+                h.apply_refinement(
+                    start_ts.clone(),
+                    end_ts.clone(),
+                    refined.start_snap,
+                    refined.end_snap,
+                    tolerance_secs,
+                );
+            }
+        })
+        .await;
+
+    match result {
+        Ok(Some(_)) => debug!(scene_id = highlight.id, "Stored highlight refinement"),
+        Ok(None) => debug!(
+            scene_id = highlight.id,
+            "Highlight vanished before refinement was stored"
+        ),
+        Err(e) => warn!(
+            scene_id = highlight.id,
+            error = %e,
+            "Failed to store highlight refinement (non-critical)"
+        ),
+    }
+}
@@ -0,0 +1,318 @@
+//! User-requested job cancellation.
+//!
+//! The API sets a cancellation flag next to the job's status cache
+//! (`ProgressChannel::request_cancel`). While an orchestration job runs, the
+//! executor polls that flag and drops the job's future once it is set. FFmpeg
+//! and yt-dlp processes are spawned with `kill_on_drop`, and background
+//! downloads and analyses live in task sets that abort on drop, so dropping
+//! the future stops all in-flight work.
+//!
+//! Render and neural analysis jobs fanned out from a parent job follow the
+//! parent's flag: they are skipped once it is set, and stop their work via
+//! [`run_unless_cancelled`] so they still release their guards and locks.
+//!
+//! [`finish_cancelled_job`] then removes partial outputs from the work
+//! directory (unless another running job shares it), refunds reserved credits for clips that were not delivered and
+//! marks the job as cancelled. A cancelled first run leaves the video
+//! cancelled; a cancelled reprocess returns it to its previous status. Clips
+//! finished before the request are kept. A final `Cancelled` progress event
+//! closes open progress streams and is delivered to `job.cancelled` webhooks.
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::{debug, info, warn};
+
+use vclip_firestore::{ClipRepository, VideoRepository};
+use vclip_models::{ClipStatus, DetectionTier, JobId, VideoId, VideoStatus};
+use vclip_queue::{ProgressChannel, QueueJob, RenderSceneStyleJob, ReprocessScenesJob};
+
+use crate::cinematic_analysis;
+use crate::credits::{cancellation_refund, refund_cancelled_credits};
+use crate::error::{WorkerError, WorkerResult};
+use crate::processor::EnhancedProcessingContext;
+
+/// How often running jobs check for a cancellation request.
+pub const CANCEL_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Whether cancellation of `job_id` has been requested.
+///
+/// Redis errors are treated as "not requested" so a flaky connection never
+/// stops a job.
+pub async fn is_cancel_requested(progress: &ProgressChannel, job_id: &JobId) -> bool {
+    match progress.is_cancel_requested(job_id).await {
+        Ok(requested) => requested,
+        Err(e) => {
+            debug!("Failed to check cancellation of job {}: {}", job_id, e);
+            false
+        }
+    }
+}
+
+/// Resolve once cancellation of `job_id` is requested.
+pub async fn wait_for_cancel(progress: &ProgressChannel, job_id: &JobId) {
+    let mut ticker = tokio::time::interval(CANCEL_POLL_INTERVAL);
+    loop {
+        ticker.tick().await;
+        if is_cancel_requested(progress, job_id).await {
+            return;
+        }
+    }
+}
+
+/// Run `work` until it finishes or `cancel_id` is cancelled.
+///
+/// Returns a cancelled error in the latter case. Fanned-out jobs wrap their
+/// work in this instead of being dropped by the executor, so the code around
+/// it still runs.
+pub async fn run_unless_cancelled<T>(
+    progress: &ProgressChannel,
+    cancel_id: Option<&JobId>,
+    work: impl Future<Output = WorkerResult<T>>,
+) -> WorkerResult<T> {
+    let Some(cancel_id) = cancel_id else {
+        return work.await;
+    };
+
+    tokio::select! {
+        result = work => result,
+        _ = wait_for_cancel(progress, cancel_id) => {
+            Err(WorkerError::cancelled(format!("parent job {} was cancelled", cancel_id)))
+        }
+    }
+}
+
+/// Clean up after a cancelled job and mark it cancelled.
+///
+/// `started_at` is when this attempt started; clips completed since then
+/// count as delivered when refunding reserved credits.
+pub async fn finish_cancelled_job(
+    ctx: &EnhancedProcessingContext,
+    job: &QueueJob,
+    started_at: DateTime<Utc>,
+) {
+    let job_id = job.job_id();
+
+    if let Some(work_dir) = job_work_dir(ctx, job) {
+        // The directory is per video; another job may still be using it
+        if video_has_other_active_job(ctx, job).await {
+            info!(
+                "Keeping work dir {:?} of cancelled job {}: another job for the video is running",
+                work_dir, job_id
+            );
+        } else if let Err(e) = tokio::fs::remove_dir_all(&work_dir).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove work dir {:?} of cancelled job {}: {}", work_dir, job_id, e);
+            }
+        }
+    }
+
+    if let QueueJob::ReprocessScenes(j) = job {
+        refund_undelivered(ctx, j, started_at).await;
+    }
+
+    match job {
+        QueueJob::ProcessVideo(j) => {
+            let video_repo = VideoRepository::new(ctx.firestore.clone(), &j.user_id);
+            if let Err(e) = video_repo.update_status(&j.video_id, VideoStatus::Cancelled).await {
+                warn!("Failed to mark video {} as cancelled: {}", j.video_id, e);
+            }
+        }
+        QueueJob::ReprocessScenes(j) => restore_video_status(ctx, &j.user_id, &j.video_id).await,
+        _ => {}
+    }
+    if let Some(video_id) = job.video_id() {
+        let video_repo = VideoRepository::new(ctx.firestore.clone(), job.user_id());
+        if let Err(e) = video_repo.clear_progress(video_id).await {
+            debug!("Failed to clear progress of video {}: {}", video_id, e);
+        }
+    }
+
+    if let Err(e) = ctx.progress.cancel_job_status(job_id).await {
+        warn!("Failed to mark job {} as cancelled: {}", job_id, e);
+    }
+    if let Err(e) = ctx.progress.clear_cancel(job_id).await {
+        debug!("Failed to clear cancellation flag of job {}: {}", job_id, e);
+    }
+    ctx.progress.log(job_id, "Job cancelled").await.ok();
+    // Final event: closes progress streams and fires the job.cancelled webhook
+    if let Err(e) = ctx
+        .progress
+        .cancelled(job_id, job.video_id().map(|v| v.as_str()))
+        .await
+    {
+        warn!("Failed to publish cancellation of job {}: {}", job_id, e);
+    }
+
+    info!("Job {} cancelled", job_id);
+}
+
+/// Clean up after a job whose parent job was cancelled.
+///
+/// Refunds the credits reserved for an undelivered render and re-opens a
+/// pending cinematic analysis, so later renders of the scene queue it again.
+/// The first child to stop also closes the parent job.
+pub async fn finish_cancelled_child(ctx: &EnhancedProcessingContext, job: &QueueJob) {
+    let Some(parent_id) = job.parent_job_id() else {
+        return;
+    };
+
+    match job {
+        QueueJob::RenderSceneStyle(j) => refund_cancelled_render(ctx, j).await,
+        QueueJob::NeuralAnalysis(j) if j.detection_tier == DetectionTier::Cinematic => {
+            if let Err(e) =
+                cinematic_analysis::reset_analysis_status(ctx, j.video_id.as_str(), j.scene_id).await
+            {
+                warn!(
+                    "Failed to reset cinematic analysis of scene {} after cancellation: {}",
+                    j.scene_id, e
+                );
+            }
+        }
+        _ => {}
+    }
+
+    let parent_open = match ctx.progress.get_job_status(parent_id).await {
+        Ok(status) => status.is_some_and(|s| !s.is_terminal()),
+        Err(e) => {
+            debug!("Failed to get status of cancelled job {}: {}", parent_id, e);
+            false
+        }
+    };
+    if parent_open {
+        if let Err(e) = ctx.progress.cancel_job_status(parent_id).await {
+            warn!("Failed to mark job {} as cancelled: {}", parent_id, e);
+        }
+        ctx.progress.log(parent_id, "Job cancelled").await.ok();
+        if let Err(e) = ctx
+            .progress
+            .cancelled(parent_id, job.video_id().map(|v| v.as_str()))
+            .await
+        {
+            warn!("Failed to publish cancellation of job {}: {}", parent_id, e);
+        }
+        info!("Job {} cancelled", parent_id);
+    }
+}
+
+/// Put a reprocessed video back to the status it had before the job.
+///
+/// The video keeps the clips it already had plus any delivered before the
+/// cancellation, so it is completed when it has clips and analyzed otherwise.
+async fn restore_video_status(ctx: &EnhancedProcessingContext, user_id: &str, video_id: &VideoId) {
+    let clip_repo = ClipRepository::new(ctx.firestore.clone(), user_id, video_id.clone());
+    let completed = match clip_repo.list(Some(ClipStatus::Completed)).await {
+        Ok(clips) => clips.len() as u32,
+        Err(e) => {
+            warn!("Failed to list clips of video {} after cancellation: {}", video_id, e);
+            return;
+        }
+    };
+
+    let video_repo = VideoRepository::new(ctx.firestore.clone(), user_id);
+    let result = if completed > 0 {
+        video_repo.complete(video_id, completed).await
+    } else {
+        video_repo.update_status(video_id, VideoStatus::Analyzed).await
+    };
+    if let Err(e) = result {
+        warn!("Failed to restore status of video {} after cancellation: {}", video_id, e);
+    }
+}
+
+/// Local work directory of an orchestration job.
+fn job_work_dir(ctx: &EnhancedProcessingContext, job: &QueueJob) -> Option<PathBuf> {
+    match job {
+        QueueJob::ProcessVideo(j) => Some(PathBuf::from(&ctx.config.work_dir).join(j.video_id.as_str())),
+        QueueJob::ReprocessScenes(j) => Some(PathBuf::from(&ctx.config.work_dir).join(j.video_id.as_str())),
+        _ => None,
+    }
+}
+
+/// Whether another running job works on the same video as `job`.
+///
+/// Errs on the side of keeping the work directory when the active job set
+/// can't be read.
+async fn video_has_other_active_job(ctx: &EnhancedProcessingContext, job: &QueueJob) -> bool {
+    let Some(video_id) = job.video_id() else {
+        return false;
+    };
+
+    match ctx.progress.get_active_jobs().await {
+        Ok(active) => active.iter().any(|status| {
+            status.video_id == video_id.as_str()
+                && status.job_id != job.job_id().as_str()
+                && !status.is_terminal()
+        }),
+        Err(e) => {
+            warn!("Failed to list active jobs for cancelled job {}: {}", job.job_id(), e);
+            true
+        }
+    }
+}
+
+/// Refund the reserved credits of clips the job did not deliver.
+async fn refund_undelivered(
+    ctx: &EnhancedProcessingContext,
+    job: &ReprocessScenesJob,
+    started_at: DateTime<Utc>,
+) {
+    if job.credits_reserved == 0 {
+        return;
+    }
+
+    let total = (job.scene_ids.len() * job.styles.len()) as u32;
+    let styles: Vec<String> = job.styles.iter().map(|s| s.to_string()).collect();
+    let clip_repo = ClipRepository::new(ctx.firestore.clone(), &job.user_id, job.video_id.clone());
+    let delivered = match clip_repo.list(Some(ClipStatus::Completed)).await {
+        Ok(clips) => clips
+            .iter()
+            .filter(|c| job.scene_ids.contains(&c.scene_id) && styles.contains(&c.style))
+            .filter(|c| c.completed_at.is_some_and(|t| t >= started_at))
+            .count() as u32,
+        Err(e) => {
+            // Without the clip list, refund everything rather than overcharge
+            warn!("Failed to list clips of cancelled job {}: {}", job.job_id, e);
+            0
+        }
+    };
+
+    let refund = cancellation_refund(job.credits_reserved, total, delivered);
+    if refund == 0 {
+        return;
+    }
+
+    match refund_cancelled_credits(
+        &ctx.firestore,
+        &job.user_id,
+        job.video_id.as_str(),
+        refund,
+        delivered,
+        total,
+    )
+    .await
+    {
+        Ok(()) => info!(
+            "Refunded {} of {} credits for cancelled job {} ({}/{} clips delivered)",
+            refund, job.credits_reserved, job.job_id, delivered, total
+        ),
+        Err(e) => warn!("Failed to refund credits for cancelled job {}: {}", job.job_id, e),
+    }
+}
+
+/// Refund the credits reserved for a render that was cancelled before delivery.
+async fn refund_cancelled_render(ctx: &EnhancedProcessingContext, job: &RenderSceneStyleJob) {
+    let credits = job.style.credit_cost();
+    if credits == 0 {
+        return;
+    }
+
+    match refund_cancelled_credits(&ctx.firestore, &job.user_id, job.video_id.as_str(), credits, 0, 1)
+        .await
+    {
+        Ok(()) => info!("Refunded {} credits for cancelled render job {}", credits, job.job_id),
+        Err(e) => warn!("Failed to refund credits for cancelled render job {}: {}", job.job_id, e),
+    }
+}
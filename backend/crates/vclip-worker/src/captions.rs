@@ -0,0 +1,178 @@
+//! Caption word resolution for rendered clips.
+//!
+//! Burned-in captions need word timings relative to the rendered segment.
+//! This module loads the cached transcript for the source video, cuts out the
+//! padded scene window, force-aligns the words against the raw segment audio
+//! (cached per scene in R2), and remaps timings through silence removal when
+//! the segment was shortened.
+//!
+//! Caption failures are never fatal: on any error the clip renders without
+//! captions, or with estimated timings when alignment alone fails.
+
+use std::path::Path;
+
+use tracing::{debug, info, warn};
+
+use vclip_media::alignment::{align_transcript, AlignmentConfig};
+use vclip_media::captions::{estimate_word_timings, remap_words_through_segments};
+use vclip_media::silence_removal::Segment;
+use vclip_models::{CaptionWord, SceneWordAlignment};
+use vclip_storage::{
+    load_transcript, load_word_alignment, store_word_alignment, transcript_cache_id_from_url,
+};
+
+use crate::processor::EnhancedProcessingContext;
+
+/// Where caption words for a scene come from.
+pub struct CaptionSource<'a> {
+    /// Video ID (for the per-scene alignment cache)
+    pub video_id: &'a str,
+    /// Scene ID (for the per-scene alignment cache)
+    pub scene_id: u32,
+    /// Source video URL used to locate the cached transcript
+    pub video_url: Option<&'a str>,
+    /// Padded segment start in source time (seconds)
+    pub window_start: f64,
+    /// Padded segment end in source time (seconds)
+    pub window_end: f64,
+    /// Raw segment before any silence removal
+    pub raw_segment: &'a Path,
+    /// Keep/Cut segments silence removal cut the raw segment with, if it was applied
+    pub kept_segments: Option<&'a [Segment]>,
+}
+
+/// Resolve timed caption words for a rendered segment.
+///
+/// Returns an empty list when no transcript is cached or nothing is spoken in the window.
+pub async fn resolve_caption_words(
+    ctx: &EnhancedProcessingContext,
+    user_id: &str,
+    source: &CaptionSource<'_>,
+) -> Vec<CaptionWord> {
+    let Some(video_url) = source.video_url.filter(|u| !u.is_empty()) else {
+        debug!("No source URL for caption transcript lookup");
+        return Vec::new();
+    };
+
+    let cache_id = transcript_cache_id_from_url(video_url);
+    let Some(transcript) = load_transcript(&ctx.storage, user_id, &cache_id).await else {
+        warn!(cache_id = %cache_id, "No cached transcript for captions, rendering without");
+        return Vec::new();
+    };
+
+    let estimated = estimate_word_timings(&transcript, source.window_start, source.window_end);
+    if estimated.is_empty() {
+        return estimated;
+    }
+
+    let words = match get_or_align_words(ctx, user_id, source, &estimated).await {
+        Some(aligned) => aligned,
+        None => estimated,
+    };
+
+    match source.kept_segments {
+        Some(segments) => remap_words_through_segments(&words, segments),
+        None => words,
+    }
+}
+
+/// Load cached word alignment for the scene, or align and cache it.
+///
+/// Returns `None` when alignment fails, so callers can fall back to estimates.
+async fn get_or_align_words(
+    ctx: &EnhancedProcessingContext,
+    user_id: &str,
+    source: &CaptionSource<'_>,
+    estimated: &[CaptionWord],
+) -> Option<Vec<CaptionWord>> {
+    if let Some(cached) =
+        load_word_alignment(&ctx.storage, user_id, source.video_id, source.scene_id).await
+    {
+        if cached.matches_window(source.window_start, source.window_end) {
+            debug!(scene_id = source.scene_id, "Using cached word alignment");
+            return Some(cached.to_caption_words());
+        }
+    }
+
+    let text = estimated
+        .iter()
+        .map(|w| w.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // Alignment is CPU-bound model inference; share the neural analysis budget
+    let _permit = ctx.neural_semaphore.acquire().await.ok()?;
+    let result =
+        match align_transcript(source.raw_segment, &text, &AlignmentConfig::default()).await {
+            Ok(result) => result,
+            Err(e) => {
+                warn!(
+                    scene_id = source.scene_id,
+                    error = %e,
+                    "Word alignment failed, using estimated timings"
+                );
+                return None;
+            }
+        };
+
+    info!(
+        scene_id = source.scene_id,
+        method = ?result.method,
+        words = result.words.len(),
+        "Aligned transcript words"
+    );
+
+    let alignment = SceneWordAlignment::new(
+        source.video_id,
+        source.scene_id,
+        source.window_start,
+        source.window_end,
+        result.method,
+        result.words,
+    )
+    .with_user(user_id);
+
+    match store_word_alignment(
+        &ctx.storage,
+        user_id,
+        source.video_id,
+        source.scene_id,
+        &alignment,
+    )
+    .await
+    {
+        Ok(stored) => {
+            let storage_repo =
+                vclip_firestore::StorageAccountingRepository::new(ctx.firestore.clone(), user_id);
+            // An overwritten entry only changes usage by the size difference
+            let previous = stored.replaced_size.unwrap_or(0);
+            let result = match stored.compressed_size.cmp(&previous) {
+                std::cmp::Ordering::Greater => storage_repo
+                    .add_neural_cache(stored.compressed_size - previous)
+                    .await
+                    .map(|_| ()),
+                std::cmp::Ordering::Less => storage_repo
+                    .remove_neural_cache(previous - stored.compressed_size)
+                    .await
+                    .map(|_| ()),
+                std::cmp::Ordering::Equal => Ok(()),
+            };
+            if let Err(e) = result {
+                warn!(
+                    user_id = %user_id,
+                    error = %e,
+                    "Failed to update storage accounting for word alignment (non-critical)"
+                );
+            }
+        }
+        Err(e) => {
+            warn!(
+                scene_id = source.scene_id,
+                error = %e,
+                "Failed to cache word alignment (non-critical)"
+            );
+        }
+    }
+
+    Some(alignment.to_caption_words())
+}
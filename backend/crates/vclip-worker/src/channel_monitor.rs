@@ -0,0 +1,341 @@
+//! Channel monitoring: poll watched YouTube channels and analyze new uploads.
+//!
+//! Every `WorkerConfig::channel_poll_interval` one worker (elected through a
+//! Redis lock) lists the newest uploads of each enabled channel with yt-dlp
+//! and enqueues an `AnalyzeVideoJob` for every upload it has not seen yet.
+//!
+//! Plan limits apply per user: only the oldest `channels_included()` channels
+//! are polled, and uploads are only queued while the user's monthly credits
+//! cover the analysis. Uploads skipped for lack of credits stay unseen, so
+//! they are picked up once credits reset. Uploads the user already analyzed
+//! (drafts or videos with the same YouTube ID) are marked seen and skipped.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use vclip_firestore::{
+    AnalysisDraftRepository, ChannelRepository, FirestoreClient, UserCreditsRepository,
+    VideoRepository,
+};
+use vclip_models::{extract_youtube_id, AnalysisDraft, MonitoredChannel, ANALYSIS_CREDIT_COST};
+use vclip_queue::{AnalyzeVideoJob, JobQueue, QueueLane};
+
+use crate::error::{WorkerError, WorkerResult};
+use crate::user_plan::resolve_user_tier;
+
+/// Uploads listed per channel and poll (newest first).
+const UPLOADS_PER_LISTING: usize = 30;
+
+/// New uploads queued per channel and poll; the rest wait for the next poll.
+const MAX_QUEUED_PER_POLL: usize = 5;
+
+/// TTL of drafts created for new uploads (matches the API's paid-plan TTL).
+const DRAFT_TTL_DAYS: i64 = 30;
+
+/// Redis lock electing the worker that polls this round.
+const POLL_LOCK_KEY: &str = "channel_monitor:poll";
+
+/// Uploads listed for a channel.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChannelListing {
+    /// Channel name
+    pub title: Option<String>,
+    /// Published upload IDs, newest first (live and upcoming streams excluded)
+    pub video_ids: Vec<String>,
+}
+
+/// Polls monitored channels and enqueues analyses for new uploads.
+pub struct ChannelMonitor {
+    firestore: FirestoreClient,
+    queue: Arc<JobQueue>,
+    interval: Duration,
+}
+
+impl ChannelMonitor {
+    /// Create a monitor polling every `interval`.
+    pub fn new(firestore: FirestoreClient, queue: Arc<JobQueue>, interval: Duration) -> Self {
+        Self {
+            firestore,
+            queue,
+            interval,
+        }
+    }
+
+    /// Run one polling round, unless another worker holds the round's lock.
+    pub async fn poll_all(&self) -> WorkerResult<()> {
+        let lock_secs = self.interval.as_secs().saturating_sub(5).max(1);
+        if !self.queue.try_acquire_idempotency(POLL_LOCK_KEY, lock_secs).await? {
+            debug!("Channel poll already running on another worker");
+            return Ok(());
+        }
+
+        let channels = ChannelRepository::list_all_enabled(&self.firestore).await?;
+        if channels.is_empty() {
+            return Ok(());
+        }
+
+        let mut by_user: BTreeMap<String, Vec<MonitoredChannel>> = BTreeMap::new();
+        for channel in channels {
+            by_user.entry(channel.user_id.clone()).or_default().push(channel);
+        }
+
+        info!(users = by_user.len(), "Polling monitored channels");
+        for (user_id, channels) in by_user {
+            if let Err(e) = self.poll_user(&user_id, channels).await {
+                warn!(user_id = %user_id, error = %e, "Failed to poll user's channels");
+            }
+        }
+        Ok(())
+    }
+
+    /// Poll one user's channels within their plan limits.
+    async fn poll_user(&self, user_id: &str, mut channels: Vec<MonitoredChannel>) -> WorkerResult<()> {
+        let tier = resolve_user_tier(&self.firestore, user_id).await;
+        let included = tier.channels_included() as usize;
+        if included == 0 {
+            debug!(user_id = %user_id, "Plan has no channel monitoring, skipping channels");
+            return Ok(());
+        }
+        channels.sort_by_key(|c| c.created_at);
+        channels.truncate(included);
+
+        let credits_used = UserCreditsRepository::new(self.firestore.clone(), user_id)
+            .get_credits_used()
+            .await?;
+        let mut budget =
+            (tier.monthly_credits().saturating_sub(credits_used) / ANALYSIS_CREDIT_COST) as usize;
+
+        let mut known = self.analyzed_video_ids(user_id).await?;
+        let repo = ChannelRepository::new(self.firestore.clone(), user_id);
+
+        for mut channel in channels {
+            match list_channel_uploads(&channel.uploads_url(), UPLOADS_PER_LISTING).await {
+                Ok(listing) => {
+                    let queued = self
+                        .queue_new_uploads(&mut channel, &listing, &mut known, &mut budget)
+                        .await;
+                    if queued > 0 {
+                        info!(
+                            user_id = %user_id,
+                            channel = %channel.channel_url,
+                            queued,
+                            "Queued analyses for new channel uploads"
+                        );
+                    }
+                    channel.last_error = None;
+                }
+                Err(e) => {
+                    warn!(channel = %channel.channel_url, error = %e, "Failed to list channel uploads");
+                    channel.last_error = Some("Could not list channel uploads".to_string());
+                }
+            }
+
+            if let Err(e) = repo.update_poll_state(&channel).await {
+                warn!(channel_id = %channel.id, error = %e, "Failed to store channel poll state");
+            }
+        }
+        Ok(())
+    }
+
+    /// Queue analyses for unseen uploads and update the channel's poll state.
+    ///
+    /// Returns the number of analyses queued.
+    async fn queue_new_uploads(
+        &self,
+        channel: &mut MonitoredChannel,
+        listing: &ChannelListing,
+        known: &mut HashSet<String>,
+        budget: &mut usize,
+    ) -> usize {
+        let mut deferred: HashSet<&String> = HashSet::new();
+        let mut queued = 0;
+
+        for video_id in channel.new_uploads(&listing.video_ids) {
+            if known.contains(video_id) {
+                debug!(video_id = %video_id, "Upload already analyzed, skipping");
+                continue;
+            }
+            if *budget == 0 || queued >= MAX_QUEUED_PER_POLL {
+                deferred.insert(video_id);
+                continue;
+            }
+            match self.enqueue_analysis(channel, video_id).await {
+                Ok(()) => {
+                    known.insert(video_id.clone());
+                    *budget -= 1;
+                    queued += 1;
+                }
+                Err(e) => {
+                    warn!(video_id = %video_id, error = %e, "Failed to queue upload analysis");
+                    deferred.insert(video_id);
+                }
+            }
+        }
+
+        if !deferred.is_empty() {
+            debug!(
+                channel = %channel.channel_url,
+                deferred = deferred.len(),
+                "Deferring uploads to the next poll"
+            );
+        }
+        let seen: Vec<String> = listing
+            .video_ids
+            .iter()
+            .filter(|id| !deferred.contains(id))
+            .cloned()
+            .collect();
+        channel.record_seen(&seen);
+        channel.uploads_queued += queued as u32;
+        channel.last_polled_at = Some(Utc::now());
+        if listing.title.is_some() {
+            channel.title = listing.title.clone();
+        }
+        queued
+    }
+
+    /// Create a draft for an upload and enqueue its analysis.
+    async fn enqueue_analysis(&self, channel: &MonitoredChannel, video_id: &str) -> WorkerResult<()> {
+        let video_url = format!("https://www.youtube.com/watch?v={}", video_id);
+        let draft_id = Uuid::new_v4().to_string();
+
+        let mut draft = AnalysisDraft::new(&draft_id, &channel.user_id, &video_url, DRAFT_TTL_DAYS)
+            .with_request_id(Uuid::new_v4().to_string());
+        if let Some(ref prompt) = channel.prompt {
+            draft = draft.with_prompt(prompt);
+        }
+        AnalysisDraftRepository::new(self.firestore.clone(), &channel.user_id)
+            .create(&draft)
+            .await?;
+
+        // Nobody is waiting on these analyses; keep them out of the interactive lane
+        let mut job = AnalyzeVideoJob::new(&channel.user_id, &draft_id, &video_url)
+            .with_highlight_detection(channel.highlight_detection)
+            .with_lane(QueueLane::Backfill);
+        if let Some(ref prompt) = channel.prompt {
+            job = job.with_prompt(prompt);
+        }
+        self.queue.enqueue_analyze(job).await?;
+        Ok(())
+    }
+
+    /// YouTube IDs of everything the user has already analyzed.
+    async fn analyzed_video_ids(&self, user_id: &str) -> WorkerResult<HashSet<String>> {
+        let drafts = AnalysisDraftRepository::new(self.firestore.clone(), user_id)
+            .list(None)
+            .await?;
+        let videos = VideoRepository::new(self.firestore.clone(), user_id)
+            .list(None)
+            .await?;
+
+        Ok(drafts
+            .iter()
+            .map(|d| d.source_url.as_str())
+            .chain(videos.iter().map(|v| v.video_url.as_str()))
+            .filter_map(|url| extract_youtube_id(url).ok())
+            .collect())
+    }
+}
+
+/// List a channel's newest uploads with yt-dlp (flat playlist, no downloads).
+pub async fn list_channel_uploads(uploads_url: &str, limit: usize) -> WorkerResult<ChannelListing> {
+    let cookies_path = vclip_media::get_writable_cookies_path().await;
+    let playlist_end = limit.to_string();
+    let mut args = vec![
+        "--flat-playlist",
+        "--dump-single-json",
+        "--playlist-end",
+        playlist_end.as_str(),
+        "--no-warnings",
+    ];
+    if let Some(cp) = cookies_path.as_deref() {
+        args.push("--cookies");
+        args.push(cp);
+    }
+    args.push(uploads_url);
+
+    let output = tokio::process::Command::new("yt-dlp")
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| WorkerError::DownloadFailed(format!("Failed to spawn yt-dlp: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(WorkerError::DownloadFailed(format!(
+            "yt-dlp failed to list uploads: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    parse_flat_playlist(&output.stdout)
+}
+
+#[derive(Deserialize)]
+struct FlatPlaylist {
+    #[serde(default)]
+    channel: Option<String>,
+    #[serde(default)]
+    uploader: Option<String>,
+    #[serde(default)]
+    entries: Vec<FlatEntry>,
+}
+
+#[derive(Deserialize)]
+struct FlatEntry {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    live_status: Option<String>,
+}
+
+/// Parse `yt-dlp --flat-playlist --dump-single-json` output.
+fn parse_flat_playlist(json: &[u8]) -> WorkerResult<ChannelListing> {
+    let playlist: FlatPlaylist = serde_json::from_slice(json).map_err(|e| {
+        WorkerError::DownloadFailed(format!("Invalid yt-dlp playlist output: {}", e))
+    })?;
+
+    let video_ids = playlist
+        .entries
+        .into_iter()
+        // Streams are analyzed once they are regular uploads
+        .filter(|e| !matches!(e.live_status.as_deref(), Some("is_live" | "is_upcoming")))
+        .filter_map(|e| e.id)
+        .filter(|id| id.len() == 11)
+        .collect();
+
+    Ok(ChannelListing {
+        title: playlist.channel.or(playlist.uploader),
+        video_ids,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flat_playlist() {
+        let json = br#"{
+            "id": "UC123",
+            "channel": "Some Creator",
+            "entries": [
+                {"id": "aaaaaaaaaaa", "title": "Live now", "live_status": "is_live"},
+                {"id": "bbbbbbbbbbb", "title": "Newest", "live_status": null},
+                {"id": "ccccccccccc", "title": "Older"},
+                {"id": "UCnested", "title": "Not a video"}
+            ]
+        }"#;
+
+        let listing = parse_flat_playlist(json).unwrap();
+        assert_eq!(listing.title.as_deref(), Some("Some Creator"));
+        assert_eq!(listing.video_ids, vec!["bbbbbbbbbbb", "ccccccccccc"]);
+
+        assert!(parse_flat_playlist(b"not json").is_err());
+    }
+}
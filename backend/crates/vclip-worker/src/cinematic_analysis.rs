@@ -6,7 +6,7 @@
 use redis::AsyncCommands;
 use tracing::{debug, info, warn};
 use vclip_models::{
-    CinematicAnalysisStatus, DetectionTier, cinematic_analysis_key,
+    CinematicAnalysisStatus, DetectionTier, JobId, cinematic_analysis_key,
     CINEMATIC_ANALYSIS_TIMEOUT_SECS,
 };
 
@@ -92,6 +92,7 @@ pub async fn check_or_queue_analysis(
     user_id: &str,
     video_id: &str,
     scene_id: u32,
+    parent_job_id: Option<&JobId>,
 ) -> WorkerResult<bool> {
     let status = get_analysis_status(ctx, video_id, scene_id).await?;
     
@@ -146,7 +147,7 @@ pub async fn check_or_queue_analysis(
             set_analysis_status(ctx, video_id, scene_id, &in_progress).await?;
             
             // Queue analysis job (neural analysis with Cinematic tier)
-            queue_cinematic_analysis_job(ctx, user_id, video_id, scene_id).await?;
+            queue_cinematic_analysis_job(ctx, user_id, video_id, scene_id, parent_job_id).await?;
             
             // Job should be rescheduled by caller
             Ok(false)
@@ -174,6 +175,7 @@ async fn queue_cinematic_analysis_job(
     user_id: &str,
     video_id: &str,
     scene_id: u32,
+    parent_job_id: Option<&JobId>,
 ) -> WorkerResult<()> {
     let Some(ref queue) = ctx.job_queue else {
         warn!("No job queue available, cannot queue cinematic analysis job");
@@ -199,6 +201,7 @@ async fn queue_cinematic_analysis_job(
         scene_id,
         source_hint_r2_key: source_hint,
         detection_tier: DetectionTier::Cinematic,
+        parent_job_id: parent_job_id.cloned(),
         created_at: chrono::Utc::now(),
     };
     
@@ -248,6 +251,27 @@ pub async fn mark_analysis_failed(
     set_analysis_status(ctx, video_id, scene_id, &status).await
 }
 
+/// Reset cinematic analysis to not started (e.g. after its job was cancelled).
+pub async fn reset_analysis_status(
+    ctx: &EnhancedProcessingContext,
+    video_id: &str,
+    scene_id: u32,
+) -> WorkerResult<()> {
+    let key = cinematic_analysis_key(video_id, scene_id);
+
+    let mut conn = ctx
+        .redis
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(|e| WorkerError::queue_failed(format!("Redis connection failed: {}", e)))?;
+
+    conn.del::<_, ()>(&key)
+        .await
+        .map_err(|e| WorkerError::queue_failed(format!("Redis DEL failed: {}", e)))?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
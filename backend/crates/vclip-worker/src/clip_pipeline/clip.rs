@@ -214,6 +214,9 @@ pub async fn process_single_clip_with_raw_key(
         }
     }
 
+    // Keep face analysis for caption placement (the request is consumed by the processor)
+    let caption_analysis = request.cached_neural_analysis.clone();
+
     if crate::watermark_check::user_requires_watermark(&ctx.firestore, user_id).await {
         request = request.with_watermark(vclip_media::WatermarkConfig::default());
     }
@@ -267,11 +270,77 @@ pub async fn process_single_clip_with_raw_key(
         e
     })?;
 
+    // Burned-in captions run as a post-pass so every style gets them
+    let mut final_file_size_bytes = result.file_size_bytes;
+    if let Some(options) = &task.captions {
+        if task.caption_words.is_empty() {
+            debug!(
+                scene_id = scene_id,
+                style = %style_name,
+                "Captions requested but no words resolved, skipping caption pass"
+            );
+        } else {
+            let config = vclip_media::CaptionConfig::for_style(
+                options.clone(),
+                task.style,
+                task.streamer_split_params.as_ref(),
+                caption_analysis.as_deref(),
+            );
+            match vclip_media::apply_captions(
+                &result.output_path,
+                &task.caption_words,
+                &config,
+                &encoding_for_style(task.style),
+            )
+            .await
+            {
+                Ok(()) => {
+                    if let Ok(meta) = tokio::fs::metadata(&result.output_path).await {
+                        final_file_size_bytes = meta.len();
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        scene_id = scene_id,
+                        style = %style_name,
+                        error = %e,
+                        "Caption burn-in failed (non-critical) - continuing without captions"
+                    );
+                }
+            }
+        }
+    }
+
+    // Audio stage (cleanup, loudness normalization, limiter) on the final render
+    let mut loudness = None;
+    if let Some(audio) = &task.audio {
+        match vclip_media::apply_audio_stage(
+            &result.output_path,
+            audio,
+            &encoding_for_style(task.style),
+        )
+        .await
+        {
+            Ok(report) => {
+                loudness = report;
+                if let Ok(meta) = tokio::fs::metadata(&result.output_path).await {
+                    final_file_size_bytes = meta.len();
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    scene_id = scene_id,
+                    style = %style_name,
+                    error = %e,
+                    "Audio stage failed (non-critical) - continuing with original audio"
+                );
+            }
+        }
+    }
+
     // Stage 3: Render complete
     emit_progress!(ClipProcessingStep::RenderComplete, None);
 
-    let final_file_size_bytes = result.file_size_bytes;
-
     // Stage 4: Uploading
     emit_progress!(ClipProcessingStep::Uploading, Some(filename.clone()));
 
@@ -333,6 +402,21 @@ pub async fn process_single_clip_with_raw_key(
         None
     };
 
+    // Platform export variants are transcoded from the uploaded master
+    let variants = if task.export_profiles.is_empty() {
+        Vec::new()
+    } else {
+        super::variants::render_clip_variants(
+            ctx,
+            task,
+            &result.output_path,
+            &filename,
+            user_id,
+            video_id,
+        )
+        .await
+    };
+
     // Stage 5: Uploaded
     emit_progress!(ClipProcessingStep::UploadComplete, Some(filename.clone()));
 
@@ -380,6 +464,8 @@ pub async fn process_single_clip_with_raw_key(
         r2_key,
         thumbnail_r2_key: thumb_key,
         raw_r2_key, // Set atomically during creation when provided
+        variants,
+        loudness,
         status: vclip_models::ClipStatus::Completed,
         created_at: chrono::Utc::now(),
         completed_at: Some(chrono::Utc::now()),
@@ -401,15 +487,15 @@ pub async fn process_single_clip_with_raw_key(
         WorkerError::Firestore(e)
     })?;
 
+    // Variants are stored alongside the clip and count towards its storage
+    let total_size_bytes = clip_meta.total_size_bytes();
+
     // Update video's total size (fire and forget - non-critical)
     let video_repo = vclip_firestore::VideoRepository::new(ctx.firestore.clone(), user_id);
-    if let Err(e) = video_repo
-        .add_clip_size(video_id, final_file_size_bytes)
-        .await
-    {
+    if let Err(e) = video_repo.add_clip_size(video_id, total_size_bytes).await {
         tracing::warn!(
             video_id = %video_id,
-            size_bytes = final_file_size_bytes,
+            size_bytes = total_size_bytes,
             error = %e,
             "Failed to update video total size (non-critical)"
         );
@@ -425,12 +511,12 @@ pub async fn process_single_clip_with_raw_key(
         ctx.firestore.clone(),
         user_id,
     );
-    if let Err(e) = storage_repo.add_styled_clip(final_file_size_bytes).await {
+    if let Err(e) = storage_repo.add_styled_clip(total_size_bytes).await {
         tracing::error!(
             user_id = %user_id,
             video_id = %video_id,
             clip_id = %clip_meta.clip_id,
-            size_bytes = final_file_size_bytes,
+            size_bytes = total_size_bytes,
             error = %e,
             error_type = "storage_accounting_drift",
             "CRITICAL: Failed to update storage accounting after all retries - quota tracking may be inaccurate"
@@ -448,6 +534,7 @@ pub async fn process_single_clip_with_raw_key(
         filename = %filename,
         duration_sec = result.duration_seconds,
         file_size_mb = final_file_size_bytes as f64 / (1024.0 * 1024.0),
+        variants = clip_meta.variants.len(),
         "Clip processing completed successfully"
     );
 
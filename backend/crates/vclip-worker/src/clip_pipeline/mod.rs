@@ -13,6 +13,7 @@ use crate::processor::{AnalysisData, EnhancedProcessingContext};
 pub mod clip;
 pub mod scene;
 pub mod tasks;
+mod variants;
 
 pub use clip::{process_single_clip, process_single_clip_with_raw_key};
 pub use scene::process_scene;
@@ -30,12 +31,20 @@ pub async fn process_clips(
     work_dir: &Path,
     analysis: &AnalysisData,
 ) -> WorkerResult<ClipProcessingResults> {
-    let clip_tasks = tasks::generate_clip_tasks(
+    let clip_tasks: Vec<_> = tasks::generate_clip_tasks(
         &analysis.highlights,
         &job.styles,
         &job.crop_mode,
         &job.target_aspect,
-    );
+        job.captions.clone(),
+    )
+    .into_iter()
+    .map(|task| {
+        task.with_output_format(job.target_aspect, job.resolution)
+            .with_export_profiles(&job.export_profiles)
+            .with_audio(job.audio.clone())
+    })
+    .collect();
     let total_clips = clip_tasks.len();
     let video_repo = VideoRepository::new(ctx.firestore.clone(), &job.user_id);
     if let Err(e) = video_repo
@@ -1,6 +1,9 @@
-use vclip_models::{sanitize_filename_title, AspectRatio, ClipTask, CropMode, Style};
+use vclip_models::{
+    sanitize_filename_title, AspectRatio, CaptionOptions, ClipTask, CropMode, ResolutionPreset,
+    Style,
+};
 
-use crate::gemini::HighlightsResponse;
+use vclip_highlights::HighlightsResponse;
 
 /// Generate clip tasks from highlights and styles.
 ///
@@ -10,6 +13,7 @@ pub fn generate_clip_tasks(
     styles: &[Style],
     crop_mode: &CropMode,
     target_aspect: &AspectRatio,
+    captions: Option<CaptionOptions>,
 ) -> Vec<ClipTask> {
     let mut tasks = Vec::new();
 
@@ -24,12 +28,17 @@ pub fn generate_clip_tasks(
                 style: *style,
                 crop_mode: *crop_mode,
                 target_aspect: *target_aspect,
+                resolution: ResolutionPreset::default(),
                 priority: highlight.id, // Use highlight ID as priority
                 pad_before: highlight.pad_before_seconds,
                 pad_after: highlight.pad_after_seconds,
                 streamer_split_params: None,
                 streamer_params: None,
                 cut_silent_parts: true,
+                captions: captions.clone(),
+                caption_words: Vec::new(),
+                export_profiles: Vec::new(),
+                audio: None,
             };
             tasks.push(task);
         }
@@ -45,6 +54,7 @@ pub fn generate_clip_tasks_from_highlights(
     styles: &[Style],
     crop_mode: &CropMode,
     target_aspect: &AspectRatio,
+    captions: Option<CaptionOptions>,
 ) -> Vec<ClipTask> {
     let mut tasks = Vec::new();
 
@@ -59,12 +69,17 @@ pub fn generate_clip_tasks_from_highlights(
                 style: *style,
                 crop_mode: *crop_mode,
                 target_aspect: *target_aspect,
+                resolution: ResolutionPreset::default(),
                 priority: highlight.id,
                 pad_before: highlight.pad_before_seconds,
                 pad_after: highlight.pad_after_seconds,
                 streamer_split_params: None,
                 streamer_params: None,
                 cut_silent_parts: true,
+                captions: captions.clone(),
+                caption_words: Vec::new(),
+                export_profiles: Vec::new(),
+                audio: None,
             });
         }
     }
@@ -103,6 +118,7 @@ pub fn generate_clip_tasks_from_firestore_highlights_with_params(
         target_aspect,
         streamer_split_params,
         true, // cut_silent_parts default
+        None,
     )
 }
 
@@ -114,6 +130,7 @@ pub fn generate_clip_tasks_from_firestore_highlights_full(
     target_aspect: &AspectRatio,
     streamer_split_params: Option<vclip_models::StreamerSplitParams>,
     cut_silent_parts: bool,
+    captions: Option<CaptionOptions>,
 ) -> Vec<ClipTask> {
     let mut tasks = Vec::new();
 
@@ -135,12 +152,17 @@ pub fn generate_clip_tasks_from_firestore_highlights_full(
                 style: *style,
                 crop_mode: *crop_mode,
                 target_aspect: *target_aspect,
+                resolution: ResolutionPreset::default(),
                 priority: highlight.id,
                 pad_before: highlight.pad_before,
                 pad_after: highlight.pad_after,
                 streamer_split_params: params,
                 streamer_params: None,
                 cut_silent_parts,
+                captions: captions.clone(),
+                caption_words: Vec::new(),
+                export_profiles: Vec::new(),
+                audio: None,
             });
         }
     }
@@ -148,3 +170,43 @@ pub fn generate_clip_tasks_from_firestore_highlights_full(
     tasks
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vclip_highlights::HighlightCandidate;
+    use vclip_models::CaptionPreset;
+
+    #[test]
+    fn test_generate_clip_tasks_carries_captions() {
+        let highlights = HighlightsResponse {
+            video_url: None,
+            video_title: None,
+            highlights: vec![HighlightCandidate {
+                id: 1,
+                title: "Opening".to_string(),
+                start: "00:00:10".to_string(),
+                end: "00:00:40".to_string(),
+                duration: 30,
+                pad_before_seconds: 1.0,
+                pad_after_seconds: 1.0,
+                hook_category: None,
+                reason: None,
+                description: None,
+                confidence: None,
+            }],
+        };
+        let captions = CaptionOptions::new(CaptionPreset::Karaoke);
+
+        let tasks = generate_clip_tasks(
+            &highlights,
+            &[Style::Split, Style::LeftFocus],
+            &CropMode::default(),
+            &AspectRatio::default(),
+            Some(captions.clone()),
+        );
+
+        assert_eq!(tasks.len(), 2);
+        assert!(tasks.iter().all(|task| task.captions.as_ref() == Some(&captions)));
+    }
+}
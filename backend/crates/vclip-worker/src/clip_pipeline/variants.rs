@@ -0,0 +1,85 @@
+//! Platform export variants for a finished clip.
+//!
+//! Each export profile on the task is transcoded from the rendered master and
+//! uploaded next to it. Variant failures are never fatal: the master clip is
+//! still saved, just without the failed variant.
+
+use std::path::Path;
+
+use tracing::{info, warn};
+use vclip_media::render_export_variant;
+use vclip_models::{ClipTask, ClipVariant, VideoId};
+
+use crate::processor::EnhancedProcessingContext;
+
+/// Render and upload every export variant requested on `task`.
+///
+/// Local variant files are removed after upload.
+pub(super) async fn render_clip_variants(
+    ctx: &EnhancedProcessingContext,
+    task: &ClipTask,
+    master_path: &Path,
+    master_filename: &str,
+    user_id: &str,
+    video_id: &VideoId,
+) -> Vec<ClipVariant> {
+    let mut variants = Vec::with_capacity(task.export_profiles.len());
+
+    for &profile in &task.export_profiles {
+        let filename = profile.variant_filename(master_filename);
+        let output_path = master_path.with_file_name(&filename);
+
+        let rendered =
+            match render_export_variant(master_path, &output_path, profile, task.target_aspect)
+                .await
+            {
+                Ok(rendered) => rendered,
+                Err(e) => {
+                    warn!(
+                        scene_id = task.scene_id,
+                        profile = %profile,
+                        error = %e,
+                        "Export variant render failed (non-critical) - skipping variant"
+                    );
+                    let _ = tokio::fs::remove_file(&output_path).await;
+                    continue;
+                }
+            };
+
+        let upload = ctx
+            .storage
+            .upload_clip(&output_path, user_id, video_id.as_str(), &filename)
+            .await;
+        let _ = tokio::fs::remove_file(&output_path).await;
+
+        match upload {
+            Ok(r2_key) => {
+                info!(
+                    scene_id = task.scene_id,
+                    profile = %profile,
+                    size_bytes = rendered.file_size_bytes,
+                    "Uploaded export variant"
+                );
+                variants.push(ClipVariant {
+                    profile,
+                    filename,
+                    r2_key,
+                    file_size_bytes: rendered.file_size_bytes,
+                    duration_seconds: rendered.duration_seconds,
+                    width: rendered.width,
+                    height: rendered.height,
+                });
+            }
+            Err(e) => {
+                warn!(
+                    scene_id = task.scene_id,
+                    profile = %profile,
+                    error = %e,
+                    "Failed to upload export variant (non-critical) - skipping variant"
+                );
+            }
+        }
+    }
+
+    variants
+}
@@ -35,6 +35,30 @@ pub struct WorkerConfig {
     pub claim_min_idle: Duration,
     /// Interval for refreshing job ownership while processing (prevents premature reclamation)
     pub job_heartbeat_interval: Duration,
+    /// Maximum distance (seconds) highlight edges may be snapped to a
+    /// sentence break, silence or shot cut before rendering (0 disables)
+    pub boundary_snap_tolerance_secs: f64,
+    /// How often monitored channels are polled for new uploads (0 disables)
+    pub channel_poll_interval: Duration,
+    /// Whether this worker delivers outbound webhooks
+    pub webhooks_enabled: bool,
+    /// Directory of Twitch/Kick chat replay exports, named `{platform}-{kind}-{id}.json`
+    /// (e.g. `twitch-vod-2101234567.json`); chat signals are skipped when unset
+    pub chat_replay_dir: Option<String>,
+    /// Target length of recorded live stream segments in seconds
+    pub live_segment_secs: u32,
+    /// Length of the trailing window highlight detection runs on during a live capture
+    pub live_window_secs: u32,
+    /// How often a live capture analyzes its newest window
+    pub live_analysis_interval: Duration,
+    /// Longest a single live capture may record before it is stopped
+    pub live_max_duration: Duration,
+    /// How often expired cache artifacts and drafts are swept (0 disables)
+    pub retention_sweep_interval: Duration,
+    /// Report what the retention sweep would delete without deleting it
+    pub retention_dry_run: bool,
+    /// Per-plan retention overrides, e.g. `pro.raw_segment=14d,free.transcript=72h`
+    pub retention_overrides: Option<String>,
 }
 
 impl Default for WorkerConfig {
@@ -53,6 +77,17 @@ impl Default for WorkerConfig {
             claim_interval: Duration::from_secs(30),
             claim_min_idle: Duration::from_secs(300), // 5 minutes
             job_heartbeat_interval: Duration::from_secs(30),
+            boundary_snap_tolerance_secs: 1.0, // Matches the default highlight padding
+            channel_poll_interval: Duration::from_secs(900), // 15 minutes
+            webhooks_enabled: true,
+            chat_replay_dir: None,
+            live_segment_secs: 30,
+            live_window_secs: 300, // 5 minutes
+            live_analysis_interval: Duration::from_secs(120),
+            live_max_duration: Duration::from_secs(6 * 3600), // 6 hours
+            retention_sweep_interval: Duration::from_secs(6 * 3600), // 6 hours
+            retention_dry_run: false,
+            retention_overrides: None,
         }
     }
 }
@@ -117,6 +152,52 @@ impl WorkerConfig {
                     .and_then(|s| s.parse().ok())
                     .unwrap_or(30),
             ),
+            boundary_snap_tolerance_secs: std::env::var("WORKER_BOUNDARY_SNAP_TOLERANCE_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(1.0),
+            channel_poll_interval: Duration::from_secs(
+                std::env::var("WORKER_CHANNEL_POLL_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(900),
+            ),
+            webhooks_enabled: std::env::var("WORKER_WEBHOOKS_ENABLED")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(true),
+            chat_replay_dir: std::env::var("WORKER_CHAT_REPLAY_DIR").ok(),
+            live_segment_secs: std::env::var("WORKER_LIVE_SEGMENT_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
+            live_window_secs: std::env::var("WORKER_LIVE_WINDOW_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(300),
+            live_analysis_interval: Duration::from_secs(
+                std::env::var("WORKER_LIVE_ANALYSIS_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(120),
+            ),
+            live_max_duration: Duration::from_secs(
+                std::env::var("WORKER_LIVE_MAX_DURATION_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(6 * 3600),
+            ),
+            retention_sweep_interval: Duration::from_secs(
+                std::env::var("WORKER_RETENTION_SWEEP_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(6 * 3600),
+            ),
+            retention_dry_run: std::env::var("WORKER_RETENTION_DRY_RUN")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(false),
+            retention_overrides: std::env::var("WORKER_RETENTION_OVERRIDES").ok(),
         }
     }
 }
@@ -1,7 +1,8 @@
 //! Credit management utilities for the worker.
 //!
 //! This module provides functions to charge credits after successful job completion.
-//! Credits are charged only on success, not upfront.
+//! Credits are charged only on success, not upfront. Reprocessing credits are
+//! reserved by the API; the undelivered share is refunded when a job is cancelled.
 //!
 //! Uses the shared `UserCreditsRepository` from `vclip-firestore` to avoid
 //! duplicating credit logic between API and worker.
@@ -71,6 +72,63 @@ pub async fn charge_analysis_credits(
     Ok(())
 }
 
+/// Share of a reservation to refund when a job is cancelled part-way.
+///
+/// Delivered clips keep their share of the reserved credits (rounded down, in
+/// the user's favour); everything else is returned.
+pub fn cancellation_refund(reserved: u32, total_clips: u32, delivered_clips: u32) -> u32 {
+    if total_clips == 0 {
+        return reserved;
+    }
+    let kept = u64::from(reserved) * u64::from(delivered_clips.min(total_clips))
+        / u64::from(total_clips);
+    reserved - kept as u32
+}
+
+/// Refund credits reserved for a cancelled reprocessing or render job.
+///
+/// Does nothing if the reservation fell in a previous billing month.
+pub async fn refund_cancelled_credits(
+    firestore: &FirestoreClient,
+    user_id: &str,
+    video_id: &str,
+    credits: u32,
+    delivered_clips: u32,
+    total_clips: u32,
+) -> WorkerResult<()> {
+    let credits_repo = UserCreditsRepository::new(firestore.clone(), user_id);
+    let Some(result) = credits_repo
+        .refund_credits(credits)
+        .await
+        .map_err(WorkerError::Firestore)?
+    else {
+        return Ok(());
+    };
+
+    let mut metadata = HashMap::new();
+    metadata.insert("delivered_clips".to_string(), delivered_clips.to_string());
+    metadata.insert("total_clips".to_string(), total_clips.to_string());
+
+    let tx = CreditTransaction::new(
+        uuid::Uuid::new_v4().to_string(),
+        user_id.to_string(),
+        CreditOperationType::Refund,
+        credits,
+        "Refund for cancelled processing".to_string(),
+        result.credits_used_after,
+    )
+    .with_optional_video_id(Some(video_id.to_string()))
+    .with_optional_metadata(Some(metadata));
+
+    if let Err(e) = CreditTransactionRepository::new(firestore.clone(), user_id)
+        .create(&tx)
+        .await
+    {
+        warn!(user_id = %user_id, error = %e, "Failed to record refund credit transaction");
+    }
+    Ok(())
+}
+
 // =============================================================================
 // Transaction Recording
 // =============================================================================
@@ -161,3 +219,22 @@ fn record_analysis_transaction(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancellation_refund() {
+        // Nothing delivered: full refund
+        assert_eq!(cancellation_refund(30, 6, 0), 30);
+        // Half delivered: half refunded
+        assert_eq!(cancellation_refund(30, 6, 3), 15);
+        // Rounding favours the user
+        assert_eq!(cancellation_refund(10, 3, 1), 7);
+        // Everything delivered: nothing to refund
+        assert_eq!(cancellation_refund(30, 6, 6), 0);
+        assert_eq!(cancellation_refund(30, 6, 9), 0);
+        assert_eq!(cancellation_refund(30, 0, 0), 30);
+    }
+}
@@ -33,6 +33,9 @@ pub enum WorkerError {
     #[error("Reschedule: {0}")]
     Reschedule(String),
 
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+
     #[error("Storage error: {0}")]
     Storage(#[from] vclip_storage::StorageError),
 
@@ -49,6 +52,15 @@ pub enum WorkerError {
     Io(#[from] std::io::Error),
 }
 
+impl From<vclip_highlights::HighlightError> for WorkerError {
+    fn from(e: vclip_highlights::HighlightError) -> Self {
+        match e {
+            vclip_highlights::HighlightError::NotConfigured(msg) => Self::ConfigError(msg),
+            other => Self::AiFailed(other.to_string()),
+        }
+    }
+}
+
 impl WorkerError {
     pub fn job_failed(msg: impl Into<String>) -> Self {
         Self::JobFailed(msg.into())
@@ -82,6 +94,11 @@ impl WorkerError {
         Self::Reschedule(msg.into())
     }
 
+    /// Create a cancelled error - the job's parent was cancelled by the user.
+    pub fn cancelled(msg: impl Into<String>) -> Self {
+        Self::Cancelled(msg.into())
+    }
+
     /// Check if error is retryable.
     pub fn is_retryable(&self) -> bool {
         matches!(
@@ -99,6 +116,11 @@ impl WorkerError {
         matches!(self, WorkerError::Reschedule(_))
     }
 
+    /// Check if the job stopped because it was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, WorkerError::Cancelled(_))
+    }
+
     /// Check if error is a quota exceeded error (not retryable, user action needed).
     pub fn is_quota_exceeded(&self) -> bool {
         matches!(self, WorkerError::QuotaExceeded(_))
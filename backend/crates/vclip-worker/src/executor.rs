@@ -9,11 +9,15 @@ use tokio::sync::Semaphore;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use vclip_queue::{JobQueue, QueueJob};
+use vclip_queue::{JobQueue, QueueJob, WebhookQueue};
 
+use crate::cancellation;
+use crate::channel_monitor::ChannelMonitor;
 use crate::config::WorkerConfig;
 use crate::error::{WorkerError, WorkerResult};
 use crate::processor::{EnhancedProcessingContext, VideoProcessor};
+use crate::retention::RetentionSweeper;
+use crate::webhooks::WebhookDispatcher;
 
 /// Job executor that processes jobs from the queue.
 pub struct JobExecutor {
@@ -152,6 +156,100 @@ impl JobExecutor {
             }
         });
 
+        // Spawn a task to poll monitored channels for new uploads
+        let channel_task = (!self.config.channel_poll_interval.is_zero()).then(|| {
+            let monitor = ChannelMonitor::new(
+                ctx.firestore.clone(),
+                Arc::clone(&self.queue),
+                self.config.channel_poll_interval,
+            );
+            let poll_interval = self.config.channel_poll_interval;
+            let mut shutdown_rx_channels = self.shutdown.subscribe();
+
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(poll_interval);
+                loop {
+                    tokio::select! {
+                        _ = shutdown_rx_channels.changed() => {
+                            if *shutdown_rx_channels.borrow() {
+                                break;
+                            }
+                        }
+                        _ = interval.tick() => {
+                            if let Err(e) = monitor.poll_all().await {
+                                warn!("Failed to poll monitored channels: {}", e);
+                            }
+                        }
+                    }
+                }
+            })
+        });
+
+        // Spawn a task to sweep expired cache artifacts and drafts
+        let retention_task = if self.config.retention_sweep_interval.is_zero() {
+            None
+        } else {
+            let sweeper = RetentionSweeper::new(
+                ctx.firestore.clone(),
+                ctx.storage.clone(),
+                self.config.retention_overrides.as_deref(),
+                self.config.retention_dry_run,
+            )?;
+            let queue = Arc::clone(&self.queue);
+            let sweep_interval = self.config.retention_sweep_interval;
+            let mut shutdown_rx_retention = self.shutdown.subscribe();
+
+            Some(tokio::spawn(async move {
+                let mut interval = tokio::time::interval(sweep_interval);
+                loop {
+                    tokio::select! {
+                        _ = shutdown_rx_retention.changed() => {
+                            if *shutdown_rx_retention.borrow() {
+                                break;
+                            }
+                        }
+                        _ = interval.tick() => {
+                            if let Err(e) = sweeper.run_scheduled(&queue, sweep_interval).await {
+                                warn!("Retention sweep failed: {}", e);
+                            }
+                        }
+                    }
+                }
+            }))
+        };
+
+        // Spawn a task to deliver outbound webhooks
+        let webhook_task = if self.config.webhooks_enabled {
+            let dispatcher = WebhookDispatcher::new(
+                ctx.firestore.clone(),
+                ctx.progress.clone(),
+                WebhookQueue::from_env()?,
+                self.consumer_name.clone(),
+            )?;
+            dispatcher.init().await?;
+            let mut shutdown_rx_webhooks = self.shutdown.subscribe();
+
+            Some(tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        _ = shutdown_rx_webhooks.changed() => {
+                            if *shutdown_rx_webhooks.borrow() {
+                                break;
+                            }
+                        }
+                        result = dispatcher.run_once() => {
+                            if let Err(e) = result {
+                                warn!("Webhook dispatch failed: {}", e);
+                                tokio::time::sleep(Duration::from_secs(5)).await;
+                            }
+                        }
+                    }
+                }
+            }))
+        } else {
+            None
+        };
+
         // Main job consumption loop
         loop {
             tokio::select! {
@@ -174,6 +272,15 @@ impl JobExecutor {
         // Wait for claim task to finish
         claim_task.abort();
         scheduled_task.abort();
+        if let Some(task) = channel_task {
+            task.abort();
+        }
+        if let Some(task) = retention_task {
+            task.abort();
+        }
+        if let Some(task) = webhook_task {
+            task.abort();
+        }
 
         // Wait for in-flight jobs to complete
         info!("Waiting for in-flight jobs to complete...");
@@ -241,6 +348,7 @@ impl JobExecutor {
         consumer_name: String,
     ) {
         let job_id = job.job_id().to_string();
+        let started_at = chrono::Utc::now();
         info!("Executing job {}", job_id);
 
         // Heartbeat to keep the job "alive" for long-running processing so Redis
@@ -309,15 +417,55 @@ impl JobExecutor {
             }
         });
 
-        let result = Self::process_job(Arc::clone(&ctx), job.clone(), video_processor).await;
+        // Orchestration jobs can be cancelled by the user; dropping the job
+        // future kills its child processes and aborts its background tasks.
+        // Jobs fanned out from a parent follow its cancellation themselves
+        // (see `cancellation::run_unless_cancelled`) so they can release what
+        // they hold, and return a cancelled error.
+        let result = if let Some(parent_id) = job.parent_job_id() {
+            if cancellation::is_cancel_requested(&ctx.progress, parent_id).await {
+                Some(Err(WorkerError::cancelled(format!("parent job {} was cancelled", parent_id))))
+            } else {
+                Some(Self::process_job(Arc::clone(&ctx), job.clone(), video_processor).await)
+            }
+        } else if !job.is_orchestration() {
+            Some(Self::process_job(Arc::clone(&ctx), job.clone(), video_processor).await)
+        } else if cancellation::is_cancel_requested(&ctx.progress, job.job_id()).await {
+            None
+        } else {
+            if let Err(e) = ctx.progress.start_job_status(job.job_id()).await {
+                debug!("Failed to mark job {} as processing: {}", job_id, e);
+            }
+            tokio::select! {
+                result = Self::process_job(Arc::clone(&ctx), job.clone(), video_processor) => Some(result),
+                _ = cancellation::wait_for_cancel(&ctx.progress, job.job_id()) => None,
+            }
+        };
 
         // Stop both heartbeat tasks
         heartbeat_task.abort();
         progress_heartbeat_task.abort();
 
+        let Some(result) = result else {
+            // Cancelled work is never retried or charged
+            if let Err(e) = queue.ack(&message_id).await {
+                error!("Failed to ack cancelled job {}: {}", job_id, e);
+            }
+            if let Err(e) = queue.clear_dedup(&job).await {
+                warn!("Failed to clear dedup key for job {}: {}", job_id, e);
+            }
+            cancellation::finish_cancelled_job(&ctx, &job, started_at).await;
+            return;
+        };
+
         match result {
             Ok(()) => {
                 info!("Job {} completed successfully", job_id);
+                if job.is_orchestration() {
+                    if let Err(e) = ctx.progress.complete_job_status(job.job_id()).await {
+                        debug!("Failed to mark job {} as completed: {}", job_id, e);
+                    }
+                }
                 if let Err(e) = queue.ack(&message_id).await {
                     error!("Failed to ack job {}: {}", job_id, e);
                 }
@@ -326,6 +474,17 @@ impl JobExecutor {
                     warn!("Failed to clear dedup key for job {}: {}", job_id, e);
                 }
             }
+            Err(ref e) if e.is_cancelled() => {
+                info!("Job {} stopped: {}", job_id, e);
+                // Cancelled work is never retried or charged
+                if let Err(e) = queue.ack(&message_id).await {
+                    error!("Failed to ack cancelled job {}: {}", job_id, e);
+                }
+                if let Err(e) = queue.clear_dedup(&job).await {
+                    warn!("Failed to clear dedup key for job {}: {}", job_id, e);
+                }
+                cancellation::finish_cancelled_child(&ctx, &job).await;
+            }
             Err(ref e) if e.is_reschedule() => {
                 // Special handling for reschedule errors (analysis-first pattern)
                 // ACK the original message, clear dedup, and re-enqueue with delay
@@ -439,6 +598,12 @@ impl JobExecutor {
                         }
                     }
 
+                    if job.is_orchestration() {
+                        if let Err(e) = ctx.progress.fail_job_status(job.job_id(), &error_msg).await {
+                            debug!("Failed to mark job {} as failed: {}", job_id, e);
+                        }
+                    }
+
                     // Emit error to progress channel
                     ctx.progress.error(job.job_id(), error_msg).await.ok();
                 } else {
@@ -446,6 +611,11 @@ impl JobExecutor {
                         "Job {} will be retried (attempt {}/{})",
                         job_id, retry_count, max_retries
                     );
+                    if job.is_orchestration() {
+                        if let Err(e) = ctx.progress.requeue_job_status(job.job_id()).await {
+                            debug!("Failed to mark job {} as queued: {}", job_id, e);
+                        }
+                    }
                     // Job will be redelivered after visibility timeout
                 }
             }
@@ -509,6 +679,10 @@ impl JobExecutor {
                 // Fine-grained job: render a single (scene, style) clip
                 video_processor.process_render_job(&ctx, &j).await
             }
+            QueueJob::CaptureLive(j) => {
+                // Long-running job: record a live stream and publish scenes as it goes
+                crate::live_capture::process_capture_live_job(&ctx, &j, &video_processor).await
+            }
         }
     }
 }
@@ -8,6 +8,10 @@
 //! - Graceful shutdown
 //! - New modular architecture with security and performance
 
+pub mod boundary_refinement;
+pub mod cancellation;
+pub mod captions;
+pub mod channel_monitor;
 pub mod clip_pipeline;
 pub mod cinematic_analysis;
 pub mod cinematic_signals;
@@ -17,7 +21,7 @@ pub mod download_source_job;
 pub mod download_coordinator;
 pub mod error;
 pub mod executor;
-pub mod gemini;
+pub mod live_capture;
 pub mod logging;
 pub mod neural_analysis_job;
 pub mod neural_cache;
@@ -25,16 +29,21 @@ pub mod processor;
 pub mod raw_segment_cache;
 pub mod render_job;
 pub mod reprocessing;
+pub mod retention;
 pub mod retry;
 pub mod scene_analysis;
 pub mod scene_renderer;
+pub mod scene_scoring;
+pub mod signal_highlights;
 pub mod silence_cache;
 pub mod source_download;
 pub mod source_video_coordinator;
 pub mod top_scenes;
 pub mod transcript;
 pub mod user_plan;
+pub mod video_metadata;
 pub mod watermark_check;
+pub mod webhooks;
 
 pub use config::WorkerConfig;
 pub use error::{WorkerError, WorkerResult};
@@ -0,0 +1,587 @@
+//! Live-stream rolling capture job processing.
+//!
+//! Records a running broadcast into segments, uploads each finished segment
+//! to R2 with a manifest, and periodically runs highlight detection on the
+//! newest window of footage. Scenes found in a window are published to the
+//! analysis draft right away, so they can be rendered while the stream is
+//! still going.
+//!
+//! The capture stops when the stream ends, when the user stops it (through
+//! the job's cancellation flag) or when it reaches the configured maximum
+//! duration. It then analyzes the remaining footage and completes the draft.
+//! A retried job resumes the manifest instead of recording from scratch.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use tracing::{info, warn};
+
+use vclip_firestore::AnalysisDraftRepository;
+use vclip_media::{
+    concat_segments, format_transcript, is_transcription_available, transcribe_file,
+    LiveRecorder, LiveRecorderConfig, TranscriptionConfig,
+};
+use vclip_models::{
+    format_seconds, parse_timestamp, DraftScene, HighlightDetection, LiveCapture,
+    LiveCaptureManifest, LiveCaptureMode, LiveCaptureStatus, LiveSegment,
+};
+use vclip_queue::CaptureLiveJob;
+use vclip_storage::{
+    live_manifest_key, live_segment_key, load_live_manifest, store_live_manifest,
+    LIVE_SEGMENT_CONTENT_TYPE,
+};
+
+use crate::cancellation::is_cancel_requested;
+use crate::error::{WorkerError, WorkerResult};
+use crate::logging::JobLogger;
+use crate::processor::{
+    draft_scene_from_candidate, load_prompt_from_file, EnhancedProcessingContext, VideoProcessor,
+    DEFAULT_PROMPT,
+};
+
+/// How often the recorder is checked for finished segments.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Least new footage (seconds) worth a periodic analysis pass.
+const MIN_NEW_FOOTAGE_SECS: f64 = 30.0;
+
+/// A segment recorded by this attempt, still on local disk.
+#[derive(Debug, Clone)]
+struct LocalSegment {
+    segment: LiveSegment,
+    path: PathBuf,
+}
+
+/// State of a running capture.
+struct LiveSession<'a> {
+    ctx: &'a EnhancedProcessingContext,
+    processor: &'a VideoProcessor,
+    job: &'a CaptureLiveJob,
+    draft_repo: AnalysisDraftRepository,
+    live: LiveCapture,
+    manifest: LiveCaptureManifest,
+    /// Timeline offset of this attempt (footage recorded by earlier attempts)
+    offset_secs: f64,
+    /// Segments of this attempt already uploaded
+    uploaded: usize,
+    local: Vec<LocalSegment>,
+    /// Start and end (seconds) of every published scene
+    published: Vec<(f64, f64)>,
+    next_scene_id: u32,
+    base_prompt: String,
+    work_dir: PathBuf,
+    windows_analyzed: u32,
+}
+
+/// Process a live capture job.
+pub async fn process_capture_live_job(
+    ctx: &EnhancedProcessingContext,
+    job: &CaptureLiveJob,
+    processor: &VideoProcessor,
+) -> WorkerResult<()> {
+    let logger = JobLogger::new(&job.job_id, "capture_live");
+    logger.log_start(&format!("Capturing live stream {}", job.video_url));
+
+    let draft_repo = AnalysisDraftRepository::new(ctx.firestore.clone(), &job.user_id);
+    let draft = draft_repo
+        .get(&job.draft_id)
+        .await?
+        .ok_or_else(|| WorkerError::job_failed(format!("Draft {} not found", job.draft_id)))?;
+
+    let manifest_key = live_manifest_key(&job.user_id, &job.draft_id);
+    let mut live = draft.live_capture.unwrap_or_else(|| {
+        LiveCapture::new(job.job_id.to_string(), job.capture_mode, manifest_key.clone())
+    });
+    let manifest = load_live_manifest(&ctx.storage, &live.manifest_r2_key)
+        .await?
+        .unwrap_or_default();
+
+    let existing = draft_repo.get_scenes(&job.draft_id).await?;
+    let published: Vec<(f64, f64)> = existing.iter().filter_map(scene_bounds).collect();
+    let next_scene_id = existing.iter().map(|s| s.id).max().map_or(1, |id| id + 1);
+
+    let resumed = !manifest.segments.is_empty();
+    if resumed {
+        info!(
+            draft_id = %job.draft_id,
+            segments = manifest.segments.len(),
+            captured_secs = manifest.end_secs(),
+            "Resuming live capture"
+        );
+    }
+
+    live.status = LiveCaptureStatus::Recording;
+    live.started_at.get_or_insert_with(Utc::now);
+    live.ended_at = None;
+    draft_repo
+        .update_live_capture(&job.draft_id, &live, published.len() as u32, None)
+        .await?;
+
+    ctx.progress
+        .log(&job.job_id, "Recording live stream...")
+        .await
+        .ok();
+
+    let work_dir = PathBuf::from(&ctx.config.work_dir).join(&job.draft_id);
+    let segment_dir = work_dir.join("live");
+    if let Err(e) = tokio::fs::remove_dir_all(&segment_dir).await {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            warn!("Failed to clear stale live segments in {:?}: {}", segment_dir, e);
+        }
+    }
+
+    // A resumed capture continues from the live edge; recording from the
+    // start again would duplicate footage already in the manifest
+    let config = LiveRecorderConfig {
+        segment_secs: ctx.config.live_segment_secs,
+        from_start: job.capture_mode == LiveCaptureMode::FromStart && !resumed,
+    };
+    let recorder = LiveRecorder::start(&job.video_url, &segment_dir, &config).await?;
+
+    let base_prompt = job
+        .prompt_instructions
+        .clone()
+        .or_else(load_prompt_from_file)
+        .unwrap_or_else(|| DEFAULT_PROMPT.to_string());
+
+    let mut session = LiveSession {
+        ctx,
+        processor,
+        job,
+        draft_repo,
+        live,
+        offset_secs: manifest.end_secs(),
+        manifest,
+        uploaded: 0,
+        local: Vec::new(),
+        published,
+        next_scene_id,
+        base_prompt,
+        work_dir: work_dir.clone(),
+        windows_analyzed: 0,
+    };
+
+    let result = session.run(recorder).await;
+
+    if let Err(e) = ctx.progress.clear_cancel(&job.job_id).await {
+        warn!("Failed to clear cancel flag of job {}: {}", job.job_id, e);
+    }
+    if let Err(e) = tokio::fs::remove_dir_all(&work_dir).await {
+        warn!("Failed to cleanup work directory {:?}: {}", work_dir, e);
+    }
+
+    let title = result?;
+    logger.log_completion(&format!(
+        "Captured {} of '{}' with {} scenes",
+        format_seconds(session.live.captured_secs.floor()),
+        title,
+        session.published.len()
+    ));
+    Ok(())
+}
+
+impl LiveSession<'_> {
+    /// Record until the capture stops, then finish the draft.
+    ///
+    /// Returns the video title the draft was completed with.
+    async fn run(&mut self, mut recorder: LiveRecorder) -> WorkerResult<String> {
+        let started = Instant::now();
+        let interval = self.ctx.config.live_analysis_interval;
+        let mut last_analysis = Instant::now();
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+
+        let stop_reason = loop {
+            ticker.tick().await;
+
+            self.upload_finished(&recorder).await?;
+
+            if last_analysis.elapsed() >= interval
+                && self.live.captured_secs - self.live.analyzed_until_secs >= MIN_NEW_FOOTAGE_SECS
+            {
+                self.analyze_window().await;
+                last_analysis = Instant::now();
+            }
+
+            if is_cancel_requested(&self.ctx.progress, &self.job.job_id).await {
+                break "stopped by user";
+            }
+            if recorder.has_ended()? {
+                break "stream ended";
+            }
+            if started.elapsed() >= self.ctx.config.live_max_duration {
+                break "maximum capture duration reached";
+            }
+        };
+
+        let failure = recorder.failure_reason().await;
+        let finished = recorder.stop().await?;
+        self.upload_new(finished).await?;
+        info!(
+            draft_id = %self.job.draft_id,
+            reason = stop_reason,
+            captured_secs = self.live.captured_secs,
+            "Live capture stopped"
+        );
+
+        if self.manifest.segments.is_empty() {
+            return Err(WorkerError::DownloadFailed(format!(
+                "Nothing was recorded from the live stream: {}",
+                failure.unwrap_or_else(|| stop_reason.to_string())
+            )));
+        }
+
+        if self.live.captured_secs > self.live.analyzed_until_secs {
+            self.analyze_window().await;
+        }
+
+        self.finish().await
+    }
+
+    /// Upload segments the recorder has finished since the last poll.
+    async fn upload_finished(&mut self, recorder: &LiveRecorder) -> WorkerResult<()> {
+        let finished = recorder.finished_segments().await?;
+        self.upload_new(finished).await
+    }
+
+    async fn upload_new(
+        &mut self,
+        finished: Vec<vclip_media::RecordedSegment>,
+    ) -> WorkerResult<()> {
+        if finished.len() <= self.uploaded {
+            return Ok(());
+        }
+
+        let storage_repo = vclip_firestore::StorageAccountingRepository::new(
+            self.ctx.firestore.clone(),
+            &self.job.user_id,
+        );
+        for recorded in finished.into_iter().skip(self.uploaded) {
+            let size_bytes = tokio::fs::metadata(&recorded.path).await?.len();
+            let index = self.manifest.next_index();
+            let key = live_segment_key(&self.job.user_id, &self.job.draft_id, index);
+            self.ctx
+                .storage
+                .upload_file(&recorded.path, &key, LIVE_SEGMENT_CONTENT_TYPE)
+                .await?;
+
+            if let Err(e) = storage_repo.add_raw_segment(size_bytes).await {
+                warn!(
+                    user_id = %self.job.user_id,
+                    size_bytes,
+                    error = %e,
+                    "Failed to update storage accounting for live segment (non-critical)"
+                );
+            }
+
+            let segment = LiveSegment {
+                index,
+                r2_key: key,
+                start_secs: self.offset_secs + recorded.start_secs,
+                end_secs: self.offset_secs + recorded.end_secs,
+                size_bytes,
+            };
+            self.manifest.segments.push(segment.clone());
+            self.local.push(LocalSegment {
+                segment,
+                path: recorded.path,
+            });
+            self.uploaded += 1;
+        }
+
+        store_live_manifest(&self.ctx.storage, &self.live.manifest_r2_key, &self.manifest).await?;
+
+        self.live.captured_secs = self.manifest.end_secs();
+        self.live.segment_count = self.manifest.segments.len() as u32;
+        self.save_progress().await;
+        Ok(())
+    }
+
+    /// Detect highlights in the newest window and publish new scenes.
+    ///
+    /// Failures are logged and the window is skipped, so one bad pass never
+    /// ends the recording.
+    async fn analyze_window(&mut self) {
+        let window_secs = self.ctx.config.live_window_secs as f64;
+        let first = window_start(&self.local, self.live.analyzed_until_secs, window_secs);
+        let window = &self.local[first..];
+        let Some(last) = window.last() else {
+            return;
+        };
+        let window_offset = window[0].segment.start_secs;
+        let window_end = last.segment.end_secs;
+
+        let window_dir = self.work_dir.join(format!("window_{:04}", self.windows_analyzed));
+        self.windows_analyzed += 1;
+
+        match self.detect_scenes(window, &window_dir, window_offset).await {
+            Ok(scenes) => {
+                let new_scenes: Vec<DraftScene> = scenes
+                    .into_iter()
+                    .filter_map(|mut scene| {
+                        let bounds = scene_bounds(&scene)?;
+                        if overlaps_published(bounds, &self.published) {
+                            return None;
+                        }
+                        scene.id = self.next_scene_id;
+                        self.next_scene_id += 1;
+                        self.published.push(bounds);
+                        Some(scene)
+                    })
+                    .collect();
+
+                if !new_scenes.is_empty() {
+                    if let Err(e) = self.draft_repo.upsert_scenes(&self.job.draft_id, &new_scenes).await {
+                        warn!(draft_id = %self.job.draft_id, error = %e, "Failed to publish live scenes");
+                    } else {
+                        info!(
+                            draft_id = %self.job.draft_id,
+                            scenes = new_scenes.len(),
+                            window_end = window_end,
+                            "Published live scenes"
+                        );
+                        self.ctx
+                            .progress
+                            .log(
+                                &self.job.job_id,
+                                format!("Found {} new highlight(s)", new_scenes.len()),
+                            )
+                            .await
+                            .ok();
+                    }
+                }
+            }
+            Err(e) => {
+                warn!(
+                    draft_id = %self.job.draft_id,
+                    window_start = window_offset,
+                    window_end = window_end,
+                    error = %e,
+                    "Live window analysis failed, skipping window"
+                );
+            }
+        }
+
+        if let Err(e) = tokio::fs::remove_dir_all(&window_dir).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove window directory {:?}: {}", window_dir, e);
+            }
+        }
+
+        self.live.analyzed_until_secs = window_end;
+        self.prune_local(window_end - window_secs).await;
+        self.save_progress().await;
+    }
+
+    /// Join the window's segments and detect highlights on them.
+    ///
+    /// Returned scenes are on the capture timeline.
+    async fn detect_scenes(
+        &self,
+        window: &[LocalSegment],
+        window_dir: &Path,
+        window_offset: f64,
+    ) -> WorkerResult<Vec<DraftScene>> {
+        tokio::fs::create_dir_all(window_dir).await?;
+        let source = window_dir.join("source.mp4");
+        let inputs: Vec<PathBuf> = window.iter().map(|s| s.path.clone()).collect();
+        concat_segments(&inputs, &source).await?;
+
+        let mode = self.job.highlight_detection;
+        let transcript = if mode.uses_transcript() && is_transcription_available() {
+            match transcribe_file(&source, &TranscriptionConfig::default()).await {
+                Ok(segments) => format_transcript(&segments),
+                Err(e) => {
+                    warn!(error = %e, "Live window transcription failed, using signals");
+                    String::new()
+                }
+            }
+        } else {
+            String::new()
+        };
+
+        let mode = if transcript.trim().is_empty() && !mode.uses_signals() {
+            HighlightDetection::Signals
+        } else {
+            mode
+        };
+
+        let analysis = self
+            .processor
+            .detect_highlights(
+                self.ctx,
+                &self.job.user_id,
+                mode,
+                &self.base_prompt,
+                &self.job.video_url,
+                &transcript,
+                window_dir,
+            )
+            .await?;
+
+        Ok(analysis
+            .highlights
+            .iter()
+            .filter_map(|h| {
+                let mut scene = draft_scene_from_candidate(&self.job.draft_id, h, &transcript);
+                scene.start = format_seconds(parse_timestamp(&scene.start).ok()? + window_offset);
+                scene.end = format_seconds(parse_timestamp(&scene.end).ok()? + window_offset);
+                Some(scene)
+            })
+            .collect())
+    }
+
+    /// Delete local segment files that end before `before_secs`.
+    async fn prune_local(&mut self, before_secs: f64) {
+        let keep_from = self
+            .local
+            .iter()
+            .position(|s| s.segment.end_secs > before_secs)
+            .unwrap_or(self.local.len());
+        for old in self.local.drain(..keep_from) {
+            if let Err(e) = tokio::fs::remove_file(&old.path).await {
+                warn!("Failed to remove recorded segment {:?}: {}", old.path, e);
+            }
+        }
+    }
+
+    /// Persist capture progress on the draft; failures are only logged.
+    async fn save_progress(&self) {
+        if let Err(e) = self
+            .draft_repo
+            .update_live_capture(
+                &self.job.draft_id,
+                &self.live,
+                self.published.len() as u32,
+                None,
+            )
+            .await
+        {
+            warn!(draft_id = %self.job.draft_id, error = %e, "Failed to save live capture progress");
+        }
+    }
+
+    /// Mark the capture ended, complete the draft and charge for the analysis.
+    async fn finish(&mut self) -> WorkerResult<String> {
+        let title = self
+            .draft_repo
+            .get(&self.job.draft_id)
+            .await?
+            .and_then(|d| d.video_title)
+            .unwrap_or_else(|| "Live stream".to_string());
+
+        self.live.status = LiveCaptureStatus::Ended;
+        self.live.ended_at = Some(Utc::now());
+        let scene_count = self.published.len() as u32;
+        self.draft_repo
+            .update_live_capture(&self.job.draft_id, &self.live, scene_count, None)
+            .await?;
+        self.draft_repo
+            .update_completion(&self.job.draft_id, Some(title.clone()), scene_count, 0)
+            .await?;
+
+        if let Err(e) = crate::credits::charge_analysis_credits(
+            &self.ctx.firestore,
+            &self.job.user_id,
+            &self.job.draft_id,
+            &title,
+            &self.job.video_url,
+            true,
+        )
+        .await
+        {
+            warn!(
+                user_id = %self.job.user_id,
+                draft_id = %self.job.draft_id,
+                error = %e,
+                "Failed to charge analysis credits (live capture itself succeeded)"
+            );
+        }
+
+        self.ctx.progress.progress(&self.job.job_id, 100).await.ok();
+        self.ctx.progress.done(&self.job.job_id, &self.job.draft_id).await.ok();
+        Ok(title)
+    }
+}
+
+/// Start and end of a scene in seconds.
+fn scene_bounds(scene: &DraftScene) -> Option<(f64, f64)> {
+    let start = parse_timestamp(&scene.start).ok()?;
+    let end = parse_timestamp(&scene.end).ok()?;
+    (end > start).then_some((start, end))
+}
+
+/// Index of the first segment of the next analysis window.
+///
+/// The window covers the last `window_secs` of footage, extended back to
+/// `analyzed_until` so nothing recorded between passes goes unanalyzed.
+fn window_start(segments: &[LocalSegment], analyzed_until: f64, window_secs: f64) -> usize {
+    let Some(last) = segments.last() else {
+        return 0;
+    };
+    let start = (last.segment.end_secs - window_secs).min(analyzed_until);
+    segments
+        .iter()
+        .position(|s| s.segment.end_secs > start)
+        .unwrap_or(segments.len() - 1)
+}
+
+/// Whether a scene overlaps a published one by more than half of the shorter.
+///
+/// Consecutive windows overlap, so the same moment is often detected twice.
+fn overlaps_published(bounds: (f64, f64), published: &[(f64, f64)]) -> bool {
+    let (start, end) = bounds;
+    published.iter().any(|&(p_start, p_end)| {
+        let overlap = end.min(p_end) - start.max(p_start);
+        let shorter = (end - start).min(p_end - p_start);
+        overlap > 0.0 && overlap * 2.0 > shorter
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(bounds: &[(f64, f64)]) -> Vec<LocalSegment> {
+        bounds
+            .iter()
+            .enumerate()
+            .map(|(i, &(start, end))| LocalSegment {
+                segment: LiveSegment {
+                    index: i as u32,
+                    r2_key: format!("seg_{}.mp4", i),
+                    start_secs: start,
+                    end_secs: end,
+                    size_bytes: 100,
+                },
+                path: PathBuf::from(format!("seg_{}.mp4", i)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_window_start() {
+        let segments = local(&[(0.0, 30.0), (30.0, 60.0), (60.0, 90.0), (90.0, 120.0)]);
+
+        // Trailing 60s, everything before already analyzed
+        assert_eq!(window_start(&segments, 100.0, 60.0), 2);
+        // Extends back to unanalyzed footage
+        assert_eq!(window_start(&segments, 40.0, 60.0), 1);
+        assert_eq!(window_start(&segments, 0.0, 60.0), 0);
+        // Never empty
+        assert_eq!(window_start(&segments, 120.0, 0.0), 3);
+        assert_eq!(window_start(&[], 0.0, 60.0), 0);
+    }
+
+    #[test]
+    fn test_overlaps_published() {
+        let published = [(100.0, 130.0)];
+
+        assert!(overlaps_published((105.0, 135.0), &published));
+        assert!(overlaps_published((110.0, 120.0), &published));
+        // A small overlap is a different moment
+        assert!(!overlaps_published((125.0, 160.0), &published));
+        assert!(!overlaps_published((130.0, 150.0), &published));
+        assert!(!overlaps_published((10.0, 40.0), &[]));
+    }
+}
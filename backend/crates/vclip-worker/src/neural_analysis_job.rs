@@ -11,6 +11,7 @@ use vclip_media::download_video;
 use vclip_models::{DetectionTier, FrameAnalysis, SceneNeuralAnalysis};
 use vclip_queue::NeuralAnalysisJob;
 
+use crate::cancellation::run_unless_cancelled;
 use crate::error::{WorkerError, WorkerResult};
 use crate::logging::JobLogger;
 use crate::processor::EnhancedProcessingContext;
@@ -37,7 +38,13 @@ pub async fn process_neural_analysis_job(
             job.video_id.as_str(),
             job.scene_id,
             job.detection_tier,
-            || compute_neural_analysis(ctx, job),
+            || {
+                run_unless_cancelled(
+                    &ctx.progress,
+                    job.parent_job_id.as_ref(),
+                    compute_neural_analysis(ctx, job),
+                )
+            },
         )
         .await;
 
@@ -71,7 +78,8 @@ pub async fn process_neural_analysis_job(
         }
         Err(e) => {
             // Mark cinematic analysis as failed for Cinematic tier
-            if job.detection_tier == DetectionTier::Cinematic {
+            // (a cancelled analysis is re-opened by the executor instead)
+            if job.detection_tier == DetectionTier::Cinematic && !e.is_cancelled() {
                 crate::cinematic_analysis::mark_analysis_failed(
                     ctx,
                     job.video_id.as_str(),
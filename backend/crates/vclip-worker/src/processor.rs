@@ -10,20 +10,30 @@ use tokio::sync::Semaphore;
 use tracing::{debug, info, warn};
 
 use vclip_firestore::{types::ToFirestoreValue, AnalysisDraftRepository, FirestoreClient};
+use vclip_highlights::{
+    prompt::build_analysis_prompt, EnergySample, HighlightCandidate, HighlightProviderKind,
+    HighlightProviders, HighlightRequest, HighlightsResponse,
+};
 use vclip_media::{
     core::{MetricsCollector, SecurityContext, StyleProcessorRegistry},
     styles::StyleProcessorFactory as MediaStyleProcessorFactory,
+    ExcitementConfig,
+};
+use vclip_models::{
+    is_stream_platform_url, AnalysisStatus, DraftScene, HighlightDetection, VideoMetadata,
+    MAX_VIDEO_DURATION_SECS,
 };
-use vclip_models::{AnalysisStatus, DraftScene, VideoMetadata};
 use vclip_queue::{AnalyzeVideoJob, ProcessVideoJob, ProgressChannel, RenderSceneStyleJob, ReprocessScenesJob};
 use vclip_storage::{load_transcript, store_transcript, transcript_cache_id_from_url, R2Client};
 
 use crate::config::WorkerConfig;
 use crate::error::{WorkerError, WorkerResult};
-use crate::gemini::GeminiClient;
+use crate::scene_scoring::analysis_metrics;
+use crate::signal_highlights::{analyze_source_excitement, fuse_highlights, signal_highlights};
+use crate::video_metadata::fetch_video_metadata;
 
 /// Default prompt for AI analysis when no custom prompt is provided.
-const DEFAULT_PROMPT: &str = r#"You are a viral content expert. Analyze this video transcript and identify the most engaging, viral-worthy moments that would work well as short-form clips for TikTok, YouTube Shorts, or Instagram Reels.
+pub(crate) const DEFAULT_PROMPT: &str = r#"You are a viral content expert. Analyze this video transcript and identify the most engaging, viral-worthy moments that would work well as short-form clips for TikTok, YouTube Shorts, or Instagram Reels.
 
 For each highlight, provide:
 - A catchy title
@@ -39,7 +49,7 @@ Focus on moments with:
 - Memorable quotes"#;
 
 /// Try to load a custom prompt from a file.
-fn load_prompt_from_file() -> Option<String> {
+pub(crate) fn load_prompt_from_file() -> Option<String> {
     let prompt_path = std::env::var("PROMPT_FILE").ok()?;
     std::fs::read_to_string(&prompt_path).ok()
 }
@@ -160,17 +170,100 @@ impl EnhancedProcessingContext {
 /// Video processing coordinator using the new architecture.
 #[derive(Clone)]
 pub struct VideoProcessor {
-    gemini_client: Arc<GeminiClient>,
+    highlight_providers: HighlightProviders,
 }
 
 impl VideoProcessor {
     /// Create a new video processor.
     pub fn new() -> WorkerResult<Self> {
         Ok(Self {
-            gemini_client: Arc::new(GeminiClient::new()?),
+            highlight_providers: HighlightProviders::from_env()?,
         })
     }
 
+    /// Detect highlights with the requested detection mode.
+    ///
+    /// Signal detection downloads the source video into `work_dir` if needed.
+    /// In fused mode a failure on either side falls back to the other.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn detect_highlights(
+        &self,
+        ctx: &EnhancedProcessingContext,
+        user_id: &str,
+        mode: HighlightDetection,
+        base_prompt: &str,
+        video_url: &str,
+        transcript: &str,
+        work_dir: &Path,
+    ) -> WorkerResult<HighlightsResponse> {
+        let config = ExcitementConfig::default();
+        let chat_replay_dir = ctx.config.chat_replay_dir.as_deref().map(Path::new);
+
+        let excitement = if mode.uses_signals() {
+            match analyze_source_excitement(video_url, work_dir, chat_replay_dir, &config).await {
+                Ok(profile) => Some(profile),
+                Err(e) if mode.uses_transcript() => {
+                    warn!(error = %e, "Excitement analysis failed, using transcript highlights only");
+                    None
+                }
+                Err(e) => return Err(e),
+            }
+        } else {
+            None
+        };
+
+        let skip_transcript = excitement.is_some() && transcript.trim().is_empty();
+        let from_transcript = if mode.uses_transcript() && !skip_transcript {
+            match self
+                .detect_transcript_highlights(ctx, user_id, base_prompt, video_url, transcript, work_dir)
+                .await
+            {
+                Ok(response) => Some(response),
+                Err(e) if excitement.is_some() => {
+                    warn!(error = %e, "Transcript highlight detection failed, using signals only");
+                    None
+                }
+                Err(e) => return Err(e),
+            }
+        } else {
+            None
+        };
+
+        info!(mode = %mode, "Detecting highlights");
+        match (from_transcript, excitement) {
+            (Some(response), Some(profile)) => Ok(fuse_highlights(response, &profile, &config)),
+            (Some(response), None) => Ok(response),
+            (None, Some(profile)) => Ok(signal_highlights(&profile, video_url, &config)),
+            (None, None) => Err(WorkerError::ai_failed("No highlight detection method produced results")),
+        }
+    }
+
+    /// Detect highlights from the transcript with the user's preferred provider, or the default.
+    async fn detect_transcript_highlights(
+        &self,
+        ctx: &EnhancedProcessingContext,
+        user_id: &str,
+        base_prompt: &str,
+        video_url: &str,
+        transcript: &str,
+        work_dir: &Path,
+    ) -> WorkerResult<HighlightsResponse> {
+        let preferred = crate::user_plan::resolve_highlight_provider(&ctx.firestore, user_id).await;
+        let provider = self.highlight_providers.get(preferred);
+
+        let mut request = HighlightRequest::new(
+            build_analysis_prompt(base_prompt, transcript),
+            transcript,
+            video_url,
+        );
+        if provider.kind() == HighlightProviderKind::Heuristic {
+            request = request.with_audio_energy(load_audio_energy(work_dir).await);
+        }
+
+        info!(provider = %provider.kind(), "Detecting highlights");
+        Ok(provider.detect_highlights(&request).await?)
+    }
+
     /// Process a video job using the enhanced architecture.
     /// 
     /// This now only performs analysis (transcript + AI scene detection).
@@ -211,18 +304,21 @@ impl VideoProcessor {
         
         // Attempt to reuse a previously persisted plan to avoid non-determinism on retries.
         let cached_plan = tokio::fs::read(&plan_path).await.ok().and_then(|bytes| {
-            serde_json::from_slice::<crate::gemini::HighlightsResponse>(&bytes).ok()
+            serde_json::from_slice::<HighlightsResponse>(&bytes).ok()
         });
 
         let ai_response = if let Some(plan) = cached_plan {
             plan
         } else {
             let analysis_result = self
-                .gemini_client
-                .analyze_transcript(
+                .detect_highlights(
+                    ctx,
+                    &job.user_id,
+                    job.highlight_detection,
                     &transcript_data.prompt,
                     &job.video_url,
                     &transcript_data.content,
+                    &work_dir,
                 )
                 .await?;
             
@@ -340,10 +436,7 @@ impl VideoProcessor {
             return Ok(transcript);
         }
 
-        let transcript = self
-            .gemini_client
-            .get_transcript_only(video_url, work_dir)
-            .await?;
+        let transcript = crate::transcript::fetch_transcript(video_url, work_dir).await?;
 
         if let Err(e) = store_transcript(&ctx.storage, user_id, cache_id, &transcript).await {
             warn!(
@@ -376,17 +469,22 @@ impl VideoProcessor {
             .or_else(|| load_prompt_from_file())
             .unwrap_or_else(|| DEFAULT_PROMPT.to_string());
 
-        let (real_video_title, canonical_video_url) = self
-            .gemini_client
-            .get_video_metadata(&job.video_url)
+        let (real_video_title, canonical_video_url) = fetch_video_metadata(&job.video_url)
             .await
             .map_err(|e| WorkerError::ai_failed(format!("Failed to get video metadata: {}", e)))?;
 
         let cache_id = transcript_cache_id_from_url(&canonical_video_url);
-        let transcript = self
+        let transcript = match self
             .get_transcript_with_cache(ctx, &job.user_id, &cache_id, &job.video_url, work_dir)
             .await
-            .map_err(|e| WorkerError::ai_failed(format!("Failed to get transcript: {}", e)))?;
+        {
+            Ok(transcript) => transcript,
+            Err(e) if job.highlight_detection.uses_signals() => {
+                warn!(error = %e, "No transcript, detecting highlights from signals");
+                String::new()
+            }
+            Err(e) => return Err(WorkerError::ai_failed(format!("Failed to get transcript: {}", e))),
+        };
 
         Ok(TranscriptData {
             title: real_video_title,
@@ -402,7 +500,7 @@ impl VideoProcessor {
         ctx: &EnhancedProcessingContext,
         job: &ProcessVideoJob,
         transcript: &TranscriptData,
-        highlights: &crate::gemini::HighlightsResponse,
+        highlights: &HighlightsResponse,
     ) -> WorkerResult<()> {
         let video_meta = VideoMetadata::new(
             job.video_id.clone(),
@@ -446,6 +544,11 @@ impl VideoProcessor {
                     });
                     highlight.reason = h.reason.clone();
                     highlight.description = h.description.clone();
+                    highlight.set_quality(analysis_metrics(
+                        h,
+                        highlight.duration as f64,
+                        &transcript.content,
+                    ));
                     highlight
                 })
                 .collect(),
@@ -537,7 +640,8 @@ impl VideoProcessor {
     /// Process an analyze video job (new two-step workflow).
     ///
     /// Downloads transcript, analyzes with AI, and stores results as an AnalysisDraft
-    /// with DraftScenes in Firestore. Does NOT render any clips.
+    /// with DraftScenes in Firestore. Does NOT render any clips. Uploaded sources
+    /// are fetched from R2 and validated instead of resolved by URL.
     pub async fn process_analyze_job(
         &self,
         ctx: &EnhancedProcessingContext,
@@ -560,21 +664,51 @@ impl VideoProcessor {
             .ok();
         ctx.progress.progress(&job.job_id, 10).await.ok();
 
-        // Get video metadata and transcript
-        let (video_title, canonical_url) = self
-            .gemini_client
-            .get_video_metadata(&job.video_url)
-            .await
-            .map_err(|e| WorkerError::ai_failed(format!("Failed to get video metadata: {}", e)))?;
-
         let work_dir = std::path::PathBuf::from(&ctx.config.work_dir).join(&job.draft_id);
         tokio::fs::create_dir_all(&work_dir).await?;
 
+        let (video_title, canonical_url) = match job.source_r2_key.as_deref() {
+            Some(r2_key) => {
+                self.fetch_uploaded_source(ctx, job, r2_key, &work_dir).await?;
+                let title = draft_repo
+                    .get(&job.draft_id)
+                    .await
+                    .map_err(WorkerError::Firestore)?
+                    .and_then(|d| d.video_title)
+                    .unwrap_or_else(|| "Uploaded video".to_string());
+                (title, job.video_url.clone())
+            }
+            // Get video metadata
+            None => fetch_video_metadata(&job.video_url)
+                .await
+                .map_err(|e| WorkerError::ai_failed(format!("Failed to get video metadata: {}", e)))?,
+        };
+
         let cache_id = transcript_cache_id_from_url(&canonical_url);
-        let transcript = self
+        let transcript = match self
             .get_transcript_with_cache(ctx, &job.user_id, &cache_id, &job.video_url, &work_dir)
             .await
-            .map_err(|e| WorkerError::ai_failed(format!("Failed to get transcript: {}", e)))?;
+        {
+            Ok(transcript) => transcript,
+            // Uploads and stream VODs can still be analyzed from signals alone
+            Err(e)
+                if job.highlight_detection.uses_signals()
+                    || job.source_r2_key.is_some()
+                    || is_stream_platform_url(&job.video_url) =>
+            {
+                warn!(error = %e, "No transcript, detecting highlights from signals");
+                String::new()
+            }
+            Err(e) => return Err(WorkerError::ai_failed(format!("Failed to get transcript: {}", e))),
+        };
+
+        // Without a transcript only the signals can find highlights
+        let highlight_detection = if transcript.trim().is_empty() && !job.highlight_detection.uses_signals() {
+            info!("No transcript for the source, detecting highlights from signals");
+            HighlightDetection::Signals
+        } else {
+            job.highlight_detection
+        };
 
         ctx.progress.progress(&job.job_id, 30).await.ok();
 
@@ -598,8 +732,15 @@ impl VideoProcessor {
 
         // Analyze transcript
         let analysis = self
-            .gemini_client
-            .analyze_transcript(&base_prompt, &job.video_url, &transcript)
+            .detect_highlights(
+                ctx,
+                &job.user_id,
+                highlight_detection,
+                &base_prompt,
+                &job.video_url,
+                &transcript,
+                &work_dir,
+            )
             .await?;
 
         ctx.progress.progress(&job.job_id, 80).await.ok();
@@ -608,33 +749,7 @@ impl VideoProcessor {
         let scenes: Vec<DraftScene> = analysis
             .highlights
             .iter()
-            .map(|h| {
-                let computed = vclip_models::Highlight::new(
-                    h.id,
-                    "temp",
-                    h.start.clone(),
-                    h.end.clone(),
-                )
-                .with_calculated_duration();
-                DraftScene {
-                    id: h.id,
-                    analysis_draft_id: job.draft_id.clone(),
-                    title: h.title.clone(),
-                    description: h.description.clone(),
-                    reason: h.reason.clone(),
-                    start: h.start.clone(),
-                    end: h.end.clone(),
-                    duration_secs: if computed.duration == 0 {
-                        h.duration
-                    } else {
-                        computed.duration
-                    },
-                    pad_before: h.pad_before_seconds,
-                    pad_after: h.pad_after_seconds,
-                    confidence: None, // Confidence scoring not yet implemented in AI response
-                    hook_category: h.hook_category.clone(),
-                }
-            })
+            .map(|h| draft_scene_from_candidate(&job.draft_id, h, &transcript))
             .collect();
 
         let scene_count = scenes.len() as u32;
@@ -685,6 +800,47 @@ impl VideoProcessor {
 
         Ok(())
     }
+
+    /// Download an uploaded source into the work directory and check it is a usable video.
+    ///
+    /// The file lands at `work_dir/source.mp4`, where signal analysis and
+    /// audio energy scoring pick it up instead of downloading by URL.
+    async fn fetch_uploaded_source(
+        &self,
+        ctx: &EnhancedProcessingContext,
+        job: &AnalyzeVideoJob,
+        r2_key: &str,
+        work_dir: &Path,
+    ) -> WorkerResult<()> {
+        ctx.progress
+            .log(&job.job_id, "Fetching uploaded video...")
+            .await
+            .ok();
+
+        let source = work_dir.join("source.mp4");
+        ctx.storage.download_file(r2_key, &source).await?;
+
+        let size_bytes = tokio::fs::metadata(&source).await?.len();
+        ctx.security.validate_file_size(size_bytes)?;
+
+        let info = vclip_media::probe_video(&source).await.map_err(|e| {
+            WorkerError::job_failed(format!("Uploaded file is not a readable video: {}", e))
+        })?;
+        if info.duration <= 0.0 || info.duration > MAX_VIDEO_DURATION_SECS {
+            return Err(WorkerError::job_failed(format!(
+                "Uploaded video duration {:.0}s is outside the supported range",
+                info.duration
+            )));
+        }
+
+        info!(
+            r2_key = %r2_key,
+            size_bytes,
+            duration = info.duration,
+            "Fetched uploaded source video"
+        );
+        Ok(())
+    }
 }
 
 /// Data structures for processing pipeline.
@@ -697,7 +853,63 @@ struct TranscriptData {
 
 pub struct AnalysisData {
     pub video_file: PathBuf,
-    pub highlights: crate::gemini::HighlightsResponse,
+    pub highlights: HighlightsResponse,
+}
+
+/// Convert a detected highlight into a draft scene with its quality metrics.
+pub(crate) fn draft_scene_from_candidate(
+    draft_id: &str,
+    h: &HighlightCandidate,
+    transcript: &str,
+) -> DraftScene {
+    let computed = vclip_models::Highlight::new(h.id, "temp", h.start.clone(), h.end.clone())
+        .with_calculated_duration();
+    let duration_secs = if computed.duration == 0 {
+        h.duration
+    } else {
+        computed.duration
+    };
+    let quality = analysis_metrics(h, duration_secs as f64, transcript);
+    let mut scene = DraftScene {
+        id: h.id,
+        analysis_draft_id: draft_id.to_string(),
+        title: h.title.clone(),
+        description: h.description.clone(),
+        reason: h.reason.clone(),
+        start: h.start.clone(),
+        end: h.end.clone(),
+        duration_secs,
+        pad_before: h.pad_before_seconds,
+        pad_after: h.pad_after_seconds,
+        confidence: quality.llm_confidence,
+        hook_category: h.hook_category.clone(),
+        virality_score: None,
+        quality: None,
+    };
+    scene.set_quality(quality);
+    scene
+}
+
+/// Load the source audio loudness timeline, if the source video is already on disk.
+///
+/// Analysis normally runs before any download, so this is usually empty and
+/// the heuristic provider scores the transcript alone.
+async fn load_audio_energy(work_dir: &Path) -> Vec<EnergySample> {
+    let source = work_dir.join("source.mp4");
+    if !source.exists() {
+        return Vec::new();
+    }
+
+    match vclip_media::measure_loudness_timeline(&source).await {
+        Ok(timeline) => timeline
+            .into_iter()
+            .map(|(time, loudness)| EnergySample { time, loudness })
+            .collect(),
+        Err(e) => {
+            warn!(source = ?source, error = %e, "Failed to measure audio energy");
+            Vec::new()
+        }
+    }
 }
 
 // Re-export JobLogger from logging module for backward compatibility
@@ -7,10 +7,15 @@
 use std::path::{Path, PathBuf};
 use tracing::info;
 
-use vclip_media::download_video;
-use vclip_models::ClipTask;
+use vclip_media::{concat_segments, download_video};
+use vclip_models::{ClipTask, VideoHighlights};
 use vclip_queue::RenderSceneStyleJob;
+use vclip_storage::load_live_manifest;
 
+use crate::boundary_refinement::{refine_scene_segment, RefinementSource};
+use crate::cancellation::run_unless_cancelled;
+use crate::scene_scoring::score_rendered_scene;
+use crate::captions::{resolve_caption_words, CaptionSource};
 use crate::cinematic_analysis;
 use crate::clip_pipeline;
 use crate::error::{WorkerError, WorkerResult};
@@ -140,8 +145,13 @@ pub async fn process_render_job(
         "Registered render job for video"
     );
 
-    // Process the clip
-    let result = process_render_clip_inner(ctx, job, &work_dir, &clips_dir).await;
+    // Process the clip, stopping early if the parent job is cancelled
+    let result = run_unless_cancelled(
+        &ctx.progress,
+        job.parent_job_id.as_ref(),
+        process_render_clip_inner(ctx, job, &work_dir, &clips_dir),
+    )
+    .await;
 
     // Finish the guard, which handles cleanup if this was the last job
     guard.finish().await;
@@ -178,6 +188,7 @@ async fn process_render_clip_inner(
             &job.user_id,
             job.video_id.as_str(),
             job.scene_id,
+            job.parent_job_id.as_ref(),
         )
         .await?;
         
@@ -209,15 +220,31 @@ async fn process_render_clip_inner(
         }
     }
 
-    // Calculate padded timestamps for raw segment
+    // Highlights are the source of truth for the video URL and boundary refinement
+    let highlights = load_highlights_for_render(ctx, job).await;
+    // Live captures have no single source file; scenes are cut from the
+    // recorded segments, never by URL
+    let live_manifest_key = load_live_manifest_key(ctx, job).await;
+    let video_url = highlights
+        .as_ref()
+        .and_then(|h| h.video_url.clone())
+        .filter(|_| live_manifest_key.is_none());
+    let highlight = highlights
+        .as_ref()
+        .and_then(|h| h.highlights.iter().find(|h| h.id == job.scene_id));
+
+    // Calculate padded timestamps for raw segment.
+    // The raw segment always covers the original (pre-refinement) window.
     let pad_before = job.pad_before_seconds.unwrap_or(1.0);
     let pad_after = job.pad_after_seconds.unwrap_or(1.0);
-    let start_secs = vclip_media::intelligent::parse_timestamp(&job.start).unwrap_or(0.0);
-    let end_secs = vclip_media::intelligent::parse_timestamp(&job.end).unwrap_or(30.0);
+    let (start_ts, end_ts) = match highlight {
+        Some(h) => (h.original_start(), h.original_end()),
+        None => (job.start.as_str(), job.end.as_str()),
+    };
+    let start_secs = vclip_media::intelligent::parse_timestamp(start_ts).unwrap_or(0.0);
+    let end_secs = vclip_media::intelligent::parse_timestamp(end_ts).unwrap_or(30.0);
     let padded_start = (start_secs - pad_before).max(0.0);
     let padded_end = end_secs + pad_after;
-    let padded_start_ts = format_timestamp(padded_start);
-    let padded_end_ts = format_timestamp(padded_end);
 
     // CACHE-FIRST: Try to get raw segment from R2 BEFORE downloading full source
     let raw_segment = work_dir.join(format!("raw_{}.mp4", job.scene_id));
@@ -255,7 +282,14 @@ async fn process_render_clip_inner(
                     scene_id = job.scene_id,
                     "Raw segment R2 download failed, falling back to source extraction"
                 );
-                let source_video = download_video_for_render(ctx, job, work_dir).await?;
+                let (source_video, source_offset) = render_source(
+                    ctx,
+                    job,
+                    work_dir,
+                    live_manifest_key.as_deref(),
+                    (padded_start, padded_end),
+                )
+                .await?;
                 let (seg, created) = ctx
                     .raw_cache
                     .get_or_create_with_outcome(
@@ -263,8 +297,8 @@ async fn process_render_clip_inner(
                         job.video_id.as_str(),
                         job.scene_id,
                         &source_video,
-                        &padded_start_ts,
-                        &padded_end_ts,
+                        &format_timestamp(padded_start - source_offset),
+                        &format_timestamp(padded_end - source_offset),
                         work_dir,
                     )
                     .await?;
@@ -278,9 +312,6 @@ async fn process_render_clip_inner(
     }
     // Step 3: Raw segment not in R2 - try yt-dlp segment download first, then fall back to full source
     else {
-        // Get video URL for potential yt-dlp segment download
-        let video_url = get_video_url_for_render(ctx, job).await;
-        
         // Try yt-dlp segment download first (much faster than full source download)
         let mut segment_downloaded = false;
         if let Some(ref url) = video_url {
@@ -342,7 +373,14 @@ async fn process_render_clip_inner(
                 scene_id = job.scene_id,
                 "Downloading full source video for segment extraction..."
             );
-            let source_video = download_video_for_render(ctx, job, work_dir).await?;
+            let (source_video, source_offset) = render_source(
+                ctx,
+                job,
+                work_dir,
+                live_manifest_key.as_deref(),
+                (padded_start, padded_end),
+            )
+            .await?;
             let (seg, created) = ctx
                 .raw_cache
                 .get_or_create_with_outcome(
@@ -350,8 +388,8 @@ async fn process_render_clip_inner(
                     job.video_id.as_str(),
                     job.scene_id,
                     &source_video,
-                    &padded_start_ts,
-                    &padded_end_ts,
+                    &format_timestamp(padded_start - source_offset),
+                    &format_timestamp(padded_end - source_offset),
                     work_dir,
                 )
                 .await?;
@@ -389,6 +427,49 @@ async fn process_render_clip_inner(
         "Using raw segment for styled render"
     );
 
+    // Snap highlight edges to natural boundaries and trim the segment to match
+    let refined = match highlight {
+        Some(highlight) => {
+            let source = RefinementSource {
+                user_id: &job.user_id,
+                video_id: &job.video_id,
+                video_url: video_url.as_deref(),
+                raw_segment: &raw_segment,
+                raw_window: (padded_start, padded_end),
+                pad_before,
+                pad_after,
+                work_dir,
+            };
+            refine_scene_segment(ctx, highlight, &source).await
+        }
+        None => None,
+    };
+    let (raw_segment, padded_start, padded_end) = match refined {
+        Some(refined) => (refined.path, refined.start, refined.end),
+        None => (raw_segment, padded_start, padded_end),
+    };
+
+    // Fill in speech and face metrics for the virality score (first render only)
+    if let Some(highlight) = highlight {
+        score_rendered_scene(ctx, &job.user_id, &job.video_id, highlight, &raw_segment).await;
+    }
+
+    // Resolve caption words against the padded window (no silence removal on this path)
+    let caption_words = if job.captions.is_some() {
+        let source = CaptionSource {
+            video_id: job.video_id.as_str(),
+            scene_id: job.scene_id,
+            video_url: video_url.as_deref(),
+            window_start: padded_start,
+            window_end: padded_end,
+            raw_segment: &raw_segment,
+            kept_segments: None,
+        };
+        resolve_caption_words(ctx, &job.user_id, &source).await
+    } else {
+        Vec::new()
+    };
+
     // Build ClipTask from job fields
     // Note: When using raw segment, start/end are relative to the raw segment (i.e., 0 to duration)
     // The raw segment already has padding applied, so we use 0-relative timestamps
@@ -402,6 +483,7 @@ async fn process_render_clip_inner(
         style: job.style,
         crop_mode: job.crop_mode,
         target_aspect: job.target_aspect,
+        resolution: job.resolution,
         priority: job.scene_id,
         // Padding already applied in raw extraction, so set to 0
         pad_before: 0.0,
@@ -409,6 +491,10 @@ async fn process_render_clip_inner(
         streamer_split_params: None, // TODO: Pass from RenderSceneStyleJob if needed
         streamer_params: None,
         cut_silent_parts: true, // TODO: Add to RenderSceneStyleJob if per-clip control needed
+        captions: job.captions.clone(),
+        caption_words,
+        export_profiles: job.export_profiles.clone(),
+        audio: job.audio.clone(),
     };
 
     // Step 3: Process the clip using the raw segment as input
@@ -528,6 +614,66 @@ async fn download_video_for_render(
     Ok(video_file)
 }
 
+/// R2 key of the segment manifest, if the video is a live capture.
+async fn load_live_manifest_key(
+    ctx: &EnhancedProcessingContext,
+    job: &RenderSceneStyleJob,
+) -> Option<String> {
+    let video_repo = vclip_firestore::VideoRepository::new(ctx.firestore.clone(), &job.user_id);
+    match video_repo.get(&job.video_id).await {
+        Ok(meta) => meta.and_then(|m| m.live_manifest_r2_key),
+        Err(e) => {
+            tracing::warn!(video_id = %job.video_id, error = %e, "Failed to load video metadata");
+            None
+        }
+    }
+}
+
+/// Video to extract the scene from, and where it starts on the video timeline.
+///
+/// Live captures join just the recorded segments covering `window`;
+/// everything else uses the full source video.
+async fn render_source(
+    ctx: &EnhancedProcessingContext,
+    job: &RenderSceneStyleJob,
+    work_dir: &Path,
+    live_manifest_key: Option<&str>,
+    window: (f64, f64),
+) -> WorkerResult<(PathBuf, f64)> {
+    let Some(manifest_key) = live_manifest_key else {
+        return Ok((download_video_for_render(ctx, job, work_dir).await?, 0.0));
+    };
+
+    let manifest = load_live_manifest(&ctx.storage, manifest_key)
+        .await?
+        .ok_or_else(|| WorkerError::job_failed("Live capture has no recorded segments"))?;
+    let segments = manifest.covering(window.0, window.1);
+    let first = segments
+        .first()
+        .ok_or_else(|| WorkerError::job_failed("Scene is outside the recorded live footage"))?;
+
+    let segment_dir = work_dir.join(format!("live_{}", job.scene_id));
+    let mut inputs = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let path = segment_dir.join(format!("seg_{:05}.mp4", segment.index));
+        ctx.storage.download_file(&segment.r2_key, &path).await?;
+        inputs.push(path);
+    }
+
+    let source = work_dir.join(format!("live_source_{}.mp4", job.scene_id));
+    concat_segments(&inputs, &source).await?;
+    if let Err(e) = tokio::fs::remove_dir_all(&segment_dir).await {
+        tracing::warn!("Failed to remove live segments {:?}: {}", segment_dir, e);
+    }
+
+    info!(
+        scene_id = job.scene_id,
+        segments = segments.len(),
+        "Joined live capture segments for render"
+    );
+    Ok((source, first.start_secs))
+}
+
 /// Fallback: download from original video URL stored in Firestore highlights.
 async fn download_video_fallback(
     ctx: &EnhancedProcessingContext,
@@ -554,24 +700,24 @@ async fn download_video_fallback(
     Ok(())
 }
 
-/// Get video URL from Firestore highlights for yt-dlp segment download.
+/// Load Firestore highlights for the video URL and boundary refinement.
 ///
-/// Returns None if highlights not found or no URL stored.
-async fn get_video_url_for_render(
+/// Returns None if highlights are not found.
+async fn load_highlights_for_render(
     ctx: &EnhancedProcessingContext,
     job: &RenderSceneStyleJob,
-) -> Option<String> {
+) -> Option<VideoHighlights> {
     let highlights_repo = vclip_firestore::HighlightsRepository::new(
         ctx.firestore.clone(),
         &job.user_id,
     );
 
     match highlights_repo.get(&job.video_id).await {
-        Ok(Some(highlights)) => highlights.video_url,
+        Ok(Some(highlights)) => Some(highlights),
         Ok(None) => {
             tracing::debug!(
                 video_id = %job.video_id,
-                "No highlights found for video URL lookup"
+                "No highlights found for render"
             );
             None
         }
@@ -579,7 +725,7 @@ async fn get_video_url_for_render(
             tracing::debug!(
                 video_id = %job.video_id,
                 error = %e,
-                "Failed to get highlights for video URL lookup"
+                "Failed to get highlights for render"
             );
             None
         }
@@ -619,6 +765,7 @@ async fn trigger_neural_analysis_job(
         scene_id: job.scene_id,
         source_hint_r2_key: source_hint,
         detection_tier,
+        parent_job_id: job.parent_job_id.clone(),
         created_at: chrono::Utc::now(),
     };
 
@@ -145,7 +145,15 @@ pub async fn reprocess_scenes(
         &job.target_aspect,
         job.streamer_split_params.clone(),
         job.cut_silent_parts,
-    );
+        job.captions.clone(),
+    )
+    .into_iter()
+    .map(|task| {
+        task.with_output_format(job.target_aspect, job.resolution)
+            .with_export_profiles(&job.export_profiles)
+            .with_audio(job.audio.clone())
+    })
+    .collect::<Vec<_>>();
 
     ctx.progress
         .log(&job.job_id, format!("Generating {} clips...", total_clips))
@@ -545,18 +553,19 @@ async fn process_uncached_scenes_optimized(
 
 /// Start prefetch downloads for uncached scenes.
 ///
-/// Returns a list of JoinHandles for the background download tasks.
-/// These can be awaited later via `wait_for_prefetch_downloads`.
+/// Returns the background download tasks, which can be awaited later via
+/// `wait_for_prefetch_downloads`. Dropping the set (e.g. when the job is
+/// cancelled) aborts the downloads.
 fn start_prefetch_downloads(
     ctx: &EnhancedProcessingContext,
     job: &ReprocessScenesJob,
     work_dir: &PathBuf,
     uncached_scenes: &[Highlight],
     video_url: Option<&str>,
-) -> Vec<tokio::task::JoinHandle<()>> {
+) -> tokio::task::JoinSet<()> {
     use vclip_media::intelligent::parse_timestamp;
     
-    let mut handles = Vec::new();
+    let mut handles = tokio::task::JoinSet::new();
     
     for highlight in uncached_scenes {
         let scene_id = highlight.id;
@@ -581,7 +590,7 @@ fn start_prefetch_downloads(
         let padded_end = end_secs + highlight.pad_after;
         
         // Spawn background download task
-        let handle = tokio::spawn(async move {
+        handles.spawn(async move {
             info!(
                 scene_id = scene_id,
                 start = padded_start,
@@ -622,8 +631,6 @@ fn start_prefetch_downloads(
                 }
             }
         });
-        
-        handles.push(handle);
     }
     
     if !handles.is_empty() {
@@ -641,9 +648,7 @@ fn start_prefetch_downloads(
 /// This function should be called before processing uncached scenes to ensure
 /// their raw segments are available. If some downloads fail, uncached processing
 /// will retry them.
-async fn wait_for_prefetch_downloads(handles: Vec<tokio::task::JoinHandle<()>>) {
-    use futures::future::join_all;
-    
+async fn wait_for_prefetch_downloads(mut handles: tokio::task::JoinSet<()>) {
     if handles.is_empty() {
         return;
     }
@@ -653,7 +658,10 @@ async fn wait_for_prefetch_downloads(handles: Vec<tokio::task::JoinHandle<()>>)
         "Waiting for prefetch downloads to complete"
     );
     
-    let results = join_all(handles).await;
+    let mut results = Vec::with_capacity(handles.len());
+    while let Some(result) = handles.join_next().await {
+        results.push(result);
+    }
     
     let completed = results.iter().filter(|r| r.is_ok()).count();
     let failed = results.len() - completed;
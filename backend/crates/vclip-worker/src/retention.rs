@@ -0,0 +1,446 @@
+//! Retention sweeper: delete expired cache artifacts from R2 and Firestore.
+//!
+//! Every `WorkerConfig::retention_sweep_interval` one worker (elected through
+//! a Redis lock) walks each user's R2 prefixes, classifies the objects into
+//! `RetentionArtifact` kinds and deletes those older than the user's plan
+//! policy allows. Deleted bytes are subtracted from the user's
+//! `StorageAccounting` in the same step, deleted source copies mark their
+//! video's source as expired, and expired analysis drafts are deleted along
+//! with any live capture footage they still reference.
+//!
+//! Live capture prefixes are swept as a unit and aged by their newest object,
+//! so a capture that is still recording is never cut short.
+//!
+//! In dry-run mode nothing is deleted; the report lists what would have been.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use vclip_firestore::{
+    AnalysisDraftRepository, FirestoreClient, StorageAccountingRepository, UserCreditsRepository,
+    VideoRepository,
+};
+use vclip_models::{
+    parse_retention_overrides, RetentionArtifact, RetentionOverride, RetentionPolicy, VideoId,
+};
+use vclip_queue::JobQueue;
+use vclip_storage::{live_capture_prefix, ObjectInfo, R2Client};
+
+use crate::error::{WorkerError, WorkerResult};
+use crate::user_plan::resolve_user_tier;
+
+/// Most keys a single S3 `DeleteObjects` request accepts.
+const DELETE_BATCH_SIZE: usize = 1000;
+
+/// Redis lock electing the worker that sweeps this round.
+const SWEEP_LOCK_KEY: &str = "retention_sweep:run";
+
+/// Classify an R2 key of `user_id` into the artifact kind it holds.
+///
+/// Returns `None` for keys the sweeper never touches (styled clips,
+/// thumbnails, highlights, uploads).
+pub fn classify_key(user_id: &str, key: &str) -> Option<RetentionArtifact> {
+    if let Some(rest) = key.strip_prefix("sources/") {
+        return rest
+            .strip_prefix(user_id)?
+            .starts_with('/')
+            .then_some(RetentionArtifact::SourceVideo);
+    }
+    if let Some(rest) = key.strip_prefix("clips/") {
+        let mut parts = rest.strip_prefix(user_id)?.strip_prefix('/')?.split('/');
+        let _video_id = parts.next()?;
+        return match parts.next()? {
+            "raw" => Some(RetentionArtifact::RawSegment),
+            "silence_removed" => Some(RetentionArtifact::SilenceRemovedSegment),
+            _ => None,
+        };
+    }
+
+    let mut parts = key.strip_prefix(user_id)?.strip_prefix('/')?.split('/');
+    match (parts.next()?, parts.next()?) {
+        ("transcripts", _) => Some(RetentionArtifact::Transcript),
+        ("live", _) => Some(RetentionArtifact::LiveSegment),
+        (_, "neural") => Some(RetentionArtifact::NeuralCache),
+        _ => None,
+    }
+}
+
+/// Live capture directory (`{user}/live/{draft}/`) of a live segment key.
+fn live_dir(key: &str) -> Option<&str> {
+    let mut slashes = key.match_indices('/').map(|(i, _)| i);
+    let end = slashes.nth(2)?;
+    Some(&key[..=end])
+}
+
+/// An object selected for deletion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiredObject {
+    pub key: String,
+    pub size: u64,
+    pub artifact: RetentionArtifact,
+}
+
+/// Select the objects of `user_id` that have outlived `policy` at `now_ms`.
+///
+/// Objects under `forced_prefixes` (footage of expired drafts) are selected
+/// regardless of age. Objects without a modification time are kept.
+pub fn select_expired(
+    user_id: &str,
+    objects: &[ObjectInfo],
+    policy: &RetentionPolicy,
+    now_ms: u64,
+    forced_prefixes: &[String],
+) -> Vec<ExpiredObject> {
+    let age_hours = |modified_ms: u64| now_ms.saturating_sub(modified_ms) as f64 / 3_600_000.0;
+
+    // Newest write per live capture directory
+    let mut live_newest: BTreeMap<&str, u64> = BTreeMap::new();
+    for obj in objects {
+        if classify_key(user_id, &obj.key) != Some(RetentionArtifact::LiveSegment) {
+            continue;
+        }
+        if let Some(dir) = live_dir(&obj.key) {
+            let newest = live_newest.entry(dir).or_default();
+            // Unknown modification time keeps the whole capture
+            *newest = (*newest).max(obj.last_modified.unwrap_or(u64::MAX));
+        }
+    }
+
+    objects
+        .iter()
+        .filter_map(|obj| {
+            let artifact = classify_key(user_id, &obj.key)?;
+            let forced = forced_prefixes
+                .iter()
+                .any(|p| obj.key.starts_with(p.as_str()));
+            let modified = match artifact {
+                RetentionArtifact::LiveSegment => live_newest.get(live_dir(&obj.key)?).copied(),
+                _ => obj.last_modified,
+            };
+            let expired = forced
+                || modified
+                    .is_some_and(|ms| ms != u64::MAX && policy.is_expired(artifact, age_hours(ms)));
+
+            expired.then(|| ExpiredObject {
+                key: obj.key.clone(),
+                size: obj.size,
+                artifact,
+            })
+        })
+        .collect()
+}
+
+/// Objects and bytes swept for one artifact kind.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ArtifactSweep {
+    pub objects: u64,
+    pub bytes: u64,
+}
+
+/// Outcome of a sweep.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SweepReport {
+    /// Whether deletions were only reported
+    pub dry_run: bool,
+    pub users_scanned: u32,
+    /// Swept objects per artifact kind
+    pub artifacts: BTreeMap<RetentionArtifact, ArtifactSweep>,
+    /// Source videos marked expired
+    pub sources_expired: u32,
+    /// Analysis drafts deleted
+    pub drafts_expired: u32,
+    /// Per-user failures; the sweep continues with the next user
+    pub errors: Vec<String>,
+}
+
+impl SweepReport {
+    fn record(&mut self, expired: &[ExpiredObject]) {
+        for obj in expired {
+            let entry = self.artifacts.entry(obj.artifact).or_default();
+            entry.objects += 1;
+            entry.bytes += obj.size;
+        }
+    }
+}
+
+/// Deletes cached artifacts that outlived their retention policy.
+pub struct RetentionSweeper {
+    firestore: FirestoreClient,
+    storage: R2Client,
+    overrides: Vec<RetentionOverride>,
+    dry_run: bool,
+}
+
+impl RetentionSweeper {
+    /// Create a sweeper; `overrides` is a spec such as `pro.raw_segment=14d`.
+    pub fn new(
+        firestore: FirestoreClient,
+        storage: R2Client,
+        overrides: Option<&str>,
+        dry_run: bool,
+    ) -> WorkerResult<Self> {
+        let overrides = overrides
+            .map(parse_retention_overrides)
+            .transpose()
+            .map_err(WorkerError::config_error)?
+            .unwrap_or_default();
+
+        Ok(Self {
+            firestore,
+            storage,
+            overrides,
+            dry_run,
+        })
+    }
+
+    /// Run one sweep, unless another worker holds the round's lock.
+    pub async fn run_scheduled(
+        &self,
+        queue: &Arc<JobQueue>,
+        interval: Duration,
+    ) -> WorkerResult<()> {
+        let lock_secs = interval.as_secs().saturating_sub(5).max(1);
+        if !queue
+            .try_acquire_idempotency(SWEEP_LOCK_KEY, lock_secs)
+            .await?
+        {
+            debug!("Retention sweep already running on another worker");
+            return Ok(());
+        }
+
+        let report = self.sweep_all().await?;
+        info!(
+            "Retention sweep finished (dry_run={}): {} users, {} objects, {} drafts, {} errors",
+            report.dry_run,
+            report.users_scanned,
+            report.artifacts.values().map(|a| a.objects).sum::<u64>(),
+            report.drafts_expired,
+            report.errors.len()
+        );
+        Ok(())
+    }
+
+    /// Sweep every user.
+    pub async fn sweep_all(&self) -> WorkerResult<SweepReport> {
+        let mut report = SweepReport {
+            dry_run: self.dry_run,
+            ..Default::default()
+        };
+
+        for user_id in UserCreditsRepository::list_user_ids(&self.firestore).await? {
+            report.users_scanned += 1;
+            if let Err(e) = self.sweep_user(&user_id, &mut report).await {
+                warn!("Retention sweep failed for user {}: {}", user_id, e);
+                report.errors.push(format!("{}: {}", user_id, e));
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Sweep a single user's artifacts and drafts.
+    pub async fn sweep_user(&self, user_id: &str, report: &mut SweepReport) -> WorkerResult<()> {
+        let tier = resolve_user_tier(&self.firestore, user_id).await;
+        let policy = RetentionPolicy::resolve(tier, &self.overrides);
+
+        let draft_repo = AnalysisDraftRepository::new(self.firestore.clone(), user_id);
+        let expired_drafts = draft_repo.list_expired().await?;
+        let forced_prefixes: Vec<String> = expired_drafts
+            .iter()
+            .filter(|d| d.live_capture.is_some())
+            .map(|d| live_capture_prefix(user_id, &d.id))
+            .collect();
+
+        let mut objects = Vec::new();
+        for prefix in [
+            format!("sources/{}/", user_id),
+            format!("clips/{}/", user_id),
+            format!("{}/", user_id),
+        ] {
+            objects.extend(self.storage.list_objects(&prefix).await?);
+        }
+
+        let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        let expired = select_expired(user_id, &objects, &policy, now_ms, &forced_prefixes);
+        report.record(&expired);
+
+        let expired_sources: HashSet<&str> = expired
+            .iter()
+            .filter(|o| o.artifact == RetentionArtifact::SourceVideo)
+            .filter_map(|o| o.key.split('/').nth(2))
+            .collect();
+        report.sources_expired += expired_sources.len() as u32;
+        report.drafts_expired += expired_drafts.len() as u32;
+
+        if self.dry_run {
+            return Ok(());
+        }
+
+        self.delete_and_account(user_id, &expired).await?;
+
+        let video_repo = VideoRepository::new(self.firestore.clone(), user_id);
+        for video_id in expired_sources {
+            if let Err(e) = video_repo
+                .set_source_video_expired(&VideoId::from(video_id.to_string()))
+                .await
+            {
+                warn!("Failed to mark source of video {} expired: {}", video_id, e);
+            }
+        }
+
+        for draft in &expired_drafts {
+            draft_repo.delete(&draft.id).await?;
+        }
+
+        if !expired.is_empty() || !expired_drafts.is_empty() {
+            info!(
+                "Swept {} objects and {} drafts for user {}",
+                expired.len(),
+                expired_drafts.len(),
+                user_id
+            );
+        }
+        Ok(())
+    }
+
+    /// Delete objects in batches, subtracting each batch from the accounting.
+    async fn delete_and_account(
+        &self,
+        user_id: &str,
+        expired: &[ExpiredObject],
+    ) -> WorkerResult<()> {
+        let accounting = StorageAccountingRepository::new(self.firestore.clone(), user_id);
+
+        for batch in expired.chunks(DELETE_BATCH_SIZE) {
+            let keys: Vec<String> = batch.iter().map(|o| o.key.clone()).collect();
+            self.storage.delete_objects(&keys).await?;
+
+            let (mut source, mut raw, mut neural) = (0u64, 0u64, 0u64);
+            for obj in batch {
+                match obj.artifact {
+                    RetentionArtifact::SourceVideo => source += obj.size,
+                    RetentionArtifact::RawSegment | RetentionArtifact::LiveSegment => {
+                        raw += obj.size
+                    }
+                    RetentionArtifact::NeuralCache => neural += obj.size,
+                    RetentionArtifact::SilenceRemovedSegment | RetentionArtifact::Transcript => {}
+                }
+            }
+            if source + raw + neural > 0 {
+                accounting.remove_cache(source, raw, neural).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vclip_models::PlanTier;
+
+    const HOUR_MS: u64 = 3_600_000;
+
+    fn object(key: &str, size: u64, modified_ms: u64) -> ObjectInfo {
+        ObjectInfo {
+            key: key.to_string(),
+            size,
+            last_modified: Some(modified_ms),
+        }
+    }
+
+    #[test]
+    fn test_classify_key() {
+        let u = "user1";
+        assert_eq!(
+            classify_key(u, "sources/user1/v1/source.mp4"),
+            Some(RetentionArtifact::SourceVideo)
+        );
+        assert_eq!(
+            classify_key(u, "clips/user1/v1/raw/3.mp4"),
+            Some(RetentionArtifact::RawSegment)
+        );
+        assert_eq!(
+            classify_key(u, "clips/user1/v1/silence_removed/3.mp4"),
+            Some(RetentionArtifact::SilenceRemovedSegment)
+        );
+        assert_eq!(
+            classify_key(u, "user1/v1/neural/3.json.gz"),
+            Some(RetentionArtifact::NeuralCache)
+        );
+        assert_eq!(
+            classify_key(u, "user1/v1/neural/3.words.json.gz"),
+            Some(RetentionArtifact::NeuralCache)
+        );
+        assert_eq!(
+            classify_key(u, "user1/transcripts/v1.txt.gz"),
+            Some(RetentionArtifact::Transcript)
+        );
+        assert_eq!(
+            classify_key(u, "user1/live/d1/seg_00001.mp4"),
+            Some(RetentionArtifact::LiveSegment)
+        );
+
+        // Styled clips, highlights and other users' keys are never swept
+        assert_eq!(classify_key(u, "user1/v1/clips/clip.mp4"), None);
+        assert_eq!(classify_key(u, "user1/v1/highlights.json"), None);
+        assert_eq!(classify_key(u, "sources/user10/v1/source.mp4"), None);
+        assert_eq!(classify_key(u, "user10/v1/neural/3.json.gz"), None);
+    }
+
+    #[test]
+    fn test_select_expired_by_policy() {
+        let policy = RetentionPolicy::for_tier(PlanTier::Free);
+        let now = 1_000 * HOUR_MS;
+        let objects = vec![
+            object("sources/u/v1/source.mp4", 100, now - 25 * HOUR_MS),
+            object("sources/u/v2/source.mp4", 100, now - 2 * HOUR_MS),
+            object("clips/u/v1/raw/1.mp4", 10, now - 8 * 24 * HOUR_MS),
+            object("u/v1/clips/styled.mp4", 10, 0),
+            ObjectInfo {
+                key: "u/v1/neural/1.json.gz".to_string(),
+                size: 1,
+                last_modified: None,
+            },
+        ];
+
+        let expired = select_expired("u", &objects, &policy, now, &[]);
+        let keys: Vec<&str> = expired.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["sources/u/v1/source.mp4", "clips/u/v1/raw/1.mp4"]
+        );
+    }
+
+    #[test]
+    fn test_select_expired_live_capture_as_unit() {
+        let policy = RetentionPolicy::for_tier(PlanTier::Free);
+        let now = 1_000 * HOUR_MS;
+        let old = now - 8 * 24 * HOUR_MS;
+        let objects = vec![
+            // Old capture: everything goes
+            object("u/live/d1/seg_00000.mp4", 5, old),
+            object("u/live/d1/manifest.json", 1, old),
+            // Long capture still being written: kept despite its old first segment
+            object("u/live/d2/seg_00000.mp4", 5, old),
+            object("u/live/d2/seg_00999.mp4", 5, now - HOUR_MS),
+            // Recent capture of an expired draft: forced
+            object("u/live/d3/seg_00000.mp4", 5, now),
+        ];
+
+        let expired = select_expired("u", &objects, &policy, now, &["u/live/d3/".to_string()]);
+        let keys: Vec<&str> = expired.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "u/live/d1/seg_00000.mp4",
+                "u/live/d1/manifest.json",
+                "u/live/d3/seg_00000.mp4"
+            ]
+        );
+    }
+}
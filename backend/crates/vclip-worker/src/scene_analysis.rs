@@ -43,6 +43,7 @@
 //! ```
 
 use std::path::Path;
+use tokio::sync::watch;
 use tracing::{debug, info, warn};
 use vclip_models::{DetectionTier, FrameAnalysis, SceneNeuralAnalysis};
 
@@ -50,6 +51,15 @@ use crate::cinematic_signals::{compute_cinematic_signals, CinematicSignalOptions
 use crate::error::{WorkerError, WorkerResult};
 use crate::processor::EnhancedProcessingContext;
 
+/// Signals a blocking detection thread to stop when dropped.
+struct CancelOnDrop(watch::Sender<bool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.send_replace(true);
+    }
+}
+
 /// Service for managing scene-level neural analysis.
 ///
 /// Ensures detection runs exactly ONCE per scene, regardless of how many
@@ -106,22 +116,34 @@ impl<'a> SceneAnalysisService<'a> {
                 let user_id = user_id.to_string();
                 let video_id = video_id.to_string();
                 let handle = tokio::runtime::Handle::current();
+                let (cancel_tx, mut cancel_rx) = watch::channel(false);
                 
                 async move {
+                    // Dropping this future (a cancelled job) signals the blocking
+                    // thread, which would otherwise run detection to the end.
+                    let _cancel = CancelOnDrop(cancel_tx);
+
                     // Offload heavy CPU work (neural analysis) to a blocking thread
                     // to avoid stalling the async runtime.
                     // We use handle.block_on to run the inner async function (which calls blocking OpenCV code)
                     // on the blocking thread.
                     tokio::task::spawn_blocking(move || {
-                        handle.block_on(run_detection(
-                            &video_path,
-                            &user_id,
-                            &video_id,
-                            scene_id,
-                            start_time,
-                            end_time,
-                            required_tier,
-                        ))
+                        handle.block_on(async {
+                            tokio::select! {
+                                result = run_detection(
+                                    &video_path,
+                                    &user_id,
+                                    &video_id,
+                                    scene_id,
+                                    start_time,
+                                    end_time,
+                                    required_tier,
+                                ) => result,
+                                _ = cancel_rx.wait_for(|cancelled| *cancelled) => {
+                                    Err(WorkerError::cancelled("Scene analysis cancelled"))
+                                }
+                            }
+                        })
                     })
                     .await
                     .map_err(|e| WorkerError::job_failed(format!("Blocking task join error: {}", e)))?
@@ -8,10 +8,12 @@
 //! The scene renderer:
 //! 1. Groups clips by scene ID for parallel style processing
 //! 2. Gets or creates cached raw segments
-//! 3. Applies silence removal if requested
-//! 4. Processes each style in parallel for efficiency
-//! 5. Tracks storage accounting for new segments
-//! 6. Processes multiple scenes in parallel for better throughput
+//! 3. Snaps highlight boundaries to sentence, silence and shot edges
+//! 4. Applies silence removal if requested
+//! 5. Resolves caption word timings when captions are requested
+//! 6. Processes each style in parallel for efficiency
+//! 7. Tracks storage accounting for new segments
+//! 8. Processes multiple scenes in parallel for better throughput
 
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -23,14 +25,18 @@ use futures::stream::{self, StreamExt};
 
 use tracing::{debug, info, warn};
 
-use vclip_models::{ClipStatus, ClipTask, ProcessingProgress, VideoHighlights};
+use vclip_media::silence_removal::Segment;
+use vclip_models::{CaptionWord, ClipStatus, ClipTask, ProcessingProgress, VideoHighlights};
 use vclip_queue::ReprocessScenesJob;
 
+use crate::boundary_refinement::{refine_scene_segment, RefinementSource};
+use crate::scene_scoring::score_rendered_scene;
+use crate::captions::{resolve_caption_words, CaptionSource};
 use crate::clip_pipeline;
 use crate::error::WorkerResult;
 use crate::processor::EnhancedProcessingContext;
 use crate::raw_segment_cache::raw_segment_r2_key;
-use crate::silence_cache::apply_silence_removal_cached;
+use crate::silence_cache::{apply_silence_removal_with_segments, SilenceRemovedSegment};
 
 /// Minimum interval between Firestore progress updates to avoid excessive writes.
 const PROGRESS_UPDATE_INTERVAL: Duration = Duration::from_secs(5);
@@ -281,19 +287,37 @@ async fn process_single_scene(
     }
 
     // Get or create cached raw segment
-    let (raw_segment, segment_duration) = prepare_raw_segment(
+    let prepared = prepare_raw_segment(
         ctx,
         job,
         first_task,
-        scene_id,
+        highlights,
         video_file,
         work_dir,
         &scene_task_refs,
     )
     .await?;
+    let raw_segment = prepared.path;
+
+    // Resolve caption words once per scene (shared by all styles)
+    let caption_words = if scene_tasks.iter().any(|t| t.captions.is_some()) {
+        let source = CaptionSource {
+            video_id: job.video_id.as_str(),
+            scene_id,
+            video_url: highlights.video_url.as_deref(),
+            window_start: prepared.padded_start,
+            window_end: prepared.padded_end,
+            raw_segment: prepared.silence_source.as_deref().unwrap_or(&raw_segment),
+            kept_segments: prepared.silence_segments.as_deref(),
+        };
+        resolve_caption_words(ctx, &job.user_id, &source).await
+    } else {
+        Vec::new()
+    };
 
     // Create modified tasks for raw segment processing
-    let modified_tasks = create_modified_tasks(&scene_task_refs, segment_duration);
+    let modified_tasks =
+        create_modified_tasks(&scene_task_refs, prepared.duration, &caption_words);
     let modified_task_refs: Vec<&ClipTask> = modified_tasks.iter().collect();
 
     // Create a temporary ProcessVideoJob for the scene processor
@@ -305,7 +329,13 @@ async fn process_single_scene(
         styles: job.styles.clone(),
         crop_mode: job.crop_mode.clone(),
         target_aspect: job.target_aspect.clone(),
+        resolution: job.resolution,
+        export_profiles: job.export_profiles.clone(),
+        audio: job.audio.clone(),
+        captions: job.captions.clone(),
+        highlight_detection: Default::default(),
         custom_prompt: None,
+        lane: job.lane,
     };
 
     // Process scene using the raw segment
@@ -422,23 +452,46 @@ async fn emit_scene_started(
     }
 }
 
+/// Raw segment ready for style processing.
+struct PreparedSegment {
+    /// Segment to render from (silence-removed when applicable)
+    path: PathBuf,
+    /// Duration of `path` in seconds
+    duration: f64,
+    /// Padded start in source time (seconds)
+    padded_start: f64,
+    /// Padded end in source time (seconds)
+    padded_end: f64,
+    /// Original raw segment, set only when silence removal changed the segment
+    silence_source: Option<PathBuf>,
+    /// Keep/Cut segments silence removal cut `silence_source` with
+    silence_segments: Option<Vec<Segment>>,
+}
+
 /// Prepare the raw segment for processing.
 async fn prepare_raw_segment(
     ctx: &EnhancedProcessingContext,
     job: &ReprocessScenesJob,
     first_task: &ClipTask,
-    scene_id: u32,
+    highlights: &VideoHighlights,
     video_file: &PathBuf,
     work_dir: &std::path::Path,
     scene_tasks: &[&ClipTask],
-) -> WorkerResult<(PathBuf, f64)> {
+) -> WorkerResult<PreparedSegment> {
     use vclip_media::intelligent::parse_timestamp;
 
+    let scene_id = first_task.scene_id;
     let pad_before = first_task.pad_before;
     let pad_after = first_task.pad_after;
+    let highlight = highlights.highlights.iter().find(|h| h.id == scene_id);
 
-    let start_secs = parse_timestamp(&first_task.start).unwrap_or(0.0);
-    let end_secs = parse_timestamp(&first_task.end).unwrap_or(30.0);
+    // The raw segment always covers the original (pre-refinement) window
+    let (start_ts, end_ts) = match highlight {
+        Some(h) => (h.original_start(), h.original_end()),
+        None => (first_task.start.as_str(), first_task.end.as_str()),
+    };
+    let start_secs = parse_timestamp(start_ts).unwrap_or(0.0);
+    let end_secs = parse_timestamp(end_ts).unwrap_or(30.0);
     let padded_start = (start_secs - pad_before).max(0.0);
     let padded_end = end_secs + pad_after;
 
@@ -470,6 +523,33 @@ async fn prepare_raw_segment(
         "Using raw segment for scene processing"
     );
 
+    // Snap highlight edges to natural boundaries and trim the segment to match
+    let refined = match highlight {
+        Some(highlight) => {
+            let source = RefinementSource {
+                user_id: &job.user_id,
+                video_id: &job.video_id,
+                video_url: highlights.video_url.as_deref(),
+                raw_segment: &raw_segment,
+                raw_window: (padded_start, padded_end),
+                pad_before,
+                pad_after,
+                work_dir,
+            };
+            refine_scene_segment(ctx, highlight, &source).await
+        }
+        None => None,
+    };
+    let (raw_segment, padded_start, padded_end) = match refined {
+        Some(refined) => (refined.path, refined.start, refined.end),
+        None => (raw_segment, padded_start, padded_end),
+    };
+
+    // Fill in speech and face metrics for the virality score (first render only)
+    if let Some(highlight) = highlight {
+        score_rendered_scene(ctx, &job.user_id, &job.video_id, highlight, &raw_segment).await;
+    }
+
     // Apply silence removal if requested
     let should_cut_silent = scene_tasks.iter().any(|t| t.cut_silent_parts);
     info!(
@@ -477,10 +557,13 @@ async fn prepare_raw_segment(
         should_cut_silent = should_cut_silent,
         "Checking silence removal flag"
     );
-    let raw_segment = if should_cut_silent {
-        apply_silence_to_segment(ctx, job, &raw_segment, scene_id).await
+    let (raw_segment, silence_source, silence_segments) = if should_cut_silent {
+        match apply_silence_to_segment(ctx, job, &raw_segment, scene_id).await {
+            Some(removed) => (removed.path, Some(raw_segment), Some(removed.segments)),
+            None => (raw_segment, None, None),
+        }
     } else {
-        raw_segment
+        (raw_segment, None, None)
     };
 
     // Calculate segment duration
@@ -493,7 +576,14 @@ async fn prepare_raw_segment(
         padded_end - padded_start
     };
 
-    Ok((raw_segment, segment_duration))
+    Ok(PreparedSegment {
+        path: raw_segment,
+        duration: segment_duration,
+        padded_start,
+        padded_end,
+        silence_source,
+        silence_segments,
+    })
 }
 
 /// Track storage accounting for a raw segment.
@@ -518,13 +608,15 @@ async fn track_raw_segment_storage(
 }
 
 /// Apply silence removal to a segment.
+///
+/// Returns `None` when the original segment should be used.
 async fn apply_silence_to_segment(
     ctx: &EnhancedProcessingContext,
     job: &ReprocessScenesJob,
     raw_segment: &PathBuf,
     scene_id: u32,
-) -> PathBuf {
-    match apply_silence_removal_cached(
+) -> Option<SilenceRemovedSegment> {
+    match apply_silence_removal_with_segments(
         ctx,
         raw_segment,
         scene_id,
@@ -534,16 +626,16 @@ async fn apply_silence_to_segment(
     )
     .await
     {
-        Ok(Some(silence_removed_path)) => {
+        Ok(Some(removed)) => {
             info!(scene_id = scene_id, "Using silence-removed segment");
-            silence_removed_path
+            Some(removed)
         }
         Ok(None) => {
             info!(
                 scene_id = scene_id,
                 "Silence removal not applied (no significant silence or too short)"
             );
-            raw_segment.clone()
+            None
         }
         Err(e) => {
             warn!(
@@ -551,13 +643,17 @@ async fn apply_silence_to_segment(
                 error = %e,
                 "Silence removal failed, using original segment"
             );
-            raw_segment.clone()
+            None
         }
     }
 }
 
 /// Create modified tasks for raw segment processing.
-fn create_modified_tasks(scene_tasks: &[&ClipTask], segment_duration: f64) -> Vec<ClipTask> {
+fn create_modified_tasks(
+    scene_tasks: &[&ClipTask],
+    segment_duration: f64,
+    caption_words: &[CaptionWord],
+) -> Vec<ClipTask> {
     scene_tasks
         .iter()
         .map(|task| ClipTask {
@@ -570,6 +666,7 @@ fn create_modified_tasks(scene_tasks: &[&ClipTask], segment_duration: f64) -> Ve
             style: task.style,
             crop_mode: task.crop_mode.clone(),
             target_aspect: task.target_aspect.clone(),
+            resolution: task.resolution,
             priority: task.priority,
             // Padding already applied in raw extraction
             pad_before: 0.0,
@@ -577,6 +674,14 @@ fn create_modified_tasks(scene_tasks: &[&ClipTask], segment_duration: f64) -> Ve
             streamer_split_params: task.streamer_split_params.clone(),
             streamer_params: task.streamer_params.clone(),
             cut_silent_parts: task.cut_silent_parts,
+            captions: task.captions.clone(),
+            caption_words: if task.captions.is_some() {
+                caption_words.to_vec()
+            } else {
+                Vec::new()
+            },
+            export_profiles: task.export_profiles.clone(),
+            audio: task.audio.clone(),
         })
         .collect()
 }
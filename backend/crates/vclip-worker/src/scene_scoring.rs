@@ -0,0 +1,170 @@
+//! Per-scene quality metrics for the virality score.
+//!
+//! Analysis-time metrics (hook strength, duration fit, detector confidence)
+//! are computed when highlights are detected. Render-time metrics (speech
+//! ratio, face presence) need the scene's media and are computed on the first
+//! render, then merged into the stored highlight. Scoring is never fatal.
+
+use std::path::Path;
+
+use tracing::{debug, warn};
+
+use vclip_highlights::{hook_strength, HighlightCandidate};
+use vclip_media::silence_removal::{analyze_audio_segments, compute_segment_stats};
+use vclip_models::{parse_timestamp, Highlight, QualityMetrics, SceneNeuralAnalysis, VideoId};
+
+use crate::processor::EnhancedProcessingContext;
+use crate::silence_cache::SilenceServiceConfig;
+
+/// Metrics available from a detected highlight and the transcript.
+pub fn analysis_metrics(
+    candidate: &HighlightCandidate,
+    duration_secs: f64,
+    transcript: &str,
+) -> QualityMetrics {
+    let hook = parse_timestamp(&candidate.start)
+        .ok()
+        .and_then(|start| hook_strength(transcript, start));
+
+    QualityMetrics {
+        hook_strength: hook,
+        llm_confidence: candidate.confidence.map(|c| c.clamp(0.0, 1.0)),
+        ..Default::default()
+    }
+    .with_duration(duration_secs)
+}
+
+/// Compute missing render-time metrics for a scene and store them.
+///
+/// `segment` is the scene's media before silence removal. Scenes that
+/// already carry render metrics are left alone.
+pub async fn score_rendered_scene(
+    ctx: &EnhancedProcessingContext,
+    user_id: &str,
+    video_id: &VideoId,
+    highlight: &Highlight,
+    segment: &Path,
+) {
+    let existing = highlight.quality.unwrap_or_default();
+    if existing.has_render_metrics() {
+        return;
+    }
+
+    let speech_ratio = match existing.speech_ratio {
+        Some(ratio) => Some(ratio),
+        None => speech_ratio(segment).await,
+    };
+    let face_presence = match existing.face_presence {
+        Some(presence) => Some(presence),
+        None => match ctx
+            .neural_cache
+            .get_cached(user_id, video_id.as_str(), highlight.id)
+            .await
+        {
+            Ok(analysis) => analysis.as_ref().and_then(face_presence),
+            Err(e) => {
+                debug!(scene_id = highlight.id, error = %e, "No neural analysis for face presence");
+                None
+            }
+        },
+    };
+
+    if speech_ratio == existing.speech_ratio && face_presence == existing.face_presence {
+        return;
+    }
+
+    let render = QualityMetrics {
+        speech_ratio,
+        face_presence,
+        ..Default::default()
+    };
+    let repo = vclip_firestore::HighlightsRepository::new(ctx.firestore.clone(), user_id);
+    let result = repo
+        .update_highlight(video_id, highlight.id, |h| {
+            let quality = h.quality.unwrap_or_default().merge(render);
+            h.set_quality(quality);
+        })
+        .await;
+
+    match result {
+        Ok(Some(h)) => debug!(
+            scene_id = highlight.id,
+            virality_score = ?h.virality_score,
+            "Stored render-time scene metrics"
+        ),
+        Ok(None) => debug!(
+            scene_id = highlight.id,
+            "Highlight vanished before scene metrics were stored"
+        ),
+        Err(e) => warn!(
+            scene_id = highlight.id,
+            error = %e,
+            "Failed to store scene metrics (non-critical)"
+        ),
+    }
+}
+
+/// Fraction of the segment VAD keeps as speech.
+async fn speech_ratio(segment: &Path) -> Option<f64> {
+    let vad_config = SilenceServiceConfig::default().vad_config;
+    match analyze_audio_segments(segment, vad_config).await {
+        Ok(segments) if !segments.is_empty() => {
+            Some(compute_segment_stats(&segments).keep_ratio.clamp(0.0, 1.0))
+        }
+        Ok(_) => None,
+        Err(e) => {
+            warn!(error = %e, "Failed to analyze speech for scene metrics");
+            None
+        }
+    }
+}
+
+/// Fraction of analyzed frames with at least one face.
+fn face_presence(analysis: &SceneNeuralAnalysis) -> Option<f64> {
+    if analysis.frames.is_empty() {
+        return None;
+    }
+    let with_faces = analysis.frames.iter().filter(|f| !f.faces.is_empty()).count();
+    Some(with_faces as f64 / analysis.frames.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vclip_models::{BoundingBox, FaceDetection, FrameAnalysis};
+
+    #[test]
+    fn test_analysis_metrics() {
+        let candidate = HighlightCandidate {
+            id: 1,
+            title: "Hook".to_string(),
+            start: "00:00:10".to_string(),
+            end: "00:00:40".to_string(),
+            duration: 30,
+            pad_before_seconds: 0.0,
+            pad_after_seconds: 0.0,
+            hook_category: None,
+            reason: None,
+            description: None,
+            confidence: Some(1.4),
+        };
+
+        let metrics = analysis_metrics(&candidate, 30.0, "");
+        assert_eq!(metrics.llm_confidence, Some(1.0));
+        assert_eq!(metrics.duration_fit, Some(1.0));
+        assert_eq!(metrics.hook_strength, None);
+        assert!(!metrics.has_render_metrics());
+    }
+
+    #[test]
+    fn test_face_presence() {
+        let mut analysis = SceneNeuralAnalysis::new("v", 1);
+        assert_eq!(face_presence(&analysis), None);
+
+        let mut with_face = FrameAnalysis::new(0.0);
+        with_face.add_face(FaceDetection::new(BoundingBox::new(0.2, 0.1, 0.3, 0.4), 0.9));
+        analysis.add_frame(with_face);
+        analysis.add_frame(FrameAnalysis::new(1.0));
+        assert_eq!(face_presence(&analysis), Some(0.5));
+    }
+}
@@ -0,0 +1,273 @@
+//! Signal-based highlights for the analysis jobs.
+//!
+//! Wraps `vclip_media::excitement` for videos whose transcript says little
+//! (gaming streams, sports): proposes highlights from loudness, speech, shot
+//! cuts and motion, and fuses them with transcript highlights. Twitch and Kick
+//! VODs with a chat replay export on disk add chat-rate spikes.
+
+use std::path::{Path, PathBuf};
+
+use tracing::{info, warn};
+use vclip_highlights::{HighlightCandidate, HighlightsResponse};
+use vclip_media::{
+    analyze_excitement, ChatReplay, ExcitementCandidate, ExcitementConfig, ExcitementProfile,
+};
+use vclip_models::{parse_stream_url, parse_timestamp};
+
+use crate::error::{WorkerError, WorkerResult};
+
+/// Share of a transcript pick's fused score granted for being picked at all.
+///
+/// The rest comes from its excitement score, so a transcript pick over an
+/// average stretch scores 0.75 and only clear signal peaks outrank it.
+const TRANSCRIPT_PRIOR: f64 = 0.5;
+
+/// Signal picks added on top of transcript picks in fused mode.
+const MAX_FUSED_SIGNAL_PICKS: usize = 4;
+
+/// Minimum excitement score for a signal pick to join transcript picks.
+const MIN_FUSED_SIGNAL_SCORE: f64 = 0.75;
+
+/// Download the source video into `work_dir` if needed and score its excitement.
+///
+/// The file is kept as `work_dir/source.mp4` so later stages can reuse it.
+/// Stream VODs and clips use their chat replay from `chat_replay_dir` when
+/// one is there; an unreadable replay is logged and skipped.
+pub async fn analyze_source_excitement(
+    video_url: &str,
+    work_dir: &Path,
+    chat_replay_dir: Option<&Path>,
+    config: &ExcitementConfig,
+) -> WorkerResult<ExcitementProfile> {
+    let source = work_dir.join("source.mp4");
+    if !source.exists() {
+        vclip_media::download_video(video_url, &source)
+            .await
+            .map_err(|e| WorkerError::DownloadFailed(format!("Source download failed: {}", e)))?;
+    }
+
+    let chat = match chat_replay_dir.and_then(|dir| find_chat_replay(video_url, dir)) {
+        Some(path) => match ChatReplay::load(&path).await {
+            Ok(replay) => {
+                info!(path = %path.display(), messages = replay.len(), "Loaded chat replay");
+                Some(replay)
+            }
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Failed to read chat replay, skipping chat signal");
+                None
+            }
+        },
+        None => None,
+    };
+
+    let profile = analyze_excitement(&source, chat.as_ref(), config).await?;
+    info!(seconds = profile.len(), "Scored source video excitement");
+    Ok(profile)
+}
+
+/// Chat replay export for a Twitch or Kick source, if one is in `dir`.
+///
+/// Files are named after the source, e.g. `twitch-vod-2101234567.json`
+/// (or `.jsonl`).
+pub fn find_chat_replay(video_url: &str, dir: &Path) -> Option<PathBuf> {
+    let key = parse_stream_url(video_url).ok()?.key();
+    ["json", "jsonl"]
+        .iter()
+        .map(|ext| dir.join(format!("{}.{}", key, ext)))
+        .find(|path| path.is_file())
+}
+
+/// Highlights from excitement signals alone, best first.
+pub fn signal_highlights(
+    profile: &ExcitementProfile,
+    video_url: &str,
+    config: &ExcitementConfig,
+) -> HighlightsResponse {
+    let highlights = profile
+        .candidates(config, &[])
+        .iter()
+        .enumerate()
+        .map(|(i, c)| to_candidate(i as u32 + 1, c))
+        .collect();
+
+    HighlightsResponse {
+        video_url: Some(video_url.to_string()),
+        video_title: None,
+        highlights,
+    }
+}
+
+/// Merge transcript highlights with non-overlapping signal highlights.
+///
+/// Transcript picks are scored by their excitement plus a fixed prior; signal
+/// picks by excitement alone. The result is sorted by that fused score and
+/// renumbered from 1.
+pub fn fuse_highlights(
+    transcript: HighlightsResponse,
+    profile: &ExcitementProfile,
+    config: &ExcitementConfig,
+) -> HighlightsResponse {
+    let mut ranked: Vec<(f64, HighlightCandidate)> = Vec::new();
+    let mut exclude: Vec<(f64, f64)> = Vec::new();
+
+    for highlight in transcript.highlights {
+        let excitement = match (
+            parse_timestamp(&highlight.start),
+            parse_timestamp(&highlight.end),
+        ) {
+            (Ok(start), Ok(end)) if end > start => {
+                exclude.push((start, end));
+                profile.score_range(start, end)
+            }
+            _ => 0.5,
+        };
+        ranked.push((
+            TRANSCRIPT_PRIOR + (1.0 - TRANSCRIPT_PRIOR) * excitement,
+            highlight,
+        ));
+    }
+
+    let signal_config = config.clone().with_max_candidates(MAX_FUSED_SIGNAL_PICKS);
+    for candidate in profile
+        .candidates(&signal_config, &exclude)
+        .iter()
+        .filter(|c| c.score >= MIN_FUSED_SIGNAL_SCORE)
+    {
+        ranked.push((candidate.score, to_candidate(0, candidate)));
+    }
+
+    ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let highlights = ranked
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, mut highlight))| {
+            highlight.id = i as u32 + 1;
+            highlight
+        })
+        .collect();
+
+    HighlightsResponse {
+        highlights,
+        ..transcript
+    }
+}
+
+fn to_candidate(id: u32, candidate: &ExcitementCandidate) -> HighlightCandidate {
+    let highlight = candidate.to_highlight(id);
+    HighlightCandidate {
+        id,
+        title: highlight.title,
+        start: highlight.start,
+        end: highlight.end,
+        duration: highlight.duration,
+        pad_before_seconds: highlight.pad_before,
+        pad_after_seconds: highlight.pad_after,
+        hook_category: None,
+        reason: highlight.reason,
+        description: None,
+        confidence: Some(candidate.score),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vclip_media::excitement::{ExcitementWeights, SignalTimeline};
+
+    fn profile() -> ExcitementProfile {
+        let mut timeline = SignalTimeline::new(400.0);
+        timeline.loudness = (0..400)
+            .map(|s| match s {
+                100..130 => -10.0,
+                300..330 => -14.0,
+                _ => -30.0 + (s % 4) as f64,
+            })
+            .collect();
+        ExcitementProfile::new(&timeline, ExcitementWeights::default())
+    }
+
+    fn transcript_pick(id: u32, start: &str, end: &str) -> HighlightCandidate {
+        HighlightCandidate {
+            id,
+            title: format!("Pick {}", id),
+            start: start.to_string(),
+            end: end.to_string(),
+            duration: 30,
+            pad_before_seconds: 1.0,
+            pad_after_seconds: 1.0,
+            hook_category: Some("educational".to_string()),
+            reason: None,
+            description: None,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_find_chat_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("twitch-vod-2101234567.jsonl"), "").unwrap();
+
+        let found = find_chat_replay("https://www.twitch.tv/videos/2101234567", dir.path());
+        assert_eq!(found, Some(dir.path().join("twitch-vod-2101234567.jsonl")));
+        assert_eq!(
+            find_chat_replay("https://www.twitch.tv/videos/999", dir.path()),
+            None
+        );
+        assert_eq!(
+            find_chat_replay("https://youtu.be/dQw4w9WgXcQ", dir.path()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_signal_highlights_are_ranked() {
+        let response = signal_highlights(&profile(), "u", &ExcitementConfig::default());
+        assert!(!response.highlights.is_empty());
+        let top = &response.highlights[0];
+        assert_eq!(top.id, 1);
+        assert!(parse_timestamp(&top.start).unwrap() < 130.0);
+        assert!(top.reason.as_deref().unwrap().contains("loud audio"));
+    }
+
+    #[test]
+    fn test_fuse_adds_non_overlapping_signal_picks() {
+        let transcript = HighlightsResponse {
+            video_url: Some("u".to_string()),
+            video_title: Some("Title".to_string()),
+            highlights: vec![
+                transcript_pick(1, "00:00:10", "00:00:40"),
+                transcript_pick(2, "00:04:55", "00:05:35"),
+            ],
+        };
+        let fused = fuse_highlights(transcript, &profile(), &ExcitementConfig::default());
+
+        assert_eq!(fused.video_title.as_deref(), Some("Title"));
+        let ids: Vec<u32> = fused.highlights.iter().map(|h| h.id).collect();
+        assert_eq!(ids, (1..=fused.highlights.len() as u32).collect::<Vec<_>>());
+
+        // The loud 100-130s stretch is added and outranks the quiet transcript pick
+        let signal_pos = fused
+            .highlights
+            .iter()
+            .position(|h| h.hook_category.is_none())
+            .unwrap();
+        let quiet_pos = fused
+            .highlights
+            .iter()
+            .position(|h| h.title == "Pick 1")
+            .unwrap();
+        assert!(signal_pos < quiet_pos);
+
+        // Transcript pick over the 300-330s peak is kept, no signal duplicate
+        let covering = fused
+            .highlights
+            .iter()
+            .filter(|h| {
+                let start = parse_timestamp(&h.start).unwrap();
+                let end = parse_timestamp(&h.end).unwrap();
+                start < 330.0 && end > 300.0
+            })
+            .count();
+        assert_eq!(covering, 1);
+    }
+}
@@ -7,10 +7,11 @@
 //! # Caching Strategy
 //!
 //! Silence-removed segments are cached in R2 to avoid reprocessing:
-//! 1. Check R2 cache first using `silence_removed_r2_key`
-//! 2. Check local filesystem (from current session)
-//! 3. If not cached, analyze with VAD and apply removal
-//! 4. Upload result to R2 for future requests
+//! 1. Analyze with VAD (the Keep/Cut map is returned with every hit)
+//! 2. Check R2 cache using `silence_removed_r2_key`
+//! 3. Check local filesystem (from current session)
+//! 4. If not cached, apply removal
+//! 5. Upload result to R2 for future requests
 //!
 //! # Architecture
 //!
@@ -32,17 +33,26 @@ use crate::error::{WorkerError, WorkerResult};
 use crate::processor::EnhancedProcessingContext;
 use crate::raw_segment_cache::silence_removed_r2_key;
 
+/// A silence-removed segment together with the cut that produced it.
+#[derive(Debug)]
+pub struct SilenceRemovedSegment {
+    /// Path to the silence-removed file
+    pub path: PathBuf,
+    /// Keep/Cut segments of the raw segment, used to remap source timings
+    pub segments: Vec<Segment>,
+}
+
 /// Result of applying silence removal to a segment.
 #[derive(Debug)]
 pub enum SilenceRemovalResult {
-    /// Silence was removed, new file created at path
-    Applied(PathBuf),
+    /// Silence was removed, new file created
+    Applied(SilenceRemovedSegment),
     /// No significant silence detected, use original segment
     NotNeeded,
     /// Used cached version from R2
-    CacheHit(PathBuf),
+    CacheHit(SilenceRemovedSegment),
     /// Used existing local file from current session
-    LocalHit(PathBuf),
+    LocalHit(SilenceRemovedSegment),
 }
 
 impl SilenceRemovalResult {
@@ -50,19 +60,24 @@ impl SilenceRemovalResult {
     /// Returns `Some(path)` for Applied/CacheHit/LocalHit, `None` for NotNeeded.
     pub fn path(&self) -> Option<&PathBuf> {
         match self {
-            SilenceRemovalResult::Applied(p) |
-            SilenceRemovalResult::CacheHit(p) |
-            SilenceRemovalResult::LocalHit(p) => Some(p),
+            SilenceRemovalResult::Applied(r) |
+            SilenceRemovalResult::CacheHit(r) |
+            SilenceRemovalResult::LocalHit(r) => Some(&r.path),
             SilenceRemovalResult::NotNeeded => None,
         }
     }
 
     /// Convert to Option<PathBuf>, consuming self.
     pub fn into_path(self) -> Option<PathBuf> {
+        self.into_removed().map(|r| r.path)
+    }
+
+    /// Convert to the removed segment and its cut, consuming self.
+    pub fn into_removed(self) -> Option<SilenceRemovedSegment> {
         match self {
-            SilenceRemovalResult::Applied(p) |
-            SilenceRemovalResult::CacheHit(p) |
-            SilenceRemovalResult::LocalHit(p) => Some(p),
+            SilenceRemovalResult::Applied(r) |
+            SilenceRemovalResult::CacheHit(r) |
+            SilenceRemovalResult::LocalHit(r) => Some(r),
             SilenceRemovalResult::NotNeeded => None,
         }
     }
@@ -133,32 +148,43 @@ impl<'a> SilenceRemovalService<'a> {
         user_id: &str,
         video_id: &str,
     ) -> WorkerResult<SilenceRemovalResult> {
+        // 1. Analyze audio; the cut is needed for cached files too, to remap timings
+        let Some(segments) = self.analyze(raw_segment, scene_id).await else {
+            return Ok(SilenceRemovalResult::NotNeeded);
+        };
+
         let output_path = self.output_path(raw_segment, scene_id);
         let r2_key = silence_removed_r2_key(user_id, video_id, scene_id);
 
-        // 1. Check R2 cache
-        if let Some(result) = self.try_r2_cache(&r2_key, &output_path, scene_id).await {
-            return Ok(result);
+        // 2. Check R2 cache
+        if self.try_r2_cache(&r2_key, &output_path, scene_id).await {
+            return Ok(SilenceRemovalResult::CacheHit(SilenceRemovedSegment {
+                path: output_path,
+                segments,
+            }));
         }
 
-        // 2. Check local cache
-        if let Some(result) = self.try_local_cache(&output_path, scene_id).await {
-            return Ok(result);
+        // 3. Check local cache
+        if self.try_local_cache(&output_path, scene_id).await {
+            return Ok(SilenceRemovalResult::LocalHit(SilenceRemovedSegment {
+                path: output_path,
+                segments,
+            }));
         }
 
-        // 3. Analyze and apply
-        let result = self
-            .analyze_and_apply(raw_segment, &output_path, scene_id, job_id)
+        // 4. Apply
+        self.apply(raw_segment, &output_path, &segments, scene_id, job_id)
             .await?;
 
-        // 4. Upload to R2 if applied and caching enabled
-        if let SilenceRemovalResult::Applied(ref path) = result {
-            if self.config.enable_r2_cache {
-                self.upload_to_r2(path, &r2_key, scene_id).await;
-            }
+        // 5. Upload to R2 if caching enabled
+        if self.config.enable_r2_cache {
+            self.upload_to_r2(&output_path, &r2_key, scene_id).await;
         }
 
-        Ok(result)
+        Ok(SilenceRemovalResult::Applied(SilenceRemovedSegment {
+            path: output_path,
+            segments,
+        }))
     }
 
     /// Generate output path for silence-removed segment.
@@ -173,9 +199,9 @@ impl<'a> SilenceRemovalService<'a> {
         r2_key: &str,
         output_path: &Path,
         scene_id: u32,
-    ) -> Option<SilenceRemovalResult> {
+    ) -> bool {
         if !self.ctx.raw_cache.check_raw_exists(r2_key).await {
-            return None;
+            return false;
         }
 
         match self.ctx.storage.download_file(r2_key, output_path).await {
@@ -185,7 +211,7 @@ impl<'a> SilenceRemovalService<'a> {
                     r2_key = %r2_key,
                     "Using cached silence-removed segment from R2"
                 );
-                Some(SilenceRemovalResult::CacheHit(output_path.to_path_buf()))
+                true
             }
             Err(e) => {
                 debug!(
@@ -193,7 +219,7 @@ impl<'a> SilenceRemovalService<'a> {
                     error = %e,
                     "Failed to download cached silence-removed segment, will regenerate"
                 );
-                None
+                false
             }
         }
     }
@@ -203,9 +229,9 @@ impl<'a> SilenceRemovalService<'a> {
         &self,
         output_path: &Path,
         scene_id: u32,
-    ) -> Option<SilenceRemovalResult> {
+    ) -> bool {
         if !output_path.exists() {
-            return None;
+            return false;
         }
 
         match tokio::fs::metadata(output_path).await {
@@ -215,27 +241,20 @@ impl<'a> SilenceRemovalService<'a> {
                     path = ?output_path,
                     "Using existing local silence-removed segment"
                 );
-                Some(SilenceRemovalResult::LocalHit(output_path.to_path_buf()))
+                true
             }
-            _ => None,
+            _ => false,
         }
     }
 
-    /// Analyze audio and apply silence removal if needed.
-    async fn analyze_and_apply(
-        &self,
-        raw_segment: &Path,
-        output_path: &Path,
-        scene_id: u32,
-        job_id: &JobId,
-    ) -> WorkerResult<SilenceRemovalResult> {
+    /// Analyze audio and return the Keep/Cut segments if silence removal should apply.
+    async fn analyze(&self, raw_segment: &Path, scene_id: u32) -> Option<Vec<Segment>> {
         debug!(
             scene_id = scene_id,
             segment = ?raw_segment,
             "Analyzing audio for silence detection"
         );
 
-        // Analyze audio segments
         let segments = match analyze_audio_segments(raw_segment, self.config.vad_config.clone()).await {
             Ok(s) => s,
             Err(e) => {
@@ -244,15 +263,22 @@ impl<'a> SilenceRemovalService<'a> {
                     error = %e,
                     "Silence analysis failed (may be too short or no audio)"
                 );
-                return Ok(SilenceRemovalResult::NotNeeded);
+                return None;
             }
         };
 
-        // Check if silence removal should be applied
-        if !should_apply_silence_removal(&segments, &self.config.vad_config) {
-            return Ok(SilenceRemovalResult::NotNeeded);
-        }
+        should_apply_silence_removal(&segments, &self.config.vad_config).then_some(segments)
+    }
 
+    /// Cut the analyzed silent parts out of the raw segment.
+    async fn apply(
+        &self,
+        raw_segment: &Path,
+        output_path: &Path,
+        segments: &[Segment],
+        scene_id: u32,
+        job_id: &JobId,
+    ) -> WorkerResult<()> {
         // Log progress
         self.ctx
             .progress
@@ -261,7 +287,7 @@ impl<'a> SilenceRemovalService<'a> {
             .ok();
 
         // Apply silence removal
-        apply_silence_removal(raw_segment, output_path, &segments)
+        apply_silence_removal(raw_segment, output_path, segments)
             .await
             .map_err(|e| WorkerError::job_failed(&format!("Silence removal failed: {}", e)))?;
 
@@ -271,9 +297,9 @@ impl<'a> SilenceRemovalService<'a> {
         }
 
         // Log duration statistics (meaningful metric for silence removal)
-        self.log_duration_statistics(&segments, scene_id);
+        self.log_duration_statistics(segments, scene_id);
 
-        Ok(SilenceRemovalResult::Applied(output_path.to_path_buf()))
+        Ok(())
     }
 
     /// Log duration statistics after silence removal.
@@ -342,21 +368,45 @@ pub async fn apply_silence_removal_cached(
     Ok(result.into_path())
 }
 
+/// Like [`apply_silence_removal_cached`], but also returns the Keep/Cut segments
+/// the removed file was cut with, for callers that remap source timings.
+pub async fn apply_silence_removal_with_segments(
+    ctx: &EnhancedProcessingContext,
+    raw_segment: &Path,
+    scene_id: u32,
+    job_id: &JobId,
+    user_id: &str,
+    video_id: &str,
+) -> WorkerResult<Option<SilenceRemovedSegment>> {
+    let service = SilenceRemovalService::new(ctx);
+    let result = service
+        .apply_cached(raw_segment, scene_id, job_id, user_id, video_id)
+        .await?;
+    Ok(result.into_removed())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn removed(path: &Path) -> SilenceRemovedSegment {
+        SilenceRemovedSegment {
+            path: path.to_path_buf(),
+            segments: Vec::new(),
+        }
+    }
+
     #[test]
     fn test_silence_removal_result_path() {
         let path = PathBuf::from("/tmp/test.mp4");
         
-        let applied = SilenceRemovalResult::Applied(path.clone());
+        let applied = SilenceRemovalResult::Applied(removed(&path));
         assert_eq!(applied.path(), Some(&path));
         
-        let cache_hit = SilenceRemovalResult::CacheHit(path.clone());
+        let cache_hit = SilenceRemovalResult::CacheHit(removed(&path));
         assert_eq!(cache_hit.path(), Some(&path));
         
-        let local_hit = SilenceRemovalResult::LocalHit(path.clone());
+        let local_hit = SilenceRemovalResult::LocalHit(removed(&path));
         assert_eq!(local_hit.path(), Some(&path));
         
         let not_needed = SilenceRemovalResult::NotNeeded;
@@ -368,13 +418,14 @@ mod tests {
         let path = PathBuf::from("/tmp/test.mp4");
         
         assert_eq!(
-            SilenceRemovalResult::Applied(path.clone()).into_path(),
+            SilenceRemovalResult::Applied(removed(&path)).into_path(),
             Some(path.clone())
         );
         assert_eq!(
             SilenceRemovalResult::NotNeeded.into_path(),
             None
         );
+        assert!(SilenceRemovalResult::NotNeeded.into_removed().is_none());
     }
 
     #[test]
@@ -236,7 +236,8 @@ async fn process_segments_parallel(
     
     // Channel for source download completion
     let (source_tx, source_rx) = oneshot::channel::<Result<PathBuf, WorkerError>>();
-    let source_download_handle: Option<tokio::task::JoinHandle<()>>;
+    // Aborted on drop, so a cancelled job also stops the download
+    let mut source_download = tokio::task::JoinSet::new();
     
     // Start source download in background if needed
     if !need_extraction.is_empty() {
@@ -245,14 +246,13 @@ async fn process_segments_parallel(
         if video_file.exists() {
             // Source already exists
             let _ = source_tx.send(Ok(video_file));
-            source_download_handle = None;
         } else if let Some(url) = video_url {
             let url = url.to_string();
             let video_file_clone = video_file.clone();
             let job_id = job.job_id.clone();
             let progress = ctx.progress.clone();
             
-            source_download_handle = Some(tokio::spawn(async move {
+            source_download.spawn(async move {
                 progress
                     .log(&job_id, "Downloading source video (background)...")
                     .await
@@ -263,14 +263,13 @@ async fn process_segments_parallel(
                     Err(e) => Err(WorkerError::job_failed(&format!("Failed to download source: {}", e))),
                 };
                 let _ = source_tx.send(result);
-            }));
+            });
         } else {
             return Err(WorkerError::job_failed("No source video available for uncached segments"));
         }
     } else {
         // No extraction needed, close the channel
         drop(source_tx);
-        source_download_handle = None;
     }
 
     // Download R2 cached segments in parallel
@@ -670,10 +669,8 @@ async fn process_segments_parallel(
         }
     }
 
-    // Clean up source download handle
-    if let Some(handle) = source_download_handle {
-        handle.await.ok();
-    }
+    // Wait for the source download task to finish
+    while source_download.join_next().await.is_some() {}
 
     Ok(result)
 }
@@ -778,6 +775,7 @@ async fn render_compilation(
         &encoding,
         &streamer_params,
         watermark.as_ref(),
+        &vclip_media::intelligent::OutputFormat::new(job.target_aspect, job.resolution),
     )
     .await
     .map_err(|e| {
@@ -888,6 +886,8 @@ async fn finalize_compilation(
         r2_key,
         thumbnail_r2_key: thumb_key,
         raw_r2_key: None,
+        variants: Vec::new(),
+        loudness: None,
         status: ClipStatus::Completed,
         created_at: chrono::Utc::now(),
         completed_at: Some(chrono::Utc::now()),
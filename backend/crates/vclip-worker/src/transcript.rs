@@ -7,7 +7,9 @@
 //! 4. YouTube Data API v3 (official API) - requires API key
 //! 5. Apify YouTube Scraper (last resort) - external API
 //!
-//! Falls back to direct yt-dlp if the multi-strategy service is unavailable.
+//! Falls back to direct yt-dlp if the multi-strategy service is unavailable,
+//! and finally to offline speech-to-text when the video has no captions.
+//! Uploaded files and Twitch/Kick VODs and clips go straight to speech-to-text.
 
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -15,6 +17,9 @@ use std::time::Duration;
 use serde::Deserialize;
 use tracing::{debug, info, warn};
 
+use vclip_media::{format_transcript, is_transcription_available, transcribe_file, TranscriptionConfig};
+use vclip_models::{is_stream_platform_url, is_upload_source};
+
 use crate::error::{WorkerError, WorkerResult};
 
 /// Timeout for the multi-strategy transcript service (3 minutes)
@@ -25,19 +30,60 @@ const YTDLP_FALLBACK_TIMEOUT_SECS: u64 = 180;
 
 /// Fetch a timestamped transcript for a video URL.
 ///
-/// Tries the multi-strategy transcript service first, falling back to direct yt-dlp.
+/// Tries the multi-strategy transcript service first, falling back to direct
+/// yt-dlp and then to transcribing the audio offline.
 pub async fn fetch_transcript(video_url: &str, workdir: &Path) -> WorkerResult<String> {
     tokio::fs::create_dir_all(workdir).await?;
 
-    // Try multi-strategy service first
-    if let Some(transcript) = try_multi_strategy_service(video_url).await? {
-        persist_transcript(workdir, &transcript).await;
-        return Ok(transcript);
+    // Uploaded files and stream VODs/clips have no captions to fetch
+    if !is_upload_source(video_url) && !is_stream_platform_url(video_url) {
+        // Try multi-strategy service first
+        if let Some(transcript) = try_multi_strategy_service(video_url).await? {
+            persist_transcript(workdir, &transcript).await;
+            return Ok(transcript);
+        }
+
+        // Fallback to direct yt-dlp
+        warn!("Multi-strategy service failed, falling back to direct yt-dlp");
+        match fetch_transcript_ytdlp(video_url, workdir).await {
+            Ok(transcript) => return Ok(transcript),
+            Err(e) if is_transcription_available() => {
+                warn!(error = %e, "No captions available, transcribing audio offline");
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    fetch_transcript_offline(video_url, workdir).await
+}
+
+/// Transcribe the source video's audio with the local speech-to-text model.
+///
+/// Uses `workdir/source.mp4` if it is already there (uploads, or a previous
+/// stage), downloading it otherwise; later stages reuse the file.
+async fn fetch_transcript_offline(video_url: &str, workdir: &Path) -> WorkerResult<String> {
+    let source = workdir.join("source.mp4");
+    if !source.exists() {
+        if is_upload_source(video_url) {
+            return Err(WorkerError::ai_failed("Uploaded source is missing from the work directory"));
+        }
+        vclip_media::download_video(video_url, &source)
+            .await
+            .map_err(|e| WorkerError::DownloadFailed(format!("Source download failed: {}", e)))?;
     }
 
-    // Fallback to direct yt-dlp
-    warn!("Multi-strategy service failed, falling back to direct yt-dlp");
-    fetch_transcript_ytdlp(video_url, workdir).await
+    info!(video_url = %video_url, "Transcribing audio with offline speech-to-text");
+    let segments = transcribe_file(&source, &TranscriptionConfig::default())
+        .await
+        .map_err(|e| WorkerError::ai_failed(format!("Offline transcription failed: {}", e)))?;
+
+    let transcript = format_transcript(&segments);
+    if transcript.is_empty() {
+        return Err(WorkerError::ai_failed("No speech found in the video"));
+    }
+
+    persist_transcript(workdir, &transcript).await;
+    Ok(transcript)
 }
 
 /// Output from the multi-strategy transcript CLI
@@ -74,6 +120,7 @@ async fn try_multi_strategy_service(video_url: &str) -> WorkerResult<Option<Stri
         tokio::process::Command::new("node")
             .arg(&script_path)
             .arg(video_url)
+            .kill_on_drop(true)
             .output(),
     )
     .await
@@ -218,7 +265,10 @@ async fn fetch_transcript_ytdlp(video_url: &str, workdir: &Path) -> WorkerResult
 
     let output = tokio::time::timeout(
         Duration::from_secs(YTDLP_FALLBACK_TIMEOUT_SECS),
-        tokio::process::Command::new("yt-dlp").args(&args).output(),
+        tokio::process::Command::new("yt-dlp")
+            .args(&args)
+            .kill_on_drop(true)
+            .output(),
     )
     .await
     .map_err(|_| WorkerError::ai_failed("yt-dlp timed out"))?
@@ -6,6 +6,7 @@
 
 use tracing::{debug, info, warn};
 use vclip_firestore::{FirestoreClient, FromFirestoreValue};
+use vclip_highlights::HighlightProviderKind;
 use vclip_models::PlanTier;
 
 /// Resolved user plan with associated features and limits.
@@ -138,6 +139,41 @@ pub async fn user_requires_watermark(
     requires
 }
 
+/// Resolve the user's preferred highlight provider, if they chose one.
+///
+/// Returns `None` (use the deployment default) if the user document is
+/// missing, has no `highlight_provider` field, names an unknown provider, or
+/// the lookup fails.
+pub async fn resolve_highlight_provider(
+    firestore: &FirestoreClient,
+    user_id: &str,
+) -> Option<HighlightProviderKind> {
+    let doc = match firestore.get_document("users", user_id).await {
+        Ok(doc) => doc?,
+        Err(e) => {
+            warn!(
+                user_id = %user_id,
+                error = %e,
+                "Failed to fetch user document, using default highlight provider"
+            );
+            return None;
+        }
+    };
+
+    let name = doc
+        .fields?
+        .get("highlight_provider")
+        .and_then(String::from_firestore_value)?;
+
+    match name.parse() {
+        Ok(kind) => Some(kind),
+        Err(e) => {
+            warn!(user_id = %user_id, error = %e, "Ignoring invalid highlight provider");
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,120 @@
+//! Video metadata lookup via yt-dlp.
+
+use tracing::{debug, info, warn};
+
+use crate::error::{WorkerError, WorkerResult};
+
+/// Get video metadata (title, canonical URL) using yt-dlp.
+pub async fn fetch_video_metadata(video_url: &str) -> WorkerResult<(String, String)> {
+    info!("Getting video metadata for {} using yt-dlp", video_url);
+
+    // Use cookies file if available for YouTube authentication (copy to writable location)
+    let cookies_path = vclip_media::get_writable_cookies_path().await;
+    let mut args = vec![
+        "--verbose",
+        "--remote-components",
+        "ejs:github",
+        "--print",
+        "title",
+        "--print",
+        "webpage_url",
+        "--no-download",
+        "--no-playlist",
+    ];
+
+    let cookies_ref = cookies_path.as_deref();
+    if let Some(cp) = cookies_ref {
+        args.push("--cookies");
+        args.push(cp);
+    }
+    args.push(video_url);
+
+    let mut child = tokio::process::Command::new("yt-dlp")
+        .args(&args)
+        .kill_on_drop(true)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| WorkerError::ai_failed(format!("Failed to spawn yt-dlp: {}", e)))?;
+
+    // Stream stdout and stderr in real-time
+    let stdout = child.stdout.take().unwrap();
+    let stderr = child.stderr.take().unwrap();
+
+    let mut stdout_reader = tokio::io::BufReader::new(stdout);
+    let mut stderr_reader = tokio::io::BufReader::new(stderr);
+
+    let stdout_handle = tokio::spawn(async move {
+        use tokio::io::AsyncBufReadExt;
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        while let Ok(n) = stdout_reader.read_line(&mut line).await {
+            if n == 0 {
+                break;
+            }
+            let trimmed = line.trim().to_string();
+            if !trimmed.is_empty() {
+                debug!("yt-dlp stdout: {}", trimmed);
+                lines.push(trimmed);
+            }
+            line.clear();
+        }
+        lines
+    });
+
+    let stderr_handle = tokio::spawn(async move {
+        use tokio::io::AsyncBufReadExt;
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        while let Ok(n) = stderr_reader.read_line(&mut line).await {
+            if n == 0 {
+                break;
+            }
+            let trimmed = line.trim().to_string();
+            if !trimmed.is_empty() {
+                warn!("yt-dlp stderr: {}", trimmed);
+                lines.push(trimmed);
+            }
+            line.clear();
+        }
+        lines
+    });
+
+    // Wait for process to finish
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| WorkerError::ai_failed(format!("Failed to wait for yt-dlp: {}", e)))?;
+
+    let stdout_lines = stdout_handle.await.unwrap_or_default();
+    let stderr_lines = stderr_handle.await.unwrap_or_default();
+
+    if !status.success() {
+        return Err(WorkerError::ai_failed(format!(
+            "yt-dlp failed to get metadata: {}",
+            stderr_lines.join("\n")
+        )));
+    }
+
+    if stdout_lines.len() < 2 {
+        return Err(WorkerError::ai_failed(format!(
+            "yt-dlp did not return expected metadata. Output: {:?}",
+            stdout_lines
+        )));
+    }
+
+    let title = stdout_lines[0].trim().to_string();
+    let canonical_url = stdout_lines[1].trim().to_string();
+
+    if title.is_empty() || canonical_url.is_empty() {
+        return Err(WorkerError::ai_failed(
+            "yt-dlp returned empty title or URL".to_string(),
+        ));
+    }
+
+    info!(
+        "Got video metadata: title='{}', url='{}'",
+        title, canonical_url
+    );
+    Ok((title, canonical_url))
+}
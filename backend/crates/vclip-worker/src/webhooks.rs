@@ -0,0 +1,310 @@
+//! Outbound webhook delivery.
+//!
+//! The dispatcher does two things on every round:
+//!
+//! 1. Fan-out: read progress events from the webhook stream, look up the
+//!    job's owner (recorded at enqueue time) and their endpoints, and
+//!    schedule one delivery per endpoint that wants the event type.
+//! 2. Delivery: claim due deliveries, POST the signed payload, and either
+//!    finish the delivery or schedule a retry with exponential backoff.
+//!
+//! Every attempt is written to the endpoint's delivery log in Firestore.
+//! Redirects are not followed, so an endpoint can't bounce requests to
+//! hosts its URL validation would have rejected. Host names are resolved by
+//! [`PublicOnlyResolver`], so a name that resolves (or rebinds) to an
+//! internal address is never connected to.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use vclip_firestore::{FirestoreClient, WebhookRepository};
+use vclip_models::{
+    is_public_ip, sign_webhook, validate_webhook_url, WebhookDelivery, WebhookDeliveryStatus,
+    WebhookPayload,
+    WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER, WEBHOOK_SIGNATURE_HEADER,
+    WEBHOOK_TIMESTAMP_HEADER,
+};
+use vclip_queue::{PendingWebhook, ProgressChannel, ProgressEvent, WebhookQueue};
+
+use crate::error::{WorkerError, WorkerResult};
+
+/// Events fanned out per round.
+const EVENTS_PER_ROUND: usize = 50;
+
+/// Deliveries attempted per round.
+const DELIVERIES_PER_ROUND: usize = 20;
+
+/// How long a round waits for new events.
+const EVENT_BLOCK_MS: u64 = 1000;
+
+/// Timeout of a single delivery request.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest response excerpt kept in the delivery log.
+const MAX_ERROR_LENGTH: usize = 300;
+
+/// DNS resolver that only returns public addresses.
+///
+/// reqwest connects to exactly the addresses returned here, so the check
+/// can't be bypassed by a host that resolves differently on a second lookup.
+struct PublicOnlyResolver;
+
+impl Resolve for PublicOnlyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(&addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} does not resolve to a public address", host).into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Fans progress events out to webhook endpoints and delivers them.
+pub struct WebhookDispatcher {
+    firestore: FirestoreClient,
+    progress: ProgressChannel,
+    queue: WebhookQueue,
+    http: reqwest::Client,
+    consumer_name: String,
+}
+
+impl WebhookDispatcher {
+    /// Create a dispatcher.
+    pub fn new(
+        firestore: FirestoreClient,
+        progress: ProgressChannel,
+        queue: WebhookQueue,
+        consumer_name: impl Into<String>,
+    ) -> WorkerResult<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(PublicOnlyResolver))
+            .user_agent("vclip-webhooks/1.0")
+            .build()
+            .map_err(|e| WorkerError::config_error(format!("Failed to build webhook client: {}", e)))?;
+
+        Ok(Self {
+            firestore,
+            progress,
+            queue,
+            http,
+            consumer_name: consumer_name.into(),
+        })
+    }
+
+    /// Create the event consumer group.
+    pub async fn init(&self) -> WorkerResult<()> {
+        self.queue.init().await?;
+        Ok(())
+    }
+
+    /// Run one round: fan out new events, then attempt due deliveries.
+    pub async fn run_once(&self) -> WorkerResult<()> {
+        self.fan_out().await?;
+        self.deliver_due().await?;
+        Ok(())
+    }
+
+    /// Schedule deliveries for new events.
+    async fn fan_out(&self) -> WorkerResult<()> {
+        let events = self
+            .queue
+            .read_events(&self.consumer_name, EVENTS_PER_ROUND, EVENT_BLOCK_MS)
+            .await?;
+
+        for (message_id, event) in events {
+            match self.fan_out_event(&event).await {
+                Ok(()) => {
+                    self.queue.ack_event(&message_id).await?;
+                }
+                // Left pending; another round reclaims it once idle
+                Err(e) => warn!("Failed to fan out webhook event {}: {}", message_id, e),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn fan_out_event(&self, event: &ProgressEvent) -> WorkerResult<()> {
+        let Some(user_id) = self.progress.get_job_owner(&event.job_id).await? else {
+            debug!("No owner for job {}; skipping webhook event", event.job_id);
+            return Ok(());
+        };
+
+        let created_at = DateTime::from_timestamp_millis(event.timestamp_ms).unwrap_or_else(Utc::now);
+        let Some(payload) = WebhookPayload::from_message(
+            Uuid::new_v4().to_string(),
+            event.job_id.as_str(),
+            &event.message,
+            created_at,
+        ) else {
+            return Ok(());
+        };
+
+        let repo = WebhookRepository::new(self.firestore.clone(), &user_id);
+        let endpoints = repo.list().await?;
+        let body = serde_json::to_string(&payload)
+            .map_err(|e| WorkerError::job_failed(format!("Failed to serialize webhook: {}", e)))?;
+
+        for endpoint in endpoints.iter().filter(|e| e.wants(payload.event_type)) {
+            let delivery = WebhookDelivery::new(&endpoint.id, &payload);
+            repo.record_delivery(&delivery).await?;
+            self.queue
+                .schedule(
+                    &PendingWebhook {
+                        delivery_id: delivery.id.clone(),
+                        user_id: user_id.clone(),
+                        endpoint_id: endpoint.id.clone(),
+                        event_type: payload.event_type,
+                        body: body.clone(),
+                        attempts: 0,
+                    },
+                    delivery.created_at,
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Attempt deliveries that are due.
+    async fn deliver_due(&self) -> WorkerResult<()> {
+        let due = self.queue.claim_due(DELIVERIES_PER_ROUND).await?;
+        join_all(due.into_iter().map(|pending| async move {
+            let delivery_id = pending.delivery_id.clone();
+            if let Err(e) = self.deliver(pending).await {
+                // The lease expires and the delivery is claimed again
+                warn!("Failed to process webhook delivery {}: {}", delivery_id, e);
+            }
+        }))
+        .await;
+        Ok(())
+    }
+
+    async fn deliver(&self, mut pending: PendingWebhook) -> WorkerResult<()> {
+        let repo = WebhookRepository::new(self.firestore.clone(), &pending.user_id);
+        let payload: WebhookPayload = serde_json::from_str(&pending.body)
+            .map_err(|e| WorkerError::job_failed(format!("Malformed webhook body: {}", e)))?;
+
+        let mut delivery = match repo.get_delivery(&pending.endpoint_id, &pending.delivery_id).await? {
+            Some(delivery) => delivery,
+            None => {
+                let mut delivery = WebhookDelivery::new(&pending.endpoint_id, &payload);
+                delivery.id = pending.delivery_id.clone();
+                delivery
+            }
+        };
+        delivery.attempts = pending.attempts;
+
+        let endpoint = match repo.get(&pending.endpoint_id).await? {
+            Some(endpoint) if endpoint.wants(pending.event_type) => endpoint,
+            other => {
+                // Endpoint deleted, disabled or no longer subscribed
+                if other.is_some() {
+                    delivery.status = WebhookDeliveryStatus::Failed;
+                    delivery.error = Some("Endpoint no longer accepts this event".to_string());
+                    delivery.next_attempt_at = None;
+                    delivery.updated_at = Utc::now();
+                    repo.record_delivery(&delivery).await?;
+                }
+                self.queue.complete(&pending.delivery_id).await?;
+                return Ok(());
+            }
+        };
+
+        // Endpoints saved before a validation rule existed, or IP literals
+        // (which skip the resolver), are checked again before every attempt
+        if let Err(reason) = validate_webhook_url(&endpoint.url) {
+            delivery.status = WebhookDeliveryStatus::Failed;
+            delivery.error = Some(reason.to_string());
+            delivery.next_attempt_at = None;
+            delivery.updated_at = Utc::now();
+            repo.record_delivery(&delivery).await?;
+            self.queue.complete(&pending.delivery_id).await?;
+            return Ok(());
+        }
+
+        let timestamp = Utc::now().timestamp();
+        let signature = sign_webhook(&endpoint.secret, timestamp, &pending.body);
+        let result = self
+            .http
+            .post(&endpoint.url)
+            .header("Content-Type", "application/json")
+            .header(WEBHOOK_EVENT_HEADER, pending.event_type.as_str())
+            .header(WEBHOOK_DELIVERY_HEADER, &pending.delivery_id)
+            .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+            .header(WEBHOOK_SIGNATURE_HEADER, format!("v1={}", signature))
+            .body(pending.body.clone())
+            .send()
+            .await;
+
+        let next_attempt = match result {
+            Ok(response) if response.status().is_success() => {
+                delivery.record_success(response.status().as_u16());
+                None
+            }
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let excerpt: String = response
+                    .text()
+                    .await
+                    .unwrap_or_default()
+                    .chars()
+                    .take(MAX_ERROR_LENGTH)
+                    .collect();
+                delivery.record_failure(Some(status), format!("HTTP {}: {}", status, excerpt.trim()))
+            }
+            Err(e) => delivery.record_failure(None, e.to_string()),
+        };
+
+        repo.record_delivery(&delivery).await?;
+
+        match next_attempt {
+            Some(due) => {
+                pending.attempts = delivery.attempts;
+                self.queue.schedule(&pending, due).await?;
+                debug!(
+                    "Webhook delivery {} to {} failed (attempt {}), retrying at {}",
+                    delivery.id, endpoint.url, delivery.attempts, due
+                );
+            }
+            None => {
+                self.queue.complete(&pending.delivery_id).await?;
+                if delivery.status == WebhookDeliveryStatus::Failed {
+                    info!(
+                        "Webhook delivery {} to {} failed after {} attempts",
+                        delivery.id, endpoint.url, delivery.attempts
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolver_rejects_internal_hosts() {
+        let name: Name = "localhost".parse().unwrap();
+        assert!(PublicOnlyResolver.resolve(name).await.is_err());
+    }
+}
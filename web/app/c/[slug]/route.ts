@@ -7,8 +7,10 @@ import type { NextRequest } from "next/server";
  *
  * GET /c/{share_slug}
  *
- * This route proxies to the backend API which resolves the share slug
- * and returns a 302 redirect to a short-lived presigned URL for playback.
+ * This route proxies to the backend API which resolves the share slug.
+ * Browsers and link unfurlers get the embeddable player page (HTML with
+ * OpenGraph/oEmbed metadata); `?raw=1`, `?download=true` and other clients
+ * get a 302 redirect to a short-lived URL for playback.
  */
 export async function GET(
   request: NextRequest,
@@ -34,15 +36,28 @@ export async function GET(
 
   try {
     // Proxy to backend /c/{share_slug} endpoint
-    const backendUrl = `${apiUrl}/c/${encodeURIComponent(slug)}`;
+    const backendUrl = new URL(`${apiUrl}/c/${encodeURIComponent(slug)}`);
+    for (const param of ["raw", "embed", "download"]) {
+      const value = request.nextUrl.searchParams.get(param);
+      if (value !== null) {
+        backendUrl.searchParams.set(param, value);
+      }
+    }
+
+    const headers: Record<string, string> = {
+      Accept: request.headers.get("accept") ?? "video/mp4,*/*",
+      "User-Agent": request.headers.get("user-agent") ?? "ViralClipAI-Web",
+    };
+    const forwardedFor = request.headers.get("x-forwarded-for");
+    if (forwardedFor) {
+      headers["X-Forwarded-For"] = forwardedFor;
+    }
 
     const response = await fetch(backendUrl, {
       method: "GET",
       redirect: "manual", // Don't follow redirects, we want to return them
-      headers: {
-        Accept: "video/mp4,*/*",
-        "User-Agent": request.headers.get("user-agent") ?? "ViralClipAI-Web",
-      },
+      cache: "no-store",
+      headers,
     });
 
     // If backend returns a redirect (302), pass it through
@@ -53,10 +68,29 @@ export async function GET(
       }
     }
 
+    // Player page: pass it through with its CSP and caching headers
+    const contentType = response.headers.get("content-type") ?? "";
+    if (response.ok && contentType.includes("text/html")) {
+      const passthrough = new Headers();
+      for (const header of [
+        "content-type",
+        "content-security-policy",
+        "cache-control",
+        "vary",
+      ]) {
+        const value = response.headers.get(header);
+        if (value) {
+          passthrough.set(header, value);
+        }
+      }
+      return new NextResponse(await response.text(), {
+        status: response.status,
+        headers: passthrough,
+      });
+    }
+
     // If backend returns an error, pass it through
     if (!response.ok) {
-      const contentType = response.headers.get("content-type") ?? "";
-
       if (contentType.includes("application/json")) {
         const errorData = await response.json();
         return NextResponse.json(errorData, { status: response.status });
@@ -0,0 +1,63 @@
+import { NextResponse } from "next/server";
+
+import type { NextRequest } from "next/server";
+
+/**
+ * Unlock route for protected share links.
+ *
+ * POST /c/{share_slug}/unlock
+ *
+ * Used by the player page's passcode form. Proxies to the backend, which
+ * checks the passcode (or the signed-in viewer) and returns a short-lived
+ * playback URL.
+ */
+export async function POST(
+  request: NextRequest,
+  { params }: { params: Promise<{ slug: string }> }
+): Promise<NextResponse> {
+  const { slug } = await params;
+
+  if (!slug || !/^[a-zA-Z0-9]{8,16}$/.test(slug)) {
+    return NextResponse.json({ error: "Invalid share link" }, { status: 400 });
+  }
+
+  const apiUrl =
+    process.env.NEXT_PUBLIC_API_URL ??
+    process.env.API_URL ??
+    "https://api.viralclipai.io";
+
+  const headers: Record<string, string> = {
+    "Content-Type": "application/json",
+    "User-Agent": request.headers.get("user-agent") ?? "ViralClipAI-Web",
+  };
+  // Passcode attempts are rate-limited per client IP
+  const forwardedFor = request.headers.get("x-forwarded-for");
+  if (forwardedFor) {
+    headers["X-Forwarded-For"] = forwardedFor;
+  }
+  const authorization = request.headers.get("authorization");
+  if (authorization) {
+    headers.Authorization = authorization;
+  }
+
+  try {
+    const response = await fetch(`${apiUrl}/c/${encodeURIComponent(slug)}/unlock`, {
+      method: "POST",
+      cache: "no-store",
+      headers,
+      body: await request.text(),
+    });
+
+    const contentType = response.headers.get("content-type") ?? "";
+    if (contentType.includes("application/json")) {
+      return NextResponse.json(await response.json(), { status: response.status });
+    }
+    return NextResponse.json(
+      { error: "Failed to unlock share link" },
+      { status: response.status }
+    );
+  } catch (error) {
+    console.error("[share-unlock-route] Failed to unlock share:", error);
+    return NextResponse.json({ error: "Failed to unlock share link" }, { status: 502 });
+  }
+}
@@ -0,0 +1,45 @@
+import { NextResponse } from "next/server";
+
+import type { NextRequest } from "next/server";
+
+/**
+ * oEmbed endpoint for share links.
+ *
+ * GET /oembed?url={share_url}&maxwidth=..&maxheight=..
+ *
+ * Discovered through the `<link rel="alternate" type="application/json+oembed">`
+ * tag of the share player page; proxies to the backend.
+ */
+export async function GET(request: NextRequest): Promise<NextResponse> {
+  const apiUrl =
+    process.env.NEXT_PUBLIC_API_URL ??
+    process.env.API_URL ??
+    "https://api.viralclipai.io";
+
+  const backendUrl = new URL(`${apiUrl}/oembed`);
+  for (const param of ["url", "maxwidth", "maxheight", "format"]) {
+    const value = request.nextUrl.searchParams.get(param);
+    if (value !== null) {
+      backendUrl.searchParams.set(param, value);
+    }
+  }
+
+  try {
+    const response = await fetch(backendUrl, {
+      method: "GET",
+      cache: "no-store",
+      headers: {
+        "User-Agent": request.headers.get("user-agent") ?? "ViralClipAI-Web",
+      },
+    });
+
+    const contentType = response.headers.get("content-type") ?? "";
+    if (contentType.includes("application/json")) {
+      return NextResponse.json(await response.json(), { status: response.status });
+    }
+    return new NextResponse(await response.text(), { status: response.status });
+  } catch (error) {
+    console.error("[oembed-route] Failed to load oEmbed:", error);
+    return NextResponse.json({ error: "Failed to load oEmbed" }, { status: 502 });
+  }
+}
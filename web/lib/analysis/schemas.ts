@@ -107,6 +107,7 @@ export const ProcessDraftResponseSchema = z.object({
   success: z.boolean(),
   draft_id: z.string(),
   video_id: z.string(),
+  job_id: z.string(),
   jobs_enqueued: z.number().int().min(0),
 });
 
@@ -123,6 +123,8 @@ export interface ProcessDraftResponse {
   success: boolean;
   draft_id: string;
   video_id: string;
+  /** Parent job of the render jobs; cancel it to stop them all */
+  job_id: string;
   jobs_enqueued: number;
 }
 
@@ -48,6 +48,12 @@ export interface CreateShareRequest {
   access_level?: ShareAccessLevel;
   expires_in_hours?: number;
   watermark_enabled?: boolean;
+  /** Passcode viewers must enter (stored hashed) */
+  passcode?: string;
+  /** Stop resolving after this many views */
+  max_views?: number;
+  /** Emails or email domains allowed to view (viewers must sign in) */
+  allowed_viewers?: string[];
 }
 
 /** Share creation response */
@@ -58,6 +64,9 @@ export interface ShareResponse {
   /** When the share expires (ISO 8601). May be undefined if field is omitted from JSON. */
   expires_at?: string | null;
   watermark_enabled: boolean;
+  passcode_protected: boolean;
+  max_views?: number | null;
+  allowed_viewers?: string[];
   created_at: string;
 }
 
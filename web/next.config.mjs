@@ -79,8 +79,27 @@ const nextConfig = {
     ].join(", ");
 
     return [
+      // Share links (/c/{slug}) serve the embeddable player page, which sets
+      // its own CSP (frame-ancestors *), so only the framing-neutral headers apply
       {
-        source: "/:path*",
+        source: "/c/:path*",
+        headers: [
+          {
+            key: "Strict-Transport-Security",
+            value: "max-age=63072000; includeSubDomains; preload",
+          },
+          {
+            key: "X-Content-Type-Options",
+            value: "nosniff",
+          },
+          {
+            key: "Referrer-Policy",
+            value: "strict-origin-when-cross-origin",
+          },
+        ],
+      },
+      {
+        source: "/((?!c/).*)",
         headers: [
           {
             key: "X-DNS-Prefetch-Control",
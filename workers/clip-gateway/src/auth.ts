@@ -20,6 +20,8 @@ export interface DeliveryToken {
   share?: boolean;
   /** Watermark flag */
   wm?: boolean;
+  /** Share slug the token was issued for (rejected once the share is revoked) */
+  ss?: string;
 }
 
 // NOTE: Synchronous token verification is not supported in Cloudflare Workers.
@@ -13,6 +13,9 @@
  *
  * Required bindings:
  * - CLIPS_BUCKET: R2 bucket binding
+ *
+ * Optional variables:
+ * - SHARE_STATUS_URL: API base URL used to reject tokens of revoked shares
  */
 
 import { DeliveryToken, verifyTokenAsync } from "./auth";
@@ -22,8 +25,12 @@ export interface Env {
   CLIPS_BUCKET: R2Bucket;
   SIGNING_SECRET: string;
   ALLOWED_ORIGINS: string;
+  SHARE_STATUS_URL?: string;
 }
 
+/** How long a share status lookup is cached at the edge (seconds). */
+const SHARE_STATUS_CACHE_TTL = 30;
+
 export default {
   async fetch(
     request: Request,
@@ -90,6 +97,12 @@ async function handleVideo(
     return new Response("Token clip ID mismatch", { status: 403 });
   }
 
+  // Reject tokens of revoked or expired shares
+  if (token.ss && !(await isShareActive(token.ss, env))) {
+    console.log("[handleVideo] Share no longer active:", token.ss);
+    return new Response("Share link has been revoked", { status: 410 });
+  }
+
   // Verify scope
   if (token.scope !== "play" && token.scope !== "dl") {
     console.log("[handleVideo] Invalid scope for video:", token.scope);
@@ -224,6 +237,36 @@ async function handleThumbnail(
   return new Response(object.body, { status: 200, headers });
 }
 
+/**
+ * Check whether a share link is still active.
+ *
+ * Asks the API (`GET /c/{slug}/status`) and caches the answer briefly at the
+ * edge, so revocation reaches outstanding tokens within the cache TTL. Fails
+ * open when the API is unreachable; the token's own expiry still applies.
+ */
+async function isShareActive(slug: string, env: Env): Promise<boolean> {
+  if (!env.SHARE_STATUS_URL) {
+    return true;
+  }
+
+  const statusUrl = `${env.SHARE_STATUS_URL.replace(/\/+$/, "")}/c/${encodeURIComponent(slug)}/status`;
+  try {
+    const response = await fetch(statusUrl, {
+      cf: {
+        cacheTtlByStatus: {
+          "200-299": SHARE_STATUS_CACHE_TTL,
+          "404": SHARE_STATUS_CACHE_TTL,
+          "410": SHARE_STATUS_CACHE_TTL,
+        },
+      },
+    });
+    return response.status !== 404 && response.status !== 410;
+  } catch (err) {
+    console.error("[isShareActive] Status check failed:", err);
+    return true;
+  }
+}
+
 /**
  * Resolve R2 key from token.
  *